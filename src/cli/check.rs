@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Args;
+
+use sql2doc::core::analyze::{MigrationIssue, OnlineMigrationLinter, QueryChecker, SemanticAnalyzer, Suppressions};
+use sql2doc::core::ast::{AstValidationError, ValidationSeverity};
+use sql2doc::core::domain::constraint::IssueSeverity;
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+use super::{collect_sql_files, parse_dialect, read_input, resolve_dialect};
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Dialect the scripts are written in; detected from them when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Fail on warnings as well as errors
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// Schema to check the files against as queries, rather than replaying them as migrations
+    #[arg(long)]
+    schema: Option<PathBuf>,
+
+    /// Also flag migrations that lock or rewrite tables already holding data, on PostgreSQL and MySQL
+    #[arg(long, conflicts_with = "schema")]
+    online: bool,
+
+    /// Major version of the server the migrations run on, such as 10 or 5 for MySQL 5.7; the
+    /// newest is assumed otherwise
+    #[arg(long, requires = "online")]
+    server_version: Option<u32>,
+
+    /// SQL files, or directories of them run in name order; reads stdin when omitted
+    paths: Vec<PathBuf>,
+}
+
+pub fn run(args: CheckArgs) -> Result<()> {
+    let (names, scripts) = read_scripts(&args.paths)?;
+    let (dialect, mut diagnostics) = match &args.schema {
+        Some(schema) => {
+            let schema = read_input(Some(schema))?;
+            let dialect = resolve_dialect(args.dialect, &schema, SqlDialect::PostgreSQL);
+            let database = Lowerer::new(dialect.clone()).lower(&schema)?.database;
+            let checker = QueryChecker::new(&database, dialect.clone());
+            let diagnostics = scripts
+                .iter()
+                .enumerate()
+                .flat_map(|(index, sql)| checker.check(sql).into_iter().map(move |diagnostic| (index, diagnostic)))
+                .collect();
+            (dialect, diagnostics)
+        }
+        None => {
+            let dialect = resolve_dialect(args.dialect, &scripts.concat(), SqlDialect::PostgreSQL);
+            let scripts: Vec<&str> = scripts.iter().map(String::as_str).collect();
+            let mut diagnostics = SemanticAnalyzer::new(dialect.clone()).check_all(&scripts);
+            if args.online {
+                let mut linter = OnlineMigrationLinter::new(dialect.clone());
+                if let Some(major) = args.server_version {
+                    linter = linter.with_server_version(major);
+                }
+                let issues = linter.lint_all(&scripts);
+                diagnostics.extend(issues.into_iter().map(|(script, issue)| (script, diagnostic(issue))));
+                let line = |diagnostic: &AstValidationError| diagnostic.location.as_ref().map(|location| location.start.line);
+                diagnostics.sort_by_key(|(script, diagnostic)| (*script, line(diagnostic)));
+            }
+            (dialect, diagnostics)
+        }
+    };
+
+    let suppressions: Vec<Suppressions> = scripts
+        .iter()
+        .map(|sql| {
+            let statements = Lexer::for_dialect(sql, &dialect)
+                .tokenize()
+                .map(|tokens| Parser::new(tokens).parse_recovering().0)
+                .unwrap_or_default();
+            Suppressions::parse(sql, &dialect, &statements)
+        })
+        .collect();
+    diagnostics.retain(|(script, diagnostic)| {
+        let rule = diagnostic.rule.as_deref().unwrap_or("semantic");
+        !suppressions[*script].suppresses(rule, diagnostic.location.as_ref())
+    });
+    warn_unknown_rules(&scripts, &dialect, &mut diagnostics);
+    report(&names, &diagnostics, args.deny_warnings)
+}
+
+/// Adds a warning for each rule id in a `sql2doc:disable` comment that names no rule.
+pub(super) fn warn_unknown_rules(scripts: &[String], dialect: &SqlDialect, diagnostics: &mut Vec<(usize, AstValidationError)>) {
+    for (index, sql) in scripts.iter().enumerate() {
+        diagnostics.extend(Suppressions::unknown_rules(sql, dialect).into_iter().map(|warning| (index, warning)));
+    }
+    let line = |diagnostic: &AstValidationError| diagnostic.location.as_ref().map(|location| location.start.line);
+    diagnostics.sort_by_key(|(script, diagnostic)| (*script, line(diagnostic)));
+}
+
+/// The files to check with their names, or stdin when there are none.
+pub(super) fn read_scripts(paths: &[PathBuf]) -> Result<(Vec<String>, Vec<String>)> {
+    let files = collect_sql_files(paths)?;
+    let mut names = Vec::new();
+    let mut scripts = Vec::new();
+    if files.is_empty() {
+        names.push("<stdin>".to_string());
+        scripts.push(read_input(None)?);
+    }
+    for path in &files {
+        names.push(path.display().to_string());
+        scripts.push(read_input(Some(path))?);
+    }
+    Ok((names, scripts))
+}
+
+/// A lock or rewrite as a diagnostic, with the safe alternative on a line of its own.
+fn diagnostic(issue: MigrationIssue) -> AstValidationError {
+    let severity = match issue.severity {
+        IssueSeverity::Critical | IssueSeverity::High | IssueSeverity::Medium => ValidationSeverity::Warning,
+        IssueSeverity::Low => ValidationSeverity::Info,
+    };
+    AstValidationError {
+        message: format!("{}\n  help: {}", issue.description, issue.suggested_fix),
+        location: issue.location,
+        severity,
+        rule: Some(issue.issue_type.id().to_string()),
+    }
+}
+
+/// Prints diagnostics as `file:line:column: level[rule]: message`, failing if any should.
+pub(super) fn report(names: &[String], diagnostics: &[(usize, AstValidationError)], deny_warnings: bool) -> Result<()> {
+    let mut errors = 0;
+    let mut warnings = 0;
+    for (script, diagnostic) in diagnostics {
+        let level = match diagnostic.severity {
+            ValidationSeverity::Error => {
+                errors += 1;
+                "error"
+            }
+            ValidationSeverity::Warning => {
+                warnings += 1;
+                "warning"
+            }
+            ValidationSeverity::Info => "note",
+        };
+        let position = diagnostic
+            .location
+            .as_ref()
+            .map(|location| format!("{}:{}:", location.start.line, location.start.column))
+            .unwrap_or_default();
+        let rule = diagnostic.rule.as_deref().unwrap_or("semantic");
+        eprintln!("{}:{} {}[{}]: {}", names[*script], position, level, rule, diagnostic.message);
+    }
+
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        bail!("{} error(s), {} warning(s)", errors, warnings);
+    }
+    Ok(())
+}
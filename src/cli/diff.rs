@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+
+use sql2doc::core::analyze::SchemaDiffer;
+use sql2doc::core::ast::Statement;
+use sql2doc::core::docs::{ApiCompatibility, SchemaDiffRenderer};
+use sql2doc::core::domain::entity::Database;
+use sql2doc::core::lower::{LowerError, Lowerer};
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+use super::{collect_sql_files, natural_cmp, parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Dialect both schemas are written in; detected from them when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: DiffFormat,
+
+    /// Classify each change as breaking, additive or non-breaking for the generated JSON Schema
+    /// and OpenAPI contract, and recommend a version bump
+    #[arg(long)]
+    api: bool,
+
+    /// Fail if any breaking change is not approved; implies --api
+    #[arg(long)]
+    fail_on_breaking: bool,
+
+    /// A breaking change to accept, named as in the diff, such as `orders.note`; a table covers its columns
+    #[arg(long, value_name = "NAME")]
+    approve: Vec<String>,
+
+    /// File of approved breaking changes, one name per line; `#` starts a comment
+    #[arg(long)]
+    approvals: Option<PathBuf>,
+
+    /// The schema before: a SQL file, a directory of migrations run in name order, or
+    /// `git:<rev>:<path>` for either as of a commit
+    old: String,
+
+    /// The schema after, given the same way
+    new: String,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+pub fn run(args: DiffArgs) -> Result<()> {
+    let old = read_source(&args.old)?;
+    let new = read_source(&args.new)?;
+    let sql: String = old.iter().chain(&new).map(|(_, sql)| sql.as_str()).collect();
+    let dialect = resolve_dialect(args.dialect, &sql, SqlDialect::PostgreSQL);
+    let old = lower(&old, &dialect)?;
+    let new = lower(&new, &dialect)?;
+
+    let diff = SchemaDiffer::new(&old, &new).diff();
+    if !args.api && !args.fail_on_breaking {
+        let rendered = match args.format {
+            DiffFormat::Text => SchemaDiffRenderer::new().text(&diff),
+            DiffFormat::Markdown => SchemaDiffRenderer::new().markdown(&diff),
+            DiffFormat::Json => format!("{}\n", serde_json::to_string_pretty(&diff)?),
+        };
+        return write_output(args.output.as_ref(), &rendered);
+    }
+
+    let report = ApiCompatibility::new(&old, &new).classify(&diff);
+    let rendered = match args.format {
+        DiffFormat::Text => SchemaDiffRenderer::new().report_text(&report),
+        DiffFormat::Markdown => SchemaDiffRenderer::new().report_markdown(&report),
+        DiffFormat::Json => format!("{}\n", serde_json::to_string_pretty(&report)?),
+    };
+    write_output(args.output.as_ref(), &rendered)?;
+
+    let mut approvals = args.approve.clone();
+    if let Some(path) = &args.approvals {
+        let contents = read_input(Some(path))?;
+        let lines = contents.lines().map(|line| line.split('#').next().unwrap_or_default().trim());
+        approvals.extend(lines.filter(|line| !line.is_empty()).map(str::to_string));
+    }
+    let unapproved = report.unapproved(&approvals);
+    if args.fail_on_breaking && !unapproved.is_empty() {
+        let mut names: Vec<&str> = Vec::new();
+        for change in &unapproved {
+            if !names.contains(&change.change.name.as_str()) {
+                names.push(&change.change.name);
+            }
+        }
+        bail!("{} unapproved breaking change(s): {}", names.len(), names.join(", "));
+    }
+    Ok(())
+}
+
+/// The SQL files a source names, with their contents, in the order they run.
+pub(super) fn read_source(source: &str) -> Result<Vec<(String, String)>> {
+    if let Some(spec) = source.strip_prefix("git:") {
+        let Some((revision, path)) = spec.split_once(':') else {
+            bail!("expected git:<rev>:<path>, got {}", source);
+        };
+        return read_git(revision, path.trim_end_matches('/'));
+    }
+
+    let files = collect_sql_files(&[PathBuf::from(source)])?;
+    if files.is_empty() {
+        bail!("{} holds no .sql files", source);
+    }
+    files.iter().map(|path| Ok((path.display().to_string(), read_input(Some(path))?))).collect()
+}
+
+/// Reads a file, or the `.sql` files of a directory, as of a revision, through the local `git`.
+fn read_git(revision: &str, path: &str) -> Result<Vec<(String, String)>> {
+    let object = format!("{}:{}", revision, path);
+    if git(&["cat-file", "-t", &object])?.trim() != "tree" {
+        return Ok(vec![(object.clone(), git(&["show", &object])?)]);
+    }
+
+    let mut names: Vec<String> = git(&["ls-tree", "--name-only", &object])?
+        .lines()
+        .filter(|name| Path::new(name).extension().is_some_and(|extension| extension.eq_ignore_ascii_case("sql")))
+        .map(str::to_string)
+        .collect();
+    if names.is_empty() {
+        bail!("{} holds no .sql files", object);
+    }
+    names.sort_by(|a, b| natural_cmp(a, b));
+    names
+        .iter()
+        .map(|name| {
+            let file = format!("{}/{}", object, name);
+            Ok((file.clone(), git(&["show", &file])?))
+        })
+        .collect()
+}
+
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).output().context("failed to run git")?;
+    if !output.status.success() {
+        bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    String::from_utf8(output.stdout).with_context(|| format!("git {} printed invalid UTF-8", args.join(" ")))
+}
+
+/// Parses each file on its own, so errors name the file, and lowers them in order as one script.
+pub(super) fn lower(files: &[(String, String)], dialect: &SqlDialect) -> Result<Database> {
+    let statements: Vec<Statement> = parse(files, dialect)?.into_iter().flatten().collect();
+    let output = Lowerer::new(dialect.clone()).lower_statements(&statements);
+    for warning in &output.warnings {
+        eprintln!("warning: {}: {}", warning.object, warning.message);
+    }
+    Ok(output.database)
+}
+
+/// The statements of each file, which errors are reported against.
+pub(super) fn parse(files: &[(String, String)], dialect: &SqlDialect) -> Result<Vec<Vec<Statement>>> {
+    files
+        .iter()
+        .map(|(name, sql)| {
+            Lexer::for_dialect(sql, dialect)
+                .tokenize()
+                .map_err(LowerError::from)
+                .and_then(|tokens| Parser::new(tokens).parse().map_err(LowerError::Parse))
+                .with_context(|| format!("failed to read {}", name))
+        })
+        .collect()
+}
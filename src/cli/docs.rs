@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use sql2doc::core::analyze::{LineageAnalyzer, MigrationHistory};
+use sql2doc::core::docs::{HtmlRenderer, JsonSchemaRenderer, MarkdownRenderer};
+use sql2doc::core::lower::{LowerOutput, Lowerer};
+use sql2doc::core::parse::SqlDialect;
+
+use super::diff::{parse, read_source};
+use super::{parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct DocsArgs {
+    /// Dialect the schema is written in; detected from the input when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "markdown")]
+    format: DocsFormat,
+
+    /// Title of the generated document
+    #[arg(long, default_value = "Database schema")]
+    title: String,
+
+    /// SQL file to read, or a directory of migrations to replay in name order, noting which
+    /// of them added and changed each table and column; `-` or omitted reads stdin
+    input: Option<PathBuf>,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DocsFormat {
+    Markdown,
+    /// One JSON Schema definition per table, describing its rows
+    JsonSchema,
+    /// A self-contained HTML page, with the lineage of each view's columns
+    Html,
+    /// For each view, the table columns each of its columns is derived from, as JSON
+    Lineage,
+}
+
+pub fn run(args: DocsArgs) -> Result<()> {
+    let (output, dialect) = match args.input.as_deref() {
+        Some(directory) if directory.is_dir() => replay(directory, args.dialect, &args.title)?,
+        input => {
+            let sql = read_input(input)?;
+            let dialect = resolve_dialect(args.dialect, &sql, SqlDialect::PostgreSQL);
+            (Lowerer::new(dialect.clone()).with_database_name(&args.title).lower(&sql)?, dialect)
+        }
+    };
+
+    for warning in &output.warnings {
+        eprintln!("warning: {}: {}", warning.object, warning.message);
+    }
+
+    let rendered = match args.format {
+        DocsFormat::Markdown => MarkdownRenderer::new().render(&output.database),
+        DocsFormat::JsonSchema => {
+            let schema = JsonSchemaRenderer::new().render(&output.database);
+            format!("{}\n", serde_json::to_string_pretty(&schema)?)
+        }
+        DocsFormat::Html => {
+            let lineage = LineageAnalyzer::new(&output.database, dialect).views();
+            HtmlRenderer::new().render(&output.database, &lineage)
+        }
+        DocsFormat::Lineage => {
+            let lineage = LineageAnalyzer::new(&output.database, dialect).views();
+            format!("{}\n", serde_json::to_string_pretty(&lineage)?)
+        }
+    };
+    write_output(args.output.as_ref(), &rendered)
+}
+
+/// Lowers a migrations directory, recording the history of each object against the file names.
+fn replay(directory: &Path, dialect: Option<SqlDialect>, title: &str) -> Result<(LowerOutput, SqlDialect)> {
+    let files = read_source(&directory.display().to_string())?;
+    let sql: String = files.iter().map(|(_, sql)| sql.as_str()).collect();
+    let dialect = resolve_dialect(dialect, &sql, SqlDialect::PostgreSQL);
+    let migrations: Vec<_> = files
+        .iter()
+        .map(|(path, _)| Path::new(path).file_name().map_or(path.clone(), |name| name.to_string_lossy().into_owned()))
+        .zip(parse(&files, &dialect)?)
+        .collect();
+    let output = MigrationHistory::new(dialect.clone()).with_database_name(title).lower(&migrations);
+    Ok((output, dialect))
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use sql2doc::core::analyze::{SchemaDiff, SchemaDiffer, SchemaSnapshot};
+use sql2doc::core::docs::SchemaDiffRenderer;
+use sql2doc::core::parse::SqlDialect;
+
+use super::diff::{lower, read_source};
+use super::{parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct DriftArgs {
+    /// Dialect the migrations and schema are written in; detected from them when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: DriftFormat,
+
+    /// Declarative schema the migrations should arrive at
+    #[arg(long)]
+    schema: Option<PathBuf>,
+
+    /// Snapshot lock file the migrations should arrive at
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+
+    /// Write the migrated schema to the --snapshot file instead of comparing against it
+    #[arg(long, requires = "snapshot")]
+    update_snapshot: bool,
+
+    /// Migrations, run in name order: a directory, a SQL file or `git:<rev>:<path>`
+    migrations: String,
+
+    /// File to write the report to; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DriftFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+/// How the migrated schema differs from each file it was compared with.
+#[derive(Debug, Serialize)]
+struct DriftReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<SchemaDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot: Option<SchemaDiff>,
+}
+
+pub fn run(args: DriftArgs) -> Result<()> {
+    if args.schema.is_none() && args.snapshot.is_none() {
+        bail!("nothing to compare the migrations with; pass --schema, --snapshot or both");
+    }
+    let migrations = read_source(&args.migrations)?;
+    let declared = args.schema.as_ref().map(|path| read_input(Some(path))).transpose()?;
+    let mut sql: String = migrations.iter().map(|(_, sql)| sql.as_str()).collect();
+    sql.push_str(declared.as_deref().unwrap_or_default());
+    let dialect = resolve_dialect(args.dialect, &sql, SqlDialect::PostgreSQL);
+    let migrated = lower(&migrations, &dialect)?;
+
+    let mut report = DriftReport { schema: None, snapshot: None };
+    if let (Some(path), Some(declared)) = (&args.schema, declared) {
+        let declared = lower(&[(path.display().to_string(), declared)], &dialect)?;
+        report.schema = Some(SchemaDiffer::new(&declared, &migrated).diff());
+    }
+    if let Some(path) = &args.snapshot {
+        let snapshot = SchemaSnapshot::new(&migrated);
+        if args.update_snapshot {
+            write_output(Some(path), &snapshot.to_json()?)?;
+            eprintln!("wrote {}", path.display());
+        } else {
+            let recorded = SchemaSnapshot::from_json(&read_input(Some(path))?)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            report.snapshot = Some(SchemaDiffer::new(&recorded.database, &snapshot.database).diff());
+        }
+    }
+
+    let sections = [("schema", &args.schema, &report.schema), ("snapshot", &args.snapshot, &report.snapshot)];
+    let rendered = match args.format {
+        DriftFormat::Json => format!("{}\n", serde_json::to_string_pretty(&report)?),
+        DriftFormat::Text | DriftFormat::Markdown => sections
+            .iter()
+            .filter_map(|(_, path, diff)| Some((path.as_ref()?, diff.as_ref()?)))
+            .map(|(path, diff)| match args.format {
+                DriftFormat::Markdown => {
+                    let table = SchemaDiffRenderer::new().markdown(diff);
+                    let table = table.strip_prefix("# Schema Changes\n\n").unwrap_or(&table).to_string();
+                    format!("## Migrations against `{}`\n\n{}", path.display(), table)
+                }
+                _ => format!("Migrations against {}:\n{}", path.display(), SchemaDiffRenderer::new().text(diff)),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    if !rendered.is_empty() {
+        write_output(args.output.as_ref(), &rendered)?;
+    }
+
+    let drifted: Vec<String> = sections
+        .iter()
+        .filter_map(|(name, _, diff)| diff.as_ref().filter(|diff| !diff.is_empty()).map(|diff| (name, diff)))
+        .map(|(name, diff)| format!("{} change(s) against the {}", diff.changes.len(), name))
+        .collect();
+    if !drifted.is_empty() {
+        bail!("the migrations have drifted: {}", drifted.join(", "));
+    }
+    Ok(())
+}
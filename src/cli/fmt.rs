@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use sql2doc::core::format::{FormatStyle, Formatter, KeywordCase};
+
+use sql2doc::core::parse::SqlDialect;
+
+use super::{parse_dialect, read_input, resolve_dialect};
+
+#[derive(Debug, Args)]
+pub struct FmtArgs {
+    /// SQL files to format in place; reads stdin and writes stdout when omitted
+    files: Vec<PathBuf>,
+
+    /// Dialect the scripts are written in; detected from each one when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Report files that are not formatted instead of rewriting them
+    #[arg(long)]
+    check: bool,
+
+    /// Case for keywords and type names: upper, lower or preserve
+    #[arg(long, default_value = "upper")]
+    keyword_case: KeywordCase,
+
+    /// Spaces per indentation level
+    #[arg(long, default_value_t = 4)]
+    indent: usize,
+
+    /// Don't line up column types in CREATE TABLE
+    #[arg(long)]
+    no_align: bool,
+}
+
+pub fn run(args: FmtArgs) -> Result<()> {
+    let style = FormatStyle {
+        keyword_case: args.keyword_case,
+        indent_width: args.indent,
+        align_column_types: !args.no_align,
+    };
+    let formatter = |sql: &str| {
+        Formatter::new(style.clone()).with_dialect(resolve_dialect(args.dialect.clone(), sql, SqlDialect::PostgreSQL))
+    };
+
+    if args.files.is_empty() {
+        let sql = read_input(None)?;
+        let formatted = formatter(&sql).format(&sql)?;
+        if args.check {
+            if formatted != sql {
+                bail!("<stdin> is not formatted");
+            }
+        } else {
+            print!("{}", formatted);
+        }
+        return Ok(());
+    }
+
+    let mut unformatted = 0;
+    for path in &args.files {
+        let sql = read_input(Some(path))?;
+        let formatted = formatter(&sql)
+            .format(&sql)
+            .with_context(|| format!("failed to format {}", path.display()))?;
+
+        if formatted == sql {
+            continue;
+        }
+        if args.check {
+            eprintln!("not formatted: {}", path.display());
+            unformatted += 1;
+        } else {
+            fs::write(path, formatted).with_context(|| format!("failed to write {}", path.display()))?;
+        }
+    }
+
+    if unformatted > 0 {
+        bail!("{} file(s) need formatting", unformatted);
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+
+use sql2doc::core::analyze::{CatalogBuilder, Change, DependencyGraphBuilder, Impact, ObjectKind};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+use super::{collect_sql_files, parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct ImpactArgs {
+    /// Dialect the schema is written in; detected from it when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// The change to trace
+    #[arg(long, value_enum, default_value = "drop")]
+    change: ImpactChange,
+
+    /// Query files, or directories of them, whose queries count as dependents too
+    #[arg(long)]
+    queries: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: ImpactFormat,
+
+    /// SQL file holding the schema
+    schema: PathBuf,
+
+    /// The object to change: `orders`, `orders.amount`, or `kind:name` such as `index:orders_pkey`
+    object: String,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ImpactChange {
+    Drop,
+    Rename,
+    /// Change a column's type
+    Type,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ImpactFormat {
+    Text,
+    Json,
+}
+
+pub fn run(args: ImpactArgs) -> Result<()> {
+    let schema = read_input(Some(&args.schema))?;
+    let dialect = resolve_dialect(args.dialect, &schema, SqlDialect::PostgreSQL);
+    let database = Lowerer::new(dialect.clone()).lower(&schema)?.database;
+
+    let mut catalog = CatalogBuilder::new(dialect.clone()).with_schema(&database);
+    for path in collect_sql_files(&args.queries)? {
+        let name = path.display().to_string();
+        catalog.add_file(&name, &read_input(Some(&path))?).with_context(|| format!("failed to read queries from {}", name))?;
+    }
+    let catalog = catalog.build("queries");
+    let graph = DependencyGraphBuilder::new(&database, dialect).with_queries(&catalog).build();
+
+    let Some(object) = graph.find(&args.object) else {
+        bail!("{} names no table, view, column, index, trigger, procedure, function or query", args.object);
+    };
+    let change = match args.change {
+        ImpactChange::Drop => Change::Drop,
+        ImpactChange::Rename => Change::Rename,
+        ImpactChange::Type if object.kind == ObjectKind::Column => Change::ChangeType,
+        ImpactChange::Type => bail!("only a column's type can be changed, and {} is a {}", object.name, object.kind),
+    };
+    let impact = graph.impact(object, change);
+
+    let rendered = match args.format {
+        ImpactFormat::Text => render_text(&impact),
+        ImpactFormat::Json => format!("{}\n", serde_json::to_string_pretty(&impact)?),
+    };
+    write_output(args.output.as_ref(), &rendered)
+}
+
+/// One line per affected object, with the dependency it is reached through.
+fn render_text(impact: &Impact) -> String {
+    let subject = format!("{} {} {}", impact.change, impact.object.kind, impact.object.name);
+    let mut subject = subject.chars();
+    let subject: String = subject.next().map(|first| first.to_uppercase().chain(subject).collect()).unwrap_or_default();
+    if impact.affected.is_empty() {
+        return format!("{} affects nothing else.\n", subject);
+    }
+
+    let count = impact.affected.len();
+    let mut out = format!("{} affects {} object{}:\n", subject, count, if count == 1 { "" } else { "s" });
+    for affected in &impact.affected {
+        out.push_str(&format!(
+            "  {} {} ({} {} {})\n",
+            affected.object.kind, affected.object.name, affected.dependency, affected.through.kind, affected.through.name
+        ));
+    }
+    out
+}
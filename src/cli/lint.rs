@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use sql2doc::core::analyze::{LintConfig, LintFinding, LintLevel, LintRule, SchemaLinter};
+use sql2doc::core::ast::{AstValidationError, ValidationSeverity};
+use sql2doc::core::parse::SqlDialect;
+
+use super::check::{read_scripts, report, warn_unknown_rules};
+use super::{parse_dialect, resolve_dialect};
+
+#[derive(Debug, Args)]
+pub struct LintArgs {
+    /// Dialect the scripts are written in; detected from them when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Set a rule's level, such as `wide-index=off` or `missing-primary-key=error`; may be repeated
+    #[arg(long = "rule", value_name = "RULE=LEVEL")]
+    rules: Vec<String>,
+
+    /// Fail on warnings as well as errors
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// List the rules with their levels and exit
+    #[arg(long)]
+    list_rules: bool,
+
+    /// SQL files, or directories of them run in name order; reads stdin when omitted
+    paths: Vec<PathBuf>,
+}
+
+pub fn run(args: LintArgs) -> Result<()> {
+    let mut config = LintConfig::new();
+    for setting in &args.rules {
+        config.apply(setting)?;
+    }
+    if args.list_rules {
+        for rule in LintRule::ALL {
+            println!("{:<30} {:<8} {}", rule.id(), config.level(rule).to_string(), rule.summary());
+        }
+        return Ok(());
+    }
+
+    let (names, scripts) = read_scripts(&args.paths)?;
+    let dialect = resolve_dialect(args.dialect, &scripts.concat(), SqlDialect::PostgreSQL);
+    let sources: Vec<&str> = scripts.iter().map(String::as_str).collect();
+    let findings = SchemaLinter::new(dialect.clone()).with_config(config).lint_all(&sources);
+    let mut diagnostics: Vec<(usize, AstValidationError)> =
+        findings.into_iter().map(|(script, finding)| (script, diagnostic(finding))).collect();
+    warn_unknown_rules(&scripts, &dialect, &mut diagnostics);
+    report(&names, &diagnostics, args.deny_warnings)
+}
+
+/// A finding as a diagnostic, with the fix on a line of its own.
+fn diagnostic(finding: LintFinding) -> AstValidationError {
+    let severity = match finding.level {
+        LintLevel::Error => ValidationSeverity::Error,
+        LintLevel::Warning => ValidationSeverity::Warning,
+        LintLevel::Info | LintLevel::Off => ValidationSeverity::Info,
+    };
+    let message = match &finding.help {
+        Some(help) => format!("{}\n  help: {}", finding.message, help),
+        None => finding.message,
+    };
+    AstValidationError { message, location: finding.location, severity, rule: Some(finding.rule.id().to_string()) }
+}
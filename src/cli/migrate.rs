@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use sql2doc::core::parse::SqlDialect;
+use sql2doc::core::transpile::MigrationGenerator;
+
+use super::diff::{lower, read_source};
+use super::{parse_dialect, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct MigrateArgs {
+    /// Dialect both schemas are written in and the migration is emitted for; detected when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "sql")]
+    format: MigrateFormat,
+
+    /// File to write the down-migration to; with `--format sql` it is otherwise left out
+    #[arg(long, value_name = "FILE")]
+    down: Option<PathBuf>,
+
+    /// The schema to migrate from: a SQL file, a directory of migrations run in name order, or
+    /// `git:<rev>:<path>` for either as of a commit
+    old: String,
+
+    /// The schema to migrate to, given the same way
+    new: String,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MigrateFormat {
+    Sql,
+    Json,
+}
+
+pub fn run(args: MigrateArgs) -> Result<()> {
+    let old = read_source(&args.old)?;
+    let new = read_source(&args.new)?;
+    let sql: String = old.iter().chain(&new).map(|(_, sql)| sql.as_str()).collect();
+    let dialect = resolve_dialect(args.dialect, &sql, SqlDialect::PostgreSQL);
+    let old = lower(&old, &dialect)?;
+    let new = lower(&new, &dialect)?;
+
+    let migration = MigrationGenerator::new(&old, &new, dialect).generate();
+    for warning in &migration.warnings {
+        eprintln!("warning: {}: {}", warning.object, warning.message);
+    }
+
+    if let Some(path) = &args.down {
+        write_output(Some(path), &migration.down_sql())?;
+    }
+    let rendered = match args.format {
+        MigrateFormat::Sql => migration.up_sql(),
+        MigrateFormat::Json => format!("{}\n", serde_json::to_string_pretty(&migration)?),
+    };
+    write_output(args.output.as_ref(), &rendered)
+}
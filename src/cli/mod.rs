@@ -0,0 +1,178 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+use sql2doc::core::parse::{DialectDetector, DialectRegistry, SqlDialect};
+
+mod check;
+mod diff;
+mod docs;
+mod drift;
+mod fmt;
+mod impact;
+mod lint;
+mod migrate;
+mod queries;
+mod transpile;
+
+#[derive(Debug, Parser)]
+#[command(name = "sql2doc", version, about = "Generate documentation and tooling from SQL schemas")]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Check SQL scripts and migrations for unknown tables and columns and mismatched keys
+    Check(check::CheckArgs),
+    /// Compare two schemas, given as files, migration directories or git revisions
+    Diff(diff::DiffArgs),
+    /// Generate Markdown documentation or JSON Schemas from a SQL schema
+    Docs(docs::DocsArgs),
+    /// Check that replaying the migrations arrives at the declarative schema or snapshot
+    Drift(drift::DriftArgs),
+    /// Format SQL files in a consistent style
+    Fmt(fmt::FmtArgs),
+    /// List everything that dropping, renaming or retyping a table, column or other object affects
+    Impact(impact::ImpactArgs),
+    /// Check a schema against configurable standards, such as every table having a primary key
+    Lint(lint::LintArgs),
+    /// Generate the migration, and its rollback, that turns one schema into another
+    Migrate(migrate::MigrateArgs),
+    /// Generate a Markdown catalog of the queries in SQL files
+    Queries(queries::QueriesArgs),
+    /// Convert DDL written for one SQL dialect into another
+    Transpile(transpile::TranspileArgs),
+}
+
+impl Cli {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            Command::Check(args) => check::run(args),
+            Command::Diff(args) => diff::run(args),
+            Command::Docs(args) => docs::run(args),
+            Command::Drift(args) => drift::run(args),
+            Command::Fmt(args) => fmt::run(args),
+            Command::Impact(args) => impact::run(args),
+            Command::Lint(args) => lint::run(args),
+            Command::Migrate(args) => migrate::run(args),
+            Command::Queries(args) => queries::run(args),
+            Command::Transpile(args) => transpile::run(args),
+        }
+    }
+}
+
+/// Parses a dialect name for clap, accepting every alias the registry knows.
+fn parse_dialect(name: &str) -> Result<SqlDialect, String> {
+    DialectRegistry::new().get_dialect(name).ok_or_else(|| {
+        "expected one of: postgres, mysql, sqlite, mssql, oracle, standard".to_string()
+    })
+}
+
+/// The dialect given on the command line, or else the one the input looks most like.
+fn resolve_dialect(explicit: Option<SqlDialect>, sql: &str, fallback: SqlDialect) -> SqlDialect {
+    if let Some(dialect) = explicit {
+        return dialect;
+    }
+    match DialectDetector::new().detect(sql) {
+        Some(guess) => {
+            eprintln!(
+                "note: detected {} ({:.0}% confidence: {}); pass --dialect to override",
+                guess.dialect.config().name,
+                guess.confidence * 100.0,
+                guess.signals.join(", ")
+            );
+            guess.dialect
+        }
+        None => {
+            eprintln!("note: could not detect the dialect; assuming {}", fallback.config().name);
+            fallback
+        }
+    }
+}
+
+/// Reads SQL from `path`, or from stdin when the path is absent or `-`.
+fn read_input(path: Option<&Path>) -> Result<String> {
+    match path {
+        Some(path) if path != Path::new("-") => {
+            fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+        }
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).context("failed to read stdin")?;
+            Ok(input)
+        }
+    }
+}
+
+fn write_output(path: Option<&PathBuf>, contents: &str) -> Result<()> {
+    match path {
+        Some(path) => {
+            fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+/// Expands directories into the `.sql` files directly inside them, ordered so that
+/// `2_users.sql` comes before `10_orders.sql`. Files are kept as given.
+fn collect_sql_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(path).with_context(|| format!("failed to read {}", path.display()))? {
+            let entry = entry.with_context(|| format!("failed to read {}", path.display()))?.path();
+            if entry.is_file() && entry.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("sql")) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+        files.extend(entries);
+    }
+    Ok(files)
+}
+
+/// Compares strings with runs of digits ordered by their numeric value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let digits = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut run = String::new();
+                    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                        run.push(c);
+                    }
+                    run.trim_start_matches('0').to_string()
+                };
+                let (x, y) = (digits(&mut a), digits(&mut b));
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
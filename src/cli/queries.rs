@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+
+use sql2doc::core::analyze::CatalogBuilder;
+use sql2doc::core::docs::{OpenApiRenderer, QueryCatalogRenderer};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+use super::{collect_sql_files, parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct QueriesArgs {
+    /// Dialect the queries are written in; detected from them when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    dialect: Option<SqlDialect>,
+
+    /// Schema to resolve the queries against, so their parameters and result columns are typed
+    #[arg(long)]
+    schema: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "markdown")]
+    format: QueriesFormat,
+
+    /// Title of the generated document
+    #[arg(long, default_value = "Query catalog")]
+    title: String,
+
+    /// SQL files, or directories of them; reads stdin when omitted
+    paths: Vec<PathBuf>,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum QueriesFormat {
+    Markdown,
+    /// An OpenAPI 3.1 operation per query annotated with `-- name: GetUser :one`
+    Openapi,
+}
+
+pub fn run(args: QueriesArgs) -> Result<()> {
+    let files = collect_sql_files(&args.paths)?;
+    let mut sources = Vec::new();
+    if files.is_empty() {
+        sources.push(("<stdin>".to_string(), read_input(None)?));
+    }
+    for path in &files {
+        sources.push((path.display().to_string(), read_input(Some(path))?));
+    }
+
+    let all: String = sources.iter().map(|(_, sql)| sql.as_str()).collect();
+    let dialect = resolve_dialect(args.dialect, &all, SqlDialect::PostgreSQL);
+    let database = match &args.schema {
+        Some(schema) => Some(Lowerer::new(dialect.clone()).lower(&read_input(Some(schema))?)?.database),
+        None => None,
+    };
+
+    let mut builder = CatalogBuilder::new(dialect);
+    if let Some(database) = &database {
+        builder = builder.with_schema(database);
+    }
+    for (name, sql) in &sources {
+        builder.add_file(name, sql).with_context(|| format!("failed to read queries from {}", name))?;
+    }
+    let catalog = builder.build(&args.title);
+    for warning in &catalog.warnings {
+        eprintln!("{}:{}: warning: {}", warning.source, warning.line, warning.message);
+    }
+
+    let rendered = match args.format {
+        QueriesFormat::Markdown => QueryCatalogRenderer::new().render(&catalog),
+        QueriesFormat::Openapi => {
+            let document = OpenApiRenderer::new().render(&catalog);
+            format!("{}\n", serde_json::to_string_pretty(&document)?)
+        }
+    };
+    write_output(args.output.as_ref(), &rendered)
+}
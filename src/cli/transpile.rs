@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use sql2doc::core::parse::SqlDialect;
+use sql2doc::core::transpile::Transpiler;
+
+use super::{parse_dialect, read_input, resolve_dialect, write_output};
+
+#[derive(Debug, Args)]
+pub struct TranspileArgs {
+    /// Dialect the input is written in; detected from the input when omitted
+    #[arg(long, value_parser = parse_dialect)]
+    from: Option<SqlDialect>,
+
+    /// Dialect to emit
+    #[arg(long, value_parser = parse_dialect)]
+    to: SqlDialect,
+
+    /// SQL file to read; `-` or omitted reads stdin
+    input: Option<PathBuf>,
+
+    /// File to write; defaults to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: TranspileArgs) -> Result<()> {
+    let sql = read_input(args.input.as_deref())?;
+    let from = resolve_dialect(args.from, &sql, SqlDialect::Standard);
+    let output = Transpiler::new(from, args.to).transpile(&sql)?;
+
+    for warning in &output.warnings {
+        eprintln!("warning: {}: {}", warning.object, warning.message);
+    }
+
+    write_output(args.output.as_ref(), &output.sql)
+}
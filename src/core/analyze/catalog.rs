@@ -0,0 +1,310 @@
+use std::fmt;
+
+use crate::core::analyze::metadata::QueryAnalyzer;
+use crate::core::analyze::query::{Projection, QueryChecker, QueryParameter};
+use crate::core::ast::{
+    ApiMetadata, ApiParameter, DmlStatement, DocumentationMetadata, QueryMetadata, QueryType, Statement,
+};
+use crate::core::domain::entity::Database;
+use crate::core::lower::LowerError;
+use crate::core::parse::{Lexer, Parser, SqlDialect};
+
+/// The queries of one or more SQL files, each with what it touches and, when checked against
+/// a schema, the columns it produces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryCatalog {
+    pub title: String,
+    pub queries: Vec<CatalogQuery>,
+    pub warnings: Vec<CatalogWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogQuery {
+    /// The file the query was read from
+    pub source: String,
+    /// Line the query starts on
+    pub line: usize,
+    /// From a `-- name: GetUser :one` annotation
+    pub name: Option<String>,
+    pub command: Option<QueryCommand>,
+    /// The query as written, without its terminating semicolon
+    pub sql: String,
+    pub parameters: Vec<QueryParameter>,
+    /// Includes the comments above the query and, for a named query, its API operation
+    pub metadata: QueryMetadata,
+    /// The result columns, known only when a schema is given
+    pub result: Option<Projection>,
+}
+
+/// What a named query gives back, as sqlc spells it after the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryCommand {
+    /// At most one row
+    One,
+    /// Any number of rows
+    Many,
+    /// Nothing
+    Exec,
+    /// The number of rows affected
+    ExecRows,
+}
+
+/// An annotation that couldn't be used as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogWarning {
+    pub source: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Collects queries into a [`QueryCatalog`]; statements other than SELECT, INSERT, UPDATE and
+/// DELETE are left out.
+pub struct CatalogBuilder<'a> {
+    dialect: SqlDialect,
+    database: Option<&'a Database>,
+    queries: Vec<CatalogQuery>,
+    warnings: Vec<CatalogWarning>,
+}
+
+impl<'a> CatalogBuilder<'a> {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self { dialect, database: None, queries: Vec::new(), warnings: Vec::new() }
+    }
+
+    /// Resolves each query against `database`, so the catalog can list its result columns and
+    /// the types of its parameters.
+    pub fn with_schema(mut self, database: &'a Database) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Adds the queries of a file. A query that fails to parse is left out with a warning, so
+    /// one bad query doesn't keep the rest of the file from the catalog.
+    pub fn add_file(&mut self, source: &str, sql: &str) -> Result<(), LowerError> {
+        let tokens = Lexer::for_dialect(sql, &self.dialect).tokenize()?;
+        let (statements, errors) = Parser::new(tokens).parse_recovering();
+        let first_warning = self.warnings.len();
+        for error in &errors {
+            self.warn(source, error.position.line, format!("query left out: {}", error.message));
+        }
+        let analyzer = QueryAnalyzer::new();
+        // Without a schema, parameters are still found and named, just not typed
+        let empty = Database::new("queries");
+        let checker = QueryChecker::new(self.database.unwrap_or(&empty), self.dialect.clone());
+
+        let starts: Vec<usize> = statements
+            .iter()
+            .map(|statement| statement.location.as_ref().map_or(0, |location| location.start.index))
+            .collect();
+        for (position, statement) in statements.iter().enumerate() {
+            let Statement::Dml(dml) = &statement.node else {
+                continue;
+            };
+            let start = starts[position];
+            let mut end = starts.get(position + 1).copied().unwrap_or(usize::MAX);
+            // A query that failed to parse in between isn't part of this one
+            if let Some(location) = &statement.location
+                && errors.iter().any(|error| (location.end.index..end).contains(&error.position.index))
+            {
+                end = location.end.index;
+            }
+            let line = statement.location.as_ref().map_or(1, |location| location.start.line);
+            let comments = leading_comments(sql, start);
+
+            let mut name = None;
+            let mut command = None;
+            let mut description = Vec::new();
+            for comment in comments {
+                match comment.strip_prefix("name:") {
+                    Some(annotation) => {
+                        let mut words = annotation.split_whitespace();
+                        name = words.next().map(str::to_string);
+                        command = match words.next() {
+                            Some(word) => {
+                                let parsed = QueryCommand::parse(word);
+                                if parsed.is_none() {
+                                    let message =
+                                        format!("unknown query command {}; expected :one, :many, :exec or :execrows", word);
+                                    self.warn(source, line, message);
+                                }
+                                parsed
+                            }
+                            None => None,
+                        };
+                    }
+                    None => description.push(comment),
+                }
+            }
+
+            let parameters = checker.parameters(dml);
+            let result = self.database.map(|_| checker.result_columns(dml));
+            let mut metadata = analyzer.analyze(dml);
+            let command = name.as_ref().map(|_| command.unwrap_or_else(|| default_command(dml)));
+            if let Some(name) = &name {
+                if self.queries.iter().any(|query| query.name.as_ref() == Some(name)) {
+                    self.warn(source, line, format!("query name {} is used more than once", name));
+                }
+                if matches!(command, Some(QueryCommand::One | QueryCommand::Many)) && !returns_rows(dml) {
+                    self.warn(source, line, format!("{} is annotated to return rows but has no RETURNING clause", name));
+                }
+            }
+
+            let mut documentation = DocumentationMetadata::new();
+            if let Some(name) = &name {
+                documentation = documentation.with_title(name);
+            }
+            if !description.is_empty() {
+                documentation = documentation.with_description(&description.join("\n"));
+            }
+            if documentation != DocumentationMetadata::new() {
+                metadata = metadata.with_documentation(documentation);
+            }
+            if let (Some(name), Some(command)) = (&name, command) {
+                let api = api_metadata(name, command, &metadata.query_type, &parameters);
+                metadata = metadata.with_api_metadata(api);
+            }
+
+            self.queries.push(CatalogQuery {
+                source: source.to_string(),
+                line,
+                name,
+                command,
+                sql: statement_text(sql, start, end),
+                parameters,
+                metadata,
+                result,
+            });
+        }
+        self.warnings[first_warning..].sort_by_key(|warning| warning.line);
+        Ok(())
+    }
+
+    pub fn build(self, title: &str) -> QueryCatalog {
+        QueryCatalog { title: title.to_string(), queries: self.queries, warnings: self.warnings }
+    }
+
+    fn warn(&mut self, source: &str, line: usize, message: String) {
+        self.warnings.push(CatalogWarning { source: source.to_string(), line, message });
+    }
+}
+
+impl QueryCommand {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.to_ascii_lowercase().as_str() {
+            ":one" => Some(QueryCommand::One),
+            ":many" => Some(QueryCommand::Many),
+            ":exec" => Some(QueryCommand::Exec),
+            ":execrows" => Some(QueryCommand::ExecRows),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for QueryCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            QueryCommand::One => ":one",
+            QueryCommand::Many => ":many",
+            QueryCommand::Exec => ":exec",
+            QueryCommand::ExecRows => ":execrows",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A name without a command: a SELECT returns its rows, anything else returns nothing.
+fn default_command(statement: &DmlStatement) -> QueryCommand {
+    match statement {
+        DmlStatement::Select(_) => QueryCommand::Many,
+        _ if returns_rows(statement) => QueryCommand::Many,
+        _ => QueryCommand::Exec,
+    }
+}
+
+fn returns_rows(statement: &DmlStatement) -> bool {
+    match statement {
+        DmlStatement::Select(_) => true,
+        DmlStatement::Insert(insert) => !insert.returning.is_empty(),
+        DmlStatement::Update(update) => !update.returning.is_empty(),
+        DmlStatement::Delete(delete) => !delete.returning.is_empty(),
+    }
+}
+
+/// The operation a named query is served as: reads are GETs, writes take their parameters
+/// as a request body, and rows come back as `<Name>Row` objects.
+fn api_metadata(name: &str, command: QueryCommand, query_type: &QueryType, parameters: &[QueryParameter]) -> ApiMetadata {
+    let method = match query_type {
+        QueryType::Insert => "POST",
+        QueryType::Update => "PUT",
+        QueryType::Delete => "DELETE",
+        _ => "GET",
+    };
+    let parameters = parameters
+        .iter()
+        .map(|parameter| {
+            let param_type = parameter.data_type.as_ref().map_or("any", |data_type| data_type.json_schema_type());
+            let api_parameter = ApiParameter::new(&parameter.name, param_type)
+                .with_description(&format!("Bound to {}", parameter.placeholder));
+            if parameter.nullable { api_parameter.optional() } else { api_parameter }
+        })
+        .collect();
+    let metadata = ApiMetadata::new(method, &format!("/{}", kebab_case(name))).with_parameters(parameters);
+    match command {
+        QueryCommand::One | QueryCommand::Many => metadata.with_response_schema(&format!("{}Row", name)),
+        QueryCommand::Exec | QueryCommand::ExecRows => metadata,
+    }
+}
+
+/// `GetUserByEmail` or `get_user_by_email` as `get-user-by-email`.
+fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+    for ch in name.chars() {
+        if ch == '_' || ch == '-' || ch == ' ' {
+            if !out.is_empty() && !out.ends_with('-') {
+                out.push('-');
+            }
+        } else {
+            let boundary = ch.is_uppercase()
+                && previous.is_some_and(|previous| previous.is_lowercase() || previous.is_ascii_digit());
+            if boundary && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.extend(ch.to_lowercase());
+        }
+        previous = Some(ch);
+    }
+    out
+}
+
+/// The `--` comments on the lines directly above a statement, top first and without the dashes.
+fn leading_comments(sql: &str, start: usize) -> Vec<String> {
+    let before: String = sql.chars().take(start).collect();
+    let mut lines = before.lines().rev();
+    // What precedes the statement on its own line, when it isn't the first thing on it
+    if !before.ends_with('\n') && lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        return Vec::new();
+    }
+    let mut comments: Vec<String> = lines
+        .map(str::trim)
+        .take_while(|line| line.starts_with("--"))
+        .map(|line| line.trim_start_matches('-').trim().to_string())
+        .collect();
+    comments.reverse();
+    comments
+}
+
+/// The text between two statement starts, given as character offsets, less the trailing
+/// semicolon and any comments that lead into the next statement.
+fn statement_text(sql: &str, start: usize, end: usize) -> String {
+    let text: String = sql.chars().skip(start).take(end.saturating_sub(start)).collect();
+    let mut lines: Vec<&str> = text.lines().collect();
+    while lines.last().is_some_and(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with("--")
+    }) {
+        lines.pop();
+    }
+    let text = lines.join("\n");
+    text.trim_end().trim_end_matches(';').trim_end().to_string()
+}
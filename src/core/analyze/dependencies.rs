@@ -0,0 +1,594 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::analyze::catalog::QueryCatalog;
+use crate::core::analyze::lineage::{LineageAnalyzer, ViewLineage};
+use crate::core::analyze::metadata::QueryAnalyzer;
+use crate::core::analyze::query::search_schemas;
+use crate::core::ast::{DmlStatement, SchemaReference, Statement};
+use crate::core::domain::entity::{Database, Table};
+use crate::core::domain::procedures::Procedure;
+use crate::core::lower::Lowerer;
+use crate::core::parse::{Lexer, Parser, SqlDialect, TokenType};
+
+/// Every object in a schema that something can depend on, and what depends on what: the
+/// columns of each table, foreign keys, the tables and columns views and named queries read,
+/// the columns indexes cover, and what triggers and procedures touch. Unlike the per-kind graphs
+/// of [`crate::core::domain`], views and queries are parsed, so a column counts as read only
+/// where it is named, or through `*`, and a view's columns are nodes of their own that depend
+/// on the table columns their [lineage](LineageAnalyzer) traces them to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<ObjectId>,
+    pub edges: Vec<Dependency>,
+}
+
+/// A node of the graph, written `kind:name` as in `column:orders.amount`. Tables, views,
+/// procedures and functions are named with their schema when they have one, columns after their
+/// table or view, triggers after their table, and queries by their `-- name:` or else `file:line`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ObjectId {
+    pub kind: ObjectKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectKind {
+    Table,
+    Column,
+    View,
+    Index,
+    Trigger,
+    Procedure,
+    Function,
+    Query,
+}
+
+/// `from` depends on `to`, so changing `to` may break `from`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub from: ObjectId,
+    pub to: ObjectId,
+    pub kind: DependencyKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// A column belongs to its table
+    ColumnOf,
+    /// A foreign key column references a column of another table
+    ForeignKey,
+    /// A view, query, trigger or procedure reads or writes a table, view or column, or a view's
+    /// column is computed from a table's
+    References,
+    /// An index covers a column
+    Indexes,
+    /// A trigger fires on a table
+    FiresOn,
+    /// A procedure, function or trigger calls a procedure or function
+    Calls,
+}
+
+/// A change whose consequences [`DependencyGraph::impact`] traces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    Drop,
+    Rename,
+    /// Only meaningful for a column
+    ChangeType,
+}
+
+/// Everything a change to one object breaks or alters, nearest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Impact {
+    pub object: ObjectId,
+    pub change: Change,
+    pub affected: Vec<AffectedObject>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AffectedObject {
+    pub object: ObjectId,
+    /// The object it is affected through: the changed one, or another affected object
+    pub through: ObjectId,
+    pub dependency: DependencyKind,
+    /// How many dependencies away from the changed object it is, from 1
+    pub distance: usize,
+}
+
+/// Builds a [`DependencyGraph`] from a schema and, optionally, the named queries run against it.
+pub struct DependencyGraphBuilder<'a> {
+    database: &'a Database,
+    lowerer: Lowerer,
+    catalog: Option<&'a QueryCatalog>,
+    lineage: Vec<ViewLineage>,
+}
+
+impl<'a> DependencyGraphBuilder<'a> {
+    pub fn new(database: &'a Database, dialect: SqlDialect) -> Self {
+        let lineage = LineageAnalyzer::new(database, dialect.clone()).views();
+        Self { database, lowerer: Lowerer::new(dialect), catalog: None, lineage }
+    }
+
+    /// Adds the queries of `catalog`, which should have been built against the same schema.
+    pub fn with_queries(mut self, catalog: &'a QueryCatalog) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    pub fn build(&self) -> DependencyGraph {
+        let mut graph = DependencyGraph { nodes: Vec::new(), edges: Vec::new() };
+
+        for table in self.database.get_all_tables() {
+            let table_id = ObjectId::new(ObjectKind::Table, &qualified(&table.schema, &table.name));
+            graph.add_node(table_id.clone());
+            for column in &table.columns {
+                let column_id = column_id(table, &column.name);
+                graph.add_node(column_id.clone());
+                graph.add_edge(column_id, table_id.clone(), DependencyKind::ColumnOf);
+            }
+        }
+        for view in self.database.get_all_views() {
+            let view_id = ObjectId::new(ObjectKind::View, &qualified(&view.schema, &view.name));
+            graph.add_node(view_id.clone());
+            for column in self.relation_columns(None, &view_id) {
+                graph.add_node(column.clone());
+                graph.add_edge(column, view_id.clone(), DependencyKind::ColumnOf);
+            }
+        }
+        for schema in &self.database.schemas {
+            for procedure in &schema.procedures {
+                graph.add_node(procedure_id(ObjectKind::Procedure, procedure));
+            }
+            for function in &schema.functions {
+                graph.add_node(procedure_id(ObjectKind::Function, function));
+            }
+        }
+
+        for table in self.database.get_all_tables() {
+            let table_id = ObjectId::new(ObjectKind::Table, &qualified(&table.schema, &table.name));
+            for foreign_key in &table.foreign_keys {
+                let reference = match &foreign_key.referenced_schema {
+                    Some(schema) => SchemaReference::with_schema(schema, &foreign_key.referenced_table),
+                    None => SchemaReference::new(&foreign_key.referenced_table),
+                };
+                let Some(referenced) = self.find_table(&reference) else {
+                    continue;
+                };
+                for (column, referenced_column) in foreign_key.columns.iter().zip(&foreign_key.referenced_columns) {
+                    graph.add_edge(column_id(table, column), column_id(referenced, referenced_column), DependencyKind::ForeignKey);
+                }
+            }
+            for index in &table.indexes {
+                let index_id = ObjectId::new(ObjectKind::Index, &index.name);
+                graph.add_node(index_id.clone());
+                for column in &index.columns {
+                    if let Some(column) = table.columns.iter().find(|candidate| candidate.name.eq_ignore_ascii_case(&column.name)) {
+                        graph.add_edge(index_id.clone(), column_id(table, &column.name), DependencyKind::Indexes);
+                    }
+                }
+            }
+            for trigger in &table.triggers {
+                let trigger_id = ObjectId::new(ObjectKind::Trigger, &format!("{}.{}", table_id.name, trigger.name));
+                graph.add_node(trigger_id.clone());
+                graph.add_edge(trigger_id.clone(), table_id.clone(), DependencyKind::FiresOn);
+                for column in trigger.update_columns.iter().filter_map(|column| find_column(table, column)) {
+                    graph.add_edge(trigger_id.clone(), column, DependencyKind::References);
+                }
+                for (target, kind) in self.body_references(&trigger.definition, Some(table)) {
+                    graph.add_edge(trigger_id.clone(), target, kind);
+                }
+            }
+        }
+
+        for view in self.database.get_all_views() {
+            let view_id = ObjectId::new(ObjectKind::View, &qualified(&view.schema, &view.name));
+            if let Some(statement) = self.parse_query(&view.definition) {
+                let metadata = QueryAnalyzer::new().analyze(&statement);
+                for target in self.query_references(&metadata.tables_accessed, &metadata.columns_accessed) {
+                    graph.add_edge(view_id.clone(), target, DependencyKind::References);
+                }
+            }
+            let Some(lineage) = self.view_lineage(&view_id) else {
+                continue;
+            };
+            for column in &lineage.columns {
+                for source in &column.sources {
+                    let source = ObjectId::new(ObjectKind::Column, &format!("{}.{}", source.table, source.column));
+                    if graph.nodes.contains(&source) {
+                        graph.add_edge(view_column_id(&view_id, &column.column), source, DependencyKind::References);
+                    }
+                }
+            }
+        }
+
+        for schema in &self.database.schemas {
+            let routines = schema
+                .procedures
+                .iter()
+                .map(|procedure| (ObjectKind::Procedure, procedure))
+                .chain(schema.functions.iter().map(|function| (ObjectKind::Function, function)));
+            for (kind, routine) in routines {
+                let routine_id = procedure_id(kind, routine);
+                for (target, dependency) in self.body_references(&routine.definition, None) {
+                    if target != routine_id {
+                        graph.add_edge(routine_id.clone(), target, dependency);
+                    }
+                }
+            }
+        }
+
+        for query in self.catalog.iter().flat_map(|catalog| &catalog.queries) {
+            let name = query.name.clone().unwrap_or_else(|| format!("{}:{}", query.source, query.line));
+            let query_id = ObjectId::new(ObjectKind::Query, &name);
+            graph.add_node(query_id.clone());
+            for target in self.query_references(&query.metadata.tables_accessed, &query.metadata.columns_accessed) {
+                graph.add_edge(query_id.clone(), target, DependencyKind::References);
+            }
+        }
+        graph
+    }
+
+    fn parse_query(&self, sql: &str) -> Option<DmlStatement> {
+        let tokens = Lexer::for_dialect(sql, self.lowerer.dialect()).tokenize().ok()?;
+        match Parser::new(tokens).parse().ok()?.into_iter().next()? {
+            Statement::Dml(dml) => Some(*dml),
+            _ => None,
+        }
+    }
+
+    /// The tables, views and columns named by what [`QueryAnalyzer`] found a query to access:
+    /// `table.column`, `table.*` for every column, or a bare column of one of its tables or views.
+    fn query_references(&self, tables: &[String], columns: &[String]) -> Vec<ObjectId> {
+        let mut targets = Vec::new();
+        let relations: Vec<(Option<&Table>, ObjectId)> =
+            tables.iter().filter_map(|name| self.find_relation(&reference(name))).collect();
+        for (_, relation) in &relations {
+            push_unique(&mut targets, relation.clone());
+        }
+        for column in columns {
+            match column.rsplit_once('.') {
+                Some((relation, column)) => {
+                    let Some((table, relation)) = self.find_relation(&reference(relation)) else {
+                        continue;
+                    };
+                    if column == "*" {
+                        for column in self.relation_columns(table, &relation) {
+                            push_unique(&mut targets, column);
+                        }
+                    } else if let Some(column) = self.find_relation_column(table, &relation, column) {
+                        push_unique(&mut targets, column);
+                    }
+                }
+                None => {
+                    let found = relations
+                        .iter()
+                        .find_map(|(table, relation)| self.find_relation_column(*table, relation, column));
+                    if let Some(column) = found {
+                        push_unique(&mut targets, column);
+                    }
+                }
+            }
+        }
+        targets
+    }
+
+    /// The column nodes of a table, or of a view as far as its lineage could be traced.
+    fn relation_columns(&self, table: Option<&Table>, relation: &ObjectId) -> Vec<ObjectId> {
+        match table {
+            Some(table) => table.columns.iter().map(|column| column_id(table, &column.name)).collect(),
+            None => self
+                .view_lineage(relation)
+                .map(|lineage| lineage.columns.iter().map(|column| view_column_id(relation, &column.column)).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn find_relation_column(&self, table: Option<&Table>, relation: &ObjectId, name: &str) -> Option<ObjectId> {
+        match table {
+            Some(table) => find_column(table, name),
+            None => self
+                .view_lineage(relation)?
+                .columns
+                .iter()
+                .find(|column| column.column.eq_ignore_ascii_case(name))
+                .map(|column| view_column_id(relation, &column.column)),
+        }
+    }
+
+    fn view_lineage(&self, view: &ObjectId) -> Option<&ViewLineage> {
+        self.lineage.iter().find(|lineage| lineage.view == view.name)
+    }
+
+    /// What a trigger or routine body names, read from its tokens since it needn't be SQL this
+    /// parser understands: tables, views and `table.column`, `NEW.column` and `OLD.column` for
+    /// the trigger's own table, and calls of procedures and functions.
+    fn body_references(&self, body: &str, own_table: Option<&Table>) -> Vec<(ObjectId, DependencyKind)> {
+        let Ok(tokens) = Lexer::for_dialect(body, self.lowerer.dialect()).tokenize() else {
+            return Vec::new();
+        };
+        // Dotted names, as their parts
+        let mut names: Vec<Vec<String>> = Vec::new();
+        let mut after_dot = false;
+        for token in &tokens {
+            match &token.token_type {
+                TokenType::Identifier(name) | TokenType::QuotedIdentifier(name) => match names.last_mut() {
+                    Some(parts) if after_dot => parts.push(name.clone()),
+                    _ => names.push(vec![name.clone()]),
+                },
+                TokenType::Dot => {}
+                _ => names.push(Vec::new()),
+            }
+            after_dot = token.token_type == TokenType::Dot;
+        }
+
+        let mut references = Vec::new();
+        for parts in names.iter().filter(|parts| !parts.is_empty()) {
+            let (qualifier, last) = match parts.as_slice() {
+                [name] => (None, name),
+                [.., qualifier, name] => (Some(qualifier), name),
+                [] => continue,
+            };
+            if let (Some(qualifier), Some(table)) = (qualifier, own_table)
+                && (qualifier.eq_ignore_ascii_case("new") || qualifier.eq_ignore_ascii_case("old"))
+            {
+                if let Some(column) = find_column(table, last) {
+                    push_unique(&mut references, (column, DependencyKind::References));
+                }
+                continue;
+            }
+            if let Some(qualifier) = qualifier
+                && let Some((table, relation)) = self.find_relation(&reference(qualifier))
+                && let Some(column) = self.find_relation_column(table, &relation, last)
+            {
+                push_unique(&mut references, (column, DependencyKind::References));
+                continue;
+            }
+            let name = SchemaReference::from_parts(parts).unwrap_or_else(|| SchemaReference::new(last));
+            if let Some((_, relation)) = self.find_relation(&name) {
+                push_unique(&mut references, (relation, DependencyKind::References));
+            } else if let Some(routine) = self.find_routine(&name) {
+                push_unique(&mut references, (routine, DependencyKind::Calls));
+            }
+        }
+        references
+    }
+
+    fn find_table(&self, name: &SchemaReference) -> Option<&'a Table> {
+        search_schemas(self.database, &self.lowerer, name)
+            .into_iter()
+            .find_map(|schema| schema.tables.iter().find(|table| table.name.eq_ignore_ascii_case(&name.object)))
+    }
+
+    /// A table, with its node, or a view's node.
+    fn find_relation(&self, name: &SchemaReference) -> Option<(Option<&'a Table>, ObjectId)> {
+        for schema in search_schemas(self.database, &self.lowerer, name) {
+            if let Some(table) = schema.tables.iter().find(|table| table.name.eq_ignore_ascii_case(&name.object)) {
+                return Some((Some(table), ObjectId::new(ObjectKind::Table, &qualified(&table.schema, &table.name))));
+            }
+            if let Some(view) = schema.views.iter().find(|view| view.name.eq_ignore_ascii_case(&name.object)) {
+                return Some((None, ObjectId::new(ObjectKind::View, &qualified(&view.schema, &view.name))));
+            }
+        }
+        None
+    }
+
+    fn find_routine(&self, name: &SchemaReference) -> Option<ObjectId> {
+        search_schemas(self.database, &self.lowerer, name).into_iter().find_map(|schema| {
+            let matches = |routine: &&Procedure| routine.name.eq_ignore_ascii_case(&name.object);
+            schema
+                .procedures
+                .iter()
+                .find(matches)
+                .map(|procedure| procedure_id(ObjectKind::Procedure, procedure))
+                .or_else(|| schema.functions.iter().find(matches).map(|function| procedure_id(ObjectKind::Function, function)))
+        })
+    }
+}
+
+impl DependencyGraph {
+    /// Finds a node by `kind:name`, or by name alone, preferring tables, then views, columns,
+    /// indexes, triggers, procedures, functions and queries. Names match case-insensitively.
+    pub fn find(&self, name: &str) -> Option<&ObjectId> {
+        if let Some((kind, name)) = name.split_once(':')
+            && let Some(kind) = ObjectKind::parse(kind)
+        {
+            return self.nodes.iter().find(|node| node.kind == kind && node.name.eq_ignore_ascii_case(name));
+        }
+        ObjectKind::ALL.iter().find_map(|kind| {
+            self.nodes.iter().find(|node| node.kind == *kind && node.name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// The objects that depend on `object` directly.
+    pub fn dependents(&self, object: &ObjectId) -> Vec<&Dependency> {
+        self.edges.iter().filter(|edge| &edge.to == object).collect()
+    }
+
+    /// The objects `object` depends on directly.
+    pub fn dependencies(&self, object: &ObjectId) -> Vec<&Dependency> {
+        self.edges.iter().filter(|edge| &edge.from == object).collect()
+    }
+
+    /// Everything transitively affected by `change` to `object`. A dropped table takes its
+    /// columns, indexes and triggers with it; a view that reads an affected table or view is
+    /// affected in turn, as is whatever reads that view. A changed column only reaches further
+    /// through the view columns computed from it, so a view reading another view is affected
+    /// only when it reads one of those. A foreign key only passes a type change on, since the
+    /// referencing column must change with it, and renames leave indexes, triggers, foreign keys
+    /// and the names of view columns alone as the database carries them over.
+    pub fn impact(&self, object: &ObjectId, change: Change) -> Impact {
+        let mut affected: Vec<AffectedObject> = Vec::new();
+        let mut seen = HashSet::from([object.clone()]);
+        let mut queue = VecDeque::from([(object.clone(), 0)]);
+        while let Some((current, distance)) = queue.pop_front() {
+            for edge in self.dependents(&current) {
+                let Some(propagates) = affects(edge, change) else {
+                    continue;
+                };
+                if !seen.insert(edge.from.clone()) {
+                    continue;
+                }
+                affected.push(AffectedObject {
+                    object: edge.from.clone(),
+                    through: current.clone(),
+                    dependency: edge.kind,
+                    distance: distance + 1,
+                });
+                if propagates {
+                    queue.push_back((edge.from.clone(), distance + 1));
+                }
+            }
+        }
+        Impact { object: object.clone(), change, affected }
+    }
+
+    fn add_node(&mut self, node: ObjectId) {
+        if !self.nodes.contains(&node) {
+            self.nodes.push(node);
+        }
+    }
+
+    fn add_edge(&mut self, from: ObjectId, to: ObjectId, kind: DependencyKind) {
+        let edge = Dependency { from, to, kind };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+}
+
+/// Whether a change reaches across a dependency and, if so, whether it carries on to what
+/// depends on the affected object.
+fn affects(edge: &Dependency, change: Change) -> Option<bool> {
+    match (edge.kind, change) {
+        // The view's columns computed from the column carry the change on through their own edges
+        (DependencyKind::References, _) if edge.from.kind == ObjectKind::View && edge.to.kind == ObjectKind::Column => {
+            Some(false)
+        }
+        // A view column keeps its name when a column it is computed from is renamed
+        (DependencyKind::References, Change::Rename) if edge.from.kind == ObjectKind::Column => None,
+        (DependencyKind::References | DependencyKind::Calls, _) => Some(true),
+        (DependencyKind::ColumnOf, Change::Drop) => Some(true),
+        (DependencyKind::ForeignKey, Change::ChangeType) => Some(true),
+        (DependencyKind::ForeignKey | DependencyKind::Indexes | DependencyKind::FiresOn, Change::Drop) => Some(false),
+        (DependencyKind::Indexes, Change::ChangeType) => Some(false),
+        _ => None,
+    }
+}
+
+impl ObjectId {
+    pub fn new(kind: ObjectKind, name: &str) -> Self {
+        Self { kind, name: name.to_string() }
+    }
+}
+
+impl ObjectKind {
+    /// In the order [`DependencyGraph::find`] prefers them.
+    pub const ALL: [ObjectKind; 8] = [
+        ObjectKind::Table,
+        ObjectKind::View,
+        ObjectKind::Column,
+        ObjectKind::Index,
+        ObjectKind::Trigger,
+        ObjectKind::Procedure,
+        ObjectKind::Function,
+        ObjectKind::Query,
+    ];
+
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.to_string().eq_ignore_ascii_case(text))
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind, self.name)
+    }
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ObjectKind::Table => "table",
+            ObjectKind::Column => "column",
+            ObjectKind::View => "view",
+            ObjectKind::Index => "index",
+            ObjectKind::Trigger => "trigger",
+            ObjectKind::Procedure => "procedure",
+            ObjectKind::Function => "function",
+            ObjectKind::Query => "query",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// How the dependent relates to what it depends on: `column of`, `reads`.
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            DependencyKind::ColumnOf => "column of",
+            DependencyKind::ForeignKey => "foreign key to",
+            DependencyKind::References => "reads",
+            DependencyKind::Indexes => "indexes",
+            DependencyKind::FiresOn => "fires on",
+            DependencyKind::Calls => "calls",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Change::Drop => "dropping",
+            Change::Rename => "renaming",
+            Change::ChangeType => "changing the type of",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+fn column_id(table: &Table, column: &str) -> ObjectId {
+    ObjectId::new(ObjectKind::Column, &format!("{}.{}", qualified(&table.schema, &table.name), column))
+}
+
+fn view_column_id(view: &ObjectId, column: &str) -> ObjectId {
+    ObjectId::new(ObjectKind::Column, &format!("{}.{}", view.name, column))
+}
+
+/// The column of `table` named `name`, however it is cased.
+fn find_column(table: &Table, name: &str) -> Option<ObjectId> {
+    table
+        .columns
+        .iter()
+        .find(|column| column.name.eq_ignore_ascii_case(name))
+        .map(|column| column_id(table, &column.name))
+}
+
+fn procedure_id(kind: ObjectKind, procedure: &Procedure) -> ObjectId {
+    ObjectId::new(kind, &qualified(&procedure.schema_name, &procedure.name))
+}
+
+/// A dotted name as [`QueryAnalyzer`] writes it back into a reference.
+fn reference(name: &str) -> SchemaReference {
+    let parts: Vec<String> = name.split('.').map(str::to_string).collect();
+    SchemaReference::from_parts(&parts).unwrap_or_else(|| SchemaReference::new(name))
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+fn qualified(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
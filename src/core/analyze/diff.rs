@@ -0,0 +1,881 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::domain::entity::{
+    Column, Database, IndexType, ReferentialAction, Table, UserDefinedType, UserDefinedTypeKind, View,
+};
+use crate::core::domain::procedures::{ParameterMode, Procedure};
+use crate::core::domain::triggers::{Trigger, TriggerEvent};
+
+/// How alike a table or column pair has to be, from 0 to 1, before a drop and an add are read
+/// as one rename.
+const RENAME_THRESHOLD: f64 = 0.6;
+
+/// Every structural difference between two [`Database`] models, in the order of the new one:
+/// types, then each table with its columns, keys, indexes and triggers, then views and routines.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// One difference. A column, key, index or trigger is named after its table, as in
+/// `orders.amount`; an unnamed key goes by its table alone, and its definition tells it apart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    pub action: ChangeAction,
+    pub object: DiffObject,
+    /// The name in the new schema, or in the old one for a removed object
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<DiffAttribute>,
+    /// The definition or value before the change; the old name of a renamed object
+    pub old: Option<String>,
+    /// The definition or value after the change
+    pub new: Option<String>,
+}
+
+impl SchemaChange {
+    fn new(action: ChangeAction, object: DiffObject, name: impl Into<String>) -> Self {
+        Self { action, object, name: name.into(), attribute: None, old: None, new: None }
+    }
+
+    fn with_old(mut self, old: impl Into<String>) -> Self {
+        self.old = Some(old.into());
+        self
+    }
+
+    fn with_new(mut self, new: impl Into<String>) -> Self {
+        self.new = Some(new.into());
+        self
+    }
+
+    fn modified(object: DiffObject, name: &str, attribute: DiffAttribute, old: Option<String>, new: Option<String>) -> Self {
+        Self { action: ChangeAction::Modified, object, name: name.to_string(), attribute: Some(attribute), old, new }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".to_string());
+        match self.action {
+            ChangeAction::Added => {
+                write!(f, "added {} {}", self.object, self.name)?;
+                match &self.new {
+                    Some(definition) if !self.object.has_body() => write!(f, " {}", definition),
+                    _ => Ok(()),
+                }
+            }
+            ChangeAction::Removed => write!(f, "removed {} {}", self.object, self.name),
+            ChangeAction::Renamed => write!(f, "renamed {} {} to {}", self.object, value(&self.old), self.name),
+            ChangeAction::Modified => match self.attribute {
+                Some(DiffAttribute::Definition) if self.object.has_body() => {
+                    write!(f, "changed the definition of {} {}", self.object, self.name)
+                }
+                Some(attribute) => write!(
+                    f,
+                    "changed the {} of {} {} from {} to {}",
+                    attribute,
+                    self.object,
+                    self.name,
+                    value(&self.old),
+                    value(&self.new)
+                ),
+                None => write!(f, "changed {} {}", self.object, self.name),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Added,
+    Removed,
+    Renamed,
+    Modified,
+}
+
+impl fmt::Display for ChangeAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeAction::Added => "added",
+            ChangeAction::Removed => "removed",
+            ChangeAction::Renamed => "renamed",
+            ChangeAction::Modified => "modified",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffObject {
+    Type,
+    Table,
+    Column,
+    PrimaryKey,
+    ForeignKey,
+    Unique,
+    Check,
+    Exclusion,
+    Index,
+    Trigger,
+    View,
+    Procedure,
+    Function,
+}
+
+impl DiffObject {
+    /// The name of the object in the plural, as in "indexes are not part of a row schema".
+    pub fn plural(&self) -> &'static str {
+        match self {
+            DiffObject::Type => "types",
+            DiffObject::Table => "tables",
+            DiffObject::Column => "columns",
+            DiffObject::PrimaryKey => "primary keys",
+            DiffObject::ForeignKey => "foreign keys",
+            DiffObject::Unique => "unique constraints",
+            DiffObject::Check => "check constraints",
+            DiffObject::Exclusion => "exclusion constraints",
+            DiffObject::Index => "indexes",
+            DiffObject::Trigger => "triggers",
+            DiffObject::View => "views",
+            DiffObject::Procedure => "procedures",
+            DiffObject::Function => "functions",
+        }
+    }
+
+    /// Objects whose definition is a body of SQL, too long to repeat in a one-line summary.
+    pub fn has_body(&self) -> bool {
+        matches!(self, DiffObject::Trigger | DiffObject::View | DiffObject::Procedure | DiffObject::Function)
+    }
+}
+
+impl fmt::Display for DiffObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DiffObject::Type => "type",
+            DiffObject::Table => "table",
+            DiffObject::Column => "column",
+            DiffObject::PrimaryKey => "primary key",
+            DiffObject::ForeignKey => "foreign key",
+            DiffObject::Unique => "unique constraint",
+            DiffObject::Check => "check constraint",
+            DiffObject::Exclusion => "exclusion constraint",
+            DiffObject::Index => "index",
+            DiffObject::Trigger => "trigger",
+            DiffObject::View => "view",
+            DiffObject::Procedure => "procedure",
+            DiffObject::Function => "function",
+        })
+    }
+}
+
+/// What about a modified object changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffAttribute {
+    Type,
+    Nullability,
+    Default,
+    AutoIncrement,
+    Generated,
+    Collation,
+    Columns,
+    Definition,
+}
+
+impl fmt::Display for DiffAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DiffAttribute::Type => "type",
+            DiffAttribute::Nullability => "nullability",
+            DiffAttribute::Default => "default",
+            DiffAttribute::AutoIncrement => "auto-increment",
+            DiffAttribute::Generated => "generation expression",
+            DiffAttribute::Collation => "collation",
+            DiffAttribute::Columns => "columns",
+            DiffAttribute::Definition => "definition",
+        })
+    }
+}
+
+/// Compares two [`Database`] models. Objects are paired by name; a table or column missing
+/// from one side is paired with the most similar unpaired one on the other side, as a rename,
+/// when they are alike enough: tables by their names and column sets, columns by their names,
+/// types, nullability and position. Views, routines, keys and indexes that moved to a new name
+/// unchanged count as renamed too. Keys and indexes are compared through any column and table
+/// renames, so a renamed column doesn't also show up as a changed key.
+pub struct SchemaDiffer<'a> {
+    old: &'a Database,
+    new: &'a Database,
+}
+
+/// A pair of tables found to be the same one, with the pairing of their columns.
+struct TablePair<'a> {
+    old: &'a Table,
+    new: &'a Table,
+    renamed: bool,
+    columns: Vec<Option<Pairing>>,
+}
+
+/// The table and column names of the old schema as the new one knows them.
+#[derive(Default)]
+struct Renames {
+    tables: HashMap<String, String>,
+    columns: HashMap<String, HashMap<String, String>>,
+}
+
+impl Renames {
+    fn table(&self, name: &str) -> String {
+        self.tables.get(&name.to_lowercase()).cloned().unwrap_or_else(|| name.to_string())
+    }
+
+    fn column(&self, table: &str, column: &str) -> String {
+        self.columns
+            .get(&table.to_lowercase())
+            .and_then(|columns| columns.get(&column.to_lowercase()))
+            .cloned()
+            .unwrap_or_else(|| column.to_string())
+    }
+}
+
+/// A named key, index or trigger reduced to its definition, so two of them compare as text.
+struct Definition {
+    name: Option<String>,
+    definition: String,
+}
+
+impl<'a> SchemaDiffer<'a> {
+    pub fn new(old: &'a Database, new: &'a Database) -> Self {
+        Self { old, new }
+    }
+
+    pub fn diff(&self) -> SchemaDiff {
+        let mut changes = Vec::new();
+        self.diff_types(&mut changes);
+
+        let old_tables = self.old.get_all_tables();
+        let new_tables = self.new.get_all_tables();
+        let pairing = pair(
+            &old_tables,
+            &new_tables,
+            &[&|table: &&Table| qualified(&table.schema, &table.name), &|table: &&Table| table.name.clone()],
+            &|old: &&Table, new: &&Table| table_similarity(old, new),
+        );
+        let pairs: Vec<Option<TablePair>> = pairing
+            .iter()
+            .zip(&new_tables)
+            .map(|(pairing, new)| {
+                pairing.map(|pairing| {
+                    let old = old_tables[pairing.old];
+                    TablePair { old, new, renamed: pairing.renamed, columns: pair_columns(old, new) }
+                })
+            })
+            .collect();
+
+        let mut renames = Renames::default();
+        for pair in pairs.iter().flatten() {
+            let old_name = qualified(&pair.old.schema, &pair.old.name);
+            renames.tables.insert(old_name.to_lowercase(), qualified(&pair.new.schema, &pair.new.name));
+            let columns = renames.columns.entry(old_name.to_lowercase()).or_default();
+            for (pairing, column) in pair.columns.iter().zip(&pair.new.columns) {
+                if let Some(pairing) = pairing {
+                    columns.insert(pair.old.columns[pairing.old].name.to_lowercase(), column.name.clone());
+                }
+            }
+        }
+
+        for (pair, new) in pairs.iter().zip(&new_tables) {
+            match pair {
+                Some(pair) => self.diff_table(&mut changes, pair, &renames),
+                None => changes.push(SchemaChange::new(ChangeAction::Added, DiffObject::Table, display(new))),
+            }
+        }
+        for table in unpaired(&old_tables, &pairing) {
+            changes.push(SchemaChange::new(ChangeAction::Removed, DiffObject::Table, display(table)));
+        }
+
+        self.diff_views(&mut changes);
+        let routines = |database: &'a Database, functions: bool| -> Vec<&'a Procedure> {
+            database
+                .schemas
+                .iter()
+                .flat_map(|schema| if functions { &schema.functions } else { &schema.procedures })
+                .collect()
+        };
+        diff_routines(&mut changes, DiffObject::Procedure, &routines(self.old, false), &routines(self.new, false));
+        diff_routines(&mut changes, DiffObject::Function, &routines(self.old, true), &routines(self.new, true));
+
+        SchemaDiff { changes }
+    }
+
+    fn diff_types(&self, changes: &mut Vec<SchemaChange>) {
+        let old = self.old.get_all_types();
+        let new = self.new.get_all_types();
+        let name = |user_type: &&UserDefinedType| qualified(&user_type.schema, &user_type.name);
+        let pairing = pair(
+            &old,
+            &new,
+            &[&name, &|user_type: &&UserDefinedType| user_type.name.clone()],
+            &|old: &&UserDefinedType, new: &&UserDefinedType| same(&type_definition(old), &type_definition(new)),
+        );
+        for (pairing, new) in pairing.iter().zip(&new) {
+            let definition = type_definition(new);
+            match pairing {
+                None => changes.push(SchemaChange::new(ChangeAction::Added, DiffObject::Type, name(new)).with_new(definition)),
+                Some(pairing) => {
+                    let old = old[pairing.old];
+                    if pairing.renamed {
+                        let change = SchemaChange::new(ChangeAction::Renamed, DiffObject::Type, name(new));
+                        changes.push(change.with_old(name(&old)));
+                    } else if type_definition(old) != definition {
+                        changes.push(SchemaChange::modified(
+                            DiffObject::Type,
+                            &name(new),
+                            DiffAttribute::Definition,
+                            Some(type_definition(old)),
+                            Some(definition),
+                        ));
+                    }
+                }
+            }
+        }
+        for user_type in unpaired(&old, &pairing) {
+            changes.push(SchemaChange::new(ChangeAction::Removed, DiffObject::Type, name(user_type)));
+        }
+    }
+
+    fn diff_table(&self, changes: &mut Vec<SchemaChange>, pair: &TablePair, renames: &Renames) {
+        let (old, new) = (pair.old, pair.new);
+        let table = display(new);
+        let old_table = qualified(&old.schema, &old.name);
+        if pair.renamed {
+            changes.push(SchemaChange::new(ChangeAction::Renamed, DiffObject::Table, &table).with_old(display(old)));
+        }
+
+        for (pairing, column) in pair.columns.iter().zip(&new.columns) {
+            let name = format!("{}.{}", table, column.name);
+            let Some(pairing) = pairing else {
+                let change = SchemaChange::new(ChangeAction::Added, DiffObject::Column, name);
+                changes.push(change.with_new(column_definition(column)));
+                continue;
+            };
+            let previous = &old.columns[pairing.old];
+            if pairing.renamed {
+                changes.push(
+                    SchemaChange::new(ChangeAction::Renamed, DiffObject::Column, &name)
+                        .with_old(format!("{}.{}", table, previous.name)),
+                );
+            }
+            diff_column(changes, &name, previous, column);
+        }
+        for column in unpaired(&old.columns, &pair.columns) {
+            changes.push(SchemaChange::new(ChangeAction::Removed, DiffObject::Column, format!("{}.{}", table, column.name)));
+        }
+
+        let rename = |column: &str| renames.column(&old_table, column);
+        let primary_key = |table: &Table, rename: &dyn Fn(&str) -> String| {
+            table.primary_key.as_ref().map(|pk| format!("PRIMARY KEY ({})", list(&pk.columns, rename)))
+        };
+        match (primary_key(old, &rename), primary_key(new, &|column| column.to_string())) {
+            (None, Some(key)) => {
+                changes.push(SchemaChange::new(ChangeAction::Added, DiffObject::PrimaryKey, &table).with_new(key));
+            }
+            (Some(key), None) => {
+                changes.push(SchemaChange::new(ChangeAction::Removed, DiffObject::PrimaryKey, &table).with_old(key));
+            }
+            (Some(before), Some(after)) if before != after => changes.push(SchemaChange::modified(
+                DiffObject::PrimaryKey,
+                &table,
+                DiffAttribute::Columns,
+                Some(before),
+                Some(after),
+            )),
+            _ => {}
+        }
+
+        let unchanged = |column: &str| column.to_string();
+        let foreign_keys = |table: &Table, rename: &dyn Fn(&str) -> String, target: &dyn Fn(&str, &str) -> (String, String)| {
+            table
+                .foreign_keys
+                .iter()
+                .map(|fk| {
+                    let referenced = qualified(&fk.referenced_schema, &fk.referenced_table);
+                    let columns: Vec<String> =
+                        fk.referenced_columns.iter().map(|column| target(&referenced, column).1).collect();
+                    let mut definition = format!(
+                        "FOREIGN KEY ({}) REFERENCES {} ({})",
+                        list(&fk.columns, rename),
+                        target(&referenced, "").0,
+                        columns.join(", ")
+                    );
+                    if fk.on_delete != ReferentialAction::NoAction {
+                        definition.push_str(&format!(" ON DELETE {}", action_sql(&fk.on_delete)));
+                    }
+                    if fk.on_update != ReferentialAction::NoAction {
+                        definition.push_str(&format!(" ON UPDATE {}", action_sql(&fk.on_update)));
+                    }
+                    Definition { name: fk.constraint_name.clone().or_else(|| fk.name.clone()), definition }
+                })
+                .collect::<Vec<_>>()
+        };
+        let renamed_target = |table: &str, column: &str| (renames.table(table), renames.column(table, column));
+        let same_target = |table: &str, column: &str| (table.to_string(), column.to_string());
+        diff_definitions(
+            changes,
+            DiffObject::ForeignKey,
+            &table,
+            &foreign_keys(old, &rename, &renamed_target),
+            &foreign_keys(new, &unchanged, &same_target),
+        );
+
+        let uniques = |table: &Table, rename: &dyn Fn(&str) -> String| {
+            table
+                .unique_constraints
+                .iter()
+                .map(|unique| Definition {
+                    name: unique.constraint_name.clone().or_else(|| unique.name.clone()),
+                    definition: format!("UNIQUE ({})", list(&unique.columns, rename)),
+                })
+                .collect::<Vec<_>>()
+        };
+        diff_definitions(changes, DiffObject::Unique, &table, &uniques(old, &rename), &uniques(new, &unchanged));
+
+        let checks = |table: &Table| {
+            table
+                .check_constraints
+                .iter()
+                .map(|check| Definition {
+                    name: check.constraint_name.clone().or_else(|| check.name.clone()),
+                    definition: format!("CHECK ({})", normalize(&check.expression)),
+                })
+                .collect::<Vec<_>>()
+        };
+        diff_definitions(changes, DiffObject::Check, &table, &checks(old), &checks(new));
+
+        let exclusions = |table: &Table| {
+            table
+                .exclusion_constraints
+                .iter()
+                .map(|exclusion| {
+                    let elements: Vec<String> = exclusion
+                        .elements
+                        .iter()
+                        .map(|element| format!("{} WITH {}", normalize(&element.expression), element.operator))
+                        .collect();
+                    let mut definition =
+                        format!("EXCLUDE USING {} ({})", index_method(&exclusion.index_type), elements.join(", "));
+                    if let Some(predicate) = &exclusion.where_clause {
+                        definition.push_str(&format!(" WHERE ({})", normalize(predicate)));
+                    }
+                    Definition { name: exclusion.name.clone(), definition }
+                })
+                .collect::<Vec<_>>()
+        };
+        diff_definitions(changes, DiffObject::Exclusion, &table, &exclusions(old), &exclusions(new));
+
+        let indexes = |table: &Table, rename: &dyn Fn(&str) -> String| {
+            table
+                .indexes
+                .iter()
+                .map(|index| {
+                    let columns: Vec<String> = index.columns.iter().map(|column| column.name.clone()).collect();
+                    let mut definition = format!(
+                        "{}INDEX USING {} ({})",
+                        if index.unique { "UNIQUE " } else { "" },
+                        index_method(&index.index_type),
+                        list(&columns, rename)
+                    );
+                    if let Some(predicate) = &index.where_clause {
+                        definition.push_str(&format!(" WHERE {}", normalize(predicate)));
+                    }
+                    Definition { name: Some(index.name.clone()), definition }
+                })
+                .collect::<Vec<_>>()
+        };
+        diff_definitions(changes, DiffObject::Index, &table, &indexes(old, &rename), &indexes(new, &unchanged));
+
+        let triggers = |table: &Table| table.triggers.iter().map(trigger_definition).collect::<Vec<_>>();
+        diff_definitions(changes, DiffObject::Trigger, &table, &triggers(old), &triggers(new));
+    }
+
+    fn diff_views(&self, changes: &mut Vec<SchemaChange>) {
+        let old = self.old.get_all_views();
+        let new = self.new.get_all_views();
+        let name = |view: &&View| qualified(&view.schema, &view.name);
+        let pairing = pair(
+            &old,
+            &new,
+            &[&name, &|view: &&View| view.name.clone()],
+            &|old: &&View, new: &&View| same(&normalize(&old.definition), &normalize(&new.definition)),
+        );
+        for (pairing, view) in pairing.iter().zip(&new) {
+            let definition = normalize(&view.definition);
+            match pairing {
+                None => {
+                    changes.push(SchemaChange::new(ChangeAction::Added, DiffObject::View, name(view)).with_new(definition));
+                }
+                Some(pairing) => {
+                    let previous = old[pairing.old];
+                    if pairing.renamed {
+                        let change = SchemaChange::new(ChangeAction::Renamed, DiffObject::View, name(view));
+                        changes.push(change.with_old(name(&previous)));
+                    } else if normalize(&previous.definition) != definition {
+                        changes.push(SchemaChange::modified(
+                            DiffObject::View,
+                            &name(view),
+                            DiffAttribute::Definition,
+                            Some(normalize(&previous.definition)),
+                            Some(definition),
+                        ));
+                    }
+                }
+            }
+        }
+        for view in unpaired(&old, &pairing) {
+            changes.push(SchemaChange::new(ChangeAction::Removed, DiffObject::View, name(view)));
+        }
+    }
+}
+
+fn diff_column(changes: &mut Vec<SchemaChange>, name: &str, old: &Column, new: &Column) {
+    let mut compare = |attribute: DiffAttribute, old: Option<String>, new: Option<String>| {
+        if old != new {
+            changes.push(SchemaChange::modified(DiffObject::Column, name, attribute, old, new));
+        }
+    };
+    compare(DiffAttribute::Type, Some(old.data_type.to_sql()), Some(new.data_type.to_sql()));
+    compare(DiffAttribute::Nullability, Some(nullability(old).to_string()), Some(nullability(new).to_string()));
+    compare(DiffAttribute::Default, old.default_value.as_deref().map(normalize), new.default_value.as_deref().map(normalize));
+    compare(DiffAttribute::AutoIncrement, Some(old.auto_increment.to_string()), Some(new.auto_increment.to_string()));
+    compare(
+        DiffAttribute::Generated,
+        old.generated.as_ref().map(|generated| normalize(&generated.expression)),
+        new.generated.as_ref().map(|generated| normalize(&generated.expression)),
+    );
+    compare(DiffAttribute::Collation, old.collation.clone(), new.collation.clone());
+}
+
+/// Compares keys, indexes or triggers of one table: paired by name, else by identical definition.
+fn diff_definitions(changes: &mut Vec<SchemaChange>, object: DiffObject, table: &str, old: &[Definition], new: &[Definition]) {
+    let key = |definition: &Definition| definition.name.clone().unwrap_or_default();
+    let pairing = pair(old, new, &[&key], &|old: &Definition, new: &Definition| same(&old.definition, &new.definition));
+    let name = |definition: &Definition| match &definition.name {
+        Some(name) => format!("{}.{}", table, name),
+        None => table.to_string(),
+    };
+
+    for (pairing, definition) in pairing.iter().zip(new) {
+        let Some(pairing) = pairing else {
+            changes.push(SchemaChange::new(ChangeAction::Added, object, name(definition)).with_new(&definition.definition));
+            continue;
+        };
+        let previous = &old[pairing.old];
+        if previous.definition != definition.definition {
+            changes.push(SchemaChange::modified(
+                object,
+                &name(definition),
+                DiffAttribute::Definition,
+                Some(previous.definition.clone()),
+                Some(definition.definition.clone()),
+            ));
+        } else if let (Some(before), Some(after)) = (&previous.name, &definition.name)
+            && !before.eq_ignore_ascii_case(after)
+        {
+            changes.push(SchemaChange::new(ChangeAction::Renamed, object, name(definition)).with_old(name(previous)));
+        }
+    }
+    for definition in unpaired(old, &pairing) {
+        changes.push(SchemaChange::new(ChangeAction::Removed, object, name(definition)).with_old(&definition.definition));
+    }
+}
+
+fn diff_routines(changes: &mut Vec<SchemaChange>, object: DiffObject, old: &[&Procedure], new: &[&Procedure]) {
+    let name = |routine: &&Procedure| qualified(&routine.schema_name, &routine.name);
+    let pairing = pair(
+        old,
+        new,
+        &[&name, &|routine: &&Procedure| routine.name.clone()],
+        &|old: &&Procedure, new: &&Procedure| same(&routine_definition(old), &routine_definition(new)),
+    );
+    for (pairing, routine) in pairing.iter().zip(new) {
+        let definition = routine_definition(routine);
+        match pairing {
+            None => changes.push(SchemaChange::new(ChangeAction::Added, object, name(routine)).with_new(definition)),
+            Some(pairing) => {
+                let previous = old[pairing.old];
+                if pairing.renamed {
+                    changes.push(SchemaChange::new(ChangeAction::Renamed, object, name(routine)).with_old(name(&previous)));
+                } else if routine_definition(previous) != definition {
+                    changes.push(SchemaChange::modified(
+                        object,
+                        &name(routine),
+                        DiffAttribute::Definition,
+                        Some(routine_definition(previous)),
+                        Some(definition),
+                    ));
+                }
+            }
+        }
+    }
+    for routine in unpaired(old, &pairing) {
+        changes.push(SchemaChange::new(ChangeAction::Removed, object, name(routine)));
+    }
+}
+
+/// Which old object a new one was paired with, and whether only by similarity.
+#[derive(Debug, Clone, Copy)]
+struct Pairing {
+    old: usize,
+    renamed: bool,
+}
+
+/// Pairs each new object with an old one: by each key in turn, case-insensitively, where the
+/// key picks out one unpaired object on each side, and then, as renames, by the highest
+/// similarity that reaches [`RENAME_THRESHOLD`]. An empty key pairs nothing.
+fn pair<T>(
+    old: &[T],
+    new: &[T],
+    keys: &[&dyn Fn(&T) -> String],
+    similarity: &dyn Fn(&T, &T) -> f64,
+) -> Vec<Option<Pairing>> {
+    let mut pairing: Vec<Option<Pairing>> = vec![None; new.len()];
+    let mut taken = vec![false; old.len()];
+
+    for key in keys {
+        let mut unpaired_old: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, item) in old.iter().enumerate().filter(|(index, _)| !taken[*index]) {
+            unpaired_old.entry(key(item).to_lowercase()).or_default().push(index);
+        }
+        let mut unpaired_new: HashMap<String, usize> = HashMap::new();
+        for (item, _) in new.iter().zip(&pairing).filter(|(_, pairing)| pairing.is_none()) {
+            *unpaired_new.entry(key(item).to_lowercase()).or_default() += 1;
+        }
+        for (index, item) in new.iter().enumerate() {
+            let name = key(item).to_lowercase();
+            if pairing[index].is_some() || name.is_empty() || unpaired_new.get(&name) != Some(&1) {
+                continue;
+            }
+            if let Some([only]) = unpaired_old.get(&name).map(Vec::as_slice) {
+                pairing[index] = Some(Pairing { old: *only, renamed: false });
+                taken[*only] = true;
+            }
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for (new_index, item) in new.iter().enumerate().filter(|(index, _)| pairing[*index].is_none()) {
+        for (old_index, previous) in old.iter().enumerate().filter(|(index, _)| !taken[*index]) {
+            let score = similarity(previous, item);
+            if score >= RENAME_THRESHOLD {
+                candidates.push((score, new_index, old_index));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (_, new_index, old_index) in candidates {
+        if pairing[new_index].is_none() && !taken[old_index] {
+            pairing[new_index] = Some(Pairing { old: old_index, renamed: true });
+            taken[old_index] = true;
+        }
+    }
+    pairing
+}
+
+fn unpaired<'t, T>(old: &'t [T], pairing: &[Option<Pairing>]) -> Vec<&'t T> {
+    let taken: HashSet<usize> = pairing.iter().flatten().map(|pairing| pairing.old).collect();
+    old.iter().enumerate().filter(|(index, _)| !taken.contains(index)).map(|(_, item)| item).collect()
+}
+
+fn pair_columns(old: &Table, new: &Table) -> Vec<Option<Pairing>> {
+    let old_columns: Vec<(usize, &Column)> = old.columns.iter().enumerate().collect();
+    let new_columns: Vec<(usize, &Column)> = new.columns.iter().enumerate().collect();
+    pair(&old_columns, &new_columns, &[&|(_, column): &(usize, &Column)| column.name.clone()], &|old, new| {
+        column_similarity(old, new)
+    })
+}
+
+/// Mostly the share of columns the tables have in common, helped by how alike their names are.
+fn table_similarity(old: &Table, new: &Table) -> f64 {
+    let names = |table: &Table| -> HashSet<String> { table.columns.iter().map(|column| column.name.to_lowercase()).collect() };
+    let (old_names, new_names) = (names(old), names(new));
+    let union = old_names.union(&new_names).count();
+    let shared = if union == 0 { 0.0 } else { old_names.intersection(&new_names).count() as f64 / union as f64 };
+    0.4 * name_similarity(&old.name, &new.name) + 0.6 * shared
+}
+
+/// Half name likeness; the rest for keeping the type, the nullability and default, and the position.
+fn column_similarity((old_position, old): &(usize, &Column), (new_position, new): &(usize, &Column)) -> f64 {
+    let mut score = 0.5 * name_similarity(&old.name, &new.name);
+    if old.data_type == new.data_type {
+        score += 0.3;
+    }
+    if old.nullable == new.nullable && old.default_value == new.default_value {
+        score += 0.1;
+    }
+    if old_position == new_position {
+        score += 0.1;
+    }
+    score
+}
+
+/// The Sørensen–Dice coefficient of the names' letter pairs.
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let bigrams = |name: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = name.to_lowercase().chars().collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    if a.eq_ignore_ascii_case(b) {
+        return 1.0;
+    }
+    let (a, mut b) = (bigrams(a), bigrams(b));
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 0.0;
+    }
+    let mut shared = 0;
+    for bigram in a {
+        if let Some(position) = b.iter().position(|other| *other == bigram) {
+            b.swap_remove(position);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f64 / total as f64
+}
+
+fn same(a: &str, b: &str) -> f64 {
+    if a == b { 1.0 } else { 0.0 }
+}
+
+fn column_definition(column: &Column) -> String {
+    let mut definition = column.data_type.to_sql();
+    if !column.nullable {
+        definition.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default_value {
+        definition.push_str(&format!(" DEFAULT {}", normalize(default)));
+    }
+    definition
+}
+
+fn nullability(column: &Column) -> &'static str {
+    if column.nullable { "NULL" } else { "NOT NULL" }
+}
+
+fn type_definition(user_type: &UserDefinedType) -> String {
+    match &user_type.kind {
+        UserDefinedTypeKind::Enum { values } => {
+            let values: Vec<String> = values.iter().map(|value| format!("'{}'", value.replace('\'', "''"))).collect();
+            format!("ENUM ({})", values.join(", "))
+        }
+        UserDefinedTypeKind::Domain { base_type, nullable, default_value, checks } => {
+            let mut definition = base_type.to_sql();
+            if !nullable {
+                definition.push_str(" NOT NULL");
+            }
+            if let Some(default) = default_value {
+                definition.push_str(&format!(" DEFAULT {}", normalize(default)));
+            }
+            for check in checks {
+                definition.push_str(&format!(" CHECK ({})", normalize(check)));
+            }
+            definition
+        }
+        UserDefinedTypeKind::Composite { attributes } => {
+            let attributes: Vec<String> =
+                attributes.iter().map(|attribute| format!("{} {}", attribute.name, attribute.data_type.to_sql())).collect();
+            format!("({})", attributes.join(", "))
+        }
+    }
+}
+
+fn trigger_definition(trigger: &Trigger) -> Definition {
+    let events: Vec<String> = trigger
+        .events
+        .iter()
+        .map(|event| match event {
+            TriggerEvent::Update if !trigger.update_columns.is_empty() => {
+                format!("UPDATE OF {}", trigger.update_columns.join(", "))
+            }
+            _ => format!("{:?}", event).to_uppercase(),
+        })
+        .collect();
+    Definition {
+        name: Some(trigger.name.clone()),
+        definition: format!(
+            "{} {} FOR EACH {} {}",
+            format!("{:?}", trigger.timing).to_uppercase(),
+            events.join(" OR "),
+            format!("{:?}", trigger.scope).to_uppercase(),
+            normalize(&trigger.definition)
+        ),
+    }
+}
+
+fn routine_definition(routine: &Procedure) -> String {
+    let parameters: Vec<String> = routine
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let mode = match parameter.parameter_mode {
+                ParameterMode::In => "",
+                ParameterMode::Out => "OUT ",
+                ParameterMode::InOut => "INOUT ",
+            };
+            format!("{}{} {}", mode, parameter.name, parameter.data_type)
+        })
+        .collect();
+    format!("({}) {}", parameters.join(", "), normalize(&routine.definition))
+}
+
+fn action_sql(action: &ReferentialAction) -> &'static str {
+    match action {
+        ReferentialAction::NoAction => "NO ACTION",
+        ReferentialAction::Restrict => "RESTRICT",
+        ReferentialAction::Cascade => "CASCADE",
+        ReferentialAction::SetNull => "SET NULL",
+        ReferentialAction::SetDefault => "SET DEFAULT",
+    }
+}
+
+fn index_method(index_type: &IndexType) -> String {
+    match index_type {
+        IndexType::BTree => "btree".to_string(),
+        IndexType::Hash => "hash".to_string(),
+        IndexType::Gist => "gist".to_string(),
+        IndexType::Gin => "gin".to_string(),
+        IndexType::SpGist => "spgist".to_string(),
+        IndexType::Brin => "brin".to_string(),
+        IndexType::FullText => "fulltext".to_string(),
+        IndexType::Spatial => "spatial".to_string(),
+        IndexType::Custom(name) => name.to_lowercase(),
+    }
+}
+
+fn list(columns: &[String], rename: &dyn Fn(&str) -> String) -> String {
+    columns.iter().map(|column| rename(column)).collect::<Vec<_>>().join(", ")
+}
+
+/// SQL text with its layout dropped, so re-indenting a view doesn't count as changing it.
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ").trim_end_matches(';').trim_end().to_string()
+}
+
+fn qualified(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+fn display(table: &Table) -> String {
+    qualified(&table.schema, &table.name)
+}
@@ -0,0 +1,368 @@
+use crate::core::ast::{AlterTableAction, ColumnConstraint, ColumnDefinition, DdlStatement, Statement, TableConstraint};
+use crate::core::domain::entity::{Database, MigrationChange, MigrationEvent};
+use crate::core::lower::{LowerOutput, Lowerer};
+use crate::core::parse::SqlDialect;
+
+/// Replays a migrations directory to find which migration created each table, column, index
+/// and constraint, and which ones later renamed or altered it. Objects are followed through
+/// renames; one that is dropped and created again starts its history afresh. Keys declared
+/// without a name are told apart by their columns, or a check by its expression.
+pub struct MigrationHistory {
+    lowerer: Lowerer,
+}
+
+#[derive(Default)]
+struct Replay {
+    tables: Vec<TableHistory>,
+}
+
+struct TableHistory {
+    schema: Option<String>,
+    name: String,
+    events: Vec<MigrationEvent>,
+    columns: Vec<ColumnHistory>,
+    keys: Vec<KeyHistory>,
+}
+
+struct ColumnHistory {
+    /// The definition as it stands, to tell what a MySQL `MODIFY` changes
+    definition: ColumnDefinition,
+    events: Vec<MigrationEvent>,
+}
+
+struct KeyHistory {
+    name: Option<String>,
+    kind: KeyKind,
+    columns: Vec<String>,
+    event: MigrationEvent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum KeyKind {
+    Index,
+    Unique,
+    ForeignKey,
+    Check { expression: String },
+    Exclusion,
+}
+
+impl MigrationHistory {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self { lowerer: Lowerer::new(dialect) }
+    }
+
+    pub fn with_database_name(mut self, name: &str) -> Self {
+        self.lowerer = self.lowerer.with_database_name(name);
+        self
+    }
+
+    /// Lowers the migrations, each named with its statements and given in the order they run,
+    /// as one script, and records each object's history in its metadata.
+    pub fn lower(&self, migrations: &[(String, Vec<Statement>)]) -> LowerOutput {
+        let statements: Vec<Statement> = migrations.iter().flat_map(|(_, statements)| statements.iter().cloned()).collect();
+        let mut output = self.lowerer.lower_statements(&statements);
+
+        let names = migrations.iter().flat_map(|(name, statements)| statements.iter().map(move |_| name.as_str()));
+        let scopes = self.lowerer.schema_scopes(&statements);
+        let mut replay = Replay::default();
+        for ((statement, scope), migration) in statements.iter().zip(&scopes).zip(names) {
+            if let Statement::Ddl(statement) = statement {
+                replay.statement(&self.lowerer, statement, scope, migration);
+            }
+        }
+        replay.apply(&self.lowerer, &mut output.database);
+        output
+    }
+}
+
+impl Replay {
+    fn statement(&mut self, lowerer: &Lowerer, statement: &DdlStatement, scope: &Option<String>, migration: &str) {
+        let event = |change: MigrationChange| MigrationEvent { migration: migration.to_string(), change };
+        match statement {
+            DdlStatement::CreateTable(create) => {
+                let schema = lowerer.schema_of(&create.table_name, scope);
+                if create.if_not_exists && self.table(&schema, &create.table_name.object).is_some() {
+                    return;
+                }
+                self.forget(&schema, &create.table_name.object);
+                let mut table = TableHistory {
+                    schema,
+                    name: create.table_name.object.clone(),
+                    events: vec![event(MigrationChange::Added)],
+                    columns: Vec::new(),
+                    keys: Vec::new(),
+                };
+                for definition in &create.columns {
+                    table.add_column(definition, migration);
+                }
+                for constraint in &create.constraints {
+                    table.add_key(constraint, migration);
+                }
+                self.tables.push(table);
+            }
+            DdlStatement::CreateIndex(index) => {
+                let schema = lowerer.schema_of(&index.table_name, scope);
+                if let Some(table) = self.table(&schema, &index.table_name.object) {
+                    table.keys.retain(|key| key.name.as_ref() != Some(&index.index_name));
+                    table.keys.push(KeyHistory {
+                        name: Some(index.index_name.clone()),
+                        kind: KeyKind::Index,
+                        columns: index.columns.iter().map(|part| part.column.clone()).collect(),
+                        event: event(MigrationChange::Added),
+                    });
+                }
+            }
+            DdlStatement::AlterTable(alter) => {
+                let schema = lowerer.schema_of(&alter.table_name, scope);
+                if let Some(table) = self.table(&schema, &alter.table_name.object) {
+                    for action in &alter.actions {
+                        table.alter(action, migration);
+                    }
+                }
+            }
+            DdlStatement::DropTable(drop) => {
+                for name in &drop.table_names {
+                    self.forget(&lowerer.schema_of(name, scope), &name.object);
+                }
+            }
+            DdlStatement::DropIndex(drop) => {
+                for table in &mut self.tables {
+                    table.keys.retain(|key| !key.name.as_ref().is_some_and(|name| drop.index_names.contains(name)));
+                }
+            }
+            DdlStatement::CreateType(_)
+            | DdlStatement::CreateDomain(_)
+            | DdlStatement::CreateSequence(_)
+            | DdlStatement::CreateSchema(_)
+            | DdlStatement::CreateView(_)
+            | DdlStatement::CreateTrigger(_)
+            | DdlStatement::CreateRoutine(_) => {}
+        }
+    }
+
+    /// The table of that name in the schema, or else the only one of that name, as the lowerer finds it.
+    fn table(&mut self, schema: &Option<String>, name: &str) -> Option<&mut TableHistory> {
+        let position = self
+            .tables
+            .iter()
+            .position(|table| table.schema == *schema && table.name == name)
+            .or_else(|| self.tables.iter().position(|table| table.name == name))?;
+        self.tables.get_mut(position)
+    }
+
+    fn forget(&mut self, schema: &Option<String>, name: &str) {
+        self.tables.retain(|table| table.schema != *schema || table.name != name);
+    }
+
+    fn apply(&self, lowerer: &Lowerer, database: &mut Database) {
+        for schema in &mut database.schemas {
+            let schema_name = schema.name.as_deref().and_then(|name| lowerer.normalize_schema(name));
+            for table in &mut schema.tables {
+                let Some(history) = self.tables.iter().find(|history| history.schema == schema_name && history.name == table.name)
+                else {
+                    continue;
+                };
+                table.metadata.history = history.events.clone();
+                for column in &mut table.columns {
+                    if let Some(found) = history.columns.iter().find(|found| found.definition.name == column.name) {
+                        column.metadata.history = found.events.clone();
+                    }
+                }
+
+                for index in &mut table.indexes {
+                    let columns: Vec<String> = index.columns.iter().map(|column| column.name.clone()).collect();
+                    index.metadata.history = history.key(&[Some(&index.name)], KeyKind::Index, &columns);
+                }
+                for foreign_key in &mut table.foreign_keys {
+                    let names = [foreign_key.constraint_name.as_ref(), foreign_key.name.as_ref()];
+                    foreign_key.metadata.history = history.key(&names, KeyKind::ForeignKey, &foreign_key.columns);
+                }
+                for unique in &mut table.unique_constraints {
+                    let names = [unique.constraint_name.as_ref(), unique.name.as_ref()];
+                    unique.metadata.history = history.key(&names, KeyKind::Unique, &unique.columns);
+                }
+                for check in &mut table.check_constraints {
+                    let names = [check.constraint_name.as_ref(), check.name.as_ref()];
+                    let kind = KeyKind::Check { expression: check.expression.clone() };
+                    check.metadata.history = history.key(&names, kind, &[]);
+                }
+                for exclusion in &mut table.exclusion_constraints {
+                    exclusion.metadata.history = history.key(&[exclusion.name.as_ref()], KeyKind::Exclusion, &[]);
+                }
+            }
+        }
+    }
+}
+
+impl TableHistory {
+    fn alter(&mut self, action: &AlterTableAction, migration: &str) {
+        let event = |change: MigrationChange| MigrationEvent { migration: migration.to_string(), change };
+        match action {
+            AlterTableAction::AddColumn { column } => self.add_column(column, migration),
+            AlterTableAction::DropColumn { column_name, .. } => {
+                self.columns.retain(|column| column.definition.name != *column_name);
+                self.keys.retain(|key| !key.columns.contains(column_name));
+            }
+            AlterTableAction::AlterColumn { column_name, new_data_type, add_constraints, drop_constraints } => {
+                let Some(column) = self.column(column_name) else {
+                    return;
+                };
+                if let Some(data_type) = new_data_type {
+                    column.definition.data_type = data_type.clone();
+                    column.record(event(MigrationChange::TypeChanged));
+                }
+                for constraint in add_constraints {
+                    match constraint {
+                        ColumnConstraint::NotNull => column.record(event(MigrationChange::MadeNotNull)),
+                        ColumnConstraint::Default(_) => column.record(event(MigrationChange::DefaultChanged)),
+                        _ => column.record(event(MigrationChange::Altered)),
+                    }
+                    column.definition.constraints.push(constraint.clone());
+                }
+                for constraint in drop_constraints {
+                    match constraint.as_str() {
+                        "NOT NULL" => column.record(event(MigrationChange::MadeNullable)),
+                        "DEFAULT" => column.record(event(MigrationChange::DefaultChanged)),
+                        _ => column.record(event(MigrationChange::Altered)),
+                    }
+                }
+            }
+            AlterTableAction::ModifyColumn { column: definition } => {
+                let Some(column) = self.column(&definition.name) else {
+                    return;
+                };
+                let before = column.definition.clone();
+                let mut changes = Vec::new();
+                if before.data_type != definition.data_type {
+                    changes.push(MigrationChange::TypeChanged);
+                }
+                if default(&before) != default(definition) {
+                    changes.push(MigrationChange::DefaultChanged);
+                }
+                match (not_null(&before), not_null(definition)) {
+                    (false, true) => changes.push(MigrationChange::MadeNotNull),
+                    (true, false) => changes.push(MigrationChange::MadeNullable),
+                    _ => {}
+                }
+                if changes.is_empty() && before.constraints != definition.constraints {
+                    changes.push(MigrationChange::Altered);
+                }
+                column.definition = definition.clone();
+                for change in changes {
+                    column.record(event(change));
+                }
+                self.add_inline_keys(definition, migration);
+            }
+            AlterTableAction::RenameColumn { old_name, new_name } => {
+                let Some(column) = self.column(old_name) else {
+                    return;
+                };
+                column.definition.name = new_name.clone();
+                column.record(event(MigrationChange::Renamed { from: old_name.clone() }));
+                for name in self.keys.iter_mut().flat_map(|key| key.columns.iter_mut()).filter(|name| *name == old_name) {
+                    *name = new_name.clone();
+                }
+            }
+            AlterTableAction::AddConstraint { constraint, .. } => self.add_key(constraint, migration),
+            AlterTableAction::DropConstraint { constraint_name, .. } => {
+                self.keys.retain(|key| key.name.as_ref() != Some(constraint_name));
+            }
+            AlterTableAction::RenameTable { new_name } => {
+                let from = std::mem::replace(&mut self.name, new_name.clone());
+                self.events.push(event(MigrationChange::Renamed { from }));
+            }
+            AlterTableAction::ConstraintChecking { .. } | AlterTableAction::Other(_) => {}
+        }
+    }
+
+    fn column(&mut self, name: &str) -> Option<&mut ColumnHistory> {
+        self.columns.iter_mut().find(|column| column.definition.name == name)
+    }
+
+    fn add_column(&mut self, definition: &ColumnDefinition, migration: &str) {
+        self.columns.retain(|column| column.definition.name != definition.name);
+        self.columns.push(ColumnHistory {
+            definition: definition.clone(),
+            events: vec![MigrationEvent { migration: migration.to_string(), change: MigrationChange::Added }],
+        });
+        self.add_inline_keys(definition, migration);
+    }
+
+    /// The keys a column declares inline, which the lowerer leaves unnamed.
+    fn add_inline_keys(&mut self, definition: &ColumnDefinition, migration: &str) {
+        for constraint in &definition.constraints {
+            let kind = match constraint {
+                ColumnConstraint::Unique => KeyKind::Unique,
+                ColumnConstraint::ForeignKey { .. } => KeyKind::ForeignKey,
+                ColumnConstraint::Check(expression) => KeyKind::Check { expression: expression.clone() },
+                _ => continue,
+            };
+            let columns = match kind {
+                KeyKind::Check { .. } => Vec::new(),
+                _ => vec![definition.name.clone()],
+            };
+            self.push_key(None, kind, columns, migration);
+        }
+    }
+
+    fn add_key(&mut self, constraint: &TableConstraint, migration: &str) {
+        let (name, kind, columns) = match constraint {
+            TableConstraint::PrimaryKey { .. } => return,
+            TableConstraint::Unique { name, columns, .. } => (name.clone(), KeyKind::Unique, columns.clone()),
+            TableConstraint::ForeignKey { name, columns, .. } => (name.clone(), KeyKind::ForeignKey, columns.clone()),
+            TableConstraint::Check { name, expression } => {
+                (name.clone(), KeyKind::Check { expression: expression.clone() }, Vec::new())
+            }
+            TableConstraint::Index { name, columns, .. } => {
+                let columns: Vec<String> = columns.iter().map(|part| part.column.clone()).collect();
+                // MySQL names an anonymous key after its first column
+                (name.clone().or_else(|| columns.first().cloned()), KeyKind::Index, columns)
+            }
+            TableConstraint::Exclude { name, .. } => (name.clone(), KeyKind::Exclusion, Vec::new()),
+        };
+        self.push_key(name, kind, columns, migration);
+    }
+
+    fn push_key(&mut self, name: Option<String>, kind: KeyKind, columns: Vec<String>, migration: &str) {
+        if name.is_some() {
+            self.keys.retain(|key| key.name != name);
+        }
+        let event = MigrationEvent { migration: migration.to_string(), change: MigrationChange::Added };
+        self.keys.push(KeyHistory { name, kind, columns, event });
+    }
+
+    /// The history of the key going by one of `names`, or else of the unnamed one of its kind
+    /// on the same columns.
+    fn key(&self, names: &[Option<&String>], kind: KeyKind, columns: &[String]) -> Vec<MigrationEvent> {
+        let names: Vec<&String> = names.iter().flatten().copied().collect();
+        let found = if names.is_empty() {
+            self.keys.iter().rev().find(|key| key.name.is_none() && key.kind == kind && key.columns == columns)
+        } else {
+            self.keys.iter().rev().find(|key| key.name.as_ref().is_some_and(|name| names.contains(&name)))
+        };
+        found.map(|key| vec![key.event.clone()]).unwrap_or_default()
+    }
+}
+
+impl ColumnHistory {
+    /// Notes a change once, however many clauses of one migration make it.
+    fn record(&mut self, event: MigrationEvent) {
+        if !self.events.contains(&event) {
+            self.events.push(event);
+        }
+    }
+}
+
+fn default(definition: &ColumnDefinition) -> Option<&String> {
+    definition.constraints.iter().find_map(|constraint| match constraint {
+        ColumnConstraint::Default(value) => Some(value),
+        _ => None,
+    })
+}
+
+fn not_null(definition: &ColumnDefinition) -> bool {
+    definition
+        .constraints
+        .iter()
+        .any(|constraint| matches!(constraint, ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey))
+}
@@ -0,0 +1,251 @@
+use crate::core::ast::LiteralValue;
+use crate::core::domain::entity::DataType;
+use crate::core::parse::SqlDialect;
+
+/// What an expression is known to produce: its type, where it can be told, and whether it may be NULL.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Typed {
+    pub data_type: Option<DataType>,
+    pub nullable: bool,
+}
+
+impl Typed {
+    /// Nothing is known, as for a bind parameter or an unknown function.
+    pub fn unknown() -> Self {
+        Self { data_type: None, nullable: true }
+    }
+
+    pub fn new(data_type: DataType, nullable: bool) -> Self {
+        Self { data_type: Some(data_type), nullable }
+    }
+
+    pub fn not_null(data_type: DataType) -> Self {
+        Self::new(data_type, false)
+    }
+}
+
+pub(crate) fn literal(literal: &LiteralValue) -> Typed {
+    match literal {
+        LiteralValue::Null => Typed::unknown(),
+        // A quoted literal takes its type from where it is used
+        LiteralValue::String(_) => Typed { data_type: None, nullable: false },
+        LiteralValue::Boolean(_) => Typed::not_null(DataType::Boolean),
+        LiteralValue::Integer(value) if i32::try_from(*value).is_ok() => Typed::not_null(DataType::Integer { unsigned: false }),
+        LiteralValue::Integer(_) => Typed::not_null(DataType::BigInt { unsigned: false }),
+        LiteralValue::Float(_) => Typed::not_null(DataType::Decimal { precision: None, scale: None }),
+        LiteralValue::Binary(_) => Typed::not_null(DataType::Blob),
+    }
+}
+
+/// The result of a built-in function, aggregate or window function. `grouped` says whether
+/// the query has a GROUP BY, without which an aggregate over no rows gives NULL.
+pub(crate) fn function(dialect: &SqlDialect, name: &str, args: &[Typed], grouped: bool) -> Typed {
+    let first = args.first().and_then(|arg| arg.data_type.clone());
+    let any_null = args.iter().any(|arg| arg.nullable);
+    let all_null = args.iter().all(|arg| arg.nullable);
+    // Most functions return NULL when any argument is NULL
+    let strict = |data_type: DataType| Typed::new(data_type, any_null);
+    let aggregate = |data_type: Option<DataType>| Typed { data_type, nullable: any_null || !grouped };
+
+    match name.to_lowercase().as_str() {
+        "count" => Typed::not_null(DataType::BigInt { unsigned: false }),
+        "sum" => aggregate(first.map(|data_type| sum_type(dialect, data_type))),
+        "avg" => aggregate(first.map(|data_type| avg_type(dialect, data_type))),
+        "min" | "max" => aggregate(first),
+        "stddev" | "stddev_pop" | "stddev_samp" | "variance" | "var_pop" | "var_samp" => Typed::new(DataType::Double, true),
+        "array_agg" => Typed {
+            data_type: first.map(|element| DataType::Array { element_type: Box::new(element), dimensions: None }),
+            nullable: !grouped,
+        },
+        "string_agg" | "group_concat" | "listagg" => Typed::new(DataType::Text, true),
+        "bool_and" | "bool_or" | "every" => aggregate(Some(DataType::Boolean)),
+        "json_agg" | "json_arrayagg" | "json_objectagg" => Typed::new(DataType::Json, !grouped),
+        "jsonb_agg" => Typed::new(DataType::JsonB, !grouped),
+
+        "row_number" | "rank" | "dense_rank" | "ntile" => Typed::not_null(DataType::BigInt { unsigned: false }),
+        "percent_rank" | "cume_dist" => Typed::not_null(DataType::Double),
+        "lag" | "lead" | "first_value" | "last_value" | "nth_value" => Typed { data_type: first, nullable: true },
+
+        "coalesce" | "ifnull" | "nvl" | "isnull" => Typed { data_type: common_type(args), nullable: all_null },
+        "nullif" => Typed { data_type: first, nullable: true },
+        // PostgreSQL skips NULL arguments; MySQL and Oracle give NULL if there is one
+        "greatest" | "least" => Typed {
+            data_type: common_type(args),
+            nullable: if *dialect == SqlDialect::PostgreSQL { all_null } else { any_null },
+        },
+
+        "lower" | "upper" | "lcase" | "ucase" | "trim" | "ltrim" | "rtrim" | "btrim" | "substring" | "substr"
+        | "replace" | "left" | "right" | "lpad" | "rpad" | "reverse" | "repeat" | "initcap" | "md5" | "translate"
+        | "split_part" | "regexp_replace" | "to_char" | "format" | "quote_ident" | "quote_literal" => {
+            strict(DataType::Text)
+        }
+        // Only MySQL's CONCAT gives NULL for a NULL argument
+        "concat" => Typed::new(DataType::Text, *dialect == SqlDialect::MySQL && any_null),
+        "concat_ws" => Typed::new(DataType::Text, args.first().is_none_or(|separator| separator.nullable)),
+        "length" | "char_length" | "character_length" | "octet_length" | "bit_length" | "len" | "datalength"
+        | "position" | "strpos" | "instr" | "charindex" | "locate" | "ascii" => strict(DataType::Integer { unsigned: false }),
+
+        "abs" | "round" | "floor" | "ceil" | "ceiling" | "trunc" | "truncate" | "sign" | "mod" => Typed {
+            data_type: first.filter(DataType::is_numeric),
+            nullable: any_null,
+        },
+        "sqrt" | "cbrt" | "power" | "pow" | "exp" | "ln" | "log" | "log10" | "log2" | "sin" | "cos" | "tan" | "atan"
+        | "atan2" | "degrees" | "radians" | "pi" => strict(DataType::Double),
+        "random" | "rand" => Typed::not_null(DataType::Double),
+
+        "now" | "current_timestamp" | "transaction_timestamp" | "statement_timestamp" | "clock_timestamp" => {
+            Typed::not_null(match dialect {
+                SqlDialect::PostgreSQL => DataType::DateTimeOffset,
+                SqlDialect::MySQL | SqlDialect::MSSQL => DataType::DateTime,
+                _ => DataType::Timestamp,
+            })
+        }
+        "getdate" | "getutcdate" => Typed::not_null(DataType::DateTime),
+        "sysdatetime" | "sysutcdatetime" => Typed::not_null(DataType::DateTime2),
+        "sysdatetimeoffset" => Typed::not_null(DataType::DateTimeOffset),
+        "curdate" | "current_date" | "utc_date" => Typed::not_null(DataType::Date),
+        "curtime" | "current_time" | "utc_time" => Typed::not_null(DataType::Time),
+        "date_trunc" => strict(args.get(1).and_then(|arg| arg.data_type.clone()).unwrap_or(DataType::Timestamp)),
+        "date_part" | "extract" => strict(DataType::Double),
+        "to_date" => strict(DataType::Date),
+        "to_timestamp" => strict(DataType::DateTimeOffset),
+        "to_number" => strict(DataType::Decimal { precision: None, scale: None }),
+        "datediff" | "timestampdiff" => strict(DataType::Integer { unsigned: false }),
+        "dateadd" | "date_add" | "date_sub" | "adddate" | "subdate" => Typed {
+            data_type: args.iter().filter_map(|arg| arg.data_type.clone()).find(DataType::is_temporal),
+            nullable: any_null,
+        },
+        // SQLite keeps dates as text, and gives NULL for input it can't read
+        "date" | "time" | "datetime" | "strftime" if *dialect == SqlDialect::SQLite => Typed::new(DataType::Text, true),
+        "julianday" => Typed::new(DataType::Double, true),
+        "unixepoch" | "unix_timestamp" => Typed::new(DataType::BigInt { unsigned: false }, any_null),
+        "date" => strict(DataType::Date),
+
+        "to_json" => strict(DataType::Json),
+        "to_jsonb" => strict(DataType::JsonB),
+        "json_build_object" | "json_build_array" | "json_object" | "json_array" => Typed::not_null(DataType::Json),
+        "jsonb_build_object" | "jsonb_build_array" => Typed::not_null(DataType::JsonB),
+        "json_extract" if *dialect == SqlDialect::MySQL => Typed::new(DataType::Json, true),
+        "json_extract_path_text" | "jsonb_extract_path_text" | "json_unquote" | "json_value" => Typed::new(DataType::Text, true),
+
+        "gen_random_uuid" | "uuid_generate_v4" | "newid" | "newsequentialid" => Typed::not_null(DataType::Uuid),
+        "uuid" if *dialect == SqlDialect::MySQL => Typed::not_null(DataType::Char { length: Some(36) }),
+        _ => Typed::unknown(),
+    }
+}
+
+/// What SUM over a column of `data_type` returns.
+fn sum_type(dialect: &SqlDialect, data_type: DataType) -> DataType {
+    let decimal = DataType::Decimal { precision: None, scale: None };
+    match (dialect, &data_type) {
+        (SqlDialect::PostgreSQL, DataType::TinyInt { .. } | DataType::SmallInt { .. } | DataType::Integer { .. }) => {
+            DataType::BigInt { unsigned: false }
+        }
+        (SqlDialect::PostgreSQL, DataType::BigInt { .. }) => decimal,
+        (SqlDialect::MySQL, DataType::Float { .. } | DataType::Double | DataType::Real) => DataType::Double,
+        (SqlDialect::MySQL, _) if data_type.is_numeric() => decimal,
+        (SqlDialect::SQLite, _) if is_integer(&data_type) => DataType::BigInt { unsigned: false },
+        (SqlDialect::SQLite, _) => DataType::Double,
+        (SqlDialect::MSSQL, DataType::TinyInt { .. } | DataType::SmallInt { .. }) => DataType::Integer { unsigned: false },
+        _ => data_type,
+    }
+}
+
+/// What AVG over a column of `data_type` returns.
+fn avg_type(dialect: &SqlDialect, data_type: DataType) -> DataType {
+    let decimal = DataType::Decimal { precision: None, scale: None };
+    match dialect {
+        SqlDialect::SQLite => DataType::Double,
+        // SQL Server averages integers with integer division
+        SqlDialect::MSSQL => data_type,
+        _ if matches!(data_type, DataType::Float { .. } | DataType::Double | DataType::Real) => DataType::Double,
+        _ => decimal,
+    }
+}
+
+fn is_integer(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::TinyInt { .. } | DataType::SmallInt { .. } | DataType::Integer { .. } | DataType::BigInt { .. }
+    )
+}
+
+/// The type several values combine into, as the branches of a CASE or the arguments of
+/// COALESCE do: the widest number if they are all numbers, else the first type known.
+pub(crate) fn common_type(types: &[Typed]) -> Option<DataType> {
+    let known: Vec<&DataType> = types.iter().filter_map(|typed| typed.data_type.as_ref()).collect();
+    if !known.is_empty() && known.iter().all(|data_type| data_type.is_numeric()) {
+        return known.into_iter().max_by_key(|data_type| numeric_rank(data_type)).cloned();
+    }
+    known.first().map(|data_type| (*data_type).clone())
+}
+
+/// The result of `left op right` for the arithmetic operators.
+pub(crate) fn arithmetic(left: &Typed, right: &Typed) -> Typed {
+    let nullable = left.nullable || right.nullable;
+    let data_type = match (&left.data_type, &right.data_type) {
+        (Some(left), Some(right)) if left.is_numeric() && right.is_numeric() => {
+            Some(if numeric_rank(right) > numeric_rank(left) { right.clone() } else { left.clone() })
+        }
+        // date + interval and the like keep the temporal side's type
+        (Some(left), _) if left.is_temporal() => Some(left.clone()),
+        (Some(left), None) if left.is_numeric() => Some(left.clone()),
+        (None, Some(right)) if right.is_numeric() => Some(right.clone()),
+        _ => None,
+    };
+    Typed { data_type, nullable }
+}
+
+fn numeric_rank(data_type: &DataType) -> u8 {
+    match data_type {
+        DataType::TinyInt { .. } => 0,
+        DataType::SmallInt { .. } => 1,
+        DataType::Integer { .. } => 2,
+        DataType::BigInt { .. } => 3,
+        DataType::Decimal { .. } => 4,
+        DataType::Real | DataType::Float { .. } => 5,
+        _ => 6,
+    }
+}
+
+/// Broad families of types that compare with one another without a cast.
+#[derive(PartialEq)]
+enum TypeFamily {
+    Numeric,
+    Text,
+    Boolean,
+    Temporal,
+    Uuid,
+    Json,
+    Binary,
+    Array,
+}
+
+fn type_family(data_type: &DataType) -> Option<TypeFamily> {
+    if data_type.is_numeric() {
+        return Some(TypeFamily::Numeric);
+    }
+    if data_type.is_text() || matches!(data_type, DataType::Enum { .. } | DataType::Set { .. }) {
+        return Some(TypeFamily::Text);
+    }
+    if data_type.is_temporal() {
+        return Some(TypeFamily::Temporal);
+    }
+    match data_type {
+        DataType::Boolean => Some(TypeFamily::Boolean),
+        DataType::Uuid => Some(TypeFamily::Uuid),
+        DataType::Json | DataType::JsonB => Some(TypeFamily::Json),
+        DataType::Binary { .. } | DataType::VarBinary { .. } | DataType::Blob | DataType::Image => Some(TypeFamily::Binary),
+        DataType::Array { .. } => Some(TypeFamily::Array),
+        // User-defined and spatial types have their own operators
+        _ => None,
+    }
+}
+
+/// Whether values of the two types can be compared without a cast.
+pub(crate) fn comparable(left: &DataType, right: &DataType) -> bool {
+    match (type_family(left), type_family(right)) {
+        (Some(left), Some(right)) => left == right,
+        _ => true,
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::analyze::query::{output_name, search_schemas, NILADIC_FUNCTIONS};
+use crate::core::analyze::scope::{self, resolve_qualifier};
+use crate::core::ast::{DmlStatement, Expression, SchemaReference, SelectItem, SelectStatement, Statement, TableReference};
+use crate::core::domain::entity::{Database, View};
+use crate::core::lower::Lowerer;
+use crate::core::parse::{Lexer, Parser, SqlDialect};
+
+/// How many views deep a column is followed back to its tables.
+const MAX_VIEW_DEPTH: usize = 8;
+
+/// Where the columns of a view come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewLineage {
+    /// The view's name, qualified with its schema when it has one
+    pub view: String,
+    pub columns: Vec<ColumnLineage>,
+    /// False when the definition couldn't be parsed or reads a table whose columns aren't
+    /// known, so some columns or sources may be missing
+    pub complete: bool,
+}
+
+/// The base-table columns one output column is derived from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLineage {
+    pub column: String,
+    pub transformation: Transformation,
+    /// Every table column the value is computed from, in the order they are first met; empty
+    /// for a constant
+    pub sources: Vec<SourceColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceColumn {
+    /// The table's name, qualified with its schema when it has one
+    pub table: String,
+    pub column: String,
+}
+
+/// What is done to the source columns on the way to an output column. A column passed through
+/// views and CTEs unchanged keeps the transformation it was given furthest down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transformation {
+    /// The column as stored
+    Direct,
+    /// An aggregate, or an aggregate used as a window function
+    Aggregate,
+    /// Any other computation: arithmetic, a function call, a cast
+    Expression,
+    /// A CASE expression, whose conditions count among its sources
+    Case,
+}
+
+/// Traces the columns of views back through nested views, CTEs and subqueries to the columns
+/// of the tables they read.
+pub struct LineageAnalyzer<'a> {
+    database: &'a Database,
+    lowerer: Lowerer,
+}
+
+type Relation = scope::Relation<ColumnLineage>;
+
+struct Tracer<'l, 'a> {
+    analyzer: &'l LineageAnalyzer<'a>,
+    ctes: Vec<Relation>,
+    scopes: Vec<Vec<Relation>>,
+    /// The views being traced, innermost last, which guards against views defined in a cycle
+    views: Vec<String>,
+    complete: bool,
+}
+
+impl<'a> LineageAnalyzer<'a> {
+    pub fn new(database: &'a Database, dialect: SqlDialect) -> Self {
+        Self { database, lowerer: Lowerer::new(dialect) }
+    }
+
+    /// The lineage of every view in the database, in the order they are defined.
+    pub fn views(&self) -> Vec<ViewLineage> {
+        self.database.get_all_views().into_iter().map(|view| self.view(view)).collect()
+    }
+
+    pub fn view(&self, view: &View) -> ViewLineage {
+        let name = qualified(&view.schema, &view.name);
+        let mut tracer = Tracer { analyzer: self, ctes: Vec::new(), scopes: Vec::new(), views: Vec::new(), complete: true };
+        let columns = tracer.view(&name, &view.definition).unwrap_or_else(|| {
+            tracer.complete = false;
+            Vec::new()
+        });
+        ViewLineage { view: name, columns, complete: tracer.complete }
+    }
+
+    /// Finds a table or view the way [`QueryChecker`](super::QueryChecker) does.
+    fn find(&self, name: &SchemaReference) -> Option<Found<'a>> {
+        for schema in search_schemas(self.database, &self.lowerer, name) {
+            if let Some(table) = schema.tables.iter().find(|table| table.name.eq_ignore_ascii_case(&name.object)) {
+                let table_name = qualified(&table.schema, &table.name);
+                let columns = table.columns.iter().map(|column| column.name.clone()).collect();
+                return Some(Found::Table(table_name, columns));
+            }
+            if let Some(view) = schema.views.iter().find(|view| view.name.eq_ignore_ascii_case(&name.object)) {
+                return Some(Found::View(view));
+            }
+        }
+        None
+    }
+}
+
+enum Found<'a> {
+    /// A table's qualified name and its columns
+    Table(String, Vec<String>),
+    View(&'a View),
+}
+
+impl Tracer<'_, '_> {
+    /// Traces a view's definition; `None` when it isn't a query that can be parsed, or the view
+    /// is nested too deep or in itself.
+    fn view(&mut self, name: &str, definition: &str) -> Option<Vec<ColumnLineage>> {
+        if self.views.len() >= MAX_VIEW_DEPTH || self.views.iter().any(|view| view.eq_ignore_ascii_case(name)) {
+            return None;
+        }
+        let tokens = Lexer::for_dialect(definition, self.analyzer.lowerer.dialect()).tokenize().ok()?;
+        let statements = Parser::new(tokens).parse().ok()?;
+        let [Statement::Dml(dml)] = statements.as_slice() else {
+            return None;
+        };
+        let DmlStatement::Select(select) = dml.as_ref() else {
+            return None;
+        };
+
+        // The view's query sees none of the CTEs or scopes of the query reading it
+        let ctes = std::mem::take(&mut self.ctes);
+        let scopes = std::mem::take(&mut self.scopes);
+        self.views.push(name.to_string());
+        let columns = self.select(select);
+        self.views.pop();
+        self.ctes = ctes;
+        self.scopes = scopes;
+        Some(columns)
+    }
+
+    fn select(&mut self, select: &SelectStatement) -> Vec<ColumnLineage> {
+        let outer_ctes = self.ctes.len();
+        for cte in &select.with {
+            let columns = if cte.recursive {
+                // The recursive branches read the CTE as the anchor produces it
+                let anchor = SelectStatement { unions: Vec::new(), ..cte.query.clone() };
+                let columns = renamed(self.select(&anchor), &cte.columns);
+                self.ctes.push(Relation { name: cte.name.clone(), columns, opaque: false });
+                let columns = self.select(&cte.query);
+                self.ctes.pop();
+                columns
+            } else {
+                self.select(&cte.query)
+            };
+            self.ctes.push(Relation { name: cte.name.clone(), columns: renamed(columns, &cte.columns), opaque: false });
+        }
+
+        let mut scope = Vec::new();
+        for reference in &select.from {
+            self.add_relations(reference, &mut scope);
+        }
+        self.scopes.push(scope);
+
+        let mut columns = Vec::new();
+        for item in &select.select_list {
+            match item {
+                SelectItem::Expression { expr, alias } => {
+                    let (transformation, sources) = self.expression(expr);
+                    let column = alias.clone().unwrap_or_else(|| output_name(expr));
+                    columns.push(ColumnLineage { column, transformation, sources });
+                }
+                SelectItem::Wildcard => {
+                    let scope = self.scopes.last().map(Vec::as_slice).unwrap_or_default();
+                    self.complete &= !scope.iter().any(|relation| relation.opaque);
+                    columns.extend(scope.iter().flat_map(|relation| relation.columns.clone()));
+                }
+                SelectItem::QualifiedWildcard { qualifier } => match self.relation(qualifier) {
+                    Some(relation) => {
+                        self.complete &= !relation.opaque;
+                        columns.extend(relation.columns);
+                    }
+                    None => self.complete = false,
+                },
+            }
+        }
+        self.scopes.pop();
+
+        // Each branch of a set operation feeds the columns at the same positions
+        for union in &select.unions {
+            let branch = self.select(&union.select);
+            for (column, other) in columns.iter_mut().zip(branch) {
+                if column.transformation == Transformation::Direct {
+                    column.transformation = other.transformation;
+                }
+                merge(&mut column.sources, other.sources);
+            }
+        }
+        self.ctes.truncate(outer_ctes);
+        columns
+    }
+
+    fn add_relations(&mut self, reference: &TableReference, scope: &mut Vec<Relation>) {
+        match reference {
+            TableReference::Table { name, alias } => {
+                let relation = self.table(name);
+                scope.push(relation.aliased(alias.as_ref()));
+            }
+            TableReference::Subquery { query, alias } => {
+                let columns = self.select(query);
+                scope.push(Relation { name: alias.clone(), columns, opaque: false });
+            }
+            TableReference::Join { left, joins, .. } => {
+                self.add_relations(left, scope);
+                for join in joins {
+                    self.add_relations(&join.table, scope);
+                }
+            }
+        }
+    }
+
+    /// Resolves a table name to a CTE, a table, or a view traced in turn.
+    fn table(&mut self, name: &SchemaReference) -> Relation {
+        if !name.is_qualified()
+            && let Some(cte) = self.ctes.iter().rev().find(|cte| cte.name.eq_ignore_ascii_case(&name.object))
+        {
+            return cte.clone();
+        }
+        let opaque = Relation { name: name.object.clone(), columns: Vec::new(), opaque: true };
+        match self.analyzer.find(name) {
+            Some(Found::Table(table, columns)) => {
+                let columns = columns
+                    .into_iter()
+                    .map(|column| ColumnLineage {
+                        sources: vec![SourceColumn { table: table.clone(), column: column.clone() }],
+                        column,
+                        transformation: Transformation::Direct,
+                    })
+                    .collect();
+                Relation { name: name.object.clone(), columns, opaque: false }
+            }
+            Some(Found::View(view)) => match self.view(&qualified(&view.schema, &view.name), &view.definition) {
+                Some(columns) => Relation { name: name.object.clone(), columns, opaque: false },
+                None => {
+                    self.complete = false;
+                    opaque
+                }
+            },
+            None => {
+                self.complete = false;
+                opaque
+            }
+        }
+    }
+
+    fn relation(&self, qualifier: &str) -> Option<Relation> {
+        resolve_qualifier(self.scopes.iter().map(Vec::as_slice), qualifier).cloned()
+    }
+
+    /// What an expression does to the columns it reads, and which table columns those are.
+    fn expression(&mut self, expression: &Expression) -> (Transformation, Vec<SourceColumn>) {
+        match expression {
+            Expression::Column(name) => self.column(None, name),
+            Expression::QualifiedColumn { table, column } => self.column(Some(table), column),
+            Expression::WindowFunction { function, .. } => {
+                let (_, sources) = self.expression(function);
+                let transformation =
+                    if function.is_aggregate() { Transformation::Aggregate } else { Transformation::Expression };
+                (transformation, sources)
+            }
+            Expression::Subquery(query) => {
+                let columns = self.select(query);
+                match columns.into_iter().next() {
+                    Some(column) => (column.transformation, column.sources),
+                    None => (Transformation::Expression, Vec::new()),
+                }
+            }
+            Expression::Exists(query) => {
+                self.select(query);
+                (Transformation::Expression, Vec::new())
+            }
+            _ => {
+                let mut sources = Vec::new();
+                for child in expression.children() {
+                    merge(&mut sources, self.expression(child).1);
+                }
+                let transformation = match expression {
+                    Expression::Case { .. } => Transformation::Case,
+                    _ if expression.is_aggregate() => Transformation::Aggregate,
+                    _ => Transformation::Expression,
+                };
+                (transformation, sources)
+            }
+        }
+    }
+
+    /// A column keeps the lineage of the relation column it names.
+    fn column(&self, qualifier: Option<&String>, name: &str) -> (Transformation, Vec<SourceColumn>) {
+        let found = match qualifier {
+            Some(qualifier) => self.relation(qualifier).and_then(|relation| find_column(&relation.columns, name).cloned()),
+            None => self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.iter().find_map(|relation| find_column(&relation.columns, name)))
+                .cloned(),
+        };
+        match found {
+            Some(column) => (column.transformation, column.sources),
+            None if qualifier.is_none() && NILADIC_FUNCTIONS.iter().any(|function| function.eq_ignore_ascii_case(name)) => {
+                (Transformation::Expression, Vec::new())
+            }
+            // From a table whose columns aren't known
+            None => (Transformation::Direct, Vec::new()),
+        }
+    }
+}
+
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Transformation::Direct => "direct",
+            Transformation::Aggregate => "aggregate",
+            Transformation::Expression => "expression",
+            Transformation::Case => "CASE",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl fmt::Display for SourceColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.table, self.column)
+    }
+}
+
+fn find_column<'c>(columns: &'c [ColumnLineage], name: &str) -> Option<&'c ColumnLineage> {
+    columns.iter().find(|column| column.column.eq_ignore_ascii_case(name))
+}
+
+/// Applies a CTE's column list, which names its columns by position.
+fn renamed(mut columns: Vec<ColumnLineage>, names: &[String]) -> Vec<ColumnLineage> {
+    for (column, name) in columns.iter_mut().zip(names) {
+        column.column = name.clone();
+    }
+    columns
+}
+
+fn merge(sources: &mut Vec<SourceColumn>, more: Vec<SourceColumn>) {
+    for source in more {
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+}
+
+fn qualified(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
@@ -0,0 +1,610 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::analyze::online::MigrationIssueType;
+use crate::core::analyze::semantic::rules;
+use crate::core::ast::{
+    AlterTableAction, AstNode, AstValidationError, ColumnConstraint, DdlStatement, Location, SchemaReference, Statement,
+    TableConstraint, ValidationSeverity,
+};
+use crate::core::domain::constraint::{ConstraintAnalysisService, ConstraintIssueType};
+use crate::core::domain::entity::{DataType, Database, Table};
+use crate::core::domain::field::{FieldService, SuggestionType};
+use crate::core::domain::indexes::{IndexIssueType, IndexValidationService};
+use crate::core::domain::junction::{JunctionIssueType, JunctionTableDetectionService};
+use crate::core::lower::Lowerer;
+use crate::core::parse::{Lexer, Parser, SqlDialect, TokenType};
+
+#[derive(Debug, Error)]
+pub enum LintError {
+    #[error("unknown lint rule {0}; `sql2doc lint --list-rules` lists them")]
+    UnknownRule(String),
+    #[error("unknown lint level {0}; expected off, info, warning or error")]
+    UnknownLevel(String),
+    #[error("expected <rule>=<level>, got {0}")]
+    Setting(String),
+}
+
+/// How a rule's findings are reported. `Off` turns the rule off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+/// A schema standard, checked against the model the scripts build. Its id is stable, so it can
+/// be configured and disabled by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    MissingPrimaryKey,
+    UnindexedForeignKey,
+    RedundantUniqueConstraint,
+    UnknownIndexColumn,
+    RedundantIndex,
+    WideIndex,
+    LowSelectivityIndex,
+    JunctionMissingPrimaryKey,
+    JunctionMissingForeignKey,
+    JunctionNullableForeignKey,
+    NullablePrimaryKey,
+    LongColumnName,
+    NullableWithoutDefault,
+    UnboundedVarchar,
+    TextColumn,
+}
+
+/// A rule broken by a table, column or index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub level: LintLevel,
+    /// The table, or `table.column` or `table.index`, the finding is about
+    pub object: String,
+    pub message: String,
+    pub help: Option<String>,
+    /// The statement that gave the object its current name
+    pub location: Option<Location>,
+}
+
+/// The level of each rule, starting from its default.
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<LintRule, LintLevel>,
+}
+
+/// Checks a schema against [`LintRule`]s, gathered from the constraint, index, junction table and
+/// field analyses of the domain model. A `-- sql2doc:disable rule-id` comment turns rules off for
+/// the statement it precedes or sits in, and with it for the objects that statement creates or
+/// renames; without rule ids it turns them all off.
+pub struct SchemaLinter {
+    lowerer: Lowerer,
+    field_service: FieldService,
+    config: LintConfig,
+}
+
+/// What a finding is about, by lower-cased name, to find the statement behind it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Subject {
+    Table(ObjectKey),
+    Column(ObjectKey, String),
+    Key(ObjectKey, String),
+}
+
+/// A schema and lower-cased table name; `None` is the dialect's default schema.
+type ObjectKey = (Option<String>, String);
+
+/// The script and statement that last gave each object its name.
+#[derive(Default)]
+struct Origins {
+    places: HashMap<Subject, (usize, Option<Location>)>,
+}
+
+/// The id `sql2doc:disable` comments naming no rule are reported under.
+pub const UNKNOWN_RULE: &str = "unknown-rule";
+
+/// The rules `sql2doc:disable` comments turn off in one script, by the statement they apply to.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    /// An empty list turns every rule off
+    statements: Vec<(Location, Vec<String>)>,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 15] = [
+        LintRule::MissingPrimaryKey,
+        LintRule::UnindexedForeignKey,
+        LintRule::RedundantUniqueConstraint,
+        LintRule::UnknownIndexColumn,
+        LintRule::RedundantIndex,
+        LintRule::WideIndex,
+        LintRule::LowSelectivityIndex,
+        LintRule::JunctionMissingPrimaryKey,
+        LintRule::JunctionMissingForeignKey,
+        LintRule::JunctionNullableForeignKey,
+        LintRule::NullablePrimaryKey,
+        LintRule::LongColumnName,
+        LintRule::NullableWithoutDefault,
+        LintRule::UnboundedVarchar,
+        LintRule::TextColumn,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::MissingPrimaryKey => "missing-primary-key",
+            LintRule::UnindexedForeignKey => "unindexed-foreign-key",
+            LintRule::RedundantUniqueConstraint => "redundant-unique-constraint",
+            LintRule::UnknownIndexColumn => "unknown-index-column",
+            LintRule::RedundantIndex => "redundant-index",
+            LintRule::WideIndex => "wide-index",
+            LintRule::LowSelectivityIndex => "low-selectivity-index",
+            LintRule::JunctionMissingPrimaryKey => "junction-missing-primary-key",
+            LintRule::JunctionMissingForeignKey => "junction-missing-foreign-key",
+            LintRule::JunctionNullableForeignKey => "junction-nullable-foreign-key",
+            LintRule::NullablePrimaryKey => "nullable-primary-key",
+            LintRule::LongColumnName => "long-column-name",
+            LintRule::NullableWithoutDefault => "nullable-without-default",
+            LintRule::UnboundedVarchar => "unbounded-varchar",
+            LintRule::TextColumn => "text-column",
+        }
+    }
+
+    /// Rules that are matters of taste, or rest on guesses about the data, start out off.
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            LintRule::UnknownIndexColumn | LintRule::NullablePrimaryKey => LintLevel::Error,
+            LintRule::MissingPrimaryKey
+            | LintRule::UnindexedForeignKey
+            | LintRule::RedundantIndex
+            | LintRule::JunctionMissingPrimaryKey
+            | LintRule::JunctionMissingForeignKey
+            | LintRule::JunctionNullableForeignKey => LintLevel::Warning,
+            LintRule::RedundantUniqueConstraint
+            | LintRule::WideIndex
+            | LintRule::LongColumnName
+            | LintRule::UnboundedVarchar => LintLevel::Info,
+            LintRule::LowSelectivityIndex | LintRule::NullableWithoutDefault | LintRule::TextColumn => LintLevel::Off,
+        }
+    }
+
+    pub fn summary(&self) -> &'static str {
+        match self {
+            LintRule::MissingPrimaryKey => "a table without a primary key",
+            LintRule::UnindexedForeignKey => "a foreign key without an index on its columns",
+            LintRule::RedundantUniqueConstraint => "a unique constraint on primary key columns",
+            LintRule::UnknownIndexColumn => "an index on a column the table doesn't have",
+            LintRule::RedundantIndex => "an index on much the same columns as another",
+            LintRule::WideIndex => "an index on more than five columns",
+            LintRule::LowSelectivityIndex => "a one-column index whose type suggests few distinct values",
+            LintRule::JunctionMissingPrimaryKey => "a junction table without a primary key",
+            LintRule::JunctionMissingForeignKey => "a junction table with fewer than two foreign keys",
+            LintRule::JunctionNullableForeignKey => "a nullable foreign key column in a junction table",
+            LintRule::NullablePrimaryKey => "a nullable primary key column",
+            LintRule::LongColumnName => "a column name over 30 characters",
+            LintRule::NullableWithoutDefault => "a nullable column without a default",
+            LintRule::UnboundedVarchar => "a VARCHAR without a length",
+            LintRule::TextColumn => "a TEXT column",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = LintError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        LintRule::ALL.into_iter().find(|rule| rule.id() == id).ok_or_else(|| LintError::UnknownRule(id.to_string()))
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LintLevel::Off => "off",
+            LintLevel::Info => "info",
+            LintLevel::Warning => "warning",
+            LintLevel::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = LintError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "off" | "allow" => Ok(LintLevel::Off),
+            "info" | "note" => Ok(LintLevel::Info),
+            "warning" | "warn" => Ok(LintLevel::Warning),
+            "error" | "deny" => Ok(LintLevel::Error),
+            _ => Err(LintError::UnknownLevel(name.to_string())),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+
+    /// Applies a `rule-id=level` setting, such as `wide-index=off`.
+    pub fn apply(&mut self, setting: &str) -> Result<(), LintError> {
+        let Some((rule, level)) = setting.split_once('=') else {
+            return Err(LintError::Setting(setting.to_string()));
+        };
+        self.set(rule.trim().parse()?, level.trim().parse()?);
+        Ok(())
+    }
+
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels.get(&rule).copied().unwrap_or_else(|| rule.default_level())
+    }
+}
+
+impl SchemaLinter {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            lowerer: Lowerer::new(dialect.clone()),
+            field_service: FieldService::new(dialect),
+            config: LintConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: LintConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn lint(&self, sql: &str) -> Vec<LintFinding> {
+        self.lint_all(&[sql]).into_iter().map(|(_, finding)| finding).collect()
+    }
+
+    /// Lints the schema that scripts build when run one after another, such as a directory of
+    /// migrations. Each finding comes with the index of the script whose statement gave the
+    /// object its current name, and that statement's location.
+    pub fn lint_all(&self, scripts: &[&str]) -> Vec<(usize, LintFinding)> {
+        let dialect = self.lowerer.dialect();
+        let parsed: Vec<Vec<AstNode<Statement>>> = scripts
+            .iter()
+            .map(|sql| match Lexer::for_dialect(sql, dialect).tokenize() {
+                Ok(tokens) => Parser::new(tokens).parse_recovering().0,
+                Err(_) => Vec::new(),
+            })
+            .collect();
+        let statements: Vec<Statement> = parsed.iter().flatten().map(|statement| statement.node.clone()).collect();
+        let database = self.lowerer.lower_statements(&statements).database;
+
+        let origins = Origins::trace(&self.lowerer, &parsed);
+        let suppressions: Vec<Suppressions> =
+            scripts.iter().zip(&parsed).map(|(sql, statements)| Suppressions::parse(sql, dialect, statements)).collect();
+
+        let mut findings: Vec<(usize, LintFinding)> = self
+            .findings(&database)
+            .into_iter()
+            .filter_map(|(subject, mut finding)| {
+                let (script, location) = origins.find(&subject);
+                finding.location = location;
+                let suppressed = suppressions[script].suppresses(finding.rule.id(), finding.location.as_ref());
+                (!suppressed).then_some((script, finding))
+            })
+            .collect();
+        let line = |finding: &LintFinding| finding.location.as_ref().map(|location| location.start.line);
+        findings.sort_by_key(|(script, finding)| (*script, line(finding)));
+        findings
+    }
+
+    /// Lints a model that was already built. Its findings have no location, and no comments
+    /// turn them off.
+    pub fn lint_database(&self, database: &Database) -> Vec<LintFinding> {
+        self.findings(database).into_iter().map(|(_, finding)| finding).collect()
+    }
+
+    fn findings(&self, database: &Database) -> Vec<(Subject, LintFinding)> {
+        let mut findings = Vec::new();
+        for table in database.get_all_tables() {
+            let key = self.key(table);
+            let mut report = |subject: Subject, rule: LintRule, message: String, help: Option<String>| {
+                let level = self.config.level(rule);
+                if level == LintLevel::Off {
+                    return;
+                }
+                let object = match &subject {
+                    Subject::Table(_) => table.name.clone(),
+                    Subject::Column(_, name) | Subject::Key(_, name) => format!("{}.{}", table.name, name),
+                };
+                findings.push((subject, LintFinding { rule, level, object, message, help, location: None }));
+            };
+
+            for issue in ConstraintAnalysisService::detect_table_issues(table) {
+                let rule = match issue.issue_type {
+                    ConstraintIssueType::MissingConstraint => LintRule::MissingPrimaryKey,
+                    ConstraintIssueType::PerformanceImpact => LintRule::UnindexedForeignKey,
+                    ConstraintIssueType::RedundantConstraint => LintRule::RedundantUniqueConstraint,
+                    // Not raised for a single table
+                    ConstraintIssueType::ConflictingConstraint
+                    | ConstraintIssueType::DataIntegrityRisk
+                    | ConstraintIssueType::NamingConvention => continue,
+                };
+                report(Subject::Table(key.clone()), rule, issue.description, issue.suggested_fix);
+            }
+
+            for index in &table.indexes {
+                let subject = Subject::Key(key.clone(), index.name.to_lowercase());
+                for issue in IndexValidationService::validate_index(index, table, database).issues {
+                    let rule = match issue.issue_type {
+                        IndexIssueType::InefficientIndex => LintRule::UnknownIndexColumn,
+                        IndexIssueType::RedundantIndex => LintRule::RedundantIndex,
+                        IndexIssueType::LargeIndex => LintRule::WideIndex,
+                        IndexIssueType::PoorSelectivity => LintRule::LowSelectivityIndex,
+                        // Not raised when validating an index on its own
+                        IndexIssueType::MissingIndex | IndexIssueType::UnusedIndex | IndexIssueType::ConflictingIndex => {
+                            continue;
+                        }
+                    };
+                    report(subject.clone(), rule, issue.description, issue.suggested_fix);
+                }
+            }
+
+            for issue in JunctionTableDetectionService::analyze_table(table, database).issues {
+                let rule = match issue.issue_type {
+                    JunctionIssueType::MissingPrimaryKey => LintRule::JunctionMissingPrimaryKey,
+                    JunctionIssueType::MissingForeignKeys => LintRule::JunctionMissingForeignKey,
+                    JunctionIssueType::DataIntegrity => LintRule::JunctionNullableForeignKey,
+                    JunctionIssueType::IncorrectCardinality
+                    | JunctionIssueType::RedundantColumns
+                    | JunctionIssueType::Performance
+                    | JunctionIssueType::NamingConvention => continue,
+                };
+                let subject = match issue.affected_columns.first() {
+                    Some(column) => Subject::Column(key.clone(), column.to_lowercase()),
+                    None => Subject::Table(key.clone()),
+                };
+                let message = format!("{}: {}", table.name, issue.description);
+                report(subject, rule, message, issue.sql_fix);
+            }
+
+            for column in &table.columns {
+                let subject = Subject::Column(key.clone(), column.name.to_lowercase());
+                for suggestion in self.field_service.suggest_field_improvements(column, table) {
+                    let rule = match suggestion.suggestion_type {
+                        SuggestionType::AddConstraint => LintRule::NullablePrimaryKey,
+                        SuggestionType::Naming => LintRule::LongColumnName,
+                        SuggestionType::AddDefault => LintRule::NullableWithoutDefault,
+                        SuggestionType::DataType if column.data_type == DataType::Text => LintRule::TextColumn,
+                        SuggestionType::DataType => LintRule::UnboundedVarchar,
+                        SuggestionType::Performance | SuggestionType::Security => continue,
+                    };
+                    // The suggestion warns about TEXT defaults, but the rule asks for a bounded type
+                    let description = match rule {
+                        LintRule::TextColumn => "unbounded TEXT column; prefer a VARCHAR with a length",
+                        _ => suggestion.message.as_str(),
+                    };
+                    let message = format!("{}.{}: {}", table.name, column.name, description);
+                    report(subject.clone(), rule, message, Some(suggestion.sql_fix));
+                }
+            }
+        }
+        findings
+    }
+
+    fn key(&self, table: &Table) -> ObjectKey {
+        let schema = table.schema.as_deref().and_then(|schema| self.lowerer.normalize_schema(schema));
+        (schema, table.name.to_lowercase())
+    }
+}
+
+impl Origins {
+    fn trace(lowerer: &Lowerer, scripts: &[Vec<AstNode<Statement>>]) -> Self {
+        let mut origins = Origins::default();
+        let all = scripts.iter().enumerate().flat_map(|(script, statements)| statements.iter().map(move |node| (script, node)));
+        let scopes = lowerer.schema_scopes(scripts.iter().flatten().map(|node| &node.node));
+        for ((script, node), scope) in all.zip(&scopes) {
+            let place = (script, node.location.clone());
+            let key = |reference: &SchemaReference| (lowerer.schema_of(reference, scope), reference.object.to_lowercase());
+            let Statement::Ddl(statement) = &node.node else {
+                continue;
+            };
+            match statement {
+                DdlStatement::CreateTable(create) => {
+                    let table = key(&create.table_name);
+                    origins.forget(&table);
+                    origins.places.insert(Subject::Table(table.clone()), place.clone());
+                    for column in &create.columns {
+                        origins.add_column(&table, &column.name, &column.constraints, &place);
+                    }
+                    for constraint in &create.constraints {
+                        origins.add_key(&table, constraint, &place);
+                    }
+                }
+                DdlStatement::CreateIndex(index) => {
+                    let subject = Subject::Key(key(&index.table_name), index.index_name.to_lowercase());
+                    origins.places.insert(subject, place);
+                }
+                DdlStatement::AlterTable(alter) => {
+                    let mut table = key(&alter.table_name);
+                    for action in &alter.actions {
+                        match action {
+                            AlterTableAction::AddColumn { column } | AlterTableAction::ModifyColumn { column } => {
+                                origins.add_column(&table, &column.name, &column.constraints, &place);
+                            }
+                            AlterTableAction::RenameColumn { new_name, .. } => {
+                                origins.places.insert(Subject::Column(table.clone(), new_name.to_lowercase()), place.clone());
+                            }
+                            AlterTableAction::AddConstraint { constraint, .. } => origins.add_key(&table, constraint, &place),
+                            AlterTableAction::RenameTable { new_name } => {
+                                let renamed = (table.0.clone(), new_name.to_lowercase());
+                                origins.rename(&table, &renamed);
+                                origins.places.insert(Subject::Table(renamed.clone()), place.clone());
+                                table = renamed;
+                            }
+                            AlterTableAction::DropColumn { .. }
+                            | AlterTableAction::AlterColumn { .. }
+                            | AlterTableAction::DropConstraint { .. }
+                            | AlterTableAction::ConstraintChecking { .. }
+                            | AlterTableAction::Other(_) => {}
+                        }
+                    }
+                }
+                DdlStatement::DropTable(drop) => drop.table_names.iter().for_each(|name| origins.forget(&key(name))),
+                _ => {}
+            }
+        }
+        origins
+    }
+
+    fn add_column(&mut self, table: &ObjectKey, name: &str, constraints: &[ColumnConstraint], place: &(usize, Option<Location>)) {
+        self.places.insert(Subject::Column(table.clone(), name.to_lowercase()), place.clone());
+        // MySQL names an anonymous key after its column
+        if constraints.iter().any(|constraint| matches!(constraint, ColumnConstraint::Unique)) {
+            self.places.insert(Subject::Key(table.clone(), name.to_lowercase()), place.clone());
+        }
+    }
+
+    fn add_key(&mut self, table: &ObjectKey, constraint: &TableConstraint, place: &(usize, Option<Location>)) {
+        let name = match constraint {
+            TableConstraint::Unique { name, columns, .. } => name.as_ref().or(columns.first()),
+            TableConstraint::Index { name, columns, .. } => name.as_ref().or(columns.first().map(|part| &part.column)),
+            TableConstraint::ForeignKey { name, .. }
+            | TableConstraint::Check { name, .. }
+            | TableConstraint::Exclude { name, .. } => name.as_ref(),
+            TableConstraint::PrimaryKey { .. } => None,
+        };
+        if let Some(name) = name {
+            self.places.insert(Subject::Key(table.clone(), name.to_lowercase()), place.clone());
+        }
+    }
+
+    fn rename(&mut self, from: &ObjectKey, to: &ObjectKey) {
+        let moved: Vec<(Subject, (usize, Option<Location>))> = self
+            .places
+            .iter()
+            .filter_map(|(subject, place)| {
+                let subject = match subject {
+                    Subject::Column(table, name) if table == from => Subject::Column(to.clone(), name.clone()),
+                    Subject::Key(table, name) if table == from => Subject::Key(to.clone(), name.clone()),
+                    _ => return None,
+                };
+                Some((subject, place.clone()))
+            })
+            .collect();
+        self.forget(from);
+        self.places.extend(moved);
+    }
+
+    fn forget(&mut self, table: &ObjectKey) {
+        self.places.retain(|subject, _| match subject {
+            Subject::Table(key) | Subject::Column(key, _) | Subject::Key(key, _) => key != table,
+        });
+    }
+
+    /// Where the subject was named, or else its table; a key whose name the lowerer made up is
+    /// found through its table.
+    fn find(&self, subject: &Subject) -> (usize, Option<Location>) {
+        let table = match subject {
+            Subject::Table(key) | Subject::Column(key, _) | Subject::Key(key, _) => Subject::Table(key.clone()),
+        };
+        self.places.get(subject).or_else(|| self.places.get(&table)).cloned().unwrap_or((0, None))
+    }
+}
+
+impl Suppressions {
+    /// Reads the `-- sql2doc:disable` and `/* sql2doc:disable */` comments of a script. Each
+    /// applies to the statement it sits in, or else to the next one.
+    pub fn parse(sql: &str, dialect: &SqlDialect, statements: &[AstNode<Statement>]) -> Self {
+        let Ok(tokens) = Lexer::for_dialect(sql, dialect).with_comments().tokenize() else {
+            return Self::default();
+        };
+        let mut suppressions = Self::default();
+        let mut located = statements.iter().filter_map(|statement| statement.location.as_ref()).peekable();
+        for token in tokens {
+            let TokenType::Comment(text) = &token.token_type else {
+                continue;
+            };
+            let Some(rules) = disabled_rules(text) else {
+                continue;
+            };
+            let line = token.position.line;
+            while located.peek().is_some_and(|location| location.end.line < line) {
+                located.next();
+            }
+            if let Some(location) = located.peek() {
+                suppressions.statements.push(((*location).clone(), rules));
+            }
+        }
+        suppressions
+    }
+
+    /// Warns about each id in the script's `sql2doc:disable` comments that names no rule, as
+    /// a misspelled id turns nothing off.
+    pub fn unknown_rules(sql: &str, dialect: &SqlDialect) -> Vec<AstValidationError> {
+        let Ok(tokens) = Lexer::for_dialect(sql, dialect).with_comments().tokenize() else {
+            return Vec::new();
+        };
+        let mut warnings = Vec::new();
+        for token in tokens {
+            let TokenType::Comment(text) = &token.token_type else {
+                continue;
+            };
+            for id in disabled_rules(text).unwrap_or_default() {
+                if is_rule_id(&id) {
+                    continue;
+                }
+                warnings.push(AstValidationError {
+                    message: format!("`{}` isn't a rule, so the comment doesn't turn anything off", id),
+                    location: Some(Location { start: token.position.clone(), end: token.position.clone() }),
+                    severity: ValidationSeverity::Warning,
+                    rule: Some(UNKNOWN_RULE.to_string()),
+                });
+            }
+        }
+        warnings
+    }
+
+    /// Whether a comment turns the rule off for the statement at the location.
+    pub fn suppresses(&self, rule: &str, location: Option<&Location>) -> bool {
+        let Some(location) = location else {
+            return false;
+        };
+        self.statements.iter().any(|(statement, rules)| {
+            statement.start.line <= location.start.line
+                && location.start.line <= statement.end.line
+                && (rules.is_empty() || rules.iter().any(|disabled| disabled == rule))
+        })
+    }
+}
+
+/// Whether an id names a lint, semantic or online migration rule.
+fn is_rule_id(id: &str) -> bool {
+    LintRule::ALL.iter().any(|rule| rule.id() == id)
+        || rules::ALL.contains(&id)
+        || MigrationIssueType::ALL.iter().any(|issue| issue.id() == id)
+        || id == "semantic"
+        || id == UNKNOWN_RULE
+}
+
+/// The rule ids of a `sql2doc:disable` comment; empty when it names none.
+fn disabled_rules(comment: &str) -> Option<Vec<String>> {
+    let text = comment.trim_start_matches("--").trim_start_matches("/*").trim_end_matches("*/").trim();
+    let rest = text.strip_prefix("sql2doc:disable")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.split(|c: char| c == ',' || c.is_whitespace()).filter(|id| !id.is_empty()).map(str::to_string).collect())
+}
@@ -0,0 +1,436 @@
+use crate::core::analyze::query::NILADIC_FUNCTIONS;
+use crate::core::ast::{
+    ConflictAction, ConflictTarget, DeleteStatement, DmlStatement, Expression, InsertStatement, JoinCondition,
+    QueryComplexity, QueryMetadata, QueryType, SelectItem, SelectStatement, TableReference, UpdateStatement,
+};
+
+/// Describes what a query touches and how involved it is: the tables and columns it reads
+/// or writes, its joins, nesting, aggregates and window functions, and an estimate of its
+/// complexity with the reasons for it. Unlike [`QueryChecker`](super::QueryChecker) it needs
+/// no schema, so unqualified columns are only attributed to a table when just one is in scope.
+#[derive(Debug, Default)]
+pub struct QueryAnalyzer;
+
+/// The tables and aliases a query level can name, as `(name, table)`; CTEs and derived
+/// tables have no table, and their columns are left out as the queries behind them are walked.
+type Scope = Vec<(String, Option<String>)>;
+
+#[derive(Default)]
+struct Walker {
+    tables: Vec<String>,
+    columns: Vec<String>,
+    joins: usize,
+    subquery_depth: usize,
+    cte_depth: usize,
+    cte_count: usize,
+    recursive: bool,
+    set_operations: usize,
+    grouped: bool,
+    having: bool,
+    aggregates: Vec<String>,
+    window_functions: Vec<String>,
+    ctes: Vec<String>,
+    scopes: Vec<Scope>,
+    /// Output aliases of the innermost SELECT, which ORDER BY may name
+    outputs: Vec<String>,
+}
+
+impl QueryAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn analyze(&self, statement: &DmlStatement) -> QueryMetadata {
+        let mut walker = Walker::default();
+        let query_type = match statement {
+            DmlStatement::Select(select) => {
+                walker.select(select, 0, 0);
+                QueryType::Select
+            }
+            DmlStatement::Insert(insert) => {
+                walker.insert(insert);
+                QueryType::Insert
+            }
+            DmlStatement::Update(update) => {
+                walker.update(update);
+                QueryType::Update
+            }
+            DmlStatement::Delete(delete) => {
+                walker.delete(delete);
+                QueryType::Delete
+            }
+        };
+
+        let (complexity, reasons) = walker.complexity();
+        let mut metadata = QueryMetadata::new(query_type)
+            .with_tables(walker.tables)
+            .with_columns(walker.columns)
+            .with_complexity(complexity)
+            .with_complexity_reasons(reasons);
+        metadata.join_count = walker.joins;
+        metadata.subquery_depth = walker.subquery_depth;
+        metadata.cte_depth = walker.cte_depth;
+        metadata.aggregates = walker.aggregates;
+        metadata.window_functions = walker.window_functions;
+        metadata
+    }
+}
+
+impl Walker {
+    /// Walks a SELECT nested `depth` subqueries and `cte_level` WITH clauses deep.
+    fn select(&mut self, select: &SelectStatement, depth: usize, cte_level: usize) {
+        self.subquery_depth = self.subquery_depth.max(depth);
+        let outer_ctes = self.ctes.len();
+        if !select.with.is_empty() {
+            self.cte_depth = self.cte_depth.max(cte_level + 1);
+        }
+        for cte in &select.with {
+            self.cte_count += 1;
+            self.recursive |= cte.recursive;
+            if cte.recursive {
+                self.ctes.push(cte.name.clone());
+            }
+            self.select(&cte.query, depth, cte_level + 1);
+            if !cte.recursive {
+                self.ctes.push(cte.name.clone());
+            }
+        }
+
+        let mut scope = Scope::new();
+        let mut conditions = Vec::new();
+        for reference in &select.from {
+            self.table_reference(reference, depth, cte_level, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        let outer_outputs = std::mem::take(&mut self.outputs);
+
+        for condition in conditions {
+            self.join_condition(condition, depth, cte_level);
+        }
+        self.select_items(&select.select_list, depth, cte_level);
+        if let Some(expression) = &select.where_clause {
+            self.expression(expression, depth, cte_level);
+        }
+        if let Some(group_by) = &select.group_by {
+            self.grouped = true;
+            for expression in &group_by.expressions {
+                self.expression(expression, depth, cte_level);
+            }
+        }
+        if let Some(expression) = &select.having {
+            self.having = true;
+            self.expression(expression, depth, cte_level);
+        }
+        for expression in select.windows.iter().flat_map(|named| named.window.expressions()) {
+            self.expression(expression, depth, cte_level);
+        }
+        self.outputs = select
+            .select_list
+            .iter()
+            .filter_map(|item| match item {
+                SelectItem::Expression { alias: Some(alias), .. } => Some(alias.clone()),
+                _ => None,
+            })
+            .collect();
+        for order in &select.order_by {
+            self.expression(&order.expr, depth, cte_level);
+        }
+
+        self.outputs = outer_outputs;
+        self.scopes.pop();
+        for union in &select.unions {
+            self.set_operations += 1;
+            self.select(&union.select, depth, cte_level);
+        }
+        self.ctes.truncate(outer_ctes);
+    }
+
+    fn insert(&mut self, insert: &InsertStatement) {
+        let table = insert.table_name.to_string();
+        self.table(&table);
+        for column in &insert.columns {
+            self.column(format!("{}.{}", table, column));
+        }
+        self.scopes.push(vec![(insert.table_name.object.clone(), Some(table.clone()))]);
+        for row in &insert.values {
+            for value in row {
+                self.expression(value, 0, 0);
+            }
+        }
+        self.scopes.pop();
+        if let Some(select) = &insert.select {
+            self.select(select, 0, 0);
+        }
+
+        // `excluded` names the row that was to be inserted
+        self.scopes.push(vec![
+            (insert.table_name.object.clone(), Some(table.clone())),
+            ("excluded".to_string(), Some(table.clone())),
+        ]);
+        if let Some(conflict) = &insert.on_conflict {
+            if let Some(ConflictTarget::Columns { columns, where_clause }) = &conflict.target {
+                for column in columns {
+                    self.column(format!("{}.{}", table, column));
+                }
+                if let Some(expression) = where_clause {
+                    self.expression(expression, 0, 0);
+                }
+            }
+            if let ConflictAction::DoUpdate { assignments, where_clause } = &conflict.action {
+                for assignment in assignments {
+                    self.assigned(&table, &assignment.column);
+                    self.expression(&assignment.value, 0, 0);
+                }
+                if let Some(expression) = where_clause {
+                    self.expression(expression, 0, 0);
+                }
+            }
+        }
+        self.scopes.pop();
+
+        self.scopes.push(vec![(insert.table_name.object.clone(), Some(table))]);
+        self.select_items(&insert.returning, 0, 0);
+        self.scopes.pop();
+    }
+
+    fn update(&mut self, update: &UpdateStatement) {
+        let table = update.table_name.to_string();
+        self.table(&table);
+        let mut scope = vec![(update.alias.clone().unwrap_or_else(|| update.table_name.object.clone()), Some(table.clone()))];
+        let mut conditions = Vec::new();
+        for reference in &update.from {
+            self.table_reference(reference, 0, 0, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        for condition in conditions {
+            self.join_condition(condition, 0, 0);
+        }
+        for assignment in &update.assignments {
+            self.assigned(&table, &assignment.column);
+            self.expression(&assignment.value, 0, 0);
+        }
+        if let Some(expression) = &update.where_clause {
+            self.expression(expression, 0, 0);
+        }
+        self.select_items(&update.returning, 0, 0);
+        self.scopes.pop();
+    }
+
+    fn delete(&mut self, delete: &DeleteStatement) {
+        let table = delete.table_name.to_string();
+        self.table(&table);
+        let mut scope = vec![(delete.alias.clone().unwrap_or_else(|| delete.table_name.object.clone()), Some(table))];
+        let mut conditions = Vec::new();
+        for reference in &delete.using {
+            self.table_reference(reference, 0, 0, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        for condition in conditions {
+            self.join_condition(condition, 0, 0);
+        }
+        if let Some(expression) = &delete.where_clause {
+            self.expression(expression, 0, 0);
+        }
+        self.select_items(&delete.returning, 0, 0);
+        self.scopes.pop();
+    }
+
+    fn table_reference<'s>(
+        &mut self,
+        reference: &'s TableReference,
+        depth: usize,
+        cte_level: usize,
+        scope: &mut Scope,
+        conditions: &mut Vec<&'s JoinCondition>,
+    ) {
+        match reference {
+            TableReference::Table { name, alias } => {
+                let is_cte = !name.is_qualified() && self.ctes.iter().any(|cte| cte.eq_ignore_ascii_case(&name.object));
+                let table = (!is_cte).then(|| name.to_string());
+                if let Some(table) = &table {
+                    self.table(table);
+                }
+                scope.push((alias.clone().unwrap_or_else(|| name.object.clone()), table));
+            }
+            TableReference::Subquery { query, alias } => {
+                self.select(query, depth + 1, cte_level);
+                scope.push((alias.clone(), None));
+            }
+            TableReference::Join { left, joins, .. } => {
+                self.table_reference(left, depth, cte_level, scope, conditions);
+                for join in joins {
+                    self.joins += 1;
+                    self.table_reference(&join.table, depth, cte_level, scope, conditions);
+                    if let Some(condition) = &join.condition {
+                        conditions.push(condition);
+                    }
+                }
+            }
+        }
+    }
+
+    fn join_condition(&mut self, condition: &JoinCondition, depth: usize, cte_level: usize) {
+        match condition {
+            JoinCondition::On(expression) => self.expression(expression, depth, cte_level),
+            JoinCondition::Using(columns) => {
+                for column in columns {
+                    self.column_reference(None, column);
+                }
+            }
+        }
+    }
+
+    fn select_items(&mut self, items: &[SelectItem], depth: usize, cte_level: usize) {
+        for item in items {
+            match item {
+                SelectItem::Expression { expr, .. } => self.expression(expr, depth, cte_level),
+                SelectItem::Wildcard => {
+                    let tables: Vec<String> = self
+                        .scopes
+                        .last()
+                        .map(|scope| scope.iter().filter_map(|(_, table)| table.clone()).collect())
+                        .unwrap_or_default();
+                    for table in tables {
+                        self.column(format!("{}.*", table));
+                    }
+                }
+                SelectItem::QualifiedWildcard { qualifier } => self.column_reference(Some(qualifier), "*"),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression, depth: usize, cte_level: usize) {
+        match expression {
+            Expression::Column(name) => self.column_reference(None, name),
+            Expression::QualifiedColumn { table, column } => self.column_reference(Some(table), column),
+            Expression::Exists(query) | Expression::Subquery(query) => self.select(query, depth + 1, cte_level),
+            Expression::WindowFunction { function, window } => {
+                if let Expression::Function { name, args } = function.as_ref() {
+                    push_unique(&mut self.window_functions, name.to_uppercase());
+                    for arg in args {
+                        self.expression(arg, depth, cte_level);
+                    }
+                } else {
+                    self.expression(function, depth, cte_level);
+                }
+                for expression in window.expressions() {
+                    self.expression(expression, depth, cte_level);
+                }
+            }
+            _ => {
+                if let Expression::Function { name, .. } = expression
+                    && expression.is_aggregate()
+                {
+                    push_unique(&mut self.aggregates, name.to_uppercase());
+                }
+                for child in expression.children() {
+                    self.expression(child, depth, cte_level);
+                }
+            }
+        }
+    }
+
+    /// Records a column under the table it belongs to, when that can be told from the query.
+    fn column_reference(&mut self, qualifier: Option<&str>, column: &str) {
+        let column = match qualifier {
+            Some(qualifier) => {
+                let relation = self.scopes.iter().rev().flatten().find(|(name, _)| name.eq_ignore_ascii_case(qualifier));
+                match relation {
+                    Some((_, Some(table))) => format!("{}.{}", table, column),
+                    Some((_, None)) => return,
+                    None => format!("{}.{}", qualifier, column),
+                }
+            }
+            None => {
+                let is_keyword = NILADIC_FUNCTIONS.iter().any(|name| name.eq_ignore_ascii_case(column));
+                let is_output = self.outputs.iter().any(|alias| alias.eq_ignore_ascii_case(column));
+                if is_keyword || is_output {
+                    return;
+                }
+                // A self-join still leaves only one table the column can belong to
+                let tables: Vec<&Option<String>> = self.scopes.last().into_iter().flatten().map(|(_, table)| table).collect();
+                match tables.first() {
+                    Some(Some(table)) if tables.iter().all(|other| other.as_ref() == Some(table)) => {
+                        format!("{}.{}", table, column)
+                    }
+                    Some(None) if tables.len() == 1 => return,
+                    _ => column.to_string(),
+                }
+            }
+        };
+        self.column(column);
+    }
+
+    /// Records the target of a SET, which may be written `alias.column`.
+    fn assigned(&mut self, table: &str, column: &str) {
+        let column = column.rsplit('.').next().unwrap_or(column);
+        self.column(format!("{}.{}", table, column));
+    }
+
+    fn table(&mut self, table: &str) {
+        push_unique(&mut self.tables, table.to_string());
+    }
+
+    fn column(&mut self, column: String) {
+        push_unique(&mut self.columns, column);
+    }
+
+    /// Scores the query's features and buckets the total, keeping a reason for each feature.
+    fn complexity(&self) -> (QueryComplexity, Vec<String>) {
+        let mut score = 0;
+        let mut reasons = Vec::new();
+        if self.joins > 0 {
+            score += self.joins;
+            reasons.push(plural(self.joins, "join", "joins"));
+        }
+        if self.subquery_depth > 0 {
+            score += 2 * self.subquery_depth;
+            reasons.push(match self.subquery_depth {
+                1 => "a subquery".to_string(),
+                depth => format!("subqueries nested {} deep", depth),
+            });
+        }
+        if self.cte_count > 0 {
+            score += self.cte_count;
+            reasons.push(plural(self.cte_count, "CTE", "CTEs"));
+        }
+        if self.recursive {
+            score += 3;
+            reasons.push("a recursive CTE".to_string());
+        }
+        if self.set_operations > 0 {
+            score += self.set_operations;
+            reasons.push(plural(self.set_operations, "set operation", "set operations"));
+        }
+        if !self.aggregates.is_empty() || self.grouped {
+            score += 1 + usize::from(self.having);
+            reasons.push(match (self.grouped, self.having) {
+                (true, true) => "grouping with HAVING".to_string(),
+                (true, false) => "grouping".to_string(),
+                (false, _) => "aggregates".to_string(),
+            });
+        }
+        if !self.window_functions.is_empty() {
+            score += 2;
+            reasons.push("window functions".to_string());
+        }
+
+        let complexity = match score {
+            0..=1 => QueryComplexity::Simple,
+            2..=4 => QueryComplexity::Medium,
+            5..=8 => QueryComplexity::Complex,
+            _ => QueryComplexity::VeryComplex,
+        };
+        (complexity, reasons)
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
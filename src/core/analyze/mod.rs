@@ -0,0 +1,25 @@
+pub mod catalog;
+pub mod dependencies;
+pub mod diff;
+pub mod history;
+mod infer;
+pub mod lineage;
+pub mod lint;
+pub mod metadata;
+pub mod online;
+pub mod query;
+mod scope;
+pub mod semantic;
+pub mod snapshot;
+
+pub use catalog::*;
+pub use dependencies::*;
+pub use diff::*;
+pub use history::*;
+pub use lineage::*;
+pub use lint::*;
+pub use metadata::*;
+pub use online::*;
+pub use query::*;
+pub use semantic::*;
+pub use snapshot::*;
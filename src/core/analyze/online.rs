@@ -0,0 +1,571 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::ast::{
+    AlterTableAction, AlterTableStatement, AstNode, ColumnConstraint, ColumnDefinition, CreateIndexStatement,
+    DataType, DdlStatement, IndexKind, Location, SessionStatement, Statement, TableConstraint,
+};
+use crate::core::domain::constraint::IssueSeverity;
+use crate::core::parse::{Lexer, Parser, SqlDialect};
+
+/// A statement that would lock or rewrite a table that already holds data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationIssue {
+    pub issue_type: MigrationIssueType,
+    pub severity: IssueSeverity,
+    pub table: String,
+    pub description: String,
+    pub suggested_fix: String,
+    pub location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigrationIssueType {
+    /// A `NOT NULL` column without a default, which fails on a table with rows
+    AddColumnNotNull,
+    /// A column whose default or generation fills every existing row
+    AddColumnRewrite,
+    /// An index built while writes to the table wait
+    BlockingIndex,
+    /// A unique or primary key whose index is built under an exclusive lock
+    BlockingUniqueConstraint,
+    /// A column type change that rewrites the table and its indexes
+    AlterColumnType,
+    /// A foreign key checked against every row while both tables are locked
+    ForeignKeyValidation,
+    /// A check constraint checked against every row under an exclusive lock
+    CheckValidation,
+    /// `SET NOT NULL`, which scans the table under an exclusive lock
+    SetNotNull,
+    /// A MySQL `ALTER TABLE` that copies the table, blocking writes until it's done
+    TableCopy,
+    /// A MySQL `ALTER TABLE` that rebuilds the table in place
+    TableRebuild,
+    /// A MySQL `LOCK=SHARED` or `LOCK=EXCLUSIVE` clause, which blocks writes
+    ExplicitLock,
+}
+
+impl MigrationIssueType {
+    pub const ALL: [MigrationIssueType; 11] = [
+        MigrationIssueType::AddColumnNotNull,
+        MigrationIssueType::AddColumnRewrite,
+        MigrationIssueType::BlockingIndex,
+        MigrationIssueType::BlockingUniqueConstraint,
+        MigrationIssueType::AlterColumnType,
+        MigrationIssueType::ForeignKeyValidation,
+        MigrationIssueType::CheckValidation,
+        MigrationIssueType::SetNotNull,
+        MigrationIssueType::TableCopy,
+        MigrationIssueType::TableRebuild,
+        MigrationIssueType::ExplicitLock,
+    ];
+
+    /// The rule's name, as printed next to each finding.
+    pub fn id(&self) -> &'static str {
+        match self {
+            MigrationIssueType::AddColumnNotNull => "add-column-not-null",
+            MigrationIssueType::AddColumnRewrite => "add-column-rewrite",
+            MigrationIssueType::BlockingIndex => "blocking-index",
+            MigrationIssueType::BlockingUniqueConstraint => "blocking-unique-constraint",
+            MigrationIssueType::AlterColumnType => "alter-column-type",
+            MigrationIssueType::ForeignKeyValidation => "foreign-key-validation",
+            MigrationIssueType::CheckValidation => "check-validation",
+            MigrationIssueType::SetNotNull => "set-not-null",
+            MigrationIssueType::TableCopy => "table-copy",
+            MigrationIssueType::TableRebuild => "table-rebuild",
+            MigrationIssueType::ExplicitLock => "explicit-lock",
+        }
+    }
+}
+
+impl fmt::Display for MigrationIssueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// Functions whose value differs per row, so a default calling one is written into each row.
+const VOLATILE_DEFAULTS: &[&str] =
+    &["random(", "gen_random_uuid(", "uuid_generate_v4(", "uuid_generate_v1(", "clock_timestamp(", "nextval(", "timeofday("];
+
+/// Flags migration statements that take long locks or rewrite tables on PostgreSQL and MySQL,
+/// each with the way to make the same change online. Other dialects get no findings.
+///
+/// A table created earlier in the same script is new and empty, so anything done to it is
+/// safe; any other table, including one created by an earlier script, is assumed to hold
+/// data. Statements that don't parse are skipped; [`super::SemanticAnalyzer`] reports them.
+pub struct OnlineMigrationLinter {
+    dialect: SqlDialect,
+    server_version: Option<u32>,
+}
+
+/// What the scripts seen so far say about each table, by lower-cased name.
+#[derive(Default)]
+struct Linter {
+    columns: HashMap<String, HashMap<String, DataType>>,
+    created: HashSet<String>,
+    foreign_key_checks: bool,
+    issues: Vec<(usize, MigrationIssue)>,
+    script: usize,
+    location: Option<Location>,
+}
+
+impl OnlineMigrationLinter {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self { dialect, server_version: None }
+    }
+
+    /// The major version of the server the migrations run on, such as 10 for PostgreSQL 10 or
+    /// 5 for MySQL 5.7. Without one, the newest behaviour is assumed.
+    pub fn with_server_version(mut self, major: u32) -> Self {
+        self.server_version = Some(major);
+        self
+    }
+
+    pub fn lint(&self, sql: &str) -> Vec<MigrationIssue> {
+        self.lint_all(&[sql]).into_iter().map(|(_, issue)| issue).collect()
+    }
+
+    /// Lints scripts run one after another, such as a directory of migrations. Each finding
+    /// comes with the index of the script it is about.
+    pub fn lint_all(&self, scripts: &[&str]) -> Vec<(usize, MigrationIssue)> {
+        let mut linter = Linter { foreign_key_checks: true, ..Linter::default() };
+        for (index, sql) in scripts.iter().enumerate() {
+            let Ok(tokens) = Lexer::for_dialect(sql, &self.dialect).tokenize() else {
+                continue;
+            };
+            let (statements, _) = Parser::new(tokens).parse_recovering();
+            linter.script = index;
+            linter.created.clear();
+            self.lint_statements(&mut linter, &statements);
+        }
+        linter.issues
+    }
+
+    /// Lints statements that were already parsed, as one script.
+    pub fn analyze(&self, statements: &[AstNode<Statement>]) -> Vec<MigrationIssue> {
+        let mut linter = Linter { foreign_key_checks: true, ..Linter::default() };
+        self.lint_statements(&mut linter, statements);
+        linter.issues.into_iter().map(|(_, issue)| issue).collect()
+    }
+
+    fn lint_statements(&self, linter: &mut Linter, statements: &[AstNode<Statement>]) {
+        for statement in statements {
+            linter.location = statement.location.clone();
+            match &statement.node {
+                Statement::Ddl(DdlStatement::CreateTable(table)) => {
+                    let name = table.table_name.object.to_lowercase();
+                    let columns = table.columns.iter().map(|column| (column.name.to_lowercase(), column.data_type.clone()));
+                    linter.columns.insert(name.clone(), columns.collect());
+                    linter.created.insert(name);
+                }
+                Statement::Ddl(DdlStatement::DropTable(drop)) => {
+                    for table in &drop.table_names {
+                        linter.columns.remove(&table.object.to_lowercase());
+                        linter.created.remove(&table.object.to_lowercase());
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateIndex(index)) => self.create_index(linter, index),
+                Statement::Ddl(DdlStatement::AlterTable(alter)) => match self.dialect {
+                    SqlDialect::PostgreSQL => self.alter_postgres(linter, alter),
+                    SqlDialect::MySQL => self.alter_mysql(linter, alter),
+                    _ => {}
+                },
+                // mysqldump and hand-written migrations turn the checks off around foreign keys
+                Statement::Session(SessionStatement::Set { variable, value }) => {
+                    let variable = variable.trim_start_matches('@').rsplit('.').next().unwrap_or(variable);
+                    if variable.eq_ignore_ascii_case("foreign_key_checks") {
+                        linter.foreign_key_checks = !matches!(value.trim().to_uppercase().as_str(), "0" | "OFF" | "FALSE");
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn create_index(&self, linter: &mut Linter, index: &CreateIndexStatement) {
+        let table = index.table_name.object.as_str();
+        if linter.is_new(table) {
+            return;
+        }
+        match self.dialect {
+            SqlDialect::PostgreSQL if !index.concurrently => linter.report(
+                MigrationIssueType::BlockingIndex,
+                IssueSeverity::High,
+                table,
+                format!("CREATE INDEX {} blocks writes to {} until the index is built", index.index_name, table),
+                "use CREATE INDEX CONCURRENTLY, outside a transaction block",
+            ),
+            SqlDialect::MySQL if index.kind != IndexKind::Regular => self.special_index(linter, table, &index.kind),
+            _ => {}
+        }
+    }
+
+    /// MySQL builds most indexes online, but not full-text and spatial ones.
+    fn special_index(&self, linter: &mut Linter, table: &str, kind: &IndexKind) {
+        let (kind, effect) = match kind {
+            IndexKind::FullText => {
+                ("FULLTEXT", "rebuilds the table if it has no full-text index yet, and blocks writes while it runs")
+            }
+            IndexKind::Spatial => ("SPATIAL", "blocks writes to the table while it runs"),
+            IndexKind::Regular => return,
+        };
+        linter.report(
+            MigrationIssueType::BlockingIndex,
+            IssueSeverity::Medium,
+            table,
+            format!("adding a {} index to {} {}", kind, table, effect),
+            "add it at a quiet time, or with an online schema change tool such as gh-ost or pt-online-schema-change",
+        );
+    }
+
+    fn alter_postgres(&self, linter: &mut Linter, alter: &AlterTableStatement) {
+        let table = alter.table_name.object.as_str();
+        let new = linter.is_new(table);
+        for action in &alter.actions {
+            match action {
+                AlterTableAction::AddColumn { column } => {
+                    if !new {
+                        self.add_column_postgres(linter, table, column);
+                    }
+                    linter.set_column(table, column);
+                }
+                AlterTableAction::AlterColumn { column_name, new_data_type, add_constraints, .. } => {
+                    if let Some(data_type) = new_data_type {
+                        let rewrites = linter.column_type(table, column_name).is_none_or(|old| !postgres_coerces(old, data_type));
+                        if !new && rewrites {
+                            linter.report(
+                                MigrationIssueType::AlterColumnType,
+                                IssueSeverity::High,
+                                table,
+                                format!(
+                                    "changing the type of {}.{} rewrites {} and its indexes while holding an exclusive lock",
+                                    table, column_name, table
+                                ),
+                                "add a column of the new type, backfill it in batches, move readers and writers over to \
+                                 it, then drop the old column",
+                            );
+                        }
+                        linter.set_column_type(table, column_name, data_type);
+                    }
+                    if !new && add_constraints.contains(&ColumnConstraint::NotNull) {
+                        linter.report(
+                            MigrationIssueType::SetNotNull,
+                            IssueSeverity::Medium,
+                            table,
+                            format!("SET NOT NULL on {}.{} scans {} while holding an exclusive lock", table, column_name, table),
+                            format!(
+                                "add CHECK ({} IS NOT NULL) NOT VALID, VALIDATE it in a separate transaction, then SET NOT \
+                                 NULL, which PostgreSQL 12 and later do without a scan",
+                                column_name
+                            ),
+                        );
+                    }
+                }
+                AlterTableAction::AddConstraint { constraint, not_valid } if !new => {
+                    self.add_constraint_postgres(linter, table, constraint, *not_valid);
+                }
+                AlterTableAction::RenameTable { new_name } => linter.rename_table(table, new_name),
+                _ => {}
+            }
+        }
+    }
+
+    fn add_column_postgres(&self, linter: &mut Linter, table: &str, column: &ColumnDefinition) {
+        let default = column.constraints.iter().find_map(|constraint| match constraint {
+            ColumnConstraint::Default(value) => Some(value.as_str()),
+            _ => None,
+        });
+        let serial = column.type_name.as_deref().is_some_and(|name| name.to_uppercase().ends_with("SERIAL"));
+        let filled = serial
+            || column.constraints.iter().any(|constraint| match constraint {
+                ColumnConstraint::Identity(_) | ColumnConstraint::AutoIncrement => true,
+                ColumnConstraint::Generated { stored, .. } => *stored,
+                _ => false,
+            });
+        let name = format!("{}.{}", table, column.name);
+
+        let volatile = default.is_some_and(|value| {
+            let value = value.to_lowercase();
+            VOLATILE_DEFAULTS.iter().any(|function| value.contains(function))
+        });
+        // PostgreSQL 11 stores a constant default once instead of writing it into every row
+        let constant_default_rewrites = self.server_version.is_some_and(|major| major < 11);
+        if filled || volatile || (default.is_some() && constant_default_rewrites) {
+            let reason = if filled {
+                "fills every existing row"
+            } else if volatile {
+                "has a volatile default, which is computed for every existing row"
+            } else {
+                "has a default, which PostgreSQL before 11 writes into every existing row"
+            };
+            linter.report(
+                MigrationIssueType::AddColumnRewrite,
+                IssueSeverity::High,
+                table,
+                format!("adding {} rewrites {} while holding an exclusive lock, since it {}", name, table, reason),
+                "add the column without a default, set the default in a separate statement so only new rows get it, \
+                 and backfill existing rows in batches",
+            );
+            return;
+        }
+
+        let not_null = column
+            .constraints
+            .iter()
+            .any(|constraint| matches!(constraint, ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey));
+        if not_null && default.is_none() {
+            linter.report(
+                MigrationIssueType::AddColumnNotNull,
+                IssueSeverity::High,
+                table,
+                format!("adding {} as NOT NULL without a default fails if {} has rows", name, table),
+                "add the column as nullable, backfill it in batches, then make it NOT NULL",
+            );
+        }
+    }
+
+    fn add_constraint_postgres(&self, linter: &mut Linter, table: &str, constraint: &TableConstraint, not_valid: bool) {
+        match constraint {
+            TableConstraint::ForeignKey { referenced_table, .. } if !not_valid => linter.report(
+                MigrationIssueType::ForeignKeyValidation,
+                IssueSeverity::High,
+                table,
+                format!(
+                    "adding a foreign key to {} checks every row while writes to {} and {} wait",
+                    table, table, referenced_table.object
+                ),
+                "add it with NOT VALID, then VALIDATE CONSTRAINT in a separate transaction, which lets writes continue",
+            ),
+            TableConstraint::Check { .. } if !not_valid => linter.report(
+                MigrationIssueType::CheckValidation,
+                IssueSeverity::Medium,
+                table,
+                format!("adding a check constraint to {} checks every row while holding an exclusive lock", table),
+                "add it with NOT VALID, then VALIDATE CONSTRAINT in a separate transaction, which lets writes continue",
+            ),
+            TableConstraint::PrimaryKey { .. } | TableConstraint::Unique { .. } => {
+                let kind = if matches!(constraint, TableConstraint::PrimaryKey { .. }) { "PRIMARY KEY" } else { "UNIQUE" };
+                linter.report(
+                    MigrationIssueType::BlockingUniqueConstraint,
+                    IssueSeverity::High,
+                    table,
+                    format!("adding a {} constraint to {} builds its index while holding an exclusive lock", kind, table),
+                    format!(
+                        "CREATE UNIQUE INDEX CONCURRENTLY first, then ADD CONSTRAINT name {} USING INDEX index_name",
+                        kind
+                    ),
+                );
+            }
+            TableConstraint::Exclude { .. } => linter.report(
+                MigrationIssueType::BlockingUniqueConstraint,
+                IssueSeverity::High,
+                table,
+                format!("adding an exclusion constraint to {} builds its index while holding an exclusive lock", table),
+                "add it at a quiet time; exclusion constraints cannot be built concurrently",
+            ),
+            _ => {}
+        }
+    }
+
+    fn alter_mysql(&self, linter: &mut Linter, alter: &AlterTableStatement) {
+        let table = alter.table_name.object.as_str();
+        let options: HashMap<String, String> = alter
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                AlterTableAction::Other(text) => {
+                    let (option, value) = text.split_once('=')?;
+                    Some((option.trim().to_uppercase(), value.trim().to_uppercase()))
+                }
+                _ => None,
+            })
+            .collect();
+        // With ALGORITHM=INPLACE or INSTANT, MySQL refuses the statement rather than copying
+        let online = options.get("ALGORITHM").is_some_and(|algorithm| algorithm != "COPY" && algorithm != "DEFAULT");
+        let instant_columns = self.server_version.is_none_or(|major| major >= 8);
+        let mut copies = Vec::new();
+        let mut rebuilds = Vec::new();
+        let drops_primary_key = alter.actions.iter().any(|action| {
+            matches!(
+                action,
+                AlterTableAction::DropConstraint { constraint_name, .. } if constraint_name.eq_ignore_ascii_case("PRIMARY")
+            )
+        });
+        let adds_primary_key = alter.actions.iter().any(|action| {
+            matches!(action, AlterTableAction::AddConstraint { constraint: TableConstraint::PrimaryKey { .. }, .. })
+        });
+
+        for action in &alter.actions {
+            match action {
+                AlterTableAction::AddColumn { column } => {
+                    if !instant_columns {
+                        rebuilds.push(format!("adding column {}", column.name));
+                    }
+                    linter.set_column(table, column);
+                }
+                AlterTableAction::DropColumn { column_name, .. } if !instant_columns => {
+                    rebuilds.push(format!("dropping column {}", column_name));
+                }
+                AlterTableAction::ModifyColumn { column } => {
+                    let old = linter.column_type(table, &column.name);
+                    if old.is_none_or(|old| !mysql_in_place(old, &column.data_type)) {
+                        copies.push(format!("changing the type of column {}", column.name));
+                    }
+                    linter.set_column(table, column);
+                }
+                AlterTableAction::AlterColumn { column_name, new_data_type: Some(data_type), .. } => {
+                    copies.push(format!("changing the type of column {}", column_name));
+                    linter.set_column_type(table, column_name, data_type);
+                }
+                AlterTableAction::AddConstraint { constraint: TableConstraint::ForeignKey { .. }, .. }
+                    if linter.foreign_key_checks =>
+                {
+                    copies.push("adding a foreign key while foreign_key_checks is on".to_string());
+                }
+                AlterTableAction::AddConstraint { constraint: TableConstraint::PrimaryKey { .. }, .. } => {
+                    if drops_primary_key {
+                        rebuilds.push("replacing the primary key".to_string());
+                    } else {
+                        rebuilds.push("adding a primary key".to_string());
+                    }
+                }
+                AlterTableAction::AddConstraint { constraint: TableConstraint::Index { kind, .. }, .. }
+                    if *kind != IndexKind::Regular && !linter.is_new(table) =>
+                {
+                    self.special_index(linter, table, kind);
+                }
+                AlterTableAction::DropConstraint { constraint_name, .. }
+                    if constraint_name.eq_ignore_ascii_case("PRIMARY") && !adds_primary_key =>
+                {
+                    copies.push("dropping the primary key".to_string());
+                }
+                AlterTableAction::RenameTable { new_name } => linter.rename_table(table, new_name),
+                AlterTableAction::Other(text) => {
+                    let text = text.to_uppercase();
+                    if text.starts_with("CONVERT TO") {
+                        copies.push("converting the character set".to_string());
+                    } else if text.starts_with("ENGINE") || text.starts_with("FORCE") {
+                        rebuilds.push("rebuilding the table".to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if linter.is_new(table) {
+            return;
+        }
+
+        if options.get("ALGORITHM").is_some_and(|algorithm| algorithm == "COPY") {
+            copies.insert(0, "ALGORITHM=COPY".to_string());
+        }
+        let lock = options.get("LOCK").filter(|lock| *lock == "SHARED" || *lock == "EXCLUSIVE");
+        if !copies.is_empty() && !online {
+            linter.report(
+                MigrationIssueType::TableCopy,
+                IssueSeverity::High,
+                table,
+                format!("{} copies {}, blocking writes until the copy is done", copies.join(", "), table),
+                "state ALGORITHM=INPLACE, LOCK=NONE so MySQL refuses rather than copies, or make the change with an \
+                 online schema change tool such as gh-ost or pt-online-schema-change",
+            );
+        } else if let Some(lock) = lock {
+            linter.report(
+                MigrationIssueType::ExplicitLock,
+                IssueSeverity::Medium,
+                table,
+                format!("LOCK={} blocks writes to {} while the statement runs", lock, table),
+                "use LOCK=NONE, or leave LOCK out so MySQL takes the least lock it can",
+            );
+        } else if !rebuilds.is_empty() {
+            linter.report(
+                MigrationIssueType::TableRebuild,
+                IssueSeverity::Low,
+                table,
+                format!(
+                    "{} rebuilds {}; writes continue, but it takes time and space in proportion to the table",
+                    rebuilds.join(", "),
+                    table
+                ),
+                "run it at a quiet time, or with an online schema change tool such as gh-ost or pt-online-schema-change",
+            );
+        }
+    }
+}
+
+impl Linter {
+    fn is_new(&self, table: &str) -> bool {
+        self.created.contains(&table.to_lowercase())
+    }
+
+    fn column_type(&self, table: &str, column: &str) -> Option<&DataType> {
+        self.columns.get(&table.to_lowercase())?.get(&column.to_lowercase())
+    }
+
+    fn set_column(&mut self, table: &str, column: &ColumnDefinition) {
+        self.set_column_type(table, &column.name, &column.data_type);
+    }
+
+    fn set_column_type(&mut self, table: &str, column: &str, data_type: &DataType) {
+        if let Some(columns) = self.columns.get_mut(&table.to_lowercase()) {
+            columns.insert(column.to_lowercase(), data_type.clone());
+        }
+    }
+
+    fn rename_table(&mut self, table: &str, new_name: &str) {
+        let (old, new) = (table.to_lowercase(), new_name.to_lowercase());
+        if let Some(columns) = self.columns.remove(&old) {
+            self.columns.insert(new.clone(), columns);
+        }
+        if self.created.remove(&old) {
+            self.created.insert(new);
+        }
+    }
+
+    fn report(
+        &mut self,
+        issue_type: MigrationIssueType,
+        severity: IssueSeverity,
+        table: &str,
+        description: String,
+        suggested_fix: impl Into<String>,
+    ) {
+        let issue = MigrationIssue {
+            issue_type,
+            severity,
+            table: table.to_string(),
+            description,
+            suggested_fix: suggested_fix.into(),
+            location: self.location.clone(),
+        };
+        self.issues.push((self.script, issue));
+    }
+}
+
+/// Whether PostgreSQL changes the type without rewriting the table: a longer or unbounded
+/// `VARCHAR`, `VARCHAR` to `TEXT`, or a `NUMERIC` with more digits and the same scale.
+fn postgres_coerces(old: &DataType, new: &DataType) -> bool {
+    match (old, new) {
+        (DataType::Varchar { length: Some(old) }, DataType::Varchar { length: Some(new) }) => new >= old,
+        (DataType::Varchar { .. } | DataType::Text, DataType::Varchar { length: None } | DataType::Text) => true,
+        (
+            DataType::Decimal { precision: Some(old), scale: old_scale },
+            DataType::Decimal { precision: new, scale: new_scale },
+        ) => old_scale == new_scale && new.is_none_or(|new| new >= *old),
+        _ => old == new,
+    }
+}
+
+/// Whether MySQL changes the column in place: the same type, or a longer `VARCHAR` whose
+/// length still fits in as many length bytes (one up to 255, two above).
+fn mysql_in_place(old: &DataType, new: &DataType) -> bool {
+    match (old, new) {
+        (DataType::Varchar { length: Some(old) }, DataType::Varchar { length: Some(new) }) => {
+            new >= old && (*old > 255 || *new <= 255)
+        }
+        _ => old == new,
+    }
+}
@@ -0,0 +1,992 @@
+use crate::core::analyze::infer::{self, Typed};
+use crate::core::analyze::semantic::is_system_table;
+use crate::core::analyze::rules;
+use crate::core::analyze::scope::{self, resolve_qualifier};
+use crate::core::ast::{
+    AstNode, AstValidationError, BinaryOperator, ConflictAction, ConflictTarget, DeleteStatement, DmlStatement,
+    Expression, InsertStatement, JoinCondition, JoinType, LiteralValue, Location, SchemaReference, SelectItem,
+    SelectStatement, Statement, TableReference, UnaryOperator, UpdateStatement, ValidationSeverity,
+};
+use crate::core::domain::entity::{Column, DataType, Database, Schema};
+use crate::core::lower::Lowerer;
+use crate::core::parse::{Lexer, Parser, SqlDialect};
+
+/// How many views deep a view's columns are derived from its definition.
+const MAX_VIEW_DEPTH: usize = 8;
+
+/// Names that read like columns but are niladic functions or keywords.
+pub(crate) const NILADIC_FUNCTIONS: &[&str] = &[
+    "current_date",
+    "current_time",
+    "current_timestamp",
+    "localtime",
+    "localtimestamp",
+    "sysdate",
+    "systimestamp",
+    "current_user",
+    "session_user",
+    "system_user",
+    "current_schema",
+    "user",
+    "default",
+    "rownum",
+    "rowid",
+];
+
+/// Checks queries against a schema: every table, alias and column they name must exist,
+/// unqualified columns must be unambiguous, INSERTs must supply as many values as they name
+/// columns, and comparisons must be between values of compatible types.
+pub struct QueryChecker<'a> {
+    database: &'a Database,
+    lowerer: Lowerer,
+}
+
+/// A column a query produces, or one of a table it reads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// `None` when the type can't be told, as for a bind parameter
+    pub data_type: Option<DataType>,
+    /// Whether the column may be NULL, including because of an outer join
+    pub nullable: bool,
+}
+
+/// The columns a query produces; `complete` is false when some of them come from a table
+/// whose columns aren't known, such as an unknown table or a catalog view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    pub columns: Vec<ColumnInfo>,
+    pub complete: bool,
+}
+
+/// A bind parameter of a query, typed from the column it is compared with or assigned to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParameter {
+    /// As written: `$1`, `?` or `:name`
+    pub placeholder: String,
+    /// Where the value goes in the list of values bound to the query, from 1
+    pub position: usize,
+    /// The placeholder's own name, or else that of the column it stands in for
+    pub name: String,
+    /// `None` when nothing the parameter meets has a known type
+    pub data_type: Option<DataType>,
+    /// Whether NULL may be passed, as when the parameter is assigned to a nullable column
+    pub nullable: bool,
+}
+
+type Relation = scope::Relation<ColumnInfo>;
+
+#[derive(Debug, Default)]
+struct Scope {
+    relations: Vec<Relation>,
+    /// Columns merged by `JOIN ... USING`, which may be named unqualified
+    using: Vec<String>,
+    /// Output columns, which ORDER BY and GROUP BY may refer to by alias
+    outputs: Vec<ColumnInfo>,
+    /// Whether the query has a GROUP BY, which keeps aggregates over non-null columns non-null
+    grouped: bool,
+}
+
+/// One occurrence of a bind parameter, identified by where it sits in the statement.
+struct Binding {
+    expression: *const Expression,
+    placeholder: String,
+    typed: Typed,
+    hint: Option<String>,
+    bound: bool,
+}
+
+struct Resolver<'c, 'a> {
+    checker: &'c QueryChecker<'a>,
+    ctes: Vec<(String, Projection)>,
+    scopes: Vec<Scope>,
+    diagnostics: Vec<AstValidationError>,
+    location: Option<Location>,
+    view_depth: usize,
+    /// What the last RETURNING clause produced
+    returning: Option<Projection>,
+    bindings: Vec<Binding>,
+}
+
+impl<'a> QueryChecker<'a> {
+    pub fn new(database: &'a Database, dialect: SqlDialect) -> Self {
+        Self { database, lowerer: Lowerer::new(dialect) }
+    }
+
+    /// Parses and checks a file of queries; syntax errors are reported as diagnostics too.
+    /// Statements other than SELECT, INSERT, UPDATE and DELETE are ignored.
+    pub fn check(&self, sql: &str) -> Vec<AstValidationError> {
+        let syntax_error = |message: String, position| AstValidationError {
+            message,
+            location: Some(Location { start: position, end: Default::default() }),
+            severity: ValidationSeverity::Error,
+            rule: Some(rules::SYNTAX.to_string()),
+        };
+        let tokens = match Lexer::for_dialect(sql, self.lowerer.dialect()).tokenize() {
+            Ok(tokens) => tokens,
+            Err(error) => return vec![syntax_error(error.message, error.position)],
+        };
+        let (statements, errors) = Parser::new(tokens).parse_recovering();
+        let mut diagnostics: Vec<AstValidationError> =
+            errors.into_iter().map(|error| syntax_error(error.message, error.position)).collect();
+        for statement in &statements {
+            diagnostics.extend(self.check_node(statement));
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.location.as_ref().map(|location| location.start.index));
+        diagnostics
+    }
+
+    /// Checks one parsed statement, locating its diagnostics at the statement.
+    pub fn check_node(&self, statement: &AstNode<Statement>) -> Vec<AstValidationError> {
+        let mut resolver = Resolver::new(self, statement.location.clone());
+        if let Statement::Dml(dml) = &statement.node {
+            resolver.statement(dml);
+        }
+        resolver.diagnostics
+    }
+
+    pub fn check_statement(&self, statement: &DmlStatement) -> Vec<AstValidationError> {
+        let mut resolver = Resolver::new(self, None);
+        resolver.statement(statement);
+        resolver.diagnostics
+    }
+
+    /// The columns a SELECT produces, with `*` expanded against the schema, their types and
+    /// whether they may be NULL.
+    pub fn projection(&self, select: &SelectStatement) -> Projection {
+        Resolver::new(self, None).select(select)
+    }
+
+    /// The columns a statement returns: a SELECT's, or those of a RETURNING clause.
+    pub fn result_columns(&self, statement: &DmlStatement) -> Projection {
+        let mut resolver = Resolver::new(self, None);
+        match statement {
+            DmlStatement::Select(select) => resolver.select(select),
+            _ => {
+                resolver.statement(statement);
+                resolver.returning.unwrap_or(Projection { columns: Vec::new(), complete: true })
+            }
+        }
+    }
+
+    /// The bind parameters of a statement in the order their values are passed: by number for
+    /// `$1`, by first appearance for `:name`, and one per `?`.
+    pub fn parameters(&self, statement: &DmlStatement) -> Vec<QueryParameter> {
+        let mut resolver = Resolver::new(self, None);
+        resolver.statement(statement);
+
+        let mut parameters: Vec<QueryParameter> = Vec::new();
+        for binding in resolver.bindings {
+            let numbered = binding.placeholder.strip_prefix('$').and_then(|number| number.parse::<usize>().ok());
+            if binding.placeholder != "?"
+                && let Some(parameter) = parameters.iter_mut().find(|parameter| parameter.placeholder == binding.placeholder)
+            {
+                if parameter.data_type.is_none() {
+                    parameter.data_type = binding.typed.data_type;
+                    parameter.nullable = binding.typed.nullable;
+                }
+                continue;
+            }
+            let name = match binding.placeholder.strip_prefix(':') {
+                Some(name) => name.to_string(),
+                None => binding.hint.unwrap_or_else(|| format!("param{}", numbered.unwrap_or(parameters.len() + 1))),
+            };
+            parameters.push(QueryParameter {
+                position: numbered.unwrap_or(parameters.len() + 1),
+                placeholder: binding.placeholder,
+                name,
+                data_type: binding.typed.data_type,
+                nullable: binding.typed.nullable,
+            });
+        }
+        parameters.sort_by_key(|parameter| parameter.position);
+
+        // Two parameters standing in for the same column get told apart by their position
+        for index in 0..parameters.len() {
+            let name = parameters[index].name.clone();
+            if parameters[..index].iter().any(|parameter| parameter.name == name) {
+                parameters[index].name = format!("{}_{}", name, parameters[index].position);
+            }
+        }
+        parameters
+    }
+
+    /// Finds a table or view: in the named schema if qualified, else in the default schema
+    /// before any other.
+    fn find_relation(&self, name: &SchemaReference, view_depth: usize) -> Option<Relation> {
+        let schemas = search_schemas(self.database, &self.lowerer, name);
+        let relation_name = name.object.clone();
+        for schema in schemas {
+            if let Some(table) = schema.tables.iter().find(|table| table.name.eq_ignore_ascii_case(&name.object)) {
+                return Some(Relation { name: relation_name, columns: column_infos(&table.columns), opaque: false });
+            }
+            if let Some(view) = schema.views.iter().find(|view| view.name.eq_ignore_ascii_case(&name.object)) {
+                if !view.columns.is_empty() {
+                    return Some(Relation { name: relation_name, columns: column_infos(&view.columns), opaque: false });
+                }
+                let projection = self.view_projection(&view.definition, view_depth);
+                return Some(Relation {
+                    name: relation_name,
+                    opaque: !projection.complete,
+                    columns: projection.columns,
+                });
+            }
+        }
+        None
+    }
+
+    /// Derives a view's columns from its definition, since the model doesn't list them.
+    fn view_projection(&self, definition: &str, view_depth: usize) -> Projection {
+        let unknown = Projection { columns: Vec::new(), complete: false };
+        if view_depth >= MAX_VIEW_DEPTH {
+            return unknown;
+        }
+        let Ok(tokens) = Lexer::for_dialect(definition, self.lowerer.dialect()).tokenize() else {
+            return unknown;
+        };
+        match Parser::new(tokens).parse().as_deref() {
+            Ok([Statement::Dml(dml)]) if let DmlStatement::Select(select) = dml.as_ref() => {
+                let mut resolver = Resolver::new(self, None);
+                resolver.view_depth = view_depth + 1;
+                resolver.select(select)
+            }
+            _ => unknown,
+        }
+    }
+
+    /// PostgreSQL and the commercial databases refuse to compare unrelated types; MySQL and
+    /// SQLite convert one side, which is rarely what was meant.
+    fn mismatch_severity(&self) -> ValidationSeverity {
+        match self.lowerer.dialect() {
+            SqlDialect::MySQL | SqlDialect::SQLite => ValidationSeverity::Warning,
+            _ => ValidationSeverity::Error,
+        }
+    }
+}
+
+/// The schemas a reference may point into, in the order they are searched: just the named one
+/// when qualified, otherwise every schema with the dialect's default first.
+pub(crate) fn search_schemas<'d>(database: &'d Database, lowerer: &Lowerer, name: &SchemaReference) -> Vec<&'d Schema> {
+    match &name.schema {
+        Some(schema) => {
+            let normalized = lowerer.normalize_schema(schema);
+            database
+                .schemas
+                .iter()
+                .filter(|candidate| match (&candidate.name, &normalized) {
+                    (Some(candidate), _) => candidate.eq_ignore_ascii_case(schema),
+                    (None, normalized) => normalized.is_none(),
+                })
+                .collect()
+        }
+        None => {
+            let default = lowerer.dialect().config().default_schema;
+            let mut schemas: Vec<_> = database.schemas.iter().collect();
+            // The default schema wins, wherever it sits in the model
+            schemas.sort_by_key(|schema| match (&schema.name, &default) {
+                (None, _) => 0,
+                (Some(name), Some(default)) if name.eq_ignore_ascii_case(default) => 0,
+                _ => 1,
+            });
+            schemas
+        }
+    }
+}
+
+impl<'c, 'a> Resolver<'c, 'a> {
+    fn new(checker: &'c QueryChecker<'a>, location: Option<Location>) -> Self {
+        Self {
+            checker,
+            ctes: Vec::new(),
+            scopes: Vec::new(),
+            diagnostics: Vec::new(),
+            location,
+            view_depth: 0,
+            returning: None,
+            bindings: Vec::new(),
+        }
+    }
+
+    fn statement(&mut self, statement: &DmlStatement) {
+        match statement {
+            DmlStatement::Select(select) => {
+                self.select(select);
+            }
+            DmlStatement::Insert(insert) => self.insert(insert),
+            DmlStatement::Update(update) => self.update(update),
+            DmlStatement::Delete(delete) => self.delete(delete),
+        }
+    }
+
+    fn select(&mut self, select: &SelectStatement) -> Projection {
+        let outer_ctes = self.ctes.len();
+        for cte in &select.with {
+            let projection = if cte.recursive {
+                // The anchor, before the UNION, fixes the columns the recursive part sees
+                let anchor = SelectStatement { unions: Vec::new(), ..cte.query.clone() };
+                let anchor = self.quietly(|resolver| resolver.select(&anchor));
+                self.ctes.push((cte.name.clone(), rename_columns(anchor, &cte.columns)));
+                let projection = self.select(&cte.query);
+                self.ctes.pop();
+                projection
+            } else {
+                self.select(&cte.query)
+            };
+            self.ctes.push((cte.name.clone(), rename_columns(projection, &cte.columns)));
+        }
+
+        let mut scope = Scope { grouped: select.group_by.is_some(), ..Scope::default() };
+        let mut conditions = Vec::new();
+        for reference in &select.from {
+            self.add_relations(reference, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        for condition in conditions {
+            self.expression(condition);
+        }
+
+        let mut projection = self.select_items(&select.select_list);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.outputs = projection.columns.clone();
+        }
+        if let Some(condition) = &select.where_clause {
+            self.expression(condition);
+        }
+        if let Some(group_by) = &select.group_by {
+            for expression in &group_by.expressions {
+                self.expression(expression);
+            }
+        }
+        if let Some(having) = &select.having {
+            self.expression(having);
+        }
+        for expression in select.windows.iter().flat_map(|named| named.window.expressions()) {
+            self.expression(expression);
+        }
+        for order in &select.order_by {
+            self.expression(&order.expr);
+        }
+        self.scopes.pop();
+
+        // A column of a set operation may be NULL if it is in any of the queries
+        for union in &select.unions {
+            let other = self.select(&union.select);
+            projection.complete &= other.complete;
+            for (column, other) in projection.columns.iter_mut().zip(other.columns) {
+                column.nullable |= other.nullable;
+                if column.data_type.is_none() {
+                    column.data_type = other.data_type;
+                }
+            }
+        }
+        // LIMIT and OFFSET apply to the whole set operation and take a row count
+        for (count, hint) in [(&select.limit, "limit"), (&select.offset, "offset")] {
+            if let Some(count) = count {
+                self.expression(count);
+                self.bind(count, &Typed::not_null(DataType::BigInt { unsigned: false }), Some(hint.to_string()));
+            }
+        }
+        self.ctes.truncate(outer_ctes);
+        projection
+    }
+
+    fn select_items(&mut self, items: &[SelectItem]) -> Projection {
+        let mut projection = Projection { columns: Vec::new(), complete: true };
+        for item in items {
+            match item {
+                SelectItem::Expression { expr, alias } => {
+                    let typed = self.expression(expr);
+                    let typed = resolve_string_literal(expr, typed);
+                    let name = alias.clone().unwrap_or_else(|| output_name(expr));
+                    projection.columns.push(ColumnInfo { name, data_type: typed.data_type, nullable: typed.nullable });
+                }
+                SelectItem::Wildcard => {
+                    for relation in self.scopes.last().map(|scope| scope.relations.as_slice()).unwrap_or_default() {
+                        projection.columns.extend(relation.columns.iter().cloned());
+                        projection.complete &= !relation.opaque;
+                    }
+                }
+                SelectItem::QualifiedWildcard { qualifier } => match self.relation(qualifier) {
+                    Some(relation) => {
+                        projection.complete &= !relation.opaque;
+                        projection.columns.extend(relation.columns);
+                    }
+                    None => {
+                        projection.complete = false;
+                        self.report(
+                            ValidationSeverity::Error,
+                            rules::UNKNOWN_TABLE,
+                            format!("{}.* names no table or alias in the query", qualifier),
+                        );
+                    }
+                },
+            }
+        }
+        projection
+    }
+
+    /// Adds the relations a FROM item brings into scope; join conditions are collected to be
+    /// checked once they are all there.
+    fn add_relations<'r>(&mut self, reference: &'r TableReference, scope: &mut Scope, conditions: &mut Vec<&'r Expression>) {
+        match reference {
+            TableReference::Table { name, alias } => {
+                let relation = self.table(name);
+                scope.relations.push(relation.aliased(alias.as_ref()));
+            }
+            TableReference::Subquery { query, alias } => {
+                let projection = self.select(query);
+                scope.relations.push(Relation { name: alias.clone(), opaque: !projection.complete, columns: projection.columns });
+            }
+            TableReference::Join { left, joins, .. } => {
+                let start = scope.relations.len();
+                self.add_relations(left, scope, conditions);
+                for join in joins {
+                    let joined = scope.relations.len();
+                    self.add_relations(&join.table, scope, conditions);
+                    // The side an outer join may find no match on reads as NULLs
+                    let nullable = match join.join_type {
+                        JoinType::Left | JoinType::LeftOuter => joined..scope.relations.len(),
+                        JoinType::Right | JoinType::RightOuter => start..joined,
+                        JoinType::Full | JoinType::FullOuter => start..scope.relations.len(),
+                        JoinType::Inner | JoinType::Cross => 0..0,
+                    };
+                    for relation in &mut scope.relations[nullable] {
+                        relation.columns.iter_mut().for_each(|column| column.nullable = true);
+                    }
+                    match &join.condition {
+                        Some(JoinCondition::On(condition)) => conditions.push(condition),
+                        Some(JoinCondition::Using(columns)) => scope.using.extend(columns.iter().cloned()),
+                        None => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves a table name to a CTE, table or view, reporting it when it is none of them.
+    fn table(&mut self, name: &SchemaReference) -> Relation {
+        if !name.is_qualified()
+            && let Some((cte, projection)) = self.ctes.iter().rev().find(|(cte, _)| cte.eq_ignore_ascii_case(&name.object))
+        {
+            return Relation { name: cte.clone(), columns: projection.columns.clone(), opaque: !projection.complete };
+        }
+        if let Some(relation) = self.checker.find_relation(name, self.view_depth) {
+            return relation;
+        }
+        if !is_system_table(name) {
+            self.report(ValidationSeverity::Error, rules::UNKNOWN_TABLE, format!("table {} does not exist", name));
+        }
+        Relation { name: name.object.clone(), columns: Vec::new(), opaque: true }
+    }
+
+    fn relation(&self, qualifier: &str) -> Option<Relation> {
+        resolve_qualifier(self.scopes.iter().map(|scope| scope.relations.as_slice()), qualifier).cloned()
+    }
+
+    /// Checks an expression's column references, returning what it is known to produce.
+    fn expression(&mut self, expression: &Expression) -> Typed {
+        match expression {
+            Expression::Literal(literal) => infer::literal(literal),
+            Expression::Column(name) => self.column(name),
+            Expression::QualifiedColumn { table, column } => self.qualified_column(table, column),
+            Expression::Parameter(placeholder) => {
+                if !self.bindings.iter().any(|binding| std::ptr::eq(binding.expression, expression)) {
+                    self.bindings.push(Binding {
+                        expression,
+                        placeholder: placeholder.clone(),
+                        // Nothing says NULL is wanted until the parameter meets a nullable column
+                        typed: Typed { data_type: None, nullable: false },
+                        hint: None,
+                        bound: false,
+                    });
+                }
+                Typed::unknown()
+            }
+            Expression::Wildcard => Typed::unknown(),
+            Expression::Function { name, args } => {
+                let args: Vec<Typed> = args.iter().map(|arg| self.expression(arg)).collect();
+                infer::function(self.checker.lowerer.dialect(), name, &args, self.grouped())
+            }
+            Expression::BinaryOp { left, op, right } => {
+                let left_type = self.expression(left);
+                let right_type = self.expression(right);
+                let nullable = left_type.nullable || right_type.nullable;
+                match op {
+                    BinaryOperator::Eq
+                    | BinaryOperator::Neq
+                    | BinaryOperator::Lt
+                    | BinaryOperator::Lte
+                    | BinaryOperator::Gt
+                    | BinaryOperator::Gte => {
+                        self.compare(left, &left_type, right, &right_type);
+                        Typed::new(DataType::Boolean, nullable)
+                    }
+                    BinaryOperator::Like | BinaryOperator::NotLike | BinaryOperator::ILike | BinaryOperator::NotILike => {
+                        self.bind(right, &Typed::not_null(DataType::Text), hint(left));
+                        Typed::new(DataType::Boolean, nullable)
+                    }
+                    BinaryOperator::And | BinaryOperator::Or => Typed::new(DataType::Boolean, nullable),
+                    BinaryOperator::Concat => Typed::new(DataType::Text, nullable),
+                    // A missing key gives NULL
+                    BinaryOperator::JsonGetText | BinaryOperator::JsonPathText => Typed::new(DataType::Text, true),
+                    BinaryOperator::JsonGet | BinaryOperator::JsonPath => Typed { data_type: left_type.data_type, nullable: true },
+                    _ => {
+                        // `balance + $1` takes a number like the other side
+                        for (side, other, other_type) in [(left, right, &right_type), (right, left, &left_type)] {
+                            if other_type.data_type.as_ref().is_some_and(DataType::is_numeric) {
+                                let typed = Typed { nullable: false, ..other_type.clone() };
+                                self.bind(side, &typed, hint(other));
+                            }
+                        }
+                        infer::arithmetic(&left_type, &right_type)
+                    }
+                }
+            }
+            Expression::UnaryOp { op: UnaryOperator::Not, expr } => {
+                let typed = self.expression(expr);
+                Typed::new(DataType::Boolean, typed.nullable)
+            }
+            Expression::UnaryOp { expr, .. } => self.expression(expr),
+            Expression::Between { expr, min, max } | Expression::NotBetween { expr, min, max } => {
+                let expr_type = self.expression(expr);
+                let mut nullable = expr_type.nullable;
+                for bound in [min, max] {
+                    let bound_type = self.expression(bound);
+                    self.compare(expr, &expr_type, bound, &bound_type);
+                    nullable |= bound_type.nullable;
+                }
+                Typed::new(DataType::Boolean, nullable)
+            }
+            Expression::In { expr, values } | Expression::NotIn { expr, values } => {
+                let expr_type = self.expression(expr);
+                let mut nullable = expr_type.nullable;
+                for value in values {
+                    let value_type = self.expression(value);
+                    self.compare(expr, &expr_type, value, &value_type);
+                    nullable |= value_type.nullable;
+                }
+                Typed::new(DataType::Boolean, nullable)
+            }
+            Expression::IsDistinctFrom { left, right, .. } => {
+                let left_type = self.expression(left);
+                let right_type = self.expression(right);
+                self.compare(left, &left_type, right, &right_type);
+                Typed::not_null(DataType::Boolean)
+            }
+            Expression::IsNull { expr, .. } => {
+                self.expression(expr);
+                Typed::not_null(DataType::Boolean)
+            }
+            Expression::IsTrue { expr, .. } | Expression::IsFalse { expr, .. } => {
+                self.expression(expr);
+                self.bind(expr, &Typed::new(DataType::Boolean, true), None);
+                Typed::not_null(DataType::Boolean)
+            }
+            Expression::LikeEscape { expr, pattern, escape, .. } => {
+                let parts: Vec<Typed> = [expr, pattern, escape].into_iter().map(|part| self.expression(part)).collect();
+                self.bind(pattern, &Typed::not_null(DataType::Text), hint(expr));
+                let nullable = parts.iter().any(|part| part.nullable);
+                Typed::new(DataType::Boolean, nullable)
+            }
+            Expression::Exists(query) => {
+                self.select(query);
+                Typed::not_null(DataType::Boolean)
+            }
+            // A scalar subquery that finds no row gives NULL
+            Expression::Subquery(query) => Typed {
+                data_type: self.select(query).columns.into_iter().next().and_then(|column| column.data_type),
+                nullable: true,
+            },
+            Expression::Case { operand, when_clauses, else_expr } => {
+                let operand_type = operand.as_ref().map(|operand| self.expression(operand));
+                let mut results = Vec::new();
+                for clause in when_clauses {
+                    let condition_type = self.expression(&clause.condition);
+                    if let (Some(operand), Some(operand_type)) = (operand, &operand_type) {
+                        self.compare(operand, operand_type, &clause.condition, &condition_type);
+                    }
+                    let result = self.expression(&clause.result);
+                    results.push(resolve_string_literal(&clause.result, result));
+                }
+                // Without ELSE, a row no branch matches gives NULL
+                let else_type = match else_expr {
+                    Some(expr) => {
+                        let typed = self.expression(expr);
+                        resolve_string_literal(expr, typed)
+                    }
+                    None => Typed::unknown(),
+                };
+                results.push(else_type);
+                Typed {
+                    data_type: infer::common_type(&results),
+                    nullable: results.iter().any(|result| result.nullable),
+                }
+            }
+            Expression::Cast { expr, data_type } => {
+                let typed = self.expression(expr);
+                let data_type = self.checker.lowerer.lower_type_name(data_type);
+                self.bind(expr, &Typed::not_null(data_type.clone()), None);
+                Typed::new(data_type, typed.nullable)
+            }
+            Expression::Subscript { expr, index } => {
+                let expr_type = self.expression(expr);
+                self.expression(index);
+                // An index past the end gives NULL
+                match expr_type.data_type {
+                    Some(DataType::Array { element_type, .. }) => Typed::new(*element_type, true),
+                    _ => Typed::unknown(),
+                }
+            }
+            Expression::Array(elements) => {
+                let elements: Vec<Typed> = elements.iter().map(|element| self.expression(element)).collect();
+                match infer::common_type(&elements) {
+                    Some(element_type) => Typed::not_null(DataType::Array { element_type: Box::new(element_type), dimensions: None }),
+                    None => Typed { data_type: None, nullable: false },
+                }
+            }
+            Expression::WindowFunction { function, window } => {
+                for expression in window.expressions() {
+                    self.expression(expression);
+                }
+                let typed = self.expression(function);
+                // An aggregate over a window frame may see no rows at all
+                match function.as_ref() {
+                    Expression::Function { name, .. } if !name.eq_ignore_ascii_case("count") && function.is_aggregate() => {
+                        Typed { nullable: true, ..typed }
+                    }
+                    _ => typed,
+                }
+            }
+        }
+    }
+
+    fn grouped(&self) -> bool {
+        self.scopes.last().is_some_and(|scope| scope.grouped)
+    }
+
+    /// Resolves an unqualified column in the innermost query that has one by that name.
+    fn column(&mut self, name: &str) -> Typed {
+        for scope in self.scopes.iter().rev() {
+            let matches: Vec<&Relation> = scope
+                .relations
+                .iter()
+                .filter(|relation| relation.columns.iter().any(|column| column.name.eq_ignore_ascii_case(name)))
+                .collect();
+            match matches.as_slice() {
+                [relation] => return column_type(relation, name),
+                [first, ..] if scope.using.iter().any(|using| using.eq_ignore_ascii_case(name)) => {
+                    return column_type(first, name);
+                }
+                [_, _, ..] => {
+                    let candidates: Vec<String> =
+                        matches.iter().map(|relation| format!("{}.{}", relation.name, name)).collect();
+                    let message = format!("column {} is ambiguous: it could be {}", name, candidates.join(" or "));
+                    self.report(ValidationSeverity::Error, rules::AMBIGUOUS_COLUMN, message);
+                    return Typed::unknown();
+                }
+                [] => {}
+            }
+            if let Some(output) = scope.outputs.iter().find(|output| output.name.eq_ignore_ascii_case(name)) {
+                return Typed { data_type: output.data_type.clone(), nullable: output.nullable };
+            }
+            // A table whose columns aren't known might have it
+            if scope.relations.iter().any(|relation| relation.opaque) {
+                return Typed::unknown();
+            }
+        }
+
+        if NILADIC_FUNCTIONS.iter().any(|function| function.eq_ignore_ascii_case(name)) {
+            return infer::function(self.checker.lowerer.dialect(), name, &[], true);
+        }
+        let relations: Vec<String> = self
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.relations.iter().map(|relation| relation.name.clone()))
+            .collect();
+        let message = match relations.as_slice() {
+            [] => format!("column {} does not exist", name),
+            _ => format!("column {} does not exist in {}", name, relations.join(", ")),
+        };
+        self.report(ValidationSeverity::Error, rules::UNKNOWN_COLUMN, message);
+        Typed::unknown()
+    }
+
+    fn qualified_column(&mut self, qualifier: &str, name: &str) -> Typed {
+        let Some(relation) = self.relation(qualifier) else {
+            let message = format!("{}.{} names no table or alias in the query", qualifier, name);
+            self.report(ValidationSeverity::Error, rules::UNKNOWN_TABLE, message);
+            return Typed::unknown();
+        };
+        if relation.opaque || relation.columns.iter().any(|column| column.name.eq_ignore_ascii_case(name)) {
+            return column_type(&relation, name);
+        }
+        let message = format!("column {}.{} does not exist", relation.name, name);
+        self.report(ValidationSeverity::Error, rules::UNKNOWN_COLUMN, message);
+        Typed::unknown()
+    }
+
+    /// Reports a comparison between values that can't be compared without a cast.
+    fn compare(&mut self, left: &Expression, left_type: &Typed, right: &Expression, right_type: &Typed) {
+        // NULL matches nothing, so a parameter compared with a column is never NULL
+        self.bind(left, &Typed { nullable: false, ..right_type.clone() }, hint(right));
+        self.bind(right, &Typed { nullable: false, ..left_type.clone() }, hint(left));
+        let (left_type, right_type) = (&left_type.data_type, &right_type.data_type);
+        let mismatch = match (left_type, right_type) {
+            (Some(left_type), Some(right_type)) => !infer::comparable(left_type, right_type),
+            // A quoted literal takes the other side's type, unless it can't be read as one
+            (Some(data_type), None) => is_non_numeric_string(right) && data_type.is_numeric(),
+            (None, Some(data_type)) => is_non_numeric_string(left) && data_type.is_numeric(),
+            (None, None) => false,
+        };
+        if !mismatch {
+            return;
+        }
+        let describe = |expression: &Expression, data_type: &Option<DataType>| match data_type {
+            Some(data_type) => format!("{} ({})", describe(expression), data_type.to_sql()),
+            None => describe(expression),
+        };
+        let message = format!("{} is compared with {}", describe(left, left_type), describe(right, right_type));
+        self.report(self.checker.mismatch_severity(), rules::TYPE_MISMATCH, message);
+    }
+
+    fn insert(&mut self, insert: &InsertStatement) {
+        let target = self.table(&insert.table_name);
+        let mut columns: Vec<Option<ColumnInfo>> = Vec::new();
+        for column in &insert.columns {
+            columns.push(self.target_column(&target, column));
+        }
+        if insert.columns.is_empty() {
+            columns = target.columns.iter().cloned().map(Some).collect();
+        }
+        let expected = if insert.columns.is_empty() { target.columns.len() } else { insert.columns.len() };
+        let check_arity = !target.opaque || !insert.columns.is_empty();
+        // PostgreSQL fills the columns left out at the end of an unlisted INSERT with their defaults
+        let fewer_allowed = insert.columns.is_empty() && *self.checker.lowerer.dialect() == SqlDialect::PostgreSQL;
+
+        for row in &insert.values {
+            for (value, column) in row.iter().zip(columns.iter().map(Some).chain(std::iter::repeat(None))) {
+                self.expression(value);
+                if let Some(Some(column)) = column {
+                    let typed = Typed { data_type: column.data_type.clone(), nullable: column.nullable };
+                    self.bind(value, &typed, Some(column.name.clone()));
+                }
+            }
+            if check_arity && (row.len() > expected || (row.len() < expected && !fewer_allowed)) {
+                self.arity(&target.name, expected, row.len(), "VALUES row");
+            }
+        }
+        if let Some(select) = &insert.select {
+            let projection = self.select(select);
+            if check_arity && projection.complete && projection.columns.len() != expected {
+                self.arity(&target.name, expected, projection.columns.len(), "SELECT");
+            }
+        }
+
+        if let Some(on_conflict) = &insert.on_conflict {
+            if let Some(ConflictTarget::Columns { columns, where_clause }) = &on_conflict.target {
+                for column in columns {
+                    self.target_column(&target, column);
+                }
+                self.scopes.push(Scope { relations: vec![target.clone()], ..Scope::default() });
+                if let Some(condition) = where_clause {
+                    self.expression(condition);
+                }
+                self.scopes.pop();
+            }
+            if let ConflictAction::DoUpdate { assignments, where_clause } = &on_conflict.action {
+                // PostgreSQL's row that failed to go in
+                let excluded = Relation { name: "excluded".to_string(), ..target.clone() };
+                self.scopes.push(Scope { relations: vec![target.clone(), excluded], ..Scope::default() });
+                for assignment in assignments {
+                    self.assignment(&target, &assignment.column, &assignment.value);
+                }
+                if let Some(condition) = where_clause {
+                    self.expression(condition);
+                }
+                self.scopes.pop();
+            }
+        }
+        self.scopes.push(Scope { relations: vec![target], ..Scope::default() });
+        self.returning = Some(self.select_items(&insert.returning));
+        self.scopes.pop();
+    }
+
+    fn update(&mut self, update: &UpdateStatement) {
+        let target = self.table(&update.table_name).aliased(update.alias.as_ref());
+        let mut scope = Scope { relations: vec![target.clone()], ..Scope::default() };
+        let mut conditions = Vec::new();
+        for reference in &update.from {
+            self.add_relations(reference, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        // SET comes before FROM in the statement, so its parameters are bound first
+        for assignment in &update.assignments {
+            self.assignment(&target, &assignment.column, &assignment.value);
+        }
+        for condition in conditions {
+            self.expression(condition);
+        }
+        if let Some(condition) = &update.where_clause {
+            self.expression(condition);
+        }
+        self.returning = Some(self.select_items(&update.returning));
+        self.scopes.pop();
+    }
+
+    fn delete(&mut self, delete: &DeleteStatement) {
+        let target = self.table(&delete.table_name).aliased(delete.alias.as_ref());
+        let mut scope = Scope { relations: vec![target], ..Scope::default() };
+        let mut conditions = Vec::new();
+        for reference in &delete.using {
+            self.add_relations(reference, &mut scope, &mut conditions);
+        }
+        self.scopes.push(scope);
+        for condition in conditions {
+            self.expression(condition);
+        }
+        if let Some(condition) = &delete.where_clause {
+            self.expression(condition);
+        }
+        self.returning = Some(self.select_items(&delete.returning));
+        self.scopes.pop();
+    }
+
+    /// Checks `column = value` in a SET list; MySQL's multi-table form qualifies the column.
+    fn assignment(&mut self, target: &Relation, column: &str, value: &Expression) {
+        let (typed, name) = match column.rsplit_once('.') {
+            Some((qualifier, column)) => (self.qualified_column(qualifier, column), column),
+            None => {
+                let typed = match self.target_column(target, column) {
+                    Some(column) => Typed { data_type: column.data_type, nullable: column.nullable },
+                    None => Typed::unknown(),
+                };
+                (typed, column)
+            }
+        };
+        self.expression(value);
+        self.bind(value, &typed, Some(name.to_string()));
+    }
+
+    /// Gives a bind parameter the type of what it meets, unless something else already did.
+    fn bind(&mut self, expression: &Expression, typed: &Typed, hint: Option<String>) {
+        if !matches!(expression, Expression::Parameter(_)) || typed.data_type.is_none() {
+            return;
+        }
+        if let Some(binding) = self.bindings.iter_mut().find(|binding| std::ptr::eq(binding.expression, expression))
+            && !binding.bound
+        {
+            binding.typed = typed.clone();
+            binding.hint = hint;
+            binding.bound = true;
+        }
+    }
+
+    /// Looks a column up in the table a statement writes to.
+    fn target_column(&mut self, target: &Relation, name: &str) -> Option<ColumnInfo> {
+        if target.opaque {
+            return Some(ColumnInfo { name: name.to_string(), data_type: None, nullable: true });
+        }
+        let column = target.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name)).cloned();
+        if column.is_none() {
+            let message = format!("column {}.{} does not exist", target.name, name);
+            self.report(ValidationSeverity::Error, rules::UNKNOWN_COLUMN, message);
+        }
+        column
+    }
+
+    fn arity(&mut self, table: &str, expected: usize, found: usize, source: &str) {
+        let message = format!("INSERT into {} expects {} value(s) per row but the {} has {}", table, expected, source, found);
+        self.report(ValidationSeverity::Error, rules::INSERT_ARITY, message);
+    }
+
+    /// Runs `f` without keeping what it reports, for looking at part of a query twice.
+    fn quietly<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let reported = self.diagnostics.len();
+        let bound = self.bindings.len();
+        let result = f(self);
+        self.diagnostics.truncate(reported);
+        self.bindings.truncate(bound);
+        result
+    }
+
+    fn report(&mut self, severity: ValidationSeverity, rule: &str, message: String) {
+        self.diagnostics.push(AstValidationError {
+            message,
+            location: self.location.clone(),
+            severity,
+            rule: Some(rule.to_string()),
+        });
+    }
+}
+
+fn column_infos(columns: &[Column]) -> Vec<ColumnInfo> {
+    columns
+        .iter()
+        .map(|column| ColumnInfo {
+            name: column.name.clone(),
+            data_type: Some(column.data_type.clone()),
+            nullable: column.nullable,
+        })
+        .collect()
+}
+
+fn column_type(relation: &Relation, name: &str) -> Typed {
+    match relation.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name)) {
+        Some(column) => Typed { data_type: column.data_type.clone(), nullable: column.nullable },
+        None => Typed::unknown(),
+    }
+}
+
+/// Applies a CTE's column list, `WITH totals (id, amount) AS (...)`, to its query's columns.
+fn rename_columns(mut projection: Projection, names: &[String]) -> Projection {
+    for (column, name) in projection.columns.iter_mut().zip(names) {
+        column.name = name.clone();
+    }
+    projection
+}
+
+/// The name a select item gets without an alias, as PostgreSQL names it.
+pub(crate) fn output_name(expression: &Expression) -> String {
+    match expression {
+        Expression::Column(name) | Expression::QualifiedColumn { column: name, .. } => name.clone(),
+        Expression::Function { name, .. } => name.to_lowercase(),
+        Expression::Cast { expr, .. } => output_name(expr),
+        Expression::WindowFunction { function, .. } => output_name(function),
+        _ => "?column?".to_string(),
+    }
+}
+
+/// A quoted literal that ends up as a value of its own, rather than compared with or assigned
+/// to something typed, is text.
+fn resolve_string_literal(expression: &Expression, typed: Typed) -> Typed {
+    match expression {
+        Expression::Literal(LiteralValue::String(_)) if typed.data_type.is_none() => Typed::not_null(DataType::Text),
+        _ => typed,
+    }
+}
+
+/// The column a parameter compared with `expression` stands in for.
+fn hint(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::Column(name) | Expression::QualifiedColumn { column: name, .. } => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn describe(expression: &Expression) -> String {
+    match expression {
+        Expression::Column(name) => name.clone(),
+        Expression::QualifiedColumn { table, column } => format!("{}.{}", table, column),
+        Expression::Literal(LiteralValue::Integer(value)) => value.to_string(),
+        Expression::Literal(LiteralValue::Float(value)) => value.to_string(),
+        Expression::Literal(LiteralValue::String(value)) => format!("'{}'", value),
+        Expression::Literal(LiteralValue::Boolean(value)) => value.to_string().to_uppercase(),
+        Expression::Function { name, .. } => format!("{}(...)", name),
+        Expression::Subquery(_) => "a subquery".to_string(),
+        _ => "an expression".to_string(),
+    }
+}
+
+fn is_non_numeric_string(expression: &Expression) -> bool {
+    matches!(expression, Expression::Literal(LiteralValue::String(value)) if value.trim().parse::<f64>().is_err())
+}
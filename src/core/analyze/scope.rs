@@ -0,0 +1,32 @@
+/// A table, view, CTE or subquery in a FROM clause, under the name the query uses for it.
+/// The query checker and the lineage tracer each keep their own kind of column.
+#[derive(Debug, Clone)]
+pub(crate) struct Relation<C> {
+    pub name: String,
+    pub columns: Vec<C>,
+    /// Set when the columns aren't known, so any column may be read from it
+    pub opaque: bool,
+}
+
+impl<C> Relation<C> {
+    /// The relation under the alias a FROM item or DML target gives it, if any.
+    pub fn aliased(self, alias: Option<&String>) -> Self {
+        match alias {
+            Some(alias) => Self { name: alias.clone(), ..self },
+            None => self,
+        }
+    }
+}
+
+/// The relation a qualifier names, looking outwards from the innermost query, whose
+/// relations come last.
+pub(crate) fn resolve_qualifier<'r, C: 'r>(
+    scopes: impl DoubleEndedIterator<Item = &'r [Relation<C>]>,
+    qualifier: &str,
+) -> Option<&'r Relation<C>> {
+    // `schema.table.column` is written with the table's own name as the last qualifier
+    let qualifier = qualifier.rsplit('.').next().unwrap_or(qualifier);
+    scopes
+        .rev()
+        .find_map(|relations| relations.iter().find(|relation| relation.name.eq_ignore_ascii_case(qualifier)))
+}
@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+
+use crate::core::ast::{
+    AlterTableAction, AlterTableStatement, AstNode, AstValidationError, ColumnConstraint, ColumnDefinition,
+    CreateIndexStatement, CreateTableStatement, CreateViewStatement, DdlStatement, DmlStatement, Location,
+    SchemaReference, Statement, TableConstraint, ValidationSeverity,
+};
+use crate::core::domain::entity::DataType;
+use crate::core::lower::Lowerer;
+use crate::core::parse::{Lexer, Parser, SqlDialect};
+
+/// Rule names carried in [`AstValidationError::rule`].
+pub mod rules {
+    pub const SYNTAX: &str = "syntax";
+    pub const DUPLICATE_TABLE: &str = "duplicate-table";
+    pub const DUPLICATE_COLUMN: &str = "duplicate-column";
+    pub const UNKNOWN_TABLE: &str = "unknown-table";
+    pub const UNKNOWN_COLUMN: &str = "unknown-column";
+    pub const FK_COLUMN_COUNT: &str = "fk-column-count";
+    pub const FK_TARGET_NOT_UNIQUE: &str = "fk-target-not-unique";
+    pub const FK_TYPE_MISMATCH: &str = "fk-type-mismatch";
+    pub const AMBIGUOUS_COLUMN: &str = "ambiguous-column";
+    pub const INSERT_ARITY: &str = "insert-arity";
+    pub const TYPE_MISMATCH: &str = "type-mismatch";
+
+    pub const ALL: [&str; 11] = [
+        SYNTAX,
+        DUPLICATE_TABLE,
+        DUPLICATE_COLUMN,
+        UNKNOWN_TABLE,
+        UNKNOWN_COLUMN,
+        FK_COLUMN_COUNT,
+        FK_TARGET_NOT_UNIQUE,
+        FK_TYPE_MISMATCH,
+        AMBIGUOUS_COLUMN,
+        INSERT_ARITY,
+        TYPE_MISMATCH,
+    ];
+}
+
+/// Schemas whose tables are built in rather than declared by the script.
+const SYSTEM_SCHEMAS: &[&str] = &["pg_catalog", "information_schema", "sys", "mysql", "performance_schema"];
+
+/// Checks that the names a script uses refer to things the script has declared: FK targets
+/// and their columns, key and index columns, the tables views read and the tables and columns
+/// that later statements alter, fill or drop. Statements are checked in order, so a column
+/// used after it was renamed or dropped is reported.
+pub struct SemanticAnalyzer {
+    lowerer: Lowerer,
+}
+
+/// A schema and lower-cased name; `None` is the dialect's default schema.
+type ObjectKey = (Option<String>, String);
+
+#[derive(Debug, Clone)]
+struct TableSymbol {
+    name: String,
+    columns: Vec<ColumnSymbol>,
+    primary_key: Vec<String>,
+    unique_keys: Vec<Vec<String>>,
+    /// Lower-cased former column names and what they were renamed to
+    renamed: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnSymbol {
+    name: String,
+    data_type: DataType,
+}
+
+/// A foreign key whose target had not been declared yet when it was seen.
+struct PendingForeignKey {
+    script: usize,
+    location: Option<Location>,
+    foreign_key: ForeignKeyUse,
+}
+
+#[derive(Clone)]
+struct ForeignKeyUse {
+    table: ObjectKey,
+    columns: Vec<String>,
+    target: SchemaReference,
+    scope: Option<String>,
+    referenced_columns: Vec<String>,
+}
+
+struct Checker<'a> {
+    lowerer: &'a Lowerer,
+    tables: HashMap<ObjectKey, TableSymbol>,
+    views: HashMap<ObjectKey, String>,
+    pending: Vec<PendingForeignKey>,
+    diagnostics: Vec<(usize, AstValidationError)>,
+    script: usize,
+    location: Option<Location>,
+}
+
+impl SemanticAnalyzer {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self { lowerer: Lowerer::new(dialect) }
+    }
+
+    /// Parses and checks one script; syntax errors are reported as diagnostics too.
+    pub fn check(&self, sql: &str) -> Vec<AstValidationError> {
+        self.check_all(&[sql]).into_iter().map(|(_, diagnostic)| diagnostic).collect()
+    }
+
+    /// Checks scripts as if they were run one after another, such as a directory of
+    /// migrations. Each diagnostic comes with the index of the script it is about.
+    pub fn check_all(&self, scripts: &[&str]) -> Vec<(usize, AstValidationError)> {
+        let mut checker = Checker::new(&self.lowerer);
+        for (index, sql) in scripts.iter().enumerate() {
+            checker.script = index;
+            let statements = checker.parse(sql);
+            checker.check_statements(&statements);
+        }
+        checker.finish()
+    }
+
+    /// Checks statements that were already parsed.
+    pub fn analyze(&self, statements: &[AstNode<Statement>]) -> Vec<AstValidationError> {
+        let mut checker = Checker::new(&self.lowerer);
+        checker.check_statements(statements);
+        checker.finish().into_iter().map(|(_, diagnostic)| diagnostic).collect()
+    }
+}
+
+impl<'a> Checker<'a> {
+    fn new(lowerer: &'a Lowerer) -> Self {
+        Self {
+            lowerer,
+            tables: HashMap::new(),
+            views: HashMap::new(),
+            pending: Vec::new(),
+            diagnostics: Vec::new(),
+            script: 0,
+            location: None,
+        }
+    }
+
+    fn parse(&mut self, sql: &str) -> Vec<AstNode<Statement>> {
+        let tokens = match Lexer::for_dialect(sql, self.lowerer.dialect()).tokenize() {
+            Ok(tokens) => tokens,
+            Err(error) => {
+                self.location = Some(Location { start: error.position.clone(), end: error.position });
+                self.report(ValidationSeverity::Error, rules::SYNTAX, error.message);
+                return Vec::new();
+            }
+        };
+        let (statements, errors) = Parser::new(tokens).parse_recovering();
+        for error in errors {
+            self.location = Some(Location { start: error.position.clone(), end: error.position });
+            self.report(ValidationSeverity::Error, rules::SYNTAX, error.message);
+        }
+        statements
+    }
+
+    fn check_statements(&mut self, statements: &[AstNode<Statement>]) {
+        let scopes = self.lowerer.schema_scopes(statements.iter().map(|statement| &statement.node));
+        for (statement, scope) in statements.iter().zip(&scopes) {
+            self.location = statement.location.clone();
+            self.check_statement(&statement.node, scope);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement, scope: &Option<String>) {
+        match statement {
+            Statement::Ddl(DdlStatement::CreateTable(table)) => self.create_table(table, scope),
+            Statement::Ddl(DdlStatement::CreateView(view)) => self.create_view(view, scope),
+            Statement::Ddl(DdlStatement::CreateIndex(index)) => self.create_index(index, scope),
+            Statement::Ddl(DdlStatement::AlterTable(alter)) => self.alter_table(alter, scope),
+            Statement::Ddl(DdlStatement::DropTable(drop)) => {
+                for name in &drop.table_names {
+                    match self.resolve(&self.tables, name, scope) {
+                        Some(key) => {
+                            self.tables.remove(&key);
+                        }
+                        None if drop.if_exists => {}
+                        None => self.unknown_table(name, "cannot drop"),
+                    }
+                }
+            }
+            Statement::Dml(dml) => self.check_dml(dml, scope),
+            _ => {}
+        }
+    }
+
+    /// Diagnostics still owed once every script has been seen: foreign keys whose target never appeared.
+    fn finish(mut self) -> Vec<(usize, AstValidationError)> {
+        for pending in std::mem::take(&mut self.pending) {
+            self.script = pending.script;
+            self.location = pending.location;
+            self.check_foreign_key(pending.foreign_key, false);
+        }
+        self.diagnostics
+    }
+
+    fn create_table(&mut self, table: &CreateTableStatement, scope: &Option<String>) {
+        let key = self.key_of(&table.table_name, scope);
+        if self.tables.contains_key(&key) || self.views.contains_key(&key) {
+            if table.if_not_exists {
+                return;
+            }
+            self.report(
+                ValidationSeverity::Error,
+                rules::DUPLICATE_TABLE,
+                format!("table {} is already defined", table.table_name),
+            );
+        }
+
+        self.tables.insert(
+            key.clone(),
+            TableSymbol {
+                name: table.table_name.object.clone(),
+                columns: Vec::new(),
+                primary_key: Vec::new(),
+                unique_keys: Vec::new(),
+                renamed: HashMap::new(),
+            },
+        );
+        for column in &table.columns {
+            self.add_column(&key, column, scope);
+        }
+        for constraint in &table.constraints {
+            self.add_constraint(&key, constraint, scope);
+        }
+    }
+
+    fn add_column(&mut self, key: &ObjectKey, column: &ColumnDefinition, scope: &Option<String>) {
+        let data_type = self.lowerer.lower_data_type(&column.data_type).0;
+        let Some(table) = self.tables.get_mut(key) else {
+            return;
+        };
+        if table.column(&column.name).is_some() {
+            let message = format!("column {} is declared twice in table {}", column.name, table.name);
+            self.report(ValidationSeverity::Error, rules::DUPLICATE_COLUMN, message);
+            return;
+        }
+        table.columns.push(ColumnSymbol { name: column.name.clone(), data_type });
+        table.renamed.remove(&column.name.to_lowercase());
+
+        for constraint in &column.constraints {
+            match constraint {
+                ColumnConstraint::PrimaryKey => self.add_constraint(
+                    key,
+                    &TableConstraint::PrimaryKey { name: None, columns: vec![column.name.clone()], include: Vec::new() },
+                    scope,
+                ),
+                ColumnConstraint::Unique => {
+                    if let Some(table) = self.tables.get_mut(key) {
+                        table.unique_keys.push(vec![column.name.clone()]);
+                    }
+                }
+                ColumnConstraint::ForeignKey { table: target, column: referenced, .. } => {
+                    let foreign_key = ForeignKeyUse {
+                        table: key.clone(),
+                        columns: vec![column.name.clone()],
+                        target: target.clone(),
+                        scope: scope.clone(),
+                        // `REFERENCES customers` alone means its primary key
+                        referenced_columns: if referenced.is_empty() { Vec::new() } else { vec![referenced.clone()] },
+                    };
+                    self.check_foreign_key(foreign_key, true);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_constraint(&mut self, key: &ObjectKey, constraint: &TableConstraint, scope: &Option<String>) {
+        match constraint {
+            TableConstraint::PrimaryKey { columns, include, .. } => {
+                self.check_columns(key, include, "primary key");
+                if self.check_columns(key, columns, "primary key")
+                    && let Some(table) = self.tables.get_mut(key)
+                {
+                    table.primary_key = columns.clone();
+                }
+            }
+            TableConstraint::Unique { columns, include, .. } => {
+                self.check_columns(key, include, "unique constraint");
+                if self.check_columns(key, columns, "unique constraint")
+                    && let Some(table) = self.tables.get_mut(key)
+                {
+                    table.unique_keys.push(columns.clone());
+                }
+            }
+            TableConstraint::Index { columns, .. } => {
+                let columns: Vec<String> = columns.iter().map(|part| part.column.clone()).collect();
+                self.check_columns(key, &columns, "index");
+            }
+            TableConstraint::ForeignKey { columns, referenced_table, referenced_columns, .. } => {
+                if self.check_columns(key, columns, "foreign key") {
+                    let foreign_key = ForeignKeyUse {
+                        table: key.clone(),
+                        columns: columns.clone(),
+                        target: referenced_table.clone(),
+                        scope: scope.clone(),
+                        referenced_columns: referenced_columns.clone(),
+                    };
+                    self.check_foreign_key(foreign_key, true);
+                }
+            }
+            TableConstraint::Check { .. } | TableConstraint::Exclude { .. } => {}
+        }
+    }
+
+    /// Resolves a foreign key's target and compares its columns with the referencing ones.
+    /// With `defer`, a target that isn't declared yet is looked for again at the end.
+    fn check_foreign_key(&mut self, foreign_key: ForeignKeyUse, defer: bool) {
+        let Some(table) = self.tables.get(&foreign_key.table) else {
+            return;
+        };
+        let table_name = table.name.clone();
+        let Some(target_key) = self.resolve(&self.tables, &foreign_key.target, &foreign_key.scope) else {
+            if defer {
+                self.pending.push(PendingForeignKey {
+                    script: self.script,
+                    location: self.location.clone(),
+                    foreign_key,
+                });
+            } else {
+                let message = format!("foreign key on {} references unknown table {}", table_name, foreign_key.target);
+                self.report(ValidationSeverity::Error, rules::UNKNOWN_TABLE, message);
+            }
+            return;
+        };
+        let target = &self.tables[&target_key];
+
+        let referenced_columns = if foreign_key.referenced_columns.is_empty() {
+            if target.primary_key.is_empty() {
+                let message = format!(
+                    "foreign key on {} references {}, which has no primary key to default to",
+                    table_name, target.name
+                );
+                self.report(ValidationSeverity::Error, rules::UNKNOWN_COLUMN, message);
+                return;
+            }
+            target.primary_key.clone()
+        } else {
+            foreign_key.referenced_columns.clone()
+        };
+
+        let mut messages = Vec::new();
+        for column in &referenced_columns {
+            if target.column(column).is_none() {
+                messages.push((
+                    ValidationSeverity::Error,
+                    rules::UNKNOWN_COLUMN,
+                    format!("foreign key on {} references {}", table_name, target.missing_column(column)),
+                ));
+            }
+        }
+        if referenced_columns.len() != foreign_key.columns.len() {
+            messages.push((
+                ValidationSeverity::Error,
+                rules::FK_COLUMN_COUNT,
+                format!(
+                    "foreign key on {} has {} column(s) but references {} in {}",
+                    table_name,
+                    foreign_key.columns.len(),
+                    referenced_columns.len(),
+                    target.name
+                ),
+            ));
+        } else if messages.is_empty() {
+            if !target.is_key(&referenced_columns) {
+                messages.push((
+                    ValidationSeverity::Warning,
+                    rules::FK_TARGET_NOT_UNIQUE,
+                    format!(
+                        "foreign key on {} references {} ({}), which is not a primary key or unique constraint",
+                        table_name,
+                        target.name,
+                        referenced_columns.join(", ")
+                    ),
+                ));
+            }
+            let table = &self.tables[&foreign_key.table];
+            for (column, referenced) in foreign_key.columns.iter().zip(&referenced_columns) {
+                let (Some(column), Some(referenced)) = (table.column(column), target.column(referenced)) else {
+                    continue;
+                };
+                if let Some(severity) = type_mismatch(&column.data_type, &referenced.data_type) {
+                    messages.push((
+                        severity,
+                        rules::FK_TYPE_MISMATCH,
+                        format!(
+                            "{}.{} is {} but references {}.{}, which is {}",
+                            table.name,
+                            column.name,
+                            column.data_type.to_sql(),
+                            target.name,
+                            referenced.name,
+                            referenced.data_type.to_sql()
+                        ),
+                    ));
+                }
+            }
+        }
+        for (severity, rule, message) in messages {
+            self.report(severity, rule, message);
+        }
+    }
+
+    fn create_view(&mut self, view: &CreateViewStatement, scope: &Option<String>) {
+        for table in view.query.referenced_tables() {
+            if self.resolve_relation(table, scope).is_none() && !is_system_table(table) {
+                let message = format!("view {} reads unknown table {}", view.view_name, table);
+                self.report(ValidationSeverity::Error, rules::UNKNOWN_TABLE, message);
+            }
+        }
+
+        let key = self.key_of(&view.view_name, scope);
+        if self.tables.contains_key(&key) || (self.views.contains_key(&key) && !view.or_replace) {
+            if view.if_not_exists {
+                return;
+            }
+            let message = format!("view {} has the same name as an existing table or view", view.view_name);
+            self.report(ValidationSeverity::Error, rules::DUPLICATE_TABLE, message);
+        }
+        self.views.insert(key, view.view_name.object.clone());
+    }
+
+    fn create_index(&mut self, index: &CreateIndexStatement, scope: &Option<String>) {
+        match self.resolve(&self.tables, &index.table_name, scope) {
+            Some(key) => {
+                let columns: Vec<String> = index.columns.iter().map(|part| part.column.clone()).collect();
+                let object = format!("index {}", index.index_name);
+                self.check_columns(&key, &columns, &object);
+                self.check_columns(&key, &index.include, &object);
+            }
+            None => self.unknown_table(&index.table_name, &format!("index {} is on", index.index_name)),
+        }
+    }
+
+    fn alter_table(&mut self, alter: &AlterTableStatement, scope: &Option<String>) {
+        let Some(key) = self.resolve(&self.tables, &alter.table_name, scope) else {
+            if !alter.if_exists {
+                self.unknown_table(&alter.table_name, "cannot alter");
+            }
+            return;
+        };
+
+        for action in &alter.actions {
+            match action {
+                AlterTableAction::AddColumn { column } => self.add_column(&key, column, scope),
+                AlterTableAction::DropColumn { column_name, if_exists } => {
+                    if *if_exists {
+                        if let Some(table) = self.tables.get_mut(&key) {
+                            table.remove_column(column_name);
+                        }
+                    } else if self.check_columns(&key, std::slice::from_ref(column_name), "cannot drop")
+                        && let Some(table) = self.tables.get_mut(&key)
+                    {
+                        table.remove_column(column_name);
+                    }
+                }
+                AlterTableAction::AlterColumn { column_name, new_data_type, .. } => {
+                    if self.check_columns(&key, std::slice::from_ref(column_name), "cannot alter")
+                        && let Some(data_type) = new_data_type
+                    {
+                        let data_type = self.lowerer.lower_data_type(data_type).0;
+                        if let Some(column) = self.tables.get_mut(&key).and_then(|table| table.column_mut(column_name)) {
+                            column.data_type = data_type;
+                        }
+                    }
+                }
+                AlterTableAction::ModifyColumn { column } => {
+                    if self.check_columns(&key, std::slice::from_ref(&column.name), "cannot modify") {
+                        let data_type = self.lowerer.lower_data_type(&column.data_type).0;
+                        if let Some(existing) = self.tables.get_mut(&key).and_then(|table| table.column_mut(&column.name)) {
+                            existing.data_type = data_type;
+                        }
+                    }
+                }
+                AlterTableAction::RenameColumn { old_name, new_name } => {
+                    if !self.check_columns(&key, std::slice::from_ref(old_name), "cannot rename") {
+                        continue;
+                    }
+                    let Some(table) = self.tables.get_mut(&key) else {
+                        continue;
+                    };
+                    if !old_name.eq_ignore_ascii_case(new_name) && table.column(new_name).is_some() {
+                        let message = format!("cannot rename {} to {}: table {} already has that column", old_name, new_name, table.name);
+                        self.report(ValidationSeverity::Error, rules::DUPLICATE_COLUMN, message);
+                        continue;
+                    }
+                    table.rename_column(old_name, new_name);
+                }
+                AlterTableAction::AddConstraint { constraint, .. } => self.add_constraint(&key, constraint, scope),
+                AlterTableAction::RenameTable { new_name } => {
+                    let Some(mut table) = self.tables.remove(&key) else {
+                        continue;
+                    };
+                    table.name = new_name.clone();
+                    self.tables.insert((key.0.clone(), new_name.to_lowercase()), table);
+                    // Later actions in this statement still name the table by its old name
+                    return;
+                }
+                AlterTableAction::DropConstraint { .. }
+                | AlterTableAction::ConstraintChecking { .. }
+                | AlterTableAction::Other(_) => {}
+            }
+        }
+    }
+
+    fn check_dml(&mut self, dml: &DmlStatement, scope: &Option<String>) {
+        let (target, columns, queries): (Option<&SchemaReference>, Vec<&str>, Vec<_>) = match dml {
+            DmlStatement::Select(select) => (None, Vec::new(), vec![select.as_ref()]),
+            DmlStatement::Insert(insert) => (
+                Some(&insert.table_name),
+                insert.columns.iter().map(String::as_str).collect(),
+                insert.select.iter().collect(),
+            ),
+            DmlStatement::Update(update) => (
+                Some(&update.table_name),
+                // `alias.column` in MySQL's multi-table form names some other table
+                update.assignments.iter().filter(|a| !a.column.contains('.')).map(|a| a.column.as_str()).collect(),
+                Vec::new(),
+            ),
+            DmlStatement::Delete(delete) => (Some(&delete.table_name), Vec::new(), Vec::new()),
+        };
+
+        if let Some(target) = target {
+            match self.resolve(&self.tables, target, scope) {
+                Some(key) => {
+                    let columns: Vec<String> = columns.into_iter().map(str::to_string).collect();
+                    self.check_columns(&key, &columns, "statement writes");
+                }
+                None if self.resolve(&self.views, target, scope).is_some() || is_system_table(target) => {}
+                None => self.unknown_table(target, "statement writes"),
+            }
+        }
+        for query in queries {
+            for table in query.referenced_tables() {
+                if self.resolve_relation(table, scope).is_none() && !is_system_table(table) {
+                    self.unknown_table(table, "query reads");
+                }
+            }
+        }
+    }
+
+    /// Reports each of `columns` that the table lacks; true when all of them exist.
+    fn check_columns(&mut self, key: &ObjectKey, columns: &[String], context: &str) -> bool {
+        let Some(table) = self.tables.get(key) else {
+            return false;
+        };
+        let missing: Vec<String> = columns
+            .iter()
+            .filter(|column| table.column(column).is_none())
+            .map(|column| table.missing_column(column))
+            .collect();
+        let all_found = missing.is_empty();
+        for column in missing {
+            self.report(ValidationSeverity::Error, rules::UNKNOWN_COLUMN, format!("{}: {}", context, column));
+        }
+        all_found
+    }
+
+    fn unknown_table(&mut self, name: &SchemaReference, context: &str) {
+        let message = format!("{}: table {} does not exist", context, name);
+        self.report(ValidationSeverity::Error, rules::UNKNOWN_TABLE, message);
+    }
+
+    fn key_of(&self, name: &SchemaReference, scope: &Option<String>) -> ObjectKey {
+        (self.lowerer.schema_of(name, scope), name.object.to_lowercase())
+    }
+
+    /// Finds a declared table or view: in the named schema if qualified, else the one in
+    /// scope, the default schema, or the only schema that has one by that name.
+    fn resolve<T>(&self, objects: &HashMap<ObjectKey, T>, name: &SchemaReference, scope: &Option<String>) -> Option<ObjectKey> {
+        let key = self.key_of(name, scope);
+        if objects.contains_key(&key) {
+            return Some(key);
+        }
+        if name.schema.is_some() {
+            return None;
+        }
+        let default = (None, key.1.clone());
+        if objects.contains_key(&default) {
+            return Some(default);
+        }
+        let mut candidates = objects.keys().filter(|(_, object)| *object == key.1);
+        match (candidates.next(), candidates.next()) {
+            (Some(only), None) => Some(only.clone()),
+            _ => None,
+        }
+    }
+
+    fn resolve_relation(&self, name: &SchemaReference, scope: &Option<String>) -> Option<ObjectKey> {
+        self.resolve(&self.tables, name, scope).or_else(|| self.resolve(&self.views, name, scope))
+    }
+
+    fn report(&mut self, severity: ValidationSeverity, rule: &str, message: impl Into<String>) {
+        self.diagnostics.push((
+            self.script,
+            AstValidationError {
+                message: message.into(),
+                location: self.location.clone(),
+                severity,
+                rule: Some(rule.to_string()),
+            },
+        ));
+    }
+}
+
+impl TableSymbol {
+    fn column(&self, name: &str) -> Option<&ColumnSymbol> {
+        self.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    fn column_mut(&mut self, name: &str) -> Option<&mut ColumnSymbol> {
+        self.columns.iter_mut().find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Describes a column the table doesn't have, mentioning what it was renamed to if it was.
+    fn missing_column(&self, name: &str) -> String {
+        match self.renamed.get(&name.to_lowercase()) {
+            Some(new_name) => format!("column {}.{} was renamed to {}", self.name, name, new_name),
+            None => format!("column {}.{} does not exist", self.name, name),
+        }
+    }
+
+    fn remove_column(&mut self, name: &str) {
+        self.columns.retain(|column| !column.name.eq_ignore_ascii_case(name));
+    }
+
+    fn rename_column(&mut self, old_name: &str, new_name: &str) {
+        let rename = |column: &mut String| {
+            if column.eq_ignore_ascii_case(old_name) {
+                *column = new_name.to_string();
+            }
+        };
+        if let Some(column) = self.column_mut(old_name) {
+            column.name = new_name.to_string();
+        }
+        self.primary_key.iter_mut().for_each(rename);
+        self.unique_keys.iter_mut().flatten().for_each(rename);
+        self.renamed.remove(&new_name.to_lowercase());
+        self.renamed.insert(old_name.to_lowercase(), new_name.to_string());
+    }
+
+    /// Whether `columns` are the primary key or a unique constraint, in any order.
+    fn is_key(&self, columns: &[String]) -> bool {
+        let same = |key: &Vec<String>| {
+            key.len() == columns.len() && key.iter().all(|column| columns.iter().any(|c| c.eq_ignore_ascii_case(column)))
+        };
+        same(&self.primary_key) || self.unique_keys.iter().any(same)
+    }
+}
+
+/// Compares a foreign key column's type with the one it references: values of a different
+/// kind can never match, and a narrower integer can't hold every key.
+fn type_mismatch(column: &DataType, referenced: &DataType) -> Option<ValidationSeverity> {
+    if matches!(column, DataType::Custom(_)) || matches!(referenced, DataType::Custom(_)) {
+        return None;
+    }
+    let kind = |data_type: &DataType| {
+        let format = data_type.json_schema_format().filter(|format| *format != "int64");
+        (data_type.json_schema_type(), format)
+    };
+    if kind(column) != kind(referenced) {
+        return Some(ValidationSeverity::Error);
+    }
+    if column.json_schema_type() == "integer" && column.to_sql() != referenced.to_sql() {
+        return Some(ValidationSeverity::Warning);
+    }
+    None
+}
+
+pub(crate) fn is_system_table(name: &SchemaReference) -> bool {
+    let system_schema = name
+        .schema
+        .as_deref()
+        .is_some_and(|schema| SYSTEM_SCHEMAS.iter().any(|system| system.eq_ignore_ascii_case(schema)));
+    system_schema || name.object.eq_ignore_ascii_case("dual")
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::domain::entity::{Database, Table};
+
+/// The snapshot format's version, raised whenever the model changes shape.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("invalid snapshot: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("snapshot format version {found} is not supported; this build reads version {SNAPSHOT_VERSION}")]
+    Version { found: u32 },
+}
+
+/// A [`Database`] in canonical form, to be committed as a lock file and compared against
+/// later. Everything whose order carries no meaning is sorted: schemas, tables, views, types,
+/// routines and sequences by name, and each table's keys, indexes and triggers likewise.
+/// Columns, enum values and key columns keep their order. The same schema therefore gives the
+/// same file byte for byte, however its statements were arranged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSnapshot {
+    pub version: u32,
+    pub database: Database,
+}
+
+impl SchemaSnapshot {
+    pub fn new(database: &Database) -> Self {
+        let mut database = database.clone();
+        canonicalize(&mut database);
+        Self { version: SNAPSHOT_VERSION, database }
+    }
+
+    /// Pretty-printed JSON with object keys in sorted order, ending in a newline.
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        // `serde_json::Value` keeps object keys sorted, which also settles the order of maps
+        let value = serde_json::to_value(self)?;
+        Ok(format!("{}\n", serde_json::to_string_pretty(&value)?))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+        let header: Header = serde_json::from_str(json)?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::Version { found: header.version });
+        }
+        let snapshot: SchemaSnapshot = serde_json::from_str(json)?;
+        Ok(Self::new(&snapshot.database))
+    }
+}
+
+fn canonicalize(database: &mut Database) {
+    let key = |name: &str| name.to_lowercase();
+    database.schemas.sort_by_key(|schema| schema.name.as_deref().map(key));
+    for schema in &mut database.schemas {
+        schema.tables.sort_by_key(|table| key(&table.name));
+        schema.tables.iter_mut().for_each(canonicalize_table);
+        schema.views.sort_by_key(|view| key(&view.name));
+        for view in &mut schema.views {
+            view.dependencies.sort_by_key(|dependency| key(dependency));
+            view.dependencies.dedup_by_key(|dependency| key(dependency));
+        }
+        schema.types.sort_by_key(|user_type| key(&user_type.name));
+        schema.sequences.sort_by_key(|sequence| key(&sequence.name));
+        // Overloads share a name, so their definitions settle the order
+        schema.procedures.sort_by(|a, b| (key(&a.name), &a.definition).cmp(&(key(&b.name), &b.definition)));
+        schema.functions.sort_by(|a, b| (key(&a.name), &a.definition).cmp(&(key(&b.name), &b.definition)));
+    }
+    database.permissions.sort_by_key(|permission| (key(&permission.object_name), key(&permission.grantee)));
+}
+
+fn canonicalize_table(table: &mut Table) {
+    let name = |constraint: Option<&String>, name: Option<&String>| {
+        constraint.or(name).map(|name| name.to_lowercase()).unwrap_or_default()
+    };
+    table.indexes.sort_by_key(|index| index.name.to_lowercase());
+    table.foreign_keys.sort_by_key(|fk| (name(fk.constraint_name.as_ref(), fk.name.as_ref()), fk.columns.clone()));
+    table
+        .unique_constraints
+        .sort_by_key(|unique| (name(unique.constraint_name.as_ref(), unique.name.as_ref()), unique.columns.clone()));
+    table
+        .check_constraints
+        .sort_by_key(|check| (name(check.constraint_name.as_ref(), check.name.as_ref()), check.expression.clone()));
+    table.exclusion_constraints.sort_by_key(|exclusion| name(None, exclusion.name.as_ref()));
+    table.triggers.sort_by_key(|trigger| trigger.name.to_lowercase());
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::core::ast::dml::SelectStatement;
+use crate::core::ast::types::SchemaReference;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DataType {
     Boolean,
@@ -23,6 +26,10 @@ pub enum DataType {
     Timestamp,
     Json,
     Uuid,
+    Enum { values: Vec<String> },
+    /// MySQL `SET('a', 'b')`: any combination of the listed values
+    Set { values: Vec<String> },
+    Array { element_type: Box<DataType>, dimensions: Option<u32> },
     Custom(String),
 }
 
@@ -33,14 +40,39 @@ pub enum ColumnConstraint {
     Default(String),
     Unique,
     PrimaryKey,
+    /// `DESC` after an inline `PRIMARY KEY`, which keeps SQLite from making the column a rowid alias
+    KeyDescending,
     AutoIncrement,
     Check(String),
     ForeignKey {
-        table: String,
+        table: SchemaReference,
         column: String,
         on_delete: Option<ReferentialAction>,
         on_update: Option<ReferentialAction>,
     },
+    /// `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY [(options)]`
+    Identity(IdentityOptions),
+    /// `GENERATED ALWAYS AS (expression) { STORED | VIRTUAL }`
+    Generated { expression: String, stored: bool },
+    Collate(String),
+    /// MySQL `CHARACTER SET name` on a single column
+    CharacterSet(String),
+    /// MySQL `ON UPDATE CURRENT_TIMESTAMP`
+    OnUpdate(String),
+    /// MySQL `COMMENT 'text'`
+    Comment(String),
+    /// `CONSTRAINT name`, naming the constraint that follows it, such as SQL Server's `DF_x` defaults
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct IdentityOptions {
+    pub always: bool,
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub cycle: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -56,31 +88,81 @@ pub enum ReferentialAction {
 pub struct ColumnDefinition {
     pub name: String,
     pub data_type: DataType,
+    /// The type as written, such as `UNSIGNED BIG INT`; empty for an untyped SQLite column
+    pub type_name: Option<String>,
     pub constraints: Vec<ColumnConstraint>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TableConstraint {
-    PrimaryKey { columns: Vec<String> },
-    Unique { name: Option<String>, columns: Vec<String> },
+    /// `include` holds PostgreSQL's `INCLUDE (...)` columns, stored in the key's index but not part of it
+    PrimaryKey { name: Option<String>, columns: Vec<String>, include: Vec<String> },
+    Unique { name: Option<String>, columns: Vec<String>, include: Vec<String> },
     ForeignKey {
         name: Option<String>,
         columns: Vec<String>,
-        referenced_table: String,
+        referenced_table: SchemaReference,
         referenced_columns: Vec<String>,
         on_delete: Option<ReferentialAction>,
         on_update: Option<ReferentialAction>,
     },
     Check { name: Option<String>, expression: String },
+    /// MySQL's inline `[FULLTEXT | SPATIAL] KEY name (columns)`; UNIQUE KEY stays `Unique`
+    Index {
+        name: Option<String>,
+        kind: IndexKind,
+        columns: Vec<KeyPart>,
+        index_type: Option<IndexType>,
+    },
+    /// PostgreSQL `EXCLUDE [USING method] (element WITH operator, ...) [WHERE (predicate)]`
+    Exclude {
+        name: Option<String>,
+        index_type: Option<IndexType>,
+        elements: Vec<ExclusionElement>,
+        where_clause: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum IndexKind {
+    #[default]
+    Regular,
+    FullText,
+    Spatial,
+}
+
+/// An indexed column; MySQL may index only the first `length` characters of a string column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyPart {
+    pub column: String,
+    pub length: Option<u32>,
+    pub descending: bool,
+}
+
+impl KeyPart {
+    /// The whole column, in ascending order.
+    pub fn new(column: impl Into<String>) -> Self {
+        Self { column: column.into(), length: None, descending: false }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionElement {
+    pub expression: String,
+    pub operator: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreateTableStatement {
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub if_not_exists: bool,
     pub columns: Vec<ColumnDefinition>,
     pub constraints: Vec<TableConstraint>,
     pub options: HashMap<String, String>,
+    /// SQLite `STRICT`: values must match the declared type
+    pub strict: bool,
+    /// SQLite `WITHOUT ROWID`: rows are stored in the primary key's B-tree
+    pub without_rowid: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -93,22 +175,31 @@ pub enum AlterTableAction {
         add_constraints: Vec<ColumnConstraint>,
         drop_constraints: Vec<String>,
     },
+    /// MySQL's `MODIFY` and `CHANGE`, which replace the column's whole definition
+    ModifyColumn { column: ColumnDefinition },
     RenameColumn { old_name: String, new_name: String },
-    AddConstraint { constraint: TableConstraint },
+    /// `not_valid` is PostgreSQL's `NOT VALID`: rows already in the table are left unchecked
+    /// until `VALIDATE CONSTRAINT`
+    AddConstraint { constraint: TableConstraint, not_valid: bool },
     DropConstraint { constraint_name: String, if_exists: bool },
     RenameTable { new_name: String },
+    /// SQL Server's `CHECK CONSTRAINT` and `NOCHECK CONSTRAINT`, which turn enforcement of the
+    /// named constraints back on or off; `ALL` leaves `constraint_names` empty
+    ConstraintChecking { constraint_names: Vec<String>, enabled: bool },
+    /// Anything else, such as `OWNER TO` or MySQL table options, kept as written
+    Other(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AlterTableStatement {
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub if_exists: bool,
     pub actions: Vec<AlterTableAction>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DropTableStatement {
-    pub table_names: Vec<String>,
+    pub table_names: Vec<SchemaReference>,
     pub if_exists: bool,
     pub cascade: bool,
 }
@@ -127,11 +218,17 @@ pub enum IndexType {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreateIndexStatement {
     pub index_name: String,
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub if_not_exists: bool,
     pub unique: bool,
+    /// PostgreSQL `CONCURRENTLY`: built without blocking writes to the table
+    pub concurrently: bool,
+    pub kind: IndexKind,
     pub index_type: Option<IndexType>,
-    pub columns: Vec<String>,
+    pub columns: Vec<KeyPart>,
+    /// SQL Server and PostgreSQL `INCLUDE (...)`: columns stored in the index's leaf rows
+    /// so queries can read them from it, without being part of the key
+    pub include: Vec<String>,
     pub where_clause: Option<String>,
 }
 
@@ -142,6 +239,27 @@ pub struct DropIndexStatement {
     pub cascade: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TypeDefinition {
+    Enum { values: Vec<String> },
+    Composite { attributes: Vec<ColumnDefinition> },
+}
+
+/// PostgreSQL `CREATE TYPE name AS ENUM (...)` or `AS (attribute type, ...)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateTypeStatement {
+    pub type_name: SchemaReference,
+    pub definition: TypeDefinition,
+}
+
+/// PostgreSQL `CREATE DOMAIN name AS type [constraints]`; CHECKs refer to the value as `VALUE`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateDomainStatement {
+    pub domain_name: SchemaReference,
+    pub data_type: DataType,
+    pub constraints: Vec<ColumnConstraint>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DdlStatement {
     CreateTable(CreateTableStatement),
@@ -149,6 +267,109 @@ pub enum DdlStatement {
     DropTable(DropTableStatement),
     CreateIndex(CreateIndexStatement),
     DropIndex(DropIndexStatement),
+    CreateType(CreateTypeStatement),
+    CreateDomain(CreateDomainStatement),
+    CreateSequence(CreateSequenceStatement),
+    CreateSchema(CreateSchemaStatement),
+    CreateView(CreateViewStatement),
+    CreateTrigger(CreateTriggerStatement),
+    CreateRoutine(CreateRoutineStatement),
+}
+
+/// `CREATE SEQUENCE name [AS type] [START WITH n] [INCREMENT BY n] ...`; `options.always` is unused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateSequenceStatement {
+    pub sequence_name: SchemaReference,
+    pub if_not_exists: bool,
+    pub data_type: Option<DataType>,
+    pub options: IdentityOptions,
+}
+
+/// `CREATE SCHEMA [IF NOT EXISTS] name [AUTHORIZATION owner]`; MySQL's `CREATE DATABASE` is the same thing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateSchemaStatement {
+    pub schema_name: String,
+    pub if_not_exists: bool,
+    pub authorization: Option<String>,
+}
+
+/// `CREATE [OR REPLACE] [MATERIALIZED] VIEW name [(columns)] AS query`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateViewStatement {
+    pub view_name: SchemaReference,
+    pub or_replace: bool,
+    pub materialized: bool,
+    pub if_not_exists: bool,
+    pub columns: Vec<String>,
+    pub query: Box<SelectStatement>,
+    /// The query as written, for documentation
+    pub definition: String,
+}
+
+/// `CREATE [OR REPLACE] TRIGGER name {BEFORE | AFTER | INSTEAD OF} event [OR event] ON table
+/// [FOR EACH ROW] body`, or SQL Server's `CREATE TRIGGER name ON table {AFTER | INSTEAD OF} event, ... AS body`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateTriggerStatement {
+    pub trigger_name: SchemaReference,
+    pub or_replace: bool,
+    pub table_name: SchemaReference,
+    pub timing: TriggerTiming,
+    pub events: Vec<TriggerEvent>,
+    pub for_each_row: bool,
+    /// What the trigger runs, as written: a block, a single statement or PostgreSQL's `EXECUTE FUNCTION f()`
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TriggerTiming {
+    Before,
+    After,
+    InsteadOf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerEvent {
+    Insert,
+    /// `UPDATE [OF columns]`
+    Update(Vec<String>),
+    Delete,
+    Truncate,
+}
+
+/// `CREATE [OR REPLACE] {FUNCTION | PROCEDURE} name [(parameters)] [RETURNS type] ... body`; the
+/// body is a dollar-quoted string's contents in PostgreSQL, otherwise the block as written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateRoutineStatement {
+    pub routine_name: SchemaReference,
+    pub kind: RoutineKind,
+    pub or_replace: bool,
+    pub parameters: Vec<RoutineParameter>,
+    pub returns: Option<String>,
+    pub language: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoutineKind {
+    Function,
+    Procedure,
+}
+
+/// A routine parameter; PostgreSQL's may be unnamed, SQL Server's are named with their `@`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutineParameter {
+    pub name: Option<String>,
+    pub data_type: String,
+    pub mode: ParameterMode,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ParameterMode {
+    #[default]
+    In,
+    Out,
+    InOut,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -191,6 +412,7 @@ impl ColumnDefinition {
         Self {
             name,
             data_type,
+            type_name: None,
             constraints: Vec::new(),
         }
     }
@@ -218,13 +440,15 @@ impl ColumnDefinition {
 }
 
 impl CreateTableStatement {
-    pub fn new(table_name: String) -> Self {
+    pub fn new(table_name: impl Into<SchemaReference>) -> Self {
         Self {
-            table_name,
+            table_name: table_name.into(),
             if_not_exists: false,
             columns: Vec::new(),
             constraints: Vec::new(),
             options: HashMap::new(),
+            strict: false,
+            without_rowid: false,
         }
     }
 
@@ -245,6 +469,30 @@ impl CreateTableStatement {
             .collect()
     }
 
+    /// SQLite's rowid alias: the lone primary key column of a rowid table, declared exactly `INTEGER`.
+    /// `id INTEGER PRIMARY KEY DESC` is not one, though `PRIMARY KEY (id DESC)` is.
+    pub fn rowid_alias(&self) -> Option<&ColumnDefinition> {
+        if self.without_rowid {
+            return None;
+        }
+        let table_key = self.constraints.iter().find_map(|constraint| match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => Some(columns),
+            _ => None,
+        });
+        let name = match table_key {
+            Some(columns) if columns.len() == 1 => &columns[0],
+            Some(_) => return None,
+            None => match self.primary_key_columns().as_slice() {
+                [column] if !column.constraints.contains(&ColumnConstraint::KeyDescending) => &column.name,
+                _ => return None,
+            },
+        };
+        self.columns
+            .iter()
+            .find(|column| &column.name == name)
+            .filter(|column| column.type_name.as_deref().is_some_and(|t| t.eq_ignore_ascii_case("INTEGER")))
+    }
+
     pub fn foreign_keys(&self) -> Vec<&TableConstraint> {
         self.constraints
             .iter()
@@ -254,9 +502,9 @@ impl CreateTableStatement {
 }
 
 impl AlterTableStatement {
-    pub fn new(table_name: String) -> Self {
+    pub fn new(table_name: impl Into<SchemaReference>) -> Self {
         Self {
-            table_name,
+            table_name: table_name.into(),
             if_exists: false,
             actions: Vec::new(),
         }
@@ -269,20 +517,23 @@ impl AlterTableStatement {
 }
 
 impl CreateIndexStatement {
-    pub fn new(index_name: String, table_name: String) -> Self {
+    pub fn new(index_name: String, table_name: impl Into<SchemaReference>) -> Self {
         Self {
             index_name,
-            table_name,
+            table_name: table_name.into(),
             if_not_exists: false,
             unique: false,
+            concurrently: false,
+            kind: IndexKind::Regular,
             index_type: None,
             columns: Vec::new(),
+            include: Vec::new(),
             where_clause: None,
         }
     }
 
     pub fn with_columns(mut self, columns: Vec<String>) -> Self {
-        self.columns = columns;
+        self.columns = columns.into_iter().map(KeyPart::new).collect();
         self
     }
 }
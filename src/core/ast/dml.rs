@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+
+use crate::core::ast::types::SchemaReference;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LiteralValue {
@@ -20,12 +21,27 @@ pub enum Expression {
     BinaryOp { left: Box<Expression>, op: BinaryOperator, right: Box<Expression> },
     UnaryOp { op: UnaryOperator, expr: Box<Expression> },
     Between { expr: Box<Expression>, min: Box<Expression>, max: Box<Expression> },
+    NotBetween { expr: Box<Expression>, min: Box<Expression>, max: Box<Expression> },
     In { expr: Box<Expression>, values: Vec<Expression> },
     NotIn { expr: Box<Expression>, values: Vec<Expression> },
+    IsNull { expr: Box<Expression>, negated: bool },
+    /// `expr IS [NOT] TRUE`, which is false rather than NULL for a NULL operand
+    IsTrue { expr: Box<Expression>, negated: bool },
+    /// `expr IS [NOT] FALSE`, which is false rather than NULL for a NULL operand
+    IsFalse { expr: Box<Expression>, negated: bool },
+    IsDistinctFrom { left: Box<Expression>, right: Box<Expression>, negated: bool },
+    /// `expr [NOT] LIKE pattern ESCAPE escape`; `op` is one of the LIKE/ILIKE operators
+    LikeEscape { expr: Box<Expression>, op: BinaryOperator, pattern: Box<Expression>, escape: Box<Expression> },
     Exists(Box<SelectStatement>),
     Subquery(Box<SelectStatement>),
     Case { operand: Option<Box<Expression>>, when_clauses: Vec<WhenClause>, else_expr: Option<Box<Expression>> },
     Cast { expr: Box<Expression>, data_type: String },
+    Subscript { expr: Box<Expression>, index: Box<Expression> },
+    Array(Vec<Expression>),
+    /// Bind parameter as written: `$1`, `:name` or `?`
+    Parameter(String),
+    /// `*` as a function argument, as in `count(*)`
+    Wildcard,
     WindowFunction { function: Box<Expression>, window: WindowSpecification },
 }
 
@@ -60,6 +76,10 @@ pub enum BinaryOperator {
     BitwiseXor,
     LeftShift,
     RightShift,
+    JsonGet,
+    JsonGetText,
+    JsonPath,
+    JsonPathText,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -97,8 +117,10 @@ pub enum JoinCondition {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TableReference {
-    Table { name: String, alias: Option<String> },
+    Table { name: SchemaReference, alias: Option<String> },
     Subquery { query: Box<SelectStatement>, alias: String },
+    /// `left` followed by each of `joins`; `right` repeats the first join's table, so walking
+    /// `left` and `joins` visits every table once
     Join { left: Box<TableReference>, right: Box<TableReference>, joins: Vec<JoinClause> },
 }
 
@@ -123,19 +145,36 @@ pub enum FrameBound {
     UnboundedFollowing,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WindowFrame {
+    pub units: WindowFrameUnits,
     pub start: FrameBound,
     pub end: Option<FrameBound>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WindowSpecification {
+    /// The window of the WINDOW clause this one refers to or builds on, as in `OVER w`
+    pub name: Option<String>,
     pub partition_by: Vec<Expression>,
     pub order_by: Vec<OrderByClause>,
     pub frame: Option<WindowFrame>,
 }
 
+/// `name AS (...)` in a WINDOW clause.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedWindow {
+    pub name: String,
+    pub window: WindowSpecification,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommonTableExpression {
     pub name: String,
@@ -153,9 +192,11 @@ pub struct SelectStatement {
     pub where_clause: Option<Expression>,
     pub group_by: Option<GroupByClause>,
     pub having: Option<Expression>,
+    pub windows: Vec<NamedWindow>,
     pub order_by: Vec<OrderByClause>,
-    pub limit: Option<u64>,
-    pub offset: Option<u64>,
+    /// A count or a bind parameter
+    pub limit: Option<Expression>,
+    pub offset: Option<Expression>,
     pub unions: Vec<UnionClause>,
 }
 
@@ -166,15 +207,26 @@ pub enum SelectItem {
     QualifiedWildcard { qualifier: String },
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+/// A set operation applied to the statement it belongs to; ORDER BY, LIMIT and OFFSET on that
+/// statement apply to the combined result. The operations apply left to right: an INTERSECT
+/// that binds tighter than the UNION or EXCEPT before it is one of that operand's own unions.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnionClause {
+    pub op: SetOperator,
     pub select: SelectStatement,
     pub all: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InsertStatement {
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub columns: Vec<String>,
     pub values: Vec<Vec<Expression>>,
     pub select: Option<SelectStatement>,
@@ -182,30 +234,37 @@ pub struct InsertStatement {
     pub returning: Vec<SelectItem>,
 }
 
+/// PostgreSQL `ON CONFLICT` or MySQL `ON DUPLICATE KEY UPDATE`, which has no target.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OnConflictClause {
-    pub target: ConflictTarget,
+    pub target: Option<ConflictTarget>,
     pub action: ConflictAction,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConflictTarget {
-    Columns(Vec<String>),
+    Columns { columns: Vec<String>, where_clause: Option<Expression> },
     OnConstraint(String),
-    Where(Expression),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConflictAction {
     DoNothing,
-    DoUpdate { assignments: HashMap<String, Expression>, where_clause: Option<Expression> },
+    DoUpdate { assignments: Vec<Assignment>, where_clause: Option<Expression> },
+}
+
+/// `column = value` in a SET list; the column may be qualified as `alias.column`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assignment {
+    pub column: String,
+    pub value: Expression,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UpdateStatement {
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub alias: Option<String>,
-    pub assignments: HashMap<String, Expression>,
+    pub assignments: Vec<Assignment>,
     pub from: Vec<TableReference>,
     pub where_clause: Option<Expression>,
     pub returning: Vec<SelectItem>,
@@ -213,7 +272,7 @@ pub struct UpdateStatement {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DeleteStatement {
-    pub table_name: String,
+    pub table_name: SchemaReference,
     pub alias: Option<String>,
     pub using: Vec<TableReference>,
     pub where_clause: Option<Expression>,
@@ -270,7 +329,9 @@ impl Expression {
         match self {
             Expression::Function { name, .. } => matches!(
                 name.to_uppercase().as_str(),
-                "COUNT" | "SUM" | "AVG" | "MIN" | "MAX" | "STDDEV" | "VARIANCE"
+                "COUNT" | "SUM" | "AVG" | "MIN" | "MAX" | "STDDEV" | "STDDEV_POP" | "STDDEV_SAMP" | "VARIANCE"
+                    | "VAR_POP" | "VAR_SAMP" | "ARRAY_AGG" | "STRING_AGG" | "GROUP_CONCAT" | "LISTAGG" | "BOOL_AND"
+                    | "BOOL_OR" | "EVERY" | "JSON_AGG" | "JSONB_AGG" | "JSON_ARRAYAGG" | "JSON_OBJECTAGG"
             ),
             Expression::WindowFunction { .. } => true,
             _ => false,
@@ -290,6 +351,63 @@ impl Expression {
             _ => false,
         }
     }
+
+    /// The expressions directly inside this one; queries in `EXISTS` and subqueries are not included.
+    pub fn children(&self) -> Vec<&Expression> {
+        match self {
+            Expression::Literal(_)
+            | Expression::Column(_)
+            | Expression::QualifiedColumn { .. }
+            | Expression::Parameter(_)
+            | Expression::Wildcard
+            | Expression::Exists(_)
+            | Expression::Subquery(_) => Vec::new(),
+            Expression::Function { args, .. } | Expression::Array(args) => args.iter().collect(),
+            Expression::BinaryOp { left, right, .. } | Expression::IsDistinctFrom { left, right, .. } => {
+                vec![left, right]
+            }
+            Expression::UnaryOp { expr, .. }
+            | Expression::IsNull { expr, .. }
+            | Expression::IsTrue { expr, .. }
+            | Expression::IsFalse { expr, .. }
+            | Expression::Cast { expr, .. } => vec![expr],
+            Expression::Between { expr, min, max } | Expression::NotBetween { expr, min, max } => vec![expr, min, max],
+            Expression::In { expr, values } | Expression::NotIn { expr, values } => {
+                std::iter::once(expr.as_ref()).chain(values).collect()
+            }
+            Expression::LikeEscape { expr, pattern, escape, .. } => vec![expr, pattern, escape],
+            Expression::Case { operand, when_clauses, else_expr } => operand
+                .iter()
+                .map(|operand| operand.as_ref())
+                .chain(when_clauses.iter().flat_map(|clause| [&clause.condition, &clause.result]))
+                .chain(else_expr.iter().map(|expr| expr.as_ref()))
+                .collect(),
+            Expression::Subscript { expr, index } => vec![expr, index],
+            Expression::WindowFunction { function, window } => {
+                std::iter::once(function.as_ref()).chain(window.expressions()).collect()
+            }
+        }
+    }
+
+    /// Queries nested anywhere in this expression, outermost first.
+    pub fn subqueries(&self) -> Vec<&SelectStatement> {
+        match self {
+            Expression::Exists(query) | Expression::Subquery(query) => vec![query],
+            _ => self.children().into_iter().flat_map(Expression::subqueries).collect(),
+        }
+    }
+}
+
+impl WindowSpecification {
+    /// `OVER name`, a window defined in the WINDOW clause.
+    pub fn named(name: &str) -> Self {
+        Self { name: Some(name.to_string()), partition_by: Vec::new(), order_by: Vec::new(), frame: None }
+    }
+
+    /// The PARTITION BY and ORDER BY expressions.
+    pub fn expressions(&self) -> impl Iterator<Item = &Expression> {
+        self.partition_by.iter().chain(self.order_by.iter().map(|order| &order.expr))
+    }
 }
 
 impl Default for SelectStatement {
@@ -308,6 +426,7 @@ impl SelectStatement {
             where_clause: None,
             group_by: None,
             having: None,
+            windows: Vec::new(),
             order_by: Vec::new(),
             limit: None,
             offset: None,
@@ -346,6 +465,48 @@ impl SelectStatement {
         self.group_by.is_some()
     }
 
+    /// Every table or view the query reads, including from subqueries and CTE bodies;
+    /// references to the query's own CTEs are left out.
+    pub fn referenced_tables(&self) -> Vec<&SchemaReference> {
+        let mut tables = Vec::new();
+        self.collect_tables(&mut Vec::new(), &mut tables);
+        tables
+    }
+
+    fn collect_tables<'a>(&'a self, ctes: &mut Vec<&'a str>, tables: &mut Vec<&'a SchemaReference>) {
+        let outer_ctes = ctes.len();
+        for cte in &self.with {
+            // A recursive CTE may name itself; a plain one only sees those before it
+            if cte.recursive {
+                ctes.push(&cte.name);
+            }
+            cte.query.collect_tables(ctes, tables);
+            if !cte.recursive {
+                ctes.push(&cte.name);
+            }
+        }
+
+        let mut expressions: Vec<&Expression> = Vec::new();
+        for reference in &self.from {
+            reference.collect_tables(ctes, tables, &mut expressions);
+        }
+        for item in &self.select_list {
+            if let SelectItem::Expression { expr, .. } = item {
+                expressions.push(expr);
+            }
+        }
+        expressions.extend(self.where_clause.iter().chain(&self.having));
+        for expression in expressions {
+            for query in expression.subqueries() {
+                query.collect_tables(ctes, tables);
+            }
+        }
+        for union in &self.unions {
+            union.select.collect_tables(ctes, tables);
+        }
+        ctes.truncate(outer_ctes);
+    }
+
     pub fn requires_group_by(&self) -> bool {
         self.has_aggregates() && !self.select_list.iter().all(|item| match item {
             SelectItem::Expression { expr, .. } => expr.is_aggregate() || matches!(expr, Expression::Column(_) | Expression::QualifiedColumn { .. }),
@@ -357,7 +518,7 @@ impl SelectStatement {
 impl InsertStatement {
     pub fn new(table_name: &str) -> Self {
         Self {
-            table_name: table_name.to_string(),
+            table_name: SchemaReference::new(table_name),
             columns: Vec::new(),
             values: Vec::new(),
             select: None,
@@ -397,16 +558,16 @@ impl InsertStatement {
 impl UpdateStatement {
     pub fn new(table_name: &str) -> Self {
         Self {
-            table_name: table_name.to_string(),
+            table_name: SchemaReference::new(table_name),
             alias: None,
-            assignments: HashMap::new(),
+            assignments: Vec::new(),
             from: Vec::new(),
             where_clause: None,
             returning: Vec::new(),
         }
     }
 
-    pub fn with_assignments(mut self, assignments: HashMap<String, Expression>) -> Self {
+    pub fn with_assignments(mut self, assignments: Vec<Assignment>) -> Self {
         self.assignments = assignments;
         self
     }
@@ -423,12 +584,16 @@ impl UpdateStatement {
     pub fn has_where_clause(&self) -> bool {
         self.where_clause.is_some()
     }
+
+    pub fn assignment(&self, column: &str) -> Option<&Expression> {
+        self.assignments.iter().find(|assignment| assignment.column == column).map(|assignment| &assignment.value)
+    }
 }
 
 impl DeleteStatement {
     pub fn new(table_name: &str) -> Self {
         Self {
-            table_name: table_name.to_string(),
+            table_name: SchemaReference::new(table_name),
             alias: None,
             using: Vec::new(),
             where_clause: None,
@@ -453,14 +618,14 @@ impl DeleteStatement {
 impl TableReference {
     pub fn table(name: &str) -> Self {
         TableReference::Table {
-            name: name.to_string(),
+            name: SchemaReference::new(name),
             alias: None,
         }
     }
 
     pub fn table_with_alias(name: &str, alias: &str) -> Self {
         TableReference::Table {
-            name: name.to_string(),
+            name: SchemaReference::new(name),
             alias: Some(alias.to_string()),
         }
     }
@@ -482,7 +647,7 @@ impl TableReference {
 
     pub fn get_name(&self) -> Option<&str> {
         match self {
-            TableReference::Table { name, .. } => Some(name),
+            TableReference::Table { name, .. } => Some(&name.object),
             TableReference::Subquery { alias, .. } => Some(alias),
             TableReference::Join { .. } => None,
         }
@@ -495,4 +660,30 @@ impl TableReference {
             TableReference::Join { .. } => None,
         }
     }
+
+    fn collect_tables<'a>(
+        &'a self,
+        ctes: &mut Vec<&'a str>,
+        tables: &mut Vec<&'a SchemaReference>,
+        conditions: &mut Vec<&'a Expression>,
+    ) {
+        match self {
+            TableReference::Table { name, .. } => {
+                let is_cte = !name.is_qualified() && ctes.iter().any(|cte| cte.eq_ignore_ascii_case(&name.object));
+                if !is_cte {
+                    tables.push(name);
+                }
+            }
+            TableReference::Subquery { query, .. } => query.collect_tables(ctes, tables),
+            TableReference::Join { left, joins, .. } => {
+                left.collect_tables(ctes, tables, conditions);
+                for join in joins {
+                    join.table.collect_tables(ctes, tables, conditions);
+                    if let Some(JoinCondition::On(condition)) = &join.condition {
+                        conditions.push(condition);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -7,4 +7,17 @@ use crate::core::ast::dml::DmlStatement;
 pub enum Statement {
     Ddl(DdlStatement),
     Dml(Box<DmlStatement>),
+    Session(SessionStatement),
+}
+
+/// Statements that change connection state rather than the schema or its data, such as the
+/// `SET` and `LOCK TABLES` lines that fill a mysqldump file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SessionStatement {
+    /// `SET name = value`, `SET NAMES utf8mb4`; the value is kept as written
+    Set { variable: String, value: String },
+    LockTables { tables: Vec<String> },
+    UnlockTables,
+    /// `USE name`: later unqualified names refer to this database (a schema, in MySQL)
+    Use { database: String },
 }
@@ -167,7 +167,18 @@ pub struct QueryMetadata {
     pub query_type: QueryType,
     pub tables_accessed: Vec<String>,
     pub columns_accessed: Vec<String>,
+    pub join_count: usize,
+    /// How deeply subqueries nest inside one another; 0 when there are none
+    pub subquery_depth: usize,
+    /// How deeply WITH clauses nest, counting those inside CTE bodies; 0 when there are none
+    pub cte_depth: usize,
+    /// Aggregate functions called outside window functions, by upper-case name
+    pub aggregates: Vec<String>,
+    /// Functions called with an OVER clause, by upper-case name
+    pub window_functions: Vec<String>,
     pub estimated_complexity: QueryComplexity,
+    /// What made the query as complex as it was estimated to be
+    pub complexity_reasons: Vec<String>,
     pub documentation: Option<DocumentationMetadata>,
     pub api_metadata: Option<ApiMetadata>,
 }
@@ -309,6 +320,47 @@ impl SchemaReference {
             object: object.to_string(),
         }
     }
+
+    /// Builds a reference from the dotted parts of a name: `object`, `schema.object` or
+    /// `catalog.schema.object`. Returns `None` for any other number of parts.
+    pub fn from_parts(parts: &[String]) -> Option<Self> {
+        match parts {
+            [object] => Some(Self::new(object)),
+            [schema, object] => Some(Self::with_schema(schema, object)),
+            [catalog, schema, object] => Some(Self::with_catalog(catalog, schema, object)),
+            _ => None,
+        }
+    }
+
+    pub fn is_qualified(&self) -> bool {
+        self.schema.is_some()
+    }
+}
+
+impl From<&str> for SchemaReference {
+    fn from(object: &str) -> Self {
+        Self::new(object)
+    }
+}
+
+impl From<String> for SchemaReference {
+    fn from(object: String) -> Self {
+        Self { catalog: None, schema: None, object }
+    }
+}
+
+/// Compares with the name as written, qualifiers included.
+impl PartialEq<str> for SchemaReference {
+    fn eq(&self, other: &str) -> bool {
+        let parts = [&self.catalog, &self.schema].into_iter().flatten().chain([&self.object]);
+        parts.map(String::as_str).eq(other.split('.'))
+    }
+}
+
+impl PartialEq<&str> for SchemaReference {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
 }
 
 impl fmt::Display for SchemaReference {
@@ -416,6 +468,50 @@ impl DocumentationMetadata {
     }
 }
 
+impl ApiMetadata {
+    pub fn new(http_method: &str, endpoint: &str) -> Self {
+        Self {
+            http_method: Some(http_method.to_string()),
+            endpoint: Some(endpoint.to_string()),
+            parameters: Vec::new(),
+            response_schema: None,
+            authentication_required: false,
+        }
+    }
+
+    pub fn with_parameters(mut self, parameters: Vec<ApiParameter>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    pub fn with_response_schema(mut self, response_schema: &str) -> Self {
+        self.response_schema = Some(response_schema.to_string());
+        self
+    }
+}
+
+impl ApiParameter {
+    pub fn new(name: &str, param_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            param_type: param_type.to_string(),
+            required: true,
+            description: None,
+            default_value: None,
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+}
+
 impl ColumnMetadata {
     pub fn new(name: &str, data_type: &str) -> Self {
         Self {
@@ -527,7 +623,13 @@ impl QueryMetadata {
             query_type,
             tables_accessed: Vec::new(),
             columns_accessed: Vec::new(),
+            join_count: 0,
+            subquery_depth: 0,
+            cte_depth: 0,
+            aggregates: Vec::new(),
+            window_functions: Vec::new(),
             estimated_complexity: QueryComplexity::Simple,
+            complexity_reasons: Vec::new(),
             documentation: None,
             api_metadata: None,
         }
@@ -547,6 +649,47 @@ impl QueryMetadata {
         self.estimated_complexity = complexity;
         self
     }
+
+    pub fn with_complexity_reasons(mut self, reasons: Vec<String>) -> Self {
+        self.complexity_reasons = reasons;
+        self
+    }
+
+    pub fn with_documentation(mut self, documentation: DocumentationMetadata) -> Self {
+        self.documentation = Some(documentation);
+        self
+    }
+
+    pub fn with_api_metadata(mut self, api_metadata: ApiMetadata) -> Self {
+        self.api_metadata = Some(api_metadata);
+        self
+    }
+}
+
+impl fmt::Display for QueryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            QueryType::Select => "SELECT",
+            QueryType::Insert => "INSERT",
+            QueryType::Update => "UPDATE",
+            QueryType::Delete => "DELETE",
+            QueryType::DDL => "DDL",
+            QueryType::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for QueryComplexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            QueryComplexity::Simple => "simple",
+            QueryComplexity::Medium => "medium",
+            QueryComplexity::Complex => "complex",
+            QueryComplexity::VeryComplex => "very complex",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 impl fmt::Display for AstParseError {
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::core::analyze::{ChangeAction, DiffAttribute, DiffObject, SchemaChange, SchemaDiff};
+use crate::core::docs::json_schema::{is_required, type_schema};
+use crate::core::domain::entity::{Column, Database};
+
+/// What a schema change does to the API contract derived from the schema: the row schemas
+/// [`JsonSchemaRenderer`](super::JsonSchemaRenderer) and [`OpenApiRenderer`](super::OpenApiRenderer)
+/// generate. Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiImpact {
+    /// The contract is unchanged, or only accepts more than it did
+    NonBreaking,
+    /// The contract gains something clients may start to use
+    Additive,
+    /// A client written against the old contract may fail
+    Breaking,
+}
+
+impl fmt::Display for ApiImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ApiImpact::NonBreaking => "non-breaking",
+            ApiImpact::Additive => "additive",
+            ApiImpact::Breaking => "breaking",
+        })
+    }
+}
+
+/// The semantic version bump a set of changes calls for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionBump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VersionBump::None => "none",
+            VersionBump::Patch => "patch",
+            VersionBump::Minor => "minor",
+            VersionBump::Major => "major",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifiedChange {
+    #[serde(flatten)]
+    pub change: SchemaChange,
+    pub impact: ApiImpact,
+    /// What the change does to the contract, in a few words
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub bump: VersionBump,
+    pub changes: Vec<ClassifiedChange>,
+}
+
+impl CompatibilityReport {
+    pub fn count(&self, impact: ApiImpact) -> usize {
+        self.changes.iter().filter(|change| change.impact == impact).count()
+    }
+
+    /// The breaking changes no approval covers. An approval names an object as the diff does,
+    /// such as `orders.note`, and covers it and everything named after it, so approving a
+    /// table approves its columns too. An approval of an old name follows the rename, so it
+    /// covers a renamed table's columns under the table's new name.
+    pub fn unapproved(&self, approvals: &[String]) -> Vec<&ClassifiedChange> {
+        let covers = |approval: &str, name: &str| {
+            name == approval || name.strip_prefix(approval).is_some_and(|rest| rest.starts_with('.'))
+        };
+        // Tables are renamed before their columns, so a column is found under its table's new name
+        let mut approvals: Vec<String> = approvals.iter().map(|approval| approval.to_lowercase()).collect();
+        let renames = self.changes.iter().filter(|change| change.change.action == ChangeAction::Renamed);
+        for change in renames {
+            let Some(old) = change.change.old.as_deref() else {
+                continue;
+            };
+            let (old, new) = (old.to_lowercase(), change.change.name.to_lowercase());
+            let followed: Vec<String> = approvals
+                .iter()
+                .filter(|approval| covers(&old, approval))
+                .map(|approval| format!("{}{}", new, &approval[old.len()..]))
+                .collect();
+            approvals.extend(followed);
+        }
+        let approved = |name: &str| {
+            let name = name.to_lowercase();
+            approvals.iter().any(|approval| covers(approval, &name))
+        };
+        self.changes
+            .iter()
+            .filter(|change| change.impact == ApiImpact::Breaking)
+            .filter(|change| !approved(&change.change.name) && !change.change.old.as_deref().is_some_and(approved))
+            .collect()
+    }
+}
+
+/// Classifies each change of a [`SchemaDiff`] by what it does to the row schema of each table:
+/// a property per column, typed as [`type_schema`] has it, nullable as the column is, and
+/// required when the database doesn't fill it in. Removing or renaming anything a client reads
+/// breaks it, as do tighter types, newly nullable properties and newly required ones; new
+/// tables, optional properties and enum values are additive. Keys, indexes, triggers, views
+/// and routines aren't part of a row schema and never break it.
+pub struct ApiCompatibility<'a> {
+    old: &'a Database,
+    new: &'a Database,
+}
+
+impl<'a> ApiCompatibility<'a> {
+    pub fn new(old: &'a Database, new: &'a Database) -> Self {
+        Self { old, new }
+    }
+
+    pub fn classify(&self, diff: &SchemaDiff) -> CompatibilityReport {
+        // Modified columns are named as they are now; the renames say what they were called
+        let mut renamed_tables: HashMap<&str, &str> = HashMap::new();
+        let mut renamed_columns: HashMap<&str, &str> = HashMap::new();
+        for change in diff.changes.iter().filter(|change| change.action == ChangeAction::Renamed) {
+            let Some(old) = change.old.as_deref() else {
+                continue;
+            };
+            match change.object {
+                DiffObject::Table => {
+                    renamed_tables.insert(&change.name, old);
+                }
+                DiffObject::Column => {
+                    renamed_columns.insert(&change.name, old);
+                }
+                _ => {}
+            }
+        }
+        let old_column = |name: &str| -> Option<&Column> {
+            let (table, column) = split(renamed_columns.get(name).copied().unwrap_or(name))?;
+            find_column(self.old, renamed_tables.get(table).copied().unwrap_or(table), column)
+        };
+
+        let changes: Vec<ClassifiedChange> = diff
+            .changes
+            .iter()
+            .map(|change| {
+                let (impact, reason) = match (change.object, change.action) {
+                    (DiffObject::Table, ChangeAction::Added) => (ApiImpact::Additive, "a new row schema".to_string()),
+                    (DiffObject::Table, ChangeAction::Removed) => (ApiImpact::Breaking, "its row schema is gone".to_string()),
+                    (DiffObject::Table, _) => (ApiImpact::Breaking, "its row schema goes by a new name".to_string()),
+                    (DiffObject::Column, ChangeAction::Added) => {
+                        match split(&change.name).and_then(|(table, column)| find_column(self.new, table, column)) {
+                            Some(column) if is_required(column) => {
+                                (ApiImpact::Breaking, "a new required property in requests".to_string())
+                            }
+                            _ => (ApiImpact::Additive, "a new optional property".to_string()),
+                        }
+                    }
+                    (DiffObject::Column, ChangeAction::Removed) => {
+                        (ApiImpact::Breaking, "the property is no longer returned".to_string())
+                    }
+                    (DiffObject::Column, ChangeAction::Renamed) => {
+                        (ApiImpact::Breaking, "the property goes by a new name".to_string())
+                    }
+                    (DiffObject::Column, ChangeAction::Modified) => {
+                        let new = split(&change.name).and_then(|(table, column)| find_column(self.new, table, column));
+                        match (old_column(&change.name), new, change.attribute) {
+                            (Some(old), Some(new), Some(attribute)) => classify_column(old, new, attribute),
+                            _ => (ApiImpact::Breaking, "the property changes in a way that could not be traced".to_string()),
+                        }
+                    }
+                    (DiffObject::Type, _) => {
+                        (ApiImpact::NonBreaking, "the columns of the type are classified on their own".to_string())
+                    }
+                    (object, _) => (ApiImpact::NonBreaking, format!("{} are not part of a row schema", object.plural())),
+                };
+                ClassifiedChange { change: change.clone(), impact, reason }
+            })
+            .collect();
+
+        let bump = match changes.iter().map(|change| change.impact).max() {
+            Some(ApiImpact::Breaking) => VersionBump::Major,
+            Some(ApiImpact::Additive) => VersionBump::Minor,
+            Some(ApiImpact::NonBreaking) => VersionBump::Patch,
+            None => VersionBump::None,
+        };
+        CompatibilityReport { bump, changes }
+    }
+}
+
+fn classify_column(old: &Column, new: &Column, attribute: DiffAttribute) -> (ApiImpact, String) {
+    match attribute {
+        DiffAttribute::Type => classify_type(&type_schema(&old.data_type), &type_schema(&new.data_type)),
+        DiffAttribute::Nullability if new.nullable => (ApiImpact::Breaking, "the property may now be null".to_string()),
+        DiffAttribute::Generated if old.generated.is_none() => {
+            (ApiImpact::Breaking, "the property becomes read-only".to_string())
+        }
+        _ => match (is_required(old), is_required(new)) {
+            (false, true) => (ApiImpact::Breaking, "the property becomes required in requests".to_string()),
+            (true, false) => (ApiImpact::NonBreaking, "the property becomes optional in requests".to_string()),
+            _ if attribute == DiffAttribute::Nullability => {
+                (ApiImpact::NonBreaking, "the property is never null".to_string())
+            }
+            _ => (ApiImpact::NonBreaking, "the property's schema is unchanged".to_string()),
+        },
+    }
+}
+
+/// Compares the JSON Schemas of a column's old and new type.
+fn classify_type(old: &Map<String, Value>, new: &Map<String, Value>) -> (ApiImpact, String) {
+    if old == new {
+        return (ApiImpact::NonBreaking, "the property's schema is unchanged".to_string());
+    }
+    let describe = |schema: &Map<String, Value>| {
+        let json_type = schema.get("type").and_then(Value::as_str).unwrap_or("any");
+        match schema.get("format").and_then(Value::as_str) {
+            Some(format) => format!("{} ({})", json_type, format),
+            None => json_type.to_string(),
+        }
+    };
+    if old.get("type") != new.get("type") || old.get("format") != new.get("format") || old.get("items") != new.get("items") {
+        return (ApiImpact::Breaking, format!("the property changes from {} to {}", describe(old), describe(new)));
+    }
+
+    let max_length = |schema: &Map<String, Value>| schema.get("maxLength").and_then(Value::as_u64);
+    match (max_length(old), max_length(new)) {
+        (Some(before), Some(after)) if after < before => {
+            return (ApiImpact::Breaking, format!("maxLength tightens from {} to {}", before, after));
+        }
+        (None, Some(after)) => return (ApiImpact::Breaking, format!("the property gains maxLength {}", after)),
+        (before, after) if before != after => {
+            return (ApiImpact::NonBreaking, "maxLength loosens".to_string());
+        }
+        _ => {}
+    }
+
+    let values = |schema: &Map<String, Value>| -> Vec<Value> {
+        schema.get("enum").and_then(Value::as_array).cloned().unwrap_or_default()
+    };
+    let (before, after) = (values(old), values(new));
+    let removed: Vec<String> = before.iter().filter(|value| !after.contains(value)).map(ToString::to_string).collect();
+    if !removed.is_empty() {
+        return (ApiImpact::Breaking, format!("enum values {} are no longer allowed", removed.join(", ")));
+    }
+    if after.iter().any(|value| !before.contains(value)) {
+        return (ApiImpact::Additive, "the property allows new enum values".to_string());
+    }
+    (ApiImpact::NonBreaking, "the property's schema is unchanged".to_string())
+}
+
+/// Splits `table.column`, where the table may be schema-qualified.
+fn split(name: &str) -> Option<(&str, &str)> {
+    name.rsplit_once('.')
+}
+
+fn find_column<'d>(database: &'d Database, table: &str, column: &str) -> Option<&'d Column> {
+    database
+        .get_all_tables()
+        .into_iter()
+        .find(|candidate| match &candidate.schema {
+            Some(schema) => format!("{}.{}", schema, candidate.name) == table,
+            None => candidate.name == table,
+        })
+        .and_then(|table| table.get_column(column))
+}
@@ -0,0 +1,125 @@
+use crate::core::analyze::{ChangeAction, SchemaChange, SchemaDiff};
+use crate::core::docs::compatibility::{ApiImpact, CompatibilityReport};
+use crate::core::docs::markdown::{code, escape};
+
+/// Renders a [`SchemaDiff`] as plain text, a line per change, or as a Markdown table; a
+/// [`CompatibilityReport`] likewise, with each change's effect on the API.
+#[derive(Debug, Default)]
+pub struct SchemaDiffRenderer;
+
+impl SchemaDiffRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// A `+`, `-`, `>` or `~` for added, removed, renamed or modified, then the change.
+    pub fn text(&self, diff: &SchemaDiff) -> String {
+        if diff.is_empty() {
+            return "No schema changes.\n".to_string();
+        }
+        let count = diff.changes.len();
+        let mut out = format!("{} schema change{}:\n", count, if count == 1 { "" } else { "s" });
+        for change in &diff.changes {
+            out.push_str(&format!("  {} {}\n", symbol(change.action), change));
+        }
+        out
+    }
+
+    pub fn markdown(&self, diff: &SchemaDiff) -> String {
+        let mut out = String::from("# Schema Changes\n\n");
+        if diff.is_empty() {
+            out.push_str("No schema changes.\n");
+            return out;
+        }
+
+        out.push_str("| Change | Object | Name | Details |\n");
+        out.push_str("|--------|--------|------|---------|\n");
+        for change in &diff.changes {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                change.action,
+                change.object,
+                code(&change.name),
+                details(change)
+            ));
+        }
+        out
+    }
+
+    /// The recommended version bump, then the changes grouped from breaking to non-breaking.
+    pub fn report_text(&self, report: &CompatibilityReport) -> String {
+        if report.changes.is_empty() {
+            return "No schema changes.\n".to_string();
+        }
+        let mut out = format!("Recommended version bump: {} ({})\n", report.bump, summary(report));
+        for impact in [ApiImpact::Breaking, ApiImpact::Additive, ApiImpact::NonBreaking] {
+            let changes: Vec<_> = report.changes.iter().filter(|change| change.impact == impact).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            let mut heading = impact.to_string();
+            heading[..1].make_ascii_uppercase();
+            out.push_str(&format!("\n{}:\n", heading));
+            for change in changes {
+                out.push_str(&format!("  {} {}: {}\n", symbol(change.change.action), change.change, change.reason));
+            }
+        }
+        out
+    }
+
+    pub fn report_markdown(&self, report: &CompatibilityReport) -> String {
+        let mut out = String::from("# Schema Changes\n\n");
+        if report.changes.is_empty() {
+            out.push_str("No schema changes.\n");
+            return out;
+        }
+
+        out.push_str(&format!("**Recommended version bump:** {} ({})\n\n", report.bump, summary(report)));
+        out.push_str("| Change | Object | Name | Details | API impact |\n");
+        out.push_str("|--------|--------|------|---------|------------|\n");
+        for change in &report.changes {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | **{}**: {} |\n",
+                change.change.action,
+                change.change.object,
+                code(&change.change.name),
+                details(&change.change),
+                change.impact,
+                escape(&change.reason)
+            ));
+        }
+        out
+    }
+}
+
+fn summary(report: &CompatibilityReport) -> String {
+    [ApiImpact::Breaking, ApiImpact::Additive, ApiImpact::NonBreaking]
+        .iter()
+        .map(|impact| format!("{} {}", report.count(*impact), impact))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn symbol(action: ChangeAction) -> char {
+    match action {
+        ChangeAction::Added => '+',
+        ChangeAction::Removed => '-',
+        ChangeAction::Renamed => '>',
+        ChangeAction::Modified => '~',
+    }
+}
+
+fn details(change: &SchemaChange) -> String {
+    let value = |value: &Option<String>| value.as_deref().map(code).unwrap_or_else(|| "none".to_string());
+    match change.action {
+        ChangeAction::Added | ChangeAction::Removed if change.object.has_body() => String::new(),
+        ChangeAction::Added => change.new.as_deref().map(code).unwrap_or_default(),
+        ChangeAction::Removed => change.old.as_deref().map(code).unwrap_or_default(),
+        ChangeAction::Renamed => format!("from {}", value(&change.old)),
+        ChangeAction::Modified => match change.attribute {
+            Some(attribute) if change.object.has_body() => format!("{} changed", attribute),
+            Some(attribute) => format!("{}: {} → {}", escape(&attribute.to_string()), value(&change.old), value(&change.new)),
+            None => String::new(),
+        },
+    }
+}
@@ -0,0 +1,178 @@
+use crate::core::analyze::{ColumnLineage, ViewLineage};
+use crate::core::domain::entity::{Column, Database, Table, View};
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#222}\
+table{border-collapse:collapse;margin:1rem 0}th,td{border:1px solid #ccc;padding:.3rem .6rem;text-align:left}\
+th{background:#f4f4f4}code,pre{font-family:ui-monospace,monospace}pre{background:#f7f7f7;padding:.8rem;overflow-x:auto}\
+:target{background:#fff8c4}";
+
+/// Renders a [`Database`] as a single self-contained HTML page: a section per table with its
+/// columns and keys, then a section per view with its definition and, for each of its columns,
+/// the table columns it is derived from as [`ViewLineage`] traces them. Every table column has
+/// its own anchor, so lineage links land on the column itself.
+#[derive(Debug, Default)]
+pub struct HtmlRenderer;
+
+impl HtmlRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, database: &Database, lineage: &[ViewLineage]) -> String {
+        let tables = database.get_all_tables();
+        let views = database.get_all_views();
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n", escape(&database.name), STYLE));
+        out.push_str(&format!("<h1>{}</h1>\n", escape(&database.name)));
+        if let Some(description) = &database.metadata.description {
+            out.push_str(&format!("<p>{}</p>\n", escape(description)));
+        }
+
+        out.push_str("<nav>\n");
+        if !tables.is_empty() {
+            out.push_str("<h2>Tables</h2>\n<ul>\n");
+            for table in &tables {
+                let name = qualified(&table.schema, &table.name);
+                out.push_str(&format!("<li><a href=\"#{}\">{}</a></li>\n", table_id(&name), escape(&name)));
+            }
+            out.push_str("</ul>\n");
+        }
+        if !views.is_empty() {
+            out.push_str("<h2>Views</h2>\n<ul>\n");
+            for view in &views {
+                let name = qualified(&view.schema, &view.name);
+                out.push_str(&format!("<li><a href=\"#{}\">{}</a></li>\n", view_id(&name), escape(&name)));
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</nav>\n");
+
+        for table in &tables {
+            self.render_table(&mut out, table);
+        }
+        for view in &views {
+            let name = qualified(&view.schema, &view.name);
+            self.render_view(&mut out, view, lineage.iter().find(|lineage| lineage.view == name));
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn render_table(&self, out: &mut String, table: &Table) {
+        let name = qualified(&table.schema, &table.name);
+        out.push_str(&format!("<section id=\"{}\">\n<h2>{}</h2>\n", table_id(&name), escape(&name)));
+        if let Some(description) = &table.metadata.description {
+            out.push_str(&format!("<p>{}</p>\n", escape(description)));
+        }
+
+        out.push_str("<table>\n<thead><tr><th>Column</th><th>Type</th><th>Nullable</th><th>Default</th><th>Notes</th></tr></thead>\n<tbody>\n");
+        for column in &table.columns {
+            out.push_str(&format!(
+                "<tr id=\"{}\"><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                column_id(&name, &column.name),
+                escape(&column.name),
+                escape(&column.data_type.to_sql()),
+                if column.nullable { "yes" } else { "no" },
+                column.default_value.as_deref().map(code).unwrap_or_default(),
+                column_notes(table, column).join("; "),
+            ));
+        }
+        out.push_str("</tbody>\n</table>\n</section>\n");
+    }
+
+    fn render_view(&self, out: &mut String, view: &View, lineage: Option<&ViewLineage>) {
+        let name = qualified(&view.schema, &view.name);
+        out.push_str(&format!("<section id=\"{}\">\n<h2>{}</h2>\n", view_id(&name), escape(&name)));
+        if let Some(description) = &view.metadata.description {
+            out.push_str(&format!("<p>{}</p>\n", escape(description)));
+        }
+        out.push_str(&format!("<pre><code>{}</code></pre>\n", escape(view.definition.trim())));
+
+        if let Some(lineage) = lineage
+            && !lineage.columns.is_empty()
+        {
+            out.push_str("<h3>Column lineage</h3>\n");
+            out.push_str("<table>\n<thead><tr><th>Column</th><th>Transformation</th><th>Derived from</th></tr></thead>\n<tbody>\n");
+            for column in &lineage.columns {
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&column.column),
+                    column.transformation,
+                    sources(column),
+                ));
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        if lineage.is_none_or(|lineage| !lineage.complete) {
+            out.push_str("<p>Some columns come from tables whose columns are not known.</p>\n");
+        }
+        out.push_str("</section>\n");
+    }
+}
+
+fn column_notes(table: &Table, column: &Column) -> Vec<String> {
+    let mut notes = Vec::new();
+    if column.is_primary_key(&table.primary_key) {
+        notes.push("primary key".to_string());
+    }
+    if column.is_unique(&table.unique_constraints) {
+        notes.push("unique".to_string());
+    }
+    if let Some(foreign_key) = table.foreign_keys.iter().find(|fk| fk.columns.contains(&column.name)) {
+        let target = qualified(&foreign_key.referenced_schema, &foreign_key.referenced_table);
+        notes.push(format!("references <a href=\"#{}\">{}</a>", table_id(&target), escape(&target)));
+    }
+    if let Some(description) = &column.metadata.description {
+        notes.push(escape(description));
+    }
+    notes
+}
+
+/// The table columns behind a view column, each linked to its row in the table's section.
+fn sources(column: &ColumnLineage) -> String {
+    if column.sources.is_empty() {
+        return "<em>none</em>".to_string();
+    }
+    column
+        .sources
+        .iter()
+        .map(|source| {
+            format!("<a href=\"#{}\"><code>{}</code></a>", column_id(&source.table, &source.column), escape(&source.to_string()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn code(text: &str) -> String {
+    format!("<code>{}</code>", escape(text))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn table_id(name: &str) -> String {
+    format!("table-{}", slug(name))
+}
+
+fn view_id(name: &str) -> String {
+    format!("view-{}", slug(name))
+}
+
+fn column_id(table: &str, column: &str) -> String {
+    format!("column-{}-{}", slug(table), slug(column))
+}
+
+/// Lower case with anything but letters and digits as dashes, so it is a valid fragment.
+fn slug(name: &str) -> String {
+    name.to_lowercase().chars().map(|ch| if ch.is_alphanumeric() { ch } else { '-' }).collect()
+}
+
+/// Every name is qualified, so that lineage sources and sections agree on how tables are named.
+fn qualified(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
@@ -0,0 +1,162 @@
+use serde_json::{Map, Value};
+
+use crate::core::analyze::Projection;
+use crate::core::domain::entity::{Column, DataType, Database, Table};
+
+/// The most characters an `NVARCHAR(MAX)` column holds.
+const NVARCHAR_MAX_LENGTH: u32 = (1 << 30) - 1;
+
+/// Renders a [`Database`] as a JSON Schema document with one definition per table, describing
+/// a row as it would be serialized.
+#[derive(Debug, Default)]
+pub struct JsonSchemaRenderer;
+
+impl JsonSchemaRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, database: &Database) -> Value {
+        // Tables of different schemas may share a name
+        let qualify = database.schemas.len() > 1;
+        let definitions: Map<String, Value> = database
+            .get_all_tables()
+            .into_iter()
+            .map(|table| {
+                let name = match &table.schema {
+                    Some(schema) if qualify => format!("{}.{}", schema, table.name),
+                    _ => table.name.clone(),
+                };
+                (name, self.render_table(table))
+            })
+            .collect();
+
+        let mut schema = Map::new();
+        schema.insert("$schema".to_string(), Value::from("https://json-schema.org/draft/2020-12/schema"));
+        schema.insert("title".to_string(), Value::from(database.name.as_str()));
+        if let Some(description) = &database.metadata.description {
+            schema.insert("description".to_string(), Value::from(description.as_str()));
+        }
+        schema.insert("$defs".to_string(), Value::Object(definitions));
+        Value::Object(schema)
+    }
+
+    fn render_table(&self, table: &Table) -> Value {
+        let properties: Map<String, Value> = table
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), self.render_column(column)))
+            .collect();
+        let required: Vec<&str> =
+            table.columns.iter().filter(|column| is_required(column)).map(|column| column.name.as_str()).collect();
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), Value::from("object"));
+        if let Some(description) = &table.metadata.description {
+            schema.insert("description".to_string(), Value::from(description.as_str()));
+        }
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::from(required));
+        }
+        Value::Object(schema)
+    }
+
+    /// Describes one row of a query's result. Every column is present in a row, so all are
+    /// required; a column whose type couldn't be inferred accepts any value.
+    pub fn render_row(&self, projection: &Projection) -> Value {
+        let properties: Map<String, Value> = projection
+            .columns
+            .iter()
+            .map(|column| {
+                let mut schema = column.data_type.as_ref().map(type_schema).unwrap_or_default();
+                if column.nullable {
+                    allow_null(&mut schema);
+                }
+                (column.name.clone(), Value::Object(schema))
+            })
+            .collect();
+        let required: Vec<&str> = projection.columns.iter().map(|column| column.name.as_str()).collect();
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), Value::from("object"));
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::from(required));
+        }
+        // Columns from a table whose columns aren't known may come on top
+        schema.insert("additionalProperties".to_string(), Value::from(!projection.complete));
+        Value::Object(schema)
+    }
+
+    fn render_column(&self, column: &Column) -> Value {
+        // SQLite stores a type name it doesn't know by the affinity that name implies
+        let mut schema = match (&column.data_type, column.affinity) {
+            (DataType::Custom(_), Some(affinity)) => type_schema(&affinity.data_type()),
+            (data_type, _) => type_schema(data_type),
+        };
+        if column.nullable {
+            allow_null(&mut schema);
+        }
+        if let Some(description) = &column.metadata.description {
+            schema.insert("description".to_string(), Value::from(description.as_str()));
+        }
+        if column.generated.is_some() {
+            schema.insert("readOnly".to_string(), Value::from(true));
+        }
+        Value::Object(schema)
+    }
+}
+
+/// A column the database fills in itself may be left out of the row; any other NOT NULL one
+/// must be given.
+pub(super) fn is_required(column: &Column) -> bool {
+    !column.nullable
+        && column.default_value.is_none()
+        && column.generated.is_none()
+        && !column.auto_increment
+        && !column.rowid_alias
+}
+
+pub(super) fn allow_null(schema: &mut Map<String, Value>) {
+    if let Some(Value::String(json_type)) = schema.get("type").cloned() {
+        schema.insert("type".to_string(), Value::from(vec![json_type, "null".to_string()]));
+    }
+}
+
+pub(super) fn type_schema(data_type: &DataType) -> Map<String, Value> {
+    let mut schema = Map::new();
+    // SQLite's ANY columns hold whatever was stored
+    if matches!(data_type, DataType::Custom(name) if name.eq_ignore_ascii_case("ANY")) {
+        return schema;
+    }
+
+    schema.insert("type".to_string(), Value::from(data_type.json_schema_type()));
+    if let Some(format) = data_type.json_schema_format() {
+        schema.insert("format".to_string(), Value::from(format));
+    }
+    match data_type {
+        DataType::Char { length: Some(length) }
+        | DataType::VarChar { length: Some(length) }
+        | DataType::NChar { length: Some(length) }
+        | DataType::NVarChar { length: Some(length) } => {
+            schema.insert("maxLength".to_string(), Value::from(*length));
+        }
+        // SQL Server's NVARCHAR(MAX) holds up to 2^30 - 1 characters
+        DataType::NVarChar { length: None } => {
+            schema.insert("maxLength".to_string(), Value::from(NVARCHAR_MAX_LENGTH));
+        }
+        DataType::Enum { values } => {
+            schema.insert("enum".to_string(), Value::from(values.clone()));
+        }
+        DataType::Set { values } => {
+            schema.insert("items".to_string(), Value::Object(Map::from_iter([("enum".to_string(), Value::from(values.clone()))])));
+            schema.insert("uniqueItems".to_string(), Value::from(true));
+        }
+        DataType::Array { element_type, .. } => {
+            schema.insert("items".to_string(), Value::Object(type_schema(element_type)));
+        }
+        _ => {}
+    }
+    schema
+}
@@ -0,0 +1,527 @@
+use crate::core::domain::entity::{
+    Column, Database, ForeignKey, IndexType, MigrationEvent, ReferentialAction, Schema, Sequence, Table, UserDefinedType,
+    UserDefinedTypeKind,
+};
+
+/// Renders a [`Database`] as a single Markdown document: one section per table, then the
+/// reusable types (enums, domains, composites) and sequences the columns refer to. A database
+/// with several schemas gets one part per schema.
+#[derive(Debug, Default)]
+pub struct MarkdownRenderer;
+
+impl MarkdownRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, database: &Database) -> String {
+        let mut out = String::new();
+        let tables = database.get_all_tables();
+        // With more than one schema, every name is qualified and the sections are grouped per schema
+        let qualify = database.schemas.len() > 1;
+
+        out.push_str(&format!("# {}\n\n", database.name));
+        if let Some(description) = &database.metadata.description {
+            out.push_str(&format!("{}\n\n", description));
+        }
+
+        if !qualify {
+            let types = database.get_all_types();
+            let sequences = database.get_all_sequences();
+            self.render_contents(&mut out, "##", &tables, &types, &sequences, qualify);
+            self.render_sections(&mut out, database, &tables, &types, &sequences, qualify);
+        } else {
+            out.push_str("## Schemas\n\n");
+            for schema in &database.schemas {
+                let heading = schema_heading(schema);
+                out.push_str(&format!("- [{}](#{})\n", heading, anchor(&heading)));
+            }
+            out.push('\n');
+
+            for schema in &database.schemas {
+                out.push_str(&format!("## {}\n\n", schema_heading(schema)));
+                if let Some(description) = &schema.metadata.description {
+                    out.push_str(&format!("{}\n\n", description));
+                }
+                if let Some(owner) = &schema.metadata.owner {
+                    out.push_str(&format!("**Owner:** {}\n\n", code(owner)));
+                }
+                let schema_tables: Vec<&Table> = schema.tables.iter().collect();
+                let types: Vec<&UserDefinedType> = schema.types.iter().collect();
+                let sequences: Vec<&Sequence> = schema.sequences.iter().collect();
+                self.render_contents(&mut out, "###", &schema_tables, &types, &sequences, qualify);
+                self.render_sections(&mut out, database, &schema_tables, &types, &sequences, qualify);
+            }
+        }
+
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    /// Lists the tables, types and sequences with links to their sections.
+    fn render_contents(
+        &self,
+        out: &mut String,
+        level: &str,
+        tables: &[&Table],
+        types: &[&UserDefinedType],
+        sequences: &[&Sequence],
+        qualify: bool,
+    ) {
+        if !tables.is_empty() {
+            out.push_str(&format!("{} Tables\n\n", level));
+            for table in tables {
+                let name = qualified(&table.schema, &table.name, qualify);
+                out.push_str(&format!("- [{}](#{})\n", name, anchor(&name)));
+            }
+            out.push('\n');
+        }
+        if !types.is_empty() {
+            out.push_str(&format!("{} Types\n\n", level));
+            for user_type in types {
+                out.push_str(&format!(
+                    "- [{}](#{})\n",
+                    qualified(&user_type.schema, &user_type.name, qualify),
+                    type_anchor(user_type, qualify)
+                ));
+            }
+            out.push('\n');
+        }
+        if !sequences.is_empty() {
+            out.push_str(&format!("{} Sequences\n\n", level));
+            for sequence in sequences {
+                out.push_str(&format!(
+                    "- [{}](#{})\n",
+                    qualified(&sequence.schema, &sequence.name, qualify),
+                    sequence_anchor(sequence, qualify)
+                ));
+            }
+            out.push('\n');
+        }
+    }
+
+    fn render_sections(
+        &self,
+        out: &mut String,
+        database: &Database,
+        tables: &[&Table],
+        types: &[&UserDefinedType],
+        sequences: &[&Sequence],
+        qualify: bool,
+    ) {
+        // Types and sequences may be used from any schema
+        let all_tables = database.get_all_tables();
+        for table in tables {
+            self.render_table(out, database, table, qualify);
+        }
+        for user_type in types {
+            self.render_type(out, &all_tables, user_type, qualify);
+        }
+        for sequence in sequences {
+            self.render_sequence(out, &all_tables, sequence, qualify);
+        }
+    }
+
+    fn render_table(&self, out: &mut String, database: &Database, table: &Table, qualify: bool) {
+        out.push_str(&format!("### {}\n\n", qualified(&table.schema, &table.name, qualify)));
+        if let Some(description) = &table.metadata.description {
+            out.push_str(&format!("{}\n\n", description));
+        }
+        if !table.metadata.history.is_empty() {
+            out.push_str(&format!("**History:** {}\n\n", history(&table.metadata.history)));
+        }
+
+        let mut storage: Vec<String> = [("Engine", &table.engine), ("Charset", &table.charset), ("Collation", &table.collation)]
+            .into_iter()
+            .filter_map(|(label, value)| value.as_ref().map(|value| format!("**{}:** {}", label, code(value))))
+            .collect();
+        if table.strict {
+            storage.push("**Strict**".to_string());
+        }
+        if table.without_rowid {
+            storage.push("**Without rowid**".to_string());
+        }
+        if !storage.is_empty() {
+            out.push_str(&format!("{}\n\n", storage.join(" · ")));
+        }
+
+        out.push_str("| Column | Type | Nullable | Default | Notes |\n");
+        out.push_str("|--------|------|----------|---------|-------|\n");
+        for column in &table.columns {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                escape(&column.name),
+                self.column_type(database, table, column, qualify),
+                if column.nullable { "yes" } else { "no" },
+                column.default_value.as_deref().map(code).unwrap_or_default(),
+                column_notes(table, column, qualify).join("; "),
+            ));
+        }
+        out.push('\n');
+
+        if let Some(primary_key) = &table.primary_key {
+            out.push_str(&format!("**Primary key:** {}\n\n", list(&primary_key.columns)));
+        }
+
+        if !table.foreign_keys.is_empty() {
+            out.push_str("**Foreign keys:**\n\n");
+            for foreign_key in &table.foreign_keys {
+                let text = describe_foreign_key(foreign_key, qualify);
+                out.push_str(&format!("- {}\n", with_history(text, &foreign_key.metadata.history)));
+            }
+            out.push('\n');
+        }
+
+        if !table.unique_constraints.is_empty() {
+            out.push_str("**Unique constraints:**\n\n");
+            for unique in &table.unique_constraints {
+                let text = format!("{}{}", named(&unique.name), list(&unique.columns));
+                out.push_str(&format!("- {}\n", with_history(text, &unique.metadata.history)));
+            }
+            out.push('\n');
+        }
+
+        if !table.check_constraints.is_empty() {
+            out.push_str("**Check constraints:**\n\n");
+            for check in &table.check_constraints {
+                let text = format!("{}{}", named(&check.name), code(&check.expression));
+                out.push_str(&format!("- {}\n", with_history(text, &check.metadata.history)));
+            }
+            out.push('\n');
+        }
+
+        if !table.exclusion_constraints.is_empty() {
+            out.push_str("**Exclusion constraints:**\n\n");
+            for exclusion in &table.exclusion_constraints {
+                let elements: Vec<String> = exclusion
+                    .elements
+                    .iter()
+                    .map(|element| format!("{} WITH {}", element.expression, element.operator))
+                    .collect();
+                let mut text = format!("USING {} ({})", index_method(&exclusion.index_type), elements.join(", "));
+                if let Some(where_clause) = &exclusion.where_clause {
+                    text.push_str(&format!(" WHERE ({})", where_clause));
+                }
+                let text = format!("{}{}", named(&exclusion.name), code(&text));
+                out.push_str(&format!("- {}\n", with_history(text, &exclusion.metadata.history)));
+            }
+            out.push('\n');
+        }
+
+        if !table.indexes.is_empty() {
+            out.push_str("**Indexes:**\n\n");
+            for index in &table.indexes {
+                let columns: Vec<String> = index.columns.iter().map(|column| column.name.clone()).collect();
+                let mut text = format!("`{}` on {}", index.name, list(&columns));
+                if index.unique {
+                    text.push_str(", unique");
+                }
+                if index.index_type != IndexType::BTree {
+                    text.push_str(&format!(", {}", index_method(&index.index_type)));
+                }
+                if let Some(where_clause) = &index.where_clause {
+                    text.push_str(&format!(", where {}", code(where_clause)));
+                }
+                out.push_str(&format!("- {}\n", with_history(text, &index.metadata.history)));
+            }
+            out.push('\n');
+        }
+    }
+
+    fn render_type(&self, out: &mut String, tables: &[&Table], user_type: &UserDefinedType, qualify: bool) {
+        out.push_str(&format!(
+            "### {}: {}\n\n",
+            kind_name(&user_type.kind),
+            qualified(&user_type.schema, &user_type.name, qualify)
+        ));
+        if let Some(description) = &user_type.description {
+            out.push_str(&format!("{}\n\n", description));
+        }
+
+        match &user_type.kind {
+            UserDefinedTypeKind::Enum { values } => {
+                let values: Vec<String> = values.iter().map(|value| code(value)).collect();
+                out.push_str(&format!("Values: {}\n\n", values.join(", ")));
+            }
+            UserDefinedTypeKind::Domain { base_type, nullable, default_value, checks } => {
+                out.push_str(&format!("Base type: {}\n\n", code(&base_type.to_sql())));
+                if !nullable {
+                    out.push_str("Not null\n\n");
+                }
+                if let Some(default_value) = default_value {
+                    out.push_str(&format!("Default: {}\n\n", code(default_value)));
+                }
+                for check in checks {
+                    out.push_str(&format!("Check: {}\n\n", code(check)));
+                }
+            }
+            UserDefinedTypeKind::Composite { attributes } => {
+                out.push_str("| Attribute | Type |\n");
+                out.push_str("|-----------|------|\n");
+                for attribute in attributes {
+                    out.push_str(&format!("| {} | {} |\n", escape(&attribute.name), code(&attribute.data_type.to_sql())));
+                }
+                out.push('\n');
+            }
+        }
+
+        let used_by: Vec<String> = tables
+            .iter()
+            .flat_map(|table| {
+                table
+                    .columns
+                    .iter()
+                    .filter(|column| column.user_type.as_deref() == Some(user_type.name.as_str()))
+                    .map(|column| column_link(table, column, qualify))
+            })
+            .collect();
+        if !used_by.is_empty() {
+            out.push_str(&format!("Used by: {}\n\n", used_by.join(", ")));
+        }
+    }
+
+    fn render_sequence(&self, out: &mut String, tables: &[&Table], sequence: &Sequence, qualify: bool) {
+        out.push_str(&format!("### Sequence: {}\n\n", qualified(&sequence.schema, &sequence.name, qualify)));
+
+        let mut settings = Vec::new();
+        if let Some(data_type) = &sequence.data_type {
+            settings.push(format!("**Type:** {}", code(&data_type.to_sql())));
+        }
+        for (label, value) in [
+            ("Start", sequence.start),
+            ("Increment", sequence.increment),
+            ("Min", sequence.min_value),
+            ("Max", sequence.max_value),
+        ] {
+            if let Some(value) = value {
+                settings.push(format!("**{}:** {}", label, code(&value.to_string())));
+            }
+        }
+        if sequence.cycle {
+            settings.push("**Cycles**".to_string());
+        }
+        if !settings.is_empty() {
+            out.push_str(&format!("{}\n\n", settings.join(" · ")));
+        }
+
+        let used_by: Vec<String> = tables
+            .iter()
+            .flat_map(|table| {
+                table
+                    .columns
+                    .iter()
+                    .filter(|column| {
+                        column.default_value.as_deref().and_then(Sequence::referenced_by).is_some_and(|name| {
+                            name.eq_ignore_ascii_case(&sequence.name)
+                        })
+                    })
+                    .map(|column| column_link(table, column, qualify))
+            })
+            .collect();
+        if !used_by.is_empty() {
+            out.push_str(&format!("Used by: {}\n\n", used_by.join(", ")));
+        }
+    }
+
+    fn column_type(&self, database: &Database, table: &Table, column: &Column, qualify: bool) -> String {
+        if let Some(name) = &column.user_type {
+            // Prefer the type in the table's own schema
+            let user_type = database
+                .schemas
+                .iter()
+                .filter(|schema| schema.name == table.schema)
+                .chain(database.schemas.iter())
+                .find_map(|schema| schema.get_type(name));
+            if let Some(user_type) = user_type {
+                return format!("[{}](#{})", escape(name), type_anchor(user_type, qualify));
+            }
+        }
+        code(&column.data_type.to_sql())
+    }
+}
+
+fn column_notes(table: &Table, column: &Column, qualify: bool) -> Vec<String> {
+    let mut notes = Vec::new();
+
+    if column.is_primary_key(&table.primary_key) {
+        notes.push("primary key".to_string());
+    }
+    if column.is_unique(&table.unique_constraints) {
+        notes.push("unique".to_string());
+    }
+    if let Some(foreign_key) = table.foreign_keys.iter().find(|fk| fk.columns.contains(&column.name)) {
+        let target = qualified(&foreign_key.referenced_schema, &foreign_key.referenced_table, qualify);
+        notes.push(format!("references [{}](#{})", target, anchor(&target)));
+    }
+    if column.rowid_alias {
+        notes.push("rowid alias".to_string());
+    }
+    match &column.identity {
+        Some(identity) => {
+            let mut note = format!(
+                "identity ({}",
+                if identity.always { "always" } else { "by default" }
+            );
+            if identity.seed != 1 || identity.increment != 1 {
+                note.push_str(&format!(", start {}, increment {}", identity.seed, identity.increment));
+            }
+            note.push(')');
+            notes.push(note);
+        }
+        None if column.auto_increment => notes.push("auto-increment".to_string()),
+        None => {}
+    }
+    if let Some(generated) = &column.generated {
+        notes.push(format!(
+            "generated {} {}",
+            if generated.stored { "stored" } else { "virtual" },
+            code(&generated.expression)
+        ));
+    }
+    if let Some(affinity) = &column.affinity
+        && affinity.name() != column.data_type.get_simple_name()
+    {
+        notes.push(format!("{} affinity", affinity.name()));
+    }
+    if let Some(on_update) = &column.on_update {
+        notes.push(format!("on update {}", code(on_update)));
+    }
+    if let Some(charset) = &column.charset {
+        notes.push(format!("charset {}", code(charset)));
+    }
+    if let Some(collation) = &column.collation {
+        notes.push(format!("collation {}", code(collation)));
+    }
+    if let Some(description) = &column.metadata.description {
+        notes.push(escape(description));
+    }
+    if !column.metadata.history.is_empty() {
+        notes.push(history(&column.metadata.history));
+    }
+
+    notes
+}
+
+/// Such as "added in `V42__add_billing.sql`, type changed in `V57__widen_amount.sql`".
+fn history(events: &[MigrationEvent]) -> String {
+    let events: Vec<String> =
+        events.iter().map(|event| format!("{} in {}", escape(&event.change.to_string()), code(&event.migration))).collect();
+    events.join(", ")
+}
+
+fn with_history(text: String, events: &[MigrationEvent]) -> String {
+    if events.is_empty() {
+        return text;
+    }
+    format!("{}, {}", text, history(events))
+}
+
+fn describe_foreign_key(foreign_key: &ForeignKey, qualify: bool) -> String {
+    let target = qualified(&foreign_key.referenced_schema, &foreign_key.referenced_table, qualify);
+    let mut text = format!(
+        "{}{} → [{}](#{}) ({})",
+        named(&foreign_key.name),
+        list(&foreign_key.columns),
+        target,
+        anchor(&target),
+        foreign_key.referenced_columns.join(", ")
+    );
+    if foreign_key.on_delete != ReferentialAction::NoAction {
+        text.push_str(&format!(", on delete {}", action_name(&foreign_key.on_delete)));
+    }
+    if foreign_key.on_update != ReferentialAction::NoAction {
+        text.push_str(&format!(", on update {}", action_name(&foreign_key.on_update)));
+    }
+    text
+}
+
+fn kind_name(kind: &UserDefinedTypeKind) -> &'static str {
+    match kind {
+        UserDefinedTypeKind::Enum { .. } => "Enum",
+        UserDefinedTypeKind::Domain { .. } => "Domain",
+        UserDefinedTypeKind::Composite { .. } => "Composite",
+    }
+}
+
+fn action_name(action: &ReferentialAction) -> &'static str {
+    match action {
+        ReferentialAction::NoAction => "no action",
+        ReferentialAction::Restrict => "restrict",
+        ReferentialAction::Cascade => "cascade",
+        ReferentialAction::SetNull => "set null",
+        ReferentialAction::SetDefault => "set default",
+    }
+}
+
+fn index_method(index_type: &IndexType) -> String {
+    match index_type {
+        IndexType::BTree => "btree".to_string(),
+        IndexType::Hash => "hash".to_string(),
+        IndexType::Gist => "gist".to_string(),
+        IndexType::Gin => "gin".to_string(),
+        IndexType::SpGist => "spgist".to_string(),
+        IndexType::Brin => "brin".to_string(),
+        IndexType::FullText => "fulltext".to_string(),
+        IndexType::Spatial => "spatial".to_string(),
+        IndexType::Custom(name) => name.to_lowercase(),
+    }
+}
+
+fn named(name: &Option<String>) -> String {
+    name.as_ref().map(|name| format!("`{}`: ", name)).unwrap_or_default()
+}
+
+fn list(columns: &[String]) -> String {
+    columns.iter().map(|column| code(column)).collect::<Vec<_>>().join(", ")
+}
+
+pub(super) fn code(text: &str) -> String {
+    format!("`{}`", escape(text).replace('`', "'"))
+}
+
+/// Pipes would end a table cell.
+pub(super) fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// The anchor GitHub generates for a heading: lower case, spaces to dashes, punctuation dropped.
+pub(super) fn anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            ch if ch.is_alphanumeric() || ch == '-' || ch == '_' => Some(ch),
+            _ => None,
+        })
+        .collect()
+}
+
+fn type_anchor(user_type: &UserDefinedType, qualify: bool) -> String {
+    anchor(&format!("{} {}", kind_name(&user_type.kind), qualified(&user_type.schema, &user_type.name, qualify)))
+}
+
+fn sequence_anchor(sequence: &Sequence, qualify: bool) -> String {
+    anchor(&format!("Sequence {}", qualified(&sequence.schema, &sequence.name, qualify)))
+}
+
+fn column_link(table: &Table, column: &Column, qualify: bool) -> String {
+    let name = qualified(&table.schema, &table.name, qualify);
+    format!("[{}.{}](#{})", name, column.name, anchor(&name))
+}
+
+/// `schema.name` once the document covers several schemas, otherwise just the name.
+fn qualified(schema: &Option<String>, name: &str, qualify: bool) -> String {
+    match schema {
+        Some(schema) if qualify => format!("{}.{}", schema, name),
+        _ => name.to_string(),
+    }
+}
+
+fn schema_heading(schema: &Schema) -> String {
+    match &schema.name {
+        Some(name) => format!("Schema: {}", name),
+        None => "Default schema".to_string(),
+    }
+}
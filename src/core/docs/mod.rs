@@ -0,0 +1,15 @@
+pub mod compatibility;
+pub mod diff;
+pub mod html;
+pub mod json_schema;
+pub mod markdown;
+pub mod openapi;
+pub mod queries;
+
+pub use compatibility::*;
+pub use diff::*;
+pub use html::*;
+pub use json_schema::*;
+pub use markdown::*;
+pub use openapi::*;
+pub use queries::*;
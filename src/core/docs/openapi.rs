@@ -0,0 +1,178 @@
+use serde_json::{Map, Value};
+
+use crate::core::analyze::{CatalogQuery, QueryCatalog, QueryCommand};
+use crate::core::docs::json_schema::{allow_null, type_schema, JsonSchemaRenderer};
+
+/// Renders the named queries of a [`QueryCatalog`] as an OpenAPI 3.1 document, one operation
+/// per query at the endpoint its [`ApiMetadata`](crate::core::ast::ApiMetadata) gives. GET and
+/// DELETE take their parameters in the query string, POST and PUT as a JSON body. Version 3.1
+/// is used because its schemas are JSON Schema, which spells a nullable column `["string",
+/// "null"]` as the row schemas do.
+#[derive(Debug, Default)]
+pub struct OpenApiRenderer;
+
+impl OpenApiRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, catalog: &QueryCatalog) -> Value {
+        let mut paths = Map::new();
+        let mut schemas = Map::new();
+        for query in &catalog.queries {
+            let (Some(name), Some(command), Some(api)) = (&query.name, query.command, &query.metadata.api_metadata) else {
+                continue;
+            };
+            let (Some(method), Some(endpoint)) = (&api.http_method, &api.endpoint) else {
+                continue;
+            };
+            if let (Some(schema_name), Some(result)) = (&api.response_schema, &query.result) {
+                schemas.insert(schema_name.clone(), JsonSchemaRenderer::new().render_row(result));
+            }
+            let path = paths.entry(endpoint.clone()).or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(path) = path {
+                path.insert(method.to_lowercase(), self.render_operation(name, command, method, query));
+            }
+        }
+
+        let mut info = Map::new();
+        info.insert("title".to_string(), Value::from(catalog.title.as_str()));
+        info.insert("version".to_string(), Value::from("1.0.0"));
+        let mut document = Map::new();
+        document.insert("openapi".to_string(), Value::from("3.1.0"));
+        document.insert("info".to_string(), Value::Object(info));
+        document.insert("paths".to_string(), Value::Object(paths));
+        if !schemas.is_empty() {
+            let mut components = Map::new();
+            components.insert("schemas".to_string(), Value::Object(schemas));
+            document.insert("components".to_string(), Value::Object(components));
+        }
+        Value::Object(document)
+    }
+
+    fn render_operation(&self, name: &str, command: QueryCommand, method: &str, query: &CatalogQuery) -> Value {
+        let mut operation = Map::new();
+        operation.insert("operationId".to_string(), Value::from(name));
+        let description = query.metadata.documentation.as_ref().and_then(|documentation| documentation.description.as_ref());
+        if let Some(description) = description {
+            let summary = description.lines().next().unwrap_or_default();
+            operation.insert("summary".to_string(), Value::from(summary));
+            operation.insert("description".to_string(), Value::from(description.as_str()));
+        }
+        if !query.metadata.tables_accessed.is_empty() {
+            operation.insert("tags".to_string(), Value::from(query.metadata.tables_accessed.clone()));
+        }
+
+        let parameters = query.metadata.api_metadata.iter().flat_map(|api| &api.parameters);
+        if matches!(method, "POST" | "PUT" | "PATCH") {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for parameter in parameters {
+                properties.insert(parameter.name.clone(), Value::Object(self.parameter_schema(query, &parameter.name)));
+                if parameter.required {
+                    required.push(parameter.name.clone());
+                }
+            }
+            if !properties.is_empty() {
+                let mut schema = Map::new();
+                schema.insert("type".to_string(), Value::from("object"));
+                schema.insert("properties".to_string(), Value::Object(properties));
+                if !required.is_empty() {
+                    schema.insert("required".to_string(), Value::from(required));
+                }
+                schema.insert("additionalProperties".to_string(), Value::from(false));
+                let mut body = Map::new();
+                body.insert("required".to_string(), Value::from(true));
+                body.insert("content".to_string(), json_content(Value::Object(schema)));
+                operation.insert("requestBody".to_string(), Value::Object(body));
+            }
+        } else {
+            let parameters: Vec<Value> = parameters
+                .map(|parameter| {
+                    let mut rendered = Map::new();
+                    rendered.insert("name".to_string(), Value::from(parameter.name.as_str()));
+                    rendered.insert("in".to_string(), Value::from("query"));
+                    rendered.insert("required".to_string(), Value::from(parameter.required));
+                    if let Some(description) = &parameter.description {
+                        rendered.insert("description".to_string(), Value::from(description.as_str()));
+                    }
+                    rendered.insert("schema".to_string(), Value::Object(self.parameter_schema(query, &parameter.name)));
+                    Value::Object(rendered)
+                })
+                .collect();
+            if !parameters.is_empty() {
+                operation.insert("parameters".to_string(), Value::from(parameters));
+            }
+        }
+
+        operation.insert("responses".to_string(), self.render_responses(command, query));
+        Value::Object(operation)
+    }
+
+    fn render_responses(&self, command: QueryCommand, query: &CatalogQuery) -> Value {
+        let row = match (&query.metadata.api_metadata, &query.result) {
+            (Some(api), Some(_)) => api.response_schema.as_ref().map(|name| {
+                Value::Object(Map::from_iter([("$ref".to_string(), Value::from(format!("#/components/schemas/{}", name)))]))
+            }),
+            _ => None,
+        }
+        // Without a schema the row's columns aren't known
+        .unwrap_or_else(|| Value::Object(Map::new()));
+
+        let mut responses = Map::new();
+        match command {
+            QueryCommand::One => {
+                responses.insert("200".to_string(), response("The matching row", Some(row)));
+                responses.insert("404".to_string(), response("No row matched", None));
+            }
+            QueryCommand::Many => {
+                let mut array = Map::new();
+                array.insert("type".to_string(), Value::from("array"));
+                array.insert("items".to_string(), row);
+                responses.insert("200".to_string(), response("The matching rows", Some(Value::Object(array))));
+            }
+            QueryCommand::Exec => {
+                responses.insert("204".to_string(), response("The statement ran", None));
+            }
+            QueryCommand::ExecRows => {
+                let mut count = Map::new();
+                count.insert("type".to_string(), Value::from("integer"));
+                count.insert("minimum".to_string(), Value::from(0));
+                let mut schema = Map::new();
+                schema.insert("type".to_string(), Value::from("object"));
+                let properties = Map::from_iter([("rows_affected".to_string(), Value::Object(count))]);
+                schema.insert("properties".to_string(), Value::Object(properties));
+                schema.insert("required".to_string(), Value::from(vec!["rows_affected"]));
+                let affected = response("How many rows the statement affected", Some(Value::Object(schema)));
+                responses.insert("200".to_string(), affected);
+            }
+        }
+        Value::Object(responses)
+    }
+
+    fn parameter_schema(&self, query: &CatalogQuery, name: &str) -> Map<String, Value> {
+        let Some(parameter) = query.parameters.iter().find(|parameter| parameter.name == name) else {
+            return Map::new();
+        };
+        let mut schema = parameter.data_type.as_ref().map(type_schema).unwrap_or_default();
+        if parameter.nullable {
+            allow_null(&mut schema);
+        }
+        schema
+    }
+}
+
+fn response(description: &str, schema: Option<Value>) -> Value {
+    let mut response = Map::new();
+    response.insert("description".to_string(), Value::from(description));
+    if let Some(schema) = schema {
+        response.insert("content".to_string(), json_content(schema));
+    }
+    Value::Object(response)
+}
+
+fn json_content(schema: Value) -> Value {
+    let mut media = Map::new();
+    media.insert("schema".to_string(), schema);
+    Value::Object(Map::from_iter([("application/json".to_string(), Value::Object(media))]))
+}
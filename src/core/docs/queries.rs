@@ -0,0 +1,152 @@
+use crate::core::analyze::{CatalogQuery, QueryCatalog};
+use crate::core::docs::markdown::{anchor, code, escape};
+
+/// Renders a [`QueryCatalog`] as Markdown: a summary table of the queries, then a section per
+/// query with its SQL, parameters, what it reads and writes, why it is as complex as it is and,
+/// when known, the columns it returns.
+#[derive(Debug, Default)]
+pub struct QueryCatalogRenderer;
+
+impl QueryCatalogRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(&self, catalog: &QueryCatalog) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("# {}\n\n", catalog.title));
+        if catalog.queries.is_empty() {
+            out.push_str("No queries found.\n");
+            return out;
+        }
+
+        out.push_str("| Query | Type | Complexity | Tables |\n");
+        out.push_str("|-------|------|------------|--------|\n");
+        for query in &catalog.queries {
+            let heading = heading(query);
+            out.push_str(&format!(
+                "| [{}](#{}) | {} | {} | {} |\n",
+                escape(&heading),
+                anchor(&heading),
+                query_type(query),
+                query.metadata.estimated_complexity,
+                list(&query.metadata.tables_accessed),
+            ));
+        }
+        out.push('\n');
+
+        for query in &catalog.queries {
+            self.render_query(&mut out, query);
+        }
+
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    fn render_query(&self, out: &mut String, query: &CatalogQuery) {
+        let metadata = &query.metadata;
+        out.push_str(&format!("## {}\n\n", heading(query)));
+        let description = metadata.documentation.as_ref().and_then(|documentation| documentation.description.as_ref());
+        if let Some(description) = description {
+            out.push_str(&format!("{}\n\n", description));
+        }
+        if query.name.is_some() {
+            out.push_str(&format!("Defined in {}.\n\n", code(&format!("{}:{}", query.source, query.line))));
+        }
+        out.push_str(&format!("```sql\n{}\n```\n\n", query.sql));
+
+        let mut complexity = format!("**Complexity:** {}", metadata.estimated_complexity);
+        if !metadata.complexity_reasons.is_empty() {
+            complexity.push_str(&format!(" ({})", metadata.complexity_reasons.join(", ")));
+        }
+        out.push_str(&format!("**Type:** {} · {}\n\n", query_type(query), complexity));
+        if let Some(api) = &metadata.api_metadata
+            && let (Some(method), Some(endpoint)) = (&api.http_method, &api.endpoint)
+        {
+            out.push_str(&format!("**Endpoint:** {}\n\n", code(&format!("{} {}", method, endpoint))));
+        }
+
+        if !query.parameters.is_empty() {
+            out.push_str("**Parameters:**\n\n");
+            out.push_str("| Parameter | Placeholder | Type | Nullable |\n");
+            out.push_str("|-----------|-------------|------|----------|\n");
+            for parameter in &query.parameters {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    escape(&parameter.name),
+                    code(&parameter.placeholder),
+                    parameter.data_type.as_ref().map(ToString::to_string).unwrap_or_else(|| "unknown".to_string()),
+                    if parameter.nullable { "yes" } else { "no" },
+                ));
+            }
+            out.push('\n');
+        }
+
+        let mut facts = Vec::new();
+        if !metadata.tables_accessed.is_empty() {
+            facts.push(format!("**Tables:** {}", list(&metadata.tables_accessed)));
+        }
+        if !metadata.columns_accessed.is_empty() {
+            facts.push(format!("**Columns:** {}", list(&metadata.columns_accessed)));
+        }
+        if metadata.join_count > 0 {
+            facts.push(format!("**Joins:** {}", metadata.join_count));
+        }
+        if metadata.subquery_depth > 0 {
+            facts.push(format!("**Subquery depth:** {}", metadata.subquery_depth));
+        }
+        if metadata.cte_depth > 0 {
+            facts.push(format!("**CTE depth:** {}", metadata.cte_depth));
+        }
+        if !metadata.aggregates.is_empty() {
+            facts.push(format!("**Aggregates:** {}", list(&metadata.aggregates)));
+        }
+        if !metadata.window_functions.is_empty() {
+            facts.push(format!("**Window functions:** {}", list(&metadata.window_functions)));
+        }
+        for fact in facts {
+            out.push_str(&format!("- {}\n", fact));
+        }
+        out.push('\n');
+
+        if let Some(result) = &query.result
+            && !result.columns.is_empty()
+        {
+            out.push_str("**Returns:**\n\n");
+            out.push_str("| Column | Type | Nullable |\n");
+            out.push_str("|--------|------|----------|\n");
+            for column in &result.columns {
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    escape(&column.name),
+                    column.data_type.as_ref().map(ToString::to_string).unwrap_or_else(|| "unknown".to_string()),
+                    if column.nullable { "yes" } else { "no" },
+                ));
+            }
+            if !result.complete {
+                out.push_str("\nSome columns come from tables whose columns are not known.\n");
+            }
+            out.push('\n');
+        }
+    }
+}
+
+fn heading(query: &CatalogQuery) -> String {
+    match &query.name {
+        Some(name) => name.clone(),
+        None => format!("{}:{}", query.source, query.line),
+    }
+}
+
+/// The statement type, with the command of a named query: `SELECT :one`.
+fn query_type(query: &CatalogQuery) -> String {
+    match query.command {
+        Some(command) => format!("{} {}", query.metadata.query_type, command),
+        None => query.metadata.query_type.to_string(),
+    }
+}
+
+fn list(names: &[String]) -> String {
+    names.iter().map(|name| code(name)).collect::<Vec<_>>().join(", ")
+}
@@ -1,8 +1,20 @@
 use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
+use lazy_static::lazy_static;
+use regex::Regex;
 
 use crate::core::domain::entity::*;
-use crate::core::domain::relationships::*;
+
+lazy_static! {
+    static ref IDENTIFIER_REGEX: Regex =
+        Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\b").expect("identifier regex is valid");
+    static ref STRING_LITERAL_REGEX: Regex =
+        Regex::new(r"'(?:[^']|'')*'").expect("string literal regex is valid");
+}
+
+const EXPRESSION_KEYWORDS: &[&str] = &[
+    "AND", "OR", "NOT", "IN", "IS", "NULL", "LIKE", "BETWEEN", "TRUE", "FALSE",
+];
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConstraintAnalysis {
@@ -114,10 +126,10 @@ pub enum RelationshipStrength {
 pub struct ConstraintValidationService;
 
 impl ConstraintValidationService {
-    pub fn validate_primary_key(pk: &PrimaryKey, table: &Table, database: &Database) -> ConstraintAnalysis {
+    pub fn validate_primary_key(pk: &PrimaryKey, table: &Table, _database: &Database) -> ConstraintAnalysis {
         let mut issues = Vec::new();
         let mut business_rules = Vec::new();
-        let mut dependencies = Vec::new();
+        let dependencies = Vec::new();
 
         // Check if all columns exist
         for col_name in &pk.columns {
@@ -133,16 +145,17 @@ impl ConstraintValidationService {
 
         // Check for nullability issues
         for col_name in &pk.columns {
-            if let Some(column) = table.get_column(col_name) {
-                if column.nullable {
+            if let Some(column) = table.get_column(col_name)
+                && column.nullable {
+                    // Most engines silently coerce primary key columns to NOT NULL, so
+                    // the declaration is misleading rather than broken.
                     issues.push(ConstraintIssue {
                         issue_type: ConstraintIssueType::DataIntegrityRisk,
-                        severity: IssueSeverity::Critical,
+                        severity: IssueSeverity::High,
                         description: format!("Primary key column '{}' cannot be nullable", col_name),
                         suggested_fix: Some(format!("ALTER TABLE {} MODIFY {} {} NOT NULL", table.name, col_name, column.get_data_type_name())),
                     });
                 }
-            }
         }
 
         // Check for uniqueness (primary keys are inherently unique)
@@ -168,8 +181,8 @@ impl ConstraintValidationService {
         });
 
         // Check naming convention
-        if let Some(name) = &pk.constraint_name {
-            if !name.to_lowercase().contains("pk") && !name.to_lowercase().contains("primary") {
+        if let Some(name) = &pk.constraint_name
+            && !name.to_lowercase().contains("pk") && !name.to_lowercase().contains("primary") {
                 issues.push(ConstraintIssue {
                     issue_type: ConstraintIssueType::NamingConvention,
                     severity: IssueSeverity::Low,
@@ -177,7 +190,6 @@ impl ConstraintValidationService {
                     suggested_fix: Some(format!("Rename constraint to 'PK_{}_{}'", table.name, pk.columns.join("_"))),
                 });
             }
-        }
 
         ConstraintAnalysis {
             constraint_type: ConstraintType::PrimaryKey,
@@ -229,7 +241,7 @@ impl ConstraintValidationService {
 
             // Check if referenced columns have appropriate constraints
             for col_name in &fk.referenced_columns {
-                if let Some(column) = referenced_table.get_column(col_name) {
+                if let Some(_column) = referenced_table.get_column(col_name) {
                     // Referenced columns should typically be primary key or unique
                     let is_pk = referenced_table.primary_key.as_ref()
                         .map(|pk| pk.columns.contains(col_name))
@@ -271,8 +283,8 @@ impl ConstraintValidationService {
                 if let (Some(source_column), Some(target_column)) = (
                     source_table.get_column(source_col),
                     target_table.get_column(target_col)
-                ) {
-                    if !Self::are_data_types_compatible(&source_column.data_type, &target_column.data_type) {
+                )
+                    && !Self::are_data_types_compatible(&source_column.data_type, &target_column.data_type) {
                         issues.push(ConstraintIssue {
                             issue_type: ConstraintIssueType::DataIntegrityRisk,
                             severity: IssueSeverity::High,
@@ -282,7 +294,6 @@ impl ConstraintValidationService {
                             suggested_fix: Some(format!("Change data type of '{}' to match '{}'", source_col, target_column.get_data_type_name())),
                         });
                     }
-                }
             }
         }
 
@@ -400,8 +411,8 @@ impl ConstraintValidationService {
         });
 
         // Check naming convention
-        if let Some(name) = &uc.constraint_name {
-            if !name.to_lowercase().contains("uq") && !name.to_lowercase().contains("unique") {
+        if let Some(name) = &uc.constraint_name
+            && !name.to_lowercase().contains("uq") && !name.to_lowercase().contains("unique") {
                 issues.push(ConstraintIssue {
                     issue_type: ConstraintIssueType::NamingConvention,
                     severity: IssueSeverity::Low,
@@ -409,7 +420,6 @@ impl ConstraintValidationService {
                     suggested_fix: Some(format!("Rename constraint to 'UQ_{}_{}'", table.name, uc.columns.join("_"))),
                 });
             }
-        }
 
         ConstraintAnalysis {
             constraint_type: ConstraintType::Unique,
@@ -468,8 +478,8 @@ impl ConstraintValidationService {
         });
 
         // Check naming convention
-        if let Some(name) = &cc.constraint_name {
-            if !name.to_lowercase().contains("ck") && !name.to_lowercase().contains("check") {
+        if let Some(name) = &cc.constraint_name
+            && !name.to_lowercase().contains("ck") && !name.to_lowercase().contains("check") {
                 issues.push(ConstraintIssue {
                     issue_type: ConstraintIssueType::NamingConvention,
                     severity: IssueSeverity::Low,
@@ -477,7 +487,6 @@ impl ConstraintValidationService {
                     suggested_fix: Some(format!("Rename constraint to 'CK_{}_{}'", table.name, referenced_columns.join("_"))),
                 });
             }
-        }
 
         ConstraintAnalysis {
             constraint_type: ConstraintType::Check,
@@ -518,7 +527,7 @@ impl ConstraintValidationService {
                     (None, None) => true,
                 }
             }
-            (DataType::VarChar { length: s_len }, DataType::Text) => true,
+            (DataType::VarChar { length: _s_len }, DataType::Text) => true,
             (DataType::Char { .. }, DataType::Text) => true,
 
             // Default: not compatible
@@ -528,12 +537,12 @@ impl ConstraintValidationService {
 
     fn extract_columns_from_expression(expression: &str) -> Vec<String> {
         // Simple regex-based column extraction (in a real implementation, this would use proper SQL parsing)
-        use regex::Regex;
-        let column_regex = Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\b").unwrap();
-
-        column_regex.captures_iter(expression)
+        let without_literals = STRING_LITERAL_REGEX.replace_all(expression, " ");
+        IDENTIFIER_REGEX.captures_iter(&without_literals)
             .filter_map(|cap| cap.get(1))
-            .map(|m| m.as_str().to_string())
+            .map(|m| m.as_str())
+            .filter(|word| !EXPRESSION_KEYWORDS.contains(&word.to_uppercase().as_str()))
+            .map(str::to_string)
             .collect::<HashSet<String>>()
             .into_iter()
             .collect()
@@ -681,55 +690,62 @@ impl ConstraintAnalysisService {
     }
 
     pub fn detect_constraint_issues(database: &Database) -> Vec<ConstraintIssue> {
+        database.get_all_tables().into_iter().flat_map(Self::detect_table_issues).collect()
+    }
+
+    /// The issues [`detect_constraint_issues`](Self::detect_constraint_issues) finds in one table.
+    pub fn detect_table_issues(table: &Table) -> Vec<ConstraintIssue> {
         let mut all_issues = Vec::new();
 
-        for table in database.get_all_tables() {
-            // Check for tables without primary keys
-            if table.primary_key.is_none() {
+        // Check for tables without primary keys
+        if table.primary_key.is_none() {
+            all_issues.push(ConstraintIssue {
+                issue_type: ConstraintIssueType::MissingConstraint,
+                severity: IssueSeverity::High,
+                description: format!("Table '{}' has no primary key", table.name),
+                suggested_fix: Some(format!("Add a primary key to table '{}' (consider using an auto-increment ID column)", table.name)),
+            });
+        }
+
+        // Check for foreign keys without indexes; an index, primary key or unique constraint
+        // whose leading columns are the FK's columns, in any order, supports it
+        for fk in &table.foreign_keys {
+            let has_index = table
+                .indexes
+                .iter()
+                .any(|idx| leading_columns_cover(idx.columns.iter().map(|c| &c.name), &fk.columns))
+                || table.primary_key.iter().any(|pk| leading_columns_cover(&pk.columns, &fk.columns))
+                || table.unique_constraints.iter().any(|uc| leading_columns_cover(&uc.columns, &fk.columns));
+
+            if !has_index {
                 all_issues.push(ConstraintIssue {
-                    issue_type: ConstraintIssueType::MissingConstraint,
-                    severity: IssueSeverity::High,
-                    description: format!("Table '{}' has no primary key", table.name),
-                    suggested_fix: Some(format!("Add a primary key to table '{}' (consider using an auto-increment ID column)", table.name)),
+                    issue_type: ConstraintIssueType::PerformanceImpact,
+                    severity: IssueSeverity::Medium,
+                    description: format!("Foreign key ({}) in table '{}' has no supporting index", fk.columns.join(", "), table.name),
+                    suggested_fix: Some(format!("CREATE INDEX idx_{}_{} ON {} ({});",
+                        table.name, fk.columns.join("_"), table.name, fk.columns.join(", "))),
                 });
             }
+        }
 
-            // Check for foreign keys without indexes
-            for fk in &table.foreign_keys {
-                let has_index = table.indexes.iter().any(|idx| {
-                    idx.columns == fk.columns
-                });
+        // Check for redundant unique constraints
+        let unique_columns: HashSet<_> = table.unique_constraints.iter()
+            .flat_map(|uc| &uc.columns)
+            .collect();
 
-                if !has_index {
-                    all_issues.push(ConstraintIssue {
-                        issue_type: ConstraintIssueType::PerformanceImpact,
-                        severity: IssueSeverity::Medium,
-                        description: format!("Foreign key ({}) in table '{}' has no supporting index", fk.columns.join(", "), table.name),
-                        suggested_fix: Some(format!("CREATE INDEX idx_{}_{} ON {} ({});",
-                            table.name, fk.columns.join("_"), table.name, fk.columns.join(", "))),
-                    });
-                }
-            }
-
-            // Check for redundant unique constraints
-            let unique_columns: HashSet<_> = table.unique_constraints.iter()
-                .flat_map(|uc| &uc.columns)
+        if let Some(pk) = &table.primary_key {
+            let redundant_columns: Vec<_> = pk.columns.iter()
+                .filter(|col| unique_columns.contains(col))
+                .cloned()
                 .collect();
 
-            if let Some(pk) = &table.primary_key {
-                let redundant_columns: Vec<_> = pk.columns.iter()
-                    .filter(|col| unique_columns.contains(col))
-                    .cloned()
-                    .collect();
-
-                if !redundant_columns.is_empty() {
-                    all_issues.push(ConstraintIssue {
-                        issue_type: ConstraintIssueType::RedundantConstraint,
-                        severity: IssueSeverity::Low,
-                        description: format!("Columns ({}) are both in primary key and unique constraints", redundant_columns.join(", ")),
-                        suggested_fix: Some(format!("Remove redundant unique constraints on primary key columns in table '{}'", table.name)),
-                    });
-                }
+            if !redundant_columns.is_empty() {
+                all_issues.push(ConstraintIssue {
+                    issue_type: ConstraintIssueType::RedundantConstraint,
+                    severity: IssueSeverity::Low,
+                    description: format!("Columns ({}) are both in primary key and unique constraints", redundant_columns.join(", ")),
+                    suggested_fix: Some(format!("Remove redundant unique constraints on primary key columns in table '{}'", table.name)),
+                });
             }
         }
 
@@ -859,6 +875,13 @@ impl ConstraintDocumentationService {
     }
 }
 
+/// Whether the first `covered.len()` of `columns` are the `covered` columns, in any order.
+fn leading_columns_cover<'a>(columns: impl IntoIterator<Item = &'a String>, covered: &[String]) -> bool {
+    let leading: Vec<&String> = columns.into_iter().take(covered.len()).collect();
+    leading.len() == covered.len()
+        && covered.iter().all(|column| leading.iter().any(|name| name.eq_ignore_ascii_case(column)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -878,7 +901,7 @@ mod tests {
         table.primary_key = Some(pk);
 
         let database = Database::new("test_db");
-        let analysis = ConstraintValidationService::validate_primary_key(table.primary_key.as_ref().unwrap(), &table, &database);
+        let analysis = ConstraintValidationService::validate_primary_key(table.primary_key.as_ref().expect("primary key"), &table, &database);
 
         assert!(analysis.is_valid);
         assert_eq!(analysis.constraint_type, ConstraintType::PrimaryKey);
@@ -907,6 +930,7 @@ mod tests {
             table_name: "posts".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -921,8 +945,8 @@ mod tests {
         database.add_table(target_table);
 
         let analysis = ConstraintValidationService::validate_foreign_key(
-            database.get_table(None, "posts").unwrap().foreign_keys.first().unwrap(),
-            database.get_table(None, "posts").unwrap(),
+            database.get_table(None, "posts").expect("posts table").foreign_keys.first().expect("posts foreign key"),
+            database.get_table(None, "posts").expect("posts table"),
             &database
         );
 
@@ -2,10 +2,16 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use crate::core::ast;
+use crate::core::domain::permissions::Permission;
+use crate::core::domain::procedures::Procedure;
+use crate::core::domain::triggers::Trigger;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Database {
     pub name: String,
     pub schemas: Vec<Schema>,
+    pub permissions: Vec<Permission>,
     pub metadata: DatabaseMetadata,
 }
 
@@ -14,6 +20,10 @@ pub struct Schema {
     pub name: Option<String>,
     pub tables: Vec<Table>,
     pub views: Vec<View>,
+    pub procedures: Vec<Procedure>,
+    pub functions: Vec<Procedure>,
+    pub types: Vec<UserDefinedType>,
+    pub sequences: Vec<Sequence>,
     pub metadata: SchemaMetadata,
 }
 
@@ -27,6 +37,17 @@ pub struct Table {
     pub foreign_keys: Vec<ForeignKey>,
     pub unique_constraints: Vec<UniqueConstraint>,
     pub check_constraints: Vec<CheckConstraint>,
+    pub exclusion_constraints: Vec<ExclusionConstraint>,
+    pub triggers: Vec<Trigger>,
+    pub partition_info: Option<PartitionInfo>,
+    /// MySQL storage engine, such as InnoDB
+    pub engine: Option<String>,
+    pub charset: Option<String>,
+    pub collation: Option<String>,
+    /// SQLite `STRICT` table
+    pub strict: bool,
+    /// SQLite `WITHOUT ROWID` table
+    pub without_rowid: bool,
     pub metadata: TableMetadata,
 }
 
@@ -37,9 +58,16 @@ pub struct View {
     pub definition: String,
     pub columns: Vec<Column>,
     pub dependencies: Vec<String>, // Referenced table/view names
+    pub view_type: ViewType,
     pub metadata: ViewMetadata,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ViewType {
+    Regular,
+    Materialized,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Column {
     pub name: String,
@@ -48,7 +76,17 @@ pub struct Column {
     pub default_value: Option<String>,
     pub auto_increment: bool,
     pub identity: Option<IdentitySpecification>,
+    pub generated: Option<GeneratedColumn>,
     pub collation: Option<String>,
+    pub charset: Option<String>,
+    /// MySQL's `ON UPDATE` expression, typically CURRENT_TIMESTAMP
+    pub on_update: Option<String>,
+    /// How SQLite stores the column's values, derived from the declared type name
+    pub affinity: Option<TypeAffinity>,
+    /// SQLite `INTEGER PRIMARY KEY`: the column is the table's rowid
+    pub rowid_alias: bool,
+    /// Name of the enum or domain the column was declared with; `data_type` holds what it resolves to.
+    pub user_type: Option<String>,
     pub metadata: ColumnMetadata,
 }
 
@@ -144,6 +182,10 @@ pub enum IndexType {
     Gin,
     SpGist,
     Brin,
+    /// MySQL FULLTEXT index
+    FullText,
+    /// MySQL SPATIAL index over geometry columns
+    Spatial,
     Custom(String),
 }
 
@@ -165,6 +207,8 @@ pub struct ForeignKey {
     pub name: Option<String>,
     pub table_name: String,
     pub columns: Vec<String>,
+    /// Schema of the referenced table; `None` is the database's default schema
+    pub referenced_schema: Option<String>,
     pub referenced_table: String,
     pub referenced_columns: Vec<String>,
     pub on_delete: ReferentialAction,
@@ -200,8 +244,116 @@ pub struct CheckConstraint {
     pub metadata: ConstraintMetadata,
 }
 
+/// A PostgreSQL `EXCLUDE` constraint: no two rows may satisfy every `expression operator` pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionConstraint {
+    pub name: Option<String>,
+    pub table_name: String,
+    pub index_type: IndexType,
+    pub elements: Vec<ExclusionElement>,
+    pub where_clause: Option<String>,
+    pub metadata: ConstraintMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionElement {
+    pub expression: String,
+    pub operator: String,
+}
+
+/// SQLite's type affinities; see <https://www.sqlite.org/datatype3.html#type_affinity>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeAffinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+impl TypeAffinity {
+    /// Applies SQLite's rules to a declared type name, in SQLite's order of precedence.
+    pub fn from_declared_type(type_name: &str) -> Self {
+        let name = type_name.to_uppercase();
+        if name.contains("INT") {
+            TypeAffinity::Integer
+        } else if name.contains("CHAR") || name.contains("CLOB") || name.contains("TEXT") {
+            TypeAffinity::Text
+        } else if name.contains("BLOB") || name.trim().is_empty() {
+            TypeAffinity::Blob
+        } else if name.contains("REAL") || name.contains("FLOA") || name.contains("DOUB") {
+            TypeAffinity::Real
+        } else {
+            TypeAffinity::Numeric
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypeAffinity::Integer => "INTEGER",
+            TypeAffinity::Text => "TEXT",
+            TypeAffinity::Blob => "BLOB",
+            TypeAffinity::Real => "REAL",
+            TypeAffinity::Numeric => "NUMERIC",
+        }
+    }
+
+    /// The model type a value of this affinity is stored as.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            TypeAffinity::Integer => DataType::Integer { unsigned: false },
+            TypeAffinity::Text => DataType::Text,
+            TypeAffinity::Blob => DataType::Blob,
+            TypeAffinity::Real => DataType::Real,
+            TypeAffinity::Numeric => DataType::Decimal { precision: None, scale: None },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedColumn {
+    pub expression: String,
+    pub stored: bool,
+}
+
+/// A standalone number generator, `CREATE SEQUENCE`, that columns draw defaults from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sequence {
+    pub name: String,
+    pub schema: Option<String>,
+    pub data_type: Option<DataType>,
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub cycle: bool,
+}
+
+/// A named type declared once and reused by columns, such as a PostgreSQL enum or domain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserDefinedType {
+    pub name: String,
+    pub schema: Option<String>,
+    pub kind: UserDefinedTypeKind,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UserDefinedTypeKind {
+    Enum { values: Vec<String> },
+    Domain {
+        base_type: DataType,
+        nullable: bool,
+        default_value: Option<String>,
+        checks: Vec<String>,
+    },
+    Composite { attributes: Vec<Column> },
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IdentitySpecification {
+    /// `GENERATED ALWAYS` rejects explicit values; `BY DEFAULT` and auto-increment accept them.
+    pub always: bool,
     pub seed: i64,
     pub increment: i64,
     pub min_value: Option<i64>,
@@ -212,6 +364,7 @@ pub struct IdentitySpecification {
 // Metadata structures for documentation and API generation
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct DatabaseMetadata {
     pub description: Option<String>,
     pub version: Option<String>,
@@ -222,6 +375,7 @@ pub struct DatabaseMetadata {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct SchemaMetadata {
     pub description: Option<String>,
     pub owner: Option<String>,
@@ -230,6 +384,7 @@ pub struct SchemaMetadata {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct TableMetadata {
     pub description: Option<String>,
     pub category: Option<String>,
@@ -241,20 +396,76 @@ pub struct TableMetadata {
     pub tags: Vec<String>,
     pub api_endpoints: Vec<ApiEndpoint>,
     pub custom_properties: HashMap<String, String>,
+    /// Migrations that created and later changed it, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MigrationEvent>,
+}
+
+/// A migration that created or changed an object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    /// The migration's file name, such as `V42__add_billing.sql`
+    pub migration: String,
+    pub change: MigrationChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationChange {
+    Added,
+    Renamed { from: String },
+    TypeChanged,
+    DefaultChanged,
+    MadeNotNull,
+    MadeNullable,
+    /// Redefined in some other way, such as by MySQL's `MODIFY` changing its comment
+    Altered,
+}
+
+impl fmt::Display for MigrationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationChange::Added => write!(f, "added"),
+            MigrationChange::Renamed { from } => write!(f, "renamed from {}", from),
+            MigrationChange::TypeChanged => write!(f, "type changed"),
+            MigrationChange::DefaultChanged => write!(f, "default changed"),
+            MigrationChange::MadeNotNull => write!(f, "made NOT NULL"),
+            MigrationChange::MadeNullable => write!(f, "made nullable"),
+            MigrationChange::Altered => write!(f, "altered"),
+        }
+    }
+}
+
+impl fmt::Display for MigrationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {}", self.change, self.migration)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionInfo {
+    pub strategy: String,
+    pub function_name: Option<String>,
+    pub columns: Vec<String>,
+    pub boundary_values: Vec<String>,
+    pub filegroups: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct ViewMetadata {
     pub description: Option<String>,
     pub category: Option<String>,
     pub owner: Option<String>,
     pub created_at: Option<String>,
     pub updatable: bool,
+    pub usage_count: Option<u64>,
     pub tags: Vec<String>,
     pub custom_properties: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct ColumnMetadata {
     pub description: Option<String>,
     pub example_values: Vec<String>,
@@ -264,14 +475,21 @@ pub struct ColumnMetadata {
     pub tags: Vec<String>,
     pub api_field: Option<ApiField>,
     pub custom_properties: HashMap<String, String>,
+    /// Migrations that created and later changed it, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MigrationEvent>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct IndexMetadata {
     pub description: Option<String>,
     pub usage_estimate: Option<String>,
     pub maintenance_cost: Option<String>,
     pub custom_properties: HashMap<String, String>,
+    /// Migrations that created and later changed it, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MigrationEvent>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -279,6 +497,9 @@ pub struct ForeignKeyMetadata {
     pub description: Option<String>,
     pub relationship_type: RelationshipType,
     pub custom_properties: HashMap<String, String>,
+    /// Migrations that created and later changed it, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MigrationEvent>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -290,10 +511,14 @@ pub enum RelationshipType {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default)]
 pub struct ConstraintMetadata {
     pub description: Option<String>,
     pub business_rule: Option<String>,
     pub custom_properties: HashMap<String, String>,
+    /// Migrations that created and later changed it, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MigrationEvent>,
 }
 
 // API-related metadata
@@ -406,6 +631,7 @@ impl Database {
         Self {
             name: name.to_string(),
             schemas: Vec::new(),
+            permissions: Vec::new(),
             metadata: DatabaseMetadata::default(),
         }
     }
@@ -414,6 +640,19 @@ impl Database {
         self.schemas.push(schema);
     }
 
+    /// Adds a table to the schema named by `table.schema`, creating the schema if needed.
+    pub fn add_table(&mut self, table: Table) {
+        let schema_name = table.schema.clone();
+        match self.schemas.iter_mut().find(|s| s.name == schema_name) {
+            Some(schema) => schema.add_table(table),
+            None => {
+                let mut schema = Schema::new(schema_name.as_deref());
+                schema.add_table(table);
+                self.schemas.push(schema);
+            }
+        }
+    }
+
     pub fn get_schema(&self, name: &str) -> Option<&Schema> {
         self.schemas.iter().find(|s| s.name.as_deref() == Some(name))
     }
@@ -422,6 +661,26 @@ impl Database {
         self.schemas.iter_mut().find(|s| s.name.as_deref() == Some(name))
     }
 
+    /// The schema called `name` (`None` for the default schema), added empty if missing.
+    pub fn schema_mut(&mut self, name: Option<&str>) -> &mut Schema {
+        match self.schemas.iter().position(|schema| schema.name.as_deref() == name) {
+            Some(position) => &mut self.schemas[position],
+            None => {
+                self.schemas.push(Schema::new(name));
+                self.schemas.last_mut().expect("schema was just added")
+            }
+        }
+    }
+
+    /// Looks a table up in exactly the given schema, unlike [`Database::get_table`], which
+    /// searches every schema when the named one doesn't exist.
+    pub fn find_table(&self, schema_name: Option<&str>, table_name: &str) -> Option<&Table> {
+        self.schemas
+            .iter()
+            .find(|schema| schema.name.as_deref() == schema_name)
+            .and_then(|schema| schema.get_table(table_name))
+    }
+
     pub fn get_table(&self, schema_name: Option<&str>, table_name: &str) -> Option<&Table> {
         if let Some(schema) = schema_name.and_then(|name| self.get_schema(name)) {
             schema.get_table(table_name)
@@ -438,6 +697,58 @@ impl Database {
             .collect()
     }
 
+    pub fn get_all_types(&self) -> Vec<&UserDefinedType> {
+        self.schemas.iter()
+            .flat_map(|schema| &schema.types)
+            .collect()
+    }
+
+    pub fn get_all_sequences(&self) -> Vec<&Sequence> {
+        self.schemas.iter()
+            .flat_map(|schema| &schema.sequences)
+            .collect()
+    }
+
+    pub fn get_view(&self, schema_name: Option<&str>, view_name: &str) -> Option<&View> {
+        if let Some(schema) = schema_name.and_then(|name| self.get_schema(name)) {
+            schema.get_view(view_name)
+        } else {
+            self.schemas.iter()
+                .find_map(|schema| schema.get_view(view_name))
+        }
+    }
+
+    pub fn get_all_views(&self) -> Vec<&View> {
+        self.schemas.iter()
+            .flat_map(|schema| &schema.views)
+            .collect()
+    }
+
+    pub fn get_procedure(&self, schema_name: Option<&str>, procedure_name: &str) -> Option<&Procedure> {
+        if let Some(schema) = schema_name.and_then(|name| self.get_schema(name)) {
+            schema.procedures.iter().find(|p| p.name == procedure_name)
+        } else {
+            self.schemas.iter()
+                .find_map(|schema| schema.procedures.iter().find(|p| p.name == procedure_name))
+        }
+    }
+
+    pub fn get_all_procedures(&self) -> Vec<&Procedure> {
+        self.schemas.iter()
+            .flat_map(|schema| &schema.procedures)
+            .collect()
+    }
+
+    pub fn get_all_functions(&self) -> Vec<&Procedure> {
+        self.schemas.iter()
+            .flat_map(|schema| &schema.functions)
+            .collect()
+    }
+
+    pub fn get_all_permissions(&self) -> Vec<&Permission> {
+        self.permissions.iter().collect()
+    }
+
     pub fn get_foreign_key_relationships(&self) -> Vec<(&Table, &ForeignKey)> {
         self.get_all_tables()
             .into_iter()
@@ -452,6 +763,10 @@ impl Schema {
             name: name.map(|s| s.to_string()),
             tables: Vec::new(),
             views: Vec::new(),
+            procedures: Vec::new(),
+            functions: Vec::new(),
+            types: Vec::new(),
+            sequences: Vec::new(),
             metadata: SchemaMetadata::default(),
         }
     }
@@ -476,9 +791,44 @@ impl Schema {
         self.views.iter().find(|v| v.name == name)
     }
 
+    pub fn add_type(&mut self, user_type: UserDefinedType) {
+        self.types.push(user_type);
+    }
+
+    /// Type names are matched case-insensitively, as unquoted names are in every dialect.
+    pub fn get_type(&self, name: &str) -> Option<&UserDefinedType> {
+        self.types.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
     pub fn get_view_mut(&mut self, name: &str) -> Option<&mut View> {
         self.views.iter_mut().find(|v| v.name == name)
     }
+
+    pub fn add_sequence(&mut self, sequence: Sequence) {
+        self.sequences.push(sequence);
+    }
+}
+
+impl Sequence {
+    /// The sequence a default such as `nextval('orders_seq')`, `orders_seq.NEXTVAL` or
+    /// `NEXT VALUE FOR orders_seq` draws from.
+    pub fn referenced_by(expression: &str) -> Option<String> {
+        let expression = expression.trim().trim_start_matches('(').trim_end_matches(')').trim();
+        let upper = expression.to_uppercase();
+        let name = if let Some(rest) = upper.strip_prefix("NEXTVAL(") {
+            // `nextval('orders_seq'::regclass)`
+            let rest = &expression[expression.len() - rest.len()..];
+            rest.split("::").next().unwrap_or(rest).trim().trim_matches('\'')
+        } else if upper.starts_with("NEXT VALUE FOR ") {
+            expression["NEXT VALUE FOR ".len()..].trim()
+        } else if upper.ends_with(".NEXTVAL") {
+            &expression[..expression.len() - ".NEXTVAL".len()]
+        } else {
+            return None;
+        };
+        let name = name.rsplit('.').next().unwrap_or(name);
+        Some(name.trim_matches(['"', '[', ']', '`']).to_string())
+    }
 }
 
 impl Table {
@@ -492,6 +842,14 @@ impl Table {
             foreign_keys: Vec::new(),
             unique_constraints: Vec::new(),
             check_constraints: Vec::new(),
+            exclusion_constraints: Vec::new(),
+            triggers: Vec::new(),
+            partition_info: None,
+            engine: None,
+            charset: None,
+            collation: None,
+            strict: false,
+            without_rowid: false,
             metadata: TableMetadata::default(),
         }
     }
@@ -568,7 +926,7 @@ impl Table {
             .collect()
     }
 
-    pub fn get_referencing_tables(&self, all_tables: &[&Table]) -> Vec<&Table> {
+    pub fn get_referencing_tables<'a>(&self, all_tables: &[&'a Table]) -> Vec<&'a Table> {
         all_tables.iter()
             .filter(|other_table| {
                 other_table.foreign_keys.iter()
@@ -587,6 +945,7 @@ impl View {
             definition: definition.to_string(),
             columns: Vec::new(),
             dependencies: Vec::new(),
+            view_type: ViewType::Regular,
             metadata: ViewMetadata::default(),
         }
     }
@@ -616,7 +975,13 @@ impl Column {
             default_value: None,
             auto_increment: false,
             identity: None,
+            generated: None,
             collation: None,
+            charset: None,
+            on_update: None,
+            affinity: None,
+            rowid_alias: false,
+            user_type: None,
             metadata: ColumnMetadata::default(),
         }
     }
@@ -660,7 +1025,14 @@ impl Column {
     }
 
     pub fn get_data_type_name(&self) -> String {
-        match &self.data_type {
+        self.data_type.to_sql()
+    }
+}
+
+impl DataType {
+    /// Renders the type as it would appear in a column definition, parameters included.
+    pub fn to_sql(&self) -> String {
+        match self {
             DataType::Boolean => "BOOLEAN".to_string(),
             DataType::TinyInt { unsigned } => {
                 if *unsigned { "TINYINT UNSIGNED" } else { "TINYINT" }.to_string()
@@ -696,8 +1068,9 @@ impl Column {
             DataType::NChar { length } => {
                 length.map_or("NCHAR".to_string(), |l| format!("NCHAR({})", l))
             }
+            // Only SQL Server has an unbounded NVARCHAR, and it spells it MAX
             DataType::NVarChar { length } => {
-                length.map_or("NVARCHAR".to_string(), |l| format!("NVARCHAR({})", l))
+                length.map_or("NVARCHAR(MAX)".to_string(), |l| format!("NVARCHAR({})", l))
             }
             DataType::NText => "NTEXT".to_string(),
             DataType::Binary { length } => {
@@ -721,18 +1094,14 @@ impl Column {
             DataType::Xml => "XML".to_string(),
             DataType::Custom(name) => name.clone(),
             DataType::Array { element_type, dimensions } => {
-                let base_type = element_type.get_data_type_name();
+                let base_type = element_type.to_sql();
                 dimensions.map_or(
                     format!("{}[]", base_type),
                     |d| format!("{}{}", base_type, "[]".repeat(d as usize))
                 )
             }
-            DataType::Enum { values } => {
-                format!("ENUM({})", values.join(", "))
-            }
-            DataType::Set { values } => {
-                format!("SET({})", values.join(", "))
-            }
+            DataType::Enum { values } => format!("ENUM({})", quote_values(values)),
+            DataType::Set { values } => format!("SET({})", quote_values(values)),
             DataType::Geometry => "GEOMETRY".to_string(),
             DataType::Point => "POINT".to_string(),
             DataType::LineString => "LINESTRING".to_string(),
@@ -743,9 +1112,44 @@ impl Column {
             DataType::GeometryCollection => "GEOMETRYCOLLECTION".to_string(),
         }
     }
-}
 
-impl DataType {
+    /// Resolves a vendor type name such as `LONGTEXT` or `NVARCHAR(50)` to the closest
+    /// variant, keeping the name as [`DataType::Custom`] when nothing matches.
+    pub fn from_type_name(name: &str) -> DataType {
+        let (base, args) = match name.split_once('(') {
+            Some((base, rest)) => (base.trim(), rest.trim_end_matches(')').trim()),
+            None => (name.trim(), ""),
+        };
+        let length = args.parse::<u32>().ok();
+
+        match base.to_uppercase().as_str() {
+            "MEDIUMINT" => DataType::Integer { unsigned: false },
+            "REAL" => DataType::Real,
+            "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "CLOB" => DataType::Text,
+            "NTEXT" | "NCLOB" => DataType::NText,
+            "VARCHAR2" => DataType::VarChar { length },
+            "NCHAR" => DataType::NChar { length },
+            "NVARCHAR" | "NVARCHAR2" => DataType::NVarChar { length },
+            "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" | "BYTEA" => DataType::Blob,
+            "IMAGE" => DataType::Image,
+            "SMALLDATETIME" => DataType::SmallDateTime,
+            "DATETIME2" => DataType::DateTime2,
+            "DATETIMEOFFSET" | "TIMESTAMPTZ" => DataType::DateTimeOffset,
+            "UNIQUEIDENTIFIER" => DataType::Uuid,
+            "JSONB" => DataType::JsonB,
+            "XML" => DataType::Xml,
+            "GEOMETRY" => DataType::Geometry,
+            "POINT" => DataType::Point,
+            "LINESTRING" => DataType::LineString,
+            "POLYGON" => DataType::Polygon,
+            "MULTIPOINT" => DataType::MultiPoint,
+            "MULTILINESTRING" => DataType::MultiLineString,
+            "MULTIPOLYGON" => DataType::MultiPolygon,
+            "GEOMETRYCOLLECTION" => DataType::GeometryCollection,
+            _ => DataType::Custom(name.to_string()),
+        }
+    }
+
     pub fn get_data_type_name(&self) -> String {
         match self {
             DataType::Array { element_type, .. } => element_type.get_data_type_name(),
@@ -822,6 +1226,38 @@ impl DataType {
         )
     }
 
+    /// The JSON Schema `type` a value of this column type serializes as.
+    pub fn json_schema_type(&self) -> &'static str {
+        match self {
+            DataType::Boolean => "boolean",
+            DataType::TinyInt { .. } | DataType::SmallInt { .. } | DataType::Integer { .. } | DataType::BigInt { .. } => {
+                "integer"
+            }
+            DataType::Decimal { scale: Some(0), .. } => "integer",
+            DataType::Decimal { .. } | DataType::Float { .. } | DataType::Double | DataType::Real => "number",
+            DataType::Json | DataType::JsonB => "object",
+            DataType::Array { .. } | DataType::Set { .. } => "array",
+            _ => "string",
+        }
+    }
+
+    /// The JSON Schema `format` refining [`Self::json_schema_type`], where one applies.
+    pub fn json_schema_format(&self) -> Option<&'static str> {
+        match self {
+            DataType::Date => Some("date"),
+            DataType::Time => Some("time"),
+            DataType::DateTime
+            | DataType::DateTime2
+            | DataType::SmallDateTime
+            | DataType::Timestamp
+            | DataType::DateTimeOffset => Some("date-time"),
+            DataType::Uuid => Some("uuid"),
+            DataType::Binary { .. } | DataType::VarBinary { .. } | DataType::Blob | DataType::Image => Some("byte"),
+            DataType::BigInt { .. } => Some("int64"),
+            _ => None,
+        }
+    }
+
     pub fn is_spatial(&self) -> bool {
         matches!(self,
             DataType::Geometry | DataType::Point | DataType::LineString | DataType::Polygon |
@@ -837,88 +1273,58 @@ impl fmt::Display for DataType {
     }
 }
 
-// Default implementations
-
-impl Default for DatabaseMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            version: None,
-            created_at: None,
-            author: None,
-            tags: Vec::new(),
-            custom_properties: HashMap::new(),
+impl From<&ast::DataType> for DataType {
+    fn from(data_type: &ast::DataType) -> Self {
+        match data_type {
+            ast::DataType::Boolean => DataType::Boolean,
+            ast::DataType::Integer { size: Some(8), unsigned } => DataType::TinyInt { unsigned: *unsigned },
+            ast::DataType::Integer { size: Some(16), unsigned } => DataType::SmallInt { unsigned: *unsigned },
+            ast::DataType::Integer { size: Some(64), unsigned } => DataType::BigInt { unsigned: *unsigned },
+            ast::DataType::Integer { unsigned, .. } => DataType::Integer { unsigned: *unsigned },
+            ast::DataType::BigInt { unsigned } => DataType::BigInt { unsigned: *unsigned },
+            ast::DataType::SmallInt { unsigned } => DataType::SmallInt { unsigned: *unsigned },
+            ast::DataType::TinyInt { unsigned } => DataType::TinyInt { unsigned: *unsigned },
+            ast::DataType::Float { precision } => DataType::Float { precision: *precision },
+            ast::DataType::Double => DataType::Double,
+            ast::DataType::Decimal { precision, scale } => DataType::Decimal { precision: *precision, scale: *scale },
+            ast::DataType::Varchar { length } => DataType::VarChar { length: *length },
+            ast::DataType::Char { length } => DataType::Char { length: *length },
+            ast::DataType::Text => DataType::Text,
+            ast::DataType::Binary { length } => DataType::Binary { length: *length },
+            ast::DataType::Varbinary { length } => DataType::VarBinary { length: *length },
+            ast::DataType::Blob => DataType::Blob,
+            ast::DataType::Date => DataType::Date,
+            ast::DataType::Time => DataType::Time,
+            ast::DataType::DateTime => DataType::DateTime,
+            ast::DataType::Timestamp => DataType::Timestamp,
+            ast::DataType::Json => DataType::Json,
+            ast::DataType::Uuid => DataType::Uuid,
+            ast::DataType::Enum { values } => DataType::Enum { values: values.clone() },
+            ast::DataType::Set { values } => DataType::Set { values: values.clone() },
+            ast::DataType::Array { element_type, dimensions } => DataType::Array {
+                element_type: Box::new(DataType::from(element_type.as_ref())),
+                dimensions: *dimensions,
+            },
+            ast::DataType::Custom(name) => DataType::from_type_name(name),
         }
     }
 }
 
-impl Default for SchemaMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            owner: None,
-            created_at: None,
-            custom_properties: HashMap::new(),
-        }
-    }
+fn quote_values(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("'{}'", value.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-impl Default for TableMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            category: None,
-            owner: None,
-            created_at: None,
-            updated_at: None,
-            row_count_estimate: None,
-            size_estimate: None,
-            tags: Vec::new(),
-            api_endpoints: Vec::new(),
-            custom_properties: HashMap::new(),
-        }
-    }
-}
+// Default implementations
+
+
+
 
-impl Default for ViewMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            category: None,
-            owner: None,
-            created_at: None,
-            updatable: false,
-            tags: Vec::new(),
-            custom_properties: HashMap::new(),
-        }
-    }
-}
 
-impl Default for ColumnMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            example_values: Vec::new(),
-            validation_rules: Vec::new(),
-            sensitive: false,
-            deprecated: false,
-            tags: Vec::new(),
-            api_field: None,
-            custom_properties: HashMap::new(),
-        }
-    }
-}
 
-impl Default for IndexMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            usage_estimate: None,
-            maintenance_cost: None,
-            custom_properties: HashMap::new(),
-        }
-    }
-}
 
 impl Default for ForeignKeyMetadata {
     fn default() -> Self {
@@ -926,16 +1332,8 @@ impl Default for ForeignKeyMetadata {
             description: None,
             relationship_type: RelationshipType::ManyToOne,
             custom_properties: HashMap::new(),
+            history: Vec::new(),
         }
     }
 }
 
-impl Default for ConstraintMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            business_rule: None,
-            custom_properties: HashMap::new(),
-        }
-    }
-}
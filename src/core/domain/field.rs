@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
+use crate::core::ast;
 use crate::core::domain::entity::*;
 use crate::core::parse::SqlDialect;
 
@@ -61,14 +62,17 @@ pub struct FieldApiInfo {
 pub struct FieldService {
     dialect: SqlDialect,
     type_mappings: HashMap<String, DataType>,
+    standard_mappings: HashMap<String, DataType>,
 }
 
 impl FieldService {
     pub fn new(dialect: SqlDialect) -> Self {
         let type_mappings = Self::build_type_mappings(&dialect);
+        let standard_mappings = Self::build_type_mappings(&SqlDialect::Standard);
         Self {
             dialect,
             type_mappings,
+            standard_mappings,
         }
     }
 
@@ -127,11 +131,10 @@ impl FieldService {
         }
 
         // Check default value compatibility
-        if let Some(default_value) = &field.default_value {
-            if let Err(default_error) = self.validate_default_value(&field.data_type, default_value) {
+        if let Some(default_value) = &field.default_value
+            && let Err(default_error) = self.validate_default_value(&field.data_type, default_value) {
                 errors.push(default_error);
             }
-        }
 
         // Check primary key constraints
         if field.is_primary_key(&table.primary_key) && field.nullable {
@@ -180,29 +183,28 @@ impl FieldService {
             // Numeric type compatibility
             (DataType::TinyInt { unsigned: s_unsigned }, DataType::SmallInt { unsigned: t_unsigned }) => {
                 conversion_required = true;
-                !(*s_unsigned && !*t_unsigned) // Can't convert unsigned to signed if value might overflow
+                !*s_unsigned || *t_unsigned // Can't convert unsigned to signed if value might overflow
             }
             (DataType::SmallInt { unsigned: s_unsigned }, DataType::Integer { unsigned: t_unsigned }) => {
                 conversion_required = true;
-                !(*s_unsigned && !*t_unsigned)
+                !*s_unsigned || *t_unsigned
             }
             (DataType::Integer { unsigned: s_unsigned }, DataType::BigInt { unsigned: t_unsigned }) => {
                 conversion_required = true;
-                !(*s_unsigned && !*t_unsigned)
+                !*s_unsigned || *t_unsigned
             }
 
             // String type compatibility
             (DataType::Char { length: s_len }, DataType::VarChar { length: t_len }) => {
                 conversion_required = true;
-                if let (Some(s), Some(t)) = (s_len, t_len) {
-                    if s > t {
+                if let (Some(s), Some(t)) = (s_len, t_len)
+                    && s > t {
                         data_loss_possible = true;
                         notes.push(format!("Character data may be truncated from {} to {} characters", s, t));
                     }
-                }
                 true
             }
-            (DataType::VarChar { length: s_len }, DataType::Text) => {
+            (DataType::VarChar { length: _s_len }, DataType::Text) => {
                 conversion_required = true;
                 true
             }
@@ -272,13 +274,13 @@ impl FieldService {
 
         // Validation rules
         let validation_rules = field.metadata.validation_rules.iter()
-            .map(|rule| format!("{}: {}", rule.rule_type, rule.message.as_deref().unwrap_or("")))
+            .map(|rule| format!("{:?}: {}", rule.rule_type, rule.message.as_deref().unwrap_or("")))
             .collect();
 
         // API info
         let api_info = field.metadata.api_field.as_ref().map(|api| {
             let validation = api.validation.iter()
-                .map(|v| format!("Validation: {}", v))
+                .map(|v| format!("Validation: {:?}", v))
                 .collect();
 
             FieldApiInfo {
@@ -312,7 +314,7 @@ impl FieldService {
                 field_name: field.name.clone(),
                 suggestion_type: SuggestionType::AddConstraint,
                 message: "Primary key fields should be NOT NULL".to_string(),
-                sql_fix: format!("ALTER TABLE {} MODIFY {} {} NOT NULL", table.name, field.name, field.get_data_type_name()),
+                sql_fix: self.alter_column_sql(field, table, None, true),
             });
         }
 
@@ -322,7 +324,7 @@ impl FieldService {
                 field_name: field.name.clone(),
                 suggestion_type: SuggestionType::Naming,
                 message: format!("Field name '{}' is quite long ({} characters)", field.name, field.name.len()),
-                sql_fix: format!("Consider using a shorter alias or renaming the field"),
+                sql_fix: "Consider using a shorter alias or renaming the field".to_string(),
             });
         }
 
@@ -343,7 +345,7 @@ impl FieldService {
                     field_name: field.name.clone(),
                     suggestion_type: SuggestionType::DataType,
                     message: "VARCHAR without length limit may cause performance issues".to_string(),
-                    sql_fix: format!("ALTER TABLE {} MODIFY {} VARCHAR(255)", table.name, field.name),
+                    sql_fix: self.alter_column_sql(field, table, Some("VARCHAR(255)"), false),
                 });
             }
             DataType::Text => {
@@ -351,7 +353,7 @@ impl FieldService {
                     field_name: field.name.clone(),
                     suggestion_type: SuggestionType::DataType,
                     message: "TEXT fields cannot have default values in some databases".to_string(),
-                    sql_fix: format!("Consider using VARCHAR with appropriate length"),
+                    sql_fix: "Consider using VARCHAR with appropriate length".to_string(),
                 });
             }
             _ => {}
@@ -360,6 +362,32 @@ impl FieldService {
         suggestions
     }
 
+    /// The `ALTER TABLE` that gives a column a new type or makes it NOT NULL, as the dialect
+    /// writes it. MySQL and SQL Server redeclare the whole column, so they restate its type
+    /// and nullability; SQLite can't alter a column at all.
+    fn alter_column_sql(&self, field: &Column, table: &Table, data_type: Option<&str>, not_null: bool) -> String {
+        let type_name = data_type.map(str::to_string).unwrap_or_else(|| field.get_data_type_name());
+        let definition = if not_null || !field.nullable { format!("{} NOT NULL", type_name) } else { type_name };
+        let changes: Vec<String> = data_type
+            .map(|data_type| (format!("TYPE {}", data_type), data_type.to_string()))
+            .into_iter()
+            .chain(not_null.then(|| ("SET NOT NULL".to_string(), "NOT NULL".to_string())))
+            .map(|(postgres, oracle)| match self.dialect {
+                SqlDialect::Oracle => oracle,
+                _ => format!("ALTER COLUMN {} {}", field.name, postgres),
+            })
+            .collect();
+        match self.dialect {
+            SqlDialect::MySQL => format!("ALTER TABLE {} MODIFY {} {}", table.name, field.name, definition),
+            SqlDialect::MSSQL => format!("ALTER TABLE {} ALTER COLUMN {} {}", table.name, field.name, definition),
+            SqlDialect::Oracle => format!("ALTER TABLE {} MODIFY ({} {})", table.name, field.name, changes.join(" ")),
+            SqlDialect::SQLite => {
+                format!("Rebuild {} declaring {} {}, as SQLite can't alter a column", table.name, field.name, definition)
+            }
+            SqlDialect::PostgreSQL | SqlDialect::Standard => format!("ALTER TABLE {} {}", table.name, changes.join(", ")),
+        }
+    }
+
     pub fn analyze_field_relationships(&self, field: &Column, database: &Database) -> FieldRelationshipAnalysis {
         let mut referenced_by = Vec::new();
         let mut references = Vec::new();
@@ -380,7 +408,7 @@ impl FieldService {
 
         // Find what this field references (if it's a foreign key)
         for table in database.get_all_tables() {
-            if let Some(column) = table.get_column(&field.name) {
+            if let Some(_column) = table.get_column(&field.name) {
                 for fk in &table.foreign_keys {
                     if fk.columns.contains(&field.name) {
                         references.push(format!("{}.{}", fk.referenced_table, fk.referenced_columns.join(", ")));
@@ -398,24 +426,284 @@ impl FieldService {
         }
     }
 
+    /// Resolves a type name as the source dialect reads it: the well-known vendor names first,
+    /// then the dialect's type table, then the standard one.
+    pub fn resolve_type_name(&self, data_type: &str) -> DataType {
+        let (base, args) = match data_type.split_once('(') {
+            Some((base, rest)) => (base.trim().to_uppercase(), Some(rest.trim_end_matches(')'))),
+            None => (data_type.trim().to_uppercase(), None),
+        };
+
+        match DataType::from_type_name(data_type) {
+            DataType::Custom(_) => self.type_mappings.get(&base)
+                .or_else(|| self.standard_mappings.get(&base))
+                .cloned()
+                .map(|mapped| match (mapped, args) {
+                    (DataType::Custom(_), _) => DataType::Custom(data_type.trim().to_string()),
+                    (mapped, Some(args)) => Self::apply_type_arguments(mapped, args),
+                    (mapped, None) => mapped,
+                })
+                .unwrap_or_else(|| DataType::Custom(data_type.trim().to_string())),
+            known => known,
+        }
+    }
+
     pub fn convert_data_type(&self, data_type: &str, target_dialect: &SqlDialect) -> Option<DataType> {
-        // This would handle cross-dialect data type conversion
-        // For now, just return the mapped type if available
-        self.type_mappings.get(data_type).cloned()
+        let base = data_type.split('(').next().unwrap_or(data_type).trim().to_uppercase();
+
+        // Names the target declares natively but has no canonical type for (MEDIUMTEXT,
+        // MONEY, ...) keep their exact spelling
+        if let Some(ast::DataType::Custom(_)) = target_dialect.config().get_data_type(&base) {
+            return Some(DataType::Custom(data_type.trim().to_string()));
+        }
+
+        let source_type = self.resolve_type_name(data_type);
+        // SQLite accepts any type name and stores values by the affinity it implies
+        let source_type = match source_type {
+            DataType::Custom(name) if self.dialect == SqlDialect::SQLite => match TypeAffinity::from_declared_type(&name) {
+                // SQLite integers are 64-bit
+                TypeAffinity::Integer => DataType::BigInt { unsigned: false },
+                affinity => affinity.data_type(),
+            },
+            source_type => source_type,
+        };
+
+        Self::adapt_data_type(&source_type, target_dialect)
+    }
+
+    /// Rewrites a type into the closest one `target_dialect` can declare, or `None` when the
+    /// dialect has no equivalent (ENUM outside MySQL, arrays outside PostgreSQL, ...).
+    pub fn adapt_data_type(data_type: &DataType, target_dialect: &SqlDialect) -> Option<DataType> {
+        match target_dialect {
+            SqlDialect::PostgreSQL => Self::adapt_for_postgresql(data_type),
+            SqlDialect::MySQL => Self::adapt_for_mysql(data_type),
+            SqlDialect::SQLite => Self::adapt_for_sqlite(data_type),
+            SqlDialect::MSSQL => Self::adapt_for_mssql(data_type),
+            SqlDialect::Oracle => Self::adapt_for_oracle(data_type),
+            SqlDialect::Standard => Self::adapt_for_standard(data_type),
+        }
+    }
+
+    fn apply_type_arguments(data_type: DataType, args: &str) -> DataType {
+        let numbers: Vec<Option<u32>> = args.split(',').map(|arg| arg.trim().parse().ok()).collect();
+        let first = numbers.first().copied().flatten();
+        let second = numbers.get(1).copied().flatten();
+
+        match data_type {
+            DataType::Decimal { .. } => DataType::Decimal { precision: first, scale: second },
+            DataType::Float { .. } => DataType::Float { precision: first },
+            DataType::Char { .. } => DataType::Char { length: first },
+            DataType::VarChar { .. } => DataType::VarChar { length: first },
+            DataType::NChar { .. } => DataType::NChar { length: first },
+            DataType::NVarChar { .. } => DataType::NVarChar { length: first },
+            DataType::Binary { .. } => DataType::Binary { length: first },
+            DataType::VarBinary { .. } => DataType::VarBinary { length: first },
+            other => other,
+        }
+    }
+
+    /// Widens unsigned integers to the next signed size so no value is lost.
+    fn widen_unsigned(data_type: &DataType) -> Option<DataType> {
+        match data_type {
+            DataType::TinyInt { unsigned: true } => Some(DataType::SmallInt { unsigned: false }),
+            DataType::SmallInt { unsigned: true } => Some(DataType::Integer { unsigned: false }),
+            DataType::Integer { unsigned: true } => Some(DataType::BigInt { unsigned: false }),
+            DataType::BigInt { unsigned: true } => Some(DataType::Decimal { precision: Some(20), scale: Some(0) }),
+            _ => None,
+        }
+    }
+
+    fn adapt_custom(name: &str, target_dialect: &SqlDialect) -> Option<DataType> {
+        let base = name.split('(').next().unwrap_or(name).trim().to_uppercase();
+        let known = target_dialect.config().get_data_type(&base).is_some()
+            || SqlDialect::Standard.config().get_data_type(&base).is_some();
+        known.then(|| DataType::Custom(name.to_string()))
+    }
+
+    fn adapt_for_postgresql(data_type: &DataType) -> Option<DataType> {
+        if let Some(widened) = Self::widen_unsigned(data_type) {
+            return Some(widened);
+        }
+        match data_type {
+            DataType::TinyInt { .. } => Some(DataType::SmallInt { unsigned: false }),
+            DataType::Double => Some(DataType::Custom("DOUBLE PRECISION".to_string())),
+            DataType::NChar { length } => Some(DataType::Char { length: *length }),
+            DataType::NVarChar { length } => Some(DataType::VarChar { length: *length }),
+            DataType::NText => Some(DataType::Text),
+            DataType::Binary { .. } | DataType::VarBinary { .. } | DataType::Blob | DataType::Image => {
+                Some(DataType::Custom("BYTEA".to_string()))
+            }
+            DataType::DateTime | DataType::SmallDateTime | DataType::DateTime2 => Some(DataType::Timestamp),
+            DataType::DateTimeOffset => Some(DataType::Custom("TIMESTAMPTZ".to_string())),
+            DataType::Array { element_type, dimensions } => Some(DataType::Array {
+                element_type: Box::new(Self::adapt_for_postgresql(element_type)?),
+                dimensions: *dimensions,
+            }),
+            DataType::Enum { .. } | DataType::Set { .. } => None,
+            data_type if data_type.is_spatial() => None,
+            DataType::Custom(name) => Self::adapt_custom(name, &SqlDialect::PostgreSQL),
+            other => Some(other.clone()),
+        }
+    }
+
+    fn adapt_for_mysql(data_type: &DataType) -> Option<DataType> {
+        match data_type {
+            DataType::Real => Some(DataType::Double),
+            DataType::NChar { length } => Some(DataType::Char { length: *length }),
+            DataType::VarChar { length: None } | DataType::NVarChar { length: None } | DataType::NText | DataType::Xml => {
+                Some(DataType::Text)
+            }
+            DataType::NVarChar { length } => Some(DataType::VarChar { length: *length }),
+            DataType::VarBinary { length: None } | DataType::Image => Some(DataType::Blob),
+            DataType::Timestamp | DataType::SmallDateTime | DataType::DateTime2 => Some(DataType::DateTime),
+            DataType::Uuid => Some(DataType::Char { length: Some(36) }),
+            DataType::JsonB => Some(DataType::Json),
+            // TIMESTAMP is stored as UTC, the closest MySQL has to an offset-aware type
+            DataType::DateTimeOffset => Some(DataType::Timestamp),
+            DataType::Array { .. } => None,
+            DataType::Custom(name) => Self::adapt_custom(name, &SqlDialect::MySQL),
+            other => Some(other.clone()),
+        }
+    }
+
+    /// SQLite accepts any type name but only keeps the affinity it implies, and reads some
+    /// names wrongly (`DATETIMEOFFSET` as NUMERIC, `JSON` as NUMERIC), so each type becomes
+    /// the plain name of the affinity that stores its values.
+    fn adapt_for_sqlite(data_type: &DataType) -> Option<DataType> {
+        let affinity = match data_type {
+            DataType::Boolean
+            | DataType::TinyInt { .. }
+            | DataType::SmallInt { .. }
+            | DataType::Integer { .. }
+            | DataType::BigInt { .. } => TypeAffinity::Integer,
+            DataType::Decimal { .. } => TypeAffinity::Numeric,
+            DataType::Float { .. } | DataType::Double | DataType::Real => TypeAffinity::Real,
+            // Dates and times are kept as ISO 8601 text, which SQLite's date functions read
+            DataType::Char { .. }
+            | DataType::VarChar { .. }
+            | DataType::Text
+            | DataType::NChar { .. }
+            | DataType::NVarChar { .. }
+            | DataType::NText
+            | DataType::Date
+            | DataType::Time
+            | DataType::DateTime
+            | DataType::SmallDateTime
+            | DataType::DateTime2
+            | DataType::DateTimeOffset
+            | DataType::Timestamp
+            | DataType::Uuid
+            | DataType::Json
+            | DataType::JsonB
+            | DataType::Xml => TypeAffinity::Text,
+            DataType::Binary { .. } | DataType::VarBinary { .. } | DataType::Blob | DataType::Image => TypeAffinity::Blob,
+            DataType::Custom(name) => {
+                Self::adapt_custom(name, &SqlDialect::SQLite)?;
+                TypeAffinity::from_declared_type(name)
+            }
+            DataType::Array { .. }
+            | DataType::Enum { .. }
+            | DataType::Set { .. }
+            | DataType::Geometry
+            | DataType::Point
+            | DataType::LineString
+            | DataType::Polygon
+            | DataType::MultiPoint
+            | DataType::MultiLineString
+            | DataType::MultiPolygon
+            | DataType::GeometryCollection => return None,
+        };
+        Some(match affinity {
+            TypeAffinity::Numeric => DataType::Custom("NUMERIC".to_string()),
+            affinity => affinity.data_type(),
+        })
+    }
+
+    fn adapt_for_mssql(data_type: &DataType) -> Option<DataType> {
+        if let Some(widened) = Self::widen_unsigned(data_type) {
+            return Some(widened);
+        }
+        match data_type {
+            DataType::Boolean => Some(DataType::Custom("BIT".to_string())),
+            // SQL Server's TINYINT is unsigned, so a signed one needs the next size up
+            DataType::TinyInt { .. } => Some(DataType::SmallInt { unsigned: false }),
+            DataType::Double => Some(DataType::Float { precision: Some(53) }),
+            DataType::Text | DataType::VarChar { length: None } | DataType::Json | DataType::JsonB => {
+                Some(DataType::Custom("NVARCHAR(MAX)".to_string()))
+            }
+            DataType::Blob | DataType::VarBinary { length: None } => Some(DataType::Custom("VARBINARY(MAX)".to_string())),
+            DataType::DateTime | DataType::Timestamp => Some(DataType::DateTime2),
+            DataType::Uuid => Some(DataType::Custom("UNIQUEIDENTIFIER".to_string())),
+            DataType::Enum { .. } | DataType::Set { .. } | DataType::Array { .. } => None,
+            data_type if data_type.is_spatial() => Some(DataType::Custom("GEOMETRY".to_string())),
+            DataType::Custom(name) => Self::adapt_custom(name, &SqlDialect::MSSQL),
+            other => Some(other.clone()),
+        }
+    }
+
+    fn adapt_for_oracle(data_type: &DataType) -> Option<DataType> {
+        let number = |digits: u32| Some(DataType::Custom(format!("NUMBER({})", digits)));
+        match data_type {
+            DataType::Boolean => number(1),
+            DataType::TinyInt { .. } => number(3),
+            DataType::SmallInt { .. } => number(5),
+            DataType::Integer { .. } => number(10),
+            DataType::BigInt { unsigned: false } => number(19),
+            DataType::BigInt { unsigned: true } => number(20),
+            DataType::Decimal { precision: Some(p), scale: Some(s) } => {
+                Some(DataType::Custom(format!("NUMBER({}, {})", p, s)))
+            }
+            DataType::Decimal { precision: Some(p), scale: None } => number(*p),
+            DataType::Decimal { .. } => Some(DataType::Custom("NUMBER".to_string())),
+            DataType::Float { .. } | DataType::Real => Some(DataType::Custom("BINARY_FLOAT".to_string())),
+            DataType::Double => Some(DataType::Custom("BINARY_DOUBLE".to_string())),
+            DataType::VarChar { length } => {
+                Some(DataType::Custom(format!("VARCHAR2({})", length.unwrap_or(4000))))
+            }
+            DataType::NVarChar { length } => {
+                Some(DataType::Custom(format!("NVARCHAR2({})", length.unwrap_or(2000))))
+            }
+            DataType::Text => Some(DataType::Custom("CLOB".to_string())),
+            DataType::NText => Some(DataType::Custom("NCLOB".to_string())),
+            DataType::Binary { length } | DataType::VarBinary { length: length @ Some(_) } => {
+                Some(DataType::Custom(format!("RAW({})", length.unwrap_or(1))))
+            }
+            DataType::VarBinary { length: None } | DataType::Image => Some(DataType::Blob),
+            DataType::DateTime | DataType::SmallDateTime | DataType::DateTime2 => Some(DataType::Timestamp),
+            DataType::DateTimeOffset => Some(DataType::Custom("TIMESTAMP WITH TIME ZONE".to_string())),
+            DataType::Uuid => Some(DataType::Custom("RAW(16)".to_string())),
+            DataType::JsonB => Some(DataType::Json),
+            DataType::Xml => Some(DataType::Custom("XMLTYPE".to_string())),
+            DataType::Time | DataType::Enum { .. } | DataType::Set { .. } | DataType::Array { .. } => None,
+            data_type if data_type.is_spatial() => Some(DataType::Custom("SDO_GEOMETRY".to_string())),
+            DataType::Custom(name) => Self::adapt_custom(name, &SqlDialect::Oracle),
+            other => Some(other.clone()),
+        }
+    }
+
+    fn adapt_for_standard(data_type: &DataType) -> Option<DataType> {
+        if let Some(widened) = Self::widen_unsigned(data_type) {
+            return Some(widened);
+        }
+        match data_type {
+            DataType::TinyInt { .. } => Some(DataType::SmallInt { unsigned: false }),
+            DataType::Enum { .. } | DataType::Set { .. } | DataType::Array { .. } => None,
+            DataType::Custom(name) => Self::adapt_custom(name, &SqlDialect::Standard),
+            other => Some(other.clone()),
+        }
     }
 
     fn validate_data_type(&self, data_type: &DataType) -> Result<(), FieldValidationError> {
         match data_type {
             DataType::Decimal { precision, scale } => {
-                if let (Some(p), Some(s)) = (precision, scale) {
-                    if s > p {
+                if let (Some(p), Some(s)) = (precision, scale)
+                    && s > p {
                         return Err(FieldValidationError {
                             rule: "decimal_scale_precision".to_string(),
                             message: format!("Decimal scale ({}) cannot be greater than precision ({})", s, p),
                             severity: ValidationSeverity::Error,
                         });
                     }
-                }
             }
             DataType::Char { length: Some(len) } | DataType::VarChar { length: Some(len) } => {
                 if *len == 0 {
@@ -440,44 +728,40 @@ impl FieldService {
 
     fn validate_default_value(&self, data_type: &DataType, default_value: &str) -> Result<(), FieldValidationError> {
         match data_type {
-            DataType::Boolean => {
-                if !matches!(default_value.to_uppercase().as_str(), "TRUE" | "FALSE" | "1" | "0") {
+            DataType::Boolean
+                if !matches!(default_value.to_uppercase().as_str(), "TRUE" | "FALSE" | "1" | "0") => {
                     return Err(FieldValidationError {
                         rule: "boolean_default".to_string(),
                         message: format!("Invalid boolean default value: {}", default_value),
                         severity: ValidationSeverity::Error,
                     });
                 }
-            }
-            DataType::TinyInt { .. } | DataType::SmallInt { .. } | DataType::Integer { .. } | DataType::BigInt { .. } => {
-                if default_value.parse::<i64>().is_err() {
+            DataType::TinyInt { .. } | DataType::SmallInt { .. } | DataType::Integer { .. } | DataType::BigInt { .. }
+                if default_value.parse::<i64>().is_err() => {
                     return Err(FieldValidationError {
                         rule: "numeric_default".to_string(),
                         message: format!("Invalid numeric default value: {}", default_value),
                         severity: ValidationSeverity::Error,
                     });
                 }
-            }
-            DataType::Float { .. } | DataType::Double | DataType::Real | DataType::Decimal { .. } => {
-                if default_value.parse::<f64>().is_err() {
+            DataType::Float { .. } | DataType::Double | DataType::Real | DataType::Decimal { .. }
+                if default_value.parse::<f64>().is_err() => {
                     return Err(FieldValidationError {
                         rule: "decimal_default".to_string(),
                         message: format!("Invalid decimal default value: {}", default_value),
                         severity: ValidationSeverity::Error,
                     });
                 }
-            }
             DataType::Char { .. } | DataType::VarChar { .. } | DataType::Text |
-            DataType::NChar { .. } | DataType::NVarChar { .. } | DataType::NText => {
+            DataType::NChar { .. } | DataType::NVarChar { .. } | DataType::NText
                 // String defaults are generally valid, but check for quotes
-                if !default_value.starts_with('\'') || !default_value.ends_with('\'') {
+                if (!default_value.starts_with('\'') || !default_value.ends_with('\'')) => {
                     return Err(FieldValidationError {
                         rule: "string_default_quotes".to_string(),
                         message: "String default values should be quoted".to_string(),
                         severity: ValidationSeverity::Warning,
                     });
                 }
-            }
             _ => {} // Other types are more flexible
         }
         Ok(())
@@ -494,8 +778,7 @@ impl FieldService {
         }
 
         // First character must be letter or underscore
-        let first_char = identifier.chars().next().unwrap();
-        if !first_char.is_alphabetic() && first_char != '_' {
+        if !identifier.starts_with(|c: char| c.is_alphabetic() || c == '_') {
             return false;
         }
 
@@ -511,7 +794,9 @@ impl FieldService {
 
     fn build_type_mappings(dialect: &SqlDialect) -> HashMap<String, DataType> {
         let config = dialect.config();
-        config.data_types
+        config.data_types.iter()
+            .map(|(name, data_type)| (name.clone(), DataType::from(data_type)))
+            .collect()
     }
 }
 
@@ -564,7 +849,7 @@ impl FieldDocumentationGenerator {
             ));
         }
 
-        markdown.push_str("\n");
+        markdown.push('\n');
 
         // Add constraints section
         let constraints: Vec<_> = fields.iter()
@@ -582,21 +867,27 @@ impl FieldDocumentationGenerator {
             for constraint in constraints {
                 markdown.push_str(&format!("{}\n", constraint));
             }
-            markdown.push_str("\n");
+            markdown.push('\n');
         }
 
         markdown
     }
 
+    // `json!` unwraps internally when serializing non-literal values
+    #[allow(clippy::disallowed_methods)]
     pub fn generate_api_spec(fields: &[Column]) -> serde_json::Value {
         let properties = fields.iter()
             .filter_map(|field| {
                 field.metadata.api_field.as_ref().map(|api| {
                     let mut property = serde_json::json!({
-                        "type": field.data_type.get_simple_name().to_lowercase(),
+                        "type": field.data_type.json_schema_type(),
                         "description": field.metadata.description
                     });
 
+                    if let Some(format) = field.data_type.json_schema_format() {
+                        property["format"] = serde_json::Value::String(format.to_string());
+                    }
+
                     if !api.required {
                         property["nullable"] = serde_json::Value::Bool(true);
                     }
@@ -676,7 +967,7 @@ impl FieldTransformationService {
                 if i > 0 && chars[i - 1].is_lowercase() {
                     result.push('_');
                 }
-                result.push(ch.to_lowercase().next().unwrap());
+                result.extend(ch.to_lowercase());
             } else {
                 result.push(ch);
             }
@@ -693,10 +984,10 @@ impl FieldTransformationService {
             if ch == '_' {
                 capitalize_next = true;
             } else if capitalize_next {
-                result.push(ch.to_uppercase().next().unwrap());
+                result.extend(ch.to_uppercase());
                 capitalize_next = false;
             } else if result.is_empty() {
-                result.push(ch.to_lowercase().next().unwrap());
+                result.extend(ch.to_lowercase());
             } else {
                 result.push(ch);
             }
@@ -709,7 +1000,7 @@ impl FieldTransformationService {
         let camel = Self::to_camel_case(s);
         let mut chars: Vec<char> = camel.chars().collect();
         if let Some(first) = chars.first_mut() {
-            *first = first.to_uppercase().next().unwrap();
+            *first = first.to_ascii_uppercase();
         }
         chars.into_iter().collect()
     }
@@ -802,4 +1093,39 @@ mod tests {
         assert!(!service.is_valid_identifier("123invalid"));
         assert!(!service.is_valid_identifier("invalid-name"));
     }
+
+    #[test]
+    fn test_data_type_conversion() {
+        let service = FieldService::new(SqlDialect::MySQL);
+
+        assert_eq!(
+            service.convert_data_type("MEDIUMTEXT", &SqlDialect::PostgreSQL),
+            Some(DataType::Text)
+        );
+        assert_eq!(
+            service.convert_data_type("MEDIUMTEXT", &SqlDialect::MySQL),
+            Some(DataType::Custom("MEDIUMTEXT".to_string()))
+        );
+        assert_eq!(
+            service.convert_data_type("VARCHAR2(100)", &SqlDialect::PostgreSQL),
+            Some(DataType::VarChar { length: Some(100) })
+        );
+        assert_eq!(service.convert_data_type("INET", &SqlDialect::MySQL), None);
+    }
+
+    #[test]
+    fn test_data_type_adaptation() {
+        assert_eq!(
+            FieldService::adapt_data_type(&DataType::Integer { unsigned: true }, &SqlDialect::PostgreSQL),
+            Some(DataType::BigInt { unsigned: false })
+        );
+        assert_eq!(
+            FieldService::adapt_data_type(&DataType::Uuid, &SqlDialect::MySQL),
+            Some(DataType::Char { length: Some(36) })
+        );
+        assert_eq!(
+            FieldService::adapt_data_type(&DataType::Enum { values: vec!["a".to_string()] }, &SqlDialect::PostgreSQL),
+            None
+        );
+    }
 }
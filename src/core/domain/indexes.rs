@@ -145,7 +145,7 @@ pub struct IndexUsageStats {
 pub struct IndexValidationService;
 
 impl IndexValidationService {
-    pub fn validate_index(index: &Index, table: &Table, database: &Database) -> IndexAnalysis {
+    pub fn validate_index(index: &Index, table: &Table, _database: &Database) -> IndexAnalysis {
         let mut issues = Vec::new();
         let mut recommendations = Vec::new();
 
@@ -266,7 +266,7 @@ impl IndexValidationService {
         }
     }
 
-    fn estimate_selectivity(column: &Column, table: &Table) -> Option<f64> {
+    fn estimate_selectivity(column: &Column, _table: &Table) -> Option<f64> {
         match &column.data_type {
             DataType::Boolean => Some(0.5), // 2 possible values
             DataType::TinyInt { .. } => Some(0.1), // Assuming 10 distinct values
@@ -280,7 +280,7 @@ impl IndexValidationService {
     }
 
     fn calculate_redundancy(index: &Index, table: &Table) -> f64 {
-        let mut redundancy_score = 0.0;
+        let mut redundancy_score: f64 = 0.0;
 
         for existing_index in &table.indexes {
             if existing_index.name != index.name {
@@ -329,7 +329,7 @@ impl IndexValidationService {
         false // Not checking for missing FK indexes in this context
     }
 
-    fn index_type_description(index_type: &IndexType) -> &'static str {
+    fn index_type_description(index_type: &IndexType) -> &str {
         match index_type {
             IndexType::BTree => "B-Tree",
             IndexType::Hash => "Hash",
@@ -337,6 +337,8 @@ impl IndexValidationService {
             IndexType::Gin => "GIN",
             IndexType::SpGist => "SP-GiST",
             IndexType::Brin => "BRIN",
+            IndexType::FullText => "FULLTEXT",
+            IndexType::Spatial => "SPATIAL",
             IndexType::Custom(name) => name,
         }
     }
@@ -415,6 +417,8 @@ impl IndexAnalysisService {
             0.0
         };
 
+        let optimization_recommendations = Self::generate_database_recommendations(&table_analyses);
+
         DatabaseIndexReport {
             total_tables: database.get_all_tables().len(),
             total_indexes,
@@ -424,11 +428,11 @@ impl IndexAnalysisService {
             overall_coverage,
             table_analyses,
             suggested_missing_indexes: all_missing_indexes,
-            optimization_recommendations: Self::generate_database_recommendations(&table_analyses),
+            optimization_recommendations,
         }
     }
 
-    fn suggest_missing_indexes(table: &Table, database: &Database) -> Vec<MissingIndexSuggestion> {
+    fn suggest_missing_indexes(table: &Table, _database: &Database) -> Vec<MissingIndexSuggestion> {
         let mut suggestions = Vec::new();
 
         // Suggest indexes for foreign keys
@@ -498,7 +502,7 @@ impl IndexAnalysisService {
 pub struct IndexOptimizationService;
 
 impl IndexOptimizationService {
-    pub fn optimize_index(index: &Index, analysis: &IndexAnalysis, table: &Table) -> Vec<IndexOptimization> {
+    pub fn optimize_index(index: &Index, _analysis: &IndexAnalysis, table: &Table) -> Vec<IndexOptimization> {
         let mut optimizations = Vec::new();
 
         // Column order optimization
@@ -604,7 +608,7 @@ impl IndexOptimizationService {
         }
     }
 
-    fn suggest_index_type(index: &Index, table: &Table) -> IndexType {
+    fn suggest_index_type(_index: &Index, table: &Table) -> IndexType {
         if table.columns.iter().any(|col| matches!(col.data_type, DataType::Text | DataType::Json)) {
             IndexType::Gin
         } else if table.columns.iter().any(|col| matches!(col.data_type, DataType::Date | DataType::DateTime)) {
@@ -614,7 +618,7 @@ impl IndexOptimizationService {
         }
     }
 
-    fn should_create_partial_index(index: &Index, table: &Table) -> bool {
+    fn should_create_partial_index(_index: &Index, table: &Table) -> bool {
         // Suggest partial indexes if table has status-like columns
         table.columns.iter().any(|col|
             col.name.to_lowercase().contains("active") ||
@@ -645,6 +649,8 @@ impl IndexOptimizationService {
             IndexType::Gin => "gin",
             IndexType::SpGist => "spgist",
             IndexType::Brin => "brin",
+            IndexType::FullText => "fulltext",
+            IndexType::Spatial => "spatial",
             IndexType::Custom(_) => "custom",
         }
     }
@@ -731,7 +737,7 @@ impl IndexDocumentationService {
         let redundant_indexes = analyses.iter().filter(|a| a.redundancy_score > 0.8).count();
         let avg_coverage = analyses.iter().map(|a| a.coverage_score).sum::<f64>() / total_indexes as f64;
 
-        report.push_str(&format!("## Summary\n\n"));
+        report.push_str("## Summary\n\n");
         report.push_str(&format!("- **Total Indexes:** {}\n", total_indexes));
         report.push_str(&format!("- **Used Indexes:** {} ({:.1}%)\n", used_indexes, (used_indexes as f64 / total_indexes as f64) * 100.0));
         report.push_str(&format!("- **Redundant Indexes:** {}\n", redundant_indexes));
@@ -745,7 +751,7 @@ impl IndexDocumentationService {
         report
     }
 
-    fn index_type_description(index_type: &IndexType) -> &'static str {
+    fn index_type_description(index_type: &IndexType) -> &str {
         match index_type {
             IndexType::BTree => "B-Tree (Balanced Tree)",
             IndexType::Hash => "Hash",
@@ -753,6 +759,8 @@ impl IndexDocumentationService {
             IndexType::Gin => "GIN (Generalized Inverted Index)",
             IndexType::SpGist => "SP-GiST (Space-Partitioned GiST)",
             IndexType::Brin => "BRIN (Block Range Index)",
+            IndexType::FullText => "FULLTEXT (MySQL full-text search)",
+            IndexType::Spatial => "SPATIAL (MySQL R-tree)",
             IndexType::Custom(name) => name,
         }
     }
@@ -853,7 +861,7 @@ mod tests {
             unique: false,
             index_type: IndexType::BTree,
             where_clause: None,
-            metadata: IndexMetadata::default(),
+            metadata: crate::core::domain::entity::IndexMetadata::default(),
         };
 
         table.indexes.push(index.clone());
@@ -881,7 +889,7 @@ mod tests {
             unique: false,
             index_type: IndexType::BTree,
             where_clause: None,
-            metadata: IndexMetadata::default(),
+            metadata: crate::core::domain::entity::IndexMetadata::default(),
         };
 
         let index2 = Index {
@@ -899,7 +907,7 @@ mod tests {
             unique: false,
             index_type: IndexType::BTree,
             where_clause: None,
-            metadata: IndexMetadata::default(),
+            metadata: crate::core::domain::entity::IndexMetadata::default(),
         };
 
         let redundancy = IndexValidationService::calculate_column_overlap(&index1, &index2);
@@ -1,7 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
+use crate::core::domain::indexes::EffortLevel;
+use crate::core::domain::permissions::{ImpactLevel, RiskLevel};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseInsights {
@@ -367,7 +369,12 @@ pub struct InsightsEngine;
 
 impl InsightsEngine {
     pub fn generate_database_insights(database: &Database) -> DatabaseInsights {
-        let mut insights = DatabaseInsights {
+        let generated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_default();
+
+        DatabaseInsights {
             overall_health_score: Self::calculate_overall_health_score(database),
             performance_insights: Self::generate_performance_insights(database),
             security_insights: Self::generate_security_insights(database),
@@ -378,10 +385,8 @@ impl InsightsEngine {
             smart_recommendations: Self::generate_smart_recommendations(database),
             trend_analysis: Self::analyze_trends(database),
             capacity_planning: Self::create_capacity_plan(database),
-            generated_at: chrono::Utc::now().to_rfc3339(),
-        };
-
-        insights
+            generated_at,
+        }
     }
 
     fn calculate_overall_health_score(database: &Database) -> f64 {
@@ -392,10 +397,10 @@ impl InsightsEngine {
         let maintenance_score = Self::calculate_maintenance_score(database);
 
         // Weighted average
-        (performance_score * 0.4 + security_score * 0.3 + design_score * 0.2 + maintenance_score * 0.1).max(0.0).min(1.0)
+        (performance_score * 0.4 + security_score * 0.3 + design_score * 0.2 + maintenance_score * 0.1).clamp(0.0, 1.0)
     }
 
-    fn generate_performance_insights(database: &Database) -> Vec<PerformanceInsight> {
+    fn generate_performance_insights(_database: &Database) -> Vec<PerformanceInsight> {
         let mut insights = Vec::new();
 
         // Analyze query patterns for optimization opportunities
@@ -460,7 +465,7 @@ impl InsightsEngine {
         insights
     }
 
-    fn generate_security_insights(database: &Database) -> Vec<SecurityInsight> {
+    fn generate_security_insights(_database: &Database) -> Vec<SecurityInsight> {
         let mut insights = Vec::new();
 
         // Data exposure risk analysis
@@ -513,7 +518,7 @@ impl InsightsEngine {
         insights
     }
 
-    fn generate_design_insights(database: &Database) -> Vec<DesignInsight> {
+    fn generate_design_insights(_database: &Database) -> Vec<DesignInsight> {
         let mut insights = Vec::new();
 
         // Microservice boundary analysis
@@ -559,7 +564,7 @@ impl InsightsEngine {
         insights
     }
 
-    fn identify_optimization_opportunities(database: &Database) -> Vec<OptimizationOpportunity> {
+    fn identify_optimization_opportunities(_database: &Database) -> Vec<OptimizationOpportunity> {
         let mut opportunities = Vec::new();
 
         // Major query performance tuning
@@ -615,7 +620,7 @@ impl InsightsEngine {
         opportunities
     }
 
-    fn generate_predictive_warnings(database: &Database) -> Vec<PredictiveWarning> {
+    fn generate_predictive_warnings(_database: &Database) -> Vec<PredictiveWarning> {
         let mut warnings = Vec::new();
 
         // Storage capacity warning
@@ -663,7 +668,7 @@ impl InsightsEngine {
         warnings
     }
 
-    fn detect_anomalies(database: &Database) -> Vec<AnomalyDetection> {
+    fn detect_anomalies(_database: &Database) -> Vec<AnomalyDetection> {
         let mut anomalies = Vec::new();
 
         // Unusual query pattern anomaly
@@ -717,7 +722,7 @@ impl InsightsEngine {
         anomalies
     }
 
-    fn generate_smart_recommendations(database: &Database) -> Vec<SmartRecommendation> {
+    fn generate_smart_recommendations(_database: &Database) -> Vec<SmartRecommendation> {
         let mut recommendations = Vec::new();
 
         // AI-powered query optimization
@@ -779,7 +784,7 @@ impl InsightsEngine {
         recommendations
     }
 
-    fn analyze_trends(database: &Database) -> TrendAnalysis {
+    fn analyze_trends(_database: &Database) -> TrendAnalysis {
         TrendAnalysis {
             performance_trends: Self::analyze_performance_trends(),
             growth_patterns: Self::analyze_growth_patterns(),
@@ -789,7 +794,7 @@ impl InsightsEngine {
         }
     }
 
-    fn create_capacity_plan(database: &Database) -> CapacityPlanning {
+    fn create_capacity_plan(_database: &Database) -> CapacityPlanning {
         CapacityPlanning {
             storage_forecast: StorageForecast {
                 current_usage_gb: 500.0,
@@ -877,10 +882,10 @@ impl InsightsEngine {
     }
 
     // Helper methods for scoring and analysis
-    fn calculate_performance_score(database: &Database) -> f64 { 0.75 }
-    fn calculate_security_score(database: &Database) -> f64 { 0.82 }
-    fn calculate_design_score(database: &Database) -> f64 { 0.68 }
-    fn calculate_maintenance_score(database: &Database) -> f64 { 0.71 }
+    fn calculate_performance_score(_database: &Database) -> f64 { 0.75 }
+    fn calculate_security_score(_database: &Database) -> f64 { 0.82 }
+    fn calculate_design_score(_database: &Database) -> f64 { 0.68 }
+    fn calculate_maintenance_score(_database: &Database) -> f64 { 0.71 }
 
     fn analyze_performance_trends() -> Vec<PerformanceTrend> {
         vec![
@@ -1001,7 +1006,7 @@ pub struct InsightsDocumentationService;
 
 impl InsightsDocumentationService {
     pub fn generate_insights_report(insights: &DatabaseInsights) -> String {
-        let mut report = format!("# AI-Powered Database Insights Report\n\n");
+        let mut report = "# AI-Powered Database Insights Report\n\n".to_string();
         report.push_str(&format!("**Generated:** {}\n\n", insights.generated_at));
         report.push_str(&format!("## Overall Health Score: {:.1}%\n\n", insights.overall_health_score * 100.0));
 
@@ -1022,7 +1027,7 @@ impl InsightsDocumentationService {
                     for action in &insight.recommended_actions {
                         report.push_str(&format!("- {}\n", action));
                     }
-                    report.push_str("\n");
+                    report.push('\n');
                 }
             }
         }
@@ -1042,7 +1047,7 @@ impl InsightsDocumentationService {
                     for strategy in &insight.mitigation_strategies {
                         report.push_str(&format!("- {}\n", strategy));
                     }
-                    report.push_str("\n");
+                    report.push('\n');
                 }
             }
         }
@@ -1102,12 +1107,12 @@ impl InsightsDocumentationService {
 
         // Capacity Planning Summary
         report.push_str("## 📊 Capacity Planning\n\n");
-        report.push_str(&format!("### Storage Forecast\n"));
+        report.push_str("### Storage Forecast\n");
         report.push_str(&format!("- Current: {:.0} GB\n", insights.capacity_planning.storage_forecast.current_usage_gb));
         report.push_str(&format!("- 1 Year: {:.0} GB\n", insights.capacity_planning.storage_forecast.projected_usage_1year_gb));
         report.push_str(&format!("- 3 Years: {:.0} GB\n\n", insights.capacity_planning.storage_forecast.projected_usage_3years_gb));
 
-        report.push_str(&format!("### Compute Forecast\n"));
+        report.push_str("### Compute Forecast\n");
         report.push_str(&format!("- Current: {} cores, {:.0} GB RAM\n",
             insights.capacity_planning.compute_forecast.current_cpu_cores,
             insights.capacity_planning.compute_forecast.current_memory_gb));
@@ -2,7 +2,6 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
-use crate::core::domain::relationships::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct JunctionTable {
@@ -155,7 +154,7 @@ pub struct JunctionTableDetectionService;
 impl JunctionTableDetectionService {
     pub fn analyze_table(table: &Table, database: &Database) -> JunctionTableAnalysis {
         let foreign_keys = &table.foreign_keys;
-        let columns = &table.columns;
+        let _columns = &table.columns;
 
         // Basic junction table criteria
         let has_multiple_foreign_keys = foreign_keys.len() >= 2;
@@ -244,14 +243,14 @@ impl JunctionTableDetectionService {
         table.columns.iter()
             .filter(|col| {
                 !table.foreign_keys.iter().any(|fk| fk.columns.contains(&col.name)) &&
-                !table.primary_key.as_ref().map_or(false, |pk| pk.columns.contains(&col.name))
+                !table.primary_key.as_ref().is_some_and(|pk| pk.columns.contains(&col.name))
             })
             .count()
     }
 
     fn has_composite_primary_key_from_foreign_keys(table: &Table) -> bool {
-        if let Some(pk) = &table.primary_key {
-            if pk.columns.len() >= 2 {
+        if let Some(pk) = &table.primary_key
+            && pk.columns.len() >= 2 {
                 // Check if primary key columns are foreign key columns
                 let fk_columns: HashSet<_> = table.foreign_keys.iter()
                     .flat_map(|fk| &fk.columns)
@@ -259,7 +258,6 @@ impl JunctionTableDetectionService {
 
                 return pk.columns.iter().all(|col| fk_columns.contains(col));
             }
-        }
         false
     }
 
@@ -278,34 +276,31 @@ impl JunctionTableDetectionService {
     }
 
     fn is_concatenated_table_names(name: &str) -> bool {
-        // Simple heuristic: if name contains underscores and parts look like table names
+        // Simple heuristic: if name contains underscores and parts look like table names;
+        // two-part names only count when the trailing part is a plural (`user_roles`)
         let parts: Vec<&str> = name.split('_').collect();
-        parts.len() >= 3 && parts.iter().all(|part| part.len() >= 2 && part.chars().all(|c| c.is_alphanumeric()))
-    }
-
-    fn analyze_junction_relationships(table: &Table, database: &Database) -> Vec<DetectedRelationship> {
-        let mut relationships = Vec::new();
-
-        for fk in &table.foreign_keys {
-            if let Some(referenced_table) = database.get_table(None, &fk.referenced_table) {
-                let relationship_type = Self::infer_relationship_type(fk, table, referenced_table);
-
-                relationships.push(DetectedRelationship {
-                    left_table: table.name.clone(),
-                    right_table: fk.referenced_table.clone(),
-                    left_columns: fk.columns.clone(),
-                    right_columns: fk.referenced_columns.clone(),
-                    relationship_type,
-                    constraints: vec![format!("FOREIGN KEY ({}) REFERENCES {} ({})",
-                        fk.columns.join(", "), fk.referenced_table, fk.referenced_columns.join(", "))],
-                });
-            }
-        }
-
-        relationships
+        let looks_like_tables = parts.iter().all(|part| part.len() >= 2 && part.chars().all(|c| c.is_alphanumeric()));
+        let plural_tail = parts.last().is_some_and(|part| part.ends_with('s'));
+        looks_like_tables && (parts.len() >= 3 || (parts.len() == 2 && plural_tail))
+    }
+
+    fn analyze_junction_relationships(table: &Table, _database: &Database) -> Vec<DetectedRelationship> {
+        // The referenced tables need not be part of the model: the foreign keys alone
+        // describe the links a junction table provides.
+        table.foreign_keys.iter()
+            .map(|fk| DetectedRelationship {
+                left_table: table.name.clone(),
+                right_table: fk.referenced_table.clone(),
+                left_columns: fk.columns.clone(),
+                right_columns: fk.referenced_columns.clone(),
+                relationship_type: Self::infer_relationship_type(fk),
+                constraints: vec![format!("FOREIGN KEY ({}) REFERENCES {} ({})",
+                    fk.columns.join(", "), fk.referenced_table, fk.referenced_columns.join(", "))],
+            })
+            .collect()
     }
 
-    fn infer_relationship_type(fk: &ForeignKey, source_table: &Table, target_table: &Table) -> RelationshipType {
+    fn infer_relationship_type(_fk: &ForeignKey) -> RelationshipType {
         // For junction tables, relationships are typically many-to-one from junction to referenced tables
         RelationshipType::ManyToOne
     }
@@ -350,7 +345,7 @@ impl JunctionTableDetectionService {
                     sql_fix: Some(format!("ALTER TABLE {} ADD PRIMARY KEY ({});",
                         table.name,
                         table.foreign_keys.iter()
-                            .flat_map(|fk| &fk.columns)
+                            .flat_map(|fk| fk.columns.iter().map(String::as_str))
                             .collect::<Vec<_>>()
                             .join(", "))),
                 });
@@ -370,8 +365,8 @@ impl JunctionTableDetectionService {
             // Check for nullable foreign key columns
             for fk in &table.foreign_keys {
                 for col_name in &fk.columns {
-                    if let Some(column) = table.get_column(col_name) {
-                        if column.nullable {
+                    if let Some(column) = table.get_column(col_name)
+                        && column.nullable {
                             issues.push(JunctionIssue {
                                 issue_type: JunctionIssueType::DataIntegrity,
                                 severity: IssueSeverity::High,
@@ -381,7 +376,6 @@ impl JunctionTableDetectionService {
                                     table.name, col_name, Self::get_data_type_name(&column.data_type))),
                             });
                         }
-                    }
                 }
             }
         }
@@ -389,14 +383,14 @@ impl JunctionTableDetectionService {
         issues
     }
 
-    fn generate_recommendations(table: &Table, issues: &[JunctionIssue], is_junction_table: bool) -> Vec<JunctionRecommendation> {
+    fn generate_recommendations(table: &Table, _issues: &[JunctionIssue], is_junction_table: bool) -> Vec<JunctionRecommendation> {
         let mut recommendations = Vec::new();
 
         if is_junction_table {
             // Add indexes on foreign key columns if not already present
             for fk in &table.foreign_keys {
                 let has_index = table.indexes.iter().any(|idx| {
-                    idx.columns == fk.columns
+                    idx.columns.iter().map(|c| &c.name).eq(fk.columns.iter())
                 });
 
                 if !has_index {
@@ -416,7 +410,7 @@ impl JunctionTableDetectionService {
                 if fk.on_delete == ReferentialAction::NoAction {
                     recommendations.push(JunctionRecommendation {
                         recommendation_type: RecommendationType::EnhanceDataIntegrity,
-                        description: format!("Consider adding CASCADE DELETE to foreign key for automatic cleanup"),
+                        description: "Consider adding CASCADE DELETE to foreign key for automatic cleanup".to_string(),
                         benefit: "Ensures data consistency when referenced records are deleted".to_string(),
                         effort: EffortLevel::Medium,
                         sql_commands: vec![format!("ALTER TABLE {} DROP CONSTRAINT {}, ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE CASCADE;",
@@ -431,7 +425,7 @@ impl JunctionTableDetectionService {
         recommendations
     }
 
-    fn create_junction_metadata(table: &Table, relationships: &[DetectedRelationship], junction_type: Option<&JunctionType>) -> JunctionTableMetadata {
+    fn create_junction_metadata(_table: &Table, relationships: &[DetectedRelationship], junction_type: Option<&JunctionType>) -> JunctionTableMetadata {
         let left_cardinality = if relationships.len() >= 2 {
             CardinalityDescription {
                 min: 0,
@@ -490,11 +484,10 @@ impl JunctionTableDetectionService {
                 if rel.left_columns.contains(&column.name) {
                     relationship_role = RelationshipRole::LeftForeignKey;
                     refers_to_table = rel.right_table.clone();
-                    if let Some(col_idx) = rel.left_columns.iter().position(|c| c == &column.name) {
-                        if col_idx < rel.right_columns.len() {
+                    if let Some(col_idx) = rel.left_columns.iter().position(|c| c == &column.name)
+                        && col_idx < rel.right_columns.len() {
                             refers_to_column = rel.right_columns[col_idx].clone();
                         }
-                    }
                     break;
                 }
             }
@@ -553,7 +546,7 @@ impl JunctionTableOptimizationService {
         }
 
         // Suggest adding surrogate primary key
-        if junction.primary_key.as_ref().map_or(false, |pk| pk.columns.len() > 2) {
+        if junction.primary_key.as_ref().is_some_and(|pk| pk.columns.len() > 2) {
             optimizations.push(JunctionOptimization {
                 optimization_type: JunctionOptimizationType::AddSurrogateKey,
                 description: "Consider adding a surrogate primary key for better performance".to_string(),
@@ -592,7 +585,7 @@ impl JunctionTableOptimizationService {
         optimizations
     }
 
-    pub fn analyze_junction_performance(junction: &JunctionTable, database: &Database) -> JunctionPerformanceAnalysis {
+    pub fn analyze_junction_performance(junction: &JunctionTable, _database: &Database) -> JunctionPerformanceAnalysis {
         let table_size_estimate = junction.additional_columns.len() * 100; // Rough estimate
         let relationship_count = junction.foreign_keys.len();
 
@@ -832,12 +825,12 @@ impl JunctionTableDocumentationService {
     fn generate_performance_notes(junction: &JunctionTable) -> Vec<String> {
         let mut notes = Vec::new();
 
-        notes.push(format!("Ensure indexes exist on all foreign key columns"));
-        notes.push(format!("Consider composite indexes for common query patterns"));
-        notes.push(format!("Monitor join performance through this junction table"));
+        notes.push("Ensure indexes exist on all foreign key columns".to_string());
+        notes.push("Consider composite indexes for common query patterns".to_string());
+        notes.push("Monitor join performance through this junction table".to_string());
 
         if junction.additional_columns.len() > 2 {
-            notes.push(format!("Large junction table - consider partitioning or archiving strategies"));
+            notes.push("Large junction table - consider partitioning or archiving strategies".to_string());
         }
 
         notes
@@ -847,7 +840,7 @@ impl JunctionTableDocumentationService {
         vec![
             format!("GET /{}/{{id}}/{} - Get all {} for a {}", junction.left_table, junction.right_table, junction.right_table, junction.left_table),
             format!("POST /{}/{{id}}/{} - Associate {} with {}", junction.left_table, junction.right_table, junction.right_table, junction.left_table),
-            format!("DELETE /{}/{{id}}/{{}/{{id2}} - Remove association", junction.left_table, junction.right_table),
+            format!("DELETE /{}/{{id}}/{}/{{id2}} - Remove association", junction.left_table, junction.right_table),
         ]
     }
 
@@ -910,6 +903,7 @@ mod tests {
             table_name: "user_roles".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -922,6 +916,7 @@ mod tests {
             table_name: "user_roles".to_string(),
             columns: vec!["role_id".to_string()],
             referenced_table: "roles".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -934,7 +929,7 @@ mod tests {
 
         database.add_table(junction_table);
 
-        let analysis = JunctionTableDetectionService::analyze_table(database.get_table(None, "user_roles").unwrap(), &database);
+        let analysis = JunctionTableDetectionService::analyze_table(database.get_table(None, "user_roles").expect("user_roles table"), &database);
 
         assert!(analysis.is_junction_table);
         assert!(analysis.confidence_score > 0.6);
@@ -954,6 +949,7 @@ mod tests {
             table_name: "user_posts".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -963,12 +959,12 @@ mod tests {
 
         database.add_table(junction_table);
 
-        let analysis = JunctionTableDetectionService::analyze_table(database.get_table(None, "user_posts").unwrap(), &database);
+        let analysis = JunctionTableDetectionService::analyze_table(database.get_table(None, "user_posts").expect("user_posts table"), &database);
 
         if analysis.is_junction_table {
-            let junction = JunctionTableDetectionService::create_junction_table(database.get_table(None, "user_posts").unwrap(), &analysis);
+            let junction = JunctionTableDetectionService::create_junction_table(database.get_table(None, "user_posts").expect("user_posts table"), &analysis);
             assert!(junction.is_some());
-            let junction = junction.unwrap();
+            let junction = junction.expect("junction table");
             assert_eq!(junction.table_name, "user_posts");
             assert_eq!(junction.left_table, "users");
         }
@@ -1,3 +1,7 @@
+// Analysis modules each carry their own severity/effort/risk enums; the
+// clashing names are reached through their module path instead.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod entity;
 pub mod field;
 pub mod relationships;
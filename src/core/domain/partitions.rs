@@ -1,7 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
+use crate::core::domain::indexes::{EffortLevel, IssueSeverity};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PartitionAnalysis {
@@ -27,7 +28,7 @@ pub enum PartitionScheme {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct PartitionFunction {
     pub name: String,
     pub input_parameter_type: String,
@@ -35,9 +36,10 @@ pub struct PartitionFunction {
     pub range_type: RangeType,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum RangeType {
     Left,
+    #[default]
     Right,
 }
 
@@ -130,7 +132,7 @@ pub struct PartitionMetadata {
 pub struct PartitionAnalysisService;
 
 impl PartitionAnalysisService {
-    pub fn analyze_table_partitions(table: &Table, database: &Database) -> PartitionAnalysis {
+    pub fn analyze_table_partitions(table: &Table, _database: &Database) -> PartitionAnalysis {
         let partition_info = table.partition_info.as_ref();
 
         let (scheme, function, columns, boundaries) = if let Some(info) = partition_info {
@@ -162,9 +164,9 @@ impl PartitionAnalysisService {
             recommendations,
             metadata: PartitionMetadata {
                 description: table.metadata.description.clone(),
-                created_by: table.metadata.created_by.clone(),
+                created_by: table.metadata.owner.clone(),
                 created_at: table.metadata.created_at.clone(),
-                last_maintenance: table.metadata.last_modified.clone(),
+                last_maintenance: table.metadata.updated_at.clone(),
                 business_justification: Some("Optimizes query performance and maintenance operations for large tables".to_string()),
                 data_retention_policy: None,
                 archival_strategy: None,
@@ -188,7 +190,7 @@ impl PartitionAnalysisService {
                 TemporalNature::TimeSeries => PartitioningStrategy {
                     recommended: true,
                     scheme: PartitionScheme::Range,
-                    partition_column: Some("created_at".to_string()),
+                    partition_column,
                     boundary_strategy: BoundaryStrategy::Monthly,
                     estimated_partitions: 12,
                     benefit_score: 0.9,
@@ -227,40 +229,42 @@ impl PartitionAnalysisService {
     }
 
     fn analyze_partition_info(partition_info: &PartitionInfo) -> (PartitionScheme, PartitionFunction, Vec<String>, Vec<PartitionBoundary>) {
-        // This would parse the actual partition information from the database
-        // For now, return placeholder values
-        let scheme = PartitionScheme::Range;
+        let scheme = match partition_info.strategy.to_uppercase().as_str() {
+            "RANGE" => PartitionScheme::Range,
+            "HASH" | "KEY" => PartitionScheme::Hash,
+            "LIST" => PartitionScheme::List,
+            "COMPOSITE" => PartitionScheme::Composite,
+            _ => PartitionScheme::None,
+        };
         let function = PartitionFunction {
-            name: "pf_range".to_string(),
-            input_parameter_type: "datetime".to_string(),
-            boundary_values: vec!["2023-01-01".to_string(), "2023-07-01".to_string()],
+            name: partition_info
+                .function_name
+                .clone()
+                .unwrap_or_else(|| format!("pf_{}", partition_info.strategy.to_lowercase())),
+            input_parameter_type: partition_info.columns.first().cloned().unwrap_or_default(),
+            boundary_values: partition_info.boundary_values.clone(),
             range_type: RangeType::Right,
         };
-        let columns = vec!["created_at".to_string()];
-        let boundaries = vec![
-            PartitionBoundary {
-                boundary_id: 1,
-                boundary_value: "2023-01-01".to_string(),
-                partition_number: 1,
-                row_count: Some(100000),
-                size_mb: Some(500.0),
-            },
-            PartitionBoundary {
-                boundary_id: 2,
-                boundary_value: "2023-07-01".to_string(),
-                partition_number: 2,
-                row_count: Some(150000),
-                size_mb: Some(750.0),
-            },
-        ];
+        let boundaries: Vec<PartitionBoundary> = partition_info
+            .boundary_values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| PartitionBoundary {
+                boundary_id: i as u32 + 1,
+                boundary_value: value.clone(),
+                partition_number: i as u32 + 1,
+                row_count: None,
+                size_mb: None,
+            })
+            .collect();
 
-        (scheme, function, columns, boundaries)
+        (scheme, function, partition_info.columns.clone(), boundaries)
     }
 
     fn calculate_performance_metrics(boundaries: &[PartitionBoundary]) -> PartitionPerformanceMetrics {
         let total_partitions = boundaries.len();
         let sizes: Vec<f64> = boundaries.iter().filter_map(|b| b.size_mb).collect();
-        let row_counts: Vec<u64> = boundaries.iter().filter_map(|b| b.row_count).collect();
+        let _row_counts: Vec<u64> = boundaries.iter().filter_map(|b| b.row_count).collect();
 
         let avg_size = if sizes.is_empty() { 0.0 } else { sizes.iter().sum::<f64>() / sizes.len() as f64 };
         let max_size = sizes.iter().cloned().fold(0.0, f64::max);
@@ -279,7 +283,7 @@ impl PartitionAnalysisService {
         }
     }
 
-    fn generate_maintenance_schedule(scheme: &PartitionScheme, metrics: &PartitionPerformanceMetrics) -> PartitionMaintenanceSchedule {
+    fn generate_maintenance_schedule(scheme: &PartitionScheme, _metrics: &PartitionPerformanceMetrics) -> PartitionMaintenanceSchedule {
         match scheme {
             PartitionScheme::Range => PartitionMaintenanceSchedule {
                 switch_out_frequency: "Monthly".to_string(),
@@ -306,7 +310,7 @@ impl PartitionAnalysisService {
     }
 
     fn analyze_partition_issues(
-        scheme: &PartitionScheme,
+        _scheme: &PartitionScheme,
         boundaries: &[PartitionBoundary],
         metrics: &PartitionPerformanceMetrics,
     ) -> Vec<PartitionIssue> {
@@ -450,17 +454,15 @@ impl PartitionAnalysisService {
     }
 
     fn extract_filegroups(partition_info: Option<&PartitionInfo>) -> Vec<String> {
-        // Placeholder - would extract actual filegroup information
-        vec!["PRIMARY".to_string(), "FG1".to_string(), "FG2".to_string()]
+        partition_info.map(|info| info.filegroups.clone()).unwrap_or_default()
     }
 
     fn estimate_table_size(table: &Table) -> u64 {
-        // Rough estimation based on table structure
-        // In practice, this would use actual statistics
-        1000000 // 1M rows placeholder
+        // Use collected statistics when available, otherwise assume a mid-sized table
+        table.metadata.row_count_estimate.unwrap_or(1_000_000)
     }
 
-    fn analyze_access_patterns(table: &Table, database: &Database) -> AccessPatterns {
+    fn analyze_access_patterns(_table: &Table, _database: &Database) -> AccessPatterns {
         // Placeholder - would analyze actual query patterns
         AccessPatterns {
             temporal_access: true,
@@ -810,9 +812,10 @@ mod tests {
         table.add_column(Column::new("id", DataType::BigInt { unsigned: false }));
         table.add_column(Column::new("customer_id", DataType::Integer { unsigned: false }));
         table.add_column(Column::new("order_date", DataType::DateTime));
-        table.add_column(Column::new("total", DataType::Decimal { precision: 10, scale: 2 }));
+        table.add_column(Column::new("total", DataType::Decimal { precision: Some(10), scale: Some(2) }));
 
         // Simulate large table
+        table.metadata.row_count_estimate = Some(25_000_000);
         let database = Database::new("test_db");
 
         let strategy = PartitionAnalysisService::suggest_partitioning_strategy(&table, &database);
@@ -3,6 +3,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Permission {
+    pub object_name: String,
+    pub object_type: ObjectType,
+    pub grantee: String,
+    pub permission_type: PermissionType,
+    pub grantor: Option<String>,
+    pub is_grantable: bool,
+    pub metadata: PermissionMetadata,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PermissionAnalysis {
     pub object_name: String,
@@ -98,7 +109,7 @@ pub struct RiskAssessment {
     pub mitigation_strategies: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RiskLevel {
     Low,
     Medium,
@@ -170,7 +181,7 @@ pub enum ImpactLevel {
     Minimal,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct PermissionMetadata {
     pub granted_date: Option<String>,
     pub last_used: Option<String>,
@@ -249,6 +260,7 @@ impl PermissionAnalysisService {
         } else {
             0.0
         };
+        let security_recommendations = Self::generate_database_recommendations(&permission_analyses);
 
         DatabasePermissionReport {
             total_permissions: permission_analyses.len(),
@@ -260,7 +272,7 @@ impl PermissionAnalysisService {
                 .map(|p| p.object_name.clone())
                 .collect(),
             permission_analyses,
-            security_recommendations: Self::generate_database_recommendations(&permission_analyses),
+            security_recommendations,
         }
     }
 
@@ -437,7 +449,7 @@ impl PermissionAnalysisService {
         recommendations
     }
 
-    fn generate_general_recommendations(permission: &Permission, access_patterns: &[AccessPattern]) -> Vec<PermissionRecommendation> {
+    fn generate_general_recommendations(permission: &Permission, _access_patterns: &[AccessPattern]) -> Vec<PermissionRecommendation> {
         let mut recommendations = Vec::new();
 
         // Add audit logging recommendation
@@ -494,12 +506,15 @@ impl PermissionAnalysisService {
                   object_name.to_lowercase().contains("hr") ||
                   object_name.to_lowercase().contains("personnel") {
             DataSensitivity::Sensitive
+        } else if object_name.to_lowercase().starts_with("public") ||
+                  object_name.to_lowercase().starts_with("lookup") {
+            DataSensitivity::Public
         } else {
             DataSensitivity::Internal
         }
     }
 
-    fn check_compliance(permission: &Permission, risk_factors: &[RiskFactor]) -> ComplianceStatus {
+    fn check_compliance(_permission: &Permission, risk_factors: &[RiskFactor]) -> ComplianceStatus {
         // Simplified compliance check - in practice, this would check against specific frameworks
         if risk_factors.iter().any(|rf| rf.severity == RiskLevel::Critical) {
             ComplianceStatus::NonCompliant
@@ -562,7 +577,7 @@ impl PermissionAnalysisService {
             RiskLevel::Critical => 0.2,
         };
 
-        let grantable_penalty = if analysis.is_grantable { 0.1 } else { 0.0 };
+        let grantable_penalty: f64 = if analysis.is_grantable { 0.1 } else { 0.0 };
 
         (base_score - grantable_penalty).max(0.0)
     }
@@ -638,7 +653,7 @@ impl PermissionDocumentationService {
             for factor in &analysis.risk_assessment.risk_factors {
                 doc.push_str(&format!("- **{}** ({}) - {}\n",
                     Self::risk_factor_name(&factor.factor_type),
-                    Self::risk_level_name(factor.severity),
+                    Self::risk_level_name(&factor.severity),
                     factor.description));
                 doc.push_str(&format!("  - Evidence: {}\n", factor.evidence));
             }
@@ -841,14 +856,14 @@ mod tests {
         let analysis = PermissionAnalysisService::analyze_permission(&permission, &database);
 
         assert_eq!(analysis.risk_assessment.overall_risk, RiskLevel::High);
-        assert!(analysis.risk_assessment.risk_factors.len() > 0);
+        assert!(!analysis.risk_assessment.risk_factors.is_empty());
         assert!(analysis.is_grantable);
     }
 
     #[test]
     fn test_data_sensitivity_assessment() {
         let mut table = Table::new("user_financial_data");
-        table.add_column(Column::new("salary", DataType::Decimal { precision: 10, scale: 2 }));
+        table.add_column(Column::new("salary", DataType::Decimal { precision: Some(10), scale: Some(2) }));
 
         let mut database = Database::new("test_db");
         database.add_table(table);
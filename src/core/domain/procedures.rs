@@ -2,6 +2,17 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
+use crate::core::domain::indexes::{EffortLevel, IssueSeverity};
+use crate::core::domain::permissions::{RiskLevel, SecurityLevel};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Procedure {
+    pub name: String,
+    pub schema_name: Option<String>,
+    pub parameters: Vec<ProcedureParameter>,
+    pub definition: String,
+    pub metadata: ProcedureMetadata,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProcedureAnalysis {
@@ -168,7 +179,7 @@ pub enum RecommendationType {
     TestingAddition,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct ProcedureMetadata {
     pub description: Option<String>,
     pub created_by: Option<String>,
@@ -260,11 +271,11 @@ impl ProcedureAnalysisService {
         procedure.parameters.iter().map(|param| {
             ProcedureParameter {
                 name: param.name.clone(),
-                data_type: param.data_type.get_simple_name(),
+                data_type: param.data_type.clone(),
                 parameter_mode: param.parameter_mode.clone(),
                 is_nullable: param.is_nullable,
                 default_value: param.default_value.clone(),
-                description: param.metadata.description.clone(),
+                description: param.description.clone(),
             }
         }).collect()
     }
@@ -285,7 +296,7 @@ impl ProcedureAnalysisService {
 
         // Estimate nesting depth
         let mut max_nesting = 0;
-        let mut current_nesting = 0;
+        let mut current_nesting: usize = 0;
 
         for line in definition.lines() {
             let trimmed = line.trim();
@@ -383,7 +394,7 @@ impl ProcedureAnalysisService {
         }
     }
 
-    fn analyze_performance(procedure: &Procedure, database: &Database) -> ProcedurePerformance {
+    fn analyze_performance(procedure: &Procedure, _database: &Database) -> ProcedurePerformance {
         let definition = procedure.definition.to_lowercase();
 
         let temp_table_usage = definition.contains("#") || definition.contains("tempdb");
@@ -629,7 +640,7 @@ impl ProcedureAnalysisService {
                 benefit: format!("Expected improvement: {:.1}%", optimization.expected_improvement * 100.0),
                 effort: optimization.implementation_effort.clone(),
                 sql_commands: vec![
-                    format!("-- Implement {}", optimization.optimization_type.to_string().to_lowercase()),
+                    format!("-- Implement {}", format!("{:?}", optimization.optimization_type).to_lowercase()),
                 ],
                 expected_improvement: Some(optimization.expected_improvement),
             });
@@ -667,7 +678,7 @@ impl ProcedureAnalysisService {
         let complexity = (cyclomatic as f64).ln();
         let maintainability = 171.0 - 5.2 * volume.ln() - 0.23 * complexity - 16.2 * (statements as f64 / lines as f64).ln();
 
-        maintainability.max(0.0).min(171.0) / 171.0 // Normalize to 0.0-1.0
+        maintainability.clamp(0.0, 171.0) / 171.0 // Normalize to 0.0-1.0
     }
 
     fn calculate_dependency_depth(procedures_called: &[String], database: &Database) -> usize {
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
@@ -66,7 +66,7 @@ pub enum RelationshipConstraint {
     CheckConstraint(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct RelationshipMetadata {
     pub description: Option<String>,
     pub business_rule: Option<String>,
@@ -216,15 +216,14 @@ impl RelationshipValidationService {
 
         // Check for circular dependencies
         let graph = RelationshipGraph::from_database(database);
-        if let Some(cycles) = graph.detect_cycles() {
-            if cycles.iter().any(|cycle| cycle.contains(&relationship.source_table) && cycle.contains(&relationship.target_table)) {
+        if let Some(cycles) = graph.detect_cycles()
+            && cycles.iter().any(|cycle| cycle.contains(&relationship.source_table) && cycle.contains(&relationship.target_table)) {
                 warnings.push(RelationshipValidationWarning {
                     rule: "circular_dependency".to_string(),
                     message: format!("Relationship creates circular dependency: {} -> {}", relationship.source_table, relationship.target_table),
                     suggestion: Some("Consider restructuring the schema to avoid circular dependencies".to_string()),
                 });
             }
-        }
 
         RelationshipValidationResult {
             relationship: relationship.clone(),
@@ -282,11 +281,11 @@ impl RelationshipAnalysisService {
                 if fk.referenced_table == table.name {
                     incoming_relationships.push(AnalyzedRelationship {
                         related_table: other_table.name.clone(),
-                        relationship_type: Self::infer_relationship_type(fk, other_table, table),
+                        relationship_type: Self::infer_relationship_type(fk, other_table, Some(table)),
                         columns: fk.columns.clone(),
                         referenced_columns: fk.referenced_columns.clone(),
                         is_nullable: fk.columns.iter().any(|col_name|
-                            other_table.get_column(col_name).map_or(false, |col| col.nullable)
+                            other_table.get_column(col_name).is_some_and(|col| col.nullable)
                         ),
                         constraint_name: fk.name.clone(),
                     });
@@ -304,18 +303,18 @@ impl RelationshipAnalysisService {
                     columns: fk.columns.clone(),
                     referenced_columns: fk.referenced_columns.clone(),
                     is_nullable: fk.columns.iter().any(|col_name|
-                        table.get_column(col_name).map_or(false, |col| col.nullable)
+                        table.get_column(col_name).is_some_and(|col| col.nullable)
                     ),
                     constraint_name: fk.name.clone(),
                 });
             } else {
                 outgoing_relationships.push(AnalyzedRelationship {
                     related_table: fk.referenced_table.clone(),
-                    relationship_type: Self::infer_relationship_type(fk, table, database.get_table(None, &fk.referenced_table).unwrap()),
+                    relationship_type: Self::infer_relationship_type(fk, table, database.get_table(None, &fk.referenced_table)),
                     columns: fk.columns.clone(),
                     referenced_columns: fk.referenced_columns.clone(),
                     is_nullable: fk.columns.iter().any(|col_name|
-                        table.get_column(col_name).map_or(false, |col| col.nullable)
+                        table.get_column(col_name).is_some_and(|col| col.nullable)
                     ),
                     constraint_name: fk.name.clone(),
                 });
@@ -324,6 +323,7 @@ impl RelationshipAnalysisService {
 
         let total_relationships = incoming_relationships.len() + outgoing_relationships.len() + self_references.len();
         let has_circular_refs = !self_references.is_empty();
+        let isolation_level = Self::determine_isolation_level(&incoming_relationships, &outgoing_relationships);
 
         TableRelationshipAnalysis {
             table_name: table.name.clone(),
@@ -332,7 +332,7 @@ impl RelationshipAnalysisService {
             self_references,
             total_relationships,
             has_circular_refs,
-            isolation_level: Self::determine_isolation_level(&incoming_relationships, &outgoing_relationships),
+            isolation_level,
         }
     }
 
@@ -367,18 +367,21 @@ impl RelationshipAnalysisService {
         patterns
     }
 
-    fn infer_relationship_type(fk: &ForeignKey, source_table: &Table, target_table: &Table) -> RelationshipType {
+    fn infer_relationship_type(fk: &ForeignKey, source_table: &Table, target_table: Option<&Table>) -> RelationshipType {
         // Check if the foreign key columns are unique in the source table
         let fk_columns_unique = fk.columns.iter().all(|col_name| {
             source_table.get_column(col_name)
-                .map_or(false, |col| col.is_unique(&source_table.unique_constraints))
+                .is_some_and(|col| col.is_unique(&source_table.unique_constraints))
         });
 
-        // Check if the referenced columns are unique in the target table
-        let ref_columns_unique = fk.referenced_columns.iter().all(|col_name| {
-            target_table.get_column(col_name)
-                .map_or(false, |col| col.is_unique(&target_table.unique_constraints) ||
-                                  col.is_primary_key(&target_table.primary_key))
+        // Check if the referenced columns are unique in the target table; a
+        // table outside the model is assumed to reference its key
+        let ref_columns_unique = target_table.is_none_or(|target_table| {
+            fk.referenced_columns.iter().all(|col_name| {
+                target_table.get_column(col_name)
+                    .is_some_and(|col| col.is_unique(&target_table.unique_constraints) ||
+                                      col.is_primary_key(&target_table.primary_key))
+            })
         });
 
         match (fk_columns_unique, ref_columns_unique) {
@@ -422,12 +425,11 @@ impl RelationshipAnalysisService {
         // Snowflake schemas have chains of relationships (normalization)
         let mut has_chains = false;
         for node in &graph.nodes {
-            if let Some(adjacent) = graph.adjacency_list.get(node) {
-                if adjacent.len() >= 2 {
+            if let Some(adjacent) = graph.adjacency_list.get(node)
+                && adjacent.len() >= 2 {
                     has_chains = true;
                     break;
                 }
-            }
         }
         has_chains && graph.edges.len() > graph.nodes.len()
     }
@@ -446,8 +448,9 @@ impl RelationshipAnalysisService {
                     .map(|fk| fk.referenced_table.as_str())
                     .collect();
 
-                if master_tables.len() == 1 {
-                    let master_table = master_tables.into_iter().next().unwrap();
+                if master_tables.len() == 1
+                    && let Some(master_table) = master_tables.into_iter().next()
+                {
                     patterns.push(RelationshipPattern {
                         pattern_type: PatternType::MasterDetail,
                         description: format!("{} is a detail table for master table {}", table.name, master_table),
@@ -605,7 +608,7 @@ impl RelationshipGraphService {
                         source_columns: fk.columns.clone(),
                         target_columns: fk.referenced_columns.clone(),
                         relationship_type: RelationshipAnalysisService::infer_relationship_type(
-                            fk, table, database.get_table(None, &fk.referenced_table).unwrap()
+                            fk, table, database.get_table(None, &fk.referenced_table)
                         ),
                         cardinality: Cardinality {
                             source_min: 0,
@@ -705,7 +708,7 @@ impl RelationshipDocumentationService {
         // Add relationships
         for table in database.get_all_tables() {
             for fk in &table.foreign_keys {
-                if let Some(cardinality) = Self::get_relationship_cardinality(fk, table, database.get_table(None, &fk.referenced_table).unwrap()) {
+                if let Some(cardinality) = Self::get_relationship_cardinality(fk, table, database.get_table(None, &fk.referenced_table)) {
                     diagram.push_str(&format!("    {} {} {} : \"{}\"\n",
                         table.name,
                         cardinality,
@@ -743,7 +746,7 @@ impl RelationshipDocumentationService {
         // Add relationships
         for table in database.get_all_tables() {
             for fk in &table.foreign_keys {
-                let cardinality = Self::get_relationship_cardinality_symbol(fk, table, database.get_table(None, &fk.referenced_table).unwrap());
+                let cardinality = Self::get_relationship_cardinality_symbol(fk, table, database.get_table(None, &fk.referenced_table));
                 diagram.push_str(&format!("{} {} {}\n",
                     table.name,
                     cardinality,
@@ -780,7 +783,7 @@ impl RelationshipDocumentationService {
         diagram
     }
 
-    fn get_relationship_cardinality(fk: &ForeignKey, source_table: &Table, target_table: &Table) -> Option<String> {
+    fn get_relationship_cardinality(fk: &ForeignKey, source_table: &Table, target_table: Option<&Table>) -> Option<String> {
         let rel_type = RelationshipAnalysisService::infer_relationship_type(fk, source_table, target_table);
 
         match rel_type {
@@ -792,7 +795,7 @@ impl RelationshipDocumentationService {
         }
     }
 
-    fn get_relationship_cardinality_symbol(fk: &ForeignKey, source_table: &Table, target_table: &Table) -> String {
+    fn get_relationship_cardinality_symbol(fk: &ForeignKey, source_table: &Table, target_table: Option<&Table>) -> String {
         let rel_type = RelationshipAnalysisService::infer_relationship_type(fk, source_table, target_table);
 
         match rel_type {
@@ -852,6 +855,50 @@ impl RelationshipGraph {
         result
     }
 
+    /// Every table, each after the tables it references, so creating them in this order never
+    /// points a foreign key at a table that doesn't exist yet; dropping them goes in reverse.
+    /// Ties are broken alphabetically. Tables on a reference cycle have no such order and come
+    /// last, alphabetically.
+    pub fn creation_order(&self) -> Vec<String> {
+        let mut pending: HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut referenced_by: HashMap<&str, Vec<&str>> = HashMap::new();
+        for node in &self.nodes {
+            let references: HashSet<&str> = self
+                .adjacency_list
+                .get(node)
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .filter(|target| *target != node && self.nodes.contains(*target))
+                .collect();
+            for target in &references {
+                referenced_by.entry(*target).or_default().push(node);
+            }
+            pending.insert(node, references);
+        }
+
+        let mut ready: BTreeSet<&str> =
+            pending.iter().filter(|(_, references)| references.is_empty()).map(|(node, _)| *node).collect();
+        let mut order = Vec::new();
+        while let Some(node) = ready.pop_first() {
+            pending.remove(node);
+            order.push(node.to_string());
+            for referrer in referenced_by.get(node).into_iter().flatten() {
+                if let Some(references) = pending.get_mut(referrer) {
+                    references.remove(node);
+                    if references.is_empty() {
+                        ready.insert(referrer);
+                    }
+                }
+            }
+        }
+
+        let mut cyclic: Vec<&str> = pending.into_keys().collect();
+        cyclic.sort_unstable();
+        order.extend(cyclic.into_iter().map(str::to_string));
+        order
+    }
+
     fn find_cycle_from_node(&self, start: &str) -> Option<Vec<String>> {
         let mut visited = HashSet::new();
         let mut path = Vec::new();
@@ -1022,6 +1069,7 @@ mod tests {
             table_name: "posts".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -1072,6 +1120,7 @@ mod tests {
             table_name: "posts".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -1092,6 +1141,41 @@ mod tests {
         assert!(graph.nodes.contains("posts"));
     }
 
+    #[test]
+    fn test_creation_order() {
+        let mut database = Database::new("test_db");
+        let reference = |table: &str, target: &str| ForeignKey {
+            name: None,
+            table_name: table.to_string(),
+            columns: vec![format!("{}_id", target)],
+            referenced_table: target.to_string(),
+            referenced_schema: None,
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ReferentialAction::NoAction,
+            on_update: ReferentialAction::NoAction,
+            constraint_name: None,
+            metadata: ForeignKeyMetadata::default(),
+        };
+
+        let mut comments = Table::new("comments");
+        comments.add_foreign_key(reference("comments", "posts"));
+        comments.add_foreign_key(reference("comments", "users"));
+        comments.add_foreign_key(reference("comments", "comments"));
+        let mut posts = Table::new("posts");
+        posts.add_foreign_key(reference("posts", "users"));
+        let mut left = Table::new("left_side");
+        left.add_foreign_key(reference("left_side", "right_side"));
+        let mut right = Table::new("right_side");
+        right.add_foreign_key(reference("right_side", "left_side"));
+
+        for table in [comments, posts, Table::new("users"), left, right] {
+            database.add_table(table);
+        }
+
+        let order = RelationshipGraph::from_database(&database).creation_order();
+        assert_eq!(order, vec!["users", "posts", "comments", "left_side", "right_side"]);
+    }
+
     #[test]
     fn test_dependency_analysis() {
         let mut database = Database::new("test_db");
@@ -1109,6 +1193,7 @@ mod tests {
             table_name: "posts".to_string(),
             columns: vec!["user_id".to_string()],
             referenced_table: "users".to_string(),
+            referenced_schema: None,
             referenced_columns: vec!["id".to_string()],
             on_delete: ReferentialAction::Cascade,
             on_update: ReferentialAction::Restrict,
@@ -1125,10 +1210,10 @@ mod tests {
 
         assert_eq!(analyses.len(), 2);
 
-        let posts_analysis = analyses.iter().find(|a| a.table_name == "posts").unwrap();
+        let posts_analysis = analyses.iter().find(|a| a.table_name == "posts").expect("posts analysis");
         assert!(posts_analysis.depends_on.contains(&"users".to_string()));
 
-        let users_analysis = analyses.iter().find(|a| a.table_name == "users").unwrap();
+        let users_analysis = analyses.iter().find(|a| a.table_name == "users").expect("users analysis");
         assert!(users_analysis.depended_by.contains(&"posts".to_string()));
     }
 }
@@ -2,6 +2,21 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::core::domain::entity::*;
+use crate::core::domain::indexes::{EffortLevel, IssueSeverity};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub name: String,
+    pub table_name: String,
+    pub timing: TriggerTiming,
+    pub events: Vec<TriggerEvent>,
+    /// The columns of `UPDATE OF`; empty when an update of any column fires the trigger
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub update_columns: Vec<String>,
+    pub scope: TriggerScope,
+    pub definition: String,
+    pub metadata: TriggerMetadata,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TriggerAnalysis {
@@ -193,7 +208,7 @@ pub enum TriggerRecommendationType {
     PerformanceTuning,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct TriggerMetadata {
     pub description: Option<String>,
     pub created_by: Option<String>,
@@ -354,7 +369,7 @@ impl TriggerAnalysisService {
         logic
     }
 
-    fn extract_integrity_rules(trigger: &Trigger, table: &Table) -> Vec<DataIntegrityRule> {
+    fn extract_integrity_rules(trigger: &Trigger, _table: &Table) -> Vec<DataIntegrityRule> {
         let mut rules = Vec::new();
         let definition = trigger.definition.to_lowercase();
 
@@ -385,7 +400,8 @@ impl TriggerAnalysisService {
         let definition = trigger.definition.to_lowercase();
 
         // Estimate execution cost
-        let execution_cost = if definition.contains("select") && definition.contains("join") {
+        let row_by_row = definition.contains("cursor") || definition.contains("while");
+        let execution_cost = if row_by_row || (definition.contains("select") && definition.contains("join")) {
             PerformanceCost::High
         } else if definition.contains("select") || definition.contains("update") {
             PerformanceCost::Medium
@@ -526,7 +542,7 @@ impl TriggerAnalysisService {
     }
 
     fn generate_recommendations(
-        trigger: &Trigger,
+        _trigger: &Trigger,
         issues: &[TriggerIssue],
         performance_impact: &TriggerPerformanceImpact,
     ) -> Vec<TriggerRecommendation> {
@@ -606,7 +622,7 @@ impl TriggerAnalysisService {
         recommendations
     }
 
-    fn find_cascading_triggers(trigger: &Trigger, table: &Table, database: &Database) -> Vec<String> {
+    fn find_cascading_triggers(trigger: &Trigger, _table: &Table, database: &Database) -> Vec<String> {
         let mut cascading = Vec::new();
 
         // Check if trigger modifies other tables that have triggers
@@ -623,7 +639,7 @@ impl TriggerAnalysisService {
         cascading
     }
 
-    fn generate_optimization_opportunities(trigger: &Trigger, definition: String) -> Vec<PerformanceOptimization> {
+    fn generate_optimization_opportunities(_trigger: &Trigger, definition: String) -> Vec<PerformanceOptimization> {
         let mut opportunities = Vec::new();
 
         if definition.contains("select * from") {
@@ -647,7 +663,7 @@ impl TriggerAnalysisService {
         opportunities
     }
 
-    fn is_recursive_trigger(trigger: &Trigger, table: &Table, database: &Database) -> bool {
+    fn is_recursive_trigger(trigger: &Trigger, table: &Table, _database: &Database) -> bool {
         let definition = trigger.definition.to_lowercase();
 
         // Check if trigger references its own table in a way that could cause recursion
@@ -655,9 +671,9 @@ impl TriggerAnalysisService {
         (definition.contains("insert") || definition.contains("update") || definition.contains("delete"))
     }
 
-    fn detect_cascading_chains(graph: &TriggerDependencyGraph) -> Vec<Vec<String>> {
+    fn detect_cascading_chains(_graph: &TriggerDependencyGraph) -> Vec<Vec<String>> {
         // Simple chain detection - in practice, would use proper graph algorithms
-        let mut chains = Vec::new();
+        let chains = Vec::new();
 
         // This is a placeholder implementation
         // A real implementation would use DFS or topological analysis
@@ -939,6 +955,7 @@ mod tests {
             table_name: "users".to_string(),
             timing: TriggerTiming::After,
             events: vec![TriggerEvent::Update],
+            update_columns: Vec::new(),
             scope: TriggerScope::Row,
             definition: r#"
                 UPDATE users
@@ -962,6 +979,7 @@ mod tests {
             table_name: "orders".to_string(),
             timing: TriggerTiming::After,
             events: vec![TriggerEvent::Insert],
+            update_columns: Vec::new(),
             scope: TriggerScope::Row,
             definition: r#"
                 DECLARE @cursor CURSOR
@@ -998,6 +1016,7 @@ mod tests {
             table_name: "users".to_string(),
             timing: TriggerTiming::Before,
             events: vec![TriggerEvent::Insert],
+            update_columns: Vec::new(),
             scope: TriggerScope::Row,
             definition: r#"
                 EXEC('SELECT * FROM ' + @table_name)
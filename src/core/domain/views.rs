@@ -1,8 +1,15 @@
 use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
+use lazy_static::lazy_static;
+use regex::Regex;
 
 use crate::core::domain::entity::*;
-use crate::core::ast::*;
+use crate::core::domain::indexes::{EffortLevel, IssueSeverity};
+
+lazy_static! {
+    static ref IDENTIFIER_REGEX: Regex =
+        Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\b").expect("identifier regex is valid");
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ViewAnalysis {
@@ -179,7 +186,7 @@ impl ViewAnalysisService {
 
         ViewAnalysis {
             view_name: view.name.clone(),
-            schema_name: view.schema_name.clone(),
+            schema_name: view.schema.clone(),
             definition: view.definition.clone(),
             is_materialized: matches!(view.view_type, ViewType::Materialized),
             dependencies,
@@ -189,13 +196,13 @@ impl ViewAnalysisService {
             recommendations,
             metadata: ViewMetadata {
                 description: view.metadata.description.clone(),
-                created_by: view.metadata.created_by.clone(),
+                created_by: view.metadata.owner.clone(),
                 created_at: view.metadata.created_at.clone(),
-                last_modified: view.metadata.last_modified.clone(),
+                last_modified: None,
                 usage_count: view.metadata.usage_count,
-                security_classification: view.metadata.security_classification.clone(),
-                business_owner: view.metadata.business_owner.clone(),
-                technical_owner: view.metadata.technical_owner.clone(),
+                security_classification: None,
+                business_owner: view.metadata.owner.clone(),
+                technical_owner: None,
                 tags: view.metadata.tags.clone(),
                 custom_properties: view.metadata.custom_properties.clone(),
             },
@@ -258,7 +265,7 @@ impl ViewAnalysisService {
 
         let mut tables_referenced = Vec::new();
         let mut views_referenced = Vec::new();
-        let mut functions_used = Vec::new();
+        let functions_used = Vec::new();
 
         // Simple pattern matching for table/view references
         // In a real implementation, this would use proper SQL parsing
@@ -296,7 +303,7 @@ impl ViewAnalysisService {
 
     fn calculate_complexity(view: &View) -> ViewComplexity {
         let definition = view.definition.to_lowercase();
-        let lines_of_code = definition.lines().count();
+        let lines_of_code = definition.lines().filter(|line| !line.trim().is_empty()).count();
 
         // Count various complexity indicators
         let nested_subqueries = definition.matches("select").count().saturating_sub(1);
@@ -373,7 +380,7 @@ impl ViewAnalysisService {
         }
     }
 
-    fn analyze_issues(view: &View, dependencies: &ViewDependencies, complexity: &ViewComplexity, database: &Database) -> Vec<ViewIssue> {
+    fn analyze_issues(view: &View, dependencies: &ViewDependencies, complexity: &ViewComplexity, _database: &Database) -> Vec<ViewIssue> {
         let mut issues = Vec::new();
 
         // Check for high complexity
@@ -382,7 +389,7 @@ impl ViewAnalysisService {
                 issue_type: ViewIssueType::MaintainabilityIssues,
                 severity: IssueSeverity::Medium,
                 description: format!("View '{}' has high complexity score ({:.2})", view.name, complexity.complexity_score),
-                impact: "Difficult to maintain and optimize",
+                impact: "Difficult to maintain and optimize".to_string(),
                 suggested_fix: Some("Consider breaking down into smaller, simpler views".to_string()),
             });
         }
@@ -393,7 +400,7 @@ impl ViewAnalysisService {
                 issue_type: ViewIssueType::CircularDependencies,
                 severity: IssueSeverity::High,
                 description: format!("View '{}' has deep dependency chain (depth: {})", view.name, dependencies.dependency_depth),
-                impact: "Performance degradation and maintenance complexity",
+                impact: "Performance degradation and maintenance complexity".to_string(),
                 suggested_fix: Some("Consider flattening the view hierarchy".to_string()),
             });
         }
@@ -404,18 +411,18 @@ impl ViewAnalysisService {
                 issue_type: ViewIssueType::MissingIndexes,
                 severity: IssueSeverity::Medium,
                 description: format!("Tables referenced by view '{}' may benefit from additional indexes", view.name),
-                impact: "Poor query performance",
+                impact: "Poor query performance".to_string(),
                 suggested_fix: Some("Analyze query execution plans and add appropriate indexes".to_string()),
             });
         }
 
         // Check for materialized view refresh concerns
-        if matches!(view.view_type, ViewType::Materialized) && dependencies.views_referenced.len() > 0 {
+        if matches!(view.view_type, ViewType::Materialized) && !dependencies.views_referenced.is_empty() {
             issues.push(ViewIssue {
                 issue_type: ViewIssueType::DataFreshness,
                 severity: IssueSeverity::Medium,
                 description: "Materialized view references other views, which may cause refresh issues".to_string(),
-                impact: "Data may become stale or refresh may fail",
+                impact: "Data may become stale or refresh may fail".to_string(),
                 suggested_fix: Some("Consider restructuring to reference base tables directly".to_string()),
             });
         }
@@ -425,7 +432,7 @@ impl ViewAnalysisService {
 
     fn generate_recommendations(
         view: &View,
-        dependencies: &ViewDependencies,
+        _dependencies: &ViewDependencies,
         complexity: &ViewComplexity,
         performance: &ViewPerformanceCharacteristics,
     ) -> Vec<ViewRecommendation> {
@@ -491,6 +498,7 @@ impl ViewAnalysisService {
     }
 
     fn count_aggregate_functions(definition: &str) -> usize {
+        let definition = definition.to_lowercase();
         let aggregates = ["count(", "sum(", "avg(", "min(", "max(", "stddev(", "variance("];
         aggregates.iter()
             .map(|agg| definition.matches(agg).count())
@@ -523,10 +531,7 @@ impl ViewAnalysisService {
 
     fn extract_columns_from_definition(definition: &str) -> Vec<String> {
         // Simple column extraction - in practice, this would use AST parsing
-        use regex::Regex;
-        let column_regex = Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\b").unwrap();
-
-        column_regex.captures_iter(definition)
+        IDENTIFIER_REGEX.captures_iter(definition)
             .filter_map(|cap| cap.get(1))
             .map(|m| m.as_str().to_string())
             .filter(|col| !Self::is_sql_keyword(col))
@@ -585,9 +590,9 @@ impl ViewAnalysisService {
         recommendations
     }
 
-    fn detect_circular_dependencies(graph: &ViewDependencyGraph) -> Vec<Vec<String>> {
+    fn detect_circular_dependencies(_graph: &ViewDependencyGraph) -> Vec<Vec<String>> {
         // Simple cycle detection - in practice, this would use proper graph algorithms
-        let mut cycles = Vec::new();
+        let cycles = Vec::new();
 
         // This is a placeholder implementation
         // A real implementation would use DFS or topological sort
@@ -771,7 +776,7 @@ mod tests {
     fn test_view_complexity_calculation() {
         let view = View {
             name: "complex_view".to_string(),
-            schema_name: Some("public".to_string()),
+            schema: Some("public".to_string()),
             definition: r#"
                 SELECT u.name, COUNT(o.id) as order_count, AVG(o.total) as avg_order
                 FROM users u
@@ -783,13 +788,15 @@ mod tests {
                 GROUP BY u.id, u.name
                 HAVING COUNT(o.id) > 5
             "#.to_string(),
+            columns: Vec::new(),
+            dependencies: Vec::new(),
             view_type: ViewType::Regular,
-            metadata: ViewMetadata::default(),
+            metadata: crate::core::domain::entity::ViewMetadata::default(),
         };
 
         let complexity = ViewAnalysisService::calculate_complexity(&view);
 
-        assert_eq!(complexity.lines_of_code, 10);
+        assert_eq!(complexity.lines_of_code, 9);
         assert_eq!(complexity.join_count, 1);
         assert!(complexity.complexity_score > 0.0);
         assert!(complexity.maintainability_index > 0.0);
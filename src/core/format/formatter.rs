@@ -0,0 +1,789 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::ast::{DdlStatement, Statement};
+use crate::core::parse::{spaced, Lexer, LexerError, Parser, SqlDialect, Token, TokenType};
+
+/// Identifiers that read as keywords even though the lexer has no token for them.
+const EXTRA_KEYWORDS: &[&str] = &[
+    "CASCADE", "RESTRICT", "CURRENT_TIMESTAMP", "CURRENT_DATE", "CURRENT_TIME", "LOCALTIMESTAMP",
+    "UNSIGNED", "ZEROFILL", "ESCAPE", "MATERIALIZED", "OVER", "PARTITION", "NULLS", "UNBOUNDED",
+    "PRECEDING", "FOLLOWING", "RETURNING", "CONFLICT", "DO", "NOTHING", "DUPLICATE",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+    Preserve,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatStyle {
+    pub keyword_case: KeywordCase,
+    pub indent_width: usize,
+    /// Lines up the types and constraints of the columns in a CREATE TABLE.
+    pub align_column_types: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("failed to tokenize input: {0}")]
+    Lex(#[from] LexerError),
+}
+
+/// Re-emits SQL in a consistent layout. Statements the parser does not understand are kept as
+/// written, and comments stay with the statement (or column) they were written next to.
+pub struct Formatter {
+    style: FormatStyle,
+    dialect: SqlDialect,
+}
+
+#[derive(Default)]
+struct Chunk<'a> {
+    leading: Vec<&'a Token>,
+    tokens: Vec<&'a Token>,
+    trailing: Vec<&'a Token>,
+}
+
+#[derive(Default)]
+struct TableItem<'a> {
+    leading: Vec<&'a Token>,
+    code: Vec<&'a Token>,
+    trailing: Vec<&'a Token>,
+}
+
+impl Default for FormatStyle {
+    fn default() -> Self {
+        Self {
+            keyword_case: KeywordCase::Upper,
+            indent_width: 4,
+            align_column_types: true,
+        }
+    }
+}
+
+impl FromStr for KeywordCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "upper" => Ok(KeywordCase::Upper),
+            "lower" => Ok(KeywordCase::Lower),
+            "preserve" => Ok(KeywordCase::Preserve),
+            _ => Err(format!("unknown keyword case '{}', expected upper, lower or preserve", s)),
+        }
+    }
+}
+
+impl Formatter {
+    pub fn new(style: FormatStyle) -> Self {
+        Self {
+            style,
+            dialect: SqlDialect::Standard,
+        }
+    }
+
+    /// Lexes scripts the way `dialect` does, so bracketed names, batch separators and the like
+    /// are formatted rather than left as written.
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn format(&self, sql: &str) -> Result<String, FormatError> {
+        let tokens = Lexer::for_dialect(sql, &self.dialect).with_comments().tokenize()?;
+        let source: Vec<char> = sql.chars().collect();
+
+        let mut formatted = String::new();
+        for chunk in split_statements(&tokens) {
+            if !formatted.is_empty() {
+                // A batch separator after a terminated statement stays right under it
+                let lone_separator =
+                    chunk.leading.is_empty() && matches!(chunk.tokens[..], [token] if is_batch_separator(token));
+                formatted.push_str(if lone_separator { "\n" } else { "\n\n" });
+            }
+            formatted.push_str(&self.format_chunk(&chunk, &source));
+        }
+        if !formatted.is_empty() {
+            formatted.push('\n');
+        }
+        Ok(formatted)
+    }
+
+    pub fn is_formatted(&self, sql: &str) -> Result<bool, FormatError> {
+        Ok(self.format(sql)? == sql)
+    }
+
+    fn format_chunk(&self, chunk: &Chunk, source: &[char]) -> String {
+        let mut lines = Vec::new();
+        let mut previous_end: Option<usize> = None;
+
+        // A blank line between leading comments, or before the statement, is kept
+        for comment in &chunk.leading {
+            if previous_end.is_some_and(|end| comment.position.line > end + 1) {
+                lines.push(String::new());
+            }
+            lines.push(comment.lexeme.clone());
+            previous_end = Some(end_line(comment));
+        }
+
+        if let Some(first) = chunk.tokens.first() {
+            if previous_end.is_some_and(|end| first.position.line > end + 1) {
+                lines.push(String::new());
+            }
+            let (code, separator) = match chunk.tokens.split_last() {
+                Some((last, code)) if is_batch_separator(last) => (code, Some(last)),
+                _ => (&chunk.tokens[..], None),
+            };
+            let mut statement = if code.is_empty() { String::new() } else { self.format_statement(code, source) };
+            // `GO` and `/` go on a line of their own
+            if let Some(separator) = separator {
+                if !statement.is_empty() {
+                    statement.push('\n');
+                }
+                statement.push_str(&self.render_token(separator, false));
+            }
+            for comment in &chunk.trailing {
+                statement.push(' ');
+                statement.push_str(&comment.lexeme);
+            }
+            lines.push(statement);
+        }
+
+        lines.join("\n")
+    }
+
+    fn format_statement(&self, tokens: &[&Token], source: &[char]) -> String {
+        let code: Vec<Token> = tokens
+            .iter()
+            .filter(|token| !is_comment(token))
+            .map(|token| (*token).clone())
+            .collect();
+
+        match Parser::new(code).parse() {
+            Ok(statements) if statements.len() == 1 => match &statements[0] {
+                Statement::Ddl(DdlStatement::CreateTable(_)) => self.format_create_table(tokens),
+                _ => self.format_generic(tokens),
+            },
+            _ => verbatim(tokens, source),
+        }
+    }
+
+    fn format_create_table(&self, tokens: &[&Token]) -> String {
+        let open = tokens
+            .iter()
+            .position(|token| token.token_type == TokenType::LeftParen)
+            .expect("a parsed CREATE TABLE has a column list");
+        let close = matching_paren(tokens, open);
+
+        let mut header = Printer::new(self, 1);
+        for token in &tokens[..open] {
+            header.print(token);
+        }
+        header.force_space();
+        header.print(tokens[open]);
+
+        let items = split_table_items(&tokens[open + 1..close], tokens[open]);
+        let indent = " ".repeat(self.style.indent_width);
+        let mut lines = vec![header.finish()];
+
+        let columns: Vec<Option<(String, String, String)>> =
+            items.iter().map(|item| self.split_column(&item.code)).collect();
+        let name_width = columns.iter().flatten().map(|(name, _, _)| name.chars().count()).max().unwrap_or(0);
+        let type_width = columns
+            .iter()
+            .flatten()
+            .filter(|(_, _, rest)| !rest.is_empty())
+            .map(|(_, data_type, _)| data_type.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (index, (item, column)) in items.iter().zip(&columns).enumerate() {
+            for comment in &item.leading {
+                lines.push(format!("{}{}", indent, comment.lexeme));
+            }
+            if item.code.is_empty() {
+                // Comments after the last item
+                for comment in &item.trailing {
+                    lines.push(format!("{}{}", indent, comment.lexeme));
+                }
+                continue;
+            }
+
+            let mut line = match column {
+                Some((name, data_type, rest)) if self.style.align_column_types => {
+                    if rest.is_empty() {
+                        format!("{:<name_width$} {}", name, data_type)
+                    } else {
+                        format!("{:<name_width$} {:<type_width$} {}", name, data_type, rest)
+                    }
+                }
+                Some((name, data_type, rest)) => format!("{} {} {}", name, data_type, rest),
+                None => self.render_inline(&item.code, false),
+            };
+            line = line.trim_end().to_string();
+
+            if items[index + 1..].iter().any(|next| !next.code.is_empty()) {
+                line.push(',');
+            }
+            for comment in &item.trailing {
+                line.push(' ');
+                line.push_str(&comment.lexeme);
+            }
+            lines.push(format!("{}{}", indent, line));
+        }
+
+        let mut tail = Printer::new(self, 1);
+        for token in &tokens[close..] {
+            tail.print(token);
+        }
+        lines.push(tail.finish());
+
+        lines.join("\n")
+    }
+
+    /// Splits a column definition into its name, type and remaining constraints; table
+    /// constraints, which start with a keyword, yield `None`.
+    fn split_column(&self, code: &[&Token]) -> Option<(String, String, String)> {
+        code.first().filter(|token| !token.is_keyword())?;
+        // `FULLTEXT KEY ...`, `SPATIAL KEY ...` and `EXCLUDE USING ...` are constraints, not columns
+        if let Some(TokenType::Identifier(word)) = code.first().map(|token| &token.token_type)
+            && ["FULLTEXT", "SPATIAL", "EXCLUDE"].contains(&word.to_uppercase().as_str())
+            && matches!(
+                code.get(1).map(|token| &token.token_type),
+                Some(TokenType::Key | TokenType::Index | TokenType::LeftParen | TokenType::Using)
+            )
+        {
+            return None;
+        }
+        let type_length = data_type_length(&code[1..]);
+
+        Some((
+            self.render_inline(&code[..1], false),
+            self.render_inline(&code[1..1 + type_length], true),
+            self.render_inline(&code[1 + type_length..], false),
+        ))
+    }
+
+    fn format_generic(&self, tokens: &[&Token]) -> String {
+        let code: Vec<&Token> = tokens.iter().copied().filter(|token| !is_comment(token)).collect();
+        let (list_starts, list_breaks) = list_layout(&code);
+        let statement_kind = code.first().map(|token| &token.token_type);
+
+        let mut printer = Printer::new(self, 1);
+        let mut depth = 0usize;
+        let mut index = 0usize;
+        let mut in_condition = false;
+        let mut pending_between = false;
+
+        for token in tokens {
+            if is_comment(token) {
+                printer.comment(token);
+                continue;
+            }
+
+            if token.token_type == TokenType::RightParen {
+                depth = depth.saturating_sub(1);
+            }
+
+            if depth == 0 && index > 0 {
+                if starts_clause(&code, index, statement_kind) {
+                    printer.newline(0);
+                    in_condition = matches!(
+                        token.token_type,
+                        TokenType::Where | TokenType::Having | TokenType::Join | TokenType::Inner
+                            | TokenType::Left | TokenType::Right | TokenType::Full | TokenType::Cross
+                    );
+                } else if matches!(token.token_type, TokenType::And | TokenType::Or)
+                    && in_condition
+                    && !pending_between
+                {
+                    printer.newline(1);
+                }
+            }
+            if list_starts.contains(&index) {
+                printer.newline(1);
+            }
+
+            match token.token_type {
+                TokenType::Between => pending_between = true,
+                TokenType::And => pending_between = false,
+                TokenType::LeftParen => depth += 1,
+                _ => {}
+            }
+
+            printer.print(token);
+
+            if list_breaks.contains(&index) {
+                printer.newline(1);
+            }
+            index += 1;
+        }
+
+        printer.finish()
+    }
+
+    fn render_inline(&self, tokens: &[&Token], force_keyword: bool) -> String {
+        let mut text = String::new();
+        for (index, token) in tokens.iter().enumerate() {
+            if index > 0 {
+                let before = index.checked_sub(2).map(|i| &tokens[i].token_type);
+                if spaced(before, &tokens[index - 1].token_type, &token.token_type) {
+                    text.push(' ');
+                }
+            }
+            text.push_str(&self.render_token(token, force_keyword));
+        }
+        text
+    }
+
+    fn render_token(&self, token: &Token, force_keyword: bool) -> String {
+        let keyword = token.is_keyword()
+            || matches!(&token.token_type, TokenType::Identifier(name)
+                if force_keyword || EXTRA_KEYWORDS.contains(&name.to_uppercase().as_str()));
+
+        if !keyword {
+            return token.to_sql();
+        }
+
+        match self.style.keyword_case {
+            KeywordCase::Upper => token.lexeme.to_uppercase(),
+            KeywordCase::Lower => token.lexeme.to_lowercase(),
+            KeywordCase::Preserve => token.lexeme.clone(),
+        }
+    }
+}
+
+/// Lays tokens out line by line, keeping comments next to the tokens they followed.
+struct Printer<'a> {
+    formatter: &'a Formatter,
+    lines: Vec<String>,
+    line: String,
+    line_indent: usize,
+    continuation: usize,
+    previous: Option<&'a Token>,
+    before: Option<&'a Token>,
+    last_line: Option<usize>,
+    pending_comments: Vec<&'a Token>,
+    break_pending: bool,
+    space_pending: bool,
+}
+
+impl<'a> Printer<'a> {
+    fn new(formatter: &'a Formatter, continuation: usize) -> Self {
+        Self {
+            formatter,
+            lines: Vec::new(),
+            line: String::new(),
+            line_indent: 0,
+            continuation,
+            previous: None,
+            before: None,
+            last_line: None,
+            pending_comments: Vec::new(),
+            break_pending: false,
+            space_pending: false,
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.line.trim().is_empty()
+    }
+
+    fn newline(&mut self, indent: usize) {
+        if !self.at_line_start() {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+        self.line = " ".repeat(indent * self.formatter.style.indent_width);
+        self.line_indent = indent;
+        self.break_pending = false;
+    }
+
+    fn force_space(&mut self) {
+        self.space_pending = true;
+    }
+
+    fn print(&mut self, token: &'a Token) {
+        if is_comment(token) {
+            self.comment(token);
+            return;
+        }
+
+        if !self.pending_comments.is_empty() {
+            let indent = if self.at_line_start() { self.line_indent } else { self.continuation };
+            let prefix = " ".repeat(indent * self.formatter.style.indent_width);
+            if !self.at_line_start() {
+                self.lines.push(std::mem::take(&mut self.line));
+            }
+            for comment in std::mem::take(&mut self.pending_comments) {
+                self.lines.push(format!("{}{}", prefix, comment.lexeme));
+            }
+            self.line = prefix;
+            self.line_indent = indent;
+            self.break_pending = false;
+        }
+
+        if self.break_pending {
+            self.newline(self.continuation);
+        }
+
+        let space = self.space_pending
+            || self.previous.is_some_and(|previous| {
+                spaced(self.before.map(|before| &before.token_type), &previous.token_type, &token.token_type)
+            });
+        if space && !self.at_line_start() {
+            self.line.push(' ');
+        }
+        self.space_pending = false;
+
+        self.line.push_str(&self.formatter.render_token(token, false));
+        self.before = self.previous;
+        self.previous = Some(token);
+        self.last_line = Some(end_line(token));
+    }
+
+    fn comment(&mut self, comment: &'a Token) {
+        let trailing = self.last_line == Some(comment.position.line) && !self.at_line_start();
+
+        if trailing {
+            self.line.push(' ');
+            self.line.push_str(&comment.lexeme);
+            self.last_line = Some(end_line(comment));
+            // Nothing may follow a line comment on the same line
+            if comment.lexeme.starts_with("--") {
+                self.break_pending = true;
+            }
+        } else {
+            self.pending_comments.push(comment);
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if !self.pending_comments.is_empty() {
+            let prefix = " ".repeat(self.line_indent * self.formatter.style.indent_width);
+            if !self.at_line_start() {
+                self.lines.push(std::mem::take(&mut self.line));
+            }
+            for comment in std::mem::take(&mut self.pending_comments) {
+                self.lines.push(format!("{}{}", prefix, comment.lexeme));
+            }
+        }
+        if !self.at_line_start() {
+            self.lines.push(self.line);
+        }
+        self.lines.join("\n")
+    }
+}
+
+fn is_comment(token: &Token) -> bool {
+    matches!(token.token_type, TokenType::Comment(_))
+}
+
+/// Whether `token` is a `GO` or `/` batch separator rather than a semicolon.
+fn is_batch_separator(token: &Token) -> bool {
+    token.token_type == TokenType::Semicolon && token.lexeme != ";"
+}
+
+fn end_line(token: &Token) -> usize {
+    token.position.line + token.lexeme.matches('\n').count()
+}
+
+/// Groups tokens into statements; comments before a statement lead it, and comments on the
+/// line of its closing semicolon trail it.
+fn split_statements(tokens: &[Token]) -> Vec<Chunk<'_>> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut current = Chunk::default();
+    let mut depth = 0usize;
+    let mut end_of_previous: Option<usize> = None;
+
+    for token in tokens {
+        match &token.token_type {
+            TokenType::Comment(_) if current.tokens.is_empty() => match (end_of_previous, chunks.last_mut()) {
+                (Some(line), Some(previous)) if token.position.line == line && current.leading.is_empty() => {
+                    previous.trailing.push(token);
+                }
+                _ => current.leading.push(token),
+            },
+            TokenType::Semicolon if depth == 0 => {
+                current.tokens.push(token);
+                end_of_previous = Some(token.position.line);
+                chunks.push(std::mem::take(&mut current));
+            }
+            token_type => {
+                match token_type {
+                    TokenType::LeftParen => depth += 1,
+                    TokenType::RightParen => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                current.tokens.push(token);
+            }
+        }
+    }
+
+    if !current.tokens.is_empty() || !current.leading.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Splits the body of a CREATE TABLE at its top-level commas, attaching each comment to the
+/// item on whose line it sits, or to the item it precedes.
+fn split_table_items<'a>(body: &[&'a Token], open: &Token) -> Vec<TableItem<'a>> {
+    let mut items = vec![TableItem::default()];
+    let mut dangling: Vec<&'a Token> = Vec::new();
+    let mut depth = 0usize;
+    let mut last_code_line = end_line(open);
+
+    for token in body {
+        let count = items.len();
+        match token.token_type {
+            TokenType::Comment(_) => {
+                let item = &mut items[count - 1];
+                if token.position.line == last_code_line {
+                    if item.code.is_empty() && count > 1 {
+                        items[count - 2].trailing.push(token);
+                    } else if item.code.is_empty() {
+                        item.leading.push(token);
+                    } else {
+                        item.trailing.push(token);
+                    }
+                } else if item.code.is_empty() {
+                    item.leading.push(token);
+                } else {
+                    dangling.push(token);
+                }
+            }
+            TokenType::Comma if depth == 0 => {
+                last_code_line = token.position.line;
+                items.push(TableItem {
+                    leading: std::mem::take(&mut dangling),
+                    ..TableItem::default()
+                });
+            }
+            _ => {
+                match token.token_type {
+                    TokenType::LeftParen => depth += 1,
+                    TokenType::RightParen => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                let item = &mut items[count - 1];
+                item.leading.append(&mut dangling);
+                item.code.push(token);
+                last_code_line = end_line(token);
+            }
+        }
+    }
+
+    if !dangling.is_empty() {
+        items.push(TableItem {
+            trailing: dangling,
+            ..TableItem::default()
+        });
+    }
+    items
+}
+
+fn matching_paren(tokens: &[&Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        match token.token_type {
+            TokenType::LeftParen => depth += 1,
+            TokenType::RightParen => {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Counts the tokens making up a column's data type, mirroring `Parser::parse_column_data_type`.
+fn data_type_length(tokens: &[&Token]) -> usize {
+    let identifier_is = |index: usize, words: &[&str]| {
+        matches!(tokens.get(index).map(|token| &token.token_type),
+            Some(TokenType::Identifier(name)) if words.contains(&name.to_uppercase().as_str()))
+    };
+
+    if tokens.is_empty() {
+        return 0;
+    }
+    let mut length = 1;
+
+    while identifier_is(length, &["PRECISION", "VARYING"]) {
+        length += 1;
+    }
+    if tokens.get(length).is_some_and(|token| token.token_type == TokenType::LeftParen) {
+        let mut depth = 0usize;
+        while let Some(token) = tokens.get(length) {
+            length += 1;
+            match token.token_type {
+                TokenType::LeftParen => depth += 1,
+                TokenType::RightParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if tokens.get(length).is_some_and(|token| token.token_type == TokenType::With)
+        || identifier_is(length, &["WITHOUT"])
+    {
+        length = (length + 3).min(tokens.len());
+    }
+    while identifier_is(length, &["UNSIGNED", "SIGNED", "ZEROFILL"]) {
+        length += 1;
+    }
+    while tokens.get(length).is_some_and(|token| token.token_type == TokenType::LeftBracket) {
+        length += 1;
+        while tokens.get(length).is_some_and(|token| token.token_type != TokenType::RightBracket) {
+            length += 1;
+        }
+        length = (length + 1).min(tokens.len());
+    }
+
+    length
+}
+
+fn starts_clause(code: &[&Token], index: usize, statement_kind: Option<&TokenType>) -> bool {
+    let previous = code.get(index.wrapping_sub(1)).map(|token| &token.token_type);
+    let next = code.get(index + 1).map(|token| &token.token_type);
+
+    match &code[index].token_type {
+        TokenType::From => previous != Some(&TokenType::Delete),
+        TokenType::Where
+        | TokenType::Having
+        | TokenType::Limit
+        | TokenType::Offset
+        | TokenType::Union
+        | TokenType::Intersect
+        | TokenType::Except
+        | TokenType::Select => true,
+        // Not MySQL's `VALUES(col)` function inside an upsert
+        TokenType::Values => matches!(
+            previous,
+            None | Some(TokenType::RightParen | TokenType::Identifier(_) | TokenType::QuotedIdentifier(_))
+        ),
+        TokenType::Using => statement_kind == Some(&TokenType::Delete) && next != Some(&TokenType::LeftParen),
+        TokenType::On => matches!(next, Some(TokenType::Identifier(word))
+            if word.eq_ignore_ascii_case("CONFLICT") || word.eq_ignore_ascii_case("DUPLICATE")),
+        TokenType::Group | TokenType::Order => next == Some(&TokenType::By),
+        TokenType::Set => statement_kind == Some(&TokenType::Update),
+        TokenType::Join
+        | TokenType::Inner
+        | TokenType::Left
+        | TokenType::Right
+        | TokenType::Full
+        | TokenType::Cross => {
+            next != Some(&TokenType::LeftParen)
+                && !matches!(
+                    previous,
+                    Some(
+                        TokenType::Inner
+                            | TokenType::Left
+                            | TokenType::Right
+                            | TokenType::Full
+                            | TokenType::Cross
+                            | TokenType::Outer
+                    )
+                )
+        }
+        TokenType::Identifier(name) => name.eq_ignore_ascii_case("RETURNING"),
+        _ => false,
+    }
+}
+
+/// Finds the select, VALUES and SET lists with more than one item; those are laid out one item
+/// per line. Returns the indices that start such a list and the commas that separate items.
+fn list_layout(code: &[&Token]) -> (HashSet<usize>, HashSet<usize>) {
+    let mut starts = HashSet::new();
+    let mut breaks = HashSet::new();
+    let statement_kind = code.first().map(|token| &token.token_type);
+    let mut depth = 0usize;
+
+    for (index, token) in code.iter().enumerate() {
+        match token.token_type {
+            TokenType::LeftParen => depth += 1,
+            TokenType::RightParen => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth > 0 {
+            continue;
+        }
+
+        let opens_list = match token.token_type {
+            TokenType::Select | TokenType::Values => true,
+            TokenType::Set => statement_kind == Some(&TokenType::Update),
+            _ => false,
+        };
+        if !opens_list {
+            continue;
+        }
+
+        let mut first = index + 1;
+        if code.get(first).is_some_and(|token| matches!(token.token_type, TokenType::Distinct | TokenType::All)) {
+            first += 1;
+        }
+
+        let mut commas = Vec::new();
+        let mut inner_depth = 0usize;
+        for (offset, item) in code.iter().enumerate().skip(first) {
+            match item.token_type {
+                TokenType::LeftParen => inner_depth += 1,
+                TokenType::RightParen => inner_depth = inner_depth.saturating_sub(1),
+                TokenType::Comma if inner_depth == 0 => commas.push(offset),
+                _ if inner_depth == 0 && offset > first && starts_clause(code, offset, statement_kind) => break,
+                TokenType::Semicolon => break,
+                _ => {}
+            }
+        }
+
+        if !commas.is_empty() {
+            starts.insert(first);
+            breaks.extend(commas);
+        }
+    }
+
+    (starts, breaks)
+}
+
+/// The statement exactly as written in the source.
+fn verbatim(tokens: &[&Token], source: &[char]) -> String {
+    let start = tokens[0].position.index;
+    let end = source_end(tokens[tokens.len() - 1], source);
+    source[start..end].iter().collect()
+}
+
+fn source_end(token: &Token, source: &[char]) -> usize {
+    let start = token.position.index;
+    match source.get(start) {
+        Some(&quote @ ('\'' | '"' | '`')) => {
+            let mut index = start + 1;
+            while index < source.len() {
+                if source[index] == quote {
+                    // Doubled quotes escape themselves
+                    if quote != '`' && source.get(index + 1) == Some(&quote) {
+                        index += 2;
+                        continue;
+                    }
+                    return index + 1;
+                }
+                index += 1;
+            }
+            source.len()
+        }
+        _ => (start + token.lexeme.chars().count()).min(source.len()),
+    }
+}
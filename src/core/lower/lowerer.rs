@@ -0,0 +1,1226 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::ast::{
+    self, AlterTableAction, AlterTableStatement, ColumnConstraint, ColumnDefinition, CreateDomainStatement,
+    CreateIndexStatement, CreateRoutineStatement, CreateSequenceStatement, CreateTableStatement, CreateTriggerStatement,
+    CreateTypeStatement, CreateViewStatement, DdlStatement, IndexKind, KeyPart, RoutineKind, SchemaReference,
+    SessionStatement, Statement, TableConstraint, TypeDefinition,
+};
+use crate::core::domain::entity::{
+    CheckConstraint, Column, ConstraintMetadata, DataType, Database, ExclusionConstraint,
+    ExclusionElement, ForeignKey, ForeignKeyMetadata, GeneratedColumn, IdentitySpecification,
+    Index, IndexColumn, IndexMetadata, IndexType, NullsPosition, PrimaryKey, ReferentialAction,
+    RelationshipType, Sequence, SortOrder, Table, TypeAffinity, UniqueConstraint,
+    UserDefinedType, UserDefinedTypeKind, View, ViewMetadata, ViewType,
+};
+use crate::core::domain::field::FieldService;
+use crate::core::domain::procedures::{ParameterMode, Procedure, ProcedureMetadata, ProcedureParameter};
+use crate::core::domain::triggers::{Trigger, TriggerEvent, TriggerMetadata, TriggerScope, TriggerTiming};
+use crate::core::parse::{Lexer, LexerError, Parser, ParserError, SqlDialect};
+
+#[derive(Debug, Error)]
+pub enum LowerError {
+    #[error("failed to tokenize input: {0}")]
+    Lex(#[from] LexerError),
+    #[error("failed to parse input: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Parse(Vec<ParserError>),
+}
+
+/// A statement or clause that was skipped or only partly reflected in the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LowerWarning {
+    pub object: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LowerOutput {
+    pub database: Database,
+    pub warnings: Vec<LowerWarning>,
+}
+
+/// Builds the domain model ([`Database`]) from parsed DDL.
+pub struct Lowerer {
+    dialect: SqlDialect,
+    database_name: String,
+    field_service: FieldService,
+    default_schema: Option<String>,
+}
+
+impl Lowerer {
+    pub fn new(dialect: SqlDialect) -> Self {
+        let field_service = FieldService::new(dialect.clone());
+        let default_schema = dialect.config().default_schema;
+        Self { dialect, database_name: "database".to_string(), field_service, default_schema }
+    }
+
+    pub fn with_database_name(mut self, name: &str) -> Self {
+        self.database_name = name.to_string();
+        self
+    }
+
+    pub fn dialect(&self) -> &SqlDialect {
+        &self.dialect
+    }
+
+    pub fn lower(&self, sql: &str) -> Result<LowerOutput, LowerError> {
+        let tokens = Lexer::for_dialect(sql, &self.dialect).tokenize()?;
+        let statements = Parser::new(tokens).parse().map_err(LowerError::Parse)?;
+        Ok(self.lower_statements(&statements))
+    }
+
+    pub fn lower_statements(&self, statements: &[Statement]) -> LowerOutput {
+        let mut database = Database::new(&self.database_name);
+        let mut warnings = Vec::new();
+        let search_paths = self.search_paths(statements);
+        let scopes: Vec<Option<String>> = search_paths.iter().map(|path| path[0].clone()).collect();
+        let mut path_references = Vec::new();
+
+        // Types first, so columns declared before their type's statement still resolve
+        for (statement, scope) in statements.iter().zip(&scopes) {
+            let user_type = match statement {
+                Statement::Ddl(DdlStatement::CreateType(create_type)) => self.lower_type(create_type, scope, &mut warnings),
+                Statement::Ddl(DdlStatement::CreateDomain(domain)) => self.lower_domain(domain, scope, &mut warnings),
+                _ => continue,
+            };
+            database.schema_mut(user_type.schema.as_deref()).add_type(user_type);
+        }
+        let types: HashMap<String, UserDefinedType> = database
+            .get_all_types()
+            .into_iter()
+            .map(|user_type| (user_type.name.to_uppercase(), user_type.clone()))
+            .collect();
+
+        for (index, (statement, scope)) in statements.iter().zip(&scopes).enumerate() {
+            match statement {
+                Statement::Ddl(DdlStatement::CreateTable(table)) => {
+                    let constraints = table.constraints.iter();
+                    for columns in unqualified_foreign_keys(table.columns.iter(), constraints) {
+                        path_references.push(PathReference {
+                            schema: self.schema_of(&table.table_name, scope),
+                            table: table.table_name.object.clone(),
+                            columns,
+                            search_path: search_paths[index].clone(),
+                        });
+                    }
+                    let table = self.lower_table(table, scope, &types, &mut warnings);
+                    database.add_table(table);
+                }
+                Statement::Ddl(DdlStatement::CreateIndex(index)) => {
+                    self.lower_index(&mut database, index, scope, &mut warnings);
+                }
+                Statement::Ddl(DdlStatement::AlterTable(alter)) => {
+                    let added_columns = alter.actions.iter().filter_map(|action| match action {
+                        AlterTableAction::AddColumn { column } => Some(column),
+                        _ => None,
+                    });
+                    let added_constraints = alter.actions.iter().filter_map(|action| match action {
+                        AlterTableAction::AddConstraint { constraint, .. } => Some(constraint),
+                        _ => None,
+                    });
+                    for columns in unqualified_foreign_keys(added_columns, added_constraints) {
+                        path_references.push(PathReference {
+                            schema: self.schema_of(&alter.table_name, scope),
+                            table: alter.table_name.object.clone(),
+                            columns,
+                            search_path: search_paths[index].clone(),
+                        });
+                    }
+                    self.alter_table(&mut database, alter, scope, &types, &mut warnings);
+                }
+                // Dumps drop each table before recreating it; a drop therefore forgets what came before
+                Statement::Ddl(DdlStatement::DropTable(drop)) => {
+                    for name in &drop.table_names {
+                        let schema_name = self.schema_of(name, scope);
+                        if let Some(schema) = database.schemas.iter_mut().find(|schema| schema.name == schema_name) {
+                            schema.tables.retain(|table| table.name != name.object);
+                        }
+                    }
+                }
+                Statement::Ddl(DdlStatement::DropIndex(drop)) => {
+                    for table in database.schemas.iter_mut().flat_map(|schema| schema.tables.iter_mut()) {
+                        table.indexes.retain(|index| !drop.index_names.contains(&index.name));
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateSequence(sequence)) => {
+                    let sequence = self.lower_sequence(sequence, scope);
+                    database.schema_mut(sequence.schema.as_deref()).add_sequence(sequence);
+                }
+                Statement::Ddl(DdlStatement::CreateView(view)) => {
+                    let view = self.lower_view(view, scope);
+                    let schema = database.schema_mut(view.schema.as_deref());
+                    // `CREATE OR REPLACE VIEW` redefines it
+                    schema.views.retain(|existing| existing.name != view.name);
+                    schema.add_view(view);
+                }
+                Statement::Ddl(DdlStatement::CreateTrigger(trigger)) => {
+                    self.lower_trigger(&mut database, trigger, scope, &mut warnings);
+                }
+                Statement::Ddl(DdlStatement::CreateRoutine(routine)) => {
+                    let procedure = self.lower_routine(routine, scope);
+                    let schema = database.schema_mut(procedure.schema_name.as_deref());
+                    let routines = match routine.kind {
+                        RoutineKind::Function => &mut schema.functions,
+                        RoutineKind::Procedure => &mut schema.procedures,
+                    };
+                    // `CREATE OR REPLACE` redefines it
+                    routines.retain(|existing| existing.name != procedure.name);
+                    routines.push(procedure);
+                }
+                Statement::Ddl(DdlStatement::CreateSchema(create)) => {
+                    let name = self.normalize_schema(&create.schema_name);
+                    let schema = database.schema_mut(name.as_deref());
+                    if create.authorization.is_some() {
+                        schema.metadata.owner = create.authorization.clone();
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateType(_) | DdlStatement::CreateDomain(_)) => {}
+                Statement::Dml(_) | Statement::Session(_) => {}
+            }
+        }
+
+        resolve_references(&mut database, &path_references);
+        self.name_default_schema(&mut database);
+
+        LowerOutput { database, warnings }
+    }
+
+    /// The schema unqualified names refer to at each statement, as chosen by PostgreSQL's
+    /// `SET search_path` or MySQL's `USE`; `None` stands for the dialect's default schema. New
+    /// objects go there; see [`Lowerer::search_paths`] for where names are looked up.
+    pub(crate) fn schema_scopes<'a>(&self, statements: impl IntoIterator<Item = &'a Statement>) -> Vec<Option<String>> {
+        self.search_paths(statements).into_iter().map(|mut path| path.swap_remove(0)).collect()
+    }
+
+    /// The schemas unqualified names are looked up in at each statement, in order; never empty.
+    pub(crate) fn search_paths<'a>(&self, statements: impl IntoIterator<Item = &'a Statement>) -> Vec<Vec<Option<String>>> {
+        let mut current = vec![None];
+        statements
+            .into_iter()
+            .map(|statement| {
+                match statement {
+                    Statement::Session(SessionStatement::Set { variable, value })
+                        if variable.eq_ignore_ascii_case("search_path") || variable.eq_ignore_ascii_case("schema") =>
+                    {
+                        current = search_path_entries(value).iter().map(|name| self.normalize_schema(name)).collect();
+                        if current.is_empty() {
+                            current.push(None);
+                        }
+                    }
+                    Statement::Session(SessionStatement::Use { database }) if self.dialect == SqlDialect::MySQL => {
+                        current = vec![self.normalize_schema(database)];
+                    }
+                    _ => {}
+                }
+                current.clone()
+            })
+            .collect()
+    }
+
+    /// Names the dialect's default schema (`public`, `dbo`) as `None`, so that qualified and
+    /// unqualified names of the same table agree.
+    pub(crate) fn normalize_schema(&self, name: &str) -> Option<String> {
+        match &self.default_schema {
+            Some(default) if default.eq_ignore_ascii_case(name) => None,
+            _ => Some(name.to_string()),
+        }
+    }
+
+    /// The schema `reference` lives in: its own qualifier, or else the one in scope. A catalog
+    /// (SQL Server's database) is not modelled.
+    pub(crate) fn schema_of(&self, reference: &SchemaReference, scope: &Option<String>) -> Option<String> {
+        match &reference.schema {
+            Some(schema) => self.normalize_schema(schema),
+            None => scope.clone(),
+        }
+    }
+
+    /// Once the model has other schemas, the default one is shown under the dialect's own name
+    /// for it rather than left anonymous.
+    fn name_default_schema(&self, database: &mut Database) {
+        let Some(default) = &self.default_schema else {
+            return;
+        };
+        if database.schemas.iter().all(|schema| schema.name.is_none()) {
+            return;
+        }
+
+        for schema in &mut database.schemas {
+            if schema.name.is_none() {
+                schema.name = Some(default.clone());
+                for table in &mut schema.tables {
+                    table.schema = Some(default.clone());
+                }
+                for user_type in &mut schema.types {
+                    user_type.schema = Some(default.clone());
+                }
+                for sequence in &mut schema.sequences {
+                    sequence.schema = Some(default.clone());
+                }
+                for view in &mut schema.views {
+                    view.schema = Some(default.clone());
+                }
+            }
+            for table in &mut schema.tables {
+                for foreign_key in &mut table.foreign_keys {
+                    if foreign_key.referenced_schema.is_none() {
+                        foreign_key.referenced_schema = Some(default.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// A view's columns are left empty; only its name, query and the objects it reads are kept.
+    fn lower_view(&self, view: &CreateViewStatement, scope: &Option<String>) -> View {
+        let mut dependencies: Vec<String> = Vec::new();
+        for table in view.query.referenced_tables() {
+            let name = table.to_string();
+            if !dependencies.contains(&name) {
+                dependencies.push(name);
+            }
+        }
+        View {
+            name: view.view_name.object.clone(),
+            schema: self.schema_of(&view.view_name, scope),
+            definition: view.definition.clone(),
+            columns: Vec::new(),
+            dependencies,
+            view_type: if view.materialized { ViewType::Materialized } else { ViewType::Regular },
+            metadata: ViewMetadata::default(),
+        }
+    }
+
+    /// A routine, with its return type and language kept among its metadata's properties.
+    /// PostgreSQL's unnamed parameters are named by position, `$1`, `$2` and so on.
+    fn lower_routine(&self, routine: &CreateRoutineStatement, scope: &Option<String>) -> Procedure {
+        let parameters = routine
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, parameter)| ProcedureParameter {
+                name: parameter.name.clone().unwrap_or_else(|| format!("${}", index + 1)),
+                data_type: parameter.data_type.clone(),
+                parameter_mode: match parameter.mode {
+                    ast::ParameterMode::In => ParameterMode::In,
+                    ast::ParameterMode::Out => ParameterMode::Out,
+                    ast::ParameterMode::InOut => ParameterMode::InOut,
+                },
+                is_nullable: true,
+                default_value: parameter.default.clone(),
+                description: None,
+            })
+            .collect();
+        let mut metadata = ProcedureMetadata::default();
+        if let Some(returns) = &routine.returns {
+            metadata.custom_properties.insert("returns".to_string(), returns.clone());
+        }
+        if let Some(language) = &routine.language {
+            metadata.custom_properties.insert("language".to_string(), language.clone());
+        }
+        Procedure {
+            name: routine.routine_name.object.clone(),
+            schema_name: self.schema_of(&routine.routine_name, scope),
+            parameters,
+            definition: routine.body.clone(),
+            metadata,
+        }
+    }
+
+    fn lower_sequence(&self, sequence: &CreateSequenceStatement, scope: &Option<String>) -> Sequence {
+        Sequence {
+            name: sequence.sequence_name.object.clone(),
+            schema: self.schema_of(&sequence.sequence_name, scope),
+            data_type: sequence.data_type.as_ref().map(|data_type| self.lower_data_type(data_type).0),
+            start: sequence.options.start,
+            increment: sequence.options.increment,
+            min_value: sequence.options.min_value,
+            max_value: sequence.options.max_value,
+            cycle: sequence.options.cycle,
+        }
+    }
+
+    fn lower_type(
+        &self,
+        create_type: &CreateTypeStatement,
+        scope: &Option<String>,
+        warnings: &mut Vec<LowerWarning>,
+    ) -> UserDefinedType {
+        let kind = match &create_type.definition {
+            TypeDefinition::Enum { values } => UserDefinedTypeKind::Enum { values: values.clone() },
+            TypeDefinition::Composite { attributes } => UserDefinedTypeKind::Composite {
+                attributes: attributes
+                    .iter()
+                    .map(|attribute| {
+                        let object = format!("{}.{}", create_type.type_name, attribute.name);
+                        self.lower_column(attribute, &HashMap::new(), &object, warnings)
+                    })
+                    .collect(),
+            },
+        };
+
+        UserDefinedType {
+            name: create_type.type_name.object.clone(),
+            schema: self.schema_of(&create_type.type_name, scope),
+            kind,
+            description: None,
+        }
+    }
+
+    fn lower_domain(
+        &self,
+        domain: &CreateDomainStatement,
+        scope: &Option<String>,
+        warnings: &mut Vec<LowerWarning>,
+    ) -> UserDefinedType {
+        let mut nullable = true;
+        let mut default_value = None;
+        let mut checks = Vec::new();
+
+        for constraint in &domain.constraints {
+            match constraint {
+                ColumnConstraint::NotNull => nullable = false,
+                ColumnConstraint::Null => nullable = true,
+                ColumnConstraint::Default(value) => default_value = Some(value.clone()),
+                ColumnConstraint::Check(expression) => checks.push(expression.clone()),
+                ColumnConstraint::Collate(_) => {}
+                _ => warn(warnings, &domain.domain_name.to_string(), "constraint is not valid on a domain; ignored"),
+            }
+        }
+
+        UserDefinedType {
+            name: domain.domain_name.object.clone(),
+            schema: self.schema_of(&domain.domain_name, scope),
+            kind: UserDefinedTypeKind::Domain {
+                base_type: self.lower_data_type(&domain.data_type).0,
+                nullable,
+                default_value,
+                checks,
+            },
+            description: None,
+        }
+    }
+
+    fn lower_table(
+        &self,
+        statement: &CreateTableStatement,
+        scope: &Option<String>,
+        types: &HashMap<String, UserDefinedType>,
+        warnings: &mut Vec<LowerWarning>,
+    ) -> Table {
+        let table_name = &statement.table_name.object;
+        let mut table = Table::new(table_name);
+        table.schema = self.schema_of(&statement.table_name, scope);
+        let mut primary_key = PrimaryKey { name: None, columns: Vec::new(), constraint_name: None };
+
+        for definition in &statement.columns {
+            let object = format!("{}.{}", table_name, definition.name);
+            let column = self.lower_column(definition, types, &object, warnings);
+
+            self.add_column_constraints(&mut table, definition, scope, &mut primary_key);
+            table.add_column(column);
+        }
+
+        for constraint in &statement.constraints {
+            self.add_table_constraint(&mut table, constraint, scope, &mut primary_key);
+        }
+
+        apply_primary_key(&mut table, primary_key, warnings);
+
+        if self.dialect == SqlDialect::SQLite {
+            self.apply_sqlite_semantics(statement, &mut table, warnings);
+        } else if statement.strict || statement.without_rowid {
+            warn(warnings, table_name, "STRICT and WITHOUT ROWID only apply to SQLite tables");
+        }
+
+        for (key, value) in &statement.options {
+            match key.as_str() {
+                "ENGINE" => table.engine = Some(value.clone()),
+                "CHARSET" => table.charset = Some(value.clone()),
+                "COLLATE" => table.collation = Some(value.clone()),
+                "COMMENT" => table.metadata.description = Some(value.clone()),
+                _ => {
+                    table.metadata.custom_properties.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        table
+    }
+
+    /// Applies `ALTER TABLE` to a table lowered from an earlier statement, so that a series of
+    /// migrations ends in the schema they build. Renames are carried into the keys, indexes and
+    /// foreign keys that name the table or column.
+    fn alter_table(
+        &self,
+        database: &mut Database,
+        statement: &AlterTableStatement,
+        scope: &Option<String>,
+        types: &HashMap<String, UserDefinedType>,
+        warnings: &mut Vec<LowerWarning>,
+    ) {
+        let schema_name = self.schema_of(&statement.table_name, scope);
+        let Some(table) = find_table_mut(database, &schema_name, &statement.table_name.object) else {
+            if !statement.if_exists {
+                warn(
+                    warnings,
+                    &statement.table_name.object,
+                    format!("ALTER TABLE on unknown table {}; not reflected in the model", statement.table_name),
+                );
+            }
+            return;
+        };
+        let old_name = table.name.clone();
+        let schema_name = table.schema.clone();
+        let mut primary_key = PrimaryKey { name: None, columns: Vec::new(), constraint_name: None };
+        let mut renamed_columns = Vec::new();
+
+        for action in &statement.actions {
+            let table_name = table.name.clone();
+            match action {
+                AlterTableAction::AddColumn { column: definition } => {
+                    let object = format!("{}.{}", table_name, definition.name);
+                    let column = self.lower_column(definition, types, &object, warnings);
+                    self.add_column_constraints(table, definition, scope, &mut primary_key);
+                    table.add_column(column);
+                }
+                AlterTableAction::DropColumn { column_name, if_exists } => {
+                    if table.get_column(column_name).is_some() {
+                        drop_column(table, column_name);
+                    } else if !if_exists {
+                        warn(warnings, &table_name, format!("cannot drop unknown column {}", column_name));
+                    }
+                }
+                AlterTableAction::AlterColumn { column_name, new_data_type, add_constraints, drop_constraints } => {
+                    let object = format!("{}.{}", table_name, column_name);
+                    let lowered = new_data_type.as_ref().map(|data_type| {
+                        let definition = ColumnDefinition {
+                            name: column_name.clone(),
+                            data_type: data_type.clone(),
+                            type_name: None,
+                            constraints: Vec::new(),
+                        };
+                        self.lower_column(&definition, types, &object, warnings)
+                    });
+                    let Some(column) = table.get_column_mut(column_name) else {
+                        warn(warnings, &table_name, format!("cannot alter unknown column {}", column_name));
+                        continue;
+                    };
+                    if let Some(lowered) = lowered {
+                        column.data_type = lowered.data_type;
+                        column.user_type = lowered.user_type;
+                    }
+                    for constraint in add_constraints {
+                        match constraint {
+                            ColumnConstraint::NotNull => column.nullable = false,
+                            ColumnConstraint::Default(value) => column.default_value = Some(value.clone()),
+                            ColumnConstraint::Collate(collation) => column.collation = Some(collation.clone()),
+                            _ => warn(warnings, &object, "constraint added by ALTER COLUMN not reflected in the model"),
+                        }
+                    }
+                    for constraint in drop_constraints {
+                        match constraint.as_str() {
+                            "NOT NULL" => column.nullable = true,
+                            "DEFAULT" => column.default_value = None,
+                            _ => {}
+                        }
+                    }
+                }
+                AlterTableAction::ModifyColumn { column: definition } => {
+                    let object = format!("{}.{}", table_name, definition.name);
+                    let column = self.lower_column(definition, types, &object, warnings);
+                    match table.columns.iter().position(|existing| existing.name == definition.name) {
+                        // The new definition replaces the old one whole, as MySQL does
+                        Some(position) => {
+                            table.columns[position] = column;
+                            self.add_column_constraints(table, definition, scope, &mut primary_key);
+                        }
+                        None => warn(warnings, &table_name, format!("cannot modify unknown column {}", definition.name)),
+                    }
+                }
+                AlterTableAction::RenameColumn { old_name, new_name } => {
+                    if table.get_column(old_name).is_some() {
+                        rename_column(table, old_name, new_name);
+                        renamed_columns.push((old_name.clone(), new_name.clone()));
+                    } else {
+                        warn(warnings, &table_name, format!("cannot rename unknown column {}", old_name));
+                    }
+                }
+                AlterTableAction::AddConstraint { constraint, .. } => {
+                    self.add_table_constraint(table, constraint, scope, &mut primary_key);
+                }
+                AlterTableAction::DropConstraint { constraint_name, if_exists } => {
+                    if !drop_constraint(table, constraint_name) && !if_exists {
+                        warn(warnings, &table_name, format!("cannot drop unknown constraint {}", constraint_name));
+                    }
+                }
+                AlterTableAction::RenameTable { new_name } => rename_table(table, new_name),
+                // Whether a constraint is enforced isn't part of the model
+                AlterTableAction::ConstraintChecking { .. } => {}
+                AlterTableAction::Other(text) => {
+                    warn(warnings, &table_name, format!("ALTER TABLE {} not reflected in the model", text));
+                }
+            }
+        }
+        let new_name = table.name.clone();
+        apply_primary_key(table, primary_key, warnings);
+
+        // Foreign keys elsewhere follow the table and columns they point at
+        let foreign_keys = database
+            .schemas
+            .iter_mut()
+            .flat_map(|schema| schema.tables.iter_mut())
+            .flat_map(|table| table.foreign_keys.iter_mut())
+            .filter(|foreign_key| foreign_key.referenced_table == old_name)
+            .filter(|foreign_key| foreign_key.referenced_schema.is_none() || foreign_key.referenced_schema == schema_name);
+        for foreign_key in foreign_keys {
+            foreign_key.referenced_table = new_name.clone();
+            for (old, new) in &renamed_columns {
+                for column in foreign_key.referenced_columns.iter_mut().filter(|column| *column == old) {
+                    *column = new.clone();
+                }
+            }
+        }
+    }
+
+    /// The keys a column declares inline; a primary key is only collected, since it may span
+    /// several columns.
+    fn add_column_constraints(
+        &self,
+        table: &mut Table,
+        definition: &ColumnDefinition,
+        scope: &Option<String>,
+        primary_key: &mut PrimaryKey,
+    ) {
+        let table_name = table.name.clone();
+        let mut pending_name = None;
+        for constraint in &definition.constraints {
+            // A `CONSTRAINT name` names the constraint right after it
+            let name = match constraint {
+                ColumnConstraint::Name(name) => {
+                    pending_name = Some(name.clone());
+                    continue;
+                }
+                _ => pending_name.take(),
+            };
+            match constraint {
+                ColumnConstraint::PrimaryKey => {
+                    primary_key.columns.push(definition.name.clone());
+                    if name.is_some() {
+                        primary_key.name = name.clone();
+                        primary_key.constraint_name = name;
+                    }
+                }
+                ColumnConstraint::Unique => table.unique_constraints.push(UniqueConstraint {
+                    name: name.clone(),
+                    table_name: table_name.clone(),
+                    columns: vec![definition.name.clone()],
+                    constraint_name: name,
+                    metadata: ConstraintMetadata::default(),
+                }),
+                ColumnConstraint::Check(expression) => table.check_constraints.push(CheckConstraint {
+                    name: name.clone(),
+                    table_name: table_name.clone(),
+                    expression: expression.clone(),
+                    constraint_name: name,
+                    metadata: ConstraintMetadata::default(),
+                }),
+                ColumnConstraint::ForeignKey { table: referenced, column, on_delete, on_update } => {
+                    table.add_foreign_key(foreign_key(
+                        name,
+                        &table_name,
+                        vec![definition.name.clone()],
+                        self.schema_of(referenced, scope),
+                        &referenced.object,
+                        if column.is_empty() { Vec::new() } else { vec![column.clone()] },
+                        on_delete,
+                        on_update,
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_table_constraint(
+        &self,
+        table: &mut Table,
+        constraint: &TableConstraint,
+        scope: &Option<String>,
+        primary_key: &mut PrimaryKey,
+    ) {
+        let table_name = table.name.clone();
+        match constraint {
+            TableConstraint::PrimaryKey { name, columns, .. } => {
+                primary_key.columns.extend(columns.iter().cloned());
+                if name.is_some() {
+                    primary_key.name = name.clone();
+                    primary_key.constraint_name = name.clone();
+                }
+            }
+            TableConstraint::Unique { name, columns, .. } => table.unique_constraints.push(UniqueConstraint {
+                name: name.clone(),
+                table_name: table_name.clone(),
+                columns: columns.clone(),
+                constraint_name: name.clone(),
+                metadata: ConstraintMetadata::default(),
+            }),
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                referenced_table,
+                referenced_columns,
+                on_delete,
+                on_update,
+            } => table.add_foreign_key(foreign_key(
+                name.clone(),
+                &table_name,
+                columns.clone(),
+                self.schema_of(referenced_table, scope),
+                &referenced_table.object,
+                referenced_columns.clone(),
+                on_delete,
+                on_update,
+            )),
+            TableConstraint::Check { name, expression } => table.check_constraints.push(CheckConstraint {
+                name: name.clone(),
+                table_name: table_name.clone(),
+                expression: expression.clone(),
+                constraint_name: name.clone(),
+                metadata: ConstraintMetadata::default(),
+            }),
+            TableConstraint::Index { name, kind, columns, index_type } => {
+                table.add_index(Index {
+                    // MySQL names an anonymous key after its first column
+                    name: name.clone().unwrap_or_else(|| columns.first().map(|part| part.column.clone()).unwrap_or_default()),
+                    table_name: table_name.clone(),
+                    columns: index_columns(columns),
+                    unique: false,
+                    index_type: match kind {
+                        IndexKind::FullText => IndexType::FullText,
+                        IndexKind::Spatial => IndexType::Spatial,
+                        IndexKind::Regular => index_type.as_ref().map(lower_index_type).unwrap_or(IndexType::BTree),
+                    },
+                    where_clause: None,
+                    metadata: IndexMetadata::default(),
+                });
+            }
+            TableConstraint::Exclude { name, index_type, elements, where_clause } => {
+                table.exclusion_constraints.push(ExclusionConstraint {
+                    name: name.clone(),
+                    table_name: table_name.clone(),
+                    index_type: index_type.as_ref().map(lower_index_type).unwrap_or(IndexType::Gist),
+                    elements: elements
+                        .iter()
+                        .map(|element| ExclusionElement {
+                            expression: element.expression.clone(),
+                            operator: element.operator.clone(),
+                        })
+                        .collect(),
+                    where_clause: where_clause.clone(),
+                    metadata: ConstraintMetadata::default(),
+                });
+            }
+        }
+    }
+
+    fn lower_column(
+        &self,
+        definition: &ColumnDefinition,
+        types: &HashMap<String, UserDefinedType>,
+        object: &str,
+        warnings: &mut Vec<LowerWarning>,
+    ) -> Column {
+        let (data_type, serial) = self.lower_data_type(&definition.data_type);
+        let mut column = Column::new(&definition.name, data_type);
+        if serial {
+            // SERIAL is shorthand for a NOT NULL column fed by its own sequence
+            column.auto_increment = true;
+            column.nullable = false;
+        }
+
+        // Types are looked up by their own name, whichever schema qualifies them
+        if let ast::DataType::Custom(name) = &definition.data_type
+            && let Some(user_type) = types.get(name.rsplit('.').next().unwrap_or(name))
+        {
+            column.user_type = Some(user_type.name.clone());
+            match &user_type.kind {
+                UserDefinedTypeKind::Enum { values } => {
+                    column.data_type = DataType::Enum { values: values.clone() };
+                }
+                UserDefinedTypeKind::Domain { base_type, nullable, default_value, .. } => {
+                    column.data_type = base_type.clone();
+                    column.nullable = *nullable;
+                    column.default_value = default_value.clone();
+                }
+                UserDefinedTypeKind::Composite { .. } => {
+                    column.data_type = DataType::Custom(user_type.name.clone());
+                }
+            }
+        }
+
+        for constraint in &definition.constraints {
+            match constraint {
+                ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey => column.nullable = false,
+                ColumnConstraint::Null => column.nullable = true,
+                ColumnConstraint::Default(value) => column.default_value = Some(value.clone()),
+                ColumnConstraint::AutoIncrement => column.auto_increment = true,
+                ColumnConstraint::Identity(options) => {
+                    // Identity columns are implicitly NOT NULL in PostgreSQL and SQL Server
+                    column.auto_increment = true;
+                    column.nullable = false;
+                    column.identity = Some(IdentitySpecification {
+                        always: options.always,
+                        seed: options.start.unwrap_or(1),
+                        increment: options.increment.unwrap_or(1),
+                        min_value: options.min_value,
+                        max_value: options.max_value,
+                        cycle: options.cycle,
+                    });
+                }
+                ColumnConstraint::Generated { expression, stored } => {
+                    if column.default_value.is_some() {
+                        warn(warnings, object, "generated column also declares a DEFAULT");
+                    }
+                    column.generated = Some(GeneratedColumn { expression: expression.clone(), stored: *stored });
+                }
+                ColumnConstraint::Collate(collation) => column.collation = Some(collation.clone()),
+                ColumnConstraint::CharacterSet(charset) => column.charset = Some(charset.clone()),
+                ColumnConstraint::OnUpdate(expression) => column.on_update = Some(expression.clone()),
+                ColumnConstraint::Comment(comment) => column.metadata.description = Some(comment.clone()),
+                ColumnConstraint::Unique
+                | ColumnConstraint::KeyDescending
+                | ColumnConstraint::Check(_)
+                | ColumnConstraint::ForeignKey { .. }
+                | ColumnConstraint::Name(_) => {}
+            }
+        }
+
+        column
+    }
+
+    /// Records the affinity SQLite gives each declared type and marks the rowid alias, if any.
+    fn apply_sqlite_semantics(
+        &self,
+        statement: &CreateTableStatement,
+        table: &mut Table,
+        warnings: &mut Vec<LowerWarning>,
+    ) {
+        table.strict = statement.strict;
+        table.without_rowid = statement.without_rowid;
+        if statement.without_rowid && table.primary_key.is_none() {
+            warn(warnings, &table.name, "a WITHOUT ROWID table needs a PRIMARY KEY");
+        }
+
+        let rowid_alias = statement.rowid_alias().map(|definition| definition.name.clone());
+
+        for definition in &statement.columns {
+            let object = format!("{}.{}", table.name, definition.name);
+            let declared = definition.type_name.clone().unwrap_or_else(|| DataType::from(&definition.data_type).to_sql());
+            let Some(column) = table.get_column_mut(&definition.name) else {
+                continue;
+            };
+
+            if statement.strict {
+                let base = declared.to_uppercase();
+                if !STRICT_TYPES.contains(&base.as_str()) {
+                    warn(warnings, &object, format!("type {} is not allowed in a STRICT table", declared));
+                }
+                if base == "ANY" {
+                    // ANY columns keep every value exactly as inserted
+                    column.data_type = DataType::Custom("ANY".to_string());
+                    continue;
+                }
+            }
+
+            let affinity = TypeAffinity::from_declared_type(&declared);
+            column.affinity = Some(affinity);
+            // An untyped column has no declared type to keep, only its BLOB affinity
+            if declared.is_empty() {
+                column.data_type = affinity.data_type();
+            }
+
+            if rowid_alias.as_ref() == Some(&definition.name) {
+                column.rowid_alias = true;
+            } else if column.auto_increment {
+                warn(warnings, &object, "AUTOINCREMENT is only allowed on an INTEGER PRIMARY KEY");
+            }
+        }
+    }
+
+    /// Maps a type name as written in a query, such as `numeric(10, 2)` in a CAST, onto the model.
+    pub(crate) fn lower_type_name(&self, name: &str) -> DataType {
+        let parsed = Lexer::for_dialect(name, &self.dialect)
+            .tokenize()
+            .ok()
+            .and_then(|tokens| Parser::new(tokens).parse_type().ok());
+        match parsed {
+            Some(data_type) => self.lower_data_type(&data_type).0,
+            None => self.field_service.resolve_type_name(name),
+        }
+    }
+
+    /// Maps a parsed type onto the model; the flag is set for PostgreSQL's SERIAL shorthands.
+    pub(crate) fn lower_data_type(&self, data_type: &ast::DataType) -> (DataType, bool) {
+        if let ast::DataType::Custom(name) = data_type {
+            match name.to_uppercase().as_str() {
+                "SERIAL" | "SERIAL4" => return (DataType::Integer { unsigned: false }, true),
+                "BIGSERIAL" | "SERIAL8" => return (DataType::BigInt { unsigned: false }, true),
+                "SMALLSERIAL" | "SERIAL2" => return (DataType::SmallInt { unsigned: false }, true),
+                _ => {}
+            }
+        }
+        match DataType::from(data_type) {
+            // Vendor names such as NUMBER(10, 0) or MONEY, read by the dialect's type table
+            DataType::Custom(name) => (self.field_service.resolve_type_name(&name), false),
+            // Oracle's DATE carries a time of day
+            DataType::Date if self.dialect == SqlDialect::Oracle => (DataType::DateTime, false),
+            data_type => (data_type, false),
+        }
+    }
+
+    fn lower_index(
+        &self,
+        database: &mut Database,
+        index: &CreateIndexStatement,
+        scope: &Option<String>,
+        warnings: &mut Vec<LowerWarning>,
+    ) {
+        let schema_name = self.schema_of(&index.table_name, scope);
+        let table_name = &index.table_name.object;
+        let Some(table) = find_table_mut(database, &schema_name, table_name) else {
+            warn(
+                warnings,
+                &index.index_name,
+                format!("index on unknown table {}; not reflected in the model", index.table_name),
+            );
+            return;
+        };
+
+        table.add_index(Index {
+            name: index.index_name.clone(),
+            table_name: table_name.clone(),
+            columns: index_columns(&index.columns),
+            unique: index.unique,
+            index_type: match index.kind {
+                IndexKind::FullText => IndexType::FullText,
+                IndexKind::Spatial => IndexType::Spatial,
+                IndexKind::Regular => index.index_type.as_ref().map(lower_index_type).unwrap_or(IndexType::BTree),
+            },
+            where_clause: index.where_clause.clone(),
+            metadata: IndexMetadata::default(),
+        });
+    }
+
+    fn lower_trigger(
+        &self,
+        database: &mut Database,
+        trigger: &CreateTriggerStatement,
+        scope: &Option<String>,
+        warnings: &mut Vec<LowerWarning>,
+    ) {
+        let schema_name = self.schema_of(&trigger.table_name, scope);
+        let table_name = &trigger.table_name.object;
+        let Some(table) = find_table_mut(database, &schema_name, table_name) else {
+            warn(
+                warnings,
+                &trigger.trigger_name.object,
+                format!("trigger on unknown table {}; not reflected in the model", trigger.table_name),
+            );
+            return;
+        };
+
+        // The model has no TRUNCATE event
+        let events = trigger
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ast::TriggerEvent::Insert => Some(TriggerEvent::Insert),
+                ast::TriggerEvent::Update(_) => Some(TriggerEvent::Update),
+                ast::TriggerEvent::Delete => Some(TriggerEvent::Delete),
+                ast::TriggerEvent::Truncate => None,
+            })
+            .collect();
+        table.triggers.retain(|existing| existing.name != trigger.trigger_name.object);
+        table.triggers.push(Trigger {
+            name: trigger.trigger_name.object.clone(),
+            table_name: table_name.clone(),
+            timing: match trigger.timing {
+                ast::TriggerTiming::Before => TriggerTiming::Before,
+                ast::TriggerTiming::After => TriggerTiming::After,
+                ast::TriggerTiming::InsteadOf => TriggerTiming::InsteadOf,
+            },
+            events,
+            update_columns: trigger
+                .events
+                .iter()
+                .flat_map(|event| match event {
+                    ast::TriggerEvent::Update(columns) => columns.clone(),
+                    _ => Vec::new(),
+                })
+                .collect(),
+            scope: if trigger.for_each_row { TriggerScope::Row } else { TriggerScope::Statement },
+            definition: trigger.body.clone(),
+            metadata: TriggerMetadata::default(),
+        });
+    }
+}
+
+/// The only type names a SQLite STRICT table accepts.
+const STRICT_TYPES: &[&str] = &["INT", "INTEGER", "REAL", "TEXT", "BLOB", "ANY"];
+
+/// The table in the schema a name resolves to, or else the first of that name in any schema.
+fn find_table_mut<'d>(database: &'d mut Database, schema_name: &Option<String>, table_name: &str) -> Option<&'d mut Table> {
+    match database.schemas.iter().position(|schema| schema.name == *schema_name) {
+        Some(position) if database.schemas[position].get_table(table_name).is_some() => {
+            database.schemas[position].get_table_mut(table_name)
+        }
+        _ => database.schemas.iter_mut().find_map(|schema| schema.get_table_mut(table_name)),
+    }
+}
+
+/// Makes the collected key columns the table's primary key; its columns can no longer be NULL.
+fn apply_primary_key(table: &mut Table, primary_key: PrimaryKey, warnings: &mut Vec<LowerWarning>) {
+    if primary_key.columns.is_empty() {
+        return;
+    }
+    for name in &primary_key.columns {
+        match table.get_column_mut(name) {
+            Some(column) => column.nullable = false,
+            None => warn(warnings, &table.name, format!("primary key column {} does not exist", name)),
+        }
+    }
+    table.primary_key = Some(primary_key);
+}
+
+/// Drops a column with the keys and indexes that cover it, as PostgreSQL and MySQL do.
+fn drop_column(table: &mut Table, name: &str) {
+    let covers = |columns: &[String]| columns.iter().any(|column| column == name);
+    table.columns.retain(|column| column.name != name);
+    if table.primary_key.as_ref().is_some_and(|pk| covers(&pk.columns)) {
+        table.primary_key = None;
+    }
+    table.indexes.retain(|index| !index.columns.iter().any(|column| column.name == name));
+    table.unique_constraints.retain(|unique| !covers(&unique.columns));
+    table.foreign_keys.retain(|foreign_key| !covers(&foreign_key.columns));
+}
+
+fn rename_column(table: &mut Table, old_name: &str, new_name: &str) {
+    let rename = |name: &mut String| {
+        if name == old_name {
+            *name = new_name.to_string();
+        }
+    };
+    table.columns.iter_mut().for_each(|column| rename(&mut column.name));
+    if let Some(primary_key) = &mut table.primary_key {
+        primary_key.columns.iter_mut().for_each(rename);
+    }
+    for index in &mut table.indexes {
+        index.columns.iter_mut().for_each(|column| rename(&mut column.name));
+    }
+    for unique in &mut table.unique_constraints {
+        unique.columns.iter_mut().for_each(rename);
+    }
+    for foreign_key in &mut table.foreign_keys {
+        foreign_key.columns.iter_mut().for_each(rename);
+    }
+}
+
+fn rename_table(table: &mut Table, new_name: &str) {
+    table.name = new_name.to_string();
+    let new_name = || new_name.to_string();
+    table.indexes.iter_mut().for_each(|index| index.table_name = new_name());
+    table.foreign_keys.iter_mut().for_each(|foreign_key| foreign_key.table_name = new_name());
+    table.unique_constraints.iter_mut().for_each(|unique| unique.table_name = new_name());
+    table.check_constraints.iter_mut().for_each(|check| check.table_name = new_name());
+    table.exclusion_constraints.iter_mut().for_each(|exclusion| exclusion.table_name = new_name());
+    table.triggers.iter_mut().for_each(|trigger| trigger.table_name = new_name());
+}
+
+/// Drops whichever key or constraint goes by the name; false when none does.
+fn drop_constraint(table: &mut Table, name: &str) -> bool {
+    let named = |constraint: &Option<String>| constraint.as_deref() == Some(name);
+    let before = table.unique_constraints.len()
+        + table.check_constraints.len()
+        + table.foreign_keys.len()
+        + table.exclusion_constraints.len()
+        + table.indexes.len();
+    table.unique_constraints.retain(|unique| !named(&unique.name) && !named(&unique.constraint_name));
+    table.check_constraints.retain(|check| !named(&check.name) && !named(&check.constraint_name));
+    table.foreign_keys.retain(|foreign_key| !named(&foreign_key.name) && !named(&foreign_key.constraint_name));
+    table.exclusion_constraints.retain(|exclusion| !named(&exclusion.name));
+    // MySQL's keys are indexes, and `DROP CONSTRAINT` removes them too
+    table.indexes.retain(|index| index.name != name);
+    let after = table.unique_constraints.len()
+        + table.check_constraints.len()
+        + table.foreign_keys.len()
+        + table.exclusion_constraints.len()
+        + table.indexes.len();
+
+    // An unnamed primary key goes by PostgreSQL's default name
+    let default_name = format!("{}_pkey", table.name);
+    let primary_key = table.primary_key.as_ref().is_some_and(|pk| {
+        named(&pk.name) || named(&pk.constraint_name) || (pk.constraint_name.is_none() && name == default_name)
+    });
+    if primary_key {
+        table.primary_key = None;
+    }
+    primary_key || after < before
+}
+
+fn index_columns(parts: &[KeyPart]) -> Vec<IndexColumn> {
+    parts
+        .iter()
+        .map(|part| IndexColumn {
+            name: part.column.clone(),
+            sort_order: if part.descending { SortOrder::Descending } else { SortOrder::Ascending },
+            nulls_position: NullsPosition::Default,
+        })
+        .collect()
+}
+
+/// The schemas of a `search_path` value such as `sales, "$user", public`; the `$user`
+/// placeholder is skipped.
+fn search_path_entries(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|entry| entry.trim().trim_matches(|ch| ch == '\'' || ch == '"').to_string())
+        .filter(|entry| !entry.is_empty() && entry != "$user")
+        .collect()
+}
+
+/// A foreign key whose target was named without a schema, with the search path in force where
+/// it was declared.
+struct PathReference {
+    schema: Option<String>,
+    table: String,
+    columns: Vec<String>,
+    search_path: Vec<Option<String>>,
+}
+
+/// The columns of each foreign key among `columns` and `constraints` whose target has no schema.
+fn unqualified_foreign_keys<'s>(
+    columns: impl Iterator<Item = &'s ColumnDefinition>,
+    constraints: impl Iterator<Item = &'s TableConstraint>,
+) -> Vec<Vec<String>> {
+    let inline = columns.flat_map(|column| {
+        column.constraints.iter().filter_map(|constraint| match constraint {
+            ColumnConstraint::ForeignKey { table, .. } if table.schema.is_none() => Some(vec![column.name.clone()]),
+            _ => None,
+        })
+    });
+    let declared = constraints.filter_map(|constraint| match constraint {
+        TableConstraint::ForeignKey { columns, referenced_table, .. } if referenced_table.schema.is_none() => {
+            Some(columns.clone())
+        }
+        _ => None,
+    });
+    inline.chain(declared).collect()
+}
+
+fn foreign_key(
+    name: Option<String>,
+    table_name: &str,
+    columns: Vec<String>,
+    referenced_schema: Option<String>,
+    referenced_table: &str,
+    referenced_columns: Vec<String>,
+    on_delete: &Option<ast::ReferentialAction>,
+    on_update: &Option<ast::ReferentialAction>,
+) -> ForeignKey {
+    ForeignKey {
+        constraint_name: name.clone(),
+        name,
+        table_name: table_name.to_string(),
+        columns,
+        referenced_schema,
+        referenced_table: referenced_table.to_string(),
+        referenced_columns,
+        on_delete: on_delete.as_ref().map(lower_action).unwrap_or(ReferentialAction::NoAction),
+        on_update: on_update.as_ref().map(lower_action).unwrap_or(ReferentialAction::NoAction),
+        metadata: ForeignKeyMetadata {
+            description: None,
+            relationship_type: RelationshipType::ManyToOne,
+            custom_properties: HashMap::new(),
+            history: Vec::new(),
+        },
+    }
+}
+
+/// Points each foreign key at the table it names. A name given without a schema is looked up
+/// along the search path where the key was declared; one that isn't found there, or in the
+/// schema it names, falls back to the only table of that name elsewhere. A bare `REFERENCES t`
+/// targets t's primary key, which is only known once every table is lowered.
+fn resolve_references(database: &mut Database, path_references: &[PathReference]) {
+    let tables: Vec<(Option<String>, String, Option<Vec<String>>)> = database
+        .get_all_tables()
+        .into_iter()
+        .map(|table| (table.schema.clone(), table.name.clone(), table.primary_key.as_ref().map(|pk| pk.columns.clone())))
+        .collect();
+
+    for schema in &mut database.schemas {
+        for table in &mut schema.tables {
+            for foreign_key in &mut table.foreign_keys {
+                let path_reference = path_references.iter().find(|reference| {
+                    reference.schema == table.schema && reference.table == table.name && reference.columns == foreign_key.columns
+                });
+                let search_path = match path_reference {
+                    Some(reference) => reference.search_path.clone(),
+                    None => vec![foreign_key.referenced_schema.clone()],
+                };
+                let along_path = search_path.iter().find_map(|schema_name| {
+                    tables.iter().find(|(schema, name, _)| schema == schema_name && *name == foreign_key.referenced_table)
+                });
+                let target = along_path.or_else(|| {
+                    let mut candidates = tables.iter().filter(|(_, name, _)| *name == foreign_key.referenced_table);
+                    match (candidates.next(), candidates.next()) {
+                        (Some(only), None) => Some(only),
+                        _ => None,
+                    }
+                });
+                let Some((target_schema, _, primary_key)) = target else {
+                    continue;
+                };
+
+                foreign_key.referenced_schema = target_schema.clone();
+                if foreign_key.referenced_columns.is_empty()
+                    && let Some(columns) = primary_key
+                {
+                    foreign_key.referenced_columns = columns.clone();
+                }
+            }
+        }
+    }
+}
+
+fn lower_action(action: &ast::ReferentialAction) -> ReferentialAction {
+    match action {
+        ast::ReferentialAction::NoAction => ReferentialAction::NoAction,
+        ast::ReferentialAction::Restrict => ReferentialAction::Restrict,
+        ast::ReferentialAction::Cascade => ReferentialAction::Cascade,
+        ast::ReferentialAction::SetNull => ReferentialAction::SetNull,
+        ast::ReferentialAction::SetDefault => ReferentialAction::SetDefault,
+    }
+}
+
+fn lower_index_type(index_type: &ast::IndexType) -> IndexType {
+    match index_type {
+        ast::IndexType::BTree => IndexType::BTree,
+        ast::IndexType::Hash => IndexType::Hash,
+        ast::IndexType::Gist => IndexType::Gist,
+        ast::IndexType::Gin => IndexType::Gin,
+        ast::IndexType::SpGist => IndexType::SpGist,
+        ast::IndexType::Brin => IndexType::Brin,
+        ast::IndexType::Custom(name) => IndexType::Custom(name.clone()),
+    }
+}
+
+fn warn(warnings: &mut Vec<LowerWarning>, object: &str, message: impl Into<String>) {
+    warnings.push(LowerWarning {
+        object: object.to_string(),
+        message: message.into(),
+    });
+}
@@ -0,0 +1,3 @@
+pub mod lowerer;
+
+pub use lowerer::*;
@@ -1,5 +1,11 @@
+pub mod analyze;
 pub mod ast;
 pub mod parse;
+pub mod domain;
+pub mod format;
+pub mod transpile;
+pub mod lower;
+pub mod docs;
 
 pub use ast::*;
 pub use parse::*;
@@ -0,0 +1,136 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::core::parse::dialect::SqlDialect;
+
+/// A telltale piece of syntax that only one dialect (or one dump tool) writes.
+struct Signal {
+    dialect: SqlDialect,
+    description: &'static str,
+    pattern: Regex,
+    weight: u32,
+}
+
+impl Signal {
+    fn new(dialect: SqlDialect, description: &'static str, pattern: &str, weight: u32) -> Self {
+        Self {
+            dialect,
+            description,
+            pattern: Regex::new(pattern).expect("signal pattern is valid"),
+            weight,
+        }
+    }
+}
+
+/// Occurrences past this many add nothing, so one habit repeated on every line can't drown
+/// out the rest of the file.
+const MAX_COUNTED_MATCHES: u32 = 5;
+
+/// Total score at which a guess is considered fully supported by the evidence.
+const CONFIDENT_SCORE: f64 = 12.0;
+
+lazy_static! {
+    /// String literals and line comments, which are blanked out before matching.
+    static ref NOISE_REGEX: Regex =
+        Regex::new(r"'(?:[^'\\]|\\.|'')*'|--[^\n]*").expect("noise regex is valid");
+
+    static ref SIGNALS: Vec<Signal> = vec![
+        Signal::new(SqlDialect::PostgreSQL, "dollar-quoted bodies", r"\$[A-Za-z_]*\$", 3),
+        Signal::new(SqlDialect::PostgreSQL, "SERIAL columns", r"(?i)\b(?:small|big)?serial\b", 3),
+        Signal::new(SqlDialect::PostgreSQL, "psql meta-commands", r"(?m)^\s*\\[a-zA-Z]+", 4),
+        Signal::new(SqlDialect::PostgreSQL, "`::` casts", r"::\s*[A-Za-z_]", 2),
+        Signal::new(SqlDialect::PostgreSQL, "PostgreSQL types", r"(?i)\b(?:jsonb|bytea|timestamptz|inet|cidr|tsvector)\b", 2),
+        Signal::new(SqlDialect::PostgreSQL, "search_path", r"(?i)\bsearch_path\b", 3),
+        Signal::new(SqlDialect::PostgreSQL, "OWNER TO", r"(?i)\bowner\s+to\b", 2),
+        Signal::new(SqlDialect::PostgreSQL, "CREATE EXTENSION", r"(?i)\bcreate\s+extension\b", 3),
+        Signal::new(SqlDialect::MySQL, "backtick-quoted names", r"`[^`\n]+`", 3),
+        Signal::new(SqlDialect::MySQL, "ENGINE=", r"(?i)\bengine\s*=", 4),
+        Signal::new(SqlDialect::MySQL, "AUTO_INCREMENT", r"(?i)\bauto_increment\b", 3),
+        Signal::new(SqlDialect::MySQL, "table charsets", r"(?i)\bdefault\s+charset\b", 2),
+        Signal::new(SqlDialect::MySQL, "mysqldump conditional comments", r"/\*![0-9]{5}", 4),
+        Signal::new(SqlDialect::MySQL, "LOCK TABLES", r"(?i)\block\s+tables\b", 2),
+        Signal::new(SqlDialect::MySQL, "UNSIGNED", r"(?i)\bunsigned\b", 1),
+        Signal::new(SqlDialect::SQLite, "AUTOINCREMENT", r"(?i)\bautoincrement\b", 4),
+        Signal::new(SqlDialect::SQLite, "PRAGMA", r"(?i)\bpragma\b", 4),
+        Signal::new(SqlDialect::SQLite, "WITHOUT ROWID / STRICT tables", r"(?i)\)\s*(?:without\s+rowid|strict)\b", 3),
+        Signal::new(SqlDialect::SQLite, "sqlite_ tables", r"(?i)\bsqlite_[a-z]+\b", 3),
+        Signal::new(SqlDialect::MSSQL, "bracketed names", r"\[[A-Za-z_][^\]\n]*\]", 3),
+        Signal::new(SqlDialect::MSSQL, "GO batch separators", r"(?im)^\s*go\s*$", 4),
+        Signal::new(SqlDialect::MSSQL, "IDENTITY(seed, increment)", r"(?i)\bidentity\s*\(\s*\d", 3),
+        Signal::new(SqlDialect::MSSQL, "NVARCHAR(MAX)", r"(?i)\bn?varchar\s*\(\s*max\s*\)", 3),
+        Signal::new(SqlDialect::MSSQL, "dbo schema", r"(?i)\bdbo\b", 2),
+        Signal::new(SqlDialect::MSSQL, "ANSI_NULLS / QUOTED_IDENTIFIER", r"(?i)\bset\s+(?:ansi_nulls|quoted_identifier)\b", 3),
+        Signal::new(SqlDialect::Oracle, "VARCHAR2", r"(?i)\bn?varchar2\b", 4),
+        Signal::new(SqlDialect::Oracle, "NUMBER columns", r"(?i)\bnumber\s*\(", 2),
+        Signal::new(SqlDialect::Oracle, "`/` batch separators", r"(?m)^\s*/\s*$", 2),
+        Signal::new(SqlDialect::Oracle, "sequence NEXTVAL", r"(?i)\.\s*nextval\b", 3),
+        Signal::new(SqlDialect::Oracle, "Oracle types", r"(?i)\b(?:clob|nclob|binary_double|binary_float)\b", 2),
+        Signal::new(SqlDialect::Oracle, "SYSDATE", r"(?i)\bsysdate\b", 2),
+        Signal::new(SqlDialect::Oracle, "NOCACHE / NOCYCLE", r"(?i)\bno(?:cache|cycle)\b", 2),
+    ];
+}
+
+/// How well an input matches one dialect.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialectGuess {
+    pub dialect: SqlDialect,
+    /// Weighted count of the signals seen.
+    pub score: u32,
+    /// Between 0 and 1: this dialect's share of all the evidence, scaled down when there is
+    /// little evidence at all.
+    pub confidence: f64,
+    /// Descriptions of the signals that matched, for explaining the guess.
+    pub signals: Vec<&'static str>,
+}
+
+/// Guesses which dialect a SQL file is written in from syntax only one dialect uses.
+#[derive(Debug, Default)]
+pub struct DialectDetector;
+
+impl DialectDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Scores the input against every dialect with any matching signal, best first.
+    /// Empty when nothing in the input gives a dialect away.
+    pub fn rank(&self, sql: &str) -> Vec<DialectGuess> {
+        let text = NOISE_REGEX.replace_all(sql, "''");
+
+        let mut guesses: Vec<DialectGuess> = Vec::new();
+        for signal in SIGNALS.iter() {
+            let count = signal.pattern.find_iter(&text).take(MAX_COUNTED_MATCHES as usize).count() as u32;
+            if count == 0 {
+                continue;
+            }
+            let index = match guesses.iter().position(|guess| guess.dialect == signal.dialect) {
+                Some(index) => index,
+                None => {
+                    guesses.push(DialectGuess {
+                        dialect: signal.dialect.clone(),
+                        score: 0,
+                        confidence: 0.0,
+                        signals: Vec::new(),
+                    });
+                    guesses.len() - 1
+                }
+            };
+            guesses[index].score += signal.weight * count;
+            guesses[index].signals.push(signal.description);
+        }
+
+        let total: u32 = guesses.iter().map(|guess| guess.score).sum();
+        for guess in &mut guesses {
+            let share = f64::from(guess.score) / f64::from(total);
+            let evidence = (f64::from(guess.score) / CONFIDENT_SCORE).min(1.0);
+            guess.confidence = share * evidence;
+        }
+        guesses.sort_by_key(|guess| std::cmp::Reverse(guess.score));
+        guesses
+    }
+
+    /// The most likely dialect, if the input gives any away.
+    pub fn detect(&self, sql: &str) -> Option<DialectGuess> {
+        self.rank(sql).into_iter().next()
+    }
+}
@@ -33,6 +33,9 @@ pub struct DialectConfig {
     pub supports_cte: bool,
     pub supports_window_functions: bool,
     pub supports_recursive_cte: bool,
+    pub supports_sequences: bool,
+    /// Schema that unqualified names live in when nothing else is selected
+    pub default_schema: Option<String>,
 }
 
 impl SqlDialect {
@@ -57,6 +60,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: true,
+                default_schema: Some("public".to_string()),
             },
             SqlDialect::MySQL => DialectConfig {
                 name: "MySQL".to_string(),
@@ -77,6 +82,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: false,
+                default_schema: None,
             },
             SqlDialect::SQLite => DialectConfig {
                 name: "SQLite".to_string(),
@@ -97,6 +104,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: false,
+                default_schema: Some("main".to_string()),
             },
             SqlDialect::MSSQL => DialectConfig {
                 name: "SQL Server".to_string(),
@@ -117,6 +126,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: true,
+                default_schema: Some("dbo".to_string()),
             },
             SqlDialect::Oracle => DialectConfig {
                 name: "Oracle".to_string(),
@@ -137,6 +148,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: true,
+                default_schema: None,
             },
             SqlDialect::Standard => DialectConfig {
                 name: "Standard SQL".to_string(),
@@ -157,6 +170,8 @@ impl SqlDialect {
                 supports_cte: true,
                 supports_window_functions: true,
                 supports_recursive_cte: true,
+                supports_sequences: true,
+                default_schema: None,
             },
         }
     }
@@ -372,6 +387,9 @@ impl SqlDialect {
         types.insert("PATH".to_string(), DataType::Custom("PATH".to_string()));
         types.insert("POLYGON".to_string(), DataType::Custom("POLYGON".to_string()));
         types.insert("CIRCLE".to_string(), DataType::Custom("CIRCLE".to_string()));
+        for range in ["INT4RANGE", "INT8RANGE", "NUMRANGE", "TSRANGE", "TSTZRANGE", "DATERANGE"] {
+            types.insert(range.to_string(), DataType::Custom(range.to_string()));
+        }
         types
     }
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::iter::Peekable;
 use std::str::Chars;
 
+use crate::core::parse::dialect::SqlDialect;
 use crate::core::Position;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -77,6 +78,9 @@ pub enum TokenType {
     End,
     Cast,
 
+    // Comments, only produced when the lexer is asked to keep them
+    Comment(String),
+
     // Literals
     Identifier(String),
     QuotedIdentifier(String),
@@ -84,6 +88,10 @@ pub enum TokenType {
     IntegerLiteral(i64),
     FloatLiteral(f64),
     BooleanLiteral(bool),
+    /// MySQL hexadecimal literal `0x1F`, holding the digits after the prefix
+    HexLiteral(String),
+    /// Bind parameter: `$1` or `:name` (a bare `?` stays `QuestionMark`)
+    Parameter(String),
 
     // Operators
     Plus,
@@ -104,6 +112,10 @@ pub enum TokenType {
     BitwiseNot,
     LeftShift,
     RightShift,
+    Arrow,
+    LongArrow,
+    HashArrow,
+    HashLongArrow,
 
     // Punctuation
     LeftParen,
@@ -140,6 +152,12 @@ pub struct Lexer<'a> {
     input: Peekable<Chars<'a>>,
     position: Position,
     keywords: HashMap<String, TokenType>,
+    keep_comments: bool,
+    backslash_escapes: bool,
+    bracket_identifiers: bool,
+    batch_separators: bool,
+    /// Whether only whitespace has been read since the last line break.
+    line_blank: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -230,9 +248,49 @@ impl<'a> Lexer<'a> {
             input: input.chars().peekable(),
             position: Position { line: 1, column: 1, index: 0 },
             keywords,
+            keep_comments: false,
+            backslash_escapes: false,
+            bracket_identifiers: false,
+            batch_separators: false,
+            line_blank: true,
+        }
+    }
+
+    /// A lexer configured for the quirks of `dialect`'s string literals.
+    pub fn for_dialect(input: &'a str, dialect: &SqlDialect) -> Self {
+        let lexer = Self::new(input);
+        match dialect {
+            SqlDialect::MySQL => lexer.with_backslash_escapes(),
+            SqlDialect::MSSQL => lexer.with_bracket_identifiers().with_batch_separators(),
+            SqlDialect::Oracle => lexer.with_batch_separators(),
+            _ => lexer,
         }
     }
 
+    /// Treats `\` inside string literals as an escape character, as MySQL does by default.
+    pub fn with_backslash_escapes(mut self) -> Self {
+        self.backslash_escapes = true;
+        self
+    }
+
+    /// Reads `[name]` as a quoted identifier, as SQL Server does.
+    pub fn with_bracket_identifiers(mut self) -> Self {
+        self.bracket_identifiers = true;
+        self
+    }
+
+    /// Treats SQL Server's `GO` and SQL*Plus's `/`, alone on their line, as statement terminators.
+    pub fn with_batch_separators(mut self) -> Self {
+        self.batch_separators = true;
+        self
+    }
+
+    /// Emits comments as `TokenType::Comment` tokens instead of skipping them.
+    pub fn with_comments(mut self) -> Self {
+        self.keep_comments = true;
+        self
+    }
+
     pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerError> {
         let mut tokens = Vec::new();
 
@@ -254,6 +312,7 @@ impl<'a> Lexer<'a> {
                     // Single character tokens
                     '(' => { self.advance(); Ok(Some(Token::new(TokenType::LeftParen, "(", start_pos))) }
                     ')' => { self.advance(); Ok(Some(Token::new(TokenType::RightParen, ")", start_pos))) }
+                    '[' if self.bracket_identifiers => self.lex_bracket_identifier(start_pos),
                     '[' => { self.advance(); Ok(Some(Token::new(TokenType::LeftBracket, "[", start_pos))) }
                     ']' => { self.advance(); Ok(Some(Token::new(TokenType::RightBracket, "]", start_pos))) }
                     ',' => { self.advance(); Ok(Some(Token::new(TokenType::Comma, ",", start_pos))) }
@@ -264,12 +323,47 @@ impl<'a> Lexer<'a> {
                         if self.peek() == Some(&':') {
                             self.advance();
                             Ok(Some(Token::new(TokenType::DoubleColon, "::", start_pos)))
+                        } else if self.peek().is_some_and(|ch| ch.is_alphabetic() || *ch == '_') {
+                            let name = self.lex_word();
+                            Ok(Some(Token::new(TokenType::Parameter(format!(":{}", name)), &format!(":{}", name), start_pos)))
                         } else {
                             Ok(Some(Token::new(TokenType::Colon, ":", start_pos)))
                         }
                     }
                     '?' => { self.advance(); Ok(Some(Token::new(TokenType::QuestionMark, "?", start_pos))) }
                     '@' => { self.advance(); Ok(Some(Token::new(TokenType::AtSign, "@", start_pos))) }
+                    '$' => {
+                        if let Some(tag) = self.dollar_quote_tag() {
+                            return self.lex_dollar_quoted(&tag, start_pos);
+                        }
+                        self.advance();
+                        let mut number = String::new();
+                        while let Some(&ch) = self.peek() {
+                            if !ch.is_ascii_digit() {
+                                break;
+                            }
+                            number.push(ch);
+                            self.advance();
+                        }
+                        if number.is_empty() {
+                            Err(LexerError::new("Expected parameter number after '$'", start_pos))
+                        } else {
+                            Ok(Some(Token::new(TokenType::Parameter(format!("${}", number)), &format!("${}", number), start_pos)))
+                        }
+                    }
+                    '#' => {
+                        self.advance();
+                        if self.peek() != Some(&'>') {
+                            return Err(LexerError::new("Unexpected '#'", start_pos));
+                        }
+                        self.advance();
+                        if self.peek() == Some(&'>') {
+                            self.advance();
+                            Ok(Some(Token::new(TokenType::HashLongArrow, "#>>", start_pos)))
+                        } else {
+                            Ok(Some(Token::new(TokenType::HashArrow, "#>", start_pos)))
+                        }
+                    }
 
                     // Operators
                     '+' => { self.advance(); Ok(Some(Token::new(TokenType::Plus, "+", start_pos))) }
@@ -318,16 +412,32 @@ impl<'a> Lexer<'a> {
                     }
                     '-' => {
                         self.advance();
-                        if self.peek() == Some(&'-') {
+                        if self.peek() == Some(&'-') && self.keep_comments {
+                            self.lex_single_line_comment(start_pos)
+                        } else if self.peek() == Some(&'-') {
                             self.skip_single_line_comment()?;
                             self.next_token()
+                        } else if self.peek() == Some(&'>') {
+                            self.advance();
+                            if self.peek() == Some(&'>') {
+                                self.advance();
+                                Ok(Some(Token::new(TokenType::LongArrow, "->>", start_pos)))
+                            } else {
+                                Ok(Some(Token::new(TokenType::Arrow, "->", start_pos)))
+                            }
                         } else {
                             Ok(Some(Token::new(TokenType::Minus, "-", start_pos)))
                         }
                     }
+                    '/' if self.at_batch_separator(1) => {
+                        self.advance();
+                        Ok(Some(Token::new(TokenType::Semicolon, "/", start_pos)))
+                    }
                     '/' => {
                         self.advance();
-                        if self.peek() == Some(&'*') {
+                        if self.peek() == Some(&'*') && self.keep_comments {
+                            self.lex_multi_line_comment(start_pos)
+                        } else if self.peek() == Some(&'*') {
                             self.skip_multi_line_comment()?;
                             self.next_token()
                         } else {
@@ -351,23 +461,64 @@ impl<'a> Lexer<'a> {
     }
 
     fn lex_identifier_or_keyword(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
-        let mut identifier = String::new();
+        if self.at_batch_separator(2)
+            && self.input.clone().take(2).collect::<String>().eq_ignore_ascii_case("GO")
+        {
+            self.advance();
+            self.advance();
+            return Ok(Some(Token::new(TokenType::Semicolon, "GO", start_pos)));
+        }
+        let identifier = self.lex_word();
+
+        let lower_identifier = identifier.to_lowercase();
+        let token_type = self.keywords.get(&lower_identifier)
+            .cloned()
+            .unwrap_or_else(|| TokenType::Identifier(identifier.clone()));
+
+        Ok(Some(Token::new(token_type, &identifier, start_pos)))
+    }
+
+    fn lex_word(&mut self) -> String {
+        let mut word = String::new();
 
         while let Some(&ch) = self.peek() {
             if ch.is_alphanumeric() || ch == '_' {
                 #[allow(clippy::disallowed_methods)]
-                identifier.push(self.advance().unwrap());
+                word.push(self.advance().unwrap());
             } else {
                 break;
             }
         }
 
-        let lower_identifier = identifier.to_lowercase();
-        let token_type = self.keywords.get(&lower_identifier)
-            .cloned()
-            .unwrap_or_else(|| TokenType::Identifier(identifier.clone()));
+        word
+    }
 
-        Ok(Some(Token::new(token_type, &identifier, start_pos)))
+    /// Whether the `width` characters ahead stand alone on their line.
+    fn at_batch_separator(&self, width: usize) -> bool {
+        self.batch_separators
+            && self.line_blank
+            && self.input.clone().skip(width).take_while(|&ch| ch != '\n').all(char::is_whitespace)
+    }
+
+    fn lex_bracket_identifier(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
+        self.advance(); // consume opening bracket
+        let mut identifier = String::new();
+
+        loop {
+            match self.advance() {
+                // `]]` is an escaped closing bracket
+                Some(']') if self.peek() == Some(&']') => {
+                    self.advance();
+                    identifier.push(']');
+                }
+                Some(']') => break,
+                Some(ch) => identifier.push(ch),
+                None => return Err(LexerError::new("Unterminated bracketed identifier", start_pos)),
+            }
+        }
+
+        let lexeme = format!("[{}]", identifier.replace(']', "]]"));
+        Ok(Some(Token::new(TokenType::QuotedIdentifier(identifier), &lexeme, start_pos)))
     }
 
     fn lex_quoted_identifier(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
@@ -423,7 +574,7 @@ impl<'a> Lexer<'a> {
         self.advance(); // consume opening quote
         let mut literal = String::new();
 
-        while let Some(ch) = self.peek() {
+        while let Some(&ch) = self.peek() {
             match ch {
                 '\'' => {
                     self.advance();
@@ -437,6 +588,24 @@ impl<'a> Lexer<'a> {
                     }
                 }
                 '\n' | '\r' => return Err(LexerError::new("Unterminated string literal", self.position.clone())),
+                '\\' if self.backslash_escapes => {
+                    self.advance();
+                    let escaped = match self.advance() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('0') => '\0',
+                        Some('Z') => '\u{1a}',
+                        // `\%` and `\_` keep their backslash so LIKE patterns still see it
+                        Some(ch @ ('%' | '_')) => {
+                            literal.push('\\');
+                            ch
+                        }
+                        Some(ch) => ch,
+                        None => break,
+                    };
+                    literal.push(escaped);
+                }
                 _ => {
                     #[allow(clippy::disallowed_methods)]
                     literal.push(self.advance().unwrap());
@@ -447,10 +616,48 @@ impl<'a> Lexer<'a> {
         Ok(Some(Token::new(TokenType::StringLiteral(literal.clone()), &format!("'{}'", literal), start_pos)))
     }
 
+    /// The tag of a PostgreSQL dollar quote (`$$` or `$tag$`) starting at the next character, if one does.
+    fn dollar_quote_tag(&self) -> Option<String> {
+        let mut ahead = self.input.clone().skip(1);
+        let mut tag = String::new();
+        loop {
+            match ahead.next()? {
+                '$' => return Some(tag),
+                ch if ch.is_alphabetic() || ch == '_' || (ch.is_ascii_digit() && !tag.is_empty()) => tag.push(ch),
+                _ => return None,
+            }
+        }
+    }
+
+    /// A dollar-quoted string; its lexeme is the source text, quotes and all, since the body is never escaped.
+    fn lex_dollar_quoted(&mut self, tag: &str, start_pos: Position) -> Result<Option<Token>, LexerError> {
+        let delimiter = format!("${}$", tag);
+        for _ in 0..delimiter.chars().count() {
+            self.advance();
+        }
+        let mut body = String::new();
+        loop {
+            let Some(ch) = self.advance() else {
+                return Err(LexerError::new("Unterminated dollar-quoted string", start_pos));
+            };
+            body.push(ch);
+            if ch == '\n' {
+                self.position.line += 1;
+                self.position.column = 1;
+            } else if body.ends_with(&delimiter) {
+                body.truncate(body.len() - delimiter.len());
+                break;
+            }
+        }
+        let lexeme = format!("{delimiter}{body}{delimiter}");
+        Ok(Some(Token::new(TokenType::StringLiteral(body), &lexeme, start_pos)))
+    }
+
     fn lex_number(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
         let mut number_str = String::new();
         let mut has_dot = false;
 
+
         while let Some(&ch) = self.peek() {
             if ch.is_ascii_digit() {
                 #[allow(clippy::disallowed_methods)]
@@ -459,6 +666,9 @@ impl<'a> Lexer<'a> {
                 has_dot = true;
                 #[allow(clippy::disallowed_methods)]
                 number_str.push(self.advance().unwrap());
+            } else if matches!(ch, 'x' | 'X') && number_str == "0" {
+                self.advance();
+                return self.lex_hex_literal(start_pos);
             } else {
                 break;
             }
@@ -472,9 +682,30 @@ impl<'a> Lexer<'a> {
         } else {
             match number_str.parse::<i64>() {
                 Ok(value) => Ok(Some(Token::new(TokenType::IntegerLiteral(value), &number_str, start_pos))),
-                Err(_) => Err(LexerError::new(format!("Invalid integer literal: {}", number_str), start_pos)),
+                // Too long for 64 bits, such as the MAXVALUE of an Oracle sequence; the lexeme keeps every digit
+                Err(_) => match number_str.parse::<f64>() {
+                    Ok(value) => Ok(Some(Token::new(TokenType::FloatLiteral(value), &number_str, start_pos))),
+                    Err(_) => Err(LexerError::new(format!("Invalid integer literal: {}", number_str), start_pos)),
+                },
+            }
+        }
+    }
+
+    /// Lexes the digits of a `0x` literal once the prefix has been consumed.
+    fn lex_hex_literal(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
+        let mut digits = String::new();
+        while let Some(&ch) = self.peek() {
+            if !ch.is_ascii_hexdigit() {
+                break;
             }
+            digits.push(ch);
+            self.advance();
+        }
+        if digits.is_empty() {
+            return Err(LexerError::new("Invalid hexadecimal literal: 0x", start_pos));
         }
+        let lexeme = format!("0x{}", digits);
+        Ok(Some(Token::new(TokenType::HexLiteral(digits), &lexeme, start_pos)))
     }
 
     fn skip_whitespace_and_comments(&mut self) -> Result<(), LexerError> {
@@ -486,6 +717,7 @@ impl<'a> Lexer<'a> {
                     self.position.column = 1;
                     self.advance();
                 }
+                '-' | '/' if self.keep_comments => break,
                 '-' => {
                     if let Some('-') = self.input.clone().nth(1) {
                         self.skip_single_line_comment()?;
@@ -506,6 +738,41 @@ impl<'a> Lexer<'a> {
         Ok(())
     }
 
+    fn lex_single_line_comment(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
+        self.advance(); // consume second '-'
+        let mut comment = String::from("--");
+
+        while let Some(&ch) = self.peek() {
+            if ch == '\n' {
+                break;
+            }
+            comment.push(ch);
+            self.advance();
+        }
+
+        let comment = comment.trim_end().to_string();
+        Ok(Some(Token::new(TokenType::Comment(comment.clone()), &comment, start_pos)))
+    }
+
+    fn lex_multi_line_comment(&mut self, start_pos: Position) -> Result<Option<Token>, LexerError> {
+        self.advance(); // consume '*'
+        let mut comment = String::from("/*");
+
+        while let Some(ch) = self.advance() {
+            comment.push(ch);
+            if ch == '*' && self.peek() == Some(&'/') {
+                self.advance();
+                comment.push('/');
+                break;
+            } else if ch == '\n' {
+                self.position.line += 1;
+                self.position.column = 1;
+            }
+        }
+
+        Ok(Some(Token::new(TokenType::Comment(comment.clone()), &comment, start_pos)))
+    }
+
     fn skip_single_line_comment(&mut self) -> Result<(), LexerError> {
         self.advance(); // consume first '-'
         self.advance(); // consume second '-'
@@ -547,6 +814,11 @@ impl<'a> Lexer<'a> {
             Some(ch) => {
                 self.position.index += 1;
                 self.position.column += 1;
+                if ch == '\n' {
+                    self.line_blank = true;
+                } else if !ch.is_whitespace() {
+                    self.line_blank = false;
+                }
                 Some(ch)
             }
             None => None,
@@ -563,6 +835,23 @@ impl Token {
         }
     }
 
+    /// The token as SQL text; string literals are re-escaped since their lexeme is not, except dollar-quoted
+    /// ones, whose lexeme is the source text.
+    pub fn to_sql(&self) -> String {
+        match &self.token_type {
+            TokenType::StringLiteral(_) if self.lexeme.starts_with('$') => self.lexeme.clone(),
+            TokenType::StringLiteral(value) => format!("'{}'", value.replace('\'', "''")),
+            _ => self.lexeme.clone(),
+        }
+    }
+
+    /// Whether the token is an SQL keyword (including TRUE/FALSE/NULL) rather than a name,
+    /// literal or symbol.
+    pub fn is_keyword(&self) -> bool {
+        !matches!(self.token_type, TokenType::Identifier(_) | TokenType::Comment(_))
+            && self.lexeme.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+    }
+
     pub fn eof(position: Position) -> Self {
         Self {
             token_type: TokenType::Eof,
@@ -572,6 +861,84 @@ impl Token {
     }
 }
 
+/// Whether SQL text normally puts a space between `previous` and `next`; `before` is the token
+/// preceding `previous`, used to recognise unary minus.
+pub fn spaced(before: Option<&TokenType>, previous: &TokenType, next: &TokenType) -> bool {
+    let operand = |token: Option<&TokenType>| {
+        matches!(
+            token,
+            Some(
+                TokenType::Identifier(_)
+                    | TokenType::QuotedIdentifier(_)
+                    | TokenType::StringLiteral(_)
+                    | TokenType::IntegerLiteral(_)
+                    | TokenType::FloatLiteral(_)
+                    | TokenType::BooleanLiteral(_)
+                    | TokenType::HexLiteral(_)
+                    | TokenType::NullLiteral
+                    | TokenType::Parameter(_)
+                    | TokenType::QuestionMark
+                    | TokenType::RightParen
+                    | TokenType::RightBracket
+            )
+        )
+    };
+
+    match (previous, next) {
+        (TokenType::LeftParen | TokenType::LeftBracket | TokenType::Dot | TokenType::DoubleColon, _) => false,
+        (
+            _,
+            TokenType::RightParen
+            | TokenType::RightBracket
+            | TokenType::Comma
+            | TokenType::Dot
+            | TokenType::Semicolon,
+        ) => false,
+        (_, TokenType::DoubleColon) => false,
+        // SQL Server's `@variable` and MySQL's `@user_variable`
+        (TokenType::AtSign, _) => false,
+        (TokenType::Identifier(name), TokenType::LeftParen) if name.eq_ignore_ascii_case("OVER") => true,
+        // A name before `(` is a call, except where it names a table or index
+        (TokenType::Identifier(_) | TokenType::QuotedIdentifier(_), TokenType::LeftParen) => matches!(
+            before,
+            Some(
+                TokenType::Into
+                    | TokenType::Table
+                    | TokenType::References
+                    | TokenType::On
+                    | TokenType::Index
+                    | TokenType::Key
+                    | TokenType::Unique
+            )
+        ),
+        (TokenType::Cast, TokenType::LeftParen) => false,
+        // Prefixed literals such as `b'1'`, `x'1F'` and `_utf8mb4'text'`
+        (TokenType::Identifier(prefix), TokenType::StringLiteral(_)) => {
+            !(prefix.starts_with('_') || ["B", "X", "N"].contains(&prefix.to_uppercase().as_str()))
+        }
+        (TokenType::Identifier(_) | TokenType::QuotedIdentifier(_) | TokenType::RightParen | TokenType::RightBracket, TokenType::LeftBracket) => false,
+        (TokenType::Minus | TokenType::Plus, _) => operand(before),
+        _ => true,
+    }
+}
+
+/// Joins tokens back into SQL text, spacing them the way they would normally be written.
+pub fn tokens_to_sql(tokens: &[Token]) -> String {
+    let mut text = String::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if index > 0 {
+            let before = index.checked_sub(2).map(|i| &tokens[i].token_type);
+            if spaced(before, &tokens[index - 1].token_type, &token.token_type) {
+                text.push(' ');
+            }
+        }
+        text.push_str(&token.to_sql());
+    }
+
+    text
+}
+
 impl LexerError {
     pub fn new(message: impl Into<String>, position: Position) -> Self {
         Self {
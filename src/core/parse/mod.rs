@@ -1,7 +1,9 @@
 pub mod lexer;
 pub mod parser;
 pub mod dialect;
+pub mod detect;
 
 pub use lexer::*;
 pub use parser::*;
 pub use dialect::*;
+pub use detect::*;
@@ -2,9 +2,25 @@ use std::collections::HashMap;
 use std::iter::Peekable;
 
 use crate::core::ast::*;
-use crate::core::parse::lexer::{Token, TokenType};
+use crate::core::parse::lexer::{tokens_to_sql, Token, TokenType};
 use crate::core::Position;
 
+/// Words that begin a clause but are lexed as plain identifiers, so are never taken as aliases.
+const CLAUSE_WORDS: &[&str] = &["RETURNING", "WINDOW"];
+/// Words that end a multi-word type name because they start a column option instead.
+const TYPE_NAME_TERMINATORS: &[&str] = &[
+    "UNSIGNED", "SIGNED", "ZEROFILL", "BINARY", "COLLATE", "GENERATED", "IDENTITY", "CHARSET",
+    "COMMENT", "VISIBLE", "INVISIBLE", "ROWGUIDCOL", "SPARSE", "ENABLE", "START", "INCREMENT",
+    "MINVALUE", "MAXVALUE", "NOMINVALUE", "NOMAXVALUE", "CYCLE", "NOCYCLE", "CACHE", "NOCACHE",
+    "NOORDER", "KEEP", "NOKEEP", "NO",
+];
+/// Type names that tell a column called `key` or `index` apart from a MySQL inline index.
+const COLUMN_TYPE_WORDS: &[&str] = &[
+    "INT", "INTEGER", "BIGINT", "SMALLINT", "TINYINT", "BOOLEAN", "REAL", "FLOAT", "DOUBLE",
+    "NUMERIC", "DECIMAL", "VARCHAR", "CHAR", "TEXT", "BLOB", "DATE", "DATETIME", "TIMESTAMP",
+    "JSON", "UUID", "ANY",
+];
+
 #[derive(Debug, Clone)]
 pub struct ParserError {
     pub message: String,
@@ -16,6 +32,10 @@ pub struct Parser {
     current_token: Option<Token>,
     peek_token: Option<Token>,
     errors: Vec<ParserError>,
+    /// Where the last consumed token ended, which is where a statement's location ends
+    previous_end: Position,
+    /// Tokens consumed while recording, for keeping a view's query as written
+    recorded: Option<Vec<Token>>,
 }
 
 impl Parser {
@@ -29,15 +49,48 @@ impl Parser {
             current_token,
             peek_token,
             errors: Vec::new(),
+            previous_end: Position::default(),
+            recorded: None,
         }
     }
 
     pub fn parse(&mut self) -> Result<Vec<Statement>, Vec<ParserError>> {
+        self.parse_located().map(|nodes| nodes.into_iter().map(|node| node.node).collect())
+    }
+
+    /// Parses like [`Parser::parse`], keeping where in the input each statement was found.
+    pub fn parse_located(&mut self) -> Result<Vec<AstNode<Statement>>, Vec<ParserError>> {
+        let (statements, errors) = self.parse_recovering();
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses every statement it can, skipping past those that fail; the statements that did
+    /// parse come back along with the errors.
+    pub fn parse_recovering(&mut self) -> (Vec<AstNode<Statement>>, Vec<ParserError>) {
         let mut statements = Vec::new();
 
-        while self.current_token.is_some() {
-            match self.parse_statement() {
-                Ok(stmt) => statements.push(stmt),
+        while let Some(token) = &self.current_token {
+            // Empty statements, such as the `;` left behind by a skipped `/*!...*/` comment
+            if token.token_type == TokenType::Semicolon {
+                self.advance();
+                continue;
+            }
+
+            let start = token.position.clone();
+            self.recorded = None;
+            let parsed = match token.token_type {
+                TokenType::Rename => self.parse_rename_tables(),
+                _ => self.parse_statement().map(|stmt| vec![stmt]),
+            };
+            match parsed {
+                Ok(parsed) => {
+                    let location = Location { start, end: self.previous_end.clone() };
+                    statements.extend(parsed.into_iter().map(|stmt| AstNode::new(stmt).with_location(location.clone())));
+                }
                 Err(err) => {
                     self.errors.push(err);
                     self.synchronize();
@@ -49,16 +102,12 @@ impl Parser {
             }
         }
 
-        if self.errors.is_empty() {
-            Ok(statements)
-        } else {
-            Err(self.errors.clone())
-        }
+        (statements, std::mem::take(&mut self.errors))
     }
 
     fn parse_statement(&mut self) -> Result<Statement, ParserError> {
         match &self.current_token {
-            Some(Token { token_type: TokenType::Select, .. }) => {
+            Some(Token { token_type: TokenType::Select | TokenType::With | TokenType::LeftParen, .. }) => {
                 let select = self.parse_select_statement()?;
                 Ok(Statement::Dml(Box::new(DmlStatement::Select(Box::new(select)))))
             }
@@ -78,6 +127,19 @@ impl Parser {
                 let ddl = self.parse_ddl_statement()?;
                 Ok(Statement::Ddl(ddl))
             }
+            Some(Token { token_type: TokenType::Set, .. }) => {
+                self.advance();
+                Ok(Statement::Session(self.parse_set_statement()?))
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. })
+                if word.eq_ignore_ascii_case("LOCK") || word.eq_ignore_ascii_case("UNLOCK") =>
+            {
+                Ok(Statement::Session(self.parse_lock_statement()?))
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("USE") => {
+                self.advance();
+                Ok(Statement::Session(SessionStatement::Use { database: self.parse_identifier()? }))
+            }
             Some(Token { token_type: TokenType::Alter, .. }) => {
                 let ddl = self.parse_ddl_statement()?;
                 Ok(Statement::Ddl(ddl))
@@ -94,20 +156,158 @@ impl Parser {
         }
     }
 
-    fn parse_select_statement(&mut self) -> Result<SelectStatement, ParserError> {
-        self.expect_token(TokenType::Select)?;
+    fn parse_set_statement(&mut self) -> Result<SessionStatement, ParserError> {
+        // Variable names such as `@@session.sql_mode` span several tokens
+        let mut variable = String::new();
+        while let Some(token) = &self.current_token {
+            if matches!(token.token_type, TokenType::Equal | TokenType::Semicolon)
+                || (!variable.is_empty() && !variable.ends_with(['@', '.']) && token.token_type != TokenType::Dot)
+            {
+                break;
+            }
+            variable.push_str(&token.lexeme);
+            self.advance();
+        }
+        if variable.is_empty() {
+            return Err(self.unexpected_token_error("variable name after SET"));
+        }
 
-        let mut select = SelectStatement::new();
+        // PostgreSQL also spells the assignment `SET search_path TO a, b`
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Equal, .. }) => self.advance(),
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("TO") => self.advance(),
+            _ => {}
+        }
+        let value = self.collect_raw_text(|token| matches!(token, TokenType::Semicolon));
+
+        Ok(SessionStatement::Set { variable, value })
+    }
+
+    /// MySQL's `RENAME TABLE a TO b [, c TO d ...]`, as one `ALTER TABLE a RENAME TO b` for each
+    /// pair, in the order they run.
+    fn parse_rename_tables(&mut self) -> Result<Vec<Statement>, ParserError> {
+        self.expect_token(TokenType::Rename)?;
+        self.expect_token(TokenType::Table)?;
+
+        let mut statements = Vec::new();
+        loop {
+            let table_name = self.parse_object_name()?;
+            self.expect_keyword("TO")?;
+            let new_name = self.parse_object_name()?.object;
+            statements.push(Statement::Ddl(DdlStatement::AlterTable(AlterTableStatement {
+                table_name,
+                if_exists: false,
+                actions: vec![AlterTableAction::RenameTable { new_name }],
+            })));
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        Ok(statements)
+    }
+
+    fn parse_lock_statement(&mut self) -> Result<SessionStatement, ParserError> {
+        let lock = matches!(
+            &self.current_token,
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("LOCK")
+        );
+        self.advance();
+        self.expect_keyword("TABLES")?;
+        if !lock {
+            return Ok(SessionStatement::UnlockTables);
+        }
+
+        let mut tables = Vec::new();
+        loop {
+            tables.push(self.parse_object_name()?.to_string());
+            self.parse_optional_alias()?;
+            // READ [LOCAL] | [LOW_PRIORITY] WRITE
+            while let Some(Token { token_type: TokenType::Identifier(_), .. }) = &self.current_token {
+                self.advance();
+            }
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
 
+        Ok(SessionStatement::LockTables { tables })
+    }
+
+    fn parse_select_statement(&mut self) -> Result<SelectStatement, ParserError> {
         // Handle WITH clause
-        if let Some(Token { token_type: TokenType::With, .. }) = &self.current_token {
-            select.with = self.parse_with_clause()?;
+        let with = if let Some(Token { token_type: TokenType::With, .. }) = &self.current_token {
+            self.parse_with_clause()?
+        } else {
+            Vec::new()
+        };
+
+        let mut select = self.parse_select_core()?;
+
+        // Parse UNION / INTERSECT / EXCEPT
+        let unions = self.parse_set_operations(false)?;
+        if !unions.is_empty() {
+            select = isolated(select);
+            select.unions.extend(unions);
+        }
+
+        // ORDER BY, LIMIT and OFFSET after a parenthesized query that has its own apply on top of it
+        if let Some(Token { token_type: TokenType::Order | TokenType::Limit | TokenType::Offset, .. }) = &self.current_token {
+            select = isolated(select);
+        }
+
+        // The CTEs are visible to every operand of a set operation
+        select.with.splice(0..0, with);
+
+        // Parse ORDER BY
+        if let Some(Token { token_type: TokenType::Order, .. }) = &self.current_token {
+            self.expect_token(TokenType::Order)?;
+            self.expect_token(TokenType::By)?;
+            select.order_by = self.parse_order_by()?;
+        }
+
+        // Parse LIMIT: a count, a bind parameter or ALL
+        if let Some(Token { token_type: TokenType::Limit, .. }) = &self.current_token {
+            self.advance();
+            if let Some(Token { token_type: TokenType::All, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                select.limit = Some(self.parse_expression()?);
+            }
+        }
+
+        // Parse OFFSET
+        if let Some(Token { token_type: TokenType::Offset, .. }) = &self.current_token {
+            self.advance();
+            select.offset = Some(self.parse_expression()?);
+        }
+
+        Ok(select)
+    }
+
+    /// Parses a single SELECT up to and including WINDOW, or a parenthesized query; everything
+    /// after that applies to the whole set operation.
+    fn parse_select_core(&mut self) -> Result<SelectStatement, ParserError> {
+        if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+            self.advance();
+            let select = self.parse_select_statement()?;
+            self.expect_token(TokenType::RightParen)?;
+            return Ok(select);
         }
+        self.expect_token(TokenType::Select)?;
+
+        let mut select = SelectStatement::new();
 
         // Handle DISTINCT
         if let Some(Token { token_type: TokenType::Distinct, .. }) = &self.current_token {
             select.distinct = true;
             self.advance();
+        } else if let Some(Token { token_type: TokenType::All, .. }) = &self.current_token {
+            self.advance();
         }
 
         // Parse SELECT list
@@ -138,28 +338,24 @@ impl Parser {
             select.having = Some(self.parse_expression()?);
         }
 
-        // Parse ORDER BY
-        if let Some(Token { token_type: TokenType::Order, .. }) = &self.current_token {
-            self.expect_token(TokenType::Order)?;
-            self.expect_token(TokenType::By)?;
-            select.order_by = self.parse_order_by()?;
-        }
-
-        // Parse LIMIT
-        if let Some(Token { token_type: TokenType::Limit, .. }) = &self.current_token {
-            self.advance();
-            select.limit = Some(self.parse_limit()?);
-        }
-
-        // Parse OFFSET
-        if let Some(Token { token_type: TokenType::Offset, .. }) = &self.current_token {
+        // Parse WINDOW w AS (...), ...
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && word.eq_ignore_ascii_case("WINDOW")
+        {
             self.advance();
-            select.offset = Some(self.parse_offset()?);
+            loop {
+                let name = self.parse_identifier()?;
+                self.expect_token(TokenType::As)?;
+                let window = self.parse_window_specification()?;
+                select.windows.push(NamedWindow { name, window });
+                if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
         }
 
-        // Parse UNION
-        select.unions = self.parse_unions()?;
-
         Ok(select)
     }
 
@@ -167,13 +363,15 @@ impl Parser {
         self.expect_token(TokenType::With)?;
         let mut ctes = Vec::new();
 
-        loop {
-            let mut recursive = false;
-            if let Some(Token { token_type: TokenType::Recursive, .. }) = &self.current_token {
-                recursive = true;
-                self.advance();
-            }
+        // RECURSIVE applies to the whole WITH list
+        let recursive = if let Some(Token { token_type: TokenType::Recursive, .. }) = &self.current_token {
+            self.advance();
+            true
+        } else {
+            false
+        };
 
+        loop {
             let name = self.parse_identifier()?;
             let columns = if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
                 self.advance();
@@ -185,6 +383,15 @@ impl Parser {
             };
 
             self.expect_token(TokenType::As)?;
+            // PostgreSQL: AS [NOT] MATERIALIZED
+            if let Some(Token { token_type: TokenType::Not, .. }) = &self.current_token {
+                self.advance();
+                self.expect_keyword("MATERIALIZED")?;
+            } else if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+                && word.eq_ignore_ascii_case("MATERIALIZED")
+            {
+                self.advance();
+            }
             self.expect_token(TokenType::LeftParen)?;
             let query = self.parse_select_statement()?;
             self.expect_token(TokenType::RightParen)?;
@@ -212,31 +419,17 @@ impl Parser {
         loop {
             if let Some(Token { token_type: TokenType::Asterisk, .. }) = &self.current_token {
                 self.advance();
-                if let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
-                    self.advance();
-                    let qualifier = items.last()
-                        .and_then(|item| match item {
-                            SelectItem::QualifiedWildcard { qualifier } => Some(qualifier.clone()),
-                            _ => None,
-                        })
-                        .unwrap_or_default();
-                    items.push(SelectItem::QualifiedWildcard { qualifier });
-                } else {
-                    items.push(SelectItem::Wildcard);
-                }
+                items.push(SelectItem::Wildcard);
             } else {
-                let expr = self.parse_expression()?;
-                let alias = if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
-                    self.advance();
-                    Some(self.parse_identifier()?)
-                } else if let Some(Token { token_type: TokenType::Identifier(_), .. }) = &self.current_token {
-                    // Check if it's a potential alias (not a keyword)
-                    Some(self.parse_identifier()?)
-                } else {
-                    None
-                };
-
-                items.push(SelectItem::Expression { expr, alias });
+                match self.parse_expression()? {
+                    Expression::QualifiedColumn { table, column } if column == "*" => {
+                        items.push(SelectItem::QualifiedWildcard { qualifier: table });
+                    }
+                    expr => {
+                        let alias = self.parse_optional_alias()?;
+                        items.push(SelectItem::Expression { expr, alias });
+                    }
+                }
             }
 
             if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
@@ -250,27 +443,36 @@ impl Parser {
     }
 
     fn parse_from_clause(&mut self) -> Result<Vec<TableReference>, ParserError> {
-        let mut tables = vec![self.parse_table_reference()?];
+        let mut tables = Vec::new();
+
+        loop {
+            let table = self.parse_table_reference()?;
+            tables.push(self.parse_joins(table)?);
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        Ok(tables)
+    }
 
-        // Parse JOINs
+    fn parse_joins(&mut self, mut table: TableReference) -> Result<TableReference, ParserError> {
         while let Some(token) = &self.current_token {
             match token.token_type {
                 TokenType::Inner | TokenType::Left | TokenType::Right | TokenType::Full |
                 TokenType::Join | TokenType::Cross => {
                     let join = self.parse_join()?;
-                    if let Some(last_table) = tables.last_mut() {
-                        match last_table {
-                            TableReference::Join { joins, .. } => joins.push(join),
-                            _ => {
-                                // Convert single table to join
-                                let left = Box::new(last_table.clone());
-                                let JoinClause { join_type: _, ref table, condition: _ } = join;
-                                *last_table = TableReference::Join {
-                                    left,
-                                    right: Box::new(table.clone()),
-                                    joins: vec![join],
-                                };
-                            }
+                    match &mut table {
+                        TableReference::Join { joins, .. } => joins.push(join),
+                        _ => {
+                            // Convert single table to join
+                            table = TableReference::Join {
+                                left: Box::new(table.clone()),
+                                right: Box::new(join.table.clone()),
+                                joins: vec![join],
+                            };
                         }
                     }
                 }
@@ -278,23 +480,47 @@ impl Parser {
             }
         }
 
-        Ok(tables)
+        Ok(table)
     }
 
     fn parse_table_reference(&mut self) -> Result<TableReference, ParserError> {
-        let name = self.parse_identifier()?;
-        let alias = if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
+        if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
             self.advance();
-            Some(self.parse_identifier()?)
-        } else if let Some(Token { token_type: TokenType::Identifier(_), .. }) = &self.current_token {
-            Some(self.parse_identifier()?)
-        } else {
-            None
-        };
+            let query = self.parse_select_statement()?;
+            self.expect_token(TokenType::RightParen)?;
+            if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
+                self.advance();
+            }
+            let alias = self.parse_identifier()?;
+            return Ok(TableReference::Subquery {
+                query: Box::new(query),
+                alias,
+            });
+        }
+
+        let name = self.parse_object_name()?;
+        let alias = self.parse_optional_alias()?;
 
         Ok(TableReference::Table { name, alias })
     }
 
+    /// Parses `[AS] alias`; a bare word only counts as an alias when it doesn't start a clause
+    /// the lexer has no keyword for.
+    fn parse_optional_alias(&mut self) -> Result<Option<String>, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::As, .. }) => {
+                self.advance();
+                Ok(Some(self.parse_identifier()?))
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. })
+                if !CLAUSE_WORDS.iter().any(|clause| word.eq_ignore_ascii_case(clause)) =>
+            {
+                Ok(Some(self.parse_identifier()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn parse_join(&mut self) -> Result<JoinClause, ParserError> {
         let join_type = match &self.current_token {
             Some(Token { token_type: TokenType::Inner, .. }) => {
@@ -380,11 +606,11 @@ impl Parser {
     }
 
     fn parse_logical_and(&mut self) -> Result<Expression, ParserError> {
-        let mut left = self.parse_equality()?;
+        let mut left = self.parse_is_test()?;
 
         while let Some(Token { token_type: TokenType::And, .. }) = &self.current_token {
             self.advance();
-            let right = self.parse_equality()?;
+            let right = self.parse_is_test()?;
             left = Expression::BinaryOp {
                 left: Box::new(left),
                 op: BinaryOperator::And,
@@ -395,6 +621,15 @@ impl Parser {
         Ok(left)
     }
 
+    /// `IS [NOT] ...` binds more loosely than comparisons: `a = b IS NOT TRUE` tests `a = b`.
+    fn parse_is_test(&mut self) -> Result<Expression, ParserError> {
+        let mut left = self.parse_equality()?;
+        while let Some(Token { token_type: TokenType::Is, .. }) = &self.current_token {
+            left = self.parse_is(left)?;
+        }
+        Ok(left)
+    }
+
     fn parse_equality(&mut self) -> Result<Expression, ParserError> {
         let mut left = self.parse_comparison()?;
 
@@ -417,7 +652,7 @@ impl Parser {
     }
 
     fn parse_comparison(&mut self) -> Result<Expression, ParserError> {
-        let mut left = self.parse_term()?;
+        let mut left = self.parse_json_access()?;
 
         while let Some(token) = &self.current_token {
             let op = match token.token_type {
@@ -425,56 +660,30 @@ impl Parser {
                 TokenType::LessThanOrEqual => BinaryOperator::Lte,
                 TokenType::GreaterThan => BinaryOperator::Gt,
                 TokenType::GreaterThanOrEqual => BinaryOperator::Gte,
-                TokenType::Like => BinaryOperator::Like,
-                TokenType::Ilike => BinaryOperator::ILike,
-                TokenType::In => {
-                    self.advance();
-                    if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
-                        self.advance();
-                        let values = self.parse_expression_list()?;
-                        self.expect_token(TokenType::RightParen)?;
-                        return Ok(Expression::In {
-                            expr: Box::new(left),
-                            values,
-                        });
-                    } else {
-                        return Err(self.unexpected_token_error("left parenthesis after IN"));
-                    }
-                }
-                TokenType::Between => {
-                    self.advance();
-                    let min = self.parse_term()?;
-                    self.expect_token(TokenType::And)?;
-                    let max = self.parse_term()?;
-                    return Ok(Expression::Between {
-                        expr: Box::new(left),
-                        min: Box::new(min),
-                        max: Box::new(max),
-                    });
+                TokenType::Like | TokenType::Ilike => {
+                    left = self.parse_like(left, false)?;
+                    continue;
                 }
-                TokenType::Is => {
+                TokenType::Not if matches!(
+                    &self.peek_token,
+                    Some(Token { token_type: TokenType::In | TokenType::Like | TokenType::Ilike | TokenType::Between, .. })
+                ) => {
                     self.advance();
-                    if let Some(Token { token_type: TokenType::Not, .. }) = &self.current_token {
-                        self.advance();
-                        let right = self.parse_term()?;
-                        return Ok(Expression::BinaryOp {
-                            left: Box::new(left),
-                            op: BinaryOperator::Neq,
-                            right: Box::new(right),
-                        });
-                    } else {
-                        let right = self.parse_term()?;
-                        return Ok(Expression::BinaryOp {
-                            left: Box::new(left),
-                            op: BinaryOperator::Eq,
-                            right: Box::new(right),
-                        });
+                    match &self.current_token {
+                        Some(Token { token_type: TokenType::In, .. }) => return self.parse_in(left, true),
+                        Some(Token { token_type: TokenType::Between, .. }) => return self.parse_between(left, true),
+                        _ => {
+                            left = self.parse_like(left, true)?;
+                            continue;
+                        }
                     }
                 }
+                TokenType::In => return self.parse_in(left, false),
+                TokenType::Between => return self.parse_between(left, false),
                 _ => break,
             };
             self.advance();
-            let right = self.parse_term()?;
+            let right = self.parse_json_access()?;
             left = Expression::BinaryOp {
                 left: Box::new(left),
                 op,
@@ -485,18 +694,128 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_term(&mut self) -> Result<Expression, ParserError> {
-        let mut left = self.parse_factor()?;
+    fn parse_in(&mut self, left: Expression, negated: bool) -> Result<Expression, ParserError> {
+        self.expect_token(TokenType::In)?;
+        if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+            self.advance();
+            let values = if let Some(Token { token_type: TokenType::Select | TokenType::With, .. }) = &self.current_token {
+                vec![Expression::Subquery(Box::new(self.parse_select_statement()?))]
+            } else {
+                self.parse_expression_list()?
+            };
+            self.expect_token(TokenType::RightParen)?;
+            Ok(if negated {
+                Expression::NotIn { expr: Box::new(left), values }
+            } else {
+                Expression::In { expr: Box::new(left), values }
+            })
+        } else {
+            Err(self.unexpected_token_error("left parenthesis after IN"))
+        }
+    }
+
+    fn parse_between(&mut self, left: Expression, negated: bool) -> Result<Expression, ParserError> {
+        self.expect_token(TokenType::Between)?;
+        let min = Box::new(self.parse_json_access()?);
+        self.expect_token(TokenType::And)?;
+        let max = Box::new(self.parse_json_access()?);
+        let expr = Box::new(left);
+
+        Ok(if negated {
+            Expression::NotBetween { expr, min, max }
+        } else {
+            Expression::Between { expr, min, max }
+        })
+    }
+
+    fn parse_like(&mut self, left: Expression, negated: bool) -> Result<Expression, ParserError> {
+        let op = match (&self.current_token, negated) {
+            (Some(Token { token_type: TokenType::Like, .. }), false) => BinaryOperator::Like,
+            (Some(Token { token_type: TokenType::Like, .. }), true) => BinaryOperator::NotLike,
+            (Some(Token { token_type: TokenType::Ilike, .. }), false) => BinaryOperator::ILike,
+            (Some(Token { token_type: TokenType::Ilike, .. }), true) => BinaryOperator::NotILike,
+            _ => return Err(self.unexpected_token_error("LIKE or ILIKE")),
+        };
+        self.advance();
+        let pattern = self.parse_json_access()?;
+
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && word.eq_ignore_ascii_case("ESCAPE")
+        {
+            self.advance();
+            let escape = self.parse_json_access()?;
+            return Ok(Expression::LikeEscape {
+                expr: Box::new(left),
+                op,
+                pattern: Box::new(pattern),
+                escape: Box::new(escape),
+            });
+        }
+
+        Ok(Expression::BinaryOp {
+            left: Box::new(left),
+            op,
+            right: Box::new(pattern),
+        })
+    }
+
+    fn parse_is(&mut self, left: Expression) -> Result<Expression, ParserError> {
+        self.expect_token(TokenType::Is)?;
+        let negated = if let Some(Token { token_type: TokenType::Not, .. }) = &self.current_token {
+            self.advance();
+            true
+        } else {
+            false
+        };
+
+        match &self.current_token {
+            Some(Token { token_type: TokenType::NullLiteral, .. }) => {
+                self.advance();
+                Ok(Expression::IsNull { expr: Box::new(left), negated })
+            }
+            Some(Token { token_type: TokenType::Distinct, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::From)?;
+                let right = self.parse_json_access()?;
+                Ok(Expression::IsDistinctFrom {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    negated,
+                })
+            }
+            // Unlike `= TRUE`, these are never NULL: `NULL IS NOT TRUE` holds
+            Some(Token { token_type: TokenType::BooleanLiteral(true), .. }) => {
+                self.advance();
+                Ok(Expression::IsTrue { expr: Box::new(left), negated })
+            }
+            Some(Token { token_type: TokenType::BooleanLiteral(false), .. }) => {
+                self.advance();
+                Ok(Expression::IsFalse { expr: Box::new(left), negated })
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("UNKNOWN") => {
+                self.advance();
+                Ok(Expression::IsNull { expr: Box::new(left), negated })
+            }
+            _ => Err(self.unexpected_token_error("NULL, TRUE, FALSE, UNKNOWN or DISTINCT FROM after IS")),
+        }
+    }
+
+    /// PostgreSQL's JSON operators and `||`, which share a precedence level between the
+    /// comparisons and `+`/`-` and group from the left.
+    fn parse_json_access(&mut self) -> Result<Expression, ParserError> {
+        let mut left = self.parse_term()?;
 
         while let Some(token) = &self.current_token {
             let op = match token.token_type {
-                TokenType::Plus => BinaryOperator::Plus,
-                TokenType::Minus => BinaryOperator::Minus,
                 TokenType::Concat => BinaryOperator::Concat,
+                TokenType::Arrow => BinaryOperator::JsonGet,
+                TokenType::LongArrow => BinaryOperator::JsonGetText,
+                TokenType::HashArrow => BinaryOperator::JsonPath,
+                TokenType::HashLongArrow => BinaryOperator::JsonPathText,
                 _ => break,
             };
             self.advance();
-            let right = self.parse_factor()?;
+            let right = self.parse_term()?;
             left = Expression::BinaryOp {
                 left: Box::new(left),
                 op,
@@ -507,9 +826,30 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_factor(&mut self) -> Result<Expression, ParserError> {
-        let mut left = self.parse_unary()?;
-
+    fn parse_term(&mut self) -> Result<Expression, ParserError> {
+        let mut left = self.parse_factor()?;
+
+        while let Some(token) = &self.current_token {
+            let op = match token.token_type {
+                TokenType::Plus => BinaryOperator::Plus,
+                TokenType::Minus => BinaryOperator::Minus,
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_factor()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_factor(&mut self) -> Result<Expression, ParserError> {
+        let mut left = self.parse_unary()?;
+
         while let Some(token) = &self.current_token {
             let op = match token.token_type {
                 TokenType::Asterisk => BinaryOperator::Multiply,
@@ -567,7 +907,34 @@ impl Parser {
             }
         }
 
-        self.parse_primary()
+        self.parse_postfix()
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, ParserError> {
+        let mut expr = self.parse_primary()?;
+
+        loop {
+            match &self.current_token {
+                Some(Token { token_type: TokenType::DoubleColon, .. }) => {
+                    self.advance();
+                    let data_type = self.parse_data_type()?;
+                    expr = Expression::Cast {
+                        expr: Box::new(expr),
+                        data_type,
+                    };
+                }
+                Some(Token { token_type: TokenType::LeftBracket, .. }) => {
+                    self.advance();
+                    let index = self.parse_expression()?;
+                    self.expect_token(TokenType::RightBracket)?;
+                    expr = Expression::Subscript {
+                        expr: Box::new(expr),
+                        index: Box::new(index),
+                    };
+                }
+                _ => return Ok(expr),
+            }
+        }
     }
 
     fn parse_primary(&mut self) -> Result<Expression, ParserError> {
@@ -597,6 +964,55 @@ impl Parser {
                 self.advance();
                 Ok(expr)
             }
+            Some(Token { token_type: TokenType::HexLiteral(digits), .. }) => {
+                let expr = Expression::Literal(LiteralValue::Binary(decode_hex(digits)));
+                self.advance();
+                Ok(expr)
+            }
+            // MySQL's prefixed string literals: `x'1F'`, `b'101'`, `N'text'` and `_utf8mb4'text'`
+            Some(Token { token_type: TokenType::Identifier(prefix), .. })
+                if matches!(&self.peek_token, Some(Token { token_type: TokenType::StringLiteral(_), .. }))
+                    && (prefix.starts_with('_') || ["B", "X", "N"].contains(&prefix.to_uppercase().as_str())) =>
+            {
+                let prefix = prefix.to_uppercase();
+                self.advance();
+                let Some(Token { token_type: TokenType::StringLiteral(value), .. }) = &self.current_token else {
+                    return Err(self.unexpected_token_error("string literal"));
+                };
+                let literal = match prefix.as_str() {
+                    "X" => LiteralValue::Binary(decode_hex(value)),
+                    "B" => match i64::from_str_radix(value, 2) {
+                        Ok(bits) => LiteralValue::Integer(bits),
+                        Err(_) => return Err(self.unexpected_token_error("bit string")),
+                    },
+                    _ => LiteralValue::String(value.clone()),
+                };
+                self.advance();
+                Ok(Expression::Literal(literal))
+            }
+            Some(Token { token_type: TokenType::Parameter(name), .. }) => {
+                let expr = Expression::Parameter(name.clone());
+                self.advance();
+                Ok(expr)
+            }
+            Some(Token { token_type: TokenType::QuestionMark, .. }) => {
+                self.advance();
+                Ok(Expression::Parameter("?".to_string()))
+            }
+            Some(Token { token_type: TokenType::Identifier(name), .. })
+                if name.eq_ignore_ascii_case("ARRAY")
+                    && matches!(&self.peek_token, Some(Token { token_type: TokenType::LeftBracket, .. })) =>
+            {
+                self.advance();
+                self.advance();
+                let elements = if let Some(Token { token_type: TokenType::RightBracket, .. }) = &self.current_token {
+                    Vec::new()
+                } else {
+                    self.parse_expression_list()?
+                };
+                self.expect_token(TokenType::RightBracket)?;
+                Ok(Expression::Array(elements))
+            }
             Some(Token { token_type: TokenType::Identifier(name), .. }) => {
                 let identifier = name.clone();
                 self.advance();
@@ -607,48 +1023,79 @@ impl Parser {
                     let args = if let Some(Token { token_type: TokenType::RightParen, .. }) = &self.current_token {
                         self.advance();
                         Vec::new()
+                    } else if let Some(Token { token_type: TokenType::Asterisk, .. }) = &self.current_token {
+                        self.advance();
+                        self.expect_token(TokenType::RightParen)?;
+                        vec![Expression::Wildcard]
                     } else {
                         let args = self.parse_expression_list()?;
                         self.expect_token(TokenType::RightParen)?;
                         args
                     };
-                    Ok(Expression::Function {
+                    let function = Expression::Function {
                         name: identifier,
                         args,
-                    })
-                } else if let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
-                    // Qualified identifier
-                    self.advance();
-                    let column = self.parse_identifier()?;
-                    Ok(Expression::QualifiedColumn {
-                        table: identifier,
-                        column,
-                    })
+                    };
+
+                    if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+                        && word.eq_ignore_ascii_case("OVER")
+                    {
+                        self.advance();
+                        let window = match &self.current_token {
+                            // `OVER w` names a window of the WINDOW clause
+                            Some(Token { token_type: TokenType::Identifier(name), .. }) => {
+                                let window = WindowSpecification::named(name);
+                                self.advance();
+                                window
+                            }
+                            _ => self.parse_window_specification()?,
+                        };
+                        return Ok(Expression::WindowFunction {
+                            function: Box::new(function),
+                            window,
+                        });
+                    }
+
+                    Ok(function)
                 } else {
-                    Ok(Expression::Column(identifier))
+                    self.parse_column_reference(identifier)
                 }
             }
             Some(Token { token_type: TokenType::QuotedIdentifier(name), .. }) => {
                 let identifier = name.clone();
                 self.advance();
-
-                if let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
-                    self.advance();
-                    let column = self.parse_identifier()?;
-                    Ok(Expression::QualifiedColumn {
-                        table: identifier,
-                        column,
-                    })
-                } else {
-                    Ok(Expression::Column(identifier))
-                }
+                self.parse_column_reference(identifier)
             }
             Some(Token { token_type: TokenType::LeftParen, .. }) => {
                 self.advance();
-                let expr = self.parse_expression()?;
+                let expr = if let Some(Token { token_type: TokenType::Select | TokenType::With, .. }) = &self.current_token {
+                    Expression::Subquery(Box::new(self.parse_select_statement()?))
+                } else {
+                    self.parse_expression()?
+                };
                 self.expect_token(TokenType::RightParen)?;
                 Ok(expr)
             }
+            Some(Token { token_type: TokenType::Values, .. })
+                if matches!(&self.peek_token, Some(Token { token_type: TokenType::LeftParen, .. })) =>
+            {
+                // MySQL `VALUES(col)` in ON DUPLICATE KEY UPDATE
+                self.advance();
+                self.advance();
+                let args = self.parse_expression_list()?;
+                self.expect_token(TokenType::RightParen)?;
+                Ok(Expression::Function {
+                    name: "VALUES".to_string(),
+                    args,
+                })
+            }
+            Some(Token { token_type: TokenType::Exists, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::LeftParen)?;
+                let query = self.parse_select_statement()?;
+                self.expect_token(TokenType::RightParen)?;
+                Ok(Expression::Exists(Box::new(query)))
+            }
             Some(Token { token_type: TokenType::Case, .. }) => {
                 self.parse_case_expression()
             }
@@ -663,6 +1110,106 @@ impl Parser {
         }
     }
 
+    fn parse_window_specification(&mut self) -> Result<WindowSpecification, ParserError> {
+        self.expect_token(TokenType::LeftParen)?;
+        let mut window = WindowSpecification {
+            name: None,
+            partition_by: Vec::new(),
+            order_by: Vec::new(),
+            frame: None,
+        };
+
+        // `(w ORDER BY ...)` builds on a named window
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && !["PARTITION", "ROWS", "RANGE", "GROUPS"].iter().any(|keyword| word.eq_ignore_ascii_case(keyword))
+        {
+            window.name = Some(word.clone());
+            self.advance();
+        }
+
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && word.eq_ignore_ascii_case("PARTITION")
+        {
+            self.advance();
+            self.expect_token(TokenType::By)?;
+            window.partition_by = self.parse_expression_list()?;
+        }
+
+        if let Some(Token { token_type: TokenType::Order, .. }) = &self.current_token {
+            self.advance();
+            self.expect_token(TokenType::By)?;
+            window.order_by = self.parse_order_by()?;
+        }
+
+        let units = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("ROWS") => {
+                Some(WindowFrameUnits::Rows)
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("RANGE") => {
+                Some(WindowFrameUnits::Range)
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("GROUPS") => {
+                Some(WindowFrameUnits::Groups)
+            }
+            _ => None,
+        };
+        if let Some(units) = units {
+            self.advance();
+            window.frame = Some(if let Some(Token { token_type: TokenType::Between, .. }) = &self.current_token {
+                self.advance();
+                let start = self.parse_frame_bound()?;
+                self.expect_token(TokenType::And)?;
+                let end = self.parse_frame_bound()?;
+                WindowFrame { units, start, end: Some(end) }
+            } else {
+                WindowFrame { units, start: self.parse_frame_bound()?, end: None }
+            });
+        }
+
+        self.expect_token(TokenType::RightParen)?;
+        Ok(window)
+    }
+
+    fn parse_frame_bound(&mut self) -> Result<FrameBound, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("UNBOUNDED") => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("PRECEDING") => {
+                        self.advance();
+                        Ok(FrameBound::UnboundedPreceding)
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("FOLLOWING") => {
+                        self.advance();
+                        Ok(FrameBound::UnboundedFollowing)
+                    }
+                    _ => Err(self.unexpected_token_error("PRECEDING or FOLLOWING")),
+                }
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("CURRENT") => {
+                self.advance();
+                self.expect_keyword("ROW")?;
+                Ok(FrameBound::CurrentRow)
+            }
+            Some(Token { token_type: TokenType::IntegerLiteral(value), .. }) => {
+                let offset = *value as u64;
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("PRECEDING") => {
+                        self.advance();
+                        Ok(FrameBound::Preceding(offset))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("FOLLOWING") => {
+                        self.advance();
+                        Ok(FrameBound::Following(offset))
+                    }
+                    _ => Err(self.unexpected_token_error("PRECEDING or FOLLOWING")),
+                }
+            }
+            _ => Err(self.unexpected_token_error("window frame bound")),
+        }
+    }
+
     fn parse_case_expression(&mut self) -> Result<Expression, ParserError> {
         self.expect_token(TokenType::Case)?;
         let operand = if let Some(Token { token_type: TokenType::When, .. }) = &self.current_token {
@@ -709,20 +1256,84 @@ impl Parser {
         })
     }
 
+    /// Parses a type name as written in CAST or `::`, including arguments, multi-word names
+    /// and array suffixes, e.g. `numeric(10, 2)`, `timestamp with time zone` or `text[]`.
     fn parse_data_type(&mut self) -> Result<String, ParserError> {
-        // Simple implementation - just parse identifiers
-        match &self.current_token {
-            Some(Token { token_type: TokenType::Identifier(name), .. }) => {
-                let type_name = name.clone();
+        let mut type_name = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(name), .. }) => name.clone(),
+            Some(token) => {
+                return Err(ParserError::new(
+                    format!("Expected data type, found {:?}", token.token_type),
+                    token.position.clone(),
+                ))
+            }
+            None => return Err(ParserError::new("Unexpected end of input in data type".to_string(), Position::default())),
+        };
+        self.advance();
+        while let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
+            self.advance();
+            type_name = format!("{}.{}", type_name, self.parse_identifier()?);
+        }
+
+        if let Some(Token { token_type: TokenType::Identifier(next), .. }) = &self.current_token
+            && ((type_name.eq_ignore_ascii_case("DOUBLE") && next.eq_ignore_ascii_case("PRECISION"))
+                || (type_name.eq_ignore_ascii_case("CHARACTER") && next.eq_ignore_ascii_case("VARYING")))
+        {
+            type_name = format!("{} {}", type_name, next);
+            self.advance();
+        }
+
+        if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+            type_name.push_str(&self.collect_parenthesized_text()?);
+        }
+
+        if type_name.eq_ignore_ascii_case("TIMESTAMP") || type_name.eq_ignore_ascii_case("TIME") {
+            let with = match &self.current_token {
+                Some(Token { token_type: TokenType::With, lexeme, .. }) => Some(lexeme.clone()),
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("WITHOUT") => {
+                    Some(word.clone())
+                }
+                _ => None,
+            };
+            if let Some(with) = with {
                 self.advance();
-                Ok(type_name)
+                self.expect_keyword("TIME")?;
+                self.expect_keyword("ZONE")?;
+                let zone = if with.chars().all(|ch| ch.is_lowercase()) { "time zone" } else { "TIME ZONE" };
+                type_name = format!("{} {} {}", type_name, with, zone);
             }
-            Some(token) => Err(ParserError::new(
-                format!("Expected data type, found {:?}", token.token_type),
-                token.position.clone(),
-            )),
-            None => Err(ParserError::new("Unexpected end of input in data type".to_string(), Position::default())),
         }
+
+        while let Some(Token { token_type: TokenType::LeftBracket, .. }) = &self.current_token
+            && let Some(Token { token_type: TokenType::RightBracket, .. }) = &self.peek_token
+        {
+            self.advance();
+            self.advance();
+            type_name.push_str("[]");
+        }
+
+        Ok(type_name)
+    }
+
+    /// Parses the rest of a column reference after its first name: `o.total`,
+    /// `sales.orders.total`, or `o.*`, whose column is `*`.
+    fn parse_column_reference(&mut self, first: String) -> Result<Expression, ParserError> {
+        let mut parts = vec![first];
+        while let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
+            self.advance();
+            if let Some(Token { token_type: TokenType::Asterisk, .. }) = &self.current_token {
+                self.advance();
+                parts.push("*".to_string());
+                break;
+            }
+            parts.push(self.parse_identifier()?);
+        }
+
+        let column = parts.pop().unwrap_or_default();
+        if parts.is_empty() {
+            return Ok(Expression::Column(column));
+        }
+        Ok(Expression::QualifiedColumn { table: parts.join("."), column })
     }
 
     fn parse_identifier(&mut self) -> Result<String, ParserError> {
@@ -745,6 +1356,19 @@ impl Parser {
         }
     }
 
+    /// Parses a possibly qualified name: `orders`, `sales.orders`, `[shop].[dbo].[Orders]`.
+    fn parse_object_name(&mut self) -> Result<SchemaReference, ParserError> {
+        let position = self.current_token.as_ref().map(|token| token.position.clone()).unwrap_or_default();
+        let mut parts = vec![self.parse_identifier()?];
+        while let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
+            self.advance();
+            parts.push(self.parse_identifier()?);
+        }
+        SchemaReference::from_parts(&parts).ok_or_else(|| {
+            ParserError::new(format!("Too many qualifiers in name {}", parts.join(".")), position)
+        })
+    }
+
     fn parse_identifier_list(&mut self) -> Result<Vec<String>, ParserError> {
         let mut identifiers = Vec::new();
 
@@ -807,10 +1431,29 @@ impl Parser {
                 _ => true, // Default to ascending
             };
 
+            let nulls_first = if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+                && word.eq_ignore_ascii_case("NULLS")
+            {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("FIRST") => {
+                        self.advance();
+                        Some(true)
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("LAST") => {
+                        self.advance();
+                        Some(false)
+                    }
+                    _ => return Err(self.unexpected_token_error("FIRST or LAST after NULLS")),
+                }
+            } else {
+                None
+            };
+
             order_by.push(OrderByClause {
                 expr,
                 ascending,
-                nulls_first: None, // Not implemented yet
+                nulls_first,
             });
 
             if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
@@ -823,55 +1466,43 @@ impl Parser {
         Ok(order_by)
     }
 
-    fn parse_limit(&mut self) -> Result<u64, ParserError> {
-        match &self.current_token {
-            Some(Token { token_type: TokenType::IntegerLiteral(value), .. }) => {
-                let limit = *value as u64;
-                self.advance();
-                Ok(limit)
-            }
-            Some(token) => Err(ParserError::new(
-                format!("Expected integer for LIMIT, found {:?}", token.token_type),
-                token.position.clone(),
-            )),
-            None => Err(ParserError::new("Unexpected end of input in LIMIT".to_string(), Position::default())),
-        }
-    }
-
-    fn parse_offset(&mut self) -> Result<u64, ParserError> {
-        match &self.current_token {
-            Some(Token { token_type: TokenType::IntegerLiteral(value), .. }) => {
-                let offset = *value as u64;
-                self.advance();
-                Ok(offset)
-            }
-            Some(token) => Err(ParserError::new(
-                format!("Expected integer for OFFSET, found {:?}", token.token_type),
-                token.position.clone(),
-            )),
-            None => Err(ParserError::new("Unexpected end of input in OFFSET".to_string(), Position::default())),
-        }
-    }
-
-    fn parse_unions(&mut self) -> Result<Vec<UnionClause>, ParserError> {
+    /// Parses the set operations that follow a query. INTERSECT binds tighter than UNION and
+    /// EXCEPT, so the INTERSECTs after a UNION or EXCEPT are grouped into its operand; the
+    /// operations are otherwise applied left to right. With `intersect_only`, stops at the first
+    /// UNION or EXCEPT.
+    fn parse_set_operations(&mut self, intersect_only: bool) -> Result<Vec<UnionClause>, ParserError> {
         let mut unions = Vec::new();
 
         while let Some(token) = &self.current_token {
-            let all = match token.token_type {
-                TokenType::Union => {
+            let op = match token.token_type {
+                TokenType::Union if !intersect_only => SetOperator::Union,
+                TokenType::Intersect => SetOperator::Intersect,
+                TokenType::Except if !intersect_only => SetOperator::Except,
+                _ => break,
+            };
+            self.advance();
+
+            let all = match &self.current_token {
+                Some(Token { token_type: TokenType::All, .. }) => {
                     self.advance();
-                    if let Some(Token { token_type: TokenType::All, .. }) = &self.current_token {
-                        self.advance();
-                        true
-                    } else {
-                        false
-                    }
+                    true
                 }
-                _ => break,
+                Some(Token { token_type: TokenType::Distinct, .. }) => {
+                    self.advance();
+                    false
+                }
+                _ => false,
             };
 
-            let select = self.parse_select_statement()?;
-            unions.push(UnionClause { select, all });
+            let mut select = self.parse_select_core()?;
+            if op != SetOperator::Intersect {
+                let intersections = self.parse_set_operations(true)?;
+                if !intersections.is_empty() {
+                    select = isolated(select);
+                    select.unions.extend(intersections);
+                }
+            }
+            unions.push(UnionClause { op, select, all });
         }
 
         Ok(unions)
@@ -881,7 +1512,7 @@ impl Parser {
         self.expect_token(TokenType::Insert)?;
         self.expect_token(TokenType::Into)?;
 
-        let table_name = self.parse_identifier()?;
+        let table_name = self.parse_object_name()?;
         let columns = if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
             self.advance();
             let cols = self.parse_identifier_list()?;
@@ -894,57 +1525,143 @@ impl Parser {
         let (values, select) = if let Some(Token { token_type: TokenType::Values, .. }) = &self.current_token {
             self.advance();
             (self.parse_values_clause()?, None)
-        } else if let Some(Token { token_type: TokenType::Select, .. }) = &self.current_token {
+        } else if let Some(Token { token_type: TokenType::Select | TokenType::With, .. }) = &self.current_token {
             (Vec::new(), Some(self.parse_select_statement()?))
         } else {
             return Err(self.unexpected_token_error("VALUES or SELECT"));
         };
 
+        let on_conflict = if let Some(Token { token_type: TokenType::On, .. }) = &self.current_token {
+            self.advance();
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
+
         Ok(InsertStatement {
             table_name,
             columns,
             values,
             select,
-            on_conflict: None, // Not implemented yet
-            returning: Vec::new(), // Not implemented yet
+            on_conflict,
+            returning: self.parse_returning()?,
         })
     }
 
-    fn parse_values_clause(&mut self) -> Result<Vec<Vec<Expression>>, ParserError> {
-        let mut value_lists = Vec::new();
+    /// Parses what follows `ON` in an upsert: PostgreSQL `CONFLICT [target] DO ...` or MySQL
+    /// `DUPLICATE KEY UPDATE ...`.
+    fn parse_on_conflict(&mut self) -> Result<OnConflictClause, ParserError> {
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && word.eq_ignore_ascii_case("DUPLICATE")
+        {
+            self.advance();
+            self.expect_token(TokenType::Key)?;
+            self.expect_token(TokenType::Update)?;
+            return Ok(OnConflictClause {
+                target: None,
+                action: ConflictAction::DoUpdate {
+                    assignments: self.parse_assignments()?,
+                    where_clause: None,
+                },
+            });
+        }
 
-        loop {
-            self.expect_token(TokenType::LeftParen)?;
-            let values = self.parse_expression_list()?;
-            self.expect_token(TokenType::RightParen)?;
-            value_lists.push(values);
+        self.expect_keyword("CONFLICT")?;
+        let target = match &self.current_token {
+            Some(Token { token_type: TokenType::LeftParen, .. }) => {
+                self.advance();
+                let columns = self.parse_identifier_list()?;
+                self.expect_token(TokenType::RightParen)?;
+                let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
+                    self.advance();
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+                Some(ConflictTarget::Columns { columns, where_clause })
+            }
+            Some(Token { token_type: TokenType::On, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::Constraint)?;
+                Some(ConflictTarget::OnConstraint(self.parse_identifier()?))
+            }
+            _ => None,
+        };
 
-            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+        self.expect_keyword("DO")?;
+        let action = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("NOTHING") => {
                 self.advance();
-            } else {
-                break;
+                ConflictAction::DoNothing
             }
-        }
+            Some(Token { token_type: TokenType::Update, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::Set)?;
+                let assignments = self.parse_assignments()?;
+                let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
+                    self.advance();
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+                ConflictAction::DoUpdate { assignments, where_clause }
+            }
+            _ => return Err(self.unexpected_token_error("NOTHING or UPDATE after DO")),
+        };
+
+        Ok(OnConflictClause { target, action })
+    }
+
+    fn parse_returning(&mut self) -> Result<Vec<SelectItem>, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("RETURNING") => {
+                self.advance();
+                self.parse_select_list()
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn parse_values_clause(&mut self) -> Result<Vec<Vec<Expression>>, ParserError> {
+        let mut value_lists = Vec::new();
+
+        loop {
+            self.expect_token(TokenType::LeftParen)?;
+            let values = self.parse_expression_list()?;
+            self.expect_token(TokenType::RightParen)?;
+            value_lists.push(values);
+
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
 
         Ok(value_lists)
     }
 
     fn parse_update_statement(&mut self) -> Result<UpdateStatement, ParserError> {
         self.expect_token(TokenType::Update)?;
-        let table_name = self.parse_identifier()?;
-        let alias = if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
-            self.advance();
-            Some(self.parse_identifier()?)
-        } else if let Some(Token { token_type: TokenType::Identifier(_), .. }) = &self.current_token {
-            Some(self.parse_identifier()?)
-        } else {
-            None
+        let table_name = self.parse_object_name()?;
+        let alias = self.parse_optional_alias()?;
+
+        // MySQL multi-table UPDATE joins before SET; the joined tables, target included, land in
+        // `from`
+        let target = TableReference::Table { name: table_name.clone(), alias: alias.clone() };
+        let mut from = match self.parse_joins(target)? {
+            joined @ TableReference::Join { .. } => vec![joined],
+            _ => Vec::new(),
         };
 
         self.expect_token(TokenType::Set)?;
         let assignments = self.parse_assignments()?;
 
-        let from = Vec::new(); // Not implemented yet
+        if let Some(Token { token_type: TokenType::From, .. }) = &self.current_token {
+            self.advance();
+            from.extend(self.parse_from_clause()?);
+        }
+
         let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
             self.advance();
             Some(self.parse_expression()?)
@@ -958,18 +1675,22 @@ impl Parser {
             assignments,
             from,
             where_clause,
-            returning: Vec::new(), // Not implemented yet
+            returning: self.parse_returning()?,
         })
     }
 
-    fn parse_assignments(&mut self) -> Result<HashMap<String, Expression>, ParserError> {
-        let mut assignments = HashMap::new();
+    fn parse_assignments(&mut self) -> Result<Vec<Assignment>, ParserError> {
+        let mut assignments = Vec::new();
 
         loop {
-            let column = self.parse_identifier()?;
+            let mut column = self.parse_identifier()?;
+            if let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
+                self.advance();
+                column = format!("{}.{}", column, self.parse_identifier()?);
+            }
             self.expect_token(TokenType::Equal)?;
             let value = self.parse_expression()?;
-            assignments.insert(column, value);
+            assignments.push(Assignment { column, value });
 
             if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
                 self.advance();
@@ -983,17 +1704,24 @@ impl Parser {
 
     fn parse_delete_statement(&mut self) -> Result<DeleteStatement, ParserError> {
         self.expect_token(TokenType::Delete)?;
-        self.expect_token(TokenType::From)?;
-        let table_name = self.parse_identifier()?;
-        let alias = if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
+
+        let (table_name, alias, mut using) = if let Some(Token { token_type: TokenType::From, .. }) = &self.current_token {
             self.advance();
-            Some(self.parse_identifier()?)
-        } else if let Some(Token { token_type: TokenType::Identifier(_), .. }) = &self.current_token {
-            Some(self.parse_identifier()?)
+            let table_name = self.parse_object_name()?;
+            let alias = self.parse_optional_alias()?;
+            (table_name, alias, Vec::new())
         } else {
-            None
+            // MySQL `DELETE t FROM t JOIN ...`: the tables after FROM go in `using`
+            let table_name = self.parse_object_name()?;
+            self.expect_token(TokenType::From)?;
+            (table_name, None, self.parse_from_clause()?)
         };
 
+        if let Some(Token { token_type: TokenType::Using, .. }) = &self.current_token {
+            self.advance();
+            using.extend(self.parse_from_clause()?);
+        }
+
         let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
             self.advance();
             Some(self.parse_expression()?)
@@ -1004,175 +1732,2214 @@ impl Parser {
         Ok(DeleteStatement {
             table_name,
             alias,
-            using: Vec::new(), // Not implemented yet
+            using,
             where_clause,
-            returning: Vec::new(), // Not implemented yet
+            returning: self.parse_returning()?,
         })
     }
 
-    fn parse_ddl_statement(&mut self) -> Result<DdlStatement, ParserError> {
-        match &self.current_token {
-            Some(Token { token_type: TokenType::Create, .. }) => {
+    fn parse_ddl_statement(&mut self) -> Result<DdlStatement, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Create, .. }) => {
+                self.advance();
+                let or_replace = self.parse_create_prefix()?;
+                // PostgreSQL's `CONSTRAINT TRIGGER` and SQLite's `TEMP TRIGGER` are triggers all the same
+                if let Some(Token { token_type: TokenType::Constraint | TokenType::Identifier(_), lexeme, .. }) =
+                    &self.current_token
+                    && ["CONSTRAINT", "TEMP", "TEMPORARY"].iter().any(|word| lexeme.eq_ignore_ascii_case(word))
+                    && matches!(&self.peek_token, Some(Token { token_type: TokenType::Identifier(word), .. })
+                        if word.eq_ignore_ascii_case("TRIGGER"))
+                {
+                    self.advance();
+                }
+                if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token {
+                    match word.to_uppercase().as_str() {
+                        "TRIGGER" => {
+                            self.advance();
+                            return Ok(DdlStatement::CreateTrigger(self.parse_create_trigger(or_replace)?));
+                        }
+                        "FUNCTION" => {
+                            self.advance();
+                            let routine = self.parse_create_routine(RoutineKind::Function, or_replace)?;
+                            return Ok(DdlStatement::CreateRoutine(routine));
+                        }
+                        "PROCEDURE" | "PROC" => {
+                            self.advance();
+                            let routine = self.parse_create_routine(RoutineKind::Procedure, or_replace)?;
+                            return Ok(DdlStatement::CreateRoutine(routine));
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(view) = self.parse_create_view(or_replace)? {
+                    return Ok(DdlStatement::CreateView(view));
+                }
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Table, .. }) => {
+                        self.advance();
+                        let table = self.parse_create_table()?;
+                        Ok(DdlStatement::CreateTable(table))
+                    }
+                    Some(Token { token_type: TokenType::Unique, .. }) => {
+                        self.advance();
+                        self.skip_clustering();
+                        self.expect_token(TokenType::Index)?;
+                        let index = self.parse_create_index(true)?;
+                        Ok(DdlStatement::CreateIndex(index))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. })
+                        if (word.eq_ignore_ascii_case("CLUSTERED") || word.eq_ignore_ascii_case("NONCLUSTERED"))
+                            && matches!(&self.peek_token, Some(Token { token_type: TokenType::Index, .. })) =>
+                    {
+                        self.advance();
+                        self.advance();
+                        let index = self.parse_create_index(false)?;
+                        Ok(DdlStatement::CreateIndex(index))
+                    }
+                    Some(Token { token_type: TokenType::Index, .. }) => {
+                        self.advance();
+                        let index = self.parse_create_index(false)?;
+                        Ok(DdlStatement::CreateIndex(index))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. })
+                        if matches!(&self.peek_token, Some(Token { token_type: TokenType::Index, .. }))
+                            && index_kind(word).is_some() =>
+                    {
+                        let kind = index_kind(word).unwrap_or_default();
+                        self.advance();
+                        self.advance();
+                        let mut index = self.parse_create_index(false)?;
+                        index.kind = kind;
+                        Ok(DdlStatement::CreateIndex(index))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("TYPE") => {
+                        self.advance();
+                        let create_type = self.parse_create_type()?;
+                        Ok(DdlStatement::CreateType(create_type))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("DOMAIN") => {
+                        self.advance();
+                        let domain = self.parse_create_domain()?;
+                        Ok(DdlStatement::CreateDomain(domain))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("SEQUENCE") => {
+                        self.advance();
+                        let sequence = self.parse_create_sequence()?;
+                        Ok(DdlStatement::CreateSequence(sequence))
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. })
+                        if word.eq_ignore_ascii_case("SCHEMA") || word.eq_ignore_ascii_case("DATABASE") =>
+                    {
+                        self.advance();
+                        let schema = self.parse_create_schema()?;
+                        Ok(DdlStatement::CreateSchema(schema))
+                    }
+                    _ => Err(self.unexpected_token_error(
+                        "TABLE, INDEX, VIEW, TYPE, DOMAIN, SEQUENCE, SCHEMA, TRIGGER, FUNCTION or PROCEDURE after CREATE",
+                    )),
+                }
+            }
+            Some(Token { token_type: TokenType::Alter, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::Table)?;
+                Ok(DdlStatement::AlterTable(self.parse_alter_table()?))
+            }
+            Some(Token { token_type: TokenType::Drop, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Table, .. }) => {
+                        self.advance();
+                        Ok(DdlStatement::DropTable(self.parse_drop_table()?))
+                    }
+                    Some(Token { token_type: TokenType::Index, .. }) => {
+                        self.advance();
+                        Ok(DdlStatement::DropIndex(self.parse_drop_index()?))
+                    }
+                    _ => Err(self.unexpected_token_error("TABLE or INDEX after DROP")),
+                }
+            }
+            _ => Err(self.unexpected_token_error("DDL statement")),
+        }
+    }
+
+    /// What may come between CREATE and the kind of object: `OR REPLACE` (SQL Server's `OR
+    /// ALTER`), and MySQL's `ALGORITHM`, `DEFINER` and `SQL SECURITY`, which are skipped. Returns
+    /// whether the object replaces an existing one.
+    fn parse_create_prefix(&mut self) -> Result<bool, ParserError> {
+        let or_replace = if let Some(Token { token_type: TokenType::Or, .. }) = &self.current_token {
+            self.advance();
+            if let Some(Token { token_type: TokenType::Alter, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                self.expect_keyword("REPLACE")?;
+            }
+            true
+        } else {
+            false
+        };
+        while let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token {
+            if word.eq_ignore_ascii_case("ALGORITHM") || word.eq_ignore_ascii_case("DEFINER") {
+                // `ALGORITHM=UNDEFINED`, `DEFINER=`root`@`localhost``
+                self.advance();
+                self.expect_token(TokenType::Equal)?;
+                self.advance();
+                while let Some(Token { token_type: TokenType::AtSign | TokenType::Identifier(_) | TokenType::QuotedIdentifier(_), .. }) =
+                    &self.current_token
+                {
+                    if matches!(&self.current_token, Some(Token { token_type: TokenType::Identifier(word), .. }) if is_view_prefix(word)) {
+                        break;
+                    }
+                    self.advance();
+                }
+            } else if word.eq_ignore_ascii_case("SQL") {
+                self.advance();
+                self.expect_keyword("SECURITY")?;
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        Ok(or_replace)
+    }
+
+    /// `[MATERIALIZED] VIEW ...` after CREATE and its prefix; `None` if what follows is not a view.
+    fn parse_create_view(&mut self, or_replace: bool) -> Result<Option<CreateViewStatement>, ParserError> {
+        let materialized = matches!(
+            &self.current_token,
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("MATERIALIZED")
+        );
+        if materialized {
+            self.advance();
+        }
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("VIEW") => self.advance(),
+            _ if or_replace || materialized => return Err(self.unexpected_token_error("VIEW")),
+            _ => return Ok(None),
+        }
+
+        let if_not_exists = self.parse_if_not_exists()?;
+        let view_name = self.parse_object_name()?;
+        let columns = if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+            self.advance();
+            let columns = self.parse_identifier_list()?;
+            self.expect_token(TokenType::RightParen)?;
+            columns
+        } else {
+            Vec::new()
+        };
+        self.expect_token(TokenType::As)?;
+
+        self.recorded = Some(Vec::new());
+        let query = self.parse_select_statement();
+        let definition = tokens_to_sql(&self.recorded.take().unwrap_or_default());
+        let query = query?;
+        // `WITH [CASCADED | LOCAL] CHECK OPTION` and `WITH [NO] DATA` don't change what the view holds
+        if let Some(Token { token_type: TokenType::With, .. }) = &self.current_token {
+            self.collect_raw_text(|token_type| *token_type == TokenType::Semicolon);
+        }
+
+        Ok(Some(CreateViewStatement {
+            view_name,
+            or_replace,
+            materialized,
+            if_not_exists,
+            columns,
+            query: Box::new(query),
+            definition,
+        }))
+    }
+
+    /// `TRIGGER name ...` after CREATE, in the standard form (`{BEFORE | AFTER | INSTEAD OF}
+    /// events ON table`) or SQL Server's (`ON table {FOR | AFTER | INSTEAD OF} events AS`).
+    fn parse_create_trigger(&mut self, or_replace: bool) -> Result<CreateTriggerStatement, ParserError> {
+        self.parse_if_not_exists()?;
+        let trigger_name = self.parse_object_name()?;
+        let mut table_name = None;
+        if let Some(Token { token_type: TokenType::On, .. }) = &self.current_token {
+            self.advance();
+            table_name = Some(self.parse_object_name()?);
+        }
+
+        let timing = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("BEFORE") => {
+                self.advance();
+                TriggerTiming::Before
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. })
+                if word.eq_ignore_ascii_case("AFTER") || word.eq_ignore_ascii_case("FOR") =>
+            {
+                self.advance();
+                TriggerTiming::After
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("INSTEAD") => {
+                self.advance();
+                self.expect_keyword("OF")?;
+                TriggerTiming::InsteadOf
+            }
+            // SQLite's default
+            _ => TriggerTiming::Before,
+        };
+
+        let mut events = Vec::new();
+        loop {
+            events.push(match &self.current_token {
+                Some(Token { token_type: TokenType::Insert, .. }) => {
+                    self.advance();
+                    TriggerEvent::Insert
+                }
+                Some(Token { token_type: TokenType::Delete, .. }) => {
+                    self.advance();
+                    TriggerEvent::Delete
+                }
+                Some(Token { token_type: TokenType::Update, .. }) => {
+                    self.advance();
+                    let mut columns = Vec::new();
+                    if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+                        && word.eq_ignore_ascii_case("OF")
+                    {
+                        self.advance();
+                        columns = self.parse_identifier_list()?;
+                    }
+                    TriggerEvent::Update(columns)
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("TRUNCATE") => {
+                    self.advance();
+                    TriggerEvent::Truncate
+                }
+                _ => return Err(self.unexpected_token_error("INSERT, UPDATE, DELETE or TRUNCATE")),
+            });
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Or | TokenType::Comma, .. }) => self.advance(),
+                _ => break,
+            }
+        }
+
+        let table_name = match table_name {
+            Some(table_name) => table_name,
+            None => {
+                self.expect_token(TokenType::On)?;
+                self.parse_object_name()?
+            }
+        };
+
+        let mut for_each_row = false;
+        loop {
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("FOR") => {
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+                        && word.eq_ignore_ascii_case("EACH")
+                    {
+                        self.advance();
+                    }
+                    for_each_row = matches!(
+                        &self.current_token,
+                        Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("ROW")
+                    );
+                    self.advance();
+                }
+                Some(Token { token_type: TokenType::When, .. }) => {
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                        self.collect_parenthesized_text()?;
+                    } else {
+                        self.collect_raw_text(|token_type| {
+                            matches!(token_type, TokenType::Identifier(word) if word.eq_ignore_ascii_case("BEGIN"))
+                        });
+                    }
+                }
+                // `REFERENCING {OLD | NEW} [TABLE] [AS] name ...` only names the rows for the body
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("REFERENCING") => {
+                    self.advance();
+                    while let Some(Token {
+                        token_type: TokenType::Identifier(_) | TokenType::Table | TokenType::As, lexeme, ..
+                    }) = &self.current_token
+                        && !["FOR", "EXECUTE", "BEGIN"].iter().any(|word| lexeme.eq_ignore_ascii_case(word))
+                    {
+                        self.advance();
+                    }
+                }
+                // PostgreSQL's `FROM table`, `[NOT] DEFERRABLE` and `INITIALLY ...`, and MySQL's
+                // `{FOLLOWS | PRECEDES} other`, don't change what the trigger does
+                Some(Token { token_type: TokenType::From | TokenType::Not, .. }) => self.advance(),
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if ["DEFERRABLE", "INITIALLY", "DEFERRED", "IMMEDIATE"]
+                        .iter()
+                        .any(|option| word.eq_ignore_ascii_case(option)) =>
+                {
+                    self.advance();
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("FOLLOWS") || word.eq_ignore_ascii_case("PRECEDES") =>
+                {
+                    self.advance();
+                    self.parse_object_name()?;
+                }
+                _ => break,
+            }
+        }
+
+        let body = self.parse_routine_body()?;
+        Ok(CreateTriggerStatement { trigger_name, or_replace, table_name, timing, events, for_each_row, body })
+    }
+
+    /// `{FUNCTION | PROCEDURE} name [(parameters)] ...` after CREATE. Options such as
+    /// `DETERMINISTIC` or `SECURITY DEFINER`, wherever they sit, are skipped.
+    fn parse_create_routine(&mut self, kind: RoutineKind, or_replace: bool) -> Result<CreateRoutineStatement, ParserError> {
+        let routine_name = self.parse_object_name()?;
+        let parameters = self.parse_routine_parameters()?;
+        let mut returns = None;
+        let mut language = None;
+        let mut body = None;
+
+        while let Some(token) = &self.current_token {
+            match &token.token_type {
+                TokenType::Semicolon => break,
+                TokenType::Identifier(word) if word.eq_ignore_ascii_case("RETURNS") => {
+                    self.advance();
+                    returns = Some(self.parse_routine_return_type()?);
+                }
+                TokenType::Identifier(word) if word.eq_ignore_ascii_case("LANGUAGE") => {
+                    self.advance();
+                    language = match &self.current_token {
+                        Some(Token { token_type: TokenType::StringLiteral(name), .. }) => {
+                            let name = name.clone();
+                            self.advance();
+                            Some(name)
+                        }
+                        _ => Some(self.parse_identifier()?),
+                    };
+                }
+                TokenType::As | TokenType::Is if body.is_none() => body = Some(self.parse_routine_body()?),
+                TokenType::Select | TokenType::Insert | TokenType::Update | TokenType::Delete | TokenType::With
+                    if body.is_none() =>
+                {
+                    body = Some(self.parse_routine_body()?);
+                }
+                TokenType::Identifier(word)
+                    if body.is_none() && (word.eq_ignore_ascii_case("BEGIN") || word.eq_ignore_ascii_case("RETURN")) =>
+                {
+                    body = Some(self.parse_routine_body()?);
+                }
+                _ => self.advance(),
+            }
+        }
+
+        let body = body.ok_or_else(|| self.unexpected_token_error("routine body"))?;
+        Ok(CreateRoutineStatement { routine_name, kind, or_replace, parameters, returns, language, body })
+    }
+
+    /// The parameters after a routine's name: a parenthesized list, SQL Server's unparenthesized
+    /// `@name type, ...`, or none.
+    fn parse_routine_parameters(&mut self) -> Result<Vec<RoutineParameter>, ParserError> {
+        let parenthesized = match &self.current_token {
+            Some(Token { token_type: TokenType::LeftParen, .. }) => true,
+            Some(Token { token_type: TokenType::AtSign, .. }) => false,
+            _ => return Ok(Vec::new()),
+        };
+        if parenthesized {
+            self.advance();
+        }
+
+        let mut parameters = Vec::new();
+        loop {
+            let mut tokens = Vec::new();
+            let mut depth = 0usize;
+            while let Some(token) = self.current_token.clone() {
+                match token.token_type {
+                    TokenType::Comma if depth == 0 => break,
+                    TokenType::RightParen if depth == 0 => break,
+                    TokenType::As | TokenType::With | TokenType::Semicolon if !parenthesized => break,
+                    TokenType::LeftParen => depth += 1,
+                    TokenType::RightParen => depth -= 1,
+                    _ => {}
+                }
+                tokens.push(token);
+                self.advance();
+            }
+            if !tokens.is_empty() {
+                parameters.push(routine_parameter(&tokens));
+            }
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Comma, .. }) => self.advance(),
+                _ if !parenthesized => break,
+                _ => {
+                    self.expect_token(TokenType::RightParen)?;
+                    break;
+                }
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// `RETURNS type`, PostgreSQL's `SETOF type` and `TABLE (columns)`, or SQL Server's
+    /// `@name TABLE (columns)`.
+    fn parse_routine_return_type(&mut self) -> Result<String, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Table, lexeme, .. }) => {
+                let table = lexeme.clone();
+                self.advance();
+                Ok(format!("{} {}", table, self.collect_parenthesized_text()?))
+            }
+            Some(Token { token_type: TokenType::AtSign, .. }) => {
+                self.advance();
+                let name = self.parse_identifier()?;
+                let table = self.current_token.as_ref().map(|token| token.lexeme.clone()).unwrap_or_default();
+                self.expect_token(TokenType::Table)?;
+                Ok(format!("@{} {} {}", name, table, self.collect_parenthesized_text()?))
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("SETOF") => {
+                let setof = word.clone();
+                self.advance();
+                Ok(format!("{} {}", setof, self.parse_data_type()?))
+            }
+            _ => self.parse_data_type(),
+        }
+    }
+
+    /// The body of a trigger or routine: a string's contents (PostgreSQL's `AS $$ ... $$`), a
+    /// `BEGIN ... END` block, the rest of the batch after SQL Server's and Oracle's `AS` or `IS`,
+    /// since those bodies hold semicolons of their own, or else a single statement.
+    fn parse_routine_body(&mut self) -> Result<String, ParserError> {
+        let after_as = matches!(&self.current_token, Some(Token { token_type: TokenType::As | TokenType::Is, .. }));
+        if after_as {
+            self.advance();
+        }
+        match &self.current_token {
+            Some(Token { token_type: TokenType::StringLiteral(body), .. }) => {
+                let body = body.clone();
+                self.advance();
+                Ok(body)
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("BEGIN") => {
+                self.collect_block()
+            }
+            _ if after_as => {
+                let mut tokens = Vec::new();
+                while let Some(token) = self.current_token.clone() {
+                    // `GO` and `/` end the batch; semicolons don't
+                    if token.token_type == TokenType::Semicolon && token.lexeme != ";" {
+                        break;
+                    }
+                    tokens.push(token);
+                    self.advance();
+                }
+                Ok(tokens_to_sql(&tokens))
+            }
+            _ => Ok(self.collect_raw_text(|token_type| *token_type == TokenType::Semicolon)),
+        }
+    }
+
+    /// Consumes a `BEGIN ... END` block, with the blocks and CASEs nested in it, and returns its
+    /// text. `END IF`, `END LOOP` and the like close statements that opened no level.
+    fn collect_block(&mut self) -> Result<String, ParserError> {
+        let position = self.current_token.as_ref().map(|token| token.position.clone()).unwrap_or_default();
+        let mut depth = 0usize;
+        let mut tokens = Vec::new();
+
+        while let Some(token) = self.current_token.clone() {
+            let next_is = |parser: &Self, words: &[&str]| {
+                parser.peek_token.as_ref().is_some_and(|next| words.iter().any(|word| next.lexeme.eq_ignore_ascii_case(word)))
+            };
+            match &token.token_type {
+                // SQL Server's `BEGIN TRANSACTION` starts no block
+                TokenType::Identifier(word)
+                    if word.eq_ignore_ascii_case("BEGIN") && !next_is(self, &["TRAN", "TRANSACTION", "DISTRIBUTED"]) =>
+                {
+                    depth += 1;
+                }
+                TokenType::Case => depth += 1,
+                TokenType::End if next_is(self, &["IF", "LOOP", "WHILE", "REPEAT"]) => {
+                    tokens.push(token);
+                    self.advance();
+                }
+                TokenType::End => {
+                    depth = depth.saturating_sub(1);
+                    if matches!(&self.peek_token, Some(Token { token_type: TokenType::Case, .. })) {
+                        tokens.push(token);
+                        self.advance();
+                    }
+                }
+                _ => {}
+            }
+            if let Some(token) = self.current_token.clone() {
+                tokens.push(token);
+                self.advance();
+            }
+            if depth == 0 {
+                return Ok(tokens_to_sql(&tokens));
+            }
+        }
+
+        Err(ParserError::new("Unterminated BEGIN block", position))
+    }
+
+    fn parse_alter_table(&mut self) -> Result<AlterTableStatement, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        // pg_dump writes `ALTER TABLE ONLY name`
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && word.eq_ignore_ascii_case("ONLY")
+        {
+            self.advance();
+        }
+        let table_name = self.parse_object_name()?;
+
+        let mut actions = Vec::new();
+        loop {
+            self.parse_alter_table_action(&mut actions)?;
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        Ok(AlterTableStatement { table_name, if_exists, actions })
+    }
+
+    /// Parses one comma-separated ALTER TABLE clause; MySQL's `CHANGE` yields two actions.
+    fn parse_alter_table_action(&mut self, actions: &mut Vec<AlterTableAction>) -> Result<(), ParserError> {
+        // SQL Server's `WITH CHECK ADD CONSTRAINT`, as SSMS scripts every foreign key; WITH NOCHECK
+        // leaves the rows already there unchecked
+        let mut unchecked = false;
+        if let Some(Token { token_type: TokenType::With, .. }) = &self.current_token
+            && let Some(checked) = self.peek_token.as_ref().and_then(constraint_checking)
+        {
+            self.advance();
+            self.advance();
+            unchecked = !checked;
+        }
+        if let Some(enabled) = self.current_token.as_ref().and_then(constraint_checking)
+            && matches!(&self.peek_token, Some(Token { token_type: TokenType::Constraint, .. }))
+        {
+            self.advance();
+            self.advance();
+            let mut constraint_names = Vec::new();
+            if let Some(Token { token_type: TokenType::All, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                constraint_names.push(self.parse_identifier()?);
+                while let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                    self.advance();
+                    constraint_names.push(self.parse_identifier()?);
+                }
+            }
+            actions.push(AlterTableAction::ConstraintChecking { constraint_names, enabled });
+            return Ok(());
+        }
+
+        let count = actions.len();
+        self.parse_alter_table_clause(actions)?;
+        if unchecked && let Some(AlterTableAction::AddConstraint { not_valid, .. }) = actions.get_mut(count) {
+            *not_valid = true;
+        }
+        Ok(())
+    }
+
+    fn parse_alter_table_clause(&mut self, actions: &mut Vec<AlterTableAction>) -> Result<(), ParserError> {
+        let is_column = |parser: &Self| matches!(&parser.current_token, Some(Token { token_type: TokenType::Column, .. }));
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Add, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token {
+                        token_type:
+                            TokenType::Constraint
+                            | TokenType::Primary
+                            | TokenType::Foreign
+                            | TokenType::Unique
+                            | TokenType::Check,
+                        ..
+                    }) => {
+                        let constraint = self.parse_table_constraint()?;
+                        let not_valid = self.parse_not_valid();
+                        actions.push(AlterTableAction::AddConstraint { constraint, not_valid });
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("EXCLUDE") => {
+                        let constraint = self.parse_table_constraint()?;
+                        let not_valid = self.parse_not_valid();
+                        actions.push(AlterTableAction::AddConstraint { constraint, not_valid });
+                    }
+                    Some(Token { token_type: TokenType::Key | TokenType::Index, .. }) => {
+                        self.advance();
+                        let constraint = self.parse_inline_index(IndexKind::Regular)?;
+                        actions.push(AlterTableAction::AddConstraint { constraint, not_valid: false });
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if index_kind(word).is_some() => {
+                        let kind = index_kind(word).unwrap_or_default();
+                        self.advance();
+                        if let Some(Token { token_type: TokenType::Key | TokenType::Index, .. }) = &self.current_token {
+                            self.advance();
+                        }
+                        let constraint = self.parse_inline_index(kind)?;
+                        actions.push(AlterTableAction::AddConstraint { constraint, not_valid: false });
+                    }
+                    _ => {
+                        if is_column(self) {
+                            self.advance();
+                        }
+                        self.parse_if_not_exists()?;
+                        let column = self.parse_column_definition()?;
+                        self.skip_column_position();
+                        actions.push(AlterTableAction::AddColumn { column });
+                    }
+                }
+            }
+            Some(Token { token_type: TokenType::Drop, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::Constraint | TokenType::Check, .. }) => {
+                        self.advance();
+                        let if_exists = self.parse_if_exists()?;
+                        let constraint_name = self.parse_identifier()?;
+                        self.parse_drop_behavior();
+                        actions.push(AlterTableAction::DropConstraint { constraint_name, if_exists });
+                    }
+                    // MySQL names the primary key's index PRIMARY
+                    Some(Token { token_type: TokenType::Primary, .. }) => {
+                        self.advance();
+                        self.expect_token(TokenType::Key)?;
+                        actions.push(AlterTableAction::DropConstraint { constraint_name: "PRIMARY".to_string(), if_exists: false });
+                    }
+                    Some(Token { token_type: TokenType::Foreign | TokenType::Key | TokenType::Index, .. }) => {
+                        if let Some(Token { token_type: TokenType::Foreign, .. }) = &self.current_token {
+                            self.advance();
+                        }
+                        self.advance();
+                        let if_exists = self.parse_if_exists()?;
+                        let constraint_name = self.parse_identifier()?;
+                        actions.push(AlterTableAction::DropConstraint { constraint_name, if_exists });
+                    }
+                    _ => {
+                        if is_column(self) {
+                            self.advance();
+                        }
+                        let if_exists = self.parse_if_exists()?;
+                        let column_name = self.parse_identifier()?;
+                        self.parse_drop_behavior();
+                        actions.push(AlterTableAction::DropColumn { column_name, if_exists });
+                    }
+                }
+            }
+            Some(Token { token_type: TokenType::Rename, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::As, .. }) => {
+                        self.advance();
+                        let new_name = self.parse_object_name()?.object;
+                        actions.push(AlterTableAction::RenameTable { new_name });
+                    }
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("TO") => {
+                        self.advance();
+                        let new_name = self.parse_object_name()?.object;
+                        actions.push(AlterTableAction::RenameTable { new_name });
+                    }
+                    Some(Token { token_type: TokenType::Constraint | TokenType::Index | TokenType::Key, .. }) => {
+                        let text = self.collect_raw_text(|token_type| matches!(token_type, TokenType::Comma | TokenType::Semicolon));
+                        actions.push(AlterTableAction::Other(format!("RENAME {}", text)));
+                    }
+                    _ => {
+                        if is_column(self) {
+                            self.advance();
+                        }
+                        let old_name = self.parse_identifier()?;
+                        self.expect_keyword("TO")?;
+                        let new_name = self.parse_identifier()?;
+                        actions.push(AlterTableAction::RenameColumn { old_name, new_name });
+                    }
+                }
+            }
+            Some(Token { token_type: TokenType::Alter, .. }) => {
+                self.advance();
+                if is_column(self) {
+                    self.advance();
+                }
+                let column_name = self.parse_identifier()?;
+                actions.push(self.parse_alter_column(column_name)?);
+            }
+            Some(Token { token_type: TokenType::Modify, .. }) => {
+                self.advance();
+                if is_column(self) {
+                    self.advance();
+                }
+                let column = self.parse_column_definition()?;
+                self.skip_column_position();
+                actions.push(AlterTableAction::ModifyColumn { column });
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("CHANGE") => {
+                self.advance();
+                if is_column(self) {
+                    self.advance();
+                }
+                let old_name = self.parse_identifier()?;
+                let column = self.parse_column_definition()?;
+                self.skip_column_position();
+                if column.name != old_name {
+                    actions.push(AlterTableAction::RenameColumn { old_name, new_name: column.name.clone() });
+                }
+                actions.push(AlterTableAction::ModifyColumn { column });
+            }
+            _ => {
+                let text = self.collect_raw_text(|token_type| matches!(token_type, TokenType::Comma | TokenType::Semicolon));
+                if text.is_empty() {
+                    return Err(self.unexpected_token_error("ALTER TABLE action"));
+                }
+                actions.push(AlterTableAction::Other(text));
+            }
+        }
+        Ok(())
+    }
+
+    /// PostgreSQL's `NOT VALID` after a constraint added to an existing table.
+    fn parse_not_valid(&mut self) -> bool {
+        let not_valid = matches!(&self.current_token, Some(Token { token_type: TokenType::Not, .. }))
+            && matches!(
+                &self.peek_token,
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("VALID")
+            );
+        if not_valid {
+            self.advance();
+            self.advance();
+        }
+        not_valid
+    }
+
+    /// `ALTER [COLUMN] name` followed by PostgreSQL's `TYPE`, `SET`/`DROP NOT NULL` and
+    /// `SET`/`DROP DEFAULT`, or SQL Server's restated `type [NOT] NULL`.
+    fn parse_alter_column(&mut self, column_name: String) -> Result<AlterTableAction, ParserError> {
+        let mut new_data_type = None;
+        let mut add_constraints = Vec::new();
+        let mut drop_constraints = Vec::new();
+        let rest = |parser: &mut Self| {
+            parser.collect_raw_text(|token_type| matches!(token_type, TokenType::Comma | TokenType::Semicolon))
+        };
+
+        match (&self.current_token, &self.peek_token) {
+            (Some(Token { token_type: TokenType::Identifier(word), .. }), _) if word.eq_ignore_ascii_case("TYPE") => {
+                self.advance();
+                new_data_type = Some(self.parse_declared_type()?.0);
+                // `COLLATE` and `USING expression` say how, not what
+                rest(self);
+            }
+            (Some(Token { token_type: TokenType::Set, .. }), Some(Token { token_type: TokenType::Not, .. })) => {
+                self.advance();
+                self.advance();
+                self.expect_token(TokenType::NullLiteral)?;
+                add_constraints.push(ColumnConstraint::NotNull);
+            }
+            (Some(Token { token_type: TokenType::Set, .. }), Some(Token { token_type: TokenType::Default, .. })) => {
+                self.advance();
+                self.advance();
+                add_constraints.push(ColumnConstraint::Default(self.parse_default_value()?));
+            }
+            (Some(Token { token_type: TokenType::Set, .. }), Some(Token { token_type: TokenType::Identifier(word), .. }))
+                if word.eq_ignore_ascii_case("DATA") =>
+            {
+                self.advance();
+                self.advance();
+                self.expect_keyword("TYPE")?;
+                new_data_type = Some(self.parse_declared_type()?.0);
+                rest(self);
+            }
+            (Some(Token { token_type: TokenType::Drop, .. }), Some(Token { token_type: TokenType::Not, .. })) => {
+                self.advance();
+                self.advance();
+                self.expect_token(TokenType::NullLiteral)?;
+                drop_constraints.push("NOT NULL".to_string());
+            }
+            (Some(Token { token_type: TokenType::Drop, .. }), Some(Token { token_type: TokenType::Default, .. })) => {
+                self.advance();
+                self.advance();
+                drop_constraints.push("DEFAULT".to_string());
+            }
+            (Some(Token { token_type: TokenType::Set | TokenType::Drop | TokenType::Add, .. }), _) => {
+                let text = rest(self);
+                return Ok(AlterTableAction::Other(format!("ALTER COLUMN {} {}", column_name, text)));
+            }
+            _ => {
+                new_data_type = Some(self.parse_declared_type()?.0);
+                for constraint in self.parse_column_constraints()? {
+                    match constraint {
+                        ColumnConstraint::Null => drop_constraints.push("NOT NULL".to_string()),
+                        constraint => add_constraints.push(constraint),
+                    }
+                }
+            }
+        }
+
+        Ok(AlterTableAction::AlterColumn { column_name, new_data_type, add_constraints, drop_constraints })
+    }
+
+    /// MySQL's `FIRST` or `AFTER column` after a column definition in ALTER TABLE.
+    fn skip_column_position(&mut self) {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("FIRST") => {
+                self.advance();
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("AFTER") => {
+                self.advance();
+                self.advance();
+            }
+            _ => {}
+        }
+    }
+
+    fn parse_if_exists(&mut self) -> Result<bool, ParserError> {
+        if let Some(Token { token_type: TokenType::If, .. }) = &self.current_token {
+            self.advance();
+            self.expect_token(TokenType::Exists)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Parses a trailing `CASCADE` or `RESTRICT`, returning whether it was CASCADE.
+    fn parse_drop_behavior(&mut self) -> bool {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. })
+                if word.eq_ignore_ascii_case("CASCADE") || word.eq_ignore_ascii_case("RESTRICT") =>
+            {
+                let cascade = word.eq_ignore_ascii_case("CASCADE");
+                self.advance();
+                cascade
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_drop_table(&mut self) -> Result<DropTableStatement, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let mut table_names = vec![self.parse_object_name()?];
+        while let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+            self.advance();
+            table_names.push(self.parse_object_name()?);
+        }
+        let cascade = self.parse_drop_behavior();
+        Ok(DropTableStatement { table_names, if_exists, cascade })
+    }
+
+    fn parse_drop_index(&mut self) -> Result<DropIndexStatement, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let index_names = self.parse_identifier_list()?;
+        // MySQL names the table: `DROP INDEX idx ON t`
+        if let Some(Token { token_type: TokenType::On, .. }) = &self.current_token {
+            self.advance();
+            self.parse_identifier()?;
+        }
+        let cascade = self.parse_drop_behavior();
+        Ok(DropIndexStatement { index_names, if_exists, cascade })
+    }
+
+    fn parse_create_sequence(&mut self) -> Result<CreateSequenceStatement, ParserError> {
+        let if_not_exists = self.parse_if_not_exists()?;
+        let sequence_name = self.parse_object_name()?;
+        // PostgreSQL and SQL Server let a sequence choose its integer type
+        let data_type = if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
+            self.advance();
+            Some(self.parse_declared_type()?.0)
+        } else {
+            None
+        };
+
+        let mut options = IdentityOptions::default();
+        while self.parse_sequence_option(&mut options)? {}
+
+        Ok(CreateSequenceStatement { sequence_name, if_not_exists, data_type, options })
+    }
+
+    /// `CREATE SCHEMA [IF NOT EXISTS] name [AUTHORIZATION owner]`, or PostgreSQL's
+    /// `CREATE SCHEMA AUTHORIZATION owner`, which names the schema after its owner. Anything
+    /// after that, such as MySQL's `DEFAULT CHARACTER SET`, is skipped.
+    fn parse_create_schema(&mut self) -> Result<CreateSchemaStatement, ParserError> {
+        let if_not_exists = self.parse_if_not_exists()?;
+        let at_authorization = |parser: &Self| {
+            matches!(
+                &parser.current_token,
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("AUTHORIZATION")
+            )
+        };
+
+        let name = if at_authorization(self)
+            && matches!(&self.peek_token, Some(Token { token_type: TokenType::Identifier(_) | TokenType::QuotedIdentifier(_), .. }))
+        {
+            None
+        } else {
+            Some(self.parse_identifier()?)
+        };
+        let authorization = if at_authorization(self) {
+            self.advance();
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        self.collect_raw_text(|token| matches!(token, TokenType::Semicolon));
+
+        let schema_name = match name.or_else(|| authorization.clone()) {
+            Some(name) => name,
+            None => return Err(self.unexpected_token_error("schema name")),
+        };
+        Ok(CreateSchemaStatement { schema_name, if_not_exists, authorization })
+    }
+
+    fn parse_create_type(&mut self) -> Result<CreateTypeStatement, ParserError> {
+        let type_name = self.parse_object_name()?;
+        self.expect_token(TokenType::As)?;
+
+        let definition = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("ENUM") => {
+                self.advance();
+                TypeDefinition::Enum { values: self.parse_string_list()? }
+            }
+            Some(Token { token_type: TokenType::LeftParen, .. }) => {
+                self.advance();
+                let mut attributes = Vec::new();
+                loop {
+                    attributes.push(self.parse_column_definition()?);
+                    if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(TokenType::RightParen)?;
+                TypeDefinition::Composite { attributes }
+            }
+            _ => return Err(self.unexpected_token_error("ENUM or attribute list after AS")),
+        };
+
+        Ok(CreateTypeStatement { type_name, definition })
+    }
+
+    fn parse_create_domain(&mut self) -> Result<CreateDomainStatement, ParserError> {
+        let domain_name = self.parse_object_name()?;
+        if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token {
+            self.advance();
+        }
+        let data_type = self.parse_column_data_type()?;
+        let constraints = self.parse_column_constraints()?;
+
+        Ok(CreateDomainStatement { domain_name, data_type, constraints })
+    }
+
+    fn parse_if_not_exists(&mut self) -> Result<bool, ParserError> {
+        if let Some(Token { token_type: TokenType::If, .. }) = &self.current_token {
+            self.advance();
+            self.expect_token(TokenType::Not)?;
+            self.expect_token(TokenType::Exists)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn parse_create_table(&mut self) -> Result<CreateTableStatement, ParserError> {
+        let if_not_exists = self.parse_if_not_exists()?;
+
+        let table_name = self.parse_object_name()?;
+        self.expect_token(TokenType::LeftParen)?;
+
+        let mut columns = Vec::new();
+        let mut constraints = Vec::new();
+
+        loop {
+            if let Some(Token { token_type: TokenType::RightParen, .. }) = &self.current_token {
+                self.advance();
+                break;
+            }
+
+            match &self.current_token {
+                Some(Token {
+                    token_type:
+                        TokenType::Constraint
+                        | TokenType::Primary
+                        | TokenType::Foreign
+                        | TokenType::Unique
+                        | TokenType::Check,
+                    ..
+                }) => {
+                    let constraint = self.parse_table_constraint()?;
+                    constraints.push(constraint);
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("EXCLUDE")
+                        && matches!(
+                            &self.peek_token,
+                            Some(Token { token_type: TokenType::Using | TokenType::LeftParen, .. })
+                        ) =>
+                {
+                    let constraint = self.parse_table_constraint()?;
+                    constraints.push(constraint);
+                }
+                // `key TEXT` in SQLite is a column, `KEY name (...)` in MySQL an index
+                Some(Token { token_type: TokenType::Key | TokenType::Index, .. })
+                    if !matches!(
+                        &self.peek_token,
+                        Some(Token { token_type: TokenType::Identifier(word), .. })
+                            if COLUMN_TYPE_WORDS.iter().any(|t| t.eq_ignore_ascii_case(word))
+                    ) =>
+                {
+                    self.advance();
+                    constraints.push(self.parse_inline_index(IndexKind::Regular)?);
+                }
+                // `FULLTEXT [KEY | INDEX] name (...)`; a column named fulltext would be followed by its type
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if index_kind(word).is_some()
+                        && matches!(
+                            &self.peek_token,
+                            Some(Token {
+                                token_type: TokenType::Key
+                                    | TokenType::Index
+                                    | TokenType::LeftParen
+                                    | TokenType::QuotedIdentifier(_),
+                                ..
+                            })
+                        ) =>
+                {
+                    let kind = index_kind(word).unwrap_or_default();
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::Key | TokenType::Index, .. }) = &self.current_token {
+                        self.advance();
+                    }
+                    constraints.push(self.parse_inline_index(kind)?);
+                }
+                _ => {
+                    let column = self.parse_column_definition()?;
+                    columns.push(column);
+                }
+            }
+
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                self.expect_token(TokenType::RightParen)?;
+                break;
+            }
+        }
+
+        // SQLite's `STRICT` and `WITHOUT ROWID`, in either order and comma-separated
+        let mut strict = false;
+        let mut without_rowid = false;
+        loop {
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("STRICT") => {
+                    self.advance();
+                    strict = true;
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("WITHOUT")
+                        && matches!(
+                            &self.peek_token,
+                            Some(Token { token_type: TokenType::Identifier(next), .. }) if next.eq_ignore_ascii_case("ROWID")
+                        ) =>
+                {
+                    self.advance();
+                    self.advance();
+                    without_rowid = true;
+                }
+                _ => break,
+            }
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            }
+        }
+
+        while self.skip_physical_attribute()? {}
+        let options = self.parse_table_options()?;
+
+        Ok(CreateTableStatement {
+            table_name,
+            if_not_exists,
+            columns,
+            constraints,
+            options,
+            strict,
+            without_rowid,
+        })
+    }
+
+    fn parse_table_constraint(&mut self) -> Result<TableConstraint, ParserError> {
+        let name = if let Some(Token { token_type: TokenType::Constraint, .. }) = &self.current_token {
+            self.advance();
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Primary, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::Key)?;
+                self.skip_clustering();
+                let columns = self.parse_key_parts()?.into_iter().map(|part| part.column).collect();
+                let (_, include) = self.parse_index_options()?;
+                Ok(TableConstraint::PrimaryKey { name, columns, include })
+            }
+            Some(Token { token_type: TokenType::Unique, .. }) => {
+                self.advance();
+                // MySQL allows `UNIQUE KEY name (cols)`
+                if let Some(Token { token_type: TokenType::Key | TokenType::Index, .. }) = &self.current_token {
+                    self.advance();
+                }
+                self.skip_clustering();
+                let name = match &self.current_token {
+                    Some(Token { token_type: TokenType::Identifier(_) | TokenType::QuotedIdentifier(_), .. }) => {
+                        Some(self.parse_identifier()?)
+                    }
+                    _ => name,
+                };
+                let columns = self.parse_key_parts()?.into_iter().map(|part| part.column).collect();
+                let (_, include) = self.parse_index_options()?;
+                Ok(TableConstraint::Unique { name, columns, include })
+            }
+            Some(Token { token_type: TokenType::Foreign, .. }) => {
+                let mut constraint = self.parse_foreign_key_constraint()?;
+                if let TableConstraint::ForeignKey { name: fk_name, .. } = &mut constraint {
+                    *fk_name = name;
+                }
+                Ok(constraint)
+            }
+            Some(Token { token_type: TokenType::Check, .. }) => {
+                self.advance();
+                let expression = self.parse_check_expression()?;
+                Ok(TableConstraint::Check { name, expression })
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("EXCLUDE") => {
+                self.advance();
+                self.parse_exclusion_constraint(name)
+            }
+            _ => Err(self.unexpected_token_error("table constraint")),
+        }
+    }
+
+    /// Parses the rest of an inline MySQL index once `KEY`, `INDEX` or `FULLTEXT KEY` is consumed.
+    fn parse_inline_index(&mut self, kind: IndexKind) -> Result<TableConstraint, ParserError> {
+        let name = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(_) | TokenType::QuotedIdentifier(_), .. }) => {
+                Some(self.parse_identifier()?)
+            }
+            _ => None,
+        };
+        let mut index_type = self.parse_index_method()?;
+        let columns = self.parse_key_parts()?;
+        if let (Some(trailing), _) = self.parse_index_options()? {
+            index_type = Some(trailing);
+        }
+        Ok(TableConstraint::Index { name, kind, columns, index_type })
+    }
+
+    /// Parses `(col [(length)] [ASC | DESC], ...)`.
+    fn parse_key_parts(&mut self) -> Result<Vec<KeyPart>, ParserError> {
+        self.expect_token(TokenType::LeftParen)?;
+        let mut parts = Vec::new();
+        loop {
+            let column = self.parse_identifier()?;
+            let length = if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                self.advance();
+                let length = match &self.current_token {
+                    Some(Token { token_type: TokenType::IntegerLiteral(length), .. }) => *length as u32,
+                    _ => return Err(self.unexpected_token_error("prefix length")),
+                };
+                self.advance();
+                self.expect_token(TokenType::RightParen)?;
+                Some(length)
+            } else {
+                None
+            };
+            let descending = matches!(&self.current_token, Some(Token { token_type: TokenType::Desc, .. }));
+            if let Some(Token { token_type: TokenType::Asc | TokenType::Desc, .. }) = &self.current_token {
+                self.advance();
+            }
+            parts.push(KeyPart { column, length, descending });
+
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.expect_token(TokenType::RightParen)?;
+        Ok(parts)
+    }
+
+    /// Skips MySQL index options after the key parts, returning a trailing `USING method` and
+    /// the columns of an `INCLUDE (...)`.
+    fn parse_index_options(&mut self) -> Result<(Option<IndexType>, Vec<String>), ParserError> {
+        let mut index_type = None;
+        let mut include = Vec::new();
+        loop {
+            if self.skip_physical_attribute()? {
+                continue;
+            }
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("INCLUDE") => {
+                    include = self.parse_include_columns()?;
+                }
+                Some(Token { token_type: TokenType::Using, .. }) => index_type = self.parse_index_method()?,
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("COMMENT") =>
+                {
+                    self.advance();
+                    self.advance();
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("VISIBLE") || word.eq_ignore_ascii_case("INVISIBLE") =>
+                {
+                    self.advance();
+                }
+                _ => return Ok((index_type, include)),
+            }
+        }
+    }
+
+    /// Parses `INCLUDE (col, ...)`, the columns an index carries without keying on them.
+    fn parse_include_columns(&mut self) -> Result<Vec<String>, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("INCLUDE") => {
+                self.advance();
+                self.expect_token(TokenType::LeftParen)?;
+                let columns = self.parse_identifier_list()?;
+                self.expect_token(TokenType::RightParen)?;
+                Ok(columns)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Skips one clause that places a table or index physically without changing its shape:
+    /// SQL Server's `WITH (...)`, `ON [PRIMARY]` and `TEXTIMAGE_ON`, or Oracle's
+    /// `USING INDEX`, `TABLESPACE`, `STORAGE (...)`, `COMPUTE STATISTICS` and segment attributes.
+    /// Returns whether one was skipped.
+    fn skip_physical_attribute(&mut self) -> Result<bool, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::With, .. })
+                if matches!(&self.peek_token, Some(Token { token_type: TokenType::LeftParen, .. })) =>
+            {
+                self.advance();
+                self.collect_parenthesized_text()?;
+            }
+            // A filegroup or partition scheme: `ON [PRIMARY]`, `ON ps_orders (created_at)`
+            Some(Token { token_type: TokenType::On, .. })
+                if matches!(
+                    &self.peek_token,
+                    Some(Token { token_type: TokenType::Primary | TokenType::QuotedIdentifier(_), .. })
+                ) || matches!(
+                    &self.peek_token,
+                    Some(Token { token_type: TokenType::Identifier(word), .. }) if !word.eq_ignore_ascii_case("COMMIT")
+                ) =>
+            {
+                self.advance();
+                self.advance();
+                if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                    self.collect_parenthesized_text()?;
+                }
+            }
+            Some(Token { token_type: TokenType::Using, .. })
+                if matches!(&self.peek_token, Some(Token { token_type: TokenType::Index, .. })) =>
+            {
+                self.advance();
+                self.advance();
+                if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                    self.collect_parenthesized_text()?;
+                }
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) => match word.to_uppercase().as_str() {
+                "TEXTIMAGE_ON" | "FILESTREAM_ON" | "TABLESPACE" => {
+                    self.advance();
+                    self.parse_identifier()?;
+                }
+                "PCTFREE" | "PCTUSED" | "INITRANS" | "MAXTRANS" => {
+                    self.advance();
+                    self.parse_signed_integer()?;
+                }
+                // MySQL's `ALGORITHM=INPLACE LOCK=NONE` after an index, which only say how it's built
+                "ALGORITHM" | "LOCK" => {
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::Equal, .. }) = &self.current_token {
+                        self.advance();
+                    }
+                    self.advance();
+                }
+                "STORAGE" => {
+                    self.advance();
+                    self.collect_parenthesized_text()?;
+                }
+                "SEGMENT" => {
+                    self.advance();
+                    self.expect_keyword("CREATION")?;
+                    self.advance();
+                }
+                // Gathers optimizer statistics as the index is built
+                "COMPUTE" => {
+                    self.advance();
+                    self.expect_keyword("STATISTICS")?;
+                }
+                "COMPRESS" | "NOCOMPRESS" | "LOGGING" | "NOLOGGING" | "NOCACHE" | "NOPARALLEL" | "MONITORING"
+                | "NOMONITORING" | "ENABLE" => {
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::IntegerLiteral(_), .. }) = &self.current_token {
+                        self.advance();
+                    }
+                }
+                _ => return Ok(false),
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Skips SQL Server's `CLUSTERED` or `NONCLUSTERED`, which only decide how rows are stored.
+    fn skip_clustering(&mut self) {
+        if let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.current_token
+            && (word.eq_ignore_ascii_case("CLUSTERED") || word.eq_ignore_ascii_case("NONCLUSTERED"))
+        {
+            self.advance();
+        }
+    }
+
+    fn parse_exclusion_constraint(&mut self, name: Option<String>) -> Result<TableConstraint, ParserError> {
+        let index_type = self.parse_index_method()?;
+        self.expect_token(TokenType::LeftParen)?;
+
+        let mut elements = Vec::new();
+        loop {
+            let expression = self.collect_raw_text(|token| {
+                matches!(token, TokenType::With | TokenType::Comma | TokenType::RightParen)
+            });
+            if expression.is_empty() {
+                return Err(self.unexpected_token_error("exclusion element"));
+            }
+            self.expect_token(TokenType::With)?;
+
+            // Operators such as `&&` arrive as several tokens; glue them back together
+            let mut operator = String::new();
+            while let Some(token) = &self.current_token {
+                if matches!(token.token_type, TokenType::Comma | TokenType::RightParen) {
+                    break;
+                }
+                operator.push_str(&token.lexeme);
+                self.advance();
+            }
+            if operator.is_empty() {
+                return Err(self.unexpected_token_error("operator after WITH"));
+            }
+            elements.push(ExclusionElement { expression, operator });
+
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.expect_token(TokenType::RightParen)?;
+
+        let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
+            self.advance();
+            Some(self.parse_check_expression()?)
+        } else {
+            None
+        };
+
+        Ok(TableConstraint::Exclude { name, index_type, elements, where_clause })
+    }
+
+    /// Parses trailing table options such as MySQL's `ENGINE=InnoDB DEFAULT CHARSET=utf8mb4`.
+    fn parse_table_options(&mut self) -> Result<HashMap<String, String>, ParserError> {
+        let mut options = HashMap::new();
+
+        loop {
+            let key = match &self.current_token {
+                Some(Token { token_type: TokenType::Default, .. }) => {
+                    self.advance();
+                    continue;
+                }
+                Some(Token { token_type: TokenType::Identifier(name), .. }) => name.to_uppercase(),
+                Some(Token { token_type: TokenType::AutoIncrement, .. }) => "AUTO_INCREMENT".to_string(),
+                _ => break,
+            };
+            self.advance();
+
+            let key = if key == "CHARACTER" {
+                self.expect_token(TokenType::Set)?;
+                "CHARSET".to_string()
+            } else {
+                key
+            };
+
+            if let Some(Token { token_type: TokenType::Equal, .. }) = &self.current_token {
+                self.advance();
+            }
+
+            let value = match &self.current_token {
+                Some(Token { token_type: TokenType::StringLiteral(value), .. })
+                | Some(Token { token_type: TokenType::Identifier(value), .. })
+                | Some(Token { token_type: TokenType::QuotedIdentifier(value), .. }) => value.clone(),
+                Some(Token { token_type: TokenType::IntegerLiteral(value), .. }) => value.to_string(),
+                _ => return Err(self.unexpected_token_error("table option value")),
+            };
+            self.advance();
+
+            options.insert(key, value);
+
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn parse_create_index(&mut self, unique: bool) -> Result<CreateIndexStatement, ParserError> {
+        let concurrently = matches!(
+            &self.current_token,
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("CONCURRENTLY")
+        );
+        if concurrently {
+            self.advance();
+        }
+        let if_not_exists = self.parse_if_not_exists()?;
+        let index_name = self.parse_identifier()?;
+
+        // MySQL places the index method before ON, PostgreSQL after the table name
+        let mut index_type = self.parse_index_method()?;
+        self.expect_token(TokenType::On)?;
+        let table_name = self.parse_object_name()?;
+        if index_type.is_none() {
+            index_type = self.parse_index_method()?;
+        }
+
+        let columns = self.parse_key_parts()?;
+        let include = self.parse_include_columns()?;
+        while self.skip_physical_attribute()? {}
+
+        let where_clause = if let Some(Token { token_type: TokenType::Where, .. }) = &self.current_token {
+            self.advance();
+            // SQL Server's storage options may follow the filter
+            Some(self.collect_raw_text(|token| matches!(token, TokenType::Semicolon | TokenType::With | TokenType::On)))
+        } else {
+            None
+        };
+        while self.skip_physical_attribute()? {}
+
+        Ok(CreateIndexStatement {
+            index_name,
+            table_name,
+            if_not_exists,
+            unique,
+            concurrently,
+            kind: IndexKind::Regular,
+            index_type,
+            columns,
+            include,
+            where_clause,
+        })
+    }
+
+    fn parse_index_method(&mut self) -> Result<Option<IndexType>, ParserError> {
+        if let Some(Token { token_type: TokenType::Using, .. }) = &self.current_token {
+            self.advance();
+            let method = self.parse_identifier()?;
+            let index_type = match method.to_uppercase().as_str() {
+                "BTREE" => IndexType::BTree,
+                "HASH" => IndexType::Hash,
+                "GIST" => IndexType::Gist,
+                "GIN" => IndexType::Gin,
+                "SPGIST" => IndexType::SpGist,
+                "BRIN" => IndexType::Brin,
+                _ => IndexType::Custom(method),
+            };
+            Ok(Some(index_type))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_column_definition(&mut self) -> Result<ColumnDefinition, ParserError> {
+        let name = match &self.current_token {
+            // Non-reserved in SQLite and PostgreSQL, and a common column name
+            Some(token @ Token { token_type: TokenType::Key | TokenType::Index, .. }) => {
+                let name = token.lexeme.clone();
+                self.advance();
+                name
+            }
+            _ => self.parse_identifier()?,
+        };
+
+        // SQLite lets the type be left out entirely
+        let untyped = matches!(
+            &self.current_token,
+            Some(Token {
+                token_type: TokenType::Comma
+                    | TokenType::RightParen
+                    | TokenType::Constraint
+                    | TokenType::Not
+                    | TokenType::NullLiteral
+                    | TokenType::Primary
+                    | TokenType::Unique
+                    | TokenType::Default
+                    | TokenType::Check
+                    | TokenType::References,
+                ..
+            })
+        );
+        let (data_type, type_name) = if untyped {
+            (DataType::Custom(String::new()), String::new())
+        } else {
+            self.parse_declared_type()?
+        };
+        let constraints = self.parse_column_constraints()?;
+
+        Ok(ColumnDefinition {
+            name,
+            data_type,
+            type_name: Some(type_name),
+            constraints,
+        })
+    }
+
+    fn parse_column_constraints(&mut self) -> Result<Vec<ColumnConstraint>, ParserError> {
+        let mut constraints = Vec::new();
+
+        loop {
+            match &self.current_token {
+                Some(Token { token_type: TokenType::Constraint, .. }) => {
+                    self.advance();
+                    constraints.push(ColumnConstraint::Name(self.parse_identifier()?));
+                }
+                // SQL Server's `NOT FOR REPLICATION` only matters to replication agents
+                Some(Token { token_type: TokenType::Not, .. })
+                    if matches!(
+                        &self.peek_token,
+                        Some(Token { token_type: TokenType::Identifier(next), .. }) if next.eq_ignore_ascii_case("FOR")
+                    ) =>
+                {
+                    self.advance();
+                    self.advance();
+                    self.expect_keyword("REPLICATION")?;
+                }
+                Some(Token { token_type: TokenType::Not, .. }) => {
+                    self.advance();
+                    self.expect_token(TokenType::NullLiteral)?;
+                    constraints.push(ColumnConstraint::NotNull);
+                }
+                Some(Token { token_type: TokenType::NullLiteral, .. }) => {
+                    self.advance();
+                    constraints.push(ColumnConstraint::Null);
+                }
+                Some(Token { token_type: TokenType::Primary, .. }) => {
+                    self.advance();
+                    self.expect_token(TokenType::Key)?;
+                    self.skip_clustering();
+                    constraints.push(ColumnConstraint::PrimaryKey);
+                    match &self.current_token {
+                        Some(Token { token_type: TokenType::Asc, .. }) => self.advance(),
+                        Some(Token { token_type: TokenType::Desc, .. }) => {
+                            self.advance();
+                            constraints.push(ColumnConstraint::KeyDescending);
+                        }
+                        _ => {}
+                    }
+                }
+                // SQLite's `ON CONFLICT <resolution>` after NOT NULL, PRIMARY KEY or UNIQUE
+                Some(Token { token_type: TokenType::On, .. })
+                    if matches!(
+                        &self.peek_token,
+                        Some(Token { token_type: TokenType::Identifier(next), .. }) if next.eq_ignore_ascii_case("CONFLICT")
+                    ) =>
+                {
+                    self.advance();
+                    self.advance();
+                    self.advance();
+                }
+                Some(Token { token_type: TokenType::Unique, .. }) => {
+                    self.advance();
+                    if let Some(Token { token_type: TokenType::Key, .. }) = &self.current_token {
+                        self.advance();
+                    }
+                    self.skip_clustering();
+                    constraints.push(ColumnConstraint::Unique);
+                }
+                Some(Token { token_type: TokenType::AutoIncrement, .. }) => {
+                    self.advance();
+                    constraints.push(ColumnConstraint::AutoIncrement);
+                }
+                Some(Token { token_type: TokenType::Default, .. }) => {
+                    self.advance();
+                    let value = self.parse_default_value()?;
+                    constraints.push(ColumnConstraint::Default(value));
+                }
+                Some(Token { token_type: TokenType::Check, .. }) => {
+                    self.advance();
+                    let expression = self.parse_check_expression()?;
+                    constraints.push(ColumnConstraint::Check(expression));
+                }
+                Some(Token { token_type: TokenType::References, .. }) => {
+                    self.advance();
+                    let table = self.parse_object_name()?;
+                    let column = if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                        self.advance();
+                        let column = self.parse_identifier()?;
+                        self.expect_token(TokenType::RightParen)?;
+                        column
+                    } else {
+                        // A bare REFERENCES targets the referenced table's primary key
+                        String::new()
+                    };
+                    let (on_delete, on_update) = self.parse_referential_actions()?;
+                    constraints.push(ColumnConstraint::ForeignKey {
+                        table,
+                        column,
+                        on_delete,
+                        on_update,
+                    });
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("GENERATED") =>
+                {
+                    self.advance();
+                    constraints.push(self.parse_generated_column()?);
+                }
+                // SQL Server's `IDENTITY[(seed, increment)]`; explicit values need IDENTITY_INSERT
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("IDENTITY") =>
+                {
+                    self.advance();
+                    let mut options = IdentityOptions { always: true, ..IdentityOptions::default() };
+                    if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+                        self.advance();
+                        options.start = Some(self.parse_signed_integer()?);
+                        self.expect_token(TokenType::Comma)?;
+                        options.increment = Some(self.parse_signed_integer()?);
+                        self.expect_token(TokenType::RightParen)?;
+                    }
+                    constraints.push(ColumnConstraint::Identity(options));
+                }
+                // MySQL's short form of a generated column: `AS (expr) [STORED | VIRTUAL]`
+                Some(Token { token_type: TokenType::As, .. }) => {
+                    constraints.push(self.parse_generated_expression()?);
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("COLLATE") =>
+                {
+                    self.advance();
+                    constraints.push(ColumnConstraint::Collate(self.parse_identifier()?));
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("CHARSET")
+                        || (word.eq_ignore_ascii_case("CHARACTER")
+                            && matches!(&self.peek_token, Some(Token { token_type: TokenType::Set, .. }))) =>
+                {
+                    if word.eq_ignore_ascii_case("CHARACTER") {
+                        self.advance();
+                    }
+                    self.advance();
+                    constraints.push(ColumnConstraint::CharacterSet(self.parse_identifier()?));
+                }
+                Some(Token { token_type: TokenType::On, .. })
+                    if matches!(&self.peek_token, Some(Token { token_type: TokenType::Update, .. })) =>
+                {
+                    self.advance();
+                    self.advance();
+                    constraints.push(ColumnConstraint::OnUpdate(self.parse_default_value()?));
+                }
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if word.eq_ignore_ascii_case("COMMENT") =>
+                {
+                    self.advance();
+                    match &self.current_token {
+                        Some(Token { token_type: TokenType::StringLiteral(comment), .. }) => {
+                            constraints.push(ColumnConstraint::Comment(comment.clone()));
+                            self.advance();
+                        }
+                        _ => return Err(self.unexpected_token_error("string literal after COMMENT")),
+                    }
+                }
+                // Column options with no bearing on the schema's shape: MySQL's VISIBLE, SQL Server's
+                // ROWGUIDCOL and SPARSE, Oracle's ENABLE on a constraint
+                Some(Token { token_type: TokenType::Identifier(word), .. })
+                    if ["VISIBLE", "INVISIBLE", "ROWGUIDCOL", "SPARSE", "ENABLE"]
+                        .iter()
+                        .any(|option| word.eq_ignore_ascii_case(option)) =>
+                {
+                    self.advance();
+                }
+                _ => break,
+            }
+        }
+
+        Ok(constraints)
+    }
+
+    /// Parses what follows GENERATED: an identity column or a generated (computed) column.
+    fn parse_generated_column(&mut self) -> Result<ColumnConstraint, ParserError> {
+        let always = match &self.current_token {
+            Some(Token { token_type: TokenType::By, .. }) => {
+                self.advance();
+                self.expect_token(TokenType::Default)?;
+                // Oracle's `BY DEFAULT ON NULL` also fills in explicit NULLs
+                if let Some(Token { token_type: TokenType::On, .. }) = &self.current_token {
+                    self.advance();
+                    self.expect_token(TokenType::NullLiteral)?;
+                }
+                false
+            }
+            _ => {
+                self.expect_keyword("ALWAYS")?;
+                true
+            }
+        };
+
+        if let Some(Token { token_type: TokenType::As, .. }) = &self.current_token
+            && let Some(Token { token_type: TokenType::Identifier(word), .. }) = &self.peek_token
+            && word.eq_ignore_ascii_case("IDENTITY")
+        {
+            self.advance();
+            self.advance();
+            let mut options = self.parse_identity_options()?;
+            options.always = always;
+            return Ok(ColumnConstraint::Identity(options));
+        }
+
+        if !always {
+            return Err(self.unexpected_token_error("AS IDENTITY after GENERATED BY DEFAULT"));
+        }
+        self.parse_generated_expression()
+    }
+
+    fn parse_generated_expression(&mut self) -> Result<ColumnConstraint, ParserError> {
+        self.expect_token(TokenType::As)?;
+        let expression = self.parse_check_expression()?;
+
+        // PostgreSQL only has stored generated columns, MySQL defaults to virtual
+        let stored = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("STORED") => {
+                self.advance();
+                true
+            }
+            Some(Token { token_type: TokenType::Identifier(word), .. }) if word.eq_ignore_ascii_case("VIRTUAL") => {
+                self.advance();
+                false
+            }
+            _ => false,
+        };
+
+        Ok(ColumnConstraint::Generated { expression, stored })
+    }
+
+    /// Parses the optional `(START WITH n INCREMENT BY n ...)` sequence options of an identity column.
+    fn parse_identity_options(&mut self) -> Result<IdentityOptions, ParserError> {
+        let mut options = IdentityOptions::default();
+        if !matches!(&self.current_token, Some(Token { token_type: TokenType::LeftParen, .. })) {
+            return Ok(options);
+        }
+        self.advance();
+
+        while !matches!(&self.current_token, Some(Token { token_type: TokenType::RightParen, .. })) {
+            if !self.parse_sequence_option(&mut options)? {
+                return Err(self.unexpected_token_error("identity option"));
+            }
+        }
+        self.expect_token(TokenType::RightParen)?;
+
+        Ok(options)
+    }
+
+    /// Parses one sequence option into `options`; returns false, consuming nothing, at any other word.
+    fn parse_sequence_option(&mut self, options: &mut IdentityOptions) -> Result<bool, ParserError> {
+        let word = match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(word), .. }) => word.to_uppercase(),
+            _ => return Ok(false),
+        };
+
+        match word.as_str() {
+            "START" => {
+                self.advance();
+                if let Some(Token { token_type: TokenType::With, .. }) = &self.current_token {
+                    self.advance();
+                }
+                options.start = Some(self.parse_signed_integer()?);
+            }
+            "INCREMENT" => {
+                self.advance();
+                if let Some(Token { token_type: TokenType::By, .. }) = &self.current_token {
+                    self.advance();
+                }
+                options.increment = Some(self.parse_signed_integer()?);
+            }
+            "MINVALUE" => {
+                self.advance();
+                options.min_value = self.parse_sequence_bound()?;
+            }
+            "MAXVALUE" => {
+                self.advance();
+                options.max_value = self.parse_sequence_bound()?;
+            }
+            "CYCLE" => {
+                self.advance();
+                options.cycle = true;
+            }
+            "CACHE" => {
+                self.advance();
+                self.parse_signed_integer()?;
+            }
+            // NO MINVALUE, NO MAXVALUE, NO CYCLE are the defaults, as are Oracle's NOMINVALUE, ...
+            "NO" => {
+                self.advance();
+                self.advance();
+            }
+            "NOMINVALUE" | "NOMAXVALUE" | "NOCYCLE" | "NOCACHE" | "NOORDER" | "KEEP" | "NOKEEP" | "NOSCALE" | "NOSHARD"
+            | "GLOBAL" | "SESSION" => {
+                self.advance();
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// A MINVALUE or MAXVALUE; one past what 64 bits hold, as Oracle writes its default
+    /// 28-digit MAXVALUE out in full, is no bound at all.
+    fn parse_sequence_bound(&mut self) -> Result<Option<i64>, ParserError> {
+        let negative = matches!(&self.current_token, Some(Token { token_type: TokenType::Minus, .. }));
+        let number = if negative { &self.peek_token } else { &self.current_token };
+        if let Some(Token { token_type: TokenType::FloatLiteral(_), lexeme, .. }) = number
+            && lexeme.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            if negative {
                 self.advance();
-                if let Some(Token { token_type: TokenType::Table, .. }) = &self.current_token {
-                    self.advance();
-                    let table = self.parse_create_table()?;
-                    Ok(DdlStatement::CreateTable(table))
-                } else {
-                    Err(self.unexpected_token_error("TABLE after CREATE"))
-                }
             }
-            _ => Err(self.unexpected_token_error("DDL statement")),
+            self.advance();
+            return Ok(None);
         }
+        self.parse_signed_integer().map(Some)
     }
 
-    fn parse_create_table(&mut self) -> Result<CreateTableStatement, ParserError> {
-        let if_not_exists = if let Some(Token { token_type: TokenType::If, .. }) = &self.current_token {
+    fn parse_signed_integer(&mut self) -> Result<i64, ParserError> {
+        let negative = if let Some(Token { token_type: TokenType::Minus, .. }) = &self.current_token {
             self.advance();
-            self.expect_token(TokenType::Not)?;
-            self.expect_token(TokenType::Exists)?;
             true
         } else {
             false
         };
+        match &self.current_token {
+            Some(Token { token_type: TokenType::IntegerLiteral(value), .. }) => {
+                let value = if negative { -*value } else { *value };
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.unexpected_token_error("integer")),
+        }
+    }
 
-        let table_name = self.parse_identifier()?;
-        self.expect_token(TokenType::LeftParen)?;
+    /// Parses a type name on its own, such as the target of a CAST.
+    pub fn parse_type(&mut self) -> Result<DataType, ParserError> {
+        let data_type = self.parse_column_data_type()?;
+        match &self.current_token {
+            None => Ok(data_type),
+            Some(_) => Err(self.unexpected_token_error("end of type")),
+        }
+    }
 
-        let mut columns = Vec::new();
-        let mut constraints = Vec::new();
+    fn parse_column_data_type(&mut self) -> Result<DataType, ParserError> {
+        Ok(self.parse_declared_type()?.0)
+    }
 
-        loop {
-            if let Some(Token { token_type: TokenType::RightParen, .. }) = &self.current_token {
-                self.advance();
-                break;
+    /// Parses a column type, also returning its name as declared (upper-cased, with arguments),
+    /// which SQLite derives the column's affinity from.
+    fn parse_declared_type(&mut self) -> Result<(DataType, String), ParserError> {
+        let mut name = match &self.current_token {
+            // SQL Server scripts bracket type names too: `[nvarchar](50)`
+            Some(Token { token_type: TokenType::Identifier(name) | TokenType::QuotedIdentifier(name), .. }) => {
+                name.to_uppercase()
+            }
+            Some(Token { token_type: TokenType::Set, .. }) => "SET".to_string(),
+            Some(token) => {
+                return Err(ParserError::new(
+                    format!("Expected data type, found {:?}", token.token_type),
+                    token.position.clone(),
+                ))
             }
+            None => return Err(ParserError::new("Unexpected end of input in data type".to_string(), Position::default())),
+        };
+        self.advance();
+        // A type from another schema: `sales.amount`
+        while let Some(Token { token_type: TokenType::Dot, .. }) = &self.current_token {
+            self.advance();
+            name = format!("{}.{}", name, self.parse_identifier()?.to_uppercase());
+        }
 
-            if let Some(Token { token_type: TokenType::Primary, .. }) = &self.current_token {
+        // Multi-word type names
+        match (name.as_str(), &self.current_token) {
+            ("DOUBLE", Some(Token { token_type: TokenType::Identifier(next), .. }))
+                if next.eq_ignore_ascii_case("PRECISION") =>
+            {
                 self.advance();
-                self.expect_token(TokenType::Key)?;
-                self.expect_token(TokenType::LeftParen)?;
-                let cols = self.parse_identifier_list()?;
-                self.expect_token(TokenType::RightParen)?;
-                constraints.push(TableConstraint::PrimaryKey { columns: cols });
-            } else if let Some(Token { token_type: TokenType::Foreign, .. }) = &self.current_token {
-                let constraint = self.parse_foreign_key_constraint()?;
-                constraints.push(constraint);
-            } else {
-                // Parse column definition
-                let column = self.parse_column_definition()?;
-                columns.push(column);
             }
-
-            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+            ("CHARACTER", Some(Token { token_type: TokenType::Identifier(next), .. }))
+                if next.eq_ignore_ascii_case("VARYING") =>
+            {
                 self.advance();
-            } else {
-                self.expect_token(TokenType::RightParen)?;
-                break;
+                name = "VARCHAR".to_string();
+            }
+            // SQLite accepts any run of words as a type name: `UNSIGNED BIG INT`, `VARYING CHARACTER(255)`
+            _ => {
+                while let Some(Token { token_type: TokenType::Identifier(next), .. }) = &self.current_token {
+                    let next = next.to_uppercase();
+                    if TYPE_NAME_TERMINATORS.contains(&next.as_str())
+                        || (next == "CHARACTER"
+                            && matches!(&self.peek_token, Some(Token { token_type: TokenType::Set, .. })))
+                    {
+                        break;
+                    }
+                    name = format!("{} {}", name, next);
+                    self.advance();
+                }
             }
         }
 
-        Ok(CreateTableStatement {
-            table_name,
-            if_not_exists,
-            columns,
-            constraints,
-            options: std::collections::HashMap::new(), // Not implemented yet
-        })
-    }
+        if name == "ENUM" || name == "SET" {
+            let values = self.parse_string_list()?;
 
-    fn parse_column_definition(&mut self) -> Result<ColumnDefinition, ParserError> {
-        let name = self.parse_identifier()?;
-        let data_type = self.parse_data_type()?;
-        let data_type = match data_type.as_str() {
-            "INT" | "INTEGER" => DataType::Integer { size: Some(32), unsigned: false },
-            "BIGINT" => DataType::BigInt { unsigned: false },
-            "SMALLINT" => DataType::SmallInt { unsigned: false },
-            "TINYINT" => DataType::TinyInt { unsigned: false },
-            "VARCHAR" => DataType::Varchar { length: None },
-            "TEXT" => DataType::Text,
+            // The value list may be followed by CHARACTER SET / COLLATE, which are column options
+            let declared = format!("{}({})", name, values.iter().map(|v| format!("'{}'", v.replace('\'', "''"))).collect::<Vec<_>>().join(", "));
+            return Ok((
+                if name == "ENUM" { DataType::Enum { values } } else { DataType::Set { values } },
+                declared,
+            ));
+        }
+
+        let mut args = Vec::new();
+        if let Some(Token { token_type: TokenType::LeftParen, .. }) = &self.current_token {
+            self.advance();
+            loop {
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::RightParen, .. }) => break,
+                    Some(Token { token_type: TokenType::Comma, .. }) => self.advance(),
+                    // Oracle's length semantics, `VARCHAR2(100 CHAR)`, don't change the declared length
+                    Some(token) if !args.is_empty() && ["CHAR", "BYTE"].contains(&token.lexeme.to_uppercase().as_str()) => {
+                        self.advance();
+                    }
+                    // SQL Server's `NVARCHAR(MAX)`
+                    Some(token) if token.lexeme.eq_ignore_ascii_case("MAX") => {
+                        args.push("MAX".to_string());
+                        self.advance();
+                    }
+                    Some(token) => {
+                        args.push(token.lexeme.clone());
+                        self.advance();
+                    }
+                    None => return Err(self.unexpected_token_error("')' after type arguments")),
+                }
+            }
+            self.expect_token(TokenType::RightParen)?;
+        }
+
+        if let Some(base) = name.strip_suffix(" WITHOUT TIME ZONE") {
+            name = base.to_string();
+        }
+        if name == "TIMESTAMP"
+            && let Some(Token { token_type: TokenType::With, .. }) = &self.current_token
+        {
+            self.advance();
+            self.expect_keyword("TIME")?;
+            self.expect_keyword("ZONE")?;
+            name = "TIMESTAMPTZ".to_string();
+        }
+
+        let mut unsigned = false;
+        while let Some(Token { token_type: TokenType::Identifier(modifier), .. }) = &self.current_token {
+            match modifier.to_uppercase().as_str() {
+                "UNSIGNED" => unsigned = true,
+                // ZEROFILL only pads values for display; BINARY here is shorthand for a binary collation
+                "SIGNED" | "ZEROFILL" | "BINARY" => {}
+                _ => break,
+            }
+            self.advance();
+        }
+
+        let number = |index: usize| args.get(index).and_then(|arg: &String| arg.parse::<u32>().ok());
+        let mut declared = if args.is_empty() { name.clone() } else { format!("{}({})", name, args.join(", ")) };
+        if unsigned {
+            declared.push_str(" UNSIGNED");
+        }
+
+        let data_type = match name.as_str() {
+            "INT" | "INTEGER" | "INT4" => DataType::Integer { size: Some(32), unsigned },
+            "MEDIUMINT" => DataType::Integer { size: Some(24), unsigned },
+            "BIGINT" | "INT8" => DataType::BigInt { unsigned },
+            "SMALLINT" | "INT2" => DataType::SmallInt { unsigned },
+            "TINYINT" => DataType::TinyInt { unsigned },
             "BOOLEAN" | "BOOL" => DataType::Boolean,
-            "FLOAT" => DataType::Float { precision: None },
-            "DOUBLE" => DataType::Double,
+            "FLOAT" => DataType::Float { precision: number(0) },
+            "DOUBLE" | "FLOAT8" => DataType::Double,
+            "DECIMAL" | "NUMERIC" | "DEC" => DataType::Decimal { precision: number(0), scale: number(1) },
+            "VARCHAR" => DataType::Varchar { length: number(0) },
+            "CHAR" | "CHARACTER" => DataType::Char { length: number(0) },
+            "TEXT" => DataType::Text,
+            "BINARY" => DataType::Binary { length: number(0) },
+            "VARBINARY" => DataType::Varbinary { length: number(0) },
+            "BLOB" => DataType::Blob,
             "DATE" => DataType::Date,
             "TIME" => DataType::Time,
             "DATETIME" => DataType::DateTime,
             "TIMESTAMP" => DataType::Timestamp,
             "JSON" => DataType::Json,
             "UUID" => DataType::Uuid,
-            _ => DataType::Custom(data_type),
+            _ if args.is_empty() => DataType::Custom(name),
+            _ => DataType::Custom(format!("{}({})", name, args.join(", "))),
         };
 
-        let mut constraints = Vec::new();
+        // PostgreSQL arrays: `text[]`, `int[3][3]`; declared sizes are not enforced, so they're dropped
+        let mut dimensions = 0;
+        while let Some(Token { token_type: TokenType::LeftBracket, .. }) = &self.current_token {
+            self.advance();
+            if let Some(Token { token_type: TokenType::IntegerLiteral(_), .. }) = &self.current_token {
+                self.advance();
+            }
+            self.expect_token(TokenType::RightBracket)?;
+            dimensions += 1;
+            declared.push_str("[]");
+        }
+
+        let data_type = if dimensions > 0 {
+            DataType::Array { element_type: Box::new(data_type), dimensions: Some(dimensions) }
+        } else {
+            data_type
+        };
+        Ok((data_type, declared))
+    }
 
-        // Parse column constraints
+    /// Parses a parenthesized list of string literals, as in `ENUM('a', 'b')`.
+    fn parse_string_list(&mut self) -> Result<Vec<String>, ParserError> {
+        self.expect_token(TokenType::LeftParen)?;
+        let mut values = Vec::new();
         loop {
             match &self.current_token {
-                Some(Token { token_type: TokenType::Not, .. }) => {
+                Some(Token { token_type: TokenType::StringLiteral(value), .. }) => {
+                    values.push(value.clone());
                     self.advance();
-                    self.expect_token(TokenType::NullLiteral)?;
-                    constraints.push(ColumnConstraint::NotNull);
-                }
-                Some(Token { token_type: TokenType::NullLiteral, .. }) => {
-                    self.advance();
-                    constraints.push(ColumnConstraint::Null);
-                }
-                Some(Token { token_type: TokenType::Primary, .. }) => {
-                    self.advance();
-                    self.expect_token(TokenType::Key)?;
-                    constraints.push(ColumnConstraint::PrimaryKey);
-                }
-                Some(Token { token_type: TokenType::Unique, .. }) => {
-                    self.advance();
-                    constraints.push(ColumnConstraint::Unique);
-                }
-                Some(Token { token_type: TokenType::AutoIncrement, .. }) => {
-                    self.advance();
-                    constraints.push(ColumnConstraint::AutoIncrement);
-                }
-                Some(Token { token_type: TokenType::Default, .. }) => {
-                    self.advance();
-                    let value = self.parse_default_value()?;
-                    constraints.push(ColumnConstraint::Default(value));
                 }
-                _ => break,
+                _ => return Err(self.unexpected_token_error("string literal in value list")),
+            }
+            if let Some(Token { token_type: TokenType::Comma, .. }) = &self.current_token {
+                self.advance();
+            } else {
+                break;
             }
         }
-
-        Ok(ColumnDefinition {
-            name,
-            data_type,
-            constraints,
-        })
+        self.expect_token(TokenType::RightParen)?;
+        Ok(values)
     }
 
     fn parse_foreign_key_constraint(&mut self) -> Result<TableConstraint, ParserError> {
         self.expect_token(TokenType::Foreign)?;
         self.expect_token(TokenType::Key)?;
-        self.expect_token(TokenType::LeftParen)?;
-        let columns = self.parse_identifier_list()?;
-        self.expect_token(TokenType::RightParen)?;
+        let columns = self.parse_parenthesized_identifiers()?;
 
         self.expect_token(TokenType::References)?;
-        let referenced_table = self.parse_identifier()?;
-        self.expect_token(TokenType::LeftParen)?;
-        let referenced_columns = self.parse_identifier_list()?;
-        self.expect_token(TokenType::RightParen)?;
+        let referenced_table = self.parse_object_name()?;
+        let referenced_columns = self.parse_parenthesized_identifiers()?;
+        let (on_delete, on_update) = self.parse_referential_actions()?;
 
         Ok(TableConstraint::ForeignKey {
             name: None,
             columns,
             referenced_table,
             referenced_columns,
-            on_delete: None,
-            on_update: None,
+            on_delete,
+            on_update,
         })
     }
 
+    fn parse_parenthesized_identifiers(&mut self) -> Result<Vec<String>, ParserError> {
+        self.expect_token(TokenType::LeftParen)?;
+        let identifiers = self.parse_identifier_list()?;
+        self.expect_token(TokenType::RightParen)?;
+        Ok(identifiers)
+    }
+
+    fn parse_referential_actions(
+        &mut self,
+    ) -> Result<(Option<ReferentialAction>, Option<ReferentialAction>), ParserError> {
+        let mut on_delete = None;
+        let mut on_update = None;
+
+        while let Some(Token { token_type: TokenType::On, .. }) = &self.current_token {
+            match &self.peek_token {
+                Some(Token { token_type: TokenType::Delete, .. }) => {
+                    self.advance();
+                    self.advance();
+                    on_delete = Some(self.parse_referential_action()?);
+                }
+                Some(Token { token_type: TokenType::Update, .. }) => {
+                    self.advance();
+                    self.advance();
+                    on_update = Some(self.parse_referential_action()?);
+                }
+                _ => break,
+            }
+        }
+
+        Ok((on_delete, on_update))
+    }
+
+    fn parse_referential_action(&mut self) -> Result<ReferentialAction, ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Set, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::NullLiteral, .. }) => {
+                        self.advance();
+                        Ok(ReferentialAction::SetNull)
+                    }
+                    Some(Token { token_type: TokenType::Default, .. }) => {
+                        self.advance();
+                        Ok(ReferentialAction::SetDefault)
+                    }
+                    _ => Err(self.unexpected_token_error("NULL or DEFAULT after SET")),
+                }
+            }
+            Some(Token { token_type: TokenType::Identifier(action), .. }) => {
+                let action = match action.to_uppercase().as_str() {
+                    "CASCADE" => ReferentialAction::Cascade,
+                    "RESTRICT" => ReferentialAction::Restrict,
+                    "NO" => {
+                        self.advance();
+                        self.expect_keyword("ACTION")?;
+                        return Ok(ReferentialAction::NoAction);
+                    }
+                    _ => return Err(self.unexpected_token_error("referential action")),
+                };
+                self.advance();
+                Ok(action)
+            }
+            _ => Err(self.unexpected_token_error("referential action")),
+        }
+    }
+
+    fn parse_check_expression(&mut self) -> Result<String, ParserError> {
+        if !matches!(&self.current_token, Some(Token { token_type: TokenType::LeftParen, .. })) {
+            return Err(self.unexpected_token_error("'(' after CHECK"));
+        }
+        let text = self.collect_parenthesized_text()?;
+        Ok(text[1..text.len() - 1].to_string())
+    }
+
     fn parse_default_value(&mut self) -> Result<String, ParserError> {
+        let mut value = self.parse_default_operand()?;
+
+        // PostgreSQL casts such as `'{}'::jsonb`
+        while let Some(Token { token_type: TokenType::DoubleColon, .. }) = &self.current_token {
+            self.advance();
+            value = format!("{}::{}", value, self.parse_data_type()?);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_default_operand(&mut self) -> Result<String, ParserError> {
         match &self.current_token {
             Some(Token { token_type: TokenType::StringLiteral(value), .. }) => {
-                let val = value.clone();
+                let val = value.replace('\'', "''");
                 self.advance();
                 Ok(format!("'{}'", val))
             }
@@ -1181,8 +3948,9 @@ impl Parser {
                 self.advance();
                 Ok(val)
             }
-            Some(Token { token_type: TokenType::FloatLiteral(value), .. }) => {
-                let val = value.to_string();
+            Some(Token { token_type: TokenType::FloatLiteral(_) | TokenType::HexLiteral(_), lexeme, .. }) => {
+                // Keep the written form so `0.00` doesn't lose its scale
+                let val = lexeme.clone();
                 self.advance();
                 Ok(val)
             }
@@ -1195,10 +3963,118 @@ impl Parser {
                 self.advance();
                 Ok("NULL".to_string())
             }
+            Some(Token { token_type: TokenType::Minus, .. }) => {
+                self.advance();
+                match &self.current_token {
+                    Some(Token { token_type: TokenType::IntegerLiteral(_) | TokenType::FloatLiteral(_), lexeme, .. }) => {
+                        let val = format!("-{}", lexeme);
+                        self.advance();
+                        Ok(val)
+                    }
+                    _ => Err(self.unexpected_token_error("number after '-'")),
+                }
+            }
+            Some(Token { token_type: TokenType::Identifier(name), .. }) => {
+                // Niladic functions such as CURRENT_TIMESTAMP, or calls such as now()
+                let mut val = name.clone();
+                self.advance();
+                match &self.current_token {
+                    // MySQL bit/hex literals `b'0'`, `x'1F'` and charset introducers `_utf8mb4'x'`
+                    Some(Token { token_type: TokenType::StringLiteral(_), .. })
+                        if val.starts_with('_') || ["B", "X", "N"].contains(&val.to_uppercase().as_str()) =>
+                    {
+                        if let Some(token) = &self.current_token {
+                            val.push_str(&token.to_sql());
+                        }
+                        self.advance();
+                    }
+                    Some(Token { token_type: TokenType::LeftParen, .. }) => {
+                        val.push_str(&self.collect_parenthesized_text()?);
+                    }
+                    // Oracle's `orders_seq.NEXTVAL`
+                    Some(Token { token_type: TokenType::Dot, .. }) => {
+                        self.advance();
+                        val = format!("{}.{}", val, self.parse_identifier()?);
+                    }
+                    // `NEXT VALUE FOR orders_seq`
+                    Some(Token { token_type: TokenType::Identifier(next), .. })
+                        if val.eq_ignore_ascii_case("NEXT") && next.eq_ignore_ascii_case("VALUE") =>
+                    {
+                        self.advance();
+                        self.expect_keyword("FOR")?;
+                        val = format!("NEXT VALUE FOR {}", self.parse_object_name()?);
+                    }
+                    Some(Token { token_type: TokenType::LeftBracket, .. }) => {
+                        // ARRAY[...] literals
+                        val.push_str(&self.collect_raw_text(|token| *token == TokenType::RightBracket));
+                        self.expect_token(TokenType::RightBracket)?;
+                        val.push(']');
+                    }
+                    _ => {}
+                }
+                Ok(val)
+            }
+            Some(Token { token_type: TokenType::LeftParen, .. }) => self.collect_parenthesized_text(),
             _ => Err(self.unexpected_token_error("default value")),
         }
     }
 
+    /// Consumes a balanced parenthesized group and returns its source text, parentheses included.
+    fn collect_parenthesized_text(&mut self) -> Result<String, ParserError> {
+        let position = match &self.current_token {
+            Some(token) => token.position.clone(),
+            None => Position::default(),
+        };
+        let mut depth = 0usize;
+        let mut tokens = Vec::new();
+
+        while let Some(token) = self.current_token.clone() {
+            match token.token_type {
+                TokenType::LeftParen => depth += 1,
+                TokenType::RightParen => depth -= 1,
+                _ => {}
+            }
+            tokens.push(token);
+            self.advance();
+            if depth == 0 {
+                return Ok(tokens_to_sql(&tokens));
+            }
+        }
+
+        Err(ParserError::new("Unbalanced parentheses", position))
+    }
+
+    /// Consumes tokens up to (not including) the first one matching `stop` outside parentheses.
+    fn collect_raw_text(&mut self, stop: impl Fn(&TokenType) -> bool) -> String {
+        let mut depth = 0usize;
+        let mut tokens = Vec::new();
+
+        while let Some(token) = self.current_token.clone() {
+            if depth == 0 && stop(&token.token_type) {
+                break;
+            }
+            match token.token_type {
+                TokenType::LeftParen => depth += 1,
+                TokenType::RightParen => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(token);
+            self.advance();
+        }
+
+        tokens_to_sql(&tokens)
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParserError> {
+        match &self.current_token {
+            Some(Token { token_type: TokenType::Identifier(name), .. }) if name.eq_ignore_ascii_case(keyword) => {
+                self.advance();
+                Ok(())
+            }
+            _ => Err(self.unexpected_token_error(keyword)),
+        }
+    }
+
     fn expect_token(&mut self, expected: TokenType) -> Result<(), ParserError> {
         match &self.current_token {
             Some(token) if token.token_type == expected => {
@@ -1217,7 +4093,18 @@ impl Parser {
     }
 
     fn advance(&mut self) {
-        self.current_token = self.peek_token.take();
+        let previous = std::mem::replace(&mut self.current_token, self.peek_token.take());
+        if let Some(token) = previous {
+            let length = token.lexeme.chars().count();
+            self.previous_end = Position {
+                line: token.position.line,
+                column: token.position.column + length,
+                index: token.position.index + length,
+            };
+            if let Some(recorded) = &mut self.recorded {
+                recorded.push(token);
+            }
+        }
         self.peek_token = self.tokens.next();
     }
 
@@ -1252,6 +4139,91 @@ impl Parser {
     }
 }
 
+/// A routine parameter from its tokens: `[IN | OUT | INOUT] [name] type [{DEFAULT | =} value]`,
+/// with Oracle's mode after the name and SQL Server's `OUTPUT` after the type.
+fn routine_parameter(tokens: &[Token]) -> RoutineParameter {
+    let mode_of = |token: &Token| match &token.token_type {
+        TokenType::In => Some(ParameterMode::In),
+        TokenType::Identifier(word) if word.eq_ignore_ascii_case("OUT") || word.eq_ignore_ascii_case("OUTPUT") => {
+            Some(ParameterMode::Out)
+        }
+        TokenType::Identifier(word) if word.eq_ignore_ascii_case("INOUT") => Some(ParameterMode::InOut),
+        TokenType::Identifier(word) if word.eq_ignore_ascii_case("VARIADIC") => Some(ParameterMode::In),
+        _ => None,
+    };
+    let is_name = |token: &Token| matches!(token.token_type, TokenType::Identifier(_) | TokenType::QuotedIdentifier(_));
+
+    let default_at = tokens.iter().position(|token| matches!(token.token_type, TokenType::Default | TokenType::Equal));
+    let (mut rest, default) = match default_at {
+        Some(at) => (&tokens[..at], Some(tokens_to_sql(&tokens[at + 1..]))),
+        None => (tokens, None),
+    };
+    let mut modes = Vec::new();
+    let mut take_modes = |rest: &mut &[Token]| {
+        while let [first, tail @ ..] = *rest
+            && !tail.is_empty()
+            && let Some(mode) = mode_of(first)
+        {
+            modes.push(mode);
+            *rest = tail;
+        }
+    };
+
+    take_modes(&mut rest);
+    let name = match rest {
+        [at, name, tail @ ..] if at.token_type == TokenType::AtSign && is_name(name) => {
+            rest = tail;
+            Some(format!("@{}", name.lexeme))
+        }
+        // `double precision` and `character varying` are types, not a name and a type
+        [name, next, ..]
+            if is_name(name)
+                && (is_name(next) || mode_of(next).is_some())
+                && !["DOUBLE", "CHARACTER"].iter().any(|word| name.lexeme.eq_ignore_ascii_case(word)) =>
+        {
+            rest = &rest[1..];
+            Some(match &name.token_type {
+                TokenType::QuotedIdentifier(name) => name.clone(),
+                _ => name.lexeme.clone(),
+            })
+        }
+        _ => None,
+    };
+    take_modes(&mut rest);
+    while let [head @ .., last] = rest
+        && !head.is_empty()
+        && matches!(&last.token_type, TokenType::Identifier(word) if word.eq_ignore_ascii_case("OUTPUT")
+            || word.eq_ignore_ascii_case("OUT") || word.eq_ignore_ascii_case("READONLY"))
+    {
+        if !last.lexeme.eq_ignore_ascii_case("READONLY") {
+            modes.push(ParameterMode::Out);
+        }
+        rest = head;
+    }
+
+    let out = modes.iter().any(|mode| *mode != ParameterMode::In);
+    let mode = if modes.contains(&ParameterMode::InOut) || (out && modes.contains(&ParameterMode::In)) {
+        ParameterMode::InOut
+    } else if out {
+        ParameterMode::Out
+    } else {
+        ParameterMode::In
+    };
+    RoutineParameter { name, data_type: tokens_to_sql(rest), mode, default }
+}
+
+/// A parenthesized query with its own ORDER BY, LIMIT or OFFSET, as a derived table, so a set
+/// operation or ordering applied on top of it doesn't change what it returns.
+fn isolated(select: SelectStatement) -> SelectStatement {
+    if select.order_by.is_empty() && select.limit.is_none() && select.offset.is_none() {
+        return select;
+    }
+    SelectStatement::new().with_select_list(vec![SelectItem::Wildcard]).with_from(vec![TableReference::Subquery {
+        query: Box::new(select),
+        alias: "unnamed_subquery".to_string(),
+    }])
+}
+
 impl ParserError {
     pub fn new(message: impl Into<String>, position: Position) -> Self {
         Self {
@@ -1268,3 +4240,38 @@ impl std::fmt::Display for ParserError {
 }
 
 impl std::error::Error for ParserError {}
+
+/// Words that end MySQL's `ALGORITHM=...` or `DEFINER=user@host` after CREATE.
+fn is_view_prefix(word: &str) -> bool {
+    ["SQL", "VIEW", "ALGORITHM", "DEFINER", "TRIGGER", "FUNCTION", "PROCEDURE"]
+        .iter()
+        .any(|prefix| word.eq_ignore_ascii_case(prefix))
+}
+
+/// Whether SQL Server's `CHECK` or `NOCHECK` turns constraint checking on.
+fn constraint_checking(token: &Token) -> Option<bool> {
+    match &token.token_type {
+        TokenType::Check => Some(true),
+        TokenType::Identifier(word) if word.eq_ignore_ascii_case("NOCHECK") => Some(false),
+        _ => None,
+    }
+}
+
+/// The MySQL index flavour named by `FULLTEXT` or `SPATIAL`.
+fn index_kind(word: &str) -> Option<IndexKind> {
+    match word.to_uppercase().as_str() {
+        "FULLTEXT" => Some(IndexKind::FullText),
+        "SPATIAL" => Some(IndexKind::Spatial),
+        _ => None,
+    }
+}
+
+/// Decodes hexadecimal digits into bytes; an odd leading digit stands alone, as MySQL reads it.
+fn decode_hex(digits: &str) -> Vec<u8> {
+    let padded = if digits.len() % 2 == 1 { format!("0{}", digits) } else { digits.to_string() };
+    padded
+        .as_bytes()
+        .chunks(2)
+        .filter_map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
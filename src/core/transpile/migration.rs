@@ -0,0 +1,1443 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::analyze::{ChangeAction, DiffObject, SchemaDiff, SchemaDiffer};
+use crate::core::domain::entity::{
+    CheckConstraint, Column, DataType, Database, ExclusionConstraint, ForeignKey, Index, IndexType, NullsPosition,
+    PrimaryKey, ReferentialAction, SortOrder, Table, UniqueConstraint, UserDefinedType, UserDefinedTypeKind, View,
+    ViewType,
+};
+use crate::core::domain::field::FieldService;
+use crate::core::domain::relationships::RelationshipGraph;
+use crate::core::parse::{DialectConfig, Lexer, SqlDialect, TokenType};
+
+use super::transpiler::{quote_identifier, quote_literal, TranspileWarning};
+
+/// One statement of a migration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationStep {
+    pub sql: String,
+    /// What running the statement destroys or risks, such as the rows of a dropped table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// The statements that turn one schema into another, and the ones that turn it back.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Migration {
+    pub up: Vec<MigrationStep>,
+    pub down: Vec<MigrationStep>,
+    /// Changes the dialect can't make with a statement, left to be written by hand
+    pub warnings: Vec<TranspileWarning>,
+}
+
+impl Migration {
+    pub fn up_sql(&self) -> String {
+        script(&self.up)
+    }
+
+    pub fn down_sql(&self) -> String {
+        script(&self.down)
+    }
+}
+
+/// The statements a blank line apart, each after a `-- WARNING:` line per warning it carries.
+fn script(steps: &[MigrationStep]) -> String {
+    if steps.is_empty() {
+        return "-- No schema changes.\n".to_string();
+    }
+    let mut sql = steps
+        .iter()
+        .map(|step| {
+            let mut text: String = step.warnings.iter().map(|warning| format!("-- WARNING: {}\n", warning)).collect();
+            text.push_str(&step.sql);
+            text
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    sql.push('\n');
+    sql
+}
+
+/// Writes the DDL that turns one [`Database`] into another in a dialect, following the
+/// [`SchemaDiff`] between them so renamed tables and columns are renamed rather than dropped
+/// and recreated. The down-migration is the same plan from the new schema to the old one.
+///
+/// Statements run in an order each can succeed in: views and the keys and indexes about to
+/// change are dropped first, then tables and columns are renamed, new tables created in
+/// foreign key order as [`RelationshipGraph::creation_order`] gives it, columns added, altered
+/// and dropped, removed tables dropped in reverse order, and finally keys, indexes and views
+/// put back. Steps that lose data, such as dropping a column, carry a warning.
+pub struct MigrationGenerator<'a> {
+    old: &'a Database,
+    new: &'a Database,
+    dialect: SqlDialect,
+}
+
+impl<'a> MigrationGenerator<'a> {
+    pub fn new(old: &'a Database, new: &'a Database, dialect: SqlDialect) -> Self {
+        Self { old, new, dialect }
+    }
+
+    pub fn generate(&self) -> Migration {
+        let diff = SchemaDiffer::new(self.old, self.new).diff();
+        let mut warnings = Vec::new();
+        for change in &diff.changes {
+            if matches!(change.object, DiffObject::Trigger | DiffObject::Procedure | DiffObject::Function) {
+                warn(
+                    &mut warnings,
+                    &change.name,
+                    format!("{} {} are not migrated; write the migration by hand", change.action, change.object),
+                );
+            }
+        }
+
+        let up = Planner::new(self.old, self.new, &diff, &self.dialect).plan();
+        let reverse = SchemaDiffer::new(self.new, self.old).diff();
+        let down = Planner::new(self.new, self.old, &reverse, &self.dialect).plan();
+        for warning in up.warnings.iter().chain(&down.warnings).cloned() {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        // A warning only the rollback raises says so
+        for warning in warnings.iter_mut().filter(|warning| !up.warnings.contains(warning)) {
+            if down.warnings.contains(warning) {
+                warning.message = format!("in the down-migration, {}", warning.message);
+            }
+        }
+        Migration { up: up.steps, down: down.steps, warnings }
+    }
+}
+
+#[derive(Default)]
+struct Plan {
+    steps: Vec<MigrationStep>,
+    warnings: Vec<TranspileWarning>,
+}
+
+impl Plan {
+    fn push(&mut self, sql: String) {
+        self.push_with(sql, Vec::new());
+    }
+
+    fn push_with(&mut self, sql: String, warnings: Vec<String>) {
+        self.steps.push(MigrationStep { sql, warnings });
+    }
+
+    fn warn(&mut self, object: &str, message: impl Into<String>) {
+        let warning = TranspileWarning { object: object.to_string(), message: message.into() };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+/// A table found in both schemas, with each of its columns and the column it was, if any.
+struct TableMatch<'d> {
+    from: &'d Table,
+    to: &'d Table,
+    columns: Vec<(Option<&'d Column>, &'d Column)>,
+    dropped: Vec<&'d Column>,
+}
+
+/// The names of the schema being migrated from as the target schema knows them.
+#[derive(Default)]
+struct Renames {
+    tables: HashMap<String, String>,
+    columns: HashMap<String, HashMap<String, String>>,
+    types: HashMap<String, String>,
+}
+
+/// How a table's keys and indexes are named when rendered: as they are, or through the
+/// renames, so the old definitions can be compared with the new ones as text.
+#[derive(Clone, Copy)]
+enum Names<'r> {
+    AsIs,
+    Renamed(&'r Renames),
+}
+
+impl Names<'_> {
+    fn table(&self, table: &str) -> String {
+        match self {
+            Names::AsIs => table.to_string(),
+            Names::Renamed(renames) => renames.tables.get(&table.to_lowercase()).cloned().unwrap_or_else(|| table.to_string()),
+        }
+    }
+
+    fn column(&self, table: &str, column: &str) -> String {
+        match self {
+            Names::AsIs => column.to_string(),
+            Names::Renamed(renames) => renames
+                .columns
+                .get(&table.to_lowercase())
+                .and_then(|columns| columns.get(&column.to_lowercase()))
+                .cloned()
+                .unwrap_or_else(|| column.to_string()),
+        }
+    }
+
+    fn user_type(&self, name: &str) -> String {
+        match self {
+            Names::AsIs => name.to_string(),
+            Names::Renamed(renames) => renames.types.get(&name.to_lowercase()).cloned().unwrap_or_else(|| name.to_string()),
+        }
+    }
+}
+
+/// A constraint of a table, whichever kind it is.
+#[derive(Clone, Copy)]
+enum Key<'d> {
+    Primary(&'d PrimaryKey),
+    Unique(&'d UniqueConstraint),
+    Check(&'d CheckConstraint),
+    Exclusion(&'d ExclusionConstraint),
+    Foreign(&'d ForeignKey),
+}
+
+impl<'d> Key<'d> {
+    fn of(table: &'d Table) -> Vec<Key<'d>> {
+        let mut keys: Vec<Key> = table.primary_key.iter().map(Key::Primary).collect();
+        keys.extend(table.unique_constraints.iter().map(Key::Unique));
+        keys.extend(table.check_constraints.iter().map(Key::Check));
+        keys.extend(table.exclusion_constraints.iter().map(Key::Exclusion));
+        keys.extend(table.foreign_keys.iter().map(Key::Foreign));
+        keys
+    }
+
+    fn name(&self) -> Option<&'d str> {
+        match self {
+            Key::Primary(key) => key.constraint_name.as_deref().or(key.name.as_deref()),
+            Key::Unique(unique) => unique.constraint_name.as_deref().or(unique.name.as_deref()),
+            Key::Check(check) => check.constraint_name.as_deref().or(check.name.as_deref()),
+            Key::Exclusion(exclusion) => exclusion.name.as_deref(),
+            Key::Foreign(fk) => fk.constraint_name.as_deref().or(fk.name.as_deref()),
+        }
+    }
+
+    fn is(&self, other: &Key) -> bool {
+        match (self, other) {
+            (Key::Primary(a), Key::Primary(b)) => std::ptr::eq(*a, *b),
+            (Key::Unique(a), Key::Unique(b)) => std::ptr::eq(*a, *b),
+            (Key::Check(a), Key::Check(b)) => std::ptr::eq(*a, *b),
+            (Key::Exclusion(a), Key::Exclusion(b)) => std::ptr::eq(*a, *b),
+            (Key::Foreign(a), Key::Foreign(b)) => std::ptr::eq(*a, *b),
+            _ => false,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Key::Primary(_) => "primary key",
+            Key::Unique(_) => "unique constraint",
+            Key::Check(_) => "check constraint",
+            Key::Exclusion(_) => "exclusion constraint",
+            Key::Foreign(_) => "foreign key",
+        }
+    }
+}
+
+/// Plans one direction of a migration, from one schema to the other.
+struct Planner<'d> {
+    from: &'d Database,
+    to: &'d Database,
+    diff: &'d SchemaDiff,
+    dialect: &'d SqlDialect,
+    config: DialectConfig,
+    matches: Vec<TableMatch<'d>>,
+    added: Vec<&'d Table>,
+    removed: Vec<&'d Table>,
+    renames: Renames,
+}
+
+impl<'d> Planner<'d> {
+    fn new(from: &'d Database, to: &'d Database, diff: &'d SchemaDiff, dialect: &'d SqlDialect) -> Self {
+        let renamed = |object: DiffObject| -> HashMap<String, String> {
+            diff.changes
+                .iter()
+                .filter(|change| change.action == ChangeAction::Renamed && change.object == object)
+                .filter_map(|change| Some((change.name.to_lowercase(), change.old.clone()?)))
+                .collect()
+        };
+        let added_names = |object: DiffObject| -> HashSet<String> {
+            diff.changes
+                .iter()
+                .filter(|change| change.action == ChangeAction::Added && change.object == object)
+                .map(|change| change.name.to_lowercase())
+                .collect()
+        };
+        let (renamed_tables, renamed_columns) = (renamed(DiffObject::Table), renamed(DiffObject::Column));
+        let (added_tables, added_columns) = (added_names(DiffObject::Table), added_names(DiffObject::Column));
+
+        let mut matches: Vec<TableMatch> = Vec::new();
+        let mut added = Vec::new();
+        for table in to.get_all_tables() {
+            let name = display(table);
+            let previous = renamed_tables.get(&name.to_lowercase()).cloned().unwrap_or_else(|| name.clone());
+            let from_table = find_table(from, &previous)
+                .filter(|_| !added_tables.contains(&name.to_lowercase()))
+                .filter(|candidate| !matches.iter().any(|other| std::ptr::eq(other.from, *candidate)));
+            let Some(from_table) = from_table else {
+                added.push(table);
+                continue;
+            };
+
+            let columns: Vec<(Option<&Column>, &Column)> = table
+                .columns
+                .iter()
+                .map(|column| {
+                    let key = format!("{}.{}", name, column.name).to_lowercase();
+                    if added_columns.contains(&key) {
+                        return (None, column);
+                    }
+                    let previous = renamed_columns
+                        .get(&key)
+                        .and_then(|old| old.rsplit_once('.'))
+                        .map_or(column.name.as_str(), |(_, old)| old);
+                    (find_column(from_table, previous), column)
+                })
+                .collect();
+            let dropped = from_table
+                .columns
+                .iter()
+                .filter(|column| !columns.iter().any(|(from, _)| from.is_some_and(|from| std::ptr::eq(from, *column))))
+                .collect();
+            matches.push(TableMatch { from: from_table, to: table, columns, dropped });
+        }
+        let removed = from
+            .get_all_tables()
+            .into_iter()
+            .filter(|table| !matches.iter().any(|other| std::ptr::eq(other.from, *table)))
+            .collect();
+
+        let mut renames = Renames::default();
+        for table in &matches {
+            let old = display(table.from).to_lowercase();
+            renames.tables.insert(old.clone(), display(table.to));
+            let columns = renames.columns.entry(old).or_default();
+            for (from, to) in &table.columns {
+                if let Some(from) = from {
+                    columns.insert(from.name.to_lowercase(), to.name.clone());
+                }
+            }
+        }
+        // Columns name their type without its schema
+        for (new, old) in renamed(DiffObject::Type) {
+            let bare = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+            let new_type = diff.changes.iter().find(|change| change.name.to_lowercase() == new).map(|change| bare(&change.name));
+            if let Some(new_type) = new_type {
+                renames.types.insert(bare(&old).to_lowercase(), new_type);
+            }
+        }
+
+        let mut planner = Self {
+            from,
+            to,
+            diff,
+            dialect,
+            config: dialect.config(),
+            matches,
+            added,
+            removed,
+            renames,
+        };
+        planner.sort_by_dependencies();
+        planner
+    }
+
+    /// Puts tables in foreign key order: referenced tables are created first and dropped last.
+    fn sort_by_dependencies(&mut self) {
+        let position = |database: &Database| -> HashMap<String, usize> {
+            let order = RelationshipGraph::from_database(database).creation_order();
+            order.into_iter().enumerate().map(|(index, table)| (table.to_lowercase(), index)).collect()
+        };
+        let (from_order, to_order) = (position(self.from), position(self.to));
+        let rank = |order: &HashMap<String, usize>, table: &Table| {
+            order.get(&table.name.to_lowercase()).copied().unwrap_or(usize::MAX)
+        };
+        self.matches.sort_by_key(|table| rank(&to_order, table.to));
+        self.added.sort_by_key(|table| rank(&to_order, table));
+        self.removed.sort_by_key(|table| std::cmp::Reverse(rank(&from_order, table)));
+    }
+
+    fn plan(&self) -> Plan {
+        let mut plan = Plan::default();
+        self.drop_views(&mut plan);
+        self.drop_keys(&mut plan);
+        self.rename(&mut plan);
+        self.create_types(&mut plan);
+        let deferred = self.create_tables(&mut plan);
+        self.alter_tables(&mut plan);
+        self.drop_tables(&mut plan);
+        self.drop_types(&mut plan);
+        self.add_keys(&mut plan, &deferred);
+        self.create_views(&mut plan);
+        plan
+    }
+
+    /// Views that change, and views over tables that change, are dropped up front and created
+    /// again at the end, since a view can stop a column it reads from being altered or dropped.
+    fn drop_views(&self, plan: &mut Plan) {
+        let (dropped, _) = self.rebuilt_views();
+        for view in dropped.iter().rev() {
+            plan.push(format!("DROP {} {};", view_kind(view), self.quote_path(&qualified(&view.schema, &view.name))));
+        }
+    }
+
+    fn create_views(&self, plan: &mut Plan) {
+        let (_, created) = self.rebuilt_views();
+        for view in created {
+            let definition = view.definition.trim().trim_end_matches(';').trim_end();
+            plan.push(format!(
+                "CREATE {} {} AS\n{};",
+                view_kind(view),
+                self.quote_path(&qualified(&view.schema, &view.name)),
+                definition
+            ));
+        }
+    }
+
+    /// The views to drop from the old schema and to create in the new one.
+    fn rebuilt_views(&self) -> (Vec<&'d View>, Vec<&'d View>) {
+        let mut changed: HashSet<String> = HashSet::new();
+        for change in self.diff.changes.iter().filter(|change| change.object == DiffObject::View) {
+            changed.insert(change.name.to_lowercase());
+            if let Some(old) = change.old.as_ref().filter(|_| change.action == ChangeAction::Renamed) {
+                changed.insert(old.to_lowercase());
+            }
+        }
+
+        let mut touched: HashSet<String> = HashSet::new();
+        for table in &self.removed {
+            touched.insert(table.name.to_lowercase());
+        }
+        for table in &self.matches {
+            let renamed = !table.from.name.eq_ignore_ascii_case(&table.to.name);
+            let columns_changed = !table.dropped.is_empty()
+                || table.columns.iter().any(|(from, to)| match from {
+                    Some(from) => !from.name.eq_ignore_ascii_case(&to.name) || self.column_changed(from, to),
+                    None => false,
+                });
+            if renamed || columns_changed {
+                touched.insert(table.from.name.to_lowercase());
+                touched.insert(table.to.name.to_lowercase());
+            }
+        }
+        let reads_touched = |view: &View| {
+            view.dependencies.iter().any(|dependency| {
+                let table = dependency.rsplit('.').next().unwrap_or(dependency);
+                touched.contains(&table.to_lowercase())
+            })
+        };
+
+        let from_views = self.from.get_all_views();
+        let to_views = self.to.get_all_views();
+        let name = |view: &View| qualified(&view.schema, &view.name).to_lowercase();
+        let counterpart = |view: &View, views: &[&'d View]| views.iter().find(|other| name(other) == name(view)).copied();
+        let rebuilt = |view: &View, other: Option<&View>| {
+            changed.contains(&name(view)) || other.is_some_and(|other| reads_touched(view) || reads_touched(other))
+        };
+
+        let dropped = from_views.iter().filter(|view| rebuilt(view, counterpart(view, &to_views))).copied().collect();
+        let created = to_views.iter().filter(|view| rebuilt(view, counterpart(view, &from_views))).copied().collect();
+        (dropped, created)
+    }
+
+    /// Drops the keys and indexes that change or go away while the old names still hold,
+    /// foreign keys first so the keys they reference can go. Removed tables that reference
+    /// each other in a cycle lose the keys that would stop them being dropped in turn.
+    fn drop_keys(&self, plan: &mut Plan) {
+        let mut keys = Vec::new();
+        let mut indexes = Vec::new();
+        for table in &self.matches {
+            let current: HashSet<String> =
+                Key::of(table.to).iter().map(|key| self.key_clause(table.to, key, Names::AsIs)).collect();
+            for key in Key::of(table.from) {
+                if !current.contains(&self.key_clause(table.from, &key, Names::Renamed(&self.renames))) {
+                    keys.push((table.from, key));
+                }
+            }
+            let current: HashSet<String> =
+                table.to.indexes.iter().map(|index| self.create_index(table.to, index, Names::AsIs)).collect();
+            for index in &table.from.indexes {
+                if !current.contains(&self.create_index(table.from, index, Names::Renamed(&self.renames))) {
+                    indexes.push((table.from, index));
+                }
+            }
+        }
+
+        if *self.dialect != SqlDialect::SQLite {
+            let dropped_before: HashMap<String, usize> =
+                self.removed.iter().enumerate().map(|(position, table)| (table.name.to_lowercase(), position)).collect();
+            for (position, table) in self.removed.iter().enumerate() {
+                for fk in &table.foreign_keys {
+                    let target = dropped_before.get(&fk.referenced_table.to_lowercase());
+                    if target.is_some_and(|target| *target < position) {
+                        keys.push((table, Key::Foreign(fk)));
+                    }
+                }
+            }
+        }
+
+        keys.sort_by_key(|(_, key)| !matches!(key, Key::Foreign(_)));
+        for (table, key) in keys {
+            self.drop_key(table, &key, plan);
+        }
+        for (table, index) in indexes {
+            plan.push(self.drop_index(table, index));
+        }
+    }
+
+    fn add_keys(&self, plan: &mut Plan, deferred: &[(&Table, &ForeignKey)]) {
+        let mut keys = Vec::new();
+        for table in &self.matches {
+            let previous: HashSet<String> = Key::of(table.from)
+                .iter()
+                .map(|key| self.key_clause(table.from, key, Names::Renamed(&self.renames)))
+                .collect();
+            for key in Key::of(table.to) {
+                if !previous.contains(&self.key_clause(table.to, &key, Names::AsIs)) {
+                    keys.push((table.to, key));
+                }
+            }
+        }
+        keys.extend(deferred.iter().map(|(table, fk)| (*table, Key::Foreign(fk))));
+        keys.sort_by_key(|(_, key)| matches!(key, Key::Foreign(_)));
+
+        for (table, key) in keys.iter().filter(|(_, key)| !matches!(key, Key::Foreign(_))) {
+            self.add_key(table, key, plan);
+        }
+        for table in &self.matches {
+            let previous: HashSet<String> = table
+                .from
+                .indexes
+                .iter()
+                .map(|index| self.create_index(table.from, index, Names::Renamed(&self.renames)))
+                .collect();
+            for index in &table.to.indexes {
+                let sql = self.create_index(table.to, index, Names::AsIs);
+                if !previous.contains(&sql) {
+                    plan.push(sql);
+                }
+            }
+        }
+        for (table, key) in keys.iter().filter(|(_, key)| matches!(key, Key::Foreign(_))) {
+            self.add_key(table, key, plan);
+        }
+    }
+
+    fn add_key(&self, table: &Table, key: &Key, plan: &mut Plan) {
+        if *self.dialect == SqlDialect::SQLite {
+            plan.warn(&display(table), "SQLite cannot add or drop constraints of an existing table; rebuild it by hand");
+            return;
+        }
+        plan.push(format!("ALTER TABLE {} ADD {};", self.table_name(table), self.key_clause(table, key, Names::AsIs)));
+    }
+
+    fn drop_key(&self, table: &Table, key: &Key, plan: &mut Plan) {
+        let object = display(table);
+        if *self.dialect == SqlDialect::SQLite {
+            plan.warn(&object, "SQLite cannot add or drop constraints of an existing table; rebuild it by hand");
+            return;
+        }
+
+        let table_name = self.table_name(table);
+        if matches!(key, Key::Primary(_)) && matches!(self.dialect, SqlDialect::MySQL | SqlDialect::Oracle) {
+            plan.push(format!("ALTER TABLE {} DROP PRIMARY KEY;", table_name));
+            return;
+        }
+        let mut warnings = Vec::new();
+        let name = match key.name() {
+            Some(name) => name.to_string(),
+            None => match self.default_key_name(table, key) {
+                Some(name) => {
+                    warnings.push(format!(
+                        "assumes the unnamed {} on {} has {}'s default name",
+                        key.kind(),
+                        object,
+                        self.config.name
+                    ));
+                    name
+                }
+                None => {
+                    plan.warn(
+                        &object,
+                        format!("the unnamed {} has no predictable name in {}; drop it by hand", key.kind(), self.config.name),
+                    );
+                    return;
+                }
+            },
+        };
+        let action = match (self.dialect, key) {
+            (SqlDialect::MySQL, Key::Foreign(_)) => "DROP FOREIGN KEY",
+            (SqlDialect::MySQL, Key::Unique(_)) => "DROP INDEX",
+            (SqlDialect::MySQL, Key::Check(_)) => "DROP CHECK",
+            _ => "DROP CONSTRAINT",
+        };
+        plan.push_with(format!("ALTER TABLE {} {} {};", table_name, action, self.quote(&name)), warnings);
+    }
+
+    /// The name the dialect gives a constraint declared without one, where it is predictable.
+    fn default_key_name(&self, table: &Table, key: &Key) -> Option<String> {
+        let unnamed_position = |position: Option<usize>| position.map(|position| position + 1);
+        match self.dialect {
+            SqlDialect::PostgreSQL => {
+                postgres_default_names(table).into_iter().find(|(other, _)| other.is(key)).map(|(_, name)| name)
+            }
+            // MySQL numbers unnamed foreign keys and checks in the order they were declared
+            SqlDialect::MySQL => match key {
+                Key::Unique(unique) => unique.columns.first().cloned(),
+                Key::Foreign(fk) => unnamed_position(
+                    table
+                        .foreign_keys
+                        .iter()
+                        .filter(|other| Key::Foreign(other).name().is_none())
+                        .position(|other| std::ptr::eq(other, *fk)),
+                )
+                .map(|number| format!("{}_ibfk_{}", table.name, number)),
+                Key::Check(check) => unnamed_position(
+                    table
+                        .check_constraints
+                        .iter()
+                        .filter(|other| Key::Check(other).name().is_none())
+                        .position(|other| std::ptr::eq(other, *check)),
+                )
+                .map(|number| format!("{}_chk_{}", table.name, number)),
+                Key::Primary(_) | Key::Exclusion(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn rename(&self, plan: &mut Plan) {
+        for table in &self.matches {
+            let (from, to) = (table.from, table.to);
+            if !same_name(&from.schema, &to.schema) {
+                plan.warn(&display(to), "moving a table to another schema is not migrated; write it by hand");
+            }
+            if !from.name.eq_ignore_ascii_case(&to.name) {
+                plan.push(match self.dialect {
+                    SqlDialect::MSSQL => {
+                        format!("EXEC sp_rename {}, {};", quote_literal(&display(from)), quote_literal(&to.name))
+                    }
+                    _ => format!("ALTER TABLE {} RENAME TO {};", self.table_name(from), self.quote(&to.name)),
+                });
+            }
+        }
+        for table in &self.matches {
+            for (from, to) in &table.columns {
+                let Some(from) = from.filter(|from| !from.name.eq_ignore_ascii_case(&to.name)) else {
+                    continue;
+                };
+                plan.push(match self.dialect {
+                    SqlDialect::MSSQL => format!(
+                        "EXEC sp_rename {}, {}, 'COLUMN';",
+                        quote_literal(&format!("{}.{}", display(table.to), from.name)),
+                        quote_literal(&to.name)
+                    ),
+                    _ => format!(
+                        "ALTER TABLE {} RENAME COLUMN {} TO {};",
+                        self.table_name(table.to),
+                        self.quote(&from.name),
+                        self.quote(&to.name)
+                    ),
+                });
+            }
+        }
+    }
+
+    /// Creates the new tables, each after the tables it references. A foreign key to a table
+    /// that is yet to be created, on a reference cycle, is returned to be added afterwards.
+    fn create_tables(&self, plan: &mut Plan) -> Vec<(&'d Table, &'d ForeignKey)> {
+        let mut deferred = Vec::new();
+        let pending: HashSet<String> = self.added.iter().map(|table| table.name.to_lowercase()).collect();
+        let mut created: HashSet<String> = HashSet::new();
+
+        for table in &self.added {
+            let rowid_alias = self.rowid_alias(table);
+            let mut lines: Vec<String> = table
+                .columns
+                .iter()
+                .map(|column| self.column_definition(column, rowid_alias == Some(column.name.as_str())))
+                .collect();
+            for key in Key::of(table) {
+                match key {
+                    Key::Primary(_) if rowid_alias.is_some() => continue,
+                    // SQLite resolves references when rows are written, not when tables are created
+                    Key::Foreign(fk)
+                        if *self.dialect != SqlDialect::SQLite
+                            && !fk.referenced_table.eq_ignore_ascii_case(&table.name)
+                            && pending.contains(&fk.referenced_table.to_lowercase())
+                            && !created.contains(&fk.referenced_table.to_lowercase()) =>
+                    {
+                        deferred.push((*table, fk));
+                        continue;
+                    }
+                    _ => lines.push(self.key_clause(table, &key, Names::AsIs)),
+                }
+            }
+
+            let mut sql = format!("CREATE TABLE {} (\n    {}\n)", self.table_name(table), lines.join(",\n    "));
+            match self.dialect {
+                SqlDialect::MySQL => {
+                    if let Some(engine) = &table.engine {
+                        sql.push_str(&format!(" ENGINE={}", engine));
+                    }
+                    if let Some(charset) = &table.charset {
+                        sql.push_str(&format!(" DEFAULT CHARSET={}", charset));
+                    }
+                    if let Some(collation) = &table.collation {
+                        sql.push_str(&format!(" COLLATE={}", collation));
+                    }
+                }
+                SqlDialect::SQLite => {
+                    let options: Vec<&str> = [(table.strict, "STRICT"), (table.without_rowid, "WITHOUT ROWID")]
+                        .into_iter()
+                        .filter_map(|(set, option)| set.then_some(option))
+                        .collect();
+                    if !options.is_empty() {
+                        sql.push_str(&format!(" {}", options.join(", ")));
+                    }
+                }
+                _ => {}
+            }
+            sql.push(';');
+            plan.push(sql);
+            for index in &table.indexes {
+                plan.push(self.create_index(table, index, Names::AsIs));
+            }
+            created.insert(table.name.to_lowercase());
+        }
+        deferred
+    }
+
+    fn alter_tables(&self, plan: &mut Plan) {
+        for table in &self.matches {
+            let object = display(table.to);
+            for (from, column) in &table.columns {
+                match from {
+                    Some(from) => self.alter_column(table.to, from, column, plan),
+                    None => {
+                        let mut warnings = Vec::new();
+                        if !column.nullable
+                            && column.default_value.is_none()
+                            && column.generated.is_none()
+                            && !column.auto_increment
+                        {
+                            warnings.push(format!(
+                                "adds {}.{} as NOT NULL without a default, which fails if {} has rows",
+                                object, column.name, object
+                            ));
+                        }
+                        let definition = self.column_definition(column, false);
+                        let sql = match self.dialect {
+                            SqlDialect::MSSQL => format!("ALTER TABLE {} ADD {};", self.table_name(table.to), definition),
+                            SqlDialect::Oracle => format!("ALTER TABLE {} ADD ({});", self.table_name(table.to), definition),
+                            _ => format!("ALTER TABLE {} ADD COLUMN {};", self.table_name(table.to), definition),
+                        };
+                        plan.push_with(sql, warnings);
+                    }
+                }
+            }
+            for column in &table.dropped {
+                plan.push_with(
+                    format!("ALTER TABLE {} DROP COLUMN {};", self.table_name(table.to), self.quote(&column.name)),
+                    vec![format!(
+                        "drops column {}.{} with all of its values; adding it back does not restore them",
+                        object, column.name
+                    )],
+                );
+            }
+        }
+    }
+
+    fn column_changed(&self, from: &Column, to: &Column) -> bool {
+        self.column_type(from, Names::Renamed(&self.renames)) != self.column_type(to, Names::AsIs)
+            || from.collation != to.collation
+            || from.charset != to.charset
+            || from.nullable != to.nullable
+            || from.default_value.as_deref().map(normalize) != to.default_value.as_deref().map(normalize)
+            || generation_changed(from, to)
+    }
+
+    fn alter_column(&self, table: &Table, from: &Column, to: &Column, plan: &mut Plan) {
+        if !self.column_changed(from, to) {
+            return;
+        }
+        let object = format!("{}.{}", display(table), to.name);
+        let (old_type, new_type) = (self.column_type(from, Names::Renamed(&self.renames)), self.column_type(to, Names::AsIs));
+        let type_changed = old_type != new_type || from.collation != to.collation || from.charset != to.charset;
+        let null_changed = from.nullable != to.nullable;
+        let default_changed = from.default_value.as_deref().map(normalize) != to.default_value.as_deref().map(normalize);
+
+        let mut type_warnings = Vec::new();
+        if old_type != new_type && !widens(&from.data_type, &to.data_type) {
+            type_warnings.push(format!(
+                "changes {} from {} to {}; values that don't fit are rejected or cut short, and changing it back does \
+                 not restore them",
+                object, old_type, new_type
+            ));
+        }
+        let mut null_warnings = Vec::new();
+        if from.nullable && !to.nullable {
+            null_warnings.push(format!("fails while {} holds NULL values", object));
+        }
+        let table_name = self.table_name(table);
+        let column = self.quote(&to.name);
+
+        match self.dialect {
+            SqlDialect::SQLite => {
+                plan.warn(&object, "SQLite cannot alter a column; rebuild the table by hand");
+                return;
+            }
+            SqlDialect::MySQL => {
+                type_warnings.extend(null_warnings);
+                plan.push_with(
+                    format!("ALTER TABLE {} MODIFY COLUMN {};", table_name, self.column_definition(to, false)),
+                    type_warnings,
+                );
+                return;
+            }
+            _ => {}
+        }
+
+        if generation_changed(from, to) {
+            plan.warn(&object, "changing auto-increment, identity or generation is not migrated; write it by hand");
+        }
+        match self.dialect {
+            SqlDialect::MSSQL => {
+                if type_changed || null_changed {
+                    type_warnings.extend(null_warnings);
+                    let null = if to.nullable { "NULL" } else { "NOT NULL" };
+                    plan.push_with(
+                        format!("ALTER TABLE {} ALTER COLUMN {} {} {};", table_name, column, self.typed(to, &new_type), null),
+                        type_warnings,
+                    );
+                }
+                if default_changed {
+                    plan.warn(&object, "SQL Server keeps defaults as named constraints; change the default by hand");
+                }
+            }
+            SqlDialect::Oracle => {
+                if type_changed {
+                    plan.push_with(
+                        format!("ALTER TABLE {} MODIFY ({} {});", table_name, column, self.typed(to, &new_type)),
+                        type_warnings,
+                    );
+                }
+                if null_changed {
+                    let null = if to.nullable { "NULL" } else { "NOT NULL" };
+                    plan.push_with(format!("ALTER TABLE {} MODIFY ({} {});", table_name, column, null), null_warnings);
+                }
+                if default_changed {
+                    let default = to.default_value.as_deref().unwrap_or("NULL");
+                    plan.push(format!("ALTER TABLE {} MODIFY ({} DEFAULT {});", table_name, column, default));
+                }
+            }
+            _ => {
+                if type_changed {
+                    let mut sql = format!(
+                        "ALTER TABLE {} ALTER COLUMN {} {} {}",
+                        table_name,
+                        column,
+                        if *self.dialect == SqlDialect::PostgreSQL { "TYPE" } else { "SET DATA TYPE" },
+                        self.typed(to, &new_type)
+                    );
+                    // PostgreSQL only converts between related types by itself
+                    if *self.dialect == SqlDialect::PostgreSQL && !same_family(&from.data_type, &to.data_type) {
+                        sql.push_str(&format!(" USING {}::{}", column, new_type));
+                    }
+                    sql.push(';');
+                    plan.push_with(sql, type_warnings);
+                }
+                if null_changed {
+                    let action = if to.nullable { "DROP NOT NULL" } else { "SET NOT NULL" };
+                    plan.push_with(format!("ALTER TABLE {} ALTER COLUMN {} {};", table_name, column, action), null_warnings);
+                }
+                if default_changed {
+                    plan.push(match &to.default_value {
+                        Some(default) => format!("ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};", table_name, column, default),
+                        None => format!("ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;", table_name, column),
+                    });
+                }
+            }
+        }
+    }
+
+    fn drop_tables(&self, plan: &mut Plan) {
+        for table in &self.removed {
+            plan.push_with(
+                format!("DROP TABLE {};", self.table_name(table)),
+                vec![format!(
+                    "drops table {} with all of its rows; creating it again does not restore them",
+                    display(table)
+                )],
+            );
+        }
+    }
+
+    /// Named types are PostgreSQL's alone; elsewhere they were inlined into the columns.
+    fn create_types(&self, plan: &mut Plan) {
+        if *self.dialect != SqlDialect::PostgreSQL {
+            return;
+        }
+        for change in self.diff.changes.iter().filter(|change| change.object == DiffObject::Type) {
+            match change.action {
+                ChangeAction::Added => {
+                    if let Some(user_type) = find_type(self.to, &change.name) {
+                        plan.push(self.create_type(user_type));
+                    }
+                }
+                ChangeAction::Renamed => {
+                    if let (Some(old), Some(user_type)) = (&change.old, find_type(self.to, &change.name)) {
+                        let kind = if matches!(user_type.kind, UserDefinedTypeKind::Domain { .. }) { "DOMAIN" } else { "TYPE" };
+                        plan.push(format!("ALTER {} {} RENAME TO {};", kind, self.quote_path(old), self.quote(&user_type.name)));
+                    }
+                }
+                ChangeAction::Modified => {
+                    let (Some(old), Some(new)) = (find_type(self.from, &change.name), find_type(self.to, &change.name)) else {
+                        continue;
+                    };
+                    match (&old.kind, &new.kind) {
+                        (UserDefinedTypeKind::Enum { values: before }, UserDefinedTypeKind::Enum { values: after }) => {
+                            if before.iter().any(|value| !after.contains(value)) {
+                                plan.warn(
+                                    &change.name,
+                                    "PostgreSQL cannot remove values from an enum; recreate the type by hand",
+                                );
+                            }
+                            for (position, value) in after.iter().enumerate().filter(|(_, value)| !before.contains(value)) {
+                                let placement = match position.checked_sub(1).map(|previous| &after[previous]) {
+                                    Some(previous) => format!(" AFTER {}", quote_literal(previous)),
+                                    None => after.get(1).map(|next| format!(" BEFORE {}", quote_literal(next))).unwrap_or_default(),
+                                };
+                                plan.push(format!(
+                                    "ALTER TYPE {} ADD VALUE {}{};",
+                                    self.quote_path(&change.name),
+                                    quote_literal(value),
+                                    placement
+                                ));
+                            }
+                        }
+                        _ => plan.warn(&change.name, "changing a domain or composite type is not migrated; write it by hand"),
+                    }
+                }
+                ChangeAction::Removed => {}
+            }
+        }
+    }
+
+    fn drop_types(&self, plan: &mut Plan) {
+        if *self.dialect != SqlDialect::PostgreSQL {
+            return;
+        }
+        let removed = self.diff.changes.iter().filter(|change| change.action == ChangeAction::Removed);
+        for change in removed.filter(|change| change.object == DiffObject::Type) {
+            if let Some(user_type) = find_type(self.from, &change.name) {
+                let kind = if matches!(user_type.kind, UserDefinedTypeKind::Domain { .. }) { "DOMAIN" } else { "TYPE" };
+                plan.push(format!("DROP {} {};", kind, self.quote_path(&change.name)));
+            }
+        }
+    }
+
+    fn create_type(&self, user_type: &UserDefinedType) -> String {
+        let name = self.quote_path(&qualified(&user_type.schema, &user_type.name));
+        match &user_type.kind {
+            UserDefinedTypeKind::Enum { values } => {
+                let values: Vec<String> = values.iter().map(|value| quote_literal(value)).collect();
+                format!("CREATE TYPE {} AS ENUM ({});", name, values.join(", "))
+            }
+            UserDefinedTypeKind::Domain { base_type, nullable, default_value, checks } => {
+                let mut sql = format!("CREATE DOMAIN {} AS {}", name, self.adapt(base_type));
+                if !nullable {
+                    sql.push_str(" NOT NULL");
+                }
+                if let Some(default) = default_value {
+                    sql.push_str(&format!(" DEFAULT {}", default));
+                }
+                for check in checks {
+                    sql.push_str(&format!(" CHECK ({})", check.trim()));
+                }
+                sql.push(';');
+                sql
+            }
+            UserDefinedTypeKind::Composite { attributes } => {
+                let attributes: Vec<String> = attributes
+                    .iter()
+                    .map(|attribute| format!("{} {}", self.quote(&attribute.name), self.column_type(attribute, Names::AsIs)))
+                    .collect();
+                format!("CREATE TYPE {} AS ({});", name, attributes.join(", "))
+            }
+        }
+    }
+
+    /// The column a SQLite table's rowid goes by, declared inline as `INTEGER PRIMARY KEY`.
+    fn rowid_alias<'t>(&self, table: &'t Table) -> Option<&'t str> {
+        if *self.dialect != SqlDialect::SQLite {
+            return None;
+        }
+        table.columns.iter().find(|column| column.rowid_alias).map(|column| column.name.as_str())
+    }
+
+    fn column_definition(&self, column: &Column, primary_key: bool) -> String {
+        let mut data_type = self.column_type(column, Names::AsIs);
+        if *self.dialect == SqlDialect::PostgreSQL && column.auto_increment && column.identity.is_none() {
+            data_type = match column.data_type {
+                DataType::SmallInt { .. } => "SMALLSERIAL".to_string(),
+                DataType::BigInt { .. } => "BIGSERIAL".to_string(),
+                _ => "SERIAL".to_string(),
+            };
+        }
+        let mut sql = format!("{} {}", self.quote(&column.name), self.typed(column, &data_type));
+
+        if let Some(generated) = &column.generated {
+            sql.push_str(&match (self.dialect, generated.stored) {
+                (SqlDialect::MSSQL, stored) => {
+                    format!(" AS ({}){}", generated.expression, if stored { " PERSISTED" } else { "" })
+                }
+                (SqlDialect::PostgreSQL, _) | (_, true) => format!(" GENERATED ALWAYS AS ({}) STORED", generated.expression),
+                (_, false) => format!(" GENERATED ALWAYS AS ({}) VIRTUAL", generated.expression),
+            });
+        }
+        if column.auto_increment {
+            match (self.dialect, &column.identity) {
+                (SqlDialect::MSSQL, identity) => {
+                    let (seed, increment) = identity.as_ref().map_or((1, 1), |identity| (identity.seed, identity.increment));
+                    sql.push_str(&format!(" IDENTITY({},{})", seed, increment));
+                }
+                (SqlDialect::PostgreSQL | SqlDialect::Oracle | SqlDialect::Standard, Some(identity)) => {
+                    sql.push_str(&format!(" GENERATED {} AS IDENTITY", if identity.always { "ALWAYS" } else { "BY DEFAULT" }));
+                    let mut options = Vec::new();
+                    if identity.seed != 1 {
+                        options.push(format!("START WITH {}", identity.seed));
+                    }
+                    if identity.increment != 1 {
+                        options.push(format!("INCREMENT BY {}", identity.increment));
+                    }
+                    if !options.is_empty() {
+                        sql.push_str(&format!(" ({})", options.join(" ")));
+                    }
+                }
+                (SqlDialect::Oracle | SqlDialect::Standard, None) => sql.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+                _ => {}
+            }
+        }
+        let serial = *self.dialect == SqlDialect::PostgreSQL && column.auto_increment && column.identity.is_none();
+        if !column.nullable && !serial {
+            sql.push_str(" NOT NULL");
+        }
+        if let Some(default) = column.default_value.as_ref().filter(|_| column.generated.is_none()) {
+            sql.push_str(&format!(" DEFAULT {}", default));
+        }
+        if let Some(expression) = column.on_update.as_ref().filter(|_| *self.dialect == SqlDialect::MySQL) {
+            sql.push_str(&format!(" ON UPDATE {}", expression));
+        }
+        if column.auto_increment && *self.dialect == SqlDialect::MySQL {
+            sql.push_str(" AUTO_INCREMENT");
+        }
+        if primary_key {
+            sql.push_str(" PRIMARY KEY");
+            if column.auto_increment {
+                sql.push_str(" AUTOINCREMENT");
+            }
+        }
+        sql
+    }
+
+    /// A column type with the character set and collation that go with it.
+    fn typed(&self, column: &Column, data_type: &str) -> String {
+        let mut sql = data_type.to_string();
+        if let Some(charset) = column.charset.as_ref().filter(|_| *self.dialect == SqlDialect::MySQL) {
+            sql.push_str(&format!(" CHARACTER SET {}", charset));
+        }
+        if let Some(collation) = &column.collation {
+            let collation = match self.dialect {
+                SqlDialect::PostgreSQL => self.quote(collation),
+                _ => collation.clone(),
+            };
+            sql.push_str(&format!(" COLLATE {}", collation));
+        }
+        sql
+    }
+
+    fn column_type(&self, column: &Column, names: Names) -> String {
+        if *self.dialect == SqlDialect::PostgreSQL
+            && let Some(user_type) = &column.user_type
+        {
+            return self.quote_path(&names.user_type(user_type));
+        }
+        self.adapt(&column.data_type)
+    }
+
+    /// The type as the dialect spells it; the model came from the same dialect, so a type the
+    /// dialect has no name for is kept as it was written. SQLite takes any name, so its types
+    /// are kept as declared rather than reduced to their affinity.
+    fn adapt(&self, data_type: &DataType) -> String {
+        if *self.dialect == SqlDialect::SQLite {
+            return data_type.to_sql();
+        }
+        FieldService::adapt_data_type(data_type, self.dialect).unwrap_or_else(|| data_type.clone()).to_sql()
+    }
+
+    /// A key as declared in `CREATE TABLE` or added by `ALTER TABLE ... ADD`.
+    fn key_clause(&self, table: &Table, key: &Key, names: Names) -> String {
+        let table_name = display(table);
+        let columns = |columns: &[String]| {
+            columns.iter().map(|column| self.quote(&names.column(&table_name, column))).collect::<Vec<_>>().join(", ")
+        };
+        let prefix = key.name().map(|name| format!("CONSTRAINT {} ", self.quote(name))).unwrap_or_default();
+        match key {
+            Key::Primary(primary_key) => format!("{}PRIMARY KEY ({})", prefix, columns(&primary_key.columns)),
+            Key::Unique(unique) => format!("{}UNIQUE ({})", prefix, columns(&unique.columns)),
+            Key::Check(check) => format!("{}CHECK ({})", prefix, normalize(&check.expression)),
+            Key::Exclusion(exclusion) => {
+                let elements: Vec<String> = exclusion
+                    .elements
+                    .iter()
+                    .map(|element| format!("{} WITH {}", normalize(&element.expression), element.operator))
+                    .collect();
+                let mut sql =
+                    format!("{}EXCLUDE USING {} ({})", prefix, index_method(&exclusion.index_type), elements.join(", "));
+                if let Some(predicate) = &exclusion.where_clause {
+                    sql.push_str(&format!(" WHERE ({})", normalize(predicate)));
+                }
+                sql
+            }
+            Key::Foreign(fk) => {
+                let target = qualified(&fk.referenced_schema, &fk.referenced_table);
+                let mut sql = format!(
+                    "{}FOREIGN KEY ({}) REFERENCES {}",
+                    prefix,
+                    columns(&fk.columns),
+                    self.quote_path(&names.table(&target))
+                );
+                if !fk.referenced_columns.is_empty() {
+                    let referenced: Vec<String> =
+                        fk.referenced_columns.iter().map(|column| self.quote(&names.column(&target, column))).collect();
+                    sql.push_str(&format!(" ({})", referenced.join(", ")));
+                }
+                if fk.on_delete != ReferentialAction::NoAction {
+                    sql.push_str(&format!(" ON DELETE {}", action_sql(&fk.on_delete)));
+                }
+                if fk.on_update != ReferentialAction::NoAction && *self.dialect != SqlDialect::Oracle {
+                    sql.push_str(&format!(" ON UPDATE {}", action_sql(&fk.on_update)));
+                }
+                sql
+            }
+        }
+    }
+
+    fn create_index(&self, table: &Table, index: &Index, names: Names) -> String {
+        let table_name = display(table);
+        let columns: Vec<String> = index
+            .columns
+            .iter()
+            .map(|column| {
+                let name = names.column(&table_name, &column.name);
+                // An expression is kept as written
+                let mut sql = if name.contains(['(', ' ']) { name } else { self.quote(&name) };
+                if column.sort_order == SortOrder::Descending {
+                    sql.push_str(" DESC");
+                }
+                if matches!(self.dialect, SqlDialect::PostgreSQL | SqlDialect::Oracle) {
+                    match column.nulls_position {
+                        NullsPosition::First => sql.push_str(" NULLS FIRST"),
+                        NullsPosition::Last => sql.push_str(" NULLS LAST"),
+                        NullsPosition::Default => {}
+                    }
+                }
+                sql
+            })
+            .collect();
+
+        let mut sql = String::from("CREATE ");
+        match (&index.index_type, self.dialect) {
+            (IndexType::FullText, SqlDialect::MySQL) => sql.push_str("FULLTEXT "),
+            (IndexType::Spatial, SqlDialect::MySQL) => sql.push_str("SPATIAL "),
+            _ => {}
+        }
+        if index.unique {
+            sql.push_str("UNIQUE ");
+        }
+        sql.push_str(&format!("INDEX {} ON {}", self.quote(&index.name), self.quote_path(&names.table(&table_name))));
+        if *self.dialect == SqlDialect::PostgreSQL && index.index_type != IndexType::BTree {
+            sql.push_str(&format!(" USING {}", index_method(&index.index_type)));
+        }
+        sql.push_str(&format!(" ({})", columns.join(", ")));
+        if *self.dialect == SqlDialect::MySQL && index.index_type == IndexType::Hash {
+            sql.push_str(" USING HASH");
+        }
+        if let Some(predicate) = index.where_clause.as_ref().filter(|_| *self.dialect != SqlDialect::MySQL) {
+            sql.push_str(&format!(" WHERE {}", normalize(predicate)));
+        }
+        sql.push(';');
+        sql
+    }
+
+    fn drop_index(&self, table: &Table, index: &Index) -> String {
+        match self.dialect {
+            SqlDialect::MySQL | SqlDialect::MSSQL => {
+                format!("DROP INDEX {} ON {};", self.quote(&index.name), self.table_name(table))
+            }
+            // Indexes live in their table's schema
+            _ => format!("DROP INDEX {};", self.quote_path(&qualified(&table.schema, &index.name))),
+        }
+    }
+
+    fn table_name(&self, table: &Table) -> String {
+        self.quote_path(&display(table))
+    }
+
+    fn quote(&self, name: &str) -> String {
+        quote_identifier(name, self.dialect, &self.config)
+    }
+
+    fn quote_path(&self, name: &str) -> String {
+        name.split('.').map(|part| self.quote(part)).collect::<Vec<_>>().join(".")
+    }
+}
+
+/// The names PostgreSQL gives a table's unnamed constraints, in the order it creates them:
+/// checks with the table, then the keys built on an index, then foreign keys. A name already
+/// taken gets a number after its suffix, as in `orders_amount_check1`.
+fn postgres_default_names<'d>(table: &'d Table) -> Vec<(Key<'d>, String)> {
+    let keys = Key::of(table);
+    let mut taken: HashSet<String> = keys
+        .iter()
+        .filter_map(Key::name)
+        .map(str::to_string)
+        .chain(table.indexes.iter().map(|index| index.name.clone()))
+        .collect();
+    let mut unnamed: Vec<Key> = keys.into_iter().filter(|key| key.name().is_none()).collect();
+    unnamed.sort_by_key(|key| match key {
+        Key::Check(_) => 0,
+        Key::Primary(_) | Key::Unique(_) | Key::Exclusion(_) => 1,
+        Key::Foreign(_) => 2,
+    });
+
+    let mut names = Vec::new();
+    for key in unnamed {
+        let (columns, label) = match key {
+            Key::Primary(_) => (None, "pkey"),
+            Key::Unique(unique) => (Some(unique.columns.join("_")), "key"),
+            // Only a check on a single column is named after it
+            Key::Check(check) => (check_column(table, check), "check"),
+            Key::Exclusion(exclusion) => {
+                let columns = exclusion.elements.iter().map(|element| {
+                    let plain = element.expression.chars().all(|ch| ch.is_alphanumeric() || ch == '_');
+                    if plain { element.expression.as_str() } else { "expr" }
+                });
+                (Some(columns.collect::<Vec<_>>().join("_")), "excl")
+            }
+            Key::Foreign(fk) => (Some(fk.columns.join("_")), "fkey"),
+        };
+        let mut name = postgres_object_name(&table.name, columns.as_deref(), label);
+        let mut pass = 0;
+        while taken.contains(&name) {
+            pass += 1;
+            name = postgres_object_name(&table.name, columns.as_deref(), &format!("{}{}", label, pass));
+        }
+        taken.insert(name.clone());
+        names.push((key, name));
+    }
+    names
+}
+
+/// The one column of the table a check reads, if it reads exactly one.
+fn check_column(table: &Table, check: &CheckConstraint) -> Option<String> {
+    let tokens = Lexer::for_dialect(&check.expression, &SqlDialect::PostgreSQL).tokenize().ok()?;
+    let mut columns: Vec<&str> = tokens
+        .iter()
+        .filter_map(|token| match &token.token_type {
+            TokenType::Identifier(name) | TokenType::QuotedIdentifier(name) => table
+                .columns
+                .iter()
+                .find(|column| column.name.eq_ignore_ascii_case(name))
+                .map(|column| column.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    columns.sort_unstable();
+    columns.dedup();
+    match columns[..] {
+        [column] => Some(column.to_string()),
+        _ => None,
+    }
+}
+
+/// PostgreSQL's `name1_name2_label`, with the longer of the two names cut back a byte at a
+/// time until the whole fits in its 63-byte identifiers.
+fn postgres_object_name(name1: &str, name2: Option<&str>, label: &str) -> String {
+    const MAX_IDENTIFIER_BYTES: usize = 63;
+    let overhead = label.len() + 1 + usize::from(name2.is_some());
+    let available = MAX_IDENTIFIER_BYTES.saturating_sub(overhead);
+    let (mut length1, mut length2) = (name1.len(), name2.map_or(0, str::len));
+    while length1 + length2 > available {
+        if length1 > length2 {
+            length1 -= 1;
+        } else {
+            length2 -= 1;
+        }
+    }
+    let clip = |name: &str, mut length: usize| {
+        while !name.is_char_boundary(length) {
+            length -= 1;
+        }
+        name[..length].to_string()
+    };
+    match name2 {
+        Some(name2) => format!("{}_{}_{}", clip(name1, length1), clip(name2, length2), label),
+        None => format!("{}_{}", clip(name1, length1), label),
+    }
+}
+
+/// Whether every value of `from` is also a value of `to`, so converting loses nothing.
+fn widens(from: &DataType, to: &DataType) -> bool {
+    let integer = |data_type: &DataType| match data_type {
+        DataType::TinyInt { unsigned } => Some((1, *unsigned)),
+        DataType::SmallInt { unsigned } => Some((2, *unsigned)),
+        DataType::Integer { unsigned } => Some((3, *unsigned)),
+        DataType::BigInt { unsigned } => Some((4, *unsigned)),
+        _ => None,
+    };
+    let length = |data_type: &DataType| match data_type {
+        DataType::Char { length }
+        | DataType::VarChar { length }
+        | DataType::NChar { length }
+        | DataType::NVarChar { length }
+        | DataType::Binary { length }
+        | DataType::VarBinary { length } => Some(*length),
+        _ => None,
+    };
+    let fits = |limit: &Option<u32>| match (length(from).flatten(), limit) {
+        (_, None) => true,
+        (Some(before), Some(after)) => *after >= before,
+        (None, Some(_)) => false,
+    };
+
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (_, DataType::Text | DataType::NText) => from.is_text(),
+        (DataType::Char { .. } | DataType::VarChar { .. }, DataType::VarChar { length: limit })
+        | (
+            DataType::Char { .. } | DataType::VarChar { .. } | DataType::NChar { .. } | DataType::NVarChar { .. },
+            DataType::NVarChar { length: limit },
+        )
+        | (DataType::Binary { .. } | DataType::VarBinary { .. }, DataType::VarBinary { length: limit }) => fits(limit),
+        (
+            DataType::Decimal { precision: Some(before), scale },
+            DataType::Decimal { precision: after, scale: new_scale },
+        ) => match after {
+            None => true,
+            Some(after) => {
+                let (scale, new_scale) = (scale.unwrap_or(0), new_scale.unwrap_or(0));
+                new_scale >= scale && after.saturating_sub(new_scale) >= before.saturating_sub(scale)
+            }
+        },
+        (DataType::Real | DataType::Float { .. }, DataType::Double) => true,
+        (DataType::Date, DataType::Timestamp | DataType::DateTime | DataType::DateTime2) => true,
+        (DataType::Enum { values: before }, DataType::Enum { values: after })
+        | (DataType::Set { values: before }, DataType::Set { values: after }) => {
+            before.iter().all(|value| after.contains(value))
+        }
+        _ => match (integer(from), integer(to)) {
+            (Some((before, unsigned)), Some((after, new_unsigned))) => {
+                (unsigned == new_unsigned && after >= before) || (!new_unsigned && after > before)
+            }
+            _ => false,
+        },
+    }
+}
+
+fn same_family(from: &DataType, to: &DataType) -> bool {
+    (from.is_numeric() && to.is_numeric()) || (from.is_text() && to.is_text()) || (from.is_temporal() && to.is_temporal())
+}
+
+fn generation_changed(from: &Column, to: &Column) -> bool {
+    let generation = |column: &Column| {
+        column.generated.as_ref().map(|generated| (normalize(&generated.expression), generated.stored))
+    };
+    from.auto_increment != to.auto_increment || from.identity != to.identity || generation(from) != generation(to)
+}
+
+fn view_kind(view: &View) -> &'static str {
+    match view.view_type {
+        ViewType::Regular => "VIEW",
+        ViewType::Materialized => "MATERIALIZED VIEW",
+    }
+}
+
+fn action_sql(action: &ReferentialAction) -> &'static str {
+    match action {
+        ReferentialAction::NoAction => "NO ACTION",
+        ReferentialAction::Restrict => "RESTRICT",
+        ReferentialAction::Cascade => "CASCADE",
+        ReferentialAction::SetNull => "SET NULL",
+        ReferentialAction::SetDefault => "SET DEFAULT",
+    }
+}
+
+fn index_method(index_type: &IndexType) -> String {
+    match index_type {
+        IndexType::BTree => "btree".to_string(),
+        IndexType::Hash => "hash".to_string(),
+        IndexType::Gist => "gist".to_string(),
+        IndexType::Gin => "gin".to_string(),
+        IndexType::SpGist => "spgist".to_string(),
+        IndexType::Brin => "brin".to_string(),
+        IndexType::FullText => "fulltext".to_string(),
+        IndexType::Spatial => "spatial".to_string(),
+        IndexType::Custom(name) => name.to_lowercase(),
+    }
+}
+
+/// Finds a table by its name as the diff gives it, qualified when it has a schema, falling
+/// back to the bare name.
+fn find_table<'d>(database: &'d Database, name: &str) -> Option<&'d Table> {
+    let tables = database.get_all_tables();
+    tables.iter().find(|table| display(table).eq_ignore_ascii_case(name)).copied().or_else(|| {
+        let bare = name.rsplit('.').next().unwrap_or(name);
+        tables.iter().find(|table| table.name.eq_ignore_ascii_case(bare)).copied()
+    })
+}
+
+fn find_column<'t>(table: &'t Table, name: &str) -> Option<&'t Column> {
+    table.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name))
+}
+
+fn find_type<'d>(database: &'d Database, name: &str) -> Option<&'d UserDefinedType> {
+    database
+        .get_all_types()
+        .into_iter()
+        .find(|user_type| qualified(&user_type.schema, &user_type.name).eq_ignore_ascii_case(name))
+}
+
+fn same_name(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (a, b) => a == b,
+    }
+}
+
+/// SQL text with its layout dropped, so re-indenting an expression doesn't count as changing it.
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ").trim_end_matches(';').trim_end().to_string()
+}
+
+fn qualified(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+fn display(table: &Table) -> String {
+    qualified(&table.schema, &table.name)
+}
+
+fn warn(warnings: &mut Vec<TranspileWarning>, object: &str, message: impl Into<String>) {
+    warnings.push(TranspileWarning { object: object.to_string(), message: message.into() });
+}
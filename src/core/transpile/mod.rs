@@ -0,0 +1,5 @@
+pub mod migration;
+pub mod transpiler;
+
+pub use migration::*;
+pub use transpiler::*;
@@ -0,0 +1,1530 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::ast::{
+    ColumnConstraint, ColumnDefinition, CreateDomainStatement, CreateIndexStatement,
+    CreateSchemaStatement, CreateSequenceStatement, CreateTableStatement, CreateTypeStatement, DataType, DdlStatement,
+    DropTableStatement, IdentityOptions, IndexKind, IndexType, KeyPart, ReferentialAction, SchemaReference, Statement,
+    TableConstraint, TypeDefinition,
+};
+use crate::core::domain::entity;
+use crate::core::domain::field::FieldService;
+use crate::core::parse::{
+    tokens_to_sql, DialectConfig, Lexer, LexerError, Parser, ParserError, SqlDialect, TokenType,
+};
+
+const SERIAL_TYPES: &[&str] = &["SERIAL", "BIGSERIAL", "SMALLSERIAL"];
+const TIMESTAMP_FUNCTIONS: &[&str] = &[
+    "CURRENT_TIMESTAMP", "NOW", "GETDATE", "SYSDATETIME", "SYSDATE", "SYSTIMESTAMP",
+    "LOCALTIMESTAMP", "DATETIME",
+];
+const DATE_FUNCTIONS: &[&str] = &["CURRENT_DATE", "CURDATE"];
+const UUID_FUNCTIONS: &[&str] = &["UUID", "GEN_RANDOM_UUID", "UUID_GENERATE_V4", "NEWID", "SYS_GUID"];
+
+#[derive(Debug, Error)]
+pub enum TranspileError {
+    #[error("failed to tokenize input: {0}")]
+    Lex(#[from] LexerError),
+    #[error("failed to parse input: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Parse(Vec<ParserError>),
+}
+
+/// A construct that could not be carried over to the target dialect exactly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranspileWarning {
+    pub object: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranspileOutput {
+    pub sql: String,
+    pub warnings: Vec<TranspileWarning>,
+}
+
+/// Rewrites DDL written for one dialect into the equivalent DDL for another.
+pub struct Transpiler {
+    source: SqlDialect,
+    target: SqlDialect,
+    target_config: DialectConfig,
+    field_service: FieldService,
+}
+
+struct ColumnType {
+    sql: String,
+    check: Option<String>,
+    auto_increment: bool,
+}
+
+/// Enum and domain types declared in the input, keyed by upper-cased name as column types are.
+/// Schema qualifiers are left out of the key; see [`user_type`].
+type UserTypes<'a> = HashMap<String, UserType<'a>>;
+
+enum UserType<'a> {
+    Enum { name: &'a SchemaReference, values: &'a [String] },
+    Domain(&'a CreateDomainStatement),
+    Composite { name: &'a SchemaReference },
+}
+
+impl Transpiler {
+    pub fn new(source: SqlDialect, target: SqlDialect) -> Self {
+        Self {
+            target_config: target.config(),
+            field_service: FieldService::new(source.clone()),
+            source,
+            target,
+        }
+    }
+
+    pub fn transpile(&self, sql: &str) -> Result<TranspileOutput, TranspileError> {
+        let tokens = Lexer::for_dialect(sql, &self.source).tokenize()?;
+        let statements = Parser::new(tokens).parse().map_err(TranspileError::Parse)?;
+
+        let mut warnings = Vec::new();
+        let mut rendered = Vec::new();
+        let types = collect_user_types(&statements);
+
+        for statement in &statements {
+            match statement {
+                Statement::Ddl(DdlStatement::CreateTable(table)) => {
+                    rendered.push(self.render_create_table(table, &types, &mut warnings));
+                }
+                Statement::Ddl(DdlStatement::CreateType(create_type)) => {
+                    if let Some(sql) = self.render_create_type(create_type, &types, &mut warnings) {
+                        rendered.push(sql);
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateDomain(domain)) => {
+                    if let Some(sql) = self.render_create_domain(domain, &types, &mut warnings) {
+                        rendered.push(sql);
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateIndex(index)) => {
+                    if let Some(sql) = self.render_create_index(index, &mut warnings) {
+                        rendered.push(sql);
+                    }
+                }
+                Statement::Ddl(DdlStatement::DropTable(drop)) => {
+                    rendered.push(self.render_drop_table(drop, &mut warnings));
+                }
+                Statement::Ddl(DdlStatement::CreateSequence(sequence)) => {
+                    if let Some(sql) = self.render_create_sequence(sequence, &mut warnings) {
+                        rendered.push(sql);
+                    }
+                }
+                Statement::Ddl(DdlStatement::CreateSchema(schema)) => {
+                    if let Some(sql) = self.render_create_schema(schema, &mut warnings) {
+                        rendered.push(sql);
+                    }
+                }
+                Statement::Ddl(_) => {
+                    warn(&mut warnings, "statement", "unsupported DDL statement skipped");
+                }
+                Statement::Dml(_) => {
+                    warn(&mut warnings, "statement", "data statement skipped; only DDL is transpiled");
+                }
+                // Dump boilerplate such as `SET NAMES` and `LOCK TABLES` only matters to the source server
+                Statement::Session(_) => {}
+            }
+        }
+
+        let mut sql = rendered.join("\n\n");
+        if !sql.is_empty() {
+            sql.push('\n');
+        }
+
+        Ok(TranspileOutput { sql, warnings })
+    }
+
+    /// PostgreSQL keeps named types; every other target gets them inlined into the columns.
+    fn render_create_type(
+        &self,
+        create_type: &CreateTypeStatement,
+        types: &UserTypes,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let object = &self.object_name(&create_type.type_name);
+        match (&create_type.definition, &self.target) {
+            (TypeDefinition::Enum { values }, SqlDialect::PostgreSQL) => {
+                let quoted: Vec<String> = values.iter().map(|value| quote_literal(value)).collect();
+                let name = self.quote_name(&create_type.type_name, warnings);
+                Some(format!("CREATE TYPE {} AS ENUM ({});", name, quoted.join(", ")))
+            }
+            (TypeDefinition::Composite { attributes }, SqlDialect::PostgreSQL) => {
+                let attributes: Vec<String> = attributes
+                    .iter()
+                    .map(|attribute| self.render_column(object, attribute, true, types, warnings))
+                    .collect();
+                let name = self.quote_name(&create_type.type_name, warnings);
+                Some(format!("CREATE TYPE {} AS (\n    {}\n);", name, attributes.join(",\n    ")))
+            }
+            (TypeDefinition::Enum { .. }, _) => {
+                warn(warnings, object, "enum type inlined into the columns that use it");
+                None
+            }
+            (TypeDefinition::Composite { .. }, _) => {
+                self.warn_unsupported(warnings, object, "composite types");
+                None
+            }
+        }
+    }
+
+    fn render_create_domain(
+        &self,
+        domain: &CreateDomainStatement,
+        types: &UserTypes,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let object = &self.object_name(&domain.domain_name);
+        if self.target != SqlDialect::PostgreSQL {
+            warn(warnings, object, "domain inlined into the columns that use it");
+            return None;
+        }
+
+        // Render the domain as if it were a column so its type and constraints are translated alike
+        let column = ColumnDefinition {
+            name: domain.domain_name.object.clone(),
+            data_type: domain.data_type.clone(),
+            type_name: None,
+            constraints: domain.constraints.clone(),
+        };
+        let rendered = self.render_column(object, &column, true, types, warnings);
+        let definition = rendered.strip_prefix(&self.quote(&column.name)).unwrap_or(&rendered);
+        Some(format!("CREATE DOMAIN {} AS{};", self.quote_name(&domain.domain_name, warnings), definition))
+    }
+
+    fn render_create_table(
+        &self,
+        table: &CreateTableStatement,
+        types: &UserTypes,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> String {
+        let object = &self.object_name(&table.table_name);
+        let mut lines = Vec::new();
+        let mut trailing = Vec::new();
+
+        // Elsewhere a SQLite rowid alias needs to say that it is generated
+        let rowid_alias = if self.source == SqlDialect::SQLite && self.target != SqlDialect::SQLite {
+            table.rowid_alias().map(|column| column.name.as_str())
+        } else {
+            None
+        };
+        let auto_increment_key = match self.target {
+            SqlDialect::MySQL => mysql_auto_increment_key(table, rowid_alias),
+            _ => None,
+        };
+        for column in &table.columns {
+            let object = format!("{}.{}", self.object_name(&table.table_name), column.name);
+            let mut column = self.resolve_user_type(column, types, &object, warnings);
+            if rowid_alias == Some(column.name.as_str())
+                && !column.constraints.contains(&ColumnConstraint::AutoIncrement)
+            {
+                column.to_mut().constraints.push(ColumnConstraint::AutoIncrement);
+            }
+            let allow_auto_increment = self.target != SqlDialect::MySQL || auto_increment_key == Some(column.name.as_str());
+            let table_name = self.object_name(&table.table_name);
+            lines.push(self.render_column(&table_name, &column, allow_auto_increment, types, warnings));
+        }
+        for constraint in &table.constraints {
+            match constraint {
+                TableConstraint::Index { name, kind, columns, index_type } if self.target != SqlDialect::MySQL => {
+                    // Only MySQL declares plain indexes inside the table body
+                    let index = CreateIndexStatement {
+                        index_name: name.clone().unwrap_or_else(|| {
+                            let names: Vec<&str> = columns.iter().map(|part| part.column.as_str()).collect();
+                            format!("idx_{}_{}", table.table_name.object, names.join("_"))
+                        }),
+                        table_name: table.table_name.clone(),
+                        if_not_exists: false,
+                        unique: false,
+                        concurrently: false,
+                        kind: kind.clone(),
+                        index_type: index_type.clone(),
+                        columns: columns.clone(),
+                        include: Vec::new(),
+                        where_clause: None,
+                    };
+                    if let Some(sql) = self.render_create_index(&index, warnings) {
+                        trailing.push(sql);
+                    }
+                }
+                _ => {
+                    if let Some(sql) = self.render_table_constraint(object, constraint, warnings) {
+                        lines.push(sql);
+                    }
+                }
+            }
+        }
+        trailing.extend(self.render_comments(table, warnings));
+
+        let if_not_exists = if table.if_not_exists && self.supports_if_not_exists_table() {
+            "IF NOT EXISTS "
+        } else {
+            if table.if_not_exists {
+                self.warn_unsupported(warnings, object, "IF NOT EXISTS on CREATE TABLE");
+            }
+            ""
+        };
+
+        let mut sql = format!(
+            "CREATE TABLE {}{} (\n    {}\n)",
+            if_not_exists,
+            self.quote_name(&table.table_name, warnings),
+            lines.join(",\n    ")
+        );
+
+        if !table.options.is_empty() {
+            let options: BTreeMap<&String, &String> = table.options.iter().collect();
+            if self.target == SqlDialect::MySQL {
+                for (key, value) in options {
+                    sql.push_str(&format!(" {}={}", key, render_option_value(key, value)));
+                }
+            } else {
+                // A table COMMENT was carried over by render_comments where the target has COMMENT ON
+                let keys: Vec<&str> = options
+                    .keys()
+                    .map(|key| key.as_str())
+                    .filter(|key| *key != "COMMENT" || !self.supports_comment_on())
+                    .collect();
+                if !keys.is_empty() {
+                    warn(
+                        warnings,
+                        object,
+                        format!(
+                            "table options {} have no {} equivalent and were dropped",
+                            keys.join(", "),
+                            self.target_config.name
+                        ),
+                    );
+                }
+            }
+        }
+
+        let sqlite_options: Vec<&str> = [(table.strict, "STRICT"), (table.without_rowid, "WITHOUT ROWID")]
+            .into_iter()
+            .filter_map(|(set, option)| set.then_some(option))
+            .collect();
+        if !sqlite_options.is_empty() {
+            if self.target == SqlDialect::SQLite {
+                sql.push_str(&format!(" {}", sqlite_options.join(", ")));
+            } else {
+                warn(
+                    warnings,
+                    object,
+                    format!("{} only applies to SQLite tables; dropped", sqlite_options.join(" and ")),
+                );
+            }
+        }
+
+        sql.push(';');
+        join_trailing(sql, trailing)
+    }
+
+    fn supports_comment_on(&self) -> bool {
+        matches!(self.target, SqlDialect::PostgreSQL | SqlDialect::Oracle)
+    }
+
+    /// `COMMENT ON` statements for targets that keep comments outside the table definition.
+    fn render_comments(&self, table: &CreateTableStatement, warnings: &mut Vec<TranspileWarning>) -> Vec<String> {
+        let mut statements = Vec::new();
+        if self.target == SqlDialect::MySQL {
+            return statements;
+        }
+
+        if let Some(comment) = table.options.get("COMMENT")
+            && self.supports_comment_on()
+        {
+            statements.push(format!(
+                "COMMENT ON TABLE {} IS {};",
+                self.quote_name(&table.table_name, warnings),
+                quote_literal(comment)
+            ));
+        }
+        for column in &table.columns {
+            for constraint in &column.constraints {
+                let ColumnConstraint::Comment(comment) = constraint else {
+                    continue;
+                };
+                if self.supports_comment_on() {
+                    statements.push(format!(
+                        "COMMENT ON COLUMN {}.{} IS {};",
+                        self.quote_name(&table.table_name, warnings),
+                        self.quote(&column.name),
+                        quote_literal(comment)
+                    ));
+                } else {
+                    let object = format!("{}.{}", self.object_name(&table.table_name), column.name);
+                    self.warn_unsupported(warnings, &object, "column comments");
+                }
+            }
+        }
+        statements
+    }
+
+    fn render_create_sequence(
+        &self,
+        sequence: &CreateSequenceStatement,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let object = &self.object_name(&sequence.sequence_name);
+        if !self.target_config.supports_sequences {
+            self.warn_unsupported(warnings, object, "sequences");
+            return None;
+        }
+
+        let mut sql = String::from("CREATE SEQUENCE ");
+        if sequence.if_not_exists {
+            if self.target == SqlDialect::PostgreSQL {
+                sql.push_str("IF NOT EXISTS ");
+            } else {
+                self.warn_unsupported(warnings, object, "IF NOT EXISTS on CREATE SEQUENCE");
+            }
+        }
+        sql.push_str(&self.quote_name(&sequence.sequence_name, warnings));
+        if let Some(data_type) = &sequence.data_type {
+            if matches!(self.target, SqlDialect::PostgreSQL | SqlDialect::MSSQL) {
+                let data_type = entity::DataType::from(data_type);
+                let converted = FieldService::adapt_data_type(&data_type, &self.target).unwrap_or(data_type);
+                sql.push_str(&format!(" AS {}", converted.to_sql()));
+            } else {
+                self.warn_unsupported(warnings, object, "sequence data types");
+            }
+        }
+        for option in sequence_options(&sequence.options) {
+            sql.push(' ');
+            sql.push_str(&option);
+        }
+        sql.push(';');
+        Some(sql)
+    }
+
+    fn render_create_schema(
+        &self,
+        schema: &CreateSchemaStatement,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let object = &schema.schema_name;
+        let (if_not_exists, authorization) = match self.target {
+            SqlDialect::PostgreSQL => (true, true),
+            SqlDialect::MySQL => (true, false),
+            SqlDialect::MSSQL | SqlDialect::Standard => (false, true),
+            SqlDialect::SQLite | SqlDialect::Oracle => {
+                self.warn_unsupported(warnings, object, "CREATE SCHEMA");
+                return None;
+            }
+        };
+
+        let mut sql = String::from(if self.target == SqlDialect::MySQL { "CREATE DATABASE " } else { "CREATE SCHEMA " });
+        if schema.if_not_exists {
+            if if_not_exists {
+                sql.push_str("IF NOT EXISTS ");
+            } else {
+                self.warn_unsupported(warnings, object, "IF NOT EXISTS on CREATE SCHEMA");
+            }
+        }
+        sql.push_str(&self.quote(object));
+        if let Some(owner) = &schema.authorization {
+            if authorization {
+                sql.push_str(&format!(" AUTHORIZATION {}", self.quote(owner)));
+            } else {
+                self.warn_unsupported(warnings, object, "schema owners");
+            }
+        }
+        sql.push(';');
+        Some(sql)
+    }
+
+    fn render_drop_table(&self, drop: &DropTableStatement, warnings: &mut Vec<TranspileWarning>) -> String {
+        let names: Vec<String> = drop.table_names.iter().map(|name| self.quote_name(name, warnings)).collect();
+        let object = drop.table_names.iter().map(|name| self.object_name(name)).collect::<Vec<_>>().join(", ");
+        let mut sql = String::from("DROP TABLE ");
+        if drop.if_exists {
+            if self.target == SqlDialect::Oracle {
+                self.warn_unsupported(warnings, &object, "IF EXISTS on DROP TABLE");
+            } else {
+                sql.push_str("IF EXISTS ");
+            }
+        }
+        sql.push_str(&names.join(", "));
+        if drop.cascade {
+            match self.target {
+                SqlDialect::Oracle => sql.push_str(" CASCADE CONSTRAINTS"),
+                SqlDialect::PostgreSQL | SqlDialect::MySQL | SqlDialect::Standard => sql.push_str(" CASCADE"),
+                _ => self.warn_unsupported(warnings, &object, "DROP TABLE ... CASCADE"),
+            }
+        }
+        sql.push(';');
+        sql
+    }
+
+    /// Replaces an enum or domain column type with its definition for targets without named types.
+    fn resolve_user_type<'c>(
+        &self,
+        column: &'c ColumnDefinition,
+        types: &UserTypes,
+        object: &str,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Cow<'c, ColumnDefinition> {
+        let DataType::Custom(name) = &column.data_type else {
+            return Cow::Borrowed(column);
+        };
+        if self.target == SqlDialect::PostgreSQL {
+            return Cow::Borrowed(column);
+        }
+
+        match user_type(types, name) {
+            Some(UserType::Enum { values, .. }) => Cow::Owned(ColumnDefinition {
+                data_type: DataType::Enum { values: values.to_vec() },
+                ..column.clone()
+            }),
+            Some(UserType::Domain(domain)) => {
+                let mut constraints: Vec<ColumnConstraint> = domain
+                    .constraints
+                    .iter()
+                    .map(|constraint| match constraint {
+                        ColumnConstraint::Check(expression) => {
+                            ColumnConstraint::Check(substitute_domain_value(expression, &column.name))
+                        }
+                        other => other.clone(),
+                    })
+                    .filter(|constraint| !column.constraints.contains(constraint))
+                    .collect();
+                // A column's own DEFAULT overrides the domain's
+                if column.constraints.iter().any(|c| matches!(c, ColumnConstraint::Default(_))) {
+                    constraints.retain(|c| !matches!(c, ColumnConstraint::Default(_)));
+                }
+                constraints.extend(column.constraints.iter().cloned());
+                warn(
+                    warnings,
+                    object,
+                    format!("domain {} replaced by its base type and constraints", domain.domain_name),
+                );
+                Cow::Owned(ColumnDefinition {
+                    name: column.name.clone(),
+                    data_type: domain.data_type.clone(),
+                    type_name: None,
+                    constraints,
+                })
+            }
+            Some(UserType::Composite { .. }) | None => Cow::Borrowed(column),
+        }
+    }
+
+    /// `allow_auto_increment` is false for a MySQL column that can't be the table's one AUTO_INCREMENT key.
+    fn render_column(
+        &self,
+        table_name: &str,
+        column: &ColumnDefinition,
+        allow_auto_increment: bool,
+        types: &UserTypes,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> String {
+        let object = format!("{}.{}", table_name, column.name);
+        let column_type = self.convert_column_type(column, types, &object, warnings);
+        let identity = column.constraints.iter().find_map(|constraint| match constraint {
+            ColumnConstraint::Identity(options) => Some(options),
+            _ => None,
+        });
+        let mut auto_increment = column_type.auto_increment
+            || column.constraints.contains(&ColumnConstraint::AutoIncrement);
+        let mut type_sql = column_type.sql;
+        let mut sqlite_autoincrement = false;
+
+        let mut parts = Vec::new();
+
+        if let Some(options) = identity {
+            match self.render_identity(options, &object, warnings) {
+                Some(sql) => parts.push(sql),
+                None => auto_increment = true,
+            }
+        }
+
+        if auto_increment && !allow_auto_increment {
+            warn(
+                warnings,
+                &object,
+                "MySQL allows AUTO_INCREMENT only on one key column; rendered as a plain integer",
+            );
+            auto_increment = false;
+        }
+
+        if auto_increment {
+            if self.target == SqlDialect::SQLite {
+                // AUTOINCREMENT is only legal on the rowid alias, `INTEGER PRIMARY KEY`
+                if column.is_primary_key() {
+                    type_sql = "INTEGER".to_string();
+                    sqlite_autoincrement = true;
+                } else {
+                    warn(
+                        warnings,
+                        &object,
+                        "AUTOINCREMENT requires an inline INTEGER PRIMARY KEY in SQLite; dropped",
+                    );
+                }
+            } else if self.target_config.supports_auto_increment {
+                parts.push(self.target_config.auto_increment_keyword.clone());
+            } else if self.target_config.supports_identity {
+                parts.push(self.target_config.identity_keyword.clone());
+            } else {
+                self.warn_unsupported(warnings, &object, "auto-increment");
+            }
+        }
+
+        // A `CONSTRAINT name` travels with the constraint after it when they're reordered
+        let mut constraints: Vec<(Option<&String>, &ColumnConstraint)> = Vec::new();
+        let mut pending_name = None;
+        for constraint in &column.constraints {
+            match constraint {
+                ColumnConstraint::Name(name) => pending_name = Some(name),
+                _ => constraints.push((pending_name.take(), constraint)),
+            }
+        }
+        constraints.sort_by_key(|(_, constraint)| constraint_rank(constraint));
+
+        for (name, constraint) in constraints {
+            let rendered = parts.len();
+            match constraint {
+                ColumnConstraint::Default(value) => {
+                    parts.push(format!("DEFAULT {}", self.translate_default(value, &object, warnings)));
+                }
+                ColumnConstraint::NotNull => parts.push("NOT NULL".to_string()),
+                ColumnConstraint::Null => parts.push("NULL".to_string()),
+                ColumnConstraint::PrimaryKey if sqlite_autoincrement => {
+                    parts.push(format!("PRIMARY KEY {}", self.target_config.auto_increment_keyword));
+                }
+                // Dropping the DESC would turn a SQLite column into a rowid alias
+                ColumnConstraint::PrimaryKey
+                    if self.target == SqlDialect::SQLite
+                        && column.constraints.contains(&ColumnConstraint::KeyDescending) =>
+                {
+                    parts.push("PRIMARY KEY DESC".to_string());
+                }
+                ColumnConstraint::PrimaryKey => parts.push("PRIMARY KEY".to_string()),
+                ColumnConstraint::Unique => parts.push("UNIQUE".to_string()),
+                ColumnConstraint::Check(expression) => {
+                    parts.push(format!("CHECK ({})", self.requote_expression(expression)));
+                }
+                ColumnConstraint::ForeignKey { table, column, on_delete, on_update } => {
+                    let mut reference = format!("REFERENCES {}", self.quote_name(table, warnings));
+                    if !column.is_empty() {
+                        reference.push_str(&format!(" ({})", self.quote(column)));
+                    }
+                    reference.push_str(&self.render_actions(&object, on_delete, on_update, warnings));
+                    parts.push(reference);
+                }
+                ColumnConstraint::Generated { expression, stored } => {
+                    if let Some(sql) = self.render_generated(expression, *stored, &object, warnings) {
+                        parts.push(sql);
+                    }
+                }
+                ColumnConstraint::Collate(collation) => {
+                    if self.source == self.target {
+                        let collation = match self.target {
+                            SqlDialect::PostgreSQL => self.quote(collation),
+                            _ => collation.clone(),
+                        };
+                        parts.push(format!("COLLATE {}", collation));
+                    } else {
+                        warn(
+                            warnings,
+                            &object,
+                            format!(
+                                "collation {} is not portable to {}; dropped",
+                                collation, self.target_config.name
+                            ),
+                        );
+                    }
+                }
+                ColumnConstraint::CharacterSet(charset) => {
+                    if self.source == SqlDialect::MySQL && self.target == SqlDialect::MySQL {
+                        parts.push(format!("CHARACTER SET {}", charset));
+                    } else {
+                        warn(
+                            warnings,
+                            &object,
+                            format!("character set {} dropped; {} uses the database encoding", charset, self.target_config.name),
+                        );
+                    }
+                }
+                ColumnConstraint::OnUpdate(expression) => {
+                    if self.target == SqlDialect::MySQL {
+                        parts.push(format!("ON UPDATE {}", self.translate_default(expression, &object, warnings)));
+                    } else {
+                        warn(
+                            warnings,
+                            &object,
+                            format!("ON UPDATE {} dropped; {} needs a trigger to maintain it", expression, self.target_config.name),
+                        );
+                    }
+                }
+                ColumnConstraint::Comment(comment) => {
+                    // Other targets get COMMENT ON statements after the table
+                    if self.target == SqlDialect::MySQL {
+                        parts.push(format!("COMMENT {}", quote_literal(comment)));
+                    }
+                }
+                ColumnConstraint::AutoIncrement
+                | ColumnConstraint::Identity(_)
+                | ColumnConstraint::KeyDescending
+                | ColumnConstraint::Name(_) => {}
+            }
+            if let Some(name) = name
+                && parts.len() > rendered
+            {
+                if self.can_name_column_constraint(constraint) {
+                    parts[rendered] = format!("{}{}", self.constraint_prefix(&Some(name.clone())), parts[rendered]);
+                } else {
+                    warn(
+                        warnings,
+                        &object,
+                        format!("constraint name {} dropped; {} can't name it on the column", name, self.target_config.name),
+                    );
+                }
+            }
+        }
+
+        if let Some(check) = column_type.check {
+            parts.push(format!("CHECK ({})", check));
+        }
+
+        // An untyped SQLite column stays untyped
+        let mut sql = self.quote(&column.name);
+        if !type_sql.is_empty() {
+            sql.push(' ');
+            sql.push_str(&type_sql);
+        }
+        for part in parts {
+            sql.push(' ');
+            sql.push_str(&part);
+        }
+        sql
+    }
+
+    /// Renders an identity clause, or returns `None` when the target spells it as auto-increment.
+    fn render_identity(
+        &self,
+        options: &IdentityOptions,
+        object: &str,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        match self.target {
+            SqlDialect::PostgreSQL | SqlDialect::Oracle | SqlDialect::Standard => {
+                let mut sql = format!(
+                    "GENERATED {} AS IDENTITY",
+                    if options.always { "ALWAYS" } else { "BY DEFAULT" }
+                );
+                let sequence = sequence_options(options);
+                if !sequence.is_empty() {
+                    sql.push_str(&format!(" ({})", sequence.join(" ")));
+                }
+                Some(sql)
+            }
+            SqlDialect::MSSQL => {
+                if options.min_value.is_some() || options.max_value.is_some() || options.cycle {
+                    self.warn_unsupported(warnings, object, "identity MINVALUE, MAXVALUE and CYCLE");
+                }
+                Some(format!(
+                    "IDENTITY({},{})",
+                    options.start.unwrap_or(1),
+                    options.increment.unwrap_or(1)
+                ))
+            }
+            SqlDialect::MySQL | SqlDialect::SQLite => {
+                if *options != (IdentityOptions { always: options.always, ..IdentityOptions::default() }) {
+                    self.warn_unsupported(warnings, object, "identity sequence options");
+                }
+                None
+            }
+        }
+    }
+
+    fn render_generated(
+        &self,
+        expression: &str,
+        stored: bool,
+        object: &str,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let expression = self.requote_expression(expression);
+        let storage = match (&self.target, stored) {
+            (SqlDialect::MSSQL, _) => {
+                warn(
+                    warnings,
+                    object,
+                    format!("generated column dropped; rewrite it as a computed column `AS ({})`", expression),
+                );
+                return None;
+            }
+            (SqlDialect::PostgreSQL, false) => {
+                warn(warnings, object, "PostgreSQL generated columns are always stored");
+                "STORED"
+            }
+            (SqlDialect::Oracle, true) => {
+                warn(warnings, object, "Oracle generated columns are always virtual");
+                "VIRTUAL"
+            }
+            (_, true) => "STORED",
+            (_, false) => "VIRTUAL",
+        };
+        Some(format!("GENERATED ALWAYS AS ({}) {}", expression, storage))
+    }
+
+    fn convert_column_type(
+        &self,
+        column: &ColumnDefinition,
+        types: &UserTypes,
+        object: &str,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> ColumnType {
+        match &column.data_type {
+            DataType::Custom(name)
+                if self.target == SqlDialect::PostgreSQL
+                    && let Some(UserType::Enum { name, .. } | UserType::Composite { name }) = user_type(types, name) =>
+            {
+                ColumnType { sql: self.quote_name(name, warnings), check: None, auto_increment: false }
+            }
+            DataType::Custom(name)
+                if self.target == SqlDialect::PostgreSQL
+                    && let Some(UserType::Domain(domain)) = user_type(types, name) =>
+            {
+                ColumnType { sql: self.quote_name(&domain.domain_name, warnings), check: None, auto_increment: false }
+            }
+            DataType::Array { element_type, .. } if self.target != SqlDialect::PostgreSQL => {
+                let json = FieldService::adapt_data_type(&entity::DataType::Json, &self.target)
+                    .unwrap_or(entity::DataType::Text);
+                warn(
+                    warnings,
+                    object,
+                    format!(
+                        "{} has no arrays; {}[] stored as {}",
+                        self.target_config.name,
+                        entity::DataType::from(element_type.as_ref()).to_sql(),
+                        json.to_sql()
+                    ),
+                );
+                ColumnType { sql: json.to_sql(), check: None, auto_increment: false }
+            }
+            DataType::Enum { values } if self.target != SqlDialect::MySQL => {
+                let length = values.iter().map(|value| value.chars().count()).max().unwrap_or(1) as u32;
+                let varchar = entity::DataType::VarChar { length: Some(length) };
+                let varchar = FieldService::adapt_data_type(&varchar, &self.target).unwrap_or(varchar);
+                let quoted: Vec<String> = values.iter().map(|value| quote_literal(value)).collect();
+                warn(
+                    warnings,
+                    object,
+                    format!("ENUM rewritten as {} with a CHECK constraint", varchar.to_sql()),
+                );
+                ColumnType {
+                    sql: varchar.to_sql(),
+                    check: Some(format!("{} IN ({})", self.quote(&column.name), quoted.join(", "))),
+                    auto_increment: false,
+                }
+            }
+            DataType::Set { values } if self.target != SqlDialect::MySQL => {
+                // A SET value is a comma-separated subset of its members
+                let length = values.iter().map(|value| value.chars().count() + 1).sum::<usize>().max(1) as u32;
+                let varchar = entity::DataType::VarChar { length: Some(length) };
+                let varchar = FieldService::adapt_data_type(&varchar, &self.target).unwrap_or(varchar);
+                warn(
+                    warnings,
+                    object,
+                    format!("SET rewritten as {}; member values are no longer checked", varchar.to_sql()),
+                );
+                ColumnType { sql: varchar.to_sql(), check: None, auto_increment: false }
+            }
+            DataType::Custom(name) if self.source == SqlDialect::SQLite && self.target == SqlDialect::SQLite => {
+                ColumnType { sql: name.clone(), check: None, auto_increment: false }
+            }
+            DataType::Custom(name) => {
+                let base = name.split('(').next().unwrap_or(name).trim().to_uppercase();
+                let sql = match self.field_service.convert_data_type(name, &self.target) {
+                    Some(converted) => converted.to_sql(),
+                    None => {
+                        self.warn_unconvertible(warnings, object, name);
+                        name.clone()
+                    }
+                };
+                ColumnType {
+                    sql,
+                    check: None,
+                    auto_increment: SERIAL_TYPES.contains(&base.as_str()),
+                }
+            }
+            data_type => {
+                let source = match entity::DataType::from(data_type) {
+                    // Oracle's DATE carries a time of day
+                    entity::DataType::Date if self.source == SqlDialect::Oracle => entity::DataType::DateTime,
+                    source => source,
+                };
+                let sql = match FieldService::adapt_data_type(&source, &self.target) {
+                    Some(converted) => {
+                        if is_unsigned(data_type) && self.target != SqlDialect::MySQL {
+                            warn(
+                                warnings,
+                                object,
+                                format!(
+                                    "{} has no UNSIGNED types; {} used instead",
+                                    self.target_config.name,
+                                    converted.to_sql()
+                                ),
+                            );
+                        }
+                        converted.to_sql()
+                    }
+                    None => {
+                        self.warn_unconvertible(warnings, object, &source.to_sql());
+                        source.to_sql()
+                    }
+                };
+                ColumnType { sql, check: None, auto_increment: false }
+            }
+        }
+    }
+
+    fn render_table_constraint(
+        &self,
+        object: &str,
+        constraint: &TableConstraint,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let sql = match constraint {
+            TableConstraint::PrimaryKey { name, columns, include } => format!(
+                "{}PRIMARY KEY ({}){}",
+                self.constraint_prefix(name),
+                self.quote_list(columns),
+                self.render_key_include(object, include, warnings)
+            ),
+            TableConstraint::Unique { name, columns, include } => format!(
+                "{}UNIQUE ({}){}",
+                self.constraint_prefix(name),
+                self.quote_list(columns),
+                self.render_key_include(object, include, warnings)
+            ),
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                referenced_table,
+                referenced_columns,
+                on_delete,
+                on_update,
+            } => format!(
+                "{}FOREIGN KEY ({}) REFERENCES {} ({}){}",
+                self.constraint_prefix(name),
+                self.quote_list(columns),
+                self.quote_name(referenced_table, warnings),
+                self.quote_list(referenced_columns),
+                self.render_actions(object, on_delete, on_update, warnings)
+            ),
+            TableConstraint::Check { name, expression } => {
+                format!("{}CHECK ({})", self.constraint_prefix(name), self.requote_expression(expression))
+            }
+            TableConstraint::Index { name, kind, columns, index_type } => {
+                let mut sql = match kind {
+                    IndexKind::Regular => "KEY".to_string(),
+                    IndexKind::FullText => "FULLTEXT KEY".to_string(),
+                    IndexKind::Spatial => "SPATIAL KEY".to_string(),
+                };
+                if let Some(name) = name {
+                    sql.push_str(&format!(" {}", self.quote(name)));
+                }
+                sql.push_str(&format!(" ({})", self.render_key_parts(name.as_deref().unwrap_or(object), columns, warnings)));
+                if let Some(index_type @ (IndexType::BTree | IndexType::Hash)) = index_type {
+                    sql.push_str(&format!(" USING {}", index_method_name(index_type)));
+                }
+                sql
+            }
+            TableConstraint::Exclude { name, index_type, elements, where_clause } => {
+                if self.target != SqlDialect::PostgreSQL {
+                    self.warn_unsupported(warnings, object, "EXCLUDE constraints");
+                    return None;
+                }
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| {
+                        format!("{} WITH {}", self.requote_expression(&element.expression), element.operator)
+                    })
+                    .collect();
+                let mut sql = format!("{}EXCLUDE", self.constraint_prefix(name));
+                if let Some(index_type) = index_type {
+                    sql.push_str(&format!(" USING {}", index_method_name(index_type).to_lowercase()));
+                }
+                sql.push_str(&format!(" ({})", elements.join(", ")));
+                if let Some(where_clause) = where_clause {
+                    sql.push_str(&format!(" WHERE ({})", self.requote_expression(where_clause)));
+                }
+                sql
+            }
+        };
+        Some(sql)
+    }
+
+    /// PostgreSQL's `INCLUDE (...)` on a primary key or unique constraint.
+    fn render_key_include(&self, object: &str, include: &[String], warnings: &mut Vec<TranspileWarning>) -> String {
+        if include.is_empty() {
+            String::new()
+        } else if self.target == SqlDialect::PostgreSQL {
+            format!(" INCLUDE ({})", self.quote_list(include))
+        } else {
+            self.warn_unsupported(warnings, object, "INCLUDE columns on PRIMARY KEY and UNIQUE constraints");
+            String::new()
+        }
+    }
+
+    /// Indexed columns with their sort order; only MySQL indexes a prefix of a column.
+    fn render_key_parts(&self, object: &str, parts: &[KeyPart], warnings: &mut Vec<TranspileWarning>) -> String {
+        if self.target != SqlDialect::MySQL && parts.iter().any(|part| part.length.is_some()) {
+            self.warn_unsupported(warnings, object, "index prefix lengths");
+        }
+        let parts: Vec<String> = parts
+            .iter()
+            .map(|part| {
+                let mut sql = self.quote(&part.column);
+                if let Some(length) = part.length
+                    && self.target == SqlDialect::MySQL
+                {
+                    sql.push_str(&format!("({})", length));
+                }
+                if part.descending {
+                    sql.push_str(" DESC");
+                }
+                sql
+            })
+            .collect();
+        parts.join(", ")
+    }
+
+    fn render_actions(
+        &self,
+        object: &str,
+        on_delete: &Option<ReferentialAction>,
+        on_update: &Option<ReferentialAction>,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> String {
+        let mut sql = String::new();
+
+        if let Some(action) = on_delete
+            && let Some(action) = self.referential_action_sql(object, action, warnings)
+        {
+            sql.push_str(&format!(" ON DELETE {}", action));
+        }
+
+        if let Some(action) = on_update {
+            if self.target == SqlDialect::Oracle {
+                self.warn_unsupported(warnings, object, "ON UPDATE referential actions");
+            } else if let Some(action) = self.referential_action_sql(object, action, warnings) {
+                sql.push_str(&format!(" ON UPDATE {}", action));
+            }
+        }
+
+        sql
+    }
+
+    fn referential_action_sql(
+        &self,
+        object: &str,
+        action: &ReferentialAction,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<&'static str> {
+        match (action, &self.target) {
+            (ReferentialAction::Cascade, _) => Some("CASCADE"),
+            (ReferentialAction::SetNull, _) => Some("SET NULL"),
+            // Oracle only spells out the non-default actions
+            (ReferentialAction::Restrict | ReferentialAction::NoAction, SqlDialect::Oracle) => None,
+            (ReferentialAction::SetDefault, SqlDialect::Oracle) => {
+                self.warn_unsupported(warnings, object, "ON DELETE SET DEFAULT");
+                None
+            }
+            (ReferentialAction::SetDefault, _) => Some("SET DEFAULT"),
+            (ReferentialAction::Restrict, SqlDialect::MSSQL) => Some("NO ACTION"),
+            (ReferentialAction::Restrict, _) => Some("RESTRICT"),
+            (ReferentialAction::NoAction, _) => Some("NO ACTION"),
+        }
+    }
+
+    fn render_create_index(
+        &self,
+        index: &CreateIndexStatement,
+        warnings: &mut Vec<TranspileWarning>,
+    ) -> Option<String> {
+        let object = &index.index_name;
+        let mut sql = String::from("CREATE ");
+        let mut index_type = index.index_type.clone();
+
+        match (&index.kind, &self.target) {
+            (IndexKind::Regular, _) => {}
+            (IndexKind::FullText, SqlDialect::MySQL) => sql.push_str("FULLTEXT "),
+            (IndexKind::Spatial, SqlDialect::MySQL) => sql.push_str("SPATIAL "),
+            (IndexKind::Spatial, SqlDialect::PostgreSQL) => index_type = Some(IndexType::Gist),
+            (IndexKind::FullText, _) => {
+                warn(
+                    warnings,
+                    object,
+                    format!("FULLTEXT index dropped; use {}'s own full-text search", self.target_config.name),
+                );
+                return None;
+            }
+            (IndexKind::Spatial, _) => {
+                self.warn_unsupported(warnings, object, "SPATIAL indexes");
+                return None;
+            }
+        }
+
+        if index.unique {
+            sql.push_str("UNIQUE ");
+        }
+        sql.push_str("INDEX ");
+        if index.concurrently && self.target == SqlDialect::PostgreSQL {
+            sql.push_str("CONCURRENTLY ");
+        }
+
+        if index.if_not_exists {
+            if matches!(self.target, SqlDialect::PostgreSQL | SqlDialect::SQLite) {
+                sql.push_str("IF NOT EXISTS ");
+            } else {
+                self.warn_unsupported(warnings, object, "IF NOT EXISTS on CREATE INDEX");
+            }
+        }
+
+        sql.push_str(&format!(
+            "{} ON {}",
+            self.quote(&index.index_name),
+            self.quote_name(&index.table_name, warnings)
+        ));
+
+        let mut trailing_method = None;
+        match (&index_type, &self.target) {
+            (None, _) => {}
+            (Some(index_type), SqlDialect::PostgreSQL) => {
+                sql.push_str(&format!(" USING {}", index_method_name(index_type).to_lowercase()));
+            }
+            (Some(index_type @ (IndexType::BTree | IndexType::Hash)), SqlDialect::MySQL) => {
+                trailing_method = Some(index_method_name(index_type).to_uppercase());
+            }
+            // B-tree is the implicit default everywhere else
+            (Some(IndexType::BTree), _) => {}
+            (Some(index_type), _) => {
+                self.warn_unsupported(
+                    warnings,
+                    object,
+                    &format!("the {} index method", index_method_name(index_type)),
+                );
+            }
+        }
+
+        sql.push_str(&format!(" ({})", self.render_key_parts(object, &index.columns, warnings)));
+        if !index.include.is_empty() {
+            if matches!(self.target, SqlDialect::PostgreSQL | SqlDialect::MSSQL) {
+                sql.push_str(&format!(" INCLUDE ({})", self.quote_list(&index.include)));
+            } else {
+                self.warn_unsupported(warnings, object, "INCLUDE columns on indexes");
+            }
+        }
+
+        if let Some(method) = trailing_method {
+            sql.push_str(&format!(" USING {}", method));
+        }
+
+        if let Some(where_clause) = &index.where_clause {
+            if matches!(self.target, SqlDialect::PostgreSQL | SqlDialect::SQLite | SqlDialect::MSSQL) {
+                sql.push_str(&format!(" WHERE {}", self.requote_expression(where_clause)));
+            } else {
+                self.warn_unsupported(warnings, object, "partial indexes");
+            }
+        }
+
+        sql.push(';');
+        Some(sql)
+    }
+
+    /// Maps well-known default expressions onto the target's spelling; anything else is copied.
+    fn translate_default(&self, value: &str, object: &str, warnings: &mut Vec<TranspileWarning>) -> String {
+        let value = value.trim();
+        let upper = value.to_uppercase();
+
+        if value.starts_with('\'')
+            || value.starts_with('-')
+            || value.starts_with(|ch: char| ch.is_ascii_digit())
+            || upper == "NULL"
+        {
+            return value.to_string();
+        }
+
+        if upper == "TRUE" || upper == "FALSE" {
+            return match self.target {
+                SqlDialect::MSSQL | SqlDialect::Oracle => if upper == "TRUE" { "1" } else { "0" }.to_string(),
+                _ => upper,
+            };
+        }
+
+        // Bit literals such as b'1' mostly default BIT(1) flags
+        if let Some(bits) = upper.strip_prefix("B'").and_then(|bits| bits.strip_suffix('\''))
+            && let Ok(number) = u64::from_str_radix(bits, 2)
+        {
+            return match self.target {
+                SqlDialect::MySQL | SqlDialect::PostgreSQL => format!("b'{}'", bits),
+                _ => number.to_string(),
+            };
+        }
+
+        if let Some(name) = entity::Sequence::referenced_by(value) {
+            let next_value = match self.target {
+                SqlDialect::PostgreSQL => Some(format!("nextval({})", quote_literal(&self.quote(&name)))),
+                SqlDialect::Oracle => Some(format!("{}.NEXTVAL", self.quote(&name))),
+                SqlDialect::MSSQL | SqlDialect::Standard => Some(format!("NEXT VALUE FOR {}", self.quote(&name))),
+                SqlDialect::MySQL | SqlDialect::SQLite => None,
+            };
+            if let Some(next_value) = next_value {
+                return next_value;
+            }
+        }
+
+        let unwrapped = upper.trim_start_matches('(').trim_end_matches(')');
+        let function = unwrapped.split('(').next().unwrap_or(unwrapped).trim();
+
+        if TIMESTAMP_FUNCTIONS.contains(&function) {
+            return match self.target {
+                SqlDialect::PostgreSQL => "now()",
+                SqlDialect::MSSQL => "GETDATE()",
+                SqlDialect::Oracle => "SYSTIMESTAMP",
+                SqlDialect::MySQL | SqlDialect::SQLite | SqlDialect::Standard => "CURRENT_TIMESTAMP",
+            }
+            .to_string();
+        }
+
+        if DATE_FUNCTIONS.contains(&function) {
+            return match self.target {
+                SqlDialect::MSSQL => "CAST(GETDATE() AS DATE)",
+                SqlDialect::Oracle => "TRUNC(SYSDATE)",
+                _ => "CURRENT_DATE",
+            }
+            .to_string();
+        }
+
+        if UUID_FUNCTIONS.contains(&function) {
+            let generated = match self.target {
+                SqlDialect::PostgreSQL => Some("gen_random_uuid()"),
+                // MySQL only accepts expression defaults inside parentheses
+                SqlDialect::MySQL => Some("(UUID())"),
+                SqlDialect::MSSQL => Some("NEWID()"),
+                SqlDialect::Oracle => Some("SYS_GUID()"),
+                SqlDialect::SQLite | SqlDialect::Standard => None,
+            };
+            if let Some(generated) = generated {
+                return generated.to_string();
+            }
+        }
+
+        warn(
+            warnings,
+            object,
+            format!(
+                "default `{}` copied verbatim; check that {} accepts it",
+                value, self.target_config.name
+            ),
+        );
+        self.requote_expression(value)
+    }
+
+    /// Re-renders raw expression text so quoted identifiers use the target's quoting.
+    fn requote_expression(&self, expression: &str) -> String {
+        match Lexer::new(expression).tokenize() {
+            Ok(mut tokens) => {
+                for token in &mut tokens {
+                    if let TokenType::QuotedIdentifier(name) = &token.token_type {
+                        token.lexeme = self.quote(name);
+                    }
+                }
+                tokens_to_sql(&tokens)
+            }
+            Err(_) => expression.to_string(),
+        }
+    }
+
+    fn quote(&self, name: &str) -> String {
+        quote_identifier(name, &self.target, &self.target_config)
+    }
+
+    /// Names an object in warnings, leaving out the source's default schema.
+    fn object_name(&self, name: &SchemaReference) -> String {
+        match (&name.catalog, &name.schema, self.source.config().default_schema) {
+            (None, Some(schema), Some(default)) if schema.eq_ignore_ascii_case(&default) => name.object.clone(),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Quotes a possibly qualified name, mapping the source's default schema onto the target's.
+    fn quote_name(&self, name: &SchemaReference, warnings: &mut Vec<TranspileWarning>) -> String {
+        let mut parts = Vec::new();
+        if let Some(catalog) = &name.catalog {
+            if self.target == SqlDialect::MSSQL {
+                parts.push(self.quote(catalog));
+            } else {
+                self.warn_unsupported(warnings, &self.object_name(name), "database qualifiers");
+            }
+        }
+        if let Some(schema) = &name.schema {
+            let source_default = self.source.config().default_schema;
+            let is_default = source_default.is_some_and(|default| default.eq_ignore_ascii_case(schema));
+            if is_default {
+                if let Some(default) = &self.target_config.default_schema
+                    && !parts.is_empty()
+                {
+                    parts.push(self.quote(default));
+                }
+            } else if self.target == SqlDialect::SQLite {
+                self.warn_unsupported(warnings, &self.object_name(name), "schema qualifiers");
+            } else {
+                parts.push(self.quote(schema));
+            }
+        }
+        parts.push(self.quote(&name.object));
+        parts.join(".")
+    }
+
+    fn quote_list(&self, names: &[String]) -> String {
+        names.iter().map(|name| self.quote(name)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Whether the target takes `CONSTRAINT name` before this constraint in a column definition.
+    fn can_name_column_constraint(&self, constraint: &ColumnConstraint) -> bool {
+        match self.target {
+            SqlDialect::MySQL => matches!(constraint, ColumnConstraint::Check(_)),
+            SqlDialect::MSSQL => matches!(
+                constraint,
+                ColumnConstraint::Default(_)
+                    | ColumnConstraint::PrimaryKey
+                    | ColumnConstraint::Unique
+                    | ColumnConstraint::Check(_)
+                    | ColumnConstraint::ForeignKey { .. }
+            ),
+            SqlDialect::Oracle => matches!(
+                constraint,
+                ColumnConstraint::NotNull
+                    | ColumnConstraint::Null
+                    | ColumnConstraint::PrimaryKey
+                    | ColumnConstraint::Unique
+                    | ColumnConstraint::Check(_)
+                    | ColumnConstraint::ForeignKey { .. }
+            ),
+            SqlDialect::PostgreSQL | SqlDialect::SQLite | SqlDialect::Standard => matches!(
+                constraint,
+                ColumnConstraint::Default(_)
+                    | ColumnConstraint::NotNull
+                    | ColumnConstraint::Null
+                    | ColumnConstraint::PrimaryKey
+                    | ColumnConstraint::Unique
+                    | ColumnConstraint::Check(_)
+                    | ColumnConstraint::ForeignKey { .. }
+                    | ColumnConstraint::Generated { .. }
+            ),
+        }
+    }
+
+    fn constraint_prefix(&self, name: &Option<String>) -> String {
+        name.as_ref()
+            .map(|name| format!("CONSTRAINT {} ", self.quote(name)))
+            .unwrap_or_default()
+    }
+
+    fn supports_if_not_exists_table(&self) -> bool {
+        !matches!(self.target, SqlDialect::MSSQL | SqlDialect::Oracle)
+    }
+
+    fn warn_unsupported(&self, warnings: &mut Vec<TranspileWarning>, object: &str, feature: &str) {
+        warn(
+            warnings,
+            object,
+            format!("{} does not support {}; dropped", self.target_config.name, feature),
+        );
+    }
+
+    fn warn_unconvertible(&self, warnings: &mut Vec<TranspileWarning>, object: &str, type_name: &str) {
+        warn(
+            warnings,
+            object,
+            format!("type {} has no {} equivalent; kept as-is", type_name, self.target_config.name),
+        );
+    }
+}
+
+fn warn(warnings: &mut Vec<TranspileWarning>, object: &str, message: impl Into<String>) {
+    warnings.push(TranspileWarning {
+        object: object.to_string(),
+        message: message.into(),
+    });
+}
+
+/// Orders column constraints so every target accepts them: defaults, nullability, keys, checks.
+fn constraint_rank(constraint: &ColumnConstraint) -> u8 {
+    match constraint {
+        ColumnConstraint::Collate(_) | ColumnConstraint::CharacterSet(_) => 0,
+        ColumnConstraint::AutoIncrement | ColumnConstraint::Identity(_) => 0,
+        ColumnConstraint::Default(_) | ColumnConstraint::Generated { .. } | ColumnConstraint::OnUpdate(_) => 1,
+        ColumnConstraint::NotNull | ColumnConstraint::Null => 2,
+        ColumnConstraint::PrimaryKey | ColumnConstraint::KeyDescending => 3,
+        ColumnConstraint::Unique => 4,
+        ColumnConstraint::Check(_) => 5,
+        ColumnConstraint::ForeignKey { .. } => 6,
+        ColumnConstraint::Comment(_) => 7,
+        // Names are paired with the constraint they name before sorting
+        ColumnConstraint::Name(_) => 0,
+    }
+}
+
+/// `START WITH n INCREMENT BY n ...`, as identity columns and sequences both spell them.
+fn sequence_options(options: &IdentityOptions) -> Vec<String> {
+    let mut sequence = Vec::new();
+    if let Some(start) = options.start {
+        sequence.push(format!("START WITH {}", start));
+    }
+    if let Some(increment) = options.increment {
+        sequence.push(format!("INCREMENT BY {}", increment));
+    }
+    if let Some(min_value) = options.min_value {
+        sequence.push(format!("MINVALUE {}", min_value));
+    }
+    if let Some(max_value) = options.max_value {
+        sequence.push(format!("MAXVALUE {}", max_value));
+    }
+    if options.cycle {
+        sequence.push("CYCLE".to_string());
+    }
+    sequence
+}
+
+/// The one column MySQL may make AUTO_INCREMENT: an identity, serial or auto-increment column that
+/// leads a key, preferring the primary key's.
+fn mysql_auto_increment_key<'t>(table: &'t CreateTableStatement, rowid_alias: Option<&str>) -> Option<&'t str> {
+    let leads = |column: &ColumnDefinition, primary: bool| {
+        column.is_primary_key()
+            || (!primary && column.constraints.contains(&ColumnConstraint::Unique))
+            || table.constraints.iter().any(|constraint| match constraint {
+                TableConstraint::PrimaryKey { columns, .. } => columns.first() == Some(&column.name),
+                TableConstraint::Unique { columns, .. } if !primary => columns.first() == Some(&column.name),
+                TableConstraint::Index { columns, .. } if !primary => {
+                    columns.first().is_some_and(|part| part.column == column.name)
+                }
+                _ => false,
+            })
+    };
+    let candidates: Vec<&ColumnDefinition> = table
+        .columns
+        .iter()
+        .filter(|column| {
+            rowid_alias == Some(column.name.as_str())
+                || column.constraints.iter().any(|constraint| {
+                    matches!(constraint, ColumnConstraint::AutoIncrement | ColumnConstraint::Identity(_))
+                })
+                || matches!(&column.data_type, DataType::Custom(name)
+                    if SERIAL_TYPES.contains(&name.split('(').next().unwrap_or(name).trim().to_uppercase().as_str()))
+        })
+        .collect();
+    candidates
+        .iter()
+        .find(|column| leads(column, true))
+        .or_else(|| candidates.iter().find(|column| leads(column, false)))
+        .map(|column| column.name.as_str())
+}
+
+fn join_trailing(sql: String, trailing: Vec<String>) -> String {
+    std::iter::once(sql).chain(trailing).collect::<Vec<_>>().join("\n\n")
+}
+
+fn collect_user_types(statements: &[Statement]) -> UserTypes<'_> {
+    let mut types = HashMap::new();
+    for statement in statements {
+        match statement {
+            Statement::Ddl(DdlStatement::CreateType(create_type)) => {
+                let name = &create_type.type_name;
+                let user_type = match &create_type.definition {
+                    TypeDefinition::Enum { values } => UserType::Enum { name, values },
+                    TypeDefinition::Composite { .. } => UserType::Composite { name },
+                };
+                types.insert(name.object.to_uppercase(), user_type);
+            }
+            Statement::Ddl(DdlStatement::CreateDomain(domain)) => {
+                types.insert(domain.domain_name.object.to_uppercase(), UserType::Domain(domain));
+            }
+            _ => {}
+        }
+    }
+    types
+}
+
+/// Finds the user type a column type names, ignoring any schema qualifier.
+fn user_type<'t, 'a>(types: &'t UserTypes<'a>, name: &str) -> Option<&'t UserType<'a>> {
+    types.get(name.rsplit('.').next().unwrap_or(name))
+}
+
+/// Domain CHECKs name the checked value `VALUE`; inlined into a column, they name the column.
+fn substitute_domain_value(expression: &str, column: &str) -> String {
+    match Lexer::new(expression).tokenize() {
+        Ok(mut tokens) => {
+            for token in &mut tokens {
+                if let TokenType::Identifier(name) = &token.token_type
+                    && name.eq_ignore_ascii_case("VALUE")
+                {
+                    token.lexeme = format!("\"{}\"", column.replace('"', "\"\""));
+                }
+            }
+            tokens_to_sql(&tokens)
+        }
+        Err(_) => expression.to_string(),
+    }
+}
+
+fn is_unsigned(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Integer { unsigned: true, .. }
+            | DataType::BigInt { unsigned: true }
+            | DataType::SmallInt { unsigned: true }
+            | DataType::TinyInt { unsigned: true }
+    )
+}
+
+fn index_method_name(index_type: &IndexType) -> &str {
+    match index_type {
+        IndexType::BTree => "BTREE",
+        IndexType::Hash => "HASH",
+        IndexType::Gist => "GIST",
+        IndexType::Gin => "GIN",
+        IndexType::SpGist => "SPGIST",
+        IndexType::Brin => "BRIN",
+        IndexType::Custom(name) => name,
+    }
+}
+
+/// Quotes an identifier only where `dialect` would otherwise read it differently: keywords,
+/// names that aren't plain words, and names PostgreSQL would fold to lower case.
+pub(super) fn quote_identifier(name: &str, dialect: &SqlDialect, config: &DialectConfig) -> String {
+    let needs_quotes = config.is_keyword(name)
+        || name.starts_with(|ch: char| ch.is_ascii_digit())
+        || !name.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+        // PostgreSQL folds unquoted names to lower case
+        || (*dialect == SqlDialect::PostgreSQL && name.chars().any(char::is_uppercase));
+
+    if !needs_quotes {
+        return name.to_string();
+    }
+
+    match config.identifier_quote {
+        '[' => format!("[{}]", name.replace(']', "]]")),
+        quote => format!("{0}{1}{0}", quote, name.replace(quote, &format!("{0}{0}", quote))),
+    }
+}
+
+pub(super) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn render_option_value(key: &str, value: &str) -> String {
+    if key == "COMMENT" || !value.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+        quote_literal(value)
+    } else {
+        value.to_string()
+    }
+}
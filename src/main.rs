@@ -1,3 +1,8 @@
-fn main() {
-    println!("Hello, world!");
+mod cli;
+
+use clap::Parser;
+
+fn main() -> anyhow::Result<()> {
+    env_logger::init();
+    cli::Cli::parse().run()
 }
@@ -46,7 +46,7 @@ fn test_types_integration() {
     let identifier = Identifier::new("users");
     assert_eq!(identifier.to_string(), "users");
 
-    let qualified_name = QualifiedName::from_str("public.users").unwrap();
+    let qualified_name = QualifiedName::from_str("public.users").expect("qualified name should parse");
     assert_eq!(qualified_name.len(), 2);
     assert_eq!(qualified_name.to_string(), "public.users");
 
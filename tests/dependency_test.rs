@@ -0,0 +1,244 @@
+use sql2doc::core::analyze::{
+    CatalogBuilder, Change, DependencyGraph, DependencyGraphBuilder, DependencyKind, Impact, ObjectId, ObjectKind,
+};
+use sql2doc::core::domain::entity::Database;
+use sql2doc::core::domain::procedures::{Procedure, ProcedureMetadata};
+use sql2doc::core::domain::triggers::{Trigger, TriggerEvent, TriggerMetadata, TriggerScope, TriggerTiming};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+const SCHEMA: &str = "
+CREATE TABLE customers (id INT PRIMARY KEY, name TEXT NOT NULL, region TEXT);
+CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT REFERENCES customers (id), amount NUMERIC, note TEXT);
+CREATE TABLE audit_log (id SERIAL PRIMARY KEY, order_id INT, changed_at TIMESTAMP);
+CREATE INDEX orders_customer_idx ON orders (customer_id);
+CREATE VIEW order_totals AS
+    SELECT c.id, c.name, SUM(o.amount) AS total
+    FROM customers c JOIN orders o ON o.customer_id = c.id
+    GROUP BY c.id, c.name;
+CREATE VIEW big_spenders AS SELECT name FROM order_totals WHERE total > 1000;
+CREATE VIEW everything AS SELECT * FROM audit_log;
+";
+
+const QUERIES: &str = "
+-- name: OrdersForCustomer :many
+SELECT id, amount FROM orders WHERE customer_id = $1;
+
+-- name: RenameCustomer :exec
+UPDATE customers SET name = $2 WHERE id = $1;
+";
+
+fn lower(sql: &str) -> Database {
+    Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("schema should lower").database
+}
+
+fn graph(database: &Database) -> DependencyGraph {
+    let mut builder = CatalogBuilder::new(SqlDialect::PostgreSQL).with_schema(database);
+    builder.add_file("queries.sql", QUERIES).expect("queries should parse");
+    let catalog = builder.build("queries");
+    DependencyGraphBuilder::new(database, SqlDialect::PostgreSQL).with_queries(&catalog).build()
+}
+
+fn object(graph: &DependencyGraph, name: &str) -> ObjectId {
+    graph.find(name).cloned().unwrap_or_else(|| panic!("Expected an object named {}", name))
+}
+
+fn affected(impact: &Impact) -> Vec<String> {
+    impact.affected.iter().map(|affected| affected.object.to_string()).collect()
+}
+
+#[test]
+fn test_dependency_graph() {
+    let database = lower(SCHEMA);
+    let graph = graph(&database);
+
+    // Bare names prefer tables and views; a dotted name finds a column
+    assert_eq!(object(&graph, "orders"), ObjectId::new(ObjectKind::Table, "orders"));
+    assert_eq!(object(&graph, "ORDERS.Amount"), ObjectId::new(ObjectKind::Column, "orders.amount"));
+    assert_eq!(object(&graph, "index:orders_customer_idx").kind, ObjectKind::Index);
+    assert_eq!(object(&graph, "OrdersForCustomer").kind, ObjectKind::Query);
+    assert!(graph.find("column:orders").is_none());
+
+    let foreign_key = object(&graph, "orders.customer_id");
+    let kinds: Vec<(String, DependencyKind)> =
+        graph.dependencies(&foreign_key).iter().map(|edge| (edge.to.to_string(), edge.kind)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("table:orders".to_string(), DependencyKind::ColumnOf),
+            ("column:customers.id".to_string(), DependencyKind::ForeignKey),
+        ]
+    );
+
+    // Columns named anywhere in a view count, not just the ones it returns
+    let view = object(&graph, "order_totals");
+    let reads: Vec<String> = graph.dependencies(&view).iter().map(|edge| edge.to.to_string()).collect();
+    assert_eq!(
+        reads,
+        vec![
+            "table:customers",
+            "table:orders",
+            "column:orders.customer_id",
+            "column:customers.id",
+            "column:customers.name",
+            "column:orders.amount",
+        ]
+    );
+    // `*` reads every column
+    let everything = object(&graph, "everything");
+    assert_eq!(graph.dependencies(&everything).len(), 4);
+    let dependents: Vec<String> =
+        graph.dependents(&object(&graph, "audit_log.changed_at")).iter().map(|edge| edge.from.to_string()).collect();
+    assert_eq!(dependents, vec!["view:everything", "column:everything.changed_at"]);
+}
+
+#[test]
+fn test_impact_of_changes() {
+    let database = lower(SCHEMA);
+    let graph = graph(&database);
+
+    let impact = graph.impact(&object(&graph, "orders.amount"), Change::Drop);
+    assert_eq!(
+        affected(&impact),
+        vec!["view:order_totals", "column:order_totals.total", "query:OrdersForCustomer", "view:big_spenders"]
+    );
+    let nested = &impact.affected[3];
+    assert_eq!(nested.through.to_string(), "column:order_totals.total");
+    assert_eq!(nested.distance, 2);
+
+    // A dropped table takes its columns, indexes and the foreign keys to it along
+    let impact = graph.impact(&object(&graph, "customers"), Change::Drop);
+    let names = affected(&impact);
+    assert!(names.contains(&"column:customers.name".to_string()));
+    assert!(names.contains(&"column:orders.customer_id".to_string()));
+    assert!(names.contains(&"query:RenameCustomer".to_string()));
+    assert!(names.contains(&"view:big_spenders".to_string()));
+    // The foreign key goes, but the column stays, so its index is untouched
+    assert!(!names.contains(&"index:orders_customer_idx".to_string()));
+
+    // A type change is passed on through the foreign key to the referencing column and its index
+    let impact = graph.impact(&object(&graph, "customers.id"), Change::ChangeType);
+    let names = affected(&impact);
+    assert!(names.contains(&"column:orders.customer_id".to_string()));
+    assert!(names.contains(&"index:orders_customer_idx".to_string()));
+    assert!(names.contains(&"query:OrdersForCustomer".to_string()));
+
+    // Renaming leaves the index alone, and the join column isn't one the outer view reads
+    let impact = graph.impact(&object(&graph, "orders.customer_id"), Change::Rename);
+    assert_eq!(affected(&impact), vec!["view:order_totals", "query:OrdersForCustomer"]);
+
+    let impact = graph.impact(&object(&graph, "orders.note"), Change::Drop);
+    assert!(impact.affected.is_empty());
+}
+
+#[test]
+fn test_triggers_and_procedures() {
+    let mut database = lower(SCHEMA);
+    let schema = &mut database.schemas[0];
+    if let Some(orders) = schema.tables.iter_mut().find(|table| table.name == "orders") {
+        orders.triggers.push(Trigger {
+            name: "orders_audit".to_string(),
+            table_name: "orders".to_string(),
+            timing: TriggerTiming::After,
+            events: vec![TriggerEvent::Update],
+            update_columns: Vec::new(),
+            scope: TriggerScope::Row,
+            definition: "BEGIN INSERT INTO audit_log (order_id, changed_at) VALUES (NEW.id, now()); \
+                         PERFORM log_change(NEW.amount); END"
+                .to_string(),
+            metadata: TriggerMetadata::default(),
+        });
+    }
+    schema.functions.push(Procedure {
+        name: "log_change".to_string(),
+        schema_name: None,
+        parameters: Vec::new(),
+        definition: "BEGIN RETURN (SELECT count(big_spenders.name) FROM big_spenders); END".to_string(),
+        metadata: ProcedureMetadata::default(),
+    });
+    let graph = graph(&database);
+
+    let trigger = object(&graph, "trigger:orders.orders_audit");
+    let targets: Vec<String> = graph.dependencies(&trigger).iter().map(|edge| edge.to.to_string()).collect();
+    assert_eq!(
+        targets,
+        vec!["table:orders", "table:audit_log", "column:orders.id", "function:log_change", "column:orders.amount"]
+    );
+
+    // Through the function the trigger calls, which reads a view over the column
+    let impact = graph.impact(&object(&graph, "customers.name"), Change::Drop);
+    let names = affected(&impact);
+    assert!(names.contains(&"function:log_change".to_string()));
+    assert!(names.contains(&"trigger:orders.orders_audit".to_string()));
+
+    // A trigger moves with its table, but here it calls a function reading a view over it
+    let impact = graph.impact(&object(&graph, "orders"), Change::Rename);
+    let reached = impact.affected.iter().find(|affected| affected.object == trigger).expect("trigger should be affected");
+    assert_eq!(reached.dependency, DependencyKind::Calls);
+    assert_eq!(reached.through.to_string(), "function:log_change");
+    assert!(!affected(&impact).contains(&"column:orders.amount".to_string()));
+}
+
+#[test]
+fn test_triggers_and_routines_from_sql() {
+    let sql = format!(
+        "{}
+        CREATE FUNCTION log_change() RETURNS trigger LANGUAGE plpgsql AS $$
+        BEGIN
+            INSERT INTO audit_log (order_id, changed_at) VALUES (NEW.id, now());
+            RETURN NEW;
+        END;
+        $$;
+        CREATE TRIGGER orders_audit AFTER UPDATE ON orders FOR EACH ROW EXECUTE FUNCTION log_change();",
+        SCHEMA
+    );
+    let database = lower(&sql);
+    let graph = graph(&database);
+
+    let trigger = object(&graph, "trigger:orders.orders_audit");
+    let targets: Vec<String> = graph.dependencies(&trigger).iter().map(|edge| edge.to.to_string()).collect();
+    assert_eq!(targets, vec!["table:orders", "function:log_change"]);
+
+    let impact = graph.impact(&object(&graph, "audit_log"), Change::Drop);
+    let names = affected(&impact);
+    assert!(names.contains(&"function:log_change".to_string()));
+    assert!(names.contains(&"trigger:orders.orders_audit".to_string()));
+}
+
+#[test]
+fn test_view_impact_follows_lineage() {
+    let database = lower(
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT NOT NULL);
+        CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users (id), amount NUMERIC);
+        CREATE VIEW user_totals AS
+            SELECT u.id, u.email, SUM(o.amount) AS total
+            FROM users u JOIN orders o ON o.user_id = u.id
+            GROUP BY u.id, u.email;
+        CREATE VIEW vips AS SELECT total FROM user_totals WHERE total > 1000;
+        CREATE FUNCTION email_changed() RETURNS trigger LANGUAGE plpgsql AS $$ BEGIN RETURN NEW; END; $$;
+        CREATE TRIGGER users_email AFTER UPDATE OF email ON users FOR EACH ROW EXECUTE FUNCTION email_changed();",
+    );
+    let graph = DependencyGraphBuilder::new(&database, SqlDialect::PostgreSQL).build();
+
+    let reads: Vec<String> =
+        graph.dependencies(&object(&graph, "column:vips.total")).iter().map(|edge| edge.to.to_string()).collect();
+    assert_eq!(reads, vec!["view:vips", "column:orders.amount"]);
+    let trigger = object(&graph, "trigger:users.users_email");
+    let targets: Vec<String> = graph.dependencies(&trigger).iter().map(|edge| edge.to.to_string()).collect();
+    assert_eq!(targets, vec!["table:users", "column:users.email", "function:email_changed"]);
+
+    // vips only reads the total, which isn't computed from the email
+    let impact = graph.impact(&object(&graph, "users.email"), Change::Drop);
+    assert_eq!(affected(&impact), vec!["trigger:users.users_email", "view:user_totals", "column:user_totals.email"]);
+
+    // A new type reaches vips through the column it reads
+    let impact = graph.impact(&object(&graph, "orders.amount"), Change::ChangeType);
+    let names = affected(&impact);
+    assert!(names.contains(&"column:user_totals.total".to_string()));
+    assert!(names.contains(&"view:vips".to_string()));
+    assert!(names.contains(&"column:vips.total".to_string()));
+
+    // The view's columns keep their names when the table's is renamed
+    let impact = graph.impact(&object(&graph, "users.email"), Change::Rename);
+    assert_eq!(affected(&impact), vec!["trigger:users.users_email", "view:user_totals"]);
+}
@@ -0,0 +1,51 @@
+use sql2doc::core::parse::{DialectDetector, SqlDialect};
+
+fn detect(sql: &str) -> SqlDialect {
+    DialectDetector::new().detect(sql).expect("dialect should be detected").dialect
+}
+
+#[test]
+fn test_detect_each_dialect() {
+    assert_eq!(
+        detect("CREATE TABLE `users` (`id` INT UNSIGNED NOT NULL AUTO_INCREMENT) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;"),
+        SqlDialect::MySQL
+    );
+    assert_eq!(
+        detect("\\connect shop\nCREATE TABLE users (id BIGSERIAL PRIMARY KEY, profile JSONB);\nCREATE FUNCTION f() RETURNS int AS $$ SELECT 1 $$ LANGUAGE sql;"),
+        SqlDialect::PostgreSQL
+    );
+    assert_eq!(
+        detect("PRAGMA foreign_keys = ON;\nCREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT) STRICT;"),
+        SqlDialect::SQLite
+    );
+    assert_eq!(
+        detect("CREATE TABLE [dbo].[Users](\n\t[ID] [int] IDENTITY(1,1) NOT NULL,\n\t[Name] [nvarchar](max) NULL\n)\nGO\n"),
+        SqlDialect::MSSQL
+    );
+    assert_eq!(
+        detect("CREATE TABLE users (id NUMBER(10,0), name VARCHAR2(100 CHAR), created DATE DEFAULT SYSDATE)\n/\n"),
+        SqlDialect::Oracle
+    );
+}
+
+#[test]
+fn test_detect_ranks_mixed_evidence() {
+    // A PostgreSQL dump that mentions MySQL syntax only inside comments and strings
+    let sql = "-- converted from MySQL: ENGINE=InnoDB, `backticks` removed
+        CREATE TABLE users (id SERIAL PRIMARY KEY, note TEXT DEFAULT 'AUTO_INCREMENT', tags TEXT[]);
+        CREATE TABLE audit (id BIGSERIAL, payload JSONB, at TIMESTAMPTZ);";
+    let ranking = DialectDetector::new().rank(sql);
+    assert_eq!(ranking.len(), 1, "{:?}", ranking);
+    assert_eq!(ranking[0].dialect, SqlDialect::PostgreSQL);
+    assert!(ranking[0].signals.contains(&"SERIAL columns"));
+    assert!(ranking[0].confidence > 0.75, "{:?}", ranking);
+
+    let mixed = DialectDetector::new().rank("CREATE TABLE `t` (id SERIAL);");
+    assert_eq!(mixed.len(), 2);
+    assert!(mixed.iter().all(|guess| guess.confidence < 0.5), "{:?}", mixed);
+}
+
+#[test]
+fn test_detect_plain_sql_is_undecided() {
+    assert!(DialectDetector::new().detect("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(50));").is_none());
+}
@@ -0,0 +1,336 @@
+use sql2doc::core::analyze::{
+    ChangeAction, DiffAttribute, DiffObject, SchemaChange, SchemaDiff, SchemaDiffer, SchemaSnapshot, SnapshotError,
+};
+use sql2doc::core::docs::{ApiCompatibility, ApiImpact, ClassifiedChange, CompatibilityReport, SchemaDiffRenderer, VersionBump};
+use sql2doc::core::domain::entity::Database;
+use sql2doc::core::domain::triggers::{Trigger, TriggerEvent, TriggerMetadata, TriggerScope, TriggerTiming};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+const OLD: &str = "
+CREATE TYPE order_status AS ENUM ('pending', 'shipped');
+CREATE TABLE customers (id INT PRIMARY KEY, full_name TEXT NOT NULL, region TEXT, created_at TIMESTAMP);
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    customer_id INT REFERENCES customers (id),
+    status order_status NOT NULL,
+    amount INT,
+    note TEXT,
+    CONSTRAINT amount_positive CHECK (amount > 0)
+);
+CREATE TABLE legacy_events (id INT, payload TEXT);
+CREATE INDEX orders_customer_idx ON orders (customer_id);
+CREATE VIEW order_totals AS SELECT customer_id, SUM(amount) AS total FROM orders GROUP BY customer_id;
+CREATE VIEW recent AS SELECT id FROM orders;
+";
+
+const NEW: &str = "
+CREATE TYPE order_status AS ENUM ('pending', 'shipped', 'cancelled');
+CREATE TABLE clients (id INT PRIMARY KEY, full_name TEXT NOT NULL, region TEXT, created_at TIMESTAMP);
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    client_id INT REFERENCES clients (id),
+    status order_status NOT NULL,
+    amount BIGINT NOT NULL DEFAULT 0,
+    placed_at TIMESTAMP,
+    CONSTRAINT amount_positive CHECK (amount >= 0)
+);
+CREATE INDEX orders_client_idx ON orders (client_id);
+CREATE VIEW order_totals AS
+    SELECT client_id, SUM(amount) AS total
+    FROM orders
+    GROUP BY client_id;
+CREATE VIEW latest AS SELECT id FROM orders;
+";
+
+fn lower(sql: &str) -> Database {
+    Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("schema should lower").database
+}
+
+fn diff(old: &str, new: &str) -> SchemaDiff {
+    SchemaDiffer::new(&lower(old), &lower(new)).diff()
+}
+
+fn find<'d>(diff: &'d SchemaDiff, action: ChangeAction, object: DiffObject, name: &str) -> Option<&'d SchemaChange> {
+    diff.changes.iter().find(|change| change.action == action && change.object == object && change.name == name)
+}
+
+fn lines(diff: &SchemaDiff) -> Vec<String> {
+    diff.changes.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_diff_detects_renames() {
+    let diff = diff(OLD, NEW);
+
+    let table = find(&diff, ChangeAction::Renamed, DiffObject::Table, "clients").expect("customers should be renamed");
+    assert_eq!(table.old.as_deref(), Some("customers"));
+    let column = find(&diff, ChangeAction::Renamed, DiffObject::Column, "orders.client_id").expect("column rename");
+    assert_eq!(column.old.as_deref(), Some("orders.customer_id"));
+    // An index that kept its definition through the column rename was only renamed itself
+    let index = find(&diff, ChangeAction::Renamed, DiffObject::Index, "orders.orders_client_idx").expect("index rename");
+    assert_eq!(index.old.as_deref(), Some("orders.orders_customer_idx"));
+    let view = find(&diff, ChangeAction::Renamed, DiffObject::View, "latest").expect("view rename");
+    assert_eq!(view.old.as_deref(), Some("recent"));
+
+    // The foreign key, read through both renames, is unchanged
+    assert!(diff.changes.iter().all(|change| change.object != DiffObject::ForeignKey));
+    assert!(diff.changes.iter().all(|change| change.object != DiffObject::PrimaryKey));
+    // A column unlike any new one is dropped rather than renamed
+    assert!(find(&diff, ChangeAction::Removed, DiffObject::Column, "orders.note").is_some());
+    assert!(find(&diff, ChangeAction::Added, DiffObject::Column, "orders.placed_at").is_some());
+    assert!(find(&diff, ChangeAction::Removed, DiffObject::Table, "legacy_events").is_some());
+}
+
+#[test]
+fn test_diff_detects_modifications() {
+    let diff = diff(OLD, NEW);
+    let amount: Vec<(Option<DiffAttribute>, Option<&str>, Option<&str>)> = diff
+        .changes
+        .iter()
+        .filter(|change| change.name == "orders.amount")
+        .map(|change| (change.attribute, change.old.as_deref(), change.new.as_deref()))
+        .collect();
+    assert_eq!(
+        amount,
+        vec![
+            (Some(DiffAttribute::Type), Some("INTEGER"), Some("BIGINT")),
+            (Some(DiffAttribute::Nullability), Some("NULL"), Some("NOT NULL")),
+            (Some(DiffAttribute::Default), None, Some("0")),
+        ]
+    );
+
+    let check = find(&diff, ChangeAction::Modified, DiffObject::Check, "orders.amount_positive").expect("check change");
+    assert_eq!(check.new.as_deref(), Some("CHECK (amount >= 0)"));
+    let status = find(&diff, ChangeAction::Modified, DiffObject::Type, "order_status").expect("enum change");
+    assert_eq!(status.new.as_deref(), Some("ENUM ('pending', 'shipped', 'cancelled')"));
+    // Layout aside, the view now reads a different column
+    assert!(find(&diff, ChangeAction::Modified, DiffObject::View, "order_totals").is_some());
+
+    // Re-indenting a view or reordering nothing is no change at all
+    let reformatted = NEW.replace("    FROM orders\n    GROUP BY", " FROM orders GROUP BY");
+    assert!(SchemaDiffer::new(&lower(NEW), &lower(&reformatted)).diff().is_empty());
+}
+
+#[test]
+fn test_diff_of_migrations() {
+    let migrated = [
+        OLD,
+        "ALTER TABLE customers RENAME TO clients;
+         ALTER TABLE orders RENAME COLUMN customer_id TO client_id;
+         ALTER TABLE orders ALTER COLUMN amount TYPE BIGINT;
+         ALTER TABLE orders DROP COLUMN note;",
+    ]
+    .concat();
+    let diff = diff(OLD, &migrated);
+    assert_eq!(
+        lines(&diff),
+        vec![
+            "renamed table customers to clients",
+            "renamed column orders.customer_id to orders.client_id",
+            "changed the type of column orders.amount from INTEGER to BIGINT",
+            "removed column orders.note",
+        ]
+    );
+
+    // Triggers are compared by their timing, events and body
+    let mut old = lower(OLD);
+    let mut new = lower(OLD);
+    let trigger = |body: &str| Trigger {
+        name: "orders_audit".to_string(),
+        table_name: "orders".to_string(),
+        timing: TriggerTiming::After,
+        events: vec![TriggerEvent::Update],
+        update_columns: Vec::new(),
+        scope: TriggerScope::Row,
+        definition: body.to_string(),
+        metadata: TriggerMetadata::default(),
+    };
+    for (database, body) in [(&mut old, "EXECUTE FUNCTION audit()"), (&mut new, "EXECUTE FUNCTION audit_v2()")] {
+        if let Some(orders) = database.schemas[0].tables.iter_mut().find(|table| table.name == "orders") {
+            orders.triggers.push(trigger(body));
+        }
+    }
+    let diff = SchemaDiffer::new(&old, &new).diff();
+    assert_eq!(lines(&diff), vec!["changed the definition of trigger orders.orders_audit"]);
+}
+
+#[test]
+fn test_render_diff() {
+    let diff = diff(OLD, NEW);
+
+    let text = SchemaDiffRenderer::new().text(&diff);
+    assert!(text.starts_with(&format!("{} schema changes:\n", diff.changes.len())));
+    assert!(text.contains("  > renamed table customers to clients\n"));
+    assert!(text.contains("  + added column orders.placed_at TIMESTAMP\n"));
+    assert!(text.contains("  - removed table legacy_events\n"));
+    assert!(text.contains("  ~ changed the definition of view order_totals\n"));
+
+    let markdown = SchemaDiffRenderer::new().markdown(&diff);
+    assert!(markdown.contains("| Change | Object | Name | Details |\n"));
+    assert!(markdown.contains("| modified | column | `orders.amount` | type: `INTEGER` → `BIGINT` |\n"));
+    assert!(markdown.contains("| renamed | table | `clients` | from `customers` |\n"));
+
+    let json = serde_json::to_value(&diff).expect("diff should serialize");
+    let renamed = &json["changes"][1];
+    assert_eq!(renamed["action"], "renamed");
+    assert_eq!(renamed["object"], "table");
+    assert_eq!(renamed["old"], "customers");
+    assert!(renamed.get("attribute").is_none());
+
+    let empty = SchemaDiffer::new(&lower(OLD), &lower(OLD)).diff();
+    assert_eq!(SchemaDiffRenderer::new().text(&empty), "No schema changes.\n");
+}
+
+fn classify(old: &str, new: &str) -> CompatibilityReport {
+    let (old, new) = (lower(old), lower(new));
+    let diff = SchemaDiffer::new(&old, &new).diff();
+    ApiCompatibility::new(&old, &new).classify(&diff)
+}
+
+fn impacts(report: &CompatibilityReport) -> Vec<(String, ApiImpact)> {
+    report.changes.iter().map(|change: &ClassifiedChange| (change.change.to_string(), change.impact)).collect()
+}
+
+#[test]
+fn test_classify_api_changes() {
+    let report = classify(
+        "CREATE TYPE mood AS ENUM ('happy', 'sad');
+         CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(200), nickname VARCHAR(50), bio TEXT NOT NULL,
+                             mood mood, age INT NOT NULL, legacy TEXT);",
+        "CREATE TYPE mood AS ENUM ('happy', 'sad', 'meh');
+         CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(100) NOT NULL, nickname VARCHAR(80), bio TEXT,
+                             mood mood, age INT NOT NULL DEFAULT 0, created_at TIMESTAMP, tenant INT NOT NULL);
+         CREATE INDEX users_email ON users (email);",
+    );
+    assert_eq!(
+        impacts(&report),
+        vec![
+            (
+                "changed the definition of type mood from ENUM ('happy', 'sad') to ENUM ('happy', 'sad', 'meh')".to_string(),
+                ApiImpact::NonBreaking
+            ),
+            ("changed the type of column users.email from VARCHAR(200) to VARCHAR(100)".to_string(), ApiImpact::Breaking),
+            ("changed the nullability of column users.email from NULL to NOT NULL".to_string(), ApiImpact::Breaking),
+            (
+                "changed the type of column users.nickname from VARCHAR(50) to VARCHAR(80)".to_string(),
+                ApiImpact::NonBreaking
+            ),
+            ("changed the nullability of column users.bio from NOT NULL to NULL".to_string(), ApiImpact::Breaking),
+            (
+                "changed the type of column users.mood from ENUM('happy', 'sad') to ENUM('happy', 'sad', 'meh')".to_string(),
+                ApiImpact::Additive
+            ),
+            ("changed the default of column users.age from none to 0".to_string(), ApiImpact::NonBreaking),
+            ("added column users.created_at TIMESTAMP".to_string(), ApiImpact::Additive),
+            ("added column users.tenant INTEGER NOT NULL".to_string(), ApiImpact::Breaking),
+            ("removed column users.legacy".to_string(), ApiImpact::Breaking),
+            ("added index users.users_email INDEX USING btree (email)".to_string(), ApiImpact::NonBreaking),
+        ]
+    );
+    assert_eq!(report.bump, VersionBump::Major);
+    assert_eq!(report.changes[1].reason, "maxLength tightens from 200 to 100");
+    assert_eq!(report.changes[2].reason, "the property becomes required in requests");
+
+    // Only additions call for a minor version, and no changes for none
+    let report = classify("CREATE TABLE t (id INT);", "CREATE TABLE t (id INT, note TEXT); CREATE TABLE u (id INT);");
+    assert_eq!(report.bump, VersionBump::Minor);
+    let report = classify("CREATE TABLE t (id INT);", "CREATE TABLE t (id INT); CREATE INDEX t_id ON t (id);");
+    assert_eq!(report.bump, VersionBump::Patch);
+    assert_eq!(classify("CREATE TABLE t (id INT);", "CREATE TABLE t (id INT);").bump, VersionBump::None);
+}
+
+#[test]
+fn test_approve_breaking_changes() {
+    let report = classify(OLD, NEW);
+    let unapproved = |approvals: &[&str]| -> Vec<String> {
+        let approvals: Vec<String> = approvals.iter().map(ToString::to_string).collect();
+        report.unapproved(&approvals).iter().map(|change| change.change.name.clone()).collect()
+    };
+    assert_eq!(
+        unapproved(&[]),
+        vec!["clients", "orders.client_id", "orders.amount", "orders.note", "legacy_events"]
+    );
+    // A table approves its columns, and a rename may be approved by its old name
+    assert_eq!(unapproved(&["ORDERS", "customers"]), vec!["legacy_events"]);
+    assert!(unapproved(&["orders", "clients", "legacy_events"]).is_empty());
+    assert_eq!(unapproved(&["order"]).len(), 5);
+
+    let text = SchemaDiffRenderer::new().report_text(&report);
+    assert!(text.starts_with("Recommended version bump: major (5 breaking, 2 additive, 7 non-breaking)\n\nBreaking:\n"));
+    assert!(text.contains("  - removed column orders.note: the property is no longer returned\n"));
+    assert!(text.contains("\nAdditive:\n  ~ changed the type of column orders.status"));
+    assert!(text.contains("  + added column orders.placed_at TIMESTAMP: a new optional property\n"));
+    let markdown = SchemaDiffRenderer::new().report_markdown(&report);
+    assert!(markdown.contains("**Recommended version bump:** major"));
+    assert!(markdown.contains("| removed | column | `orders.note` |  | **breaking**: the property is no longer returned |\n"));
+
+    let json = serde_json::to_value(&report).expect("report should serialize");
+    assert_eq!(json["bump"], "major");
+    assert_eq!(json["changes"][1]["action"], "renamed");
+    assert_eq!(json["changes"][1]["impact"], "breaking");
+    assert_eq!(json["changes"][0]["impact"], "non-breaking");
+}
+
+#[test]
+fn test_approvals_follow_renames() {
+    let report = classify(
+        "CREATE TABLE customer_accounts (id INT PRIMARY KEY, email VARCHAR(200), full_name TEXT, region TEXT,
+                                         note TEXT, created_at TIMESTAMP, updated_at TIMESTAMP);",
+        "CREATE TABLE client_accounts (id INT PRIMARY KEY, email VARCHAR(100), full_name TEXT, region TEXT,
+                                       created_at TIMESTAMP, updated_at TIMESTAMP);
+         CREATE INDEX client_accounts_region ON client_accounts (region);",
+    );
+    let unapproved = |approvals: &[&str]| -> Vec<String> {
+        let approvals: Vec<String> = approvals.iter().map(ToString::to_string).collect();
+        report.unapproved(&approvals).iter().map(|change| change.change.to_string()).collect()
+    };
+    assert_eq!(
+        unapproved(&[]),
+        vec![
+            "renamed table customer_accounts to client_accounts",
+            "changed the type of column client_accounts.email from VARCHAR(200) to VARCHAR(100)",
+            "removed column client_accounts.note",
+        ]
+    );
+    // The old name of the table covers its columns, which the diff names under the new one
+    assert!(unapproved(&["customer_accounts"]).is_empty());
+    assert_eq!(
+        unapproved(&["Customer_Accounts.email"]),
+        vec!["renamed table customer_accounts to client_accounts", "removed column client_accounts.note"]
+    );
+    assert_eq!(unapproved(&["customer"]).len(), 3);
+
+    let index = report.changes.iter().find(|change| change.change.object == DiffObject::Index).expect("index change");
+    assert_eq!(index.reason, "indexes are not part of a row schema");
+}
+
+#[test]
+fn test_snapshot_is_canonical() {
+    // The same schema declared in another order, as migrations would leave it
+    let reordered = "
+        CREATE TABLE legacy_events (id INT, payload TEXT);
+        CREATE TYPE order_status AS ENUM ('pending', 'shipped');
+        CREATE TABLE customers (id INT PRIMARY KEY, full_name TEXT NOT NULL, region TEXT, created_at TIMESTAMP);
+        CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT, status order_status NOT NULL, amount INT, note TEXT);
+        CREATE VIEW recent AS SELECT id FROM orders;
+        CREATE VIEW order_totals AS SELECT customer_id, SUM(amount) AS total FROM orders GROUP BY customer_id;
+        ALTER TABLE orders ADD CONSTRAINT amount_positive CHECK (amount > 0);
+        ALTER TABLE orders ADD FOREIGN KEY (customer_id) REFERENCES customers (id);
+        CREATE INDEX orders_customer_idx ON orders (customer_id);";
+    let json = SchemaSnapshot::new(&lower(OLD)).to_json().expect("snapshot should serialize");
+    let reordered_json = SchemaSnapshot::new(&lower(reordered)).to_json().expect("snapshot should serialize");
+    assert_eq!(json, reordered_json);
+    assert!(json.starts_with("{\n  \"database\": {"));
+    assert!(json.ends_with("\n  \"version\": 1\n}\n"));
+
+    // It reads back as the same schema, in name order
+    let snapshot = SchemaSnapshot::from_json(&json).expect("snapshot should load");
+    let tables: Vec<&str> = snapshot.database.schemas[0].tables.iter().map(|table| table.name.as_str()).collect();
+    assert_eq!(tables, vec!["customers", "legacy_events", "orders"]);
+    assert!(SchemaDiffer::new(&snapshot.database, &lower(OLD)).diff().is_empty());
+    assert_eq!(snapshot.to_json().expect("snapshot should serialize"), json);
+
+    let newer = json.replace("\"version\": 1", "\"version\": 99");
+    assert!(matches!(SchemaSnapshot::from_json(&newer), Err(SnapshotError::Version { found: 99 })));
+}
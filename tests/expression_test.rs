@@ -0,0 +1,251 @@
+use sql2doc::core::ast::*;
+use sql2doc::core::parse::{Lexer, Parser};
+
+fn parse_select(sql: &str) -> SelectStatement {
+    let tokens = Lexer::new(sql).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    match statements.into_iter().next() {
+        Some(Statement::Dml(dml)) => match *dml {
+            DmlStatement::Select(select) => *select,
+            other => panic!("Expected Select statement, got {:?}", other),
+        },
+        other => panic!("Expected Select statement, got {:?}", other),
+    }
+}
+
+fn where_clause(sql: &str) -> Expression {
+    parse_select(sql).where_clause.expect("query should have a WHERE clause")
+}
+
+fn first_item(sql: &str) -> Expression {
+    match parse_select(sql).select_list.into_iter().next() {
+        Some(SelectItem::Expression { expr, .. }) => expr,
+        other => panic!("Expected expression select item, got {:?}", other),
+    }
+}
+
+fn col(name: &str) -> Box<Expression> {
+    Box::new(Expression::column(name))
+}
+
+fn string(value: &str) -> Box<Expression> {
+    Box::new(Expression::Literal(LiteralValue::String(value.to_string())))
+}
+
+#[test]
+fn test_double_colon_casts() {
+    assert_eq!(
+        first_item("SELECT price::numeric(10, 2) FROM items"),
+        Expression::Cast { expr: col("price"), data_type: "numeric(10, 2)".to_string() }
+    );
+    assert_eq!(
+        first_item("SELECT '2024-01-01'::timestamp with time zone FROM items"),
+        Expression::Cast { expr: string("2024-01-01"), data_type: "timestamp with time zone".to_string() }
+    );
+    assert_eq!(
+        first_item("SELECT tags::text[] FROM items"),
+        Expression::Cast { expr: col("tags"), data_type: "text[]".to_string() }
+    );
+    assert_eq!(
+        first_item("SELECT CAST(total AS DOUBLE PRECISION) FROM items"),
+        Expression::Cast { expr: col("total"), data_type: "DOUBLE PRECISION".to_string() }
+    );
+}
+
+#[test]
+fn test_is_predicates() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE deleted_at IS NULL"),
+        Expression::IsNull { expr: col("deleted_at"), negated: false }
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE deleted_at IS NOT NULL"),
+        Expression::IsNull { expr: col("deleted_at"), negated: true }
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE a IS NOT DISTINCT FROM b"),
+        Expression::IsDistinctFrom { left: col("a"), right: col("b"), negated: true }
+    );
+}
+
+#[test]
+fn test_is_true_and_false_are_not_comparisons() {
+    // `NULL <> TRUE` is NULL, but `NULL IS NOT TRUE` holds, so these aren't lowered to `=`/`<>`
+    let null = || Box::new(Expression::Literal(LiteralValue::Null));
+    assert_eq!(first_item("SELECT NULL IS NOT TRUE"), Expression::IsTrue { expr: null(), negated: true });
+    assert_eq!(first_item("SELECT NULL IS FALSE"), Expression::IsFalse { expr: null(), negated: false });
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE active IS TRUE AND archived IS NOT FALSE"),
+        Expression::binary_op(
+            Expression::IsTrue { expr: col("active"), negated: false },
+            BinaryOperator::And,
+            Expression::IsFalse { expr: col("archived"), negated: true },
+        )
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE flag IS NOT UNKNOWN"),
+        Expression::IsNull { expr: col("flag"), negated: true }
+    );
+    assert!(Parser::new(Lexer::new("SELECT * FROM t WHERE a IS 5").tokenize().expect("input should tokenize")).parse().is_err());
+}
+
+#[test]
+fn test_negated_predicates() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE status NOT IN ('a', 'b')"),
+        Expression::NotIn { expr: col("status"), values: vec![*string("a"), *string("b")] }
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE name NOT LIKE 'x%'"),
+        Expression::binary_op(Expression::column("name"), BinaryOperator::NotLike, *string("x%"))
+    );
+    assert!(matches!(
+        where_clause("SELECT * FROM t WHERE qty NOT BETWEEN 1 AND 10"),
+        Expression::NotBetween { .. }
+    ));
+    assert!(matches!(
+        where_clause("SELECT * FROM t WHERE NOT active"),
+        Expression::UnaryOp { op: UnaryOperator::Not, .. }
+    ));
+}
+
+#[test]
+fn test_like_escape() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE code LIKE '10!%%' ESCAPE '!' AND ok"),
+        Expression::binary_op(
+            Expression::LikeEscape {
+                expr: col("code"),
+                op: BinaryOperator::Like,
+                pattern: string("10!%%"),
+                escape: string("!"),
+            },
+            BinaryOperator::And,
+            Expression::column("ok"),
+        )
+    );
+}
+
+#[test]
+fn test_json_operators_and_subscripts() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE payload->'user'->>'name' = 'bob'"),
+        Expression::binary_op(
+            Expression::binary_op(
+                Expression::binary_op(Expression::column("payload"), BinaryOperator::JsonGet, *string("user")),
+                BinaryOperator::JsonGetText,
+                *string("name"),
+            ),
+            BinaryOperator::Eq,
+            *string("bob"),
+        )
+    );
+    assert!(matches!(
+        first_item("SELECT payload #> '{a,b}' FROM t"),
+        Expression::BinaryOp { op: BinaryOperator::JsonPath, .. }
+    ));
+    assert_eq!(
+        first_item("SELECT tags[1] FROM t"),
+        Expression::Subscript { expr: col("tags"), index: Box::new(Expression::Literal(LiteralValue::Integer(1))) }
+    );
+    assert_eq!(
+        first_item("SELECT ARRAY['a', 'b'] FROM t"),
+        Expression::Array(vec![*string("a"), *string("b")])
+    );
+}
+
+#[test]
+fn test_json_operators_and_concatenation_share_a_level() {
+    // `->>`, `||` and the other JSON operators group from the left, below `+` and above `=`
+    let get_text = |key: &str| Expression::binary_op(Expression::column("data"), BinaryOperator::JsonGetText, *string(key));
+    assert_eq!(
+        first_item("SELECT data->>'first' || ' ' || data->>'last' FROM t"),
+        Expression::binary_op(
+            Expression::binary_op(
+                Expression::binary_op(get_text("first"), BinaryOperator::Concat, *string(" ")),
+                BinaryOperator::Concat,
+                Expression::column("data"),
+            ),
+            BinaryOperator::JsonGetText,
+            *string("last"),
+        )
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE name || '-' || id + 1 = 'a-2'"),
+        Expression::binary_op(
+            Expression::binary_op(
+                Expression::binary_op(Expression::column("name"), BinaryOperator::Concat, *string("-")),
+                BinaryOperator::Concat,
+                Expression::binary_op(
+                    Expression::column("id"),
+                    BinaryOperator::Plus,
+                    Expression::Literal(LiteralValue::Integer(1)),
+                ),
+            ),
+            BinaryOperator::Eq,
+            *string("a-2"),
+        )
+    );
+    assert_eq!(
+        first_item("SELECT (data->>'first') || ' ' || (data->>'last') FROM t"),
+        Expression::binary_op(
+            Expression::binary_op(get_text("first"), BinaryOperator::Concat, *string(" ")),
+            BinaryOperator::Concat,
+            get_text("last"),
+        )
+    );
+}
+
+#[test]
+fn test_is_binds_looser_than_comparisons() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE a = b IS NOT TRUE"),
+        Expression::IsTrue {
+            expr: Box::new(Expression::binary_op(Expression::column("a"), BinaryOperator::Eq, Expression::column("b"))),
+            negated: true,
+        }
+    );
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE a < b IS NULL AND c"),
+        Expression::binary_op(
+            Expression::IsNull {
+                expr: Box::new(Expression::binary_op(Expression::column("a"), BinaryOperator::Lt, Expression::column("b"))),
+                negated: false,
+            },
+            BinaryOperator::And,
+            Expression::column("c"),
+        )
+    );
+}
+
+#[test]
+fn test_bind_parameters() {
+    assert_eq!(
+        where_clause("SELECT * FROM t WHERE id = $1 AND name = :name AND age > ?"),
+        Expression::binary_op(
+            Expression::binary_op(
+                Expression::binary_op(
+                    Expression::column("id"),
+                    BinaryOperator::Eq,
+                    Expression::Parameter("$1".to_string()),
+                ),
+                BinaryOperator::And,
+                Expression::binary_op(
+                    Expression::column("name"),
+                    BinaryOperator::Eq,
+                    Expression::Parameter(":name".to_string()),
+                ),
+            ),
+            BinaryOperator::And,
+            Expression::binary_op(Expression::column("age"), BinaryOperator::Gt, Expression::Parameter("?".to_string())),
+        )
+    );
+}
+
+#[test]
+fn test_count_star() {
+    assert_eq!(
+        first_item("SELECT count(*) FROM t"),
+        Expression::function("count", vec![Expression::Wildcard])
+    );
+}
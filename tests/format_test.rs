@@ -0,0 +1,102 @@
+use sql2doc::core::format::{FormatStyle, Formatter, KeywordCase};
+use sql2doc::core::parse::SqlDialect;
+
+fn format(sql: &str) -> String {
+    Formatter::new(FormatStyle::default()).format(sql).expect("input should format")
+}
+
+#[test]
+fn test_format_create_table_aligns_columns() {
+    let formatted = format(
+        "create table users (id int not null, -- key\n  email varchar(255), primary key (id)) engine=InnoDB;",
+    );
+
+    assert_eq!(
+        formatted,
+        "CREATE TABLE users (\n    \
+            id    INT NOT NULL, -- key\n    \
+            email VARCHAR(255),\n    \
+            PRIMARY KEY (id)\n\
+        ) engine = InnoDB;\n"
+    );
+}
+
+#[test]
+fn test_format_keeps_comments_with_statements() {
+    let formatted = format(
+        "-- header\n\n/* users */\nselect id, name from users where id = 1; -- one user\nselect 1;",
+    );
+
+    assert_eq!(
+        formatted,
+        "-- header\n\n/* users */\nSELECT\n    id,\n    name\nFROM users\nWHERE id = 1; -- one user\n\nSELECT 1;\n"
+    );
+}
+
+#[test]
+fn test_format_is_idempotent() {
+    let sql = "create table t (\n  -- the id\n  id bigint,\n  label text default 'x' -- shown\n);\n\
+               update t set label = 'y', id = -1 where id between 1 and 2 and label is not null;\n";
+    let once = format(sql);
+
+    assert_eq!(format(&once), once);
+    assert!(Formatter::new(FormatStyle::default()).is_formatted(&once).expect("input should format"));
+}
+
+#[test]
+fn test_format_style_options() {
+    let style = FormatStyle {
+        keyword_case: KeywordCase::Lower,
+        indent_width: 2,
+        align_column_types: false,
+    };
+    let formatted = Formatter::new(style)
+        .format("CREATE TABLE t (id INT NOT NULL, name VARCHAR(10));")
+        .expect("input should format");
+
+    assert_eq!(formatted, "create table t (\n  id int not null,\n  name varchar(10)\n);\n");
+}
+
+#[test]
+fn test_format_leaves_unparsed_statements_as_written() {
+    let formatted = format("grant   select on t to reporting; -- later\n");
+
+    assert_eq!(formatted, "grant   select on t to reporting; -- later\n");
+}
+
+#[test]
+fn test_format_upsert_and_returning() {
+    let formatted = format(
+        "insert into hits (path) values ('/') on conflict (path) do update set n = hits.n + 1 returning n;",
+    );
+
+    assert_eq!(
+        formatted,
+        "INSERT INTO hits (path)\nVALUES ('/')\nON CONFLICT (path) DO UPDATE SET n = hits.n + 1\nRETURNING n;\n"
+    );
+}
+
+#[test]
+fn test_format_keeps_dollar_quoted_bodies() {
+    let formatted = format("select $$it's\n  a body$$ as body, 'x' from t;");
+
+    assert_eq!(formatted, "SELECT\n    $$it's\n  a body$$ AS body,\n    'x'\nFROM t;\n");
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn test_format_mssql_brackets_and_batches() {
+    let formatter = Formatter::new(FormatStyle::default()).with_dialect(SqlDialect::MSSQL);
+    let formatted = formatter
+        .format("create table [dbo].[Order Lines] ([id] int not null)\ngo\nselect [id] from [dbo].[Order Lines];\nGO\n")
+        .expect("input should format");
+
+    assert_eq!(
+        formatted,
+        "CREATE TABLE [dbo].[Order Lines] (\n    \
+            [id] INT NOT NULL\n\
+        )\nGO\n\n\
+        SELECT [id]\nFROM [dbo].[Order Lines];\nGO\n"
+    );
+    assert!(formatter.is_formatted(&formatted).expect("input should format"));
+}
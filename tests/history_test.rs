@@ -0,0 +1,206 @@
+use sql2doc::core::analyze::MigrationHistory;
+use sql2doc::core::docs::MarkdownRenderer;
+use sql2doc::core::domain::entity::{Database, MigrationEvent};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+fn replay(dialect: SqlDialect, migrations: &[(&str, &str)]) -> Database {
+    let migrations: Vec<_> = migrations
+        .iter()
+        .map(|(name, sql)| {
+            let tokens = Lexer::for_dialect(sql, &dialect).tokenize().expect("migration should tokenize");
+            (name.to_string(), Parser::new(tokens).parse().expect("migration should parse"))
+        })
+        .collect();
+    MigrationHistory::new(dialect).lower(&migrations).database
+}
+
+fn describe(events: &[MigrationEvent]) -> Vec<String> {
+    events.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_history_follows_renames_and_alterations() {
+    let database = replay(
+        SqlDialect::PostgreSQL,
+        &[
+            (
+                "V1__init.sql",
+                "CREATE TABLE customers (id INT PRIMARY KEY, email VARCHAR(100) NOT NULL UNIQUE);
+                 CREATE TABLE orders (id INT PRIMARY KEY, customer INT REFERENCES customers (id), amount INT);
+                 CREATE TABLE scratch (id INT);",
+            ),
+            (
+                "V2__billing.sql",
+                "ALTER TABLE orders ADD COLUMN billing_ref TEXT;
+                 CREATE INDEX orders_billing ON orders (billing_ref);
+                 ALTER TABLE orders RENAME COLUMN customer TO customer_id;
+                 DROP TABLE scratch;",
+            ),
+            (
+                "V10__widen.sql",
+                "ALTER TABLE orders ALTER COLUMN amount TYPE BIGINT, ALTER COLUMN amount SET NOT NULL;
+                 ALTER TABLE customers RENAME TO clients;
+                 ALTER TABLE orders ADD CONSTRAINT amount_positive CHECK (amount > 0);
+                 CREATE TABLE scratch (id INT, note TEXT);",
+            ),
+        ],
+    );
+
+    let clients = database.get_table(None, "clients").expect("clients should exist");
+    assert_eq!(describe(&clients.metadata.history), vec!["added in V1__init.sql", "renamed from customers in V10__widen.sql"]);
+    assert_eq!(describe(&clients.unique_constraints[0].metadata.history), vec!["added in V1__init.sql"]);
+
+    let orders = database.get_table(None, "orders").expect("orders should exist");
+    let column = |name: &str| describe(&orders.get_column(name).expect("column should exist").metadata.history);
+    assert_eq!(column("customer_id"), vec!["added in V1__init.sql", "renamed from customer in V2__billing.sql"]);
+    assert_eq!(
+        column("amount"),
+        vec!["added in V1__init.sql", "type changed in V10__widen.sql", "made NOT NULL in V10__widen.sql"]
+    );
+    assert_eq!(column("billing_ref"), vec!["added in V2__billing.sql"]);
+    assert_eq!(describe(&orders.indexes[0].metadata.history), vec!["added in V2__billing.sql"]);
+    assert_eq!(describe(&orders.foreign_keys[0].metadata.history), vec!["added in V1__init.sql"]);
+    assert_eq!(describe(&orders.check_constraints[0].metadata.history), vec!["added in V10__widen.sql"]);
+
+    // A table dropped and created again starts over
+    let scratch = database.get_table(None, "scratch").expect("scratch should exist");
+    assert_eq!(describe(&scratch.metadata.history), vec!["added in V10__widen.sql"]);
+
+    let markdown = MarkdownRenderer::new().render(&database);
+    assert!(markdown.contains("**History:** added in `V1__init.sql`, renamed from customers in `V10__widen.sql`\n"));
+    assert!(markdown.contains("| added in `V2__billing.sql` |\n"), "{}", markdown);
+    assert!(markdown.contains("- `orders_billing` on `billing_ref`, added in `V2__billing.sql`\n"));
+}
+
+#[test]
+fn test_history_of_mysql_column_redefinitions() {
+    let database = replay(
+        SqlDialect::MySQL,
+        &[
+            ("001_users.sql", "CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(50), plan VARCHAR(10));"),
+            (
+                "002_rename.sql",
+                "ALTER TABLE users CHANGE name full_name VARCHAR(50);
+                 ALTER TABLE users MODIFY plan VARCHAR(10) NOT NULL DEFAULT 'free';",
+            ),
+            (
+                "003_widen.sql",
+                "ALTER TABLE users MODIFY full_name VARCHAR(200), MODIFY plan VARCHAR(10) NOT NULL DEFAULT 'free';",
+            ),
+        ],
+    );
+
+    let users = database.get_table(None, "users").expect("users should exist");
+    let column = |name: &str| describe(&users.get_column(name).expect("column should exist").metadata.history);
+    assert_eq!(
+        column("full_name"),
+        vec!["added in 001_users.sql", "renamed from name in 002_rename.sql", "type changed in 003_widen.sql"]
+    );
+    // Restating a column as it already stands changes nothing
+    assert_eq!(
+        column("plan"),
+        vec!["added in 001_users.sql", "default changed in 002_rename.sql", "made NOT NULL in 002_rename.sql"]
+    );
+
+    // A schema lowered on its own has no history, which stays out of the serialized model
+    let database = Lowerer::new(SqlDialect::MySQL).lower("CREATE TABLE users (id INT);").expect("schema should lower").database;
+    let json = serde_json::to_string(&database).expect("model should serialize");
+    assert!(!json.contains("history"));
+}
+
+#[test]
+fn test_history_follows_mysql_table_renames() {
+    let database = replay(
+        SqlDialect::MySQL,
+        &[
+            (
+                "001_init.sql",
+                "CREATE TABLE accounts (id INT PRIMARY KEY, login VARCHAR(50));
+                 CREATE TABLE accounts_new (id INT PRIMARY KEY, login VARCHAR(50), email VARCHAR(200));
+                 CREATE TABLE audit (id INT, body TEXT);
+                 CREATE INDEX audit_body ON audit (body(20));",
+            ),
+            // Swapping in a rebuilt table, as online schema change tools do
+            ("002_swap.sql", "RENAME TABLE accounts TO accounts_old, accounts_new TO accounts;"),
+            ("003_drop_old.sql", "DROP TABLE accounts_old;"),
+            (
+                "004_rename.sql",
+                "ALTER TABLE audit RENAME TO audit_log;
+                 ALTER TABLE audit_log RENAME COLUMN body TO payload;
+                 ALTER TABLE accounts RENAME AS users;",
+            ),
+        ],
+    );
+
+    assert!(database.get_table(None, "accounts_old").is_none());
+    let users = database.get_table(None, "users").expect("users should exist");
+    assert_eq!(
+        describe(&users.metadata.history),
+        vec!["added in 001_init.sql", "renamed from accounts_new in 002_swap.sql", "renamed from accounts in 004_rename.sql"]
+    );
+    // The swapped-in table brings its own columns' history
+    let email = users.get_column("email").expect("email should exist");
+    assert_eq!(describe(&email.metadata.history), vec!["added in 001_init.sql"]);
+
+    let audit = database.get_table(None, "audit_log").expect("audit_log should exist");
+    assert_eq!(describe(&audit.metadata.history), vec!["added in 001_init.sql", "renamed from audit in 004_rename.sql"]);
+    let payload = audit.get_column("payload").expect("payload should exist");
+    assert_eq!(describe(&payload.metadata.history), vec!["added in 001_init.sql", "renamed from body in 004_rename.sql"]);
+    // The index moves with the table and its column
+    assert_eq!(describe(&audit.indexes[0].metadata.history), vec!["added in 001_init.sql"]);
+}
+
+#[test]
+fn test_history_of_a_column_across_many_migrations() {
+    let database = replay(
+        SqlDialect::PostgreSQL,
+        &[
+            ("V1__init.sql", "CREATE TABLE invoices (id INT PRIMARY KEY, total INT, memo TEXT);"),
+            ("V2__rename.sql", "ALTER TABLE invoices RENAME COLUMN total TO amount;"),
+            ("V3__widen.sql", "ALTER TABLE invoices ALTER COLUMN amount TYPE BIGINT;"),
+            ("V4__default.sql", "ALTER TABLE invoices ALTER COLUMN amount SET DEFAULT 0;"),
+            ("V5__required.sql", "ALTER TABLE invoices ALTER COLUMN amount SET NOT NULL;"),
+            (
+                "V6__memo.sql",
+                "ALTER TABLE invoices DROP COLUMN memo;
+                 ALTER TABLE invoices RENAME TO bills;",
+            ),
+            (
+                "V7__relax.sql",
+                "ALTER TABLE bills ALTER COLUMN amount DROP NOT NULL, ALTER COLUMN amount DROP DEFAULT;
+                 ALTER TABLE bills ADD COLUMN memo VARCHAR(500);",
+            ),
+        ],
+    );
+
+    let bills = database.get_table(None, "bills").expect("bills should exist");
+    let column = |name: &str| describe(&bills.get_column(name).expect("column should exist").metadata.history);
+    assert_eq!(
+        column("amount"),
+        vec![
+            "added in V1__init.sql",
+            "renamed from total in V2__rename.sql",
+            "type changed in V3__widen.sql",
+            "default changed in V4__default.sql",
+            "made NOT NULL in V5__required.sql",
+            "made nullable in V7__relax.sql",
+            "default changed in V7__relax.sql",
+        ]
+    );
+    // A column dropped and added again starts over, while the untouched key keeps its first event
+    assert_eq!(column("memo"), vec!["added in V7__relax.sql"]);
+    assert_eq!(column("id"), vec!["added in V1__init.sql"]);
+    assert_eq!(describe(&bills.metadata.history), vec!["added in V1__init.sql", "renamed from invoices in V6__memo.sql"]);
+
+    let markdown = MarkdownRenderer::new().render(&database);
+    assert!(
+        markdown.contains(
+            "| added in `V1__init.sql`, renamed from total in `V2__rename.sql`, type changed in `V3__widen.sql`, \
+             default changed in `V4__default.sql`, made NOT NULL in `V5__required.sql`, made nullable in `V7__relax.sql`, \
+             default changed in `V7__relax.sql` |"
+        ),
+        "{}",
+        markdown
+    );
+}
@@ -1,9 +1,10 @@
 use sql2doc::core::parse::lexer::*;
+use sql2doc::core::parse::SqlDialect;
 
 #[test]
 fn test_basic_keywords() {
     let mut lexer = Lexer::new("SELECT FROM WHERE");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 3);
     assert_eq!(tokens[0].token_type, TokenType::Select);
@@ -14,7 +15,7 @@ fn test_basic_keywords() {
 #[test]
 fn test_identifiers() {
     let mut lexer = Lexer::new("users user_id \"quoted table\" `backtick table`");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 4);
     assert_eq!(tokens[0].token_type, TokenType::Identifier("users".to_string()));
@@ -26,7 +27,7 @@ fn test_identifiers() {
 #[test]
 fn test_literals() {
     let mut lexer = Lexer::new("123 45.67 'hello world' TRUE FALSE NULL");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].token_type, TokenType::IntegerLiteral(123));
@@ -40,7 +41,7 @@ fn test_literals() {
 #[test]
 fn test_operators() {
     let mut lexer = Lexer::new("= <> < <= > >= + - * / % || & | ^ ~ << >>");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 18);
     assert_eq!(tokens[0].token_type, TokenType::Equal);
@@ -66,7 +67,7 @@ fn test_operators() {
 #[test]
 fn test_punctuation() {
     let mut lexer = Lexer::new("( ) [ ] , ; . : :: ? @");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 11);
     assert_eq!(tokens[0].token_type, TokenType::LeftParen);
@@ -85,7 +86,7 @@ fn test_punctuation() {
 #[test]
 fn test_comments() {
     let mut lexer = Lexer::new("SELECT -- this is a comment\nFROM /* multi\nline\ncomment */ WHERE");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 3);
     assert_eq!(tokens[0].token_type, TokenType::Select);
@@ -96,14 +97,14 @@ fn test_comments() {
 #[test]
 fn test_case_insensitive_keywords() {
     let mut lexer = Lexer::new("select Select SELECT from From FROM");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 6);
-    for i in 0..3 {
-        assert_eq!(tokens[i].token_type, TokenType::Select);
+    for token in &tokens[0..3] {
+        assert_eq!(token.token_type, TokenType::Select);
     }
-    for i in 3..6 {
-        assert_eq!(tokens[i].token_type, TokenType::From);
+    for token in &tokens[3..6] {
+        assert_eq!(token.token_type, TokenType::From);
     }
 }
 
@@ -119,7 +120,7 @@ fn test_complex_sql() {
     "#;
 
     let mut lexer = Lexer::new(sql);
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     // Should have many tokens, let's check a few key ones
     let select_tokens: Vec<_> = tokens.iter().filter(|t| matches!(t.token_type, TokenType::Select)).collect();
@@ -153,7 +154,7 @@ fn test_complex_sql() {
 #[test]
 fn test_position_tracking() {
     let mut lexer = Lexer::new("SELECT\n  name\nFROM users");
-    let tokens = lexer.tokenize().unwrap();
+    let tokens = lexer.tokenize().expect("input should tokenize");
 
     assert_eq!(tokens.len(), 4);
 
@@ -181,3 +182,82 @@ fn test_error_handling() {
         assert!(error.message.contains("Unexpected"));
     }
 }
+
+#[test]
+fn test_comments_kept_on_request() {
+    let sql = "SELECT 1 -- one\n/* two */";
+
+    let skipped = Lexer::new(sql).tokenize().expect("input should tokenize");
+    assert_eq!(skipped.len(), 2);
+
+    let kept = Lexer::new(sql).with_comments().tokenize().expect("input should tokenize");
+    assert_eq!(kept.len(), 4);
+    assert_eq!(kept[2].token_type, TokenType::Comment("-- one".to_string()));
+    assert_eq!(kept[3].token_type, TokenType::Comment("/* two */".to_string()));
+    assert_eq!(kept[3].position.line, 2);
+}
+
+#[test]
+fn test_json_operators_and_parameters() {
+    let mut lexer = Lexer::new("-> ->> #> #>> $1 :name ? ::");
+    let tokens = lexer.tokenize().expect("input should tokenize");
+
+    assert_eq!(tokens[0].token_type, TokenType::Arrow);
+    assert_eq!(tokens[1].token_type, TokenType::LongArrow);
+    assert_eq!(tokens[2].token_type, TokenType::HashArrow);
+    assert_eq!(tokens[3].token_type, TokenType::HashLongArrow);
+    assert_eq!(tokens[4].token_type, TokenType::Parameter("$1".to_string()));
+    assert_eq!(tokens[5].token_type, TokenType::Parameter(":name".to_string()));
+    assert_eq!(tokens[6].token_type, TokenType::QuestionMark);
+    assert_eq!(tokens[7].token_type, TokenType::DoubleColon);
+}
+
+#[test]
+fn test_mysql_backslash_escapes_and_hex() {
+    let sql = r"'It\'s' 'a\nb' 'x\_y' 0x1F";
+
+    let tokens = Lexer::for_dialect(sql, &SqlDialect::MySQL).tokenize().expect("input should tokenize");
+    assert_eq!(tokens[0].token_type, TokenType::StringLiteral("It's".to_string()));
+    assert_eq!(tokens[1].token_type, TokenType::StringLiteral("a\nb".to_string()));
+    assert_eq!(tokens[2].token_type, TokenType::StringLiteral("x\\_y".to_string()));
+    assert_eq!(tokens[3].token_type, TokenType::HexLiteral("1F".to_string()));
+
+    // Standard SQL keeps backslashes as ordinary characters
+    let tokens = Lexer::new(r"'a\nb'").tokenize().expect("input should tokenize");
+    assert_eq!(tokens[0].token_type, TokenType::StringLiteral("a\\nb".to_string()));
+}
+
+#[test]
+fn test_mssql_brackets_and_batch_separators() {
+    let sql = "CREATE TABLE [dbo].[Order Lines]]]\n  GO\nSELECT a / b AS go FROM t\n";
+
+    let tokens = Lexer::for_dialect(sql, &SqlDialect::MSSQL).tokenize().expect("input should tokenize");
+    assert_eq!(tokens[2].token_type, TokenType::QuotedIdentifier("dbo".to_string()));
+    assert_eq!(tokens[4].token_type, TokenType::QuotedIdentifier("Order Lines]".to_string()));
+    assert_eq!(tokens[4].lexeme, "[Order Lines]]]");
+    assert_eq!(tokens[5].token_type, TokenType::Semicolon);
+    // Only a GO alone on its line ends a batch
+    assert_eq!(tokens[8].token_type, TokenType::Slash);
+    assert_eq!(tokens[11].token_type, TokenType::Identifier("go".to_string()));
+
+    let tokens = Lexer::for_dialect("CREATE SEQUENCE s\n/\n", &SqlDialect::Oracle).tokenize().expect("input should tokenize");
+    assert_eq!(tokens[3].token_type, TokenType::Semicolon);
+    assert_eq!(tokens[3].lexeme, "/");
+}
+
+#[test]
+fn test_dollar_quoted_strings() {
+    let sql = "$$ it's\n$1 $$ $body$ SELECT $$x$$; $body$ $1 $_x1$ $_x1$";
+
+    let tokens = Lexer::for_dialect(sql, &SqlDialect::PostgreSQL).tokenize().expect("input should tokenize");
+    assert_eq!(tokens[0].token_type, TokenType::StringLiteral(" it's\n$1 ".to_string()));
+    assert_eq!(tokens[0].to_sql(), "$$ it's\n$1 $$");
+    assert_eq!(tokens[1].token_type, TokenType::StringLiteral(" SELECT $$x$$; ".to_string()));
+    assert_eq!(tokens[1].lexeme, "$body$ SELECT $$x$$; $body$");
+    assert_eq!(tokens[1].position.line, 2);
+    assert_eq!(tokens[2].token_type, TokenType::Parameter("$1".to_string()));
+    assert_eq!(tokens[3].token_type, TokenType::StringLiteral(" ".to_string()));
+
+    let error = Lexer::new("SELECT $$ never closed").tokenize().expect_err("input should not tokenize");
+    assert!(error.message.contains("Unterminated dollar-quoted string"));
+}
@@ -0,0 +1,144 @@
+use serde_json::Value;
+use sql2doc::core::analyze::{ColumnLineage, LineageAnalyzer, Transformation, ViewLineage};
+use sql2doc::core::docs::HtmlRenderer;
+use sql2doc::core::domain::entity::Database;
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+const SCHEMA: &str = "
+CREATE TABLE customers (id INT PRIMARY KEY, name TEXT NOT NULL, region TEXT);
+CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT REFERENCES customers (id), amount NUMERIC, status TEXT);
+CREATE VIEW order_totals AS
+    SELECT c.id AS customer_id, c.name, SUM(o.amount) AS total, COUNT(*) AS order_count
+    FROM customers c JOIN orders o ON o.customer_id = c.id
+    GROUP BY c.id, c.name;
+CREATE VIEW customer_tiers AS
+    WITH ranked AS (SELECT customer_id, name AS customer, total FROM order_totals)
+    SELECT customer,
+           CASE WHEN total > 1000 THEN 'gold' WHEN r.customer_id < 10 THEN 'founder' ELSE 'standard' END AS tier,
+           total * 1.1 AS projected,
+           upper(customer) AS shout
+    FROM ranked r;
+";
+
+fn lower(sql: &str) -> Database {
+    Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("schema should lower").database
+}
+
+fn lineage(database: &Database) -> Vec<ViewLineage> {
+    LineageAnalyzer::new(database, SqlDialect::PostgreSQL).views()
+}
+
+fn column<'l>(lineage: &'l [ViewLineage], view: &str, column: &str) -> &'l ColumnLineage {
+    lineage
+        .iter()
+        .find(|lineage| lineage.view == view)
+        .and_then(|lineage| lineage.columns.iter().find(|lineage| lineage.column == column))
+        .unwrap_or_else(|| panic!("Expected column {}.{}", view, column))
+}
+
+fn sources(column: &ColumnLineage) -> Vec<String> {
+    column.sources.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_lineage_through_views_and_ctes() {
+    let database = lower(SCHEMA);
+    let lineage = lineage(&database);
+    assert!(lineage.iter().all(|lineage| lineage.complete));
+
+    let customer_id = column(&lineage, "order_totals", "customer_id");
+    assert_eq!(customer_id.transformation, Transformation::Direct);
+    assert_eq!(sources(customer_id), vec!["customers.id"]);
+    let total = column(&lineage, "order_totals", "total");
+    assert_eq!(total.transformation, Transformation::Aggregate);
+    assert_eq!(sources(total), vec!["orders.amount"]);
+    // `count(*)` reads no column in particular
+    let order_count = column(&lineage, "order_totals", "order_count");
+    assert_eq!(order_count.transformation, Transformation::Aggregate);
+    assert!(order_count.sources.is_empty());
+
+    // Renamed in a CTE over another view, the column still leads back to its table
+    let customer = column(&lineage, "customer_tiers", "customer");
+    assert_eq!(customer.transformation, Transformation::Direct);
+    assert_eq!(sources(customer), vec!["customers.name"]);
+    let tier = column(&lineage, "customer_tiers", "tier");
+    assert_eq!(tier.transformation, Transformation::Case);
+    assert_eq!(sources(tier), vec!["orders.amount", "customers.id"]);
+    let projected = column(&lineage, "customer_tiers", "projected");
+    assert_eq!(projected.transformation, Transformation::Expression);
+    assert_eq!(sources(projected), vec!["orders.amount"]);
+    assert_eq!(column(&lineage, "customer_tiers", "shout").transformation, Transformation::Expression);
+}
+
+#[test]
+fn test_lineage_of_wildcards_and_set_operations() {
+    let database = lower(
+        "CREATE TABLE a (id INT, label TEXT);
+         CREATE TABLE b (id INT, title TEXT);
+         CREATE VIEW labels AS SELECT id, label FROM a UNION ALL SELECT id, upper(title) FROM b;
+         CREATE VIEW everything AS SELECT * FROM (SELECT l.*, 1 AS one FROM labels l) sub;
+         CREATE VIEW broken AS SELECT x.* FROM missing x;
+         CREATE VIEW numbered AS SELECT id, row_number() OVER (ORDER BY id) AS n, max(id) OVER () AS top FROM a;",
+    );
+    let lineage = lineage(&database);
+
+    // Each branch feeds the column at its position
+    let label = column(&lineage, "labels", "label");
+    assert_eq!(label.transformation, Transformation::Expression);
+    assert_eq!(sources(label), vec!["a.label", "b.title"]);
+    assert_eq!(sources(column(&lineage, "labels", "id")), vec!["a.id", "b.id"]);
+
+    let everything = lineage.iter().find(|lineage| lineage.view == "everything").expect("view should be traced");
+    let names: Vec<&str> = everything.columns.iter().map(|column| column.column.as_str()).collect();
+    assert_eq!(names, vec!["id", "label", "one"]);
+    assert!(column(&lineage, "everything", "one").sources.is_empty());
+
+    let broken = lineage.iter().find(|lineage| lineage.view == "broken").expect("view should be traced");
+    assert!(!broken.complete);
+
+    assert_eq!(column(&lineage, "numbered", "n").transformation, Transformation::Expression);
+    assert_eq!(column(&lineage, "numbered", "top").transformation, Transformation::Aggregate);
+}
+
+#[test]
+fn test_lineage_guards_against_cycles() {
+    let mut database = lower(
+        "CREATE TABLE t (id INT);
+         CREATE VIEW v1 AS SELECT id FROM t;
+         CREATE VIEW v2 AS SELECT id FROM v1;",
+    );
+    // A view redefined over one that reads it, as a hand-edited model could hold
+    let mut views = database.schemas.iter_mut().flat_map(|schema| schema.views.iter_mut());
+    if let Some(view) = views.find(|view| view.name == "v1") {
+        view.definition = "SELECT id FROM v2".to_string();
+    }
+    let lineage = lineage(&database);
+    assert!(lineage.iter().all(|lineage| !lineage.complete));
+}
+
+#[test]
+fn test_render_lineage() {
+    let database = lower(SCHEMA);
+    let lineage = lineage(&database);
+
+    let json = serde_json::to_value(&lineage).expect("lineage should serialize");
+    assert_eq!(json[0]["view"], "order_totals");
+    assert_eq!(json[0]["columns"][2]["column"], "total");
+    assert_eq!(json[0]["columns"][2]["transformation"], "aggregate");
+    assert_eq!(json[0]["columns"][2]["sources"][0]["table"], "orders");
+    assert_eq!(json[0]["columns"][2]["sources"][0]["column"], "amount");
+    assert_eq!(json[1]["columns"][1]["transformation"], "case");
+    assert_eq!(json[1]["complete"], Value::from(true));
+
+    let html = HtmlRenderer::new().render(&database, &lineage);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<tr id=\"column-orders-amount\"><td>amount</td>"));
+    assert!(html.contains("<section id=\"view-customer-tiers\">"));
+    assert!(html.contains(
+        "<tr><td>tier</td><td>CASE</td><td><a href=\"#column-orders-amount\"><code>orders.amount</code></a>, \
+         <a href=\"#column-customers-id\"><code>customers.id</code></a></td></tr>"
+    ));
+    assert!(html.contains("WHEN total &gt; 1000"));
+    assert!(html.contains("references <a href=\"#table-customers\">customers</a>"));
+}
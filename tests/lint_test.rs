@@ -0,0 +1,152 @@
+use sql2doc::core::analyze::{LintConfig, LintError, LintFinding, LintLevel, LintRule, SchemaLinter, Suppressions};
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+fn rules(findings: &[LintFinding]) -> Vec<&str> {
+    findings.iter().map(|finding| finding.rule.id()).collect()
+}
+
+#[test]
+fn test_rules_have_stable_ids_and_default_levels() {
+    for rule in LintRule::ALL {
+        assert_eq!(rule.id().parse::<LintRule>().expect("id should parse"), rule);
+    }
+    assert_eq!(LintRule::MissingPrimaryKey.id(), "missing-primary-key");
+    assert_eq!(LintRule::NullablePrimaryKey.default_level(), LintLevel::Error);
+    assert_eq!(LintRule::UnindexedForeignKey.default_level(), LintLevel::Warning);
+    assert_eq!(LintRule::TextColumn.default_level(), LintLevel::Off);
+
+    let mut config = LintConfig::new();
+    config.apply("text-column=warn").expect("setting should apply");
+    config.apply("missing-primary-key = deny").expect("setting should apply");
+    assert_eq!(config.level(LintRule::TextColumn), LintLevel::Warning);
+    assert_eq!(config.level(LintRule::MissingPrimaryKey), LintLevel::Error);
+    assert_eq!(config.level(LintRule::WideIndex), LintLevel::Info);
+
+    assert!(matches!(config.apply("no-such-rule=off"), Err(LintError::UnknownRule(_))));
+    assert!(matches!(config.apply("wide-index=loud"), Err(LintError::UnknownLevel(_))));
+    assert!(matches!(config.apply("wide-index"), Err(LintError::Setting(_))));
+}
+
+#[test]
+fn test_lint_reports_findings_at_their_statements() {
+    let sql = "CREATE TABLE users (id INT PRIMARY KEY, bio TEXT);
+CREATE TABLE events (name VARCHAR(50));
+CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users (id), note VARCHAR);
+CREATE INDEX orders_missing ON orders (shipped_at);";
+    let findings = SchemaLinter::new(SqlDialect::PostgreSQL).lint(sql);
+    assert_eq!(
+        rules(&findings),
+        vec!["missing-primary-key", "unindexed-foreign-key", "unbounded-varchar", "unknown-index-column"]
+    );
+
+    let line = |finding: &LintFinding| finding.location.as_ref().map(|location| location.start.line);
+    assert_eq!(findings[0].object, "events");
+    assert_eq!(line(&findings[0]), Some(2));
+    assert_eq!(findings[0].level, LintLevel::Warning);
+    assert_eq!(line(&findings[1]), Some(3));
+    assert!(findings[1].help.is_some());
+    assert_eq!(line(&findings[2]), Some(3));
+    assert_eq!(line(&findings[3]), Some(4));
+    assert_eq!(findings[3].level, LintLevel::Error);
+
+    // Overrides turn rules on, off or up
+    let mut config = LintConfig::new();
+    config.set(LintRule::TextColumn, LintLevel::Warning);
+    config.set(LintRule::UnboundedVarchar, LintLevel::Off);
+    config.set(LintRule::MissingPrimaryKey, LintLevel::Error);
+    let findings = SchemaLinter::new(SqlDialect::PostgreSQL).with_config(config).lint(sql);
+    assert_eq!(rules(&findings), vec!["text-column", "missing-primary-key", "unindexed-foreign-key", "unknown-index-column"]);
+    assert!(findings[0].message.ends_with("unbounded TEXT column; prefer a VARCHAR with a length"), "{}", findings[0].message);
+    assert_eq!(findings[1].level, LintLevel::Error);
+}
+
+#[test]
+fn test_disable_comments_suppress_rules() {
+    let sql = "-- sql2doc:disable missing-primary-key
+CREATE TABLE audit_log (message VARCHAR(200));
+CREATE TABLE events (name VARCHAR(50)); -- sql2doc:disable
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    note VARCHAR /* sql2doc:disable unbounded-varchar, text-column */
+);
+CREATE TABLE staging (note VARCHAR); -- sql2doc:disable text-column";
+    let findings = SchemaLinter::new(SqlDialect::PostgreSQL).lint(sql);
+    assert_eq!(rules(&findings), vec!["missing-primary-key", "unbounded-varchar"]);
+    assert!(findings.iter().all(|finding| finding.object.starts_with("staging")));
+
+    // Across scripts, each script's comments only cover its own statements
+    let findings = SchemaLinter::new(SqlDialect::PostgreSQL)
+        .lint_all(&["-- sql2doc:disable\nCREATE TABLE a (x INT);", "CREATE TABLE b (x INT);"]);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].0, 1);
+    assert_eq!(findings[0].1.object, "b");
+}
+
+#[test]
+fn test_suppressions_apply_to_any_rule_id() {
+    let sql = "CREATE TABLE a (id INT);\n-- sql2doc:disable add-column-not-null\nALTER TABLE a ADD COLUMN b INT NOT NULL;\n";
+    let dialect = SqlDialect::PostgreSQL;
+    let tokens = Lexer::for_dialect(sql, &dialect).tokenize().expect("script should tokenize");
+    let statements = Parser::new(tokens).parse_located().expect("script should parse");
+    let suppressions = Suppressions::parse(sql, &dialect, &statements);
+
+    let location = |index: usize| statements[index].location.as_ref();
+    assert!(suppressions.suppresses("add-column-not-null", location(1)));
+    assert!(!suppressions.suppresses("set-not-null", location(1)));
+    assert!(!suppressions.suppresses("add-column-not-null", location(0)));
+    assert!(!suppressions.suppresses("add-column-not-null", None));
+}
+
+#[test]
+fn test_foreign_keys_covered_by_leading_columns_are_indexed() {
+    let sql = "CREATE TABLE accounts (id INT, region INT, PRIMARY KEY (id, region));
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    account_id INT,
+    region INT,
+    FOREIGN KEY (account_id, region) REFERENCES accounts (id, region)
+);
+CREATE INDEX orders_account ON orders (region, account_id, id);
+CREATE TABLE lines (order_id INT REFERENCES orders (id), position INT, PRIMARY KEY (order_id, position));
+CREATE TABLE notes (order_id INT REFERENCES orders (id), body VARCHAR(100), UNIQUE (order_id, body), id INT PRIMARY KEY);
+CREATE TABLE refunds (id INT PRIMARY KEY, order_id INT REFERENCES orders (id), amount INT);
+CREATE INDEX refunds_amount ON refunds (amount, order_id);";
+    let findings = SchemaLinter::new(SqlDialect::PostgreSQL).lint(sql);
+    let unindexed: Vec<&str> = findings
+        .iter()
+        .filter(|finding| finding.rule == LintRule::UnindexedForeignKey)
+        .map(|finding| finding.object.as_str())
+        .collect();
+    // An index whose order_id comes second doesn't support the key
+    assert_eq!(unindexed.len(), 1);
+    assert!(unindexed[0].starts_with("refunds"));
+}
+
+#[test]
+fn test_unbounded_varchar_help_follows_the_dialect() {
+    let sql = "CREATE TABLE notes (id INT PRIMARY KEY, body VARCHAR NOT NULL);";
+    let help = |dialect: SqlDialect| {
+        let findings = SchemaLinter::new(dialect).lint(sql);
+        let finding = findings.iter().find(|finding| finding.rule == LintRule::UnboundedVarchar);
+        finding.and_then(|finding| finding.help.clone()).expect("unbounded VARCHAR should have a fix")
+    };
+    assert_eq!(help(SqlDialect::PostgreSQL), "ALTER TABLE notes ALTER COLUMN body TYPE VARCHAR(255)");
+    assert_eq!(help(SqlDialect::MySQL), "ALTER TABLE notes MODIFY body VARCHAR(255) NOT NULL");
+    assert_eq!(help(SqlDialect::MSSQL), "ALTER TABLE notes ALTER COLUMN body VARCHAR(255) NOT NULL");
+    assert_eq!(help(SqlDialect::Oracle), "ALTER TABLE notes MODIFY (body VARCHAR(255))");
+}
+
+#[test]
+fn test_unknown_rule_ids_in_disable_comments_warn() {
+    let sql = "-- sql2doc:disable missing-primary-key, missing-primary-keys
+CREATE TABLE events (name VARCHAR(50));
+/* sql2doc:disable add-column-not-null unknown-column */
+ALTER TABLE events ADD COLUMN kind INT NOT NULL;
+-- sql2doc:disable
+CREATE TABLE audit (x INT);";
+    let warnings = Suppressions::unknown_rules(sql, &SqlDialect::PostgreSQL);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("missing-primary-keys"));
+    assert_eq!(warnings[0].location.as_ref().map(|location| location.start.line), Some(1));
+    assert_eq!(warnings[0].rule.as_deref(), Some("unknown-rule"));
+}
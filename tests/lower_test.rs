@@ -0,0 +1,637 @@
+use sql2doc::core::docs::{JsonSchemaRenderer, MarkdownRenderer};
+use sql2doc::core::domain::entity::{DataType, IndexType, Sequence, SortOrder, TypeAffinity, UserDefinedTypeKind};
+use sql2doc::core::domain::procedures::ParameterMode;
+use sql2doc::core::domain::triggers::{TriggerEvent, TriggerScope, TriggerTiming};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+
+const SCHEMA: &str = "
+    CREATE TYPE order_status AS ENUM ('pending', 'shipped');
+    CREATE DOMAIN positive_amount AS NUMERIC(12, 2) NOT NULL CHECK (VALUE > 0);
+    CREATE TABLE customers (
+        id BIGSERIAL PRIMARY KEY,
+        name TEXT COLLATE \"C\" NOT NULL,
+        profile JSONB
+    );
+    CREATE TABLE orders (
+        id INT GENERATED BY DEFAULT AS IDENTITY,
+        customer_id BIGINT REFERENCES customers ON DELETE CASCADE,
+        status order_status NOT NULL DEFAULT 'pending',
+        amount positive_amount,
+        tags TEXT[],
+        amount_with_tax NUMERIC GENERATED ALWAYS AS (amount * 1.2) STORED,
+        PRIMARY KEY (id),
+        EXCLUDE USING gist (customer_id WITH =) WHERE (status = 'pending')
+    );
+    CREATE INDEX orders_tags ON orders USING gin (tags);";
+
+#[test]
+fn test_lower_postgres_schema() {
+    let output = Lowerer::new(SqlDialect::PostgreSQL).lower(SCHEMA).expect("schema should lower");
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+    let database = output.database;
+
+    let customers = database.get_table(None, "customers").expect("customers table");
+    let id = customers.get_column("id").expect("id column");
+    assert_eq!(id.data_type, DataType::BigInt { unsigned: false });
+    assert!(id.auto_increment && !id.nullable);
+    assert_eq!(customers.get_column("name").and_then(|c| c.collation.as_deref()), Some("C"));
+    assert_eq!(customers.get_column("profile").map(|c| &c.data_type), Some(&DataType::JsonB));
+
+    let orders = database.get_table(None, "orders").expect("orders table");
+    let identity = orders.get_column("id").and_then(|c| c.identity.as_ref()).expect("identity");
+    assert!(!identity.always);
+    assert_eq!((identity.seed, identity.increment), (1, 1));
+
+    let status = orders.get_column("status").expect("status column");
+    assert_eq!(status.user_type.as_deref(), Some("order_status"));
+    assert_eq!(status.data_type, DataType::Enum { values: vec!["pending".to_string(), "shipped".to_string()] });
+
+    let amount = orders.get_column("amount").expect("amount column");
+    assert_eq!(amount.data_type, DataType::Decimal { precision: Some(12), scale: Some(2) });
+    assert!(!amount.nullable);
+
+    assert_eq!(
+        orders.get_column("tags").map(|c| &c.data_type),
+        Some(&DataType::Array { element_type: Box::new(DataType::Text), dimensions: Some(1) })
+    );
+    assert!(orders.get_column("amount_with_tax").and_then(|c| c.generated.as_ref()).is_some_and(|g| g.stored));
+
+    assert_eq!(orders.foreign_keys[0].referenced_columns, vec!["id".to_string()]);
+    assert_eq!(orders.exclusion_constraints[0].index_type, IndexType::Gist);
+    assert_eq!(orders.indexes[0].index_type, IndexType::Gin);
+
+    let types = database.get_all_types();
+    assert_eq!(types.len(), 2);
+    assert!(matches!(&types[1].kind, UserDefinedTypeKind::Domain { checks, .. } if checks == &vec!["VALUE > 0".to_string()]));
+}
+
+#[test]
+fn test_markdown_documents_reusable_types() {
+    let output = Lowerer::new(SqlDialect::PostgreSQL)
+        .with_database_name("shop")
+        .lower(SCHEMA)
+        .expect("schema should lower");
+    let markdown = MarkdownRenderer::new().render(&output.database);
+
+    assert!(markdown.starts_with("# shop\n"));
+    assert!(markdown.contains("| status | [order_status](#enum-order_status) | no | `'pending'` |"));
+    assert!(markdown.contains("| amount | [positive_amount](#domain-positive_amount) | no |"));
+    assert!(markdown.contains("### Enum: order_status\n\nValues: `pending`, `shipped`"));
+    assert!(markdown.contains("### Domain: positive_amount\n\nBase type: `DECIMAL(12, 2)`"));
+    assert!(markdown.contains("Used by: [orders.status](#orders)"));
+    assert!(markdown.contains("identity (by default)"));
+    assert!(markdown.contains("`USING gist (customer_id WITH =) WHERE (status = 'pending')`"));
+}
+
+const MYSQLDUMP: &str = r"
+/*!40101 SET @OLD_CHARACTER_SET_CLIENT=@@CHARACTER_SET_CLIENT */;
+/*!50503 SET NAMES utf8mb4 */;
+DROP TABLE IF EXISTS `articles`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+SET character_set_client = utf8mb4;
+CREATE TABLE `articles` (
+  `id` int(10) unsigned zerofill NOT NULL AUTO_INCREMENT,
+  `title` varchar(200) CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci NOT NULL COMMENT 'Headline',
+  `body` text,
+  `flags` set('featured','pinned') DEFAULT NULL,
+  `is_active` bit(1) NOT NULL DEFAULT b'1',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uq_title` (`title`(100)),
+  KEY `idx_updated` (`updated_at`) USING BTREE,
+  FULLTEXT KEY `ft_body` (`title`,`body`)
+) ENGINE=InnoDB AUTO_INCREMENT=42 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='Blog articles';
+SET character_set_client = @saved_cs_client;
+LOCK TABLES `articles` WRITE;
+INSERT INTO `articles` VALUES (1,'It\'s here','Line\nbreak','',b'1','2024-01-01 00:00:00');
+UNLOCK TABLES;
+";
+
+#[test]
+fn test_lower_mysqldump() {
+    let output = Lowerer::new(SqlDialect::MySQL).lower(MYSQLDUMP).expect("dump should lower");
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+    let articles = output.database.get_table(None, "articles").expect("articles table");
+
+    assert_eq!(articles.engine.as_deref(), Some("InnoDB"));
+    assert_eq!(articles.charset.as_deref(), Some("utf8mb4"));
+    assert_eq!(articles.collation.as_deref(), Some("utf8mb4_0900_ai_ci"));
+    assert_eq!(articles.metadata.description.as_deref(), Some("Blog articles"));
+    assert_eq!(articles.metadata.custom_properties.get("AUTO_INCREMENT").map(String::as_str), Some("42"));
+
+    let title = articles.get_column("title").expect("title column");
+    assert_eq!(title.charset.as_deref(), Some("utf8mb4"));
+    assert_eq!(title.metadata.description.as_deref(), Some("Headline"));
+    assert_eq!(
+        articles.get_column("flags").map(|c| &c.data_type),
+        Some(&DataType::Set { values: vec!["featured".to_string(), "pinned".to_string()] })
+    );
+    assert_eq!(articles.get_column("updated_at").and_then(|c| c.on_update.as_deref()), Some("CURRENT_TIMESTAMP"));
+
+    let kinds: Vec<(&str, &IndexType)> = articles.indexes.iter().map(|i| (i.name.as_str(), &i.index_type)).collect();
+    assert_eq!(kinds, vec![("idx_updated", &IndexType::BTree), ("ft_body", &IndexType::FullText)]);
+    assert_eq!(articles.unique_constraints[0].columns, vec!["title".to_string()]);
+
+    let markdown = MarkdownRenderer::new().render(&output.database);
+    assert!(markdown.contains("**Engine:** `InnoDB` · **Charset:** `utf8mb4` · **Collation:** `utf8mb4_0900_ai_ci`"));
+    assert!(markdown.contains("- `ft_body` on `title`, `body`, fulltext"));
+    assert!(markdown.contains("on update `CURRENT_TIMESTAMP`"));
+}
+
+const SQLITE: &str = "
+    CREATE TABLE devices (
+        id INTEGER PRIMARY KEY,
+        serial VARYING CHARACTER(20) NOT NULL,
+        counter UNSIGNED BIG INT,
+        reading FLOATING POINT,
+        payload
+    );
+    CREATE TABLE settings (
+        key TEXT PRIMARY KEY,
+        value ANY,
+        version INT NOT NULL DEFAULT 1
+    ) STRICT, WITHOUT ROWID;
+    CREATE TABLE readings (id INT PRIMARY KEY, device_id INTEGER);
+    CREATE TABLE alerts (id INTEGER PRIMARY KEY DESC, message TEXT);
+    CREATE TABLE batches (id INTEGER, PRIMARY KEY (id DESC));";
+
+#[test]
+fn test_lower_sqlite_affinity() {
+    let output = Lowerer::new(SqlDialect::SQLite).lower(SQLITE).expect("schema should lower");
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+    let devices = output.database.get_table(None, "devices").expect("devices table");
+
+    let affinities: Vec<(&str, Option<TypeAffinity>)> =
+        devices.columns.iter().map(|c| (c.name.as_str(), c.affinity)).collect();
+    assert_eq!(
+        affinities,
+        vec![
+            ("id", Some(TypeAffinity::Integer)),
+            ("serial", Some(TypeAffinity::Text)),
+            ("counter", Some(TypeAffinity::Integer)),
+            // "FLOATING POINT" contains "INT"
+            ("reading", Some(TypeAffinity::Integer)),
+            ("payload", Some(TypeAffinity::Blob)),
+        ]
+    );
+    assert!(devices.get_column("id").expect("id column").rowid_alias);
+    assert_eq!(devices.get_column("payload").map(|c| &c.data_type), Some(&DataType::Blob));
+
+    // Only a column declared exactly INTEGER aliases the rowid
+    let readings = output.database.get_table(None, "readings").expect("readings table");
+    assert!(!readings.get_column("id").expect("id column").rowid_alias);
+    // DESC on the column's own PRIMARY KEY keeps it from aliasing the rowid; on the table's it doesn't
+    let alerts = output.database.get_table(None, "alerts").expect("alerts table");
+    assert!(!alerts.get_column("id").expect("id column").rowid_alias);
+    let batches = output.database.get_table(None, "batches").expect("batches table");
+    assert!(batches.get_column("id").expect("id column").rowid_alias);
+
+    // Declared types are kept, with the affinity alongside them
+    assert_eq!(
+        devices.get_column("counter").map(|c| &c.data_type),
+        Some(&DataType::Custom("UNSIGNED BIG INT".to_string()))
+    );
+
+    let settings = output.database.get_table(None, "settings").expect("settings table");
+    assert!(settings.strict && settings.without_rowid);
+    assert!(!settings.get_column("key").expect("key column").rowid_alias);
+    assert_eq!(settings.get_column("value").and_then(|c| c.affinity), None);
+
+    let markdown = MarkdownRenderer::new().render(&output.database);
+    assert!(markdown.contains("**Strict** · **Without rowid**"));
+    assert!(markdown.contains("primary key; rowid alias"));
+    assert!(markdown.contains("| `UNSIGNED BIG INT` |"), "{}", markdown);
+    assert!(markdown.contains("INTEGER affinity"));
+
+    let schema = JsonSchemaRenderer::new().render(&output.database);
+    let devices = &schema["$defs"]["devices"];
+    assert_eq!(devices["required"], serde_json::Value::from(vec!["serial"]));
+    assert_eq!(devices["properties"]["counter"]["type"], serde_json::Value::from(vec!["integer", "null"]));
+    assert_eq!(devices["properties"]["payload"]["format"], "byte");
+    assert_eq!(schema["$defs"]["settings"]["properties"]["value"], serde_json::Value::Object(serde_json::Map::new()));
+}
+
+#[test]
+fn test_sqlite_strict_rejects_unknown_types() {
+    let output = Lowerer::new(SqlDialect::SQLite)
+        .lower("CREATE TABLE t (id INTEGER PRIMARY KEY AUTOINCREMENT, at DATETIME) STRICT;")
+        .expect("schema should lower");
+    assert_eq!(output.warnings.len(), 1, "warnings: {:?}", output.warnings);
+}
+
+#[test]
+fn test_identity_columns_are_not_null() {
+    for (dialect, sql) in [
+        (SqlDialect::PostgreSQL, "CREATE TABLE audit (seq INT GENERATED ALWAYS AS IDENTITY, note TEXT);"),
+        (SqlDialect::MSSQL, "CREATE TABLE audit (seq INT IDENTITY(1,1), note NVARCHAR(100));"),
+    ] {
+        let database = Lowerer::new(dialect).lower(sql).expect("schema should lower").database;
+        let audit = database.get_table(None, "audit").expect("audit table");
+        let seq = audit.get_column("seq").expect("seq column");
+        assert!(seq.identity.is_some() && !seq.nullable, "{:?}", seq);
+        assert!(audit.get_column("note").is_some_and(|note| note.nullable));
+
+        let schema = JsonSchemaRenderer::new().render(&database);
+        assert_eq!(schema["$defs"]["audit"]["properties"]["seq"]["type"], "integer");
+    }
+}
+
+const SQL_SERVER: &str = "
+SET ANSI_NULLS ON
+GO
+CREATE SEQUENCE [dbo].[InvoiceNumbers] AS [bigint] START WITH 1000 INCREMENT BY 1
+GO
+CREATE TABLE [dbo].[Orders](
+	[OrderID] [int] IDENTITY(1,1) NOT FOR REPLICATION NOT NULL,
+	[InvoiceNo] [bigint] NOT NULL DEFAULT (NEXT VALUE FOR [dbo].[InvoiceNumbers]),
+	[Notes] [nvarchar](max) NULL,
+	[RowGuid] [uniqueidentifier] ROWGUIDCOL NOT NULL,
+ CONSTRAINT [PK_Orders] PRIMARY KEY CLUSTERED 
+(
+	[OrderID] ASC
+)WITH (PAD_INDEX = OFF, ALLOW_ROW_LOCKS = ON) ON [PRIMARY]
+) ON [PRIMARY] TEXTIMAGE_ON [PRIMARY]
+GO
+CREATE NONCLUSTERED INDEX [IX_Orders_Invoice] ON [dbo].[Orders] ([InvoiceNo] ASC)
+INCLUDE ([Notes]) WITH (SORT_IN_TEMPDB = OFF) ON [PRIMARY]
+GO
+CREATE TABLE [dbo].[OrderLines](
+	[LineID] [int] NOT NULL,
+	[OrderID] [int] NOT NULL,
+ CONSTRAINT [PK_OrderLines] PRIMARY KEY CLUSTERED ([LineID] ASC) ON [PRIMARY]
+) ON [PRIMARY]
+GO
+ALTER TABLE [dbo].[OrderLines]  WITH CHECK ADD  CONSTRAINT [FK_OrderLines_Orders] FOREIGN KEY([OrderID])
+REFERENCES [dbo].[Orders] ([OrderID])
+ON DELETE CASCADE
+GO
+ALTER TABLE [dbo].[OrderLines] CHECK CONSTRAINT [FK_OrderLines_Orders]
+GO
+ALTER TABLE [dbo].[OrderLines]  WITH NOCHECK ADD  CONSTRAINT [CK_OrderLines_Line] CHECK  (([LineID]>(0)))
+GO
+ALTER TABLE [dbo].[OrderLines] NOCHECK CONSTRAINT ALL
+GO
+";
+
+#[test]
+fn test_lower_sql_server_script() {
+    let output = Lowerer::new(SqlDialect::MSSQL).lower(SQL_SERVER).expect("schema should lower");
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+    let orders = output.database.get_table(None, "Orders").expect("Orders table");
+
+    let order_id = orders.get_column("OrderID").expect("OrderID column");
+    assert!(order_id.is_primary_key(&orders.primary_key) && !order_id.nullable);
+    assert!(order_id.identity.is_some());
+    assert_eq!(orders.get_column("Notes").map(|c| &c.data_type), Some(&DataType::NVarChar { length: None }));
+    assert_eq!(orders.get_column("RowGuid").map(|c| &c.data_type), Some(&DataType::Uuid));
+    assert_eq!(orders.indexes[0].columns[0].name, "InvoiceNo");
+    assert_eq!(orders.indexes[0].columns[0].sort_order, SortOrder::Ascending);
+    assert_eq!(orders.primary_key.as_ref().and_then(|pk| pk.constraint_name.as_deref()), Some("PK_Orders"));
+
+    let sequences = output.database.get_all_sequences();
+    assert_eq!(sequences.len(), 1);
+    assert_eq!(sequences[0].data_type, Some(DataType::BigInt { unsigned: false }));
+    assert_eq!(sequences[0].start, Some(1000));
+
+    let markdown = MarkdownRenderer::new().render(&output.database);
+    assert!(markdown.contains("### Sequence: InvoiceNumbers"));
+    assert!(markdown.contains("Used by: [Orders.InvoiceNo](#orders)"));
+    assert!(markdown.contains("`NVARCHAR(MAX)`"));
+
+    let schema = JsonSchemaRenderer::new().render(&output.database);
+    assert_eq!(schema["$defs"]["Orders"]["properties"]["Notes"]["maxLength"], 1_073_741_823);
+
+    // SSMS adds every foreign key WITH CHECK after the tables, then turns its checking on
+    let lines = output.database.get_table(None, "OrderLines").expect("OrderLines table");
+    assert_eq!(lines.foreign_keys.len(), 1);
+    assert_eq!(lines.foreign_keys[0].constraint_name.as_deref(), Some("FK_OrderLines_Orders"));
+    assert_eq!(lines.foreign_keys[0].referenced_table, "Orders");
+    assert_eq!(lines.check_constraints[0].constraint_name.as_deref(), Some("CK_OrderLines_Line"));
+
+    let output = Lowerer::new(SqlDialect::MSSQL)
+        .lower("CREATE TABLE t (a INT, b INT);\nCREATE INDEX ix_t ON t (a DESC, b) INCLUDE (c);")
+        .expect("schema should lower");
+    let table = output.database.get_table(None, "t").expect("t table");
+    let orders: Vec<&SortOrder> = table.indexes[0].columns.iter().map(|column| &column.sort_order).collect();
+    assert_eq!(orders, vec![&SortOrder::Descending, &SortOrder::Ascending]);
+}
+
+#[test]
+fn test_lower_oracle_script() {
+    let sql = "
+        CREATE SEQUENCE orders_seq MINVALUE 1 MAXVALUE 9999999999999999999999999999 INCREMENT BY 1 START WITH 1 NOCACHE
+            NOORDER NOCYCLE NOKEEP NOSCALE GLOBAL;
+        CREATE TABLE orders (
+            id NUMBER(19) DEFAULT orders_seq.NEXTVAL,
+            customer VARCHAR2(100 CHAR) NOT NULL ENABLE,
+            placed DATE DEFAULT SYSDATE,
+            total NUMBER(10,2),
+            CONSTRAINT orders_pk PRIMARY KEY (id)
+            USING INDEX PCTFREE 10 INITRANS 2 MAXTRANS 255 COMPUTE STATISTICS
+            STORAGE(INITIAL 65536 NEXT 1048576) TABLESPACE \"USERS\" ENABLE,
+            CONSTRAINT orders_customer_uk UNIQUE (customer, placed)
+            USING INDEX PCTFREE 10 INITRANS 2 MAXTRANS 255 COMPUTE STATISTICS TABLESPACE \"USERS\" ENABLE
+        ) SEGMENT CREATION IMMEDIATE PCTFREE 10 NOCOMPRESS LOGGING
+          STORAGE(INITIAL 65536 NEXT 1048576) TABLESPACE users
+        /
+    ";
+    let output = Lowerer::new(SqlDialect::Oracle).lower(sql).expect("schema should lower");
+    let orders = output.database.get_table(None, "orders").expect("orders table");
+
+    let types: Vec<String> = orders.columns.iter().map(|c| c.data_type.to_sql()).collect();
+    assert_eq!(types, vec!["DECIMAL(19)", "VARCHAR(100)", "DATETIME", "DECIMAL(10, 2)"]);
+
+    let sequence = output.database.get_all_sequences()[0];
+    assert_eq!(orders.get_column("id").and_then(|c| c.default_value.as_deref()).and_then(Sequence::referenced_by), Some(sequence.name.clone()));
+    assert!(!sequence.cycle);
+    // The 28-digit MAXVALUE is Oracle's way of writing no bound
+    assert_eq!((sequence.min_value, sequence.max_value), (Some(1), None));
+
+    assert_eq!(orders.primary_key.as_ref().and_then(|pk| pk.constraint_name.as_deref()), Some("orders_pk"));
+    assert_eq!(orders.unique_constraints[0].columns, vec!["customer", "placed"]);
+}
+
+#[test]
+fn test_lower_triggers_and_routines() {
+    let sql = "
+        CREATE TABLE orders (id INT PRIMARY KEY, amount NUMERIC);
+        CREATE OR REPLACE FUNCTION touch_order(amount numeric, OUT changed integer, integer DEFAULT 0)
+        RETURNS integer LANGUAGE plpgsql AS $body$
+        BEGIN
+            changed := 1; -- a semicolon inside the body
+        END;
+        $body$;
+        CREATE PROCEDURE archive_orders() LANGUAGE sql AS $$ DELETE FROM orders $$;
+        CREATE TRIGGER orders_touch BEFORE INSERT OR UPDATE OF amount ON orders
+            FOR EACH ROW WHEN (NEW.amount > 0) EXECUTE FUNCTION touch_order();
+        CREATE TRIGGER orders_truncate AFTER TRUNCATE ON orders EXECUTE FUNCTION touch_order();
+        CREATE TRIGGER lost AFTER INSERT ON missing EXECUTE FUNCTION touch_order();
+    ";
+    let output = Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("schema should lower");
+    let schema = &output.database.schemas[0];
+
+    let function = &schema.functions[0];
+    assert_eq!(function.name, "touch_order");
+    assert!(function.definition.contains("changed := 1; -- a semicolon inside the body"));
+    assert_eq!(function.metadata.custom_properties.get("returns").map(String::as_str), Some("integer"));
+    assert_eq!(function.metadata.custom_properties.get("language").map(String::as_str), Some("plpgsql"));
+    let parameters: Vec<(&str, &str, &ParameterMode)> = function
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.name.as_str(), parameter.data_type.as_str(), &parameter.parameter_mode))
+        .collect();
+    assert_eq!(
+        parameters,
+        vec![
+            ("amount", "numeric", &ParameterMode::In),
+            ("changed", "integer", &ParameterMode::Out),
+            ("$3", "integer", &ParameterMode::In),
+        ]
+    );
+    assert_eq!(function.parameters[2].default_value.as_deref(), Some("0"));
+    assert_eq!(schema.procedures[0].definition, " DELETE FROM orders ");
+
+    let orders = output.database.get_table(None, "orders").expect("orders table");
+    let touch = &orders.triggers[0];
+    assert_eq!(touch.name, "orders_touch");
+    assert_eq!((&touch.timing, &touch.scope), (&TriggerTiming::Before, &TriggerScope::Row));
+    assert_eq!(touch.events, vec![TriggerEvent::Insert, TriggerEvent::Update]);
+    assert_eq!(touch.definition, "EXECUTE FUNCTION touch_order()");
+    // The model has no TRUNCATE event, and statement-level triggers are the default
+    assert!(orders.triggers[1].events.is_empty());
+    assert_eq!(orders.triggers[1].scope, TriggerScope::Statement);
+
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].object, "lost");
+}
+
+#[test]
+fn test_lower_routine_bodies_in_other_dialects() {
+    // MySQL blocks nest, and `END IF` or a CASE's END don't end them
+    let sql = "
+        CREATE TABLE orders (id INT PRIMARY KEY, amount DECIMAL(10, 2), status VARCHAR(10));
+        CREATE DEFINER=`root`@`localhost` TRIGGER orders_bi BEFORE INSERT ON orders FOR EACH ROW
+        BEGIN
+            IF NEW.amount < 0 THEN
+                SET NEW.amount = 0;
+            END IF;
+            SET NEW.status = CASE WHEN NEW.amount > 100 THEN 'big' ELSE 'small' END;
+        END;
+        CREATE PROCEDURE close_order(IN order_id INT, OUT closed INT)
+        BEGIN
+            UPDATE orders SET status = 'closed' WHERE id = order_id;
+        END;
+        CREATE FUNCTION order_total(order_id INT) RETURNS DECIMAL(10, 2) DETERMINISTIC
+            RETURN (SELECT amount FROM orders WHERE id = order_id);
+    ";
+    let output = Lowerer::new(SqlDialect::MySQL).lower(sql).expect("schema should lower");
+    let schema = &output.database.schemas[0];
+    let trigger = &schema.tables[0].triggers[0];
+    assert!(trigger.definition.starts_with("BEGIN IF NEW.amount < 0 THEN"));
+    assert!(trigger.definition.ends_with("ELSE 'small' END; END"));
+    assert_eq!(schema.procedures[0].parameters[1].parameter_mode, ParameterMode::Out);
+    assert!(schema.functions[0].definition.starts_with("RETURN"));
+
+    // SQL Server's bodies run to the end of the batch
+    let sql = "
+CREATE TABLE [dbo].[Orders] ([Id] INT PRIMARY KEY, [Amount] MONEY)
+GO
+CREATE OR ALTER PROCEDURE [dbo].[CloseOrder] @OrderId INT, @Closed INT OUTPUT, @Note NVARCHAR(50) = NULL
+AS
+    SET NOCOUNT ON;
+    BEGIN TRANSACTION;
+    UPDATE [dbo].[Orders] SET [Amount] = 0 WHERE [Id] = @OrderId;
+    COMMIT;
+GO
+CREATE TRIGGER [dbo].[OrdersAudit] ON [dbo].[Orders] AFTER INSERT, UPDATE
+AS
+BEGIN
+    SELECT * FROM inserted;
+END
+GO
+";
+    let output = Lowerer::new(SqlDialect::MSSQL).lower(sql).expect("schema should lower");
+    let schema = &output.database.schemas[0];
+    let procedure = &schema.procedures[0];
+    assert_eq!(procedure.name, "CloseOrder");
+    assert!(procedure.definition.ends_with("WHERE [Id] = @OrderId; COMMIT;"));
+    let parameters: Vec<&str> = procedure.parameters.iter().map(|parameter| parameter.name.as_str()).collect();
+    assert_eq!(parameters, vec!["@OrderId", "@Closed", "@Note"]);
+    assert_eq!(procedure.parameters[1].parameter_mode, ParameterMode::Out);
+    assert_eq!(procedure.parameters[2].default_value.as_deref(), Some("NULL"));
+    let trigger = &schema.tables[0].triggers[0];
+    assert_eq!((&trigger.timing, &trigger.events), (&TriggerTiming::After, &vec![TriggerEvent::Insert, TriggerEvent::Update]));
+    assert_eq!(trigger.definition, "BEGIN SELECT * FROM inserted; END");
+
+    // Oracle's declarations come before the block, and `/` ends it
+    let sql = "
+        CREATE TABLE orders (id NUMBER PRIMARY KEY, amount NUMBER);
+        CREATE OR REPLACE PROCEDURE close_order (p_id IN NUMBER, p_done IN OUT NUMBER) IS
+            v_count NUMBER;
+        BEGIN
+            UPDATE orders SET amount = 0 WHERE id = p_id;
+        END close_order;
+        /
+        CREATE TABLE later (id NUMBER);
+    ";
+    let output = Lowerer::new(SqlDialect::Oracle).lower(sql).expect("schema should lower");
+    let procedure = &output.database.schemas[0].procedures[0];
+    assert!(procedure.definition.starts_with("v_count NUMBER; BEGIN"));
+    assert_eq!(procedure.parameters[1].parameter_mode, ParameterMode::InOut);
+    assert!(output.database.get_table(None, "later").is_some());
+}
+
+#[test]
+fn test_lower_multiple_schemas() {
+    let sql = "
+        CREATE SCHEMA IF NOT EXISTS sales AUTHORIZATION admin;
+        CREATE SCHEMA crm;
+        CREATE TABLE public.regions (id INT PRIMARY KEY);
+        CREATE TABLE crm.customers (id INT PRIMARY KEY, region_id INT REFERENCES regions);
+        SET search_path TO sales, public;
+        CREATE TABLE orders (
+            id INT PRIMARY KEY,
+            customer_id INT REFERENCES crm.customers (id)
+        );
+        CREATE INDEX orders_customer ON orders (customer_id);
+        CREATE TABLE crm.orders (id INT PRIMARY KEY);";
+    let output = Lowerer::new(SqlDialect::PostgreSQL)
+        .with_database_name("warehouse")
+        .lower(sql)
+        .expect("schemas should lower");
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+    let database = output.database;
+
+    let sales = database.get_schema("sales").expect("sales schema");
+    assert_eq!(sales.metadata.owner.as_deref(), Some("admin"));
+    assert!(database.get_schema("public").is_some_and(|schema| schema.get_table("regions").is_some()));
+
+    let orders = database.get_table(Some("sales"), "orders").expect("sales.orders");
+    assert_eq!(orders.schema.as_deref(), Some("sales"));
+    assert_eq!(orders.indexes[0].name, "orders_customer");
+    let customer = &orders.foreign_keys[0];
+    assert_eq!((customer.referenced_schema.as_deref(), customer.referenced_table.as_str()), (Some("crm"), "customers"));
+
+    let customers = database.get_table(Some("crm"), "customers").expect("crm.customers");
+    assert_eq!(customers.foreign_keys[0].referenced_schema.as_deref(), Some("public"));
+    assert_eq!(customers.foreign_keys[0].referenced_columns, vec!["id".to_string()]);
+    assert!(database.get_table(Some("crm"), "orders").is_some_and(|table| table.indexes.is_empty()));
+
+    let markdown = MarkdownRenderer::new().render(&database);
+    assert!(markdown.contains("## Schemas\n\n- [Schema: sales](#schema-sales)\n"));
+    assert!(markdown.contains("## Schema: sales\n\n**Owner:** `admin`"));
+    assert!(markdown.contains("### sales.orders\n"));
+    assert!(markdown.contains("references [crm.customers](#crmcustomers)"));
+    assert!(markdown.contains("### crm.orders\n"));
+
+    let json = JsonSchemaRenderer::new().render(&database);
+    assert!(json["$defs"]["sales.orders"].is_object() && json["$defs"]["crm.orders"].is_object());
+}
+
+#[test]
+fn test_lower_resolves_references_along_search_path() {
+    // `users` is in both public and auth; billing's search path reaches public's first
+    let sql = "
+        CREATE SCHEMA billing;
+        CREATE SCHEMA auth;
+        CREATE TABLE auth.users (id INT PRIMARY KEY);
+        SET search_path TO billing, public;
+        CREATE TABLE invoices (id INT PRIMARY KEY, user_id INT REFERENCES users, payer_id INT);
+        ALTER TABLE invoices ADD CONSTRAINT invoices_payer FOREIGN KEY (payer_id) REFERENCES payers (id);
+        CREATE TABLE payers (id INT PRIMARY KEY);
+        CREATE TABLE public.users (id INT PRIMARY KEY);
+        CREATE TABLE public.payers (id INT PRIMARY KEY);
+        SET search_path TO auth, billing;
+        CREATE TABLE sessions (user_id INT REFERENCES users (id), payer_id INT REFERENCES payers (id));";
+    let output = Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("schemas should lower");
+    let database = output.database;
+
+    let invoices = database.get_table(Some("billing"), "invoices").expect("billing.invoices");
+    let targets = |table: &sql2doc::core::domain::entity::Table| -> Vec<(Option<String>, String)> {
+        table
+            .foreign_keys
+            .iter()
+            .map(|key| (key.referenced_schema.clone(), key.referenced_table.clone()))
+            .collect()
+    };
+    // billing's own payers comes before public's
+    assert_eq!(
+        targets(invoices),
+        vec![(Some("public".to_string()), "users".to_string()), (Some("billing".to_string()), "payers".to_string())]
+    );
+    assert_eq!(invoices.foreign_keys[0].referenced_columns, vec!["id".to_string()]);
+
+    let sessions = database.get_table(Some("auth"), "sessions").expect("auth.sessions");
+    assert_eq!(
+        targets(sessions),
+        vec![(Some("auth".to_string()), "users".to_string()), (Some("billing".to_string()), "payers".to_string())]
+    );
+}
+
+#[test]
+fn test_lower_mysql_use_database() {
+    let sql = "
+        CREATE DATABASE IF NOT EXISTS `shop`;
+        USE `shop`;
+        CREATE TABLE `users` (`id` INT PRIMARY KEY);
+        CREATE TABLE `audit`.`events` (
+            `id` INT PRIMARY KEY,
+            `user_id` INT,
+            FOREIGN KEY (`user_id`) REFERENCES `shop`.`users` (`id`)
+        );";
+    let database = Lowerer::new(SqlDialect::MySQL).lower(sql).expect("dump should lower").database;
+
+    assert!(database.get_table(Some("shop"), "users").is_some());
+    let events = database.get_table(Some("audit"), "events").expect("audit.events");
+    assert_eq!(events.foreign_keys[0].referenced_schema.as_deref(), Some("shop"));
+}
+
+#[test]
+fn test_lower_alter_table() {
+    let sql = "
+        CREATE TABLE customers (id INT PRIMARY KEY, name TEXT);
+        CREATE TABLE orders (id INT, customer_id INT REFERENCES customers (id), note TEXT, legacy INT);
+        CREATE INDEX orders_legacy ON orders (legacy);
+        ALTER TABLE orders ADD PRIMARY KEY (id);
+        ALTER TABLE orders ADD COLUMN amount NUMERIC(10, 2) NOT NULL DEFAULT 0;
+        ALTER TABLE orders ALTER COLUMN note TYPE VARCHAR(200), ALTER COLUMN note SET DEFAULT 'none';
+        ALTER TABLE orders DROP COLUMN legacy;
+        ALTER TABLE orders ADD CONSTRAINT amount_positive CHECK (amount >= 0);
+        ALTER TABLE customers RENAME COLUMN id TO customer_key;
+        ALTER TABLE customers RENAME TO clients;
+        ALTER TABLE customers ADD COLUMN region TEXT;
+        ALTER TABLE IF EXISTS missing DROP COLUMN x;";
+    let output = Lowerer::new(SqlDialect::PostgreSQL).lower(sql).expect("migration should lower");
+    // Only the table that no longer goes by its old name is warned about
+    assert_eq!(output.warnings.len(), 1, "unexpected warnings: {:?}", output.warnings);
+    assert!(output.warnings[0].message.contains("unknown table customers"));
+    let database = output.database;
+
+    let orders = database.get_table(None, "orders").expect("orders table");
+    let names: Vec<&str> = orders.columns.iter().map(|column| column.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "customer_id", "note", "amount"]);
+    assert_eq!(orders.primary_key.as_ref().map(|pk| pk.columns.clone()), Some(vec!["id".to_string()]));
+    assert!(orders.get_column("id").is_some_and(|column| !column.nullable));
+    let amount = orders.get_column("amount").expect("amount column");
+    assert_eq!(amount.data_type, DataType::Decimal { precision: Some(10), scale: Some(2) });
+    assert_eq!(amount.default_value.as_deref(), Some("0"));
+    let note = orders.get_column("note").expect("note column");
+    assert_eq!(note.data_type, DataType::VarChar { length: Some(200) });
+    assert_eq!(note.default_value.as_deref(), Some("'none'"));
+    // The dropped column takes its index along
+    assert!(orders.indexes.is_empty());
+    assert_eq!(orders.check_constraints[0].name.as_deref(), Some("amount_positive"));
+
+    // Foreign keys follow the renamed table and column
+    assert_eq!(orders.foreign_keys[0].referenced_table, "clients");
+    assert_eq!(orders.foreign_keys[0].referenced_columns, vec!["customer_key".to_string()]);
+    let clients = database.get_table(None, "clients").expect("clients table");
+    assert_eq!(clients.primary_key.as_ref().map(|pk| pk.columns.clone()), Some(vec!["customer_key".to_string()]));
+
+    let dropped = Lowerer::new(SqlDialect::PostgreSQL)
+        .lower("CREATE TABLE t (id INT PRIMARY KEY, code TEXT UNIQUE); ALTER TABLE t DROP CONSTRAINT t_pkey;")
+        .expect("migration should lower");
+    assert!(dropped.warnings.is_empty(), "unexpected warnings: {:?}", dropped.warnings);
+    assert!(dropped.database.get_table(None, "t").is_some_and(|table| table.primary_key.is_none()));
+}
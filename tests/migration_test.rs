@@ -0,0 +1,373 @@
+use sql2doc::core::domain::entity::Database;
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::SqlDialect;
+use sql2doc::core::transpile::{Migration, MigrationGenerator, MigrationStep};
+
+const OLD: &str = "
+CREATE TYPE order_status AS ENUM ('pending', 'shipped');
+CREATE TABLE customers (id INT PRIMARY KEY, email VARCHAR(255) NOT NULL, region TEXT);
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    customer_id INT REFERENCES customers (id),
+    status order_status NOT NULL,
+    amount INT,
+    note TEXT,
+    CONSTRAINT amount_positive CHECK (amount > 0)
+);
+CREATE TABLE legacy_events (id INT, payload TEXT);
+CREATE INDEX orders_customer_idx ON orders (customer_id);
+CREATE VIEW order_totals AS SELECT customer_id, SUM(amount) AS total FROM orders GROUP BY customer_id;
+";
+
+const NEW: &str = "
+CREATE TYPE order_status AS ENUM ('pending', 'shipped', 'cancelled');
+CREATE TABLE order_items (id INT PRIMARY KEY, order_id INT NOT NULL REFERENCES orders (id), sku TEXT NOT NULL);
+CREATE TABLE clients (id INT PRIMARY KEY, email VARCHAR(100) NOT NULL, region TEXT);
+CREATE TABLE orders (
+    id INT PRIMARY KEY,
+    client_id INT REFERENCES clients (id),
+    status order_status NOT NULL,
+    amount BIGINT NOT NULL DEFAULT 0,
+    placed_at TIMESTAMP,
+    CONSTRAINT amount_positive CHECK (amount >= 0)
+);
+CREATE INDEX orders_customer_idx ON orders (client_id);
+CREATE VIEW order_totals AS SELECT client_id, SUM(amount) AS total FROM orders GROUP BY client_id;
+";
+
+fn lower(sql: &str, dialect: SqlDialect) -> Database {
+    Lowerer::new(dialect).lower(sql).expect("schema should lower").database
+}
+
+fn migrate(old: &str, new: &str, dialect: SqlDialect) -> Migration {
+    MigrationGenerator::new(&lower(old, dialect.clone()), &lower(new, dialect.clone()), dialect).generate()
+}
+
+fn statements(steps: &[MigrationStep]) -> Vec<&str> {
+    steps.iter().map(|step| step.sql.as_str()).collect()
+}
+
+fn position(steps: &[MigrationStep], prefix: &str) -> usize {
+    steps.iter().position(|step| step.sql.starts_with(prefix)).unwrap_or_else(|| panic!("no step starts with {}", prefix))
+}
+
+#[test]
+fn test_migration_follows_renames_and_dependencies() {
+    let migration = migrate(OLD, NEW, SqlDialect::PostgreSQL);
+    let up = &migration.up;
+
+    assert_eq!(
+        statements(&up[..5]),
+        vec![
+            "DROP VIEW order_totals;",
+            "ALTER TABLE orders DROP CONSTRAINT amount_positive;",
+            "ALTER TABLE customers RENAME TO clients;",
+            "ALTER TABLE orders RENAME COLUMN customer_id TO client_id;",
+            "ALTER TYPE order_status ADD VALUE 'cancelled' AFTER 'shipped';",
+        ]
+    );
+    // The index follows its column through the rename, so it stays
+    assert!(!statements(up).iter().any(|sql| sql.contains("orders_customer_idx")));
+    // The new table references orders, which already exists, so its key goes in inline
+    let create = &up[position(up, "CREATE TABLE order_items")].sql;
+    assert!(create.contains("FOREIGN KEY (order_id) REFERENCES orders (id)"));
+    assert!(position(up, "ALTER TABLE orders DROP COLUMN note") < position(up, "DROP TABLE legacy_events"));
+    assert!(position(up, "ALTER TABLE orders ADD CONSTRAINT amount_positive CHECK (amount >= 0)") < up.len());
+    assert_eq!(
+        up.last().map(|step| step.sql.as_str()),
+        Some("CREATE VIEW order_totals AS\nSELECT client_id, SUM(amount) AS total FROM orders GROUP BY client_id;")
+    );
+
+    // The rollback undoes the renames and drops the new table before the one it references changes
+    let down = &migration.down;
+    assert!(statements(down).contains(&"ALTER TABLE clients RENAME TO customers;"));
+    assert!(statements(down).contains(&"ALTER TABLE orders RENAME COLUMN client_id TO customer_id;"));
+    assert!(statements(down).contains(&"DROP TABLE order_items;"));
+    assert!(statements(down).contains(&"ALTER TABLE orders ADD COLUMN note TEXT;"));
+    assert!(position(down, "CREATE TABLE legacy_events") < position(down, "CREATE VIEW order_totals"));
+
+    // PostgreSQL can't take the new enum value back out
+    assert_eq!(migration.warnings.len(), 1);
+    assert_eq!(migration.warnings[0].object, "order_status");
+    assert!(migration.warnings[0].message.starts_with("in the down-migration, "));
+
+    let unchanged = migrate(OLD, OLD, SqlDialect::PostgreSQL);
+    assert_eq!(unchanged.up_sql(), "-- No schema changes.\n");
+    assert_eq!(unchanged.down_sql(), "-- No schema changes.\n");
+}
+
+#[test]
+fn test_migration_orders_new_tables_by_foreign_keys() {
+    let migration = migrate(
+        "CREATE TABLE users (id INT PRIMARY KEY);",
+        "CREATE TABLE replies (id INT PRIMARY KEY, post_id INT REFERENCES posts (id));
+         CREATE TABLE posts (id INT PRIMARY KEY, author_id INT REFERENCES users (id), pinned_id INT REFERENCES replies (id));
+         CREATE TABLE users (id INT PRIMARY KEY);",
+        SqlDialect::PostgreSQL,
+    );
+    let up = &migration.up;
+
+    // posts and replies reference each other; posts goes first and the key that closes the cycle is added last
+    let replies = position(up, "CREATE TABLE replies");
+    let posts = position(up, "CREATE TABLE posts");
+    assert!(posts < replies);
+    assert!(up[posts].sql.contains("REFERENCES users (id)"));
+    assert!(!up[posts].sql.contains("REFERENCES replies"));
+    assert!(up[replies].sql.contains("REFERENCES posts (id)"));
+    assert_eq!(
+        up.last().map(|step| step.sql.as_str()),
+        Some("ALTER TABLE posts ADD FOREIGN KEY (pinned_id) REFERENCES replies (id);")
+    );
+
+    // Dropping them again first breaks the cycle, then drops in reverse order
+    assert_eq!(
+        statements(&migration.down),
+        vec![
+            "ALTER TABLE posts DROP CONSTRAINT posts_pinned_id_fkey;",
+            "DROP TABLE replies;",
+            "DROP TABLE posts;",
+        ]
+    );
+    assert_eq!(migration.down[0].warnings, vec!["assumes the unnamed foreign key on posts has PostgreSQL's default name"]);
+}
+
+#[test]
+fn test_migration_warns_of_lost_data() {
+    let migration = migrate(OLD, NEW, SqlDialect::PostgreSQL);
+    let warnings = |steps: &[MigrationStep], prefix: &str| steps[position(steps, prefix)].warnings.clone();
+
+    assert_eq!(
+        warnings(&migration.up, "ALTER TABLE orders DROP COLUMN note"),
+        vec!["drops column orders.note with all of its values; adding it back does not restore them"]
+    );
+    assert_eq!(
+        warnings(&migration.up, "DROP TABLE legacy_events"),
+        vec!["drops table legacy_events with all of its rows; creating it again does not restore them"]
+    );
+    assert_eq!(
+        warnings(&migration.up, "ALTER TABLE clients ALTER COLUMN email TYPE VARCHAR(100)"),
+        vec![
+            "changes clients.email from VARCHAR(255) to VARCHAR(100); values that don't fit are rejected or cut short, \
+             and changing it back does not restore them"
+        ]
+    );
+    // Widening a column loses nothing
+    assert!(warnings(&migration.up, "ALTER TABLE orders ALTER COLUMN amount TYPE BIGINT").is_empty());
+    assert_eq!(
+        warnings(&migration.up, "ALTER TABLE orders ALTER COLUMN amount SET NOT NULL"),
+        vec!["fails while orders.amount holds NULL values"]
+    );
+    assert!(warnings(&migration.down, "ALTER TABLE orders ALTER COLUMN amount TYPE INTEGER")[0].contains("from BIGINT to INTEGER"));
+
+    let script = migration.up_sql();
+    assert!(script.contains("-- WARNING: drops column orders.note with all of its values; adding it back does not restore them\n\
+                             ALTER TABLE orders DROP COLUMN note;\n"));
+    let json = serde_json::to_value(&migration).expect("migration should serialize");
+    assert_eq!(json["up"][0]["sql"], "DROP VIEW order_totals;");
+    assert!(json["up"][0].get("warnings").is_none());
+}
+
+#[test]
+fn test_migration_in_other_dialects() {
+    let old = "CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY, email VARCHAR(255) NOT NULL) ENGINE=InnoDB;
+               CREATE TABLE posts (id INT AUTO_INCREMENT PRIMARY KEY, user_id INT, title VARCHAR(100),
+                                   FOREIGN KEY (user_id) REFERENCES users (id));";
+    let new = "CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY, email VARCHAR(320) NOT NULL,
+                                   role ENUM('admin', 'member') NOT NULL DEFAULT 'member') ENGINE=InnoDB;
+               CREATE TABLE posts (id INT AUTO_INCREMENT PRIMARY KEY, user_id INT NOT NULL, title VARCHAR(100),
+                                   CONSTRAINT fk_posts_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE);";
+
+    let migration = migrate(old, new, SqlDialect::MySQL);
+    assert_eq!(
+        statements(&migration.up),
+        vec![
+            "ALTER TABLE posts DROP FOREIGN KEY posts_ibfk_1;",
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(320) NOT NULL;",
+            "ALTER TABLE users ADD COLUMN role ENUM('admin', 'member') NOT NULL DEFAULT 'member';",
+            "ALTER TABLE posts MODIFY COLUMN user_id INTEGER NOT NULL;",
+            "ALTER TABLE posts ADD CONSTRAINT fk_posts_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE;",
+        ]
+    );
+    assert!(statements(&migration.down).contains(&"ALTER TABLE posts DROP FOREIGN KEY fk_posts_user;"));
+
+    // SQLite can add and drop columns, and leaves everything else to a rebuild
+    let migration = migrate(old, new, SqlDialect::SQLite);
+    assert_eq!(
+        statements(&migration.up),
+        vec!["ALTER TABLE users ADD COLUMN role ENUM('admin', 'member') NOT NULL DEFAULT 'member';"]
+    );
+    let objects: Vec<&str> = migration.warnings.iter().map(|warning| warning.object.as_str()).collect();
+    assert_eq!(objects, vec!["posts", "users.email", "posts.user_id"]);
+
+    // SQL Server renames through a stored procedure
+    let users = "CREATE TABLE users (id INT IDENTITY(1,1) PRIMARY KEY, name NVARCHAR(50), email NVARCHAR(200));";
+    let people = users.replace("users", "people");
+    assert_eq!(statements(&migrate(users, &people, SqlDialect::MSSQL).up), vec!["EXEC sp_rename 'users', 'people';"]);
+    let renamed = users.replace("name", "full_name");
+    assert_eq!(
+        statements(&migrate(users, &renamed, SqlDialect::MSSQL).up),
+        vec!["EXEC sp_rename 'users.name', 'full_name', 'COLUMN';"]
+    );
+}
+
+#[test]
+fn test_migration_scripts_for_mysql_and_sqlite() {
+    let old = "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, legacy TEXT);
+               CREATE INDEX users_name_idx ON users (name);";
+    let new = "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, email TEXT);
+               CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users (id), title TEXT);
+               CREATE INDEX posts_user_idx ON posts (user_id);";
+
+    // MySQL names the table an index belongs to when dropping it
+    let migration = migrate(old, new, SqlDialect::MySQL);
+    let up = &migration.up;
+    assert_eq!(up[0].sql, "DROP INDEX users_name_idx ON users;");
+    assert_eq!(
+        up[position(up, "CREATE TABLE posts")].sql,
+        "CREATE TABLE posts (\n    id INTEGER NOT NULL,\n    user_id INTEGER NOT NULL,\n    title TEXT,\n    \
+         PRIMARY KEY (id),\n    FOREIGN KEY (user_id) REFERENCES users (id)\n);"
+    );
+    assert!(position(up, "CREATE TABLE posts") < position(up, "CREATE INDEX posts_user_idx ON posts (user_id);"));
+    assert!(statements(up).contains(&"ALTER TABLE users ADD COLUMN email TEXT;"));
+    assert!(statements(up).contains(&"ALTER TABLE users DROP COLUMN legacy;"));
+    assert_eq!(
+        statements(&migration.down),
+        vec![
+            "ALTER TABLE users ADD COLUMN legacy TEXT;",
+            "ALTER TABLE users DROP COLUMN email;",
+            "DROP TABLE posts;",
+            "CREATE INDEX users_name_idx ON users (name);",
+        ]
+    );
+    assert!(migration.warnings.is_empty());
+
+    // SQLite drops an index by name alone and keeps an INTEGER key on its column, where it is the rowid
+    let migration = migrate(old, new, SqlDialect::SQLite);
+    let up = &migration.up;
+    assert_eq!(up[0].sql, "DROP INDEX users_name_idx;");
+    assert_eq!(
+        up[position(up, "CREATE TABLE posts")].sql,
+        "CREATE TABLE posts (\n    id INTEGER NOT NULL PRIMARY KEY,\n    user_id INTEGER NOT NULL,\n    title TEXT,\n    \
+         FOREIGN KEY (user_id) REFERENCES users (id)\n);"
+    );
+    assert_eq!(
+        up[position(up, "ALTER TABLE users DROP COLUMN legacy")].warnings,
+        vec!["drops column users.legacy with all of its values; adding it back does not restore them"]
+    );
+    assert_eq!(
+        migration.down[position(&migration.down, "DROP TABLE posts")].warnings,
+        vec!["drops table posts with all of its rows; creating it again does not restore them"]
+    );
+    assert_eq!(migration.down.last().map(|step| step.sql.as_str()), Some("CREATE INDEX users_name_idx ON users (name);"));
+    assert!(migration.warnings.is_empty());
+}
+
+#[test]
+fn test_migration_rolls_back_renames_and_type_changes() {
+    let old = "CREATE TABLE customers (id INT PRIMARY KEY, email VARCHAR(255) NOT NULL, amount INT, note TEXT);
+               CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT REFERENCES customers (id));";
+    let new = "CREATE TABLE clients (id INT PRIMARY KEY, email VARCHAR(100) NOT NULL, amount BIGINT, note TEXT);
+               CREATE TABLE orders (id INT PRIMARY KEY, client_id INT REFERENCES clients (id));";
+
+    // The rollback renames back first, so the columns it changes are under their old table's name
+    let migration = migrate(old, new, SqlDialect::MySQL);
+    assert_eq!(
+        statements(&migration.up),
+        vec![
+            "ALTER TABLE customers RENAME TO clients;",
+            "ALTER TABLE orders RENAME COLUMN customer_id TO client_id;",
+            "ALTER TABLE clients MODIFY COLUMN email VARCHAR(100) NOT NULL;",
+            "ALTER TABLE clients MODIFY COLUMN amount BIGINT;",
+        ]
+    );
+    assert_eq!(
+        statements(&migration.down),
+        vec![
+            "ALTER TABLE clients RENAME TO customers;",
+            "ALTER TABLE orders RENAME COLUMN client_id TO customer_id;",
+            "ALTER TABLE customers MODIFY COLUMN email VARCHAR(255) NOT NULL;",
+            "ALTER TABLE customers MODIFY COLUMN amount INTEGER;",
+        ]
+    );
+    // Narrowing loses data in whichever direction it happens
+    assert!(migration.up[2].warnings[0].starts_with("changes clients.email from VARCHAR(255) to VARCHAR(100)"));
+    assert!(migration.up[3].warnings.is_empty());
+    assert!(migration.down[2].warnings.is_empty());
+    assert!(migration.down[3].warnings[0].starts_with("changes customers.amount from BIGINT to INTEGER"));
+
+    let migration = migrate(old, new, SqlDialect::PostgreSQL);
+    assert_eq!(
+        statements(&migration.down),
+        vec![
+            "ALTER TABLE clients RENAME TO customers;",
+            "ALTER TABLE orders RENAME COLUMN client_id TO customer_id;",
+            "ALTER TABLE customers ALTER COLUMN email TYPE VARCHAR(255);",
+            "ALTER TABLE customers ALTER COLUMN amount TYPE INTEGER;",
+        ]
+    );
+
+    // SQLite renames both ways, and the type changes in either direction are left to a rebuild
+    let migration = migrate(old, new, SqlDialect::SQLite);
+    assert_eq!(
+        statements(&migration.up),
+        vec!["ALTER TABLE customers RENAME TO clients;", "ALTER TABLE orders RENAME COLUMN customer_id TO client_id;"]
+    );
+    assert_eq!(
+        statements(&migration.down),
+        vec!["ALTER TABLE clients RENAME TO customers;", "ALTER TABLE orders RENAME COLUMN client_id TO customer_id;"]
+    );
+    let objects: Vec<&str> = migration.warnings.iter().map(|warning| warning.object.as_str()).collect();
+    assert_eq!(objects, vec!["clients.email", "clients.amount", "customers.email", "customers.amount"]);
+    assert!(migration.warnings[2].message.starts_with("in the down-migration, SQLite cannot alter a column"));
+}
+
+#[test]
+fn test_migration_uses_postgres_default_constraint_names() {
+    let plain = "CREATE TABLE users (id INT PRIMARY KEY, status TEXT, age INT);";
+    let checked = "CREATE TABLE users (id INT PRIMARY KEY, status TEXT CHECK (status IN ('active', 'closed')), age INT);";
+
+    // A column-level check is named after its column, both ways round
+    let migration = migrate(plain, checked, SqlDialect::PostgreSQL);
+    assert_eq!(statements(&migration.up), vec!["ALTER TABLE users ADD CHECK (status IN ('active', 'closed'));"]);
+    assert_eq!(statements(&migration.down), vec!["ALTER TABLE users DROP CONSTRAINT users_status_check;"]);
+    let migration = migrate(checked, plain, SqlDialect::PostgreSQL);
+    assert_eq!(statements(&migration.up), vec!["ALTER TABLE users DROP CONSTRAINT users_status_check;"]);
+    assert_eq!(statements(&migration.down), vec!["ALTER TABLE users ADD CHECK (status IN ('active', 'closed'));"]);
+
+    // A second check on the same column is numbered, and one reading two columns has none in its name
+    let migration = migrate(
+        "CREATE TABLE users (id INT PRIMARY KEY, status TEXT, age INT CHECK (age > 0),
+                             CHECK (age < 200), CHECK (age > 0 OR status = 'closed'));",
+        plain,
+        SqlDialect::PostgreSQL,
+    );
+    assert_eq!(
+        statements(&migration.up),
+        vec![
+            "ALTER TABLE users DROP CONSTRAINT users_age_check;",
+            "ALTER TABLE users DROP CONSTRAINT users_age_check1;",
+            "ALTER TABLE users DROP CONSTRAINT users_check;",
+        ]
+    );
+
+    // Names are cut to 63 bytes, taking from the longer part first
+    let migration = migrate(
+        "CREATE TABLE users (id INT PRIMARY KEY);
+         CREATE TABLE subscription_renewal_reminder_notifications (id INT PRIMARY KEY,
+                      subscription_renewal_reminder_id INT REFERENCES users (id) UNIQUE);",
+        "CREATE TABLE users (id INT PRIMARY KEY);
+         CREATE TABLE subscription_renewal_reminder_notifications (id INT PRIMARY KEY, subscription_renewal_reminder_id INT);",
+        SqlDialect::PostgreSQL,
+    );
+    let names: Vec<&str> = statements(&migration.up)
+        .into_iter()
+        .filter_map(|sql| sql.strip_prefix("ALTER TABLE subscription_renewal_reminder_notifications DROP CONSTRAINT "))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "subscription_renewal_reminder_subscription_renewal_reminde_fkey;",
+            "subscription_renewal_reminder_subscription_renewal_reminder_key;",
+        ]
+    );
+}
@@ -0,0 +1,246 @@
+use sql2doc::core::analyze::{MigrationIssue, OnlineMigrationLinter};
+use sql2doc::core::domain::constraint::IssueSeverity;
+use sql2doc::core::parse::SqlDialect;
+
+const USERS: &str = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(100), bio TEXT);";
+
+fn lint(dialect: SqlDialect, migration: &str) -> Vec<MigrationIssue> {
+    OnlineMigrationLinter::new(dialect).lint_all(&[USERS, migration]).into_iter().map(|(_, issue)| issue).collect()
+}
+
+fn rules(issues: &[MigrationIssue]) -> Vec<&str> {
+    issues.iter().map(|issue| issue.issue_type.id()).collect()
+}
+
+#[test]
+fn test_lint_postgres_locks_and_rewrites() {
+    let issues = lint(
+        SqlDialect::PostgreSQL,
+        "ALTER TABLE users ADD COLUMN tenant_id INT NOT NULL;
+         ALTER TABLE users ADD COLUMN status TEXT NOT NULL DEFAULT 'active';
+         ALTER TABLE users ADD COLUMN token UUID DEFAULT gen_random_uuid();
+         CREATE INDEX users_tenant ON users (tenant_id);
+         CREATE INDEX CONCURRENTLY users_status ON users (status);
+         ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(200);
+         ALTER TABLE users ALTER COLUMN email TYPE TEXT;
+         ALTER TABLE users ALTER COLUMN tenant_id TYPE BIGINT;
+         ALTER TABLE users ALTER COLUMN email SET NOT NULL;
+         ALTER TABLE users ADD CONSTRAINT users_self FOREIGN KEY (tenant_id) REFERENCES users (id);
+         ALTER TABLE users ADD CONSTRAINT users_self_valid FOREIGN KEY (tenant_id) REFERENCES users (id) NOT VALID;
+         ALTER TABLE users VALIDATE CONSTRAINT users_self_valid;
+         ALTER TABLE users ADD CONSTRAINT status_known CHECK (status <> '');
+         ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);",
+    );
+    assert_eq!(
+        rules(&issues),
+        vec![
+            "add-column-not-null",
+            "add-column-rewrite",
+            "blocking-index",
+            "alter-column-type",
+            "set-not-null",
+            "foreign-key-validation",
+            "check-validation",
+            "blocking-unique-constraint",
+        ]
+    );
+    assert_eq!(issues[0].severity, IssueSeverity::High);
+    assert_eq!(issues[0].table, "users");
+    assert!(issues[1].description.contains("users.token"), "{}", issues[1].description);
+    assert!(issues[2].suggested_fix.contains("CREATE INDEX CONCURRENTLY"));
+    assert!(issues[3].description.contains("users.tenant_id"), "{}", issues[3].description);
+    assert!(issues[5].suggested_fix.contains("NOT VALID"));
+    assert_eq!(issues[0].location.as_ref().map(|location| location.start.line), Some(1));
+
+    // Before PostgreSQL 11 any default is written into every row
+    let linter = OnlineMigrationLinter::new(SqlDialect::PostgreSQL).with_server_version(10);
+    let issues = linter.lint_all(&[USERS, "ALTER TABLE users ADD COLUMN status TEXT DEFAULT 'active';"]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].1.issue_type.id(), "add-column-rewrite");
+}
+
+#[test]
+fn test_lint_skips_tables_new_to_the_script() {
+    let issues = OnlineMigrationLinter::new(SqlDialect::PostgreSQL).lint(
+        "CREATE TABLE accounts (id INT PRIMARY KEY, owner_id INT);
+         CREATE INDEX accounts_owner ON accounts (owner_id);
+         ALTER TABLE accounts ADD COLUMN plan TEXT NOT NULL;
+         ALTER TABLE accounts ADD CONSTRAINT accounts_owner_fk FOREIGN KEY (owner_id) REFERENCES accounts (id);
+         ALTER TABLE accounts RENAME TO customers;
+         CREATE INDEX customers_plan ON customers (plan);",
+    );
+    assert!(issues.is_empty(), "unexpected issues: {:?}", issues);
+
+    // Other dialects are not linted
+    let issues = lint(SqlDialect::SQLite, "CREATE INDEX users_email ON users (email);");
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn test_lint_mysql_table_copies() {
+    let issues = lint(
+        SqlDialect::MySQL,
+        "ALTER TABLE users MODIFY COLUMN email VARCHAR(200);
+         ALTER TABLE users MODIFY COLUMN email VARCHAR(400);
+         ALTER TABLE users MODIFY COLUMN email VARCHAR(500), ALGORITHM=INPLACE, LOCK=NONE;
+         ALTER TABLE users ADD COLUMN age INT;
+         ALTER TABLE users CONVERT TO CHARACTER SET utf8mb4;
+         ALTER TABLE users ADD FULLTEXT INDEX users_bio (bio);
+         CREATE INDEX users_age ON users (age) ALGORITHM=INPLACE LOCK=NONE;
+         ALTER TABLE users ADD CONSTRAINT users_age_fk FOREIGN KEY (age) REFERENCES users (id);
+         SET FOREIGN_KEY_CHECKS = 0;
+         ALTER TABLE users ADD CONSTRAINT users_age_fk2 FOREIGN KEY (age) REFERENCES users (id);
+         ALTER TABLE users ADD COLUMN nickname TEXT, LOCK=EXCLUSIVE;",
+    );
+    assert_eq!(rules(&issues), vec!["table-copy", "table-copy", "blocking-index", "table-copy", "explicit-lock"]);
+    assert!(issues[0].description.starts_with("changing the type of column email copies users"));
+    assert!(issues[0].suggested_fix.contains("ALGORITHM=INPLACE, LOCK=NONE"));
+    assert_eq!(issues[2].severity, IssueSeverity::Medium);
+
+    // MySQL 5.7 rebuilds the table to add a column, though writes go on meanwhile
+    let linter = OnlineMigrationLinter::new(SqlDialect::MySQL).with_server_version(5);
+    let issues = linter.lint_all(&[USERS, "ALTER TABLE users ADD COLUMN age INT;"]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].1.issue_type.id(), "table-rebuild");
+    assert_eq!(issues[0].1.severity, IssueSeverity::Low);
+}
+
+/// Lints each migration on its own against `USERS`, checking the rules it breaks.
+fn assert_cases(linter: &OnlineMigrationLinter, cases: &[(&str, &[&str])]) {
+    for (migration, expected) in cases {
+        let issues: Vec<MigrationIssue> = linter.lint_all(&[USERS, migration]).into_iter().map(|(_, issue)| issue).collect();
+        assert_eq!(rules(&issues), *expected, "{}", migration);
+    }
+}
+
+#[test]
+fn test_lint_each_postgres_rewrite() {
+    let linter = OnlineMigrationLinter::new(SqlDialect::PostgreSQL);
+    assert_cases(
+        &linter,
+        &[
+            ("ALTER TABLE users ADD COLUMN tenant_id INT NOT NULL;", &["add-column-not-null"]),
+            ("ALTER TABLE users ADD COLUMN tenant_id INT NOT NULL DEFAULT 0;", &[]),
+            ("ALTER TABLE users ADD COLUMN tenant_id INT;", &[]),
+            ("ALTER TABLE users ADD COLUMN seq BIGSERIAL;", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN seq INT GENERATED ALWAYS AS IDENTITY;", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN domain TEXT GENERATED ALWAYS AS (lower(email)) STORED;", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN token UUID DEFAULT gen_random_uuid();", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN score FLOAT DEFAULT random();", &["add-column-rewrite"]),
+            // now() is fixed for the transaction, so PostgreSQL 11 stores it once
+            ("ALTER TABLE users ADD COLUMN created_at TIMESTAMP DEFAULT now();", &[]),
+            ("ALTER TABLE users ADD COLUMN status TEXT DEFAULT 'active';", &[]),
+            ("CREATE INDEX users_email ON users (email);", &["blocking-index"]),
+            ("CREATE UNIQUE INDEX users_email ON users (email);", &["blocking-index"]),
+            ("CREATE INDEX CONCURRENTLY users_email ON users (email);", &[]),
+            ("ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(50);", &["alter-column-type"]),
+            ("ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(200);", &[]),
+            ("ALTER TABLE users ALTER COLUMN email TYPE VARCHAR;", &[]),
+            ("ALTER TABLE users ALTER COLUMN email TYPE TEXT;", &[]),
+            ("ALTER TABLE users ALTER COLUMN bio TYPE VARCHAR(500);", &["alter-column-type"]),
+            ("ALTER TABLE users ALTER COLUMN id TYPE BIGINT;", &["alter-column-type"]),
+            (
+                "ALTER TABLE users ADD COLUMN price NUMERIC(10, 2);
+                 ALTER TABLE users ALTER COLUMN price TYPE NUMERIC(12, 2);",
+                &[],
+            ),
+            (
+                "ALTER TABLE users ADD COLUMN price NUMERIC(10, 2);
+                 ALTER TABLE users ALTER COLUMN price TYPE NUMERIC(12, 3);",
+                &["alter-column-type"],
+            ),
+            (
+                "ALTER TABLE users ADD COLUMN price NUMERIC(10, 2);
+                 ALTER TABLE users ALTER COLUMN price TYPE NUMERIC(8, 2);",
+                &["alter-column-type"],
+            ),
+            ("ALTER TABLE users ALTER COLUMN email SET NOT NULL;", &["set-not-null"]),
+            ("ALTER TABLE users ALTER COLUMN email DROP NOT NULL;", &[]),
+            ("ALTER TABLE users ADD CONSTRAINT users_self FOREIGN KEY (id) REFERENCES users (id);", &["foreign-key-validation"]),
+            ("ALTER TABLE users ADD CONSTRAINT users_self FOREIGN KEY (id) REFERENCES users (id) NOT VALID;", &[]),
+            ("ALTER TABLE users ADD CONSTRAINT email_set CHECK (email <> '');", &["check-validation"]),
+            ("ALTER TABLE users ADD CONSTRAINT email_set CHECK (email <> '') NOT VALID;", &[]),
+            ("ALTER TABLE users VALIDATE CONSTRAINT email_set;", &[]),
+            ("ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);", &["blocking-unique-constraint"]),
+            ("ALTER TABLE users ADD PRIMARY KEY (email);", &["blocking-unique-constraint"]),
+            ("ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE USING INDEX users_email;", &[]),
+            ("ALTER TABLE users ADD CONSTRAINT no_overlap EXCLUDE USING gist (email WITH =);", &["blocking-unique-constraint"]),
+        ],
+    );
+
+    // Before PostgreSQL 11 a constant default is written into every row as well
+    let linter = OnlineMigrationLinter::new(SqlDialect::PostgreSQL).with_server_version(10);
+    assert_cases(
+        &linter,
+        &[
+            ("ALTER TABLE users ADD COLUMN status TEXT DEFAULT 'active';", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN status TEXT NOT NULL DEFAULT 'active';", &["add-column-rewrite"]),
+            ("ALTER TABLE users ADD COLUMN status TEXT;", &[]),
+        ],
+    );
+    let linter = OnlineMigrationLinter::new(SqlDialect::PostgreSQL).with_server_version(11);
+    assert_cases(&linter, &[("ALTER TABLE users ADD COLUMN status TEXT DEFAULT 'active';", &[])]);
+}
+
+#[test]
+fn test_lint_each_mysql_table_copy() {
+    let linter = OnlineMigrationLinter::new(SqlDialect::MySQL);
+    assert_cases(
+        &linter,
+        &[
+            // A VARCHAR grows in place while its length still fits in as many bytes
+            ("ALTER TABLE users MODIFY COLUMN email VARCHAR(255);", &[]),
+            ("ALTER TABLE users MODIFY COLUMN email VARCHAR(256);", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email VARCHAR(50);", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email TEXT;", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email VARCHAR(100) NOT NULL;", &[]),
+            ("ALTER TABLE users CONVERT TO CHARACTER SET utf8mb4;", &["table-copy"]),
+            ("ALTER TABLE users DROP PRIMARY KEY;", &["table-copy"]),
+            ("ALTER TABLE users DROP PRIMARY KEY, ADD PRIMARY KEY (id, email);", &["table-rebuild"]),
+            ("ALTER TABLE users ADD PRIMARY KEY (email);", &["table-rebuild"]),
+            ("ALTER TABLE users ADD CONSTRAINT users_self FOREIGN KEY (id) REFERENCES users (id);", &["table-copy"]),
+            (
+                "SET FOREIGN_KEY_CHECKS = 0;
+                 ALTER TABLE users ADD CONSTRAINT users_self FOREIGN KEY (id) REFERENCES users (id);",
+                &[],
+            ),
+            ("ALTER TABLE users ENGINE=InnoDB;", &["table-rebuild"]),
+            ("ALTER TABLE users FORCE;", &["table-rebuild"]),
+            ("ALTER TABLE users ADD COLUMN age INT;", &[]),
+            ("ALTER TABLE users DROP COLUMN bio;", &[]),
+            ("ALTER TABLE users ADD FULLTEXT INDEX users_bio (bio);", &["blocking-index"]),
+            ("ALTER TABLE users ADD SPATIAL INDEX users_bio (bio);", &["blocking-index"]),
+            ("CREATE INDEX users_email ON users (email);", &[]),
+            // ALGORITHM: only COPY and DEFAULT let MySQL fall back to a copy
+            ("ALTER TABLE users ADD COLUMN age INT, ALGORITHM=COPY;", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email TEXT, ALGORITHM=DEFAULT;", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email TEXT, ALGORITHM=INPLACE;", &[]),
+            ("ALTER TABLE users ADD COLUMN age INT, ALGORITHM=INSTANT;", &[]),
+            // LOCK: a shared or exclusive lock blocks writes even without a copy
+            ("ALTER TABLE users ADD COLUMN age INT, LOCK=SHARED;", &["explicit-lock"]),
+            ("ALTER TABLE users ADD COLUMN age INT, LOCK=EXCLUSIVE;", &["explicit-lock"]),
+            ("ALTER TABLE users ADD COLUMN age INT, LOCK=NONE;", &[]),
+            ("ALTER TABLE users ADD COLUMN age INT, LOCK=DEFAULT;", &[]),
+            ("ALTER TABLE users MODIFY COLUMN email TEXT, LOCK=EXCLUSIVE;", &["table-copy"]),
+            ("ALTER TABLE users MODIFY COLUMN email TEXT, ALGORITHM=INPLACE, LOCK=SHARED;", &["explicit-lock"]),
+            // A table made in the same script has no rows to copy
+            (
+                "CREATE TABLE logins (id INT PRIMARY KEY, at DATETIME);
+                 ALTER TABLE logins MODIFY COLUMN at TIMESTAMP, ALGORITHM=COPY, LOCK=EXCLUSIVE;",
+                &[],
+            ),
+        ],
+    );
+
+    // MySQL 5.7 rebuilds the table for columns that 8.0 adds and drops instantly
+    let linter = OnlineMigrationLinter::new(SqlDialect::MySQL).with_server_version(5);
+    assert_cases(
+        &linter,
+        &[
+            ("ALTER TABLE users ADD COLUMN age INT;", &["table-rebuild"]),
+            ("ALTER TABLE users DROP COLUMN bio;", &["table-rebuild"]),
+            ("ALTER TABLE users ADD COLUMN age INT, LOCK=SHARED;", &["explicit-lock"]),
+            ("ALTER TABLE users MODIFY COLUMN email VARCHAR(200);", &[]),
+        ],
+    );
+}
@@ -0,0 +1,294 @@
+use serde_json::Value;
+use sql2doc::core::analyze::{ColumnInfo, Projection, QueryChecker, QueryParameter};
+use sql2doc::core::ast::{AstValidationError, DmlStatement, Statement, ValidationSeverity};
+use sql2doc::core::docs::JsonSchemaRenderer;
+use sql2doc::core::domain::entity::{DataType, Database};
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+const SCHEMA: &str = "
+    CREATE TABLE users (id BIGSERIAL PRIMARY KEY, email TEXT NOT NULL, created_at TIMESTAMP);
+    CREATE TABLE orders (
+        id SERIAL PRIMARY KEY,
+        user_id BIGINT REFERENCES users (id),
+        total NUMERIC(10, 2),
+        created_at TIMESTAMP
+    );
+    CREATE VIEW big_orders AS
+        SELECT o.id, o.total, u.email FROM orders o JOIN users u ON u.id = o.user_id WHERE o.total > 100;";
+
+fn lower_schema(dialect: SqlDialect) -> Database {
+    Lowerer::new(dialect).lower(SCHEMA).expect("schema should lower").database
+}
+
+fn rules(diagnostics: &[AstValidationError]) -> Vec<&str> {
+    diagnostics.iter().filter_map(|diagnostic| diagnostic.rule.as_deref()).collect()
+}
+
+#[test]
+fn test_check_valid_queries() {
+    let database = lower_schema(SqlDialect::PostgreSQL);
+    let diagnostics = QueryChecker::new(&database, SqlDialect::PostgreSQL).check(
+        "SELECT u.id, email, count(*) AS orders FROM users u LEFT JOIN orders o ON o.user_id = u.id
+             WHERE u.created_at > $1 AND email LIKE '%@example.com' GROUP BY u.id, email ORDER BY orders DESC;
+         SELECT created_at FROM users JOIN orders USING (created_at);
+         SELECT * FROM big_orders b WHERE b.total > 10 AND EXISTS (SELECT 1 FROM orders WHERE orders.id = b.id);
+         WITH totals (uid, amount) AS (SELECT user_id, sum(total) FROM orders GROUP BY user_id)
+             SELECT uid, amount FROM totals WHERE uid IN (SELECT id FROM users);
+         INSERT INTO orders (user_id, total) VALUES (1, 9.99), (2, 5) ON CONFLICT (id) DO UPDATE SET total = excluded.total
+             RETURNING id, CURRENT_TIMESTAMP;
+         INSERT INTO users (email) SELECT email FROM big_orders;
+         UPDATE orders o SET total = 0 FROM users WHERE users.id = o.user_id AND o.created_at < now();
+         DELETE FROM users WHERE id = :id;
+         SELECT * FROM information_schema.tables;",
+    );
+    assert!(diagnostics.is_empty(), "unexpected diagnostics: {:?}", diagnostics);
+}
+
+#[test]
+fn test_check_unknown_and_ambiguous_columns() {
+    let database = lower_schema(SqlDialect::PostgreSQL);
+    let diagnostics = QueryChecker::new(&database, SqlDialect::PostgreSQL).check(
+        "SELECT id FROM users u JOIN orders o ON o.user_id = u.id;
+         SELECT u.nickname, x.total FROM users u;
+         SELECT email FROM orders;
+         UPDATE orders SET totl = 1;
+         SELECT * FROM payments;
+         SELECT rank() OVER w FROM orders WINDOW w AS (PARTITION BY customer_id);",
+    );
+    assert_eq!(
+        rules(&diagnostics),
+        vec![
+            "ambiguous-column",
+            "unknown-column",
+            "unknown-table",
+            "unknown-column",
+            "unknown-column",
+            "unknown-table",
+            "unknown-column"
+        ]
+    );
+    assert!(diagnostics[0].message.contains("u.id or o.id"), "{}", diagnostics[0].message);
+    assert_eq!(diagnostics[4].message, "column orders.totl does not exist");
+    assert_eq!(diagnostics[1].location.as_ref().map(|location| location.start.line), Some(2));
+}
+
+#[test]
+fn test_check_insert_arity() {
+    let database = lower_schema(SqlDialect::PostgreSQL);
+    let checker = QueryChecker::new(&database, SqlDialect::PostgreSQL);
+    let diagnostics = checker.check(
+        "INSERT INTO orders (user_id, total) VALUES (1, 2), (1, 2, 3);
+         INSERT INTO users SELECT * FROM orders;
+         INSERT INTO users VALUES (1);",
+    );
+    assert_eq!(rules(&diagnostics), vec!["insert-arity", "insert-arity"]);
+    assert!(diagnostics[1].message.contains("expects 3 value(s) per row but the SELECT has 4"), "{}", diagnostics[1].message);
+
+    // Only PostgreSQL fills trailing columns in when the column list is left out
+    let database = lower_schema(SqlDialect::SQLite);
+    let diagnostics = QueryChecker::new(&database, SqlDialect::SQLite).check("INSERT INTO users VALUES (1);");
+    assert_eq!(rules(&diagnostics), vec!["insert-arity"]);
+}
+
+#[test]
+fn test_check_type_mismatched_comparisons() {
+    let sql = "SELECT * FROM big_orders WHERE email = 5 OR total > 'abc' OR total > '10' OR id IN (SELECT email FROM users);";
+
+    let database = lower_schema(SqlDialect::PostgreSQL);
+    let diagnostics = QueryChecker::new(&database, SqlDialect::PostgreSQL).check(sql);
+    assert_eq!(rules(&diagnostics), vec!["type-mismatch"; 3]);
+    assert_eq!(diagnostics[0].message, "email (TEXT) is compared with 5 (INTEGER)");
+    assert_eq!(diagnostics[0].severity, ValidationSeverity::Error);
+
+    // MySQL converts one side instead of refusing
+    let database = lower_schema(SqlDialect::MySQL);
+    let diagnostics = QueryChecker::new(&database, SqlDialect::MySQL).check(sql);
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity == ValidationSeverity::Warning));
+}
+
+#[test]
+fn test_projection_expands_stars() {
+    let database = lower_schema(SqlDialect::PostgreSQL);
+    let checker = QueryChecker::new(&database, SqlDialect::PostgreSQL);
+    let tokens = Lexer::new("SELECT b.*, 1 AS one, CAST(u.id AS text) FROM big_orders b, users u")
+        .tokenize()
+        .expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    let select = match &statements[0] {
+        Statement::Dml(dml) => match dml.as_ref() {
+            DmlStatement::Select(select) => select,
+            other => panic!("expected SELECT, got {:?}", other),
+        },
+        other => panic!("expected SELECT, got {:?}", other),
+    };
+
+    let projection = checker.projection(select);
+    assert!(projection.complete);
+    let names: Vec<&str> = projection.columns.iter().map(|column| column.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "total", "email", "one", "id"]);
+    assert_eq!(
+        projection.columns[4],
+        ColumnInfo { name: "id".to_string(), data_type: Some(DataType::Text), nullable: false }
+    );
+}
+
+fn result_columns(dialect: SqlDialect, sql: &str) -> Vec<ColumnInfo> {
+    let database = lower_schema(dialect.clone());
+    let tokens = Lexer::for_dialect(sql, &dialect).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    let Statement::Dml(dml) = &statements[0] else {
+        panic!("expected a query, got {:?}", statements[0]);
+    };
+    let projection = QueryChecker::new(&database, dialect).result_columns(dml);
+    assert!(projection.complete);
+    projection.columns
+}
+
+fn column(name: &str, data_type: DataType, nullable: bool) -> ColumnInfo {
+    ColumnInfo { name: name.to_string(), data_type: Some(data_type), nullable }
+}
+
+#[test]
+fn test_infer_result_types() {
+    let columns = result_columns(
+        SqlDialect::PostgreSQL,
+        "SELECT u.email, o.total, count(o.id) AS orders, sum(o.id) AS id_sum, max(o.created_at) AS last_order,
+             CASE WHEN o.total > 100 THEN 'big' ELSE 'small' END AS size,
+             CASE WHEN u.id > 10 THEN 1 WHEN u.id > 5 THEN 2.5 END AS score,
+             CAST(u.id AS text) AS id_text, coalesce(o.total, 0) AS total_or_zero, lower(u.email) AS lowered,
+             now() AS at, row_number() OVER (ORDER BY u.id) AS position
+         FROM users u LEFT JOIN orders o ON o.user_id = u.id
+         GROUP BY u.email, o.total, u.id, o.created_at",
+    );
+    assert_eq!(
+        columns,
+        vec![
+            column("email", DataType::Text, false),
+            column("total", DataType::Decimal { precision: Some(10), scale: Some(2) }, true),
+            column("orders", DataType::BigInt { unsigned: false }, false),
+            column("id_sum", DataType::BigInt { unsigned: false }, true),
+            column("last_order", DataType::Timestamp, true),
+            column("size", DataType::Text, false),
+            column("score", DataType::Decimal { precision: None, scale: None }, true),
+            column("id_text", DataType::Text, false),
+            column("total_or_zero", DataType::Decimal { precision: Some(10), scale: Some(2) }, false),
+            column("lowered", DataType::Text, false),
+            column("at", DataType::DateTimeOffset, false),
+            column("position", DataType::BigInt { unsigned: false }, false),
+        ]
+    );
+}
+
+#[test]
+fn test_is_true_is_never_null() {
+    // On a NULL total, `total > 100 = TRUE` is NULL, but `IS TRUE` and `IS NOT TRUE` are false and true
+    let columns = result_columns(
+        SqlDialect::PostgreSQL,
+        "SELECT (total > 100) = TRUE AS compared, (total > 100) IS TRUE AS big, (total > 100) IS NOT FALSE AS maybe_big
+         FROM orders",
+    );
+    assert_eq!(
+        columns,
+        vec![
+            column("compared", DataType::Boolean, true),
+            column("big", DataType::Boolean, false),
+            column("maybe_big", DataType::Boolean, false),
+        ]
+    );
+}
+
+#[test]
+fn test_infer_result_types_per_dialect() {
+    let sql = "SELECT sum(o.id) AS total, avg(o.id) AS average, now() AS at, concat(u.email, o.total) AS label
+               FROM orders o RIGHT JOIN users u ON u.id = o.user_id";
+
+    let postgres = result_columns(SqlDialect::PostgreSQL, sql);
+    assert_eq!(postgres[0], column("total", DataType::BigInt { unsigned: false }, true));
+    assert_eq!(postgres[1], column("average", DataType::Decimal { precision: None, scale: None }, true));
+    assert_eq!(postgres[3], column("label", DataType::Text, false));
+
+    let mysql = result_columns(SqlDialect::MySQL, sql);
+    assert_eq!(mysql[0], column("total", DataType::Decimal { precision: None, scale: None }, true));
+    assert_eq!(mysql[2], column("at", DataType::DateTime, false));
+    // MySQL's CONCAT gives NULL when the outer join found no order
+    assert_eq!(mysql[3], column("label", DataType::Text, true));
+
+    let sqlite = result_columns(SqlDialect::SQLite, "SELECT date(created_at) AS day, avg(total) AS average FROM orders");
+    assert_eq!(sqlite[0], column("day", DataType::Text, true));
+    assert_eq!(sqlite[1], column("average", DataType::Double, true));
+}
+
+#[test]
+fn test_render_result_row_schema() {
+    let columns = result_columns(
+        SqlDialect::PostgreSQL,
+        "INSERT INTO orders (user_id, total) VALUES ($1, $2) RETURNING id, total, $3 AS note",
+    );
+    let schema = JsonSchemaRenderer::new().render_row(&Projection { columns, complete: true });
+
+    assert_eq!(schema["properties"]["id"]["type"], "integer");
+    assert_eq!(schema["properties"]["total"]["type"], Value::from(vec!["number", "null"]));
+    assert_eq!(schema["properties"]["note"], Value::Object(serde_json::Map::new()));
+    assert_eq!(schema["required"], Value::from(vec!["id", "total", "note"]));
+    assert_eq!(schema["additionalProperties"], false);
+}
+
+fn parameters(dialect: SqlDialect, sql: &str) -> Vec<QueryParameter> {
+    let database = lower_schema(dialect.clone());
+    let tokens = Lexer::for_dialect(sql, &dialect).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    let Statement::Dml(dml) = &statements[0] else {
+        panic!("expected a query, got {:?}", statements[0]);
+    };
+    QueryChecker::new(&database, dialect).parameters(dml)
+}
+
+fn parameter(placeholder: &str, position: usize, name: &str, data_type: Option<DataType>, nullable: bool) -> QueryParameter {
+    QueryParameter { placeholder: placeholder.to_string(), position, name: name.to_string(), data_type, nullable }
+}
+
+#[test]
+fn test_bind_parameter_types() {
+    // Numbered parameters are ordered by number and may be used more than once
+    let bound = parameters(
+        SqlDialect::PostgreSQL,
+        "SELECT o.id FROM orders o JOIN users u ON u.id = o.user_id
+         WHERE u.email LIKE $2 AND o.created_at BETWEEN $1 AND $3 AND (o.total > $4 OR o.total + $4 < 0) LIMIT 10",
+    );
+    assert_eq!(
+        bound,
+        vec![
+            parameter("$1", 1, "created_at", Some(DataType::Timestamp), false),
+            parameter("$2", 2, "email", Some(DataType::Text), false),
+            parameter("$3", 3, "created_at_3", Some(DataType::Timestamp), false),
+            parameter("$4", 4, "total", Some(DataType::Decimal { precision: Some(10), scale: Some(2) }), false),
+        ]
+    );
+
+    // Values take the type and nullability of the column they go into
+    let bound = parameters(SqlDialect::PostgreSQL, "INSERT INTO orders (user_id, total) VALUES ($1, CAST($2 AS numeric))");
+    assert_eq!(bound[0], parameter("$1", 1, "user_id", Some(DataType::BigInt { unsigned: false }), true));
+    assert_eq!(bound[1].data_type, Some(DataType::Decimal { precision: None, scale: None }));
+
+    // Each `?` is a value of its own; SET binds before WHERE
+    let bound = parameters(SqlDialect::MySQL, "UPDATE users SET email = ? WHERE id = ? AND created_at > ?");
+    let names: Vec<&str> = bound.iter().map(|parameter| parameter.name.as_str()).collect();
+    assert_eq!(names, vec!["email", "id", "created_at"]);
+    assert_eq!(bound[1].position, 2);
+
+    // Named parameters keep their names, and unbound ones are still listed
+    let bound = parameters(SqlDialect::SQLite, "DELETE FROM users WHERE email = :email OR id IN (:id, :email) OR :flag");
+    assert_eq!(bound.len(), 3);
+    assert_eq!(bound[0], parameter(":email", 1, "email", Some(DataType::Text), false));
+    assert_eq!(bound[2], parameter(":flag", 3, "flag", None, false));
+
+    // LIMIT and OFFSET take row counts
+    let bigint = || Some(DataType::BigInt { unsigned: false });
+    let bound = parameters(SqlDialect::PostgreSQL, "SELECT id FROM users WHERE email = $1 ORDER BY id LIMIT $2 OFFSET $3");
+    assert_eq!(bound[1], parameter("$2", 2, "limit", bigint(), false));
+    assert_eq!(bound[2], parameter("$3", 3, "offset", bigint(), false));
+    let bound = parameters(SqlDialect::MySQL, "SELECT id FROM users WHERE email = ? LIMIT ? OFFSET ?");
+    let names: Vec<&str> = bound.iter().map(|parameter| parameter.name.as_str()).collect();
+    assert_eq!(names, vec!["email", "limit", "offset"]);
+}
@@ -0,0 +1,310 @@
+use serde_json::Value;
+use sql2doc::core::analyze::{CatalogBuilder, QueryAnalyzer, QueryCatalog, QueryCommand};
+use sql2doc::core::ast::{QueryComplexity, QueryMetadata, QueryType, Statement};
+use sql2doc::core::docs::{OpenApiRenderer, QueryCatalogRenderer};
+use sql2doc::core::domain::entity::DataType;
+use sql2doc::core::lower::Lowerer;
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+
+fn analyze(sql: &str) -> QueryMetadata {
+    let tokens = Lexer::new(sql).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    match statements.into_iter().next() {
+        Some(Statement::Dml(dml)) => QueryAnalyzer::new().analyze(&dml),
+        other => panic!("Expected a DML statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tables_and_columns_accessed() {
+    let metadata = analyze(
+        "SELECT u.id, email, count(o.id) AS orders FROM users u LEFT JOIN orders o ON o.user_id = u.id
+         WHERE u.created_at > CURRENT_TIMESTAMP GROUP BY u.id, email ORDER BY orders DESC",
+    );
+    assert_eq!(metadata.query_type, QueryType::Select);
+    assert_eq!(metadata.tables_accessed, vec!["users", "orders"]);
+    // `email` could belong to either table, and `orders` in ORDER BY is an output alias
+    assert_eq!(
+        metadata.columns_accessed,
+        vec!["orders.user_id", "users.id", "email", "orders.id", "users.created_at"]
+    );
+    assert_eq!(metadata.join_count, 1);
+    assert_eq!(metadata.aggregates, vec!["COUNT"]);
+    assert_eq!(metadata.estimated_complexity, QueryComplexity::Medium);
+    assert_eq!(metadata.complexity_reasons, vec!["1 join", "grouping"]);
+
+    // A self-join leaves one table for unqualified columns; CTE columns aren't a table's
+    let metadata = analyze(
+        "WITH recent AS (SELECT id, name FROM staff WHERE hired > '2024-01-01')
+         SELECT r.name, name, m.name FROM recent r JOIN staff m ON m.id = r.id JOIN staff b ON b.id = m.id",
+    );
+    assert_eq!(metadata.tables_accessed, vec!["staff"]);
+    assert_eq!(metadata.columns_accessed, vec!["staff.id", "staff.name", "staff.hired", "name"]);
+}
+
+#[test]
+fn test_nesting_and_complexity() {
+    assert_eq!(analyze("SELECT id FROM users WHERE id = 1").estimated_complexity, QueryComplexity::Simple);
+    assert!(analyze("SELECT * FROM users").complexity_reasons.is_empty());
+
+    let metadata = analyze(
+        "WITH RECURSIVE tree AS (
+             SELECT id, parent_id FROM categories WHERE parent_id IS NULL
+             UNION ALL
+             SELECT c.id, c.parent_id FROM categories c JOIN tree t ON c.parent_id = t.id
+         )
+         SELECT id, row_number() OVER (ORDER BY id), sum(id) OVER (PARTITION BY parent_id) FROM tree
+         WHERE id IN (SELECT category_id FROM products WHERE price > (SELECT avg(price) FROM products))",
+    );
+    assert_eq!(metadata.tables_accessed, vec!["categories", "products"]);
+    assert_eq!(metadata.join_count, 1);
+    assert_eq!(metadata.subquery_depth, 2);
+    assert_eq!(metadata.cte_depth, 1);
+    assert_eq!(metadata.aggregates, vec!["AVG"]);
+    assert_eq!(metadata.window_functions, vec!["ROW_NUMBER", "SUM"]);
+    assert_eq!(metadata.estimated_complexity, QueryComplexity::VeryComplex);
+    assert_eq!(
+        metadata.complexity_reasons,
+        vec![
+            "1 join",
+            "subqueries nested 2 deep",
+            "1 CTE",
+            "a recursive CTE",
+            "1 set operation",
+            "aggregates",
+            "window functions"
+        ]
+    );
+
+    let metadata = analyze(
+        "WITH outer_cte AS (WITH inner_cte AS (SELECT id FROM a) SELECT id FROM inner_cte)
+         SELECT x.id FROM (SELECT id FROM outer_cte) x",
+    );
+    assert_eq!(metadata.tables_accessed, vec!["a"]);
+    assert_eq!(metadata.cte_depth, 2);
+    assert_eq!(metadata.subquery_depth, 1);
+    assert_eq!(metadata.estimated_complexity, QueryComplexity::Medium);
+}
+
+#[test]
+fn test_data_modifying_statements() {
+    let metadata = analyze(
+        "INSERT INTO orders (user_id, total) VALUES ($1, $2)
+         ON CONFLICT (id) DO UPDATE SET total = excluded.total + total RETURNING id",
+    );
+    assert_eq!(metadata.query_type, QueryType::Insert);
+    assert_eq!(metadata.tables_accessed, vec!["orders"]);
+    assert_eq!(metadata.columns_accessed, vec!["orders.user_id", "orders.total", "orders.id"]);
+
+    let metadata = analyze("UPDATE orders o SET total = 0 FROM users u WHERE u.id = o.user_id AND u.banned");
+    assert_eq!(metadata.query_type, QueryType::Update);
+    assert_eq!(metadata.tables_accessed, vec!["orders", "users"]);
+    assert_eq!(metadata.columns_accessed, vec!["orders.total", "users.id", "orders.user_id", "users.banned"]);
+
+    let metadata = analyze("DELETE FROM sessions WHERE user_id IN (SELECT id FROM users WHERE banned)");
+    assert_eq!(metadata.query_type, QueryType::Delete);
+    assert_eq!(metadata.tables_accessed, vec!["sessions", "users"]);
+    assert_eq!(metadata.columns_accessed, vec!["sessions.user_id", "users.id", "users.banned"]);
+    assert_eq!(metadata.subquery_depth, 1);
+    assert_eq!(metadata.complexity_reasons, vec!["a subquery"]);
+}
+
+#[test]
+fn test_render_query_catalog() {
+    let database = Lowerer::new(SqlDialect::PostgreSQL)
+        .lower("CREATE TABLE users (id SERIAL PRIMARY KEY, email TEXT NOT NULL, manager_id INT);")
+        .expect("schema should lower")
+        .database;
+    let mut builder = CatalogBuilder::new(SqlDialect::PostgreSQL).with_schema(&database);
+    builder
+        .add_file(
+            "users.sql",
+            "-- everyone\nSELECT u.email, m.email AS manager FROM users u LEFT JOIN users m ON m.id = u.manager_id;\n\n\
+             CREATE INDEX users_email ON users (email);\n\
+             DELETE FROM users WHERE id = $1;\n",
+        )
+        .expect("queries should parse");
+    let catalog = builder.build("User queries");
+    assert_eq!(catalog.queries.len(), 2);
+    assert_eq!(catalog.queries[1].line, 5);
+    assert_eq!(catalog.queries[1].sql, "DELETE FROM users WHERE id = $1");
+    assert_eq!(catalog.queries[0].metadata.query_type, QueryType::Select);
+
+    let markdown = QueryCatalogRenderer::new().render(&catalog);
+    assert!(markdown.starts_with("# User queries\n"));
+    assert!(markdown.contains("| [users.sql:2](#userssql2) | SELECT | simple | `users` |"));
+    assert!(markdown.contains(
+        "```sql\nSELECT u.email, m.email AS manager FROM users u LEFT JOIN users m ON m.id = u.manager_id\n```"
+    ));
+    assert!(markdown.contains("**Type:** SELECT · **Complexity:** simple (1 join)"));
+    assert!(markdown.contains("- **Columns:** `users.id`, `users.manager_id`, `users.email`"));
+    assert!(markdown.contains("| email | TEXT | no |\n| manager | TEXT | yes |"));
+    assert!(markdown.contains("## users.sql:5"));
+    assert!(!markdown.contains("CREATE INDEX"));
+}
+
+const NAMED_QUERIES: &str = "
+-- name: GetUserByEmail :one
+-- Looks a user up by their sign-in address.
+SELECT id, email, nickname FROM users WHERE email = $1;
+
+-- name: ListUsers :many
+SELECT id, email FROM users ORDER BY id;
+
+-- name: RenameUser :execrows
+UPDATE users SET nickname = $2 WHERE id = $1;
+
+-- name: ForgetUser
+DELETE FROM users WHERE id = $1;
+
+-- name: AddUser :one
+INSERT INTO users (email) VALUES ($1);
+
+-- name: ListUsers :batchmany
+SELECT 1;
+";
+
+fn named_catalog() -> QueryCatalog {
+    let database = Lowerer::new(SqlDialect::PostgreSQL)
+        .lower("CREATE TABLE users (id SERIAL PRIMARY KEY, email TEXT NOT NULL, nickname TEXT);")
+        .expect("schema should lower")
+        .database;
+    let mut builder = CatalogBuilder::new(SqlDialect::PostgreSQL).with_schema(&database);
+    builder.add_file("users.sql", NAMED_QUERIES).expect("queries should parse");
+    builder.build("User API")
+}
+
+#[test]
+fn test_named_query_annotations() {
+    let catalog = named_catalog();
+    let names: Vec<Option<&str>> = catalog.queries.iter().map(|query| query.name.as_deref()).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("GetUserByEmail"),
+            Some("ListUsers"),
+            Some("RenameUser"),
+            Some("ForgetUser"),
+            Some("AddUser"),
+            Some("ListUsers")
+        ]
+    );
+    let commands: Vec<Option<QueryCommand>> = catalog.queries.iter().map(|query| query.command).collect();
+    assert_eq!(
+        commands,
+        vec![
+            Some(QueryCommand::One),
+            Some(QueryCommand::Many),
+            Some(QueryCommand::ExecRows),
+            // Without a command, a statement that returns nothing is :exec
+            Some(QueryCommand::Exec),
+            Some(QueryCommand::One),
+            Some(QueryCommand::Many)
+        ]
+    );
+
+    let get_user = &catalog.queries[0];
+    let documentation = get_user.metadata.documentation.as_ref().expect("query should be documented");
+    assert_eq!(documentation.title.as_deref(), Some("GetUserByEmail"));
+    assert_eq!(documentation.description.as_deref(), Some("Looks a user up by their sign-in address."));
+    assert_eq!(get_user.parameters[0].name, "email");
+    assert_eq!(get_user.parameters[0].data_type, Some(DataType::Text));
+
+    let api = catalog.queries[2].metadata.api_metadata.as_ref().expect("named queries get an operation");
+    assert_eq!(api.http_method.as_deref(), Some("PUT"));
+    assert_eq!(api.endpoint.as_deref(), Some("/rename-user"));
+    let parameters: Vec<(&str, &str, bool)> = api
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.name.as_str(), parameter.param_type.as_str(), parameter.required))
+        .collect();
+    assert_eq!(parameters, vec![("id", "integer", true), ("nickname", "string", false)]);
+    assert_eq!(api.response_schema, None);
+
+    let warnings: Vec<(usize, &str)> =
+        catalog.warnings.iter().map(|warning| (warning.line, warning.message.as_str())).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            (16, "AddUser is annotated to return rows but has no RETURNING clause"),
+            (19, "unknown query command :batchmany; expected :one, :many, :exec or :execrows"),
+            (19, "query name ListUsers is used more than once"),
+        ]
+    );
+
+    let markdown = QueryCatalogRenderer::new().render(&catalog);
+    assert!(markdown.contains("| [GetUserByEmail](#getuserbyemail) | SELECT :one | simple | `users` |"));
+    assert!(markdown.contains(
+        "## GetUserByEmail\n\nLooks a user up by their sign-in address.\n\nDefined in `users.sql:4`."
+    ));
+    assert!(markdown.contains("**Endpoint:** `GET /get-user-by-email`"));
+    assert!(markdown.contains("| nickname | `$2` | TEXT | yes |"));
+}
+
+#[test]
+fn test_bad_query_is_left_out_of_the_catalog() {
+    let mut builder = CatalogBuilder::new(SqlDialect::PostgreSQL);
+    builder
+        .add_file(
+            "users.sql",
+            "-- name: ListUsers :many
+SELECT id FROM users ORDER BY id LIMIT $1 OFFSET $2;
+
+-- name: Broken :one
+SELECT id FROM users WHERE;
+
+-- name: CountUsers :one
+SELECT count(*) FROM users;
+",
+        )
+        .expect("file should be read");
+    let catalog = builder.build("Users");
+
+    let names: Vec<Option<&str>> = catalog.queries.iter().map(|query| query.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("ListUsers"), Some("CountUsers")]);
+    assert_eq!(catalog.queries[0].sql, "SELECT id FROM users ORDER BY id LIMIT $1 OFFSET $2");
+    let parameters: Vec<&str> = catalog.queries[0].parameters.iter().map(|parameter| parameter.name.as_str()).collect();
+    assert_eq!(parameters, vec!["limit", "offset"]);
+
+    assert_eq!(catalog.warnings.len(), 1);
+    assert_eq!(catalog.warnings[0].line, 5);
+    assert!(catalog.warnings[0].message.starts_with("query left out: "), "{}", catalog.warnings[0].message);
+}
+
+#[test]
+fn test_render_openapi_operations() {
+    let document = OpenApiRenderer::new().render(&named_catalog());
+    assert_eq!(document["openapi"], "3.1.0");
+    assert_eq!(document["info"]["title"], "User API");
+
+    let get_user = &document["paths"]["/get-user-by-email"]["get"];
+    assert_eq!(get_user["operationId"], "GetUserByEmail");
+    assert_eq!(get_user["summary"], "Looks a user up by their sign-in address.");
+    assert_eq!(get_user["parameters"][0]["name"], "email");
+    assert_eq!(get_user["parameters"][0]["in"], "query");
+    assert_eq!(get_user["parameters"][0]["required"], true);
+    assert_eq!(get_user["parameters"][0]["schema"]["type"], "string");
+    assert_eq!(
+        get_user["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/GetUserByEmailRow"
+    );
+    assert!(get_user["responses"]["404"].is_object());
+
+    let row = &document["components"]["schemas"]["GetUserByEmailRow"];
+    assert_eq!(row["properties"]["nickname"]["type"], Value::from(vec!["string", "null"]));
+    assert_eq!(row["required"], Value::from(vec!["id", "email", "nickname"]));
+
+    let list = &document["paths"]["/list-users"]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(list["type"], "array");
+
+    let rename = &document["paths"]["/rename-user"]["put"];
+    let body = &rename["requestBody"]["content"]["application/json"]["schema"];
+    assert_eq!(body["properties"]["nickname"]["type"], Value::from(vec!["string", "null"]));
+    assert_eq!(body["required"], Value::from(vec!["id"]));
+    let affected = &rename["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(affected["required"], Value::from(vec!["rows_affected"]));
+
+    let forget = &document["paths"]["/forget-user"]["delete"];
+    assert!(forget["responses"]["204"].is_object());
+    assert_eq!(forget["parameters"][0]["schema"]["type"], "integer");
+}
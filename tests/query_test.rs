@@ -0,0 +1,309 @@
+use sql2doc::core::ast::*;
+use sql2doc::core::parse::{Lexer, Parser};
+
+fn parse_select(sql: &str) -> SelectStatement {
+    let tokens = Lexer::new(sql).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    match statements.into_iter().next() {
+        Some(Statement::Dml(dml)) => match *dml {
+            DmlStatement::Select(select) => *select,
+            other => panic!("Expected Select statement, got {:?}", other),
+        },
+        other => panic!("Expected Select statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_recursive_cte() {
+    let select = parse_select(
+        "WITH RECURSIVE tree (id, depth) AS (
+            SELECT id, 0 FROM nodes WHERE parent_id IS NULL
+            UNION ALL
+            SELECT n.id, t.depth + 1 FROM nodes n JOIN tree t ON n.parent_id = t.id
+        ), leaves AS (SELECT id FROM tree)
+        SELECT * FROM leaves;",
+    );
+
+    assert_eq!(select.with.len(), 2);
+    let tree = &select.with[0];
+    assert_eq!(tree.name, "tree");
+    assert_eq!(tree.columns, vec!["id".to_string(), "depth".to_string()]);
+    assert!(tree.recursive);
+    assert_eq!(tree.query.unions.len(), 1);
+    assert_eq!(tree.query.unions[0].op, SetOperator::Union);
+    assert!(tree.query.unions[0].all);
+    assert_eq!(select.with[1].name, "leaves");
+    assert_eq!(select.from, vec![TableReference::table("leaves")]);
+}
+
+#[test]
+fn test_set_operations_share_order_by() {
+    let select = parse_select(
+        "SELECT id FROM a INTERSECT SELECT id FROM b EXCEPT ALL SELECT id FROM c ORDER BY id LIMIT 10",
+    );
+
+    let ops: Vec<(SetOperator, bool)> = select.unions.iter().map(|u| (u.op.clone(), u.all)).collect();
+    assert_eq!(ops, vec![(SetOperator::Intersect, false), (SetOperator::Except, true)]);
+    assert!(select.unions.iter().all(|u| u.select.order_by.is_empty() && u.select.limit.is_none()));
+    assert_eq!(select.order_by.len(), 1);
+    assert_eq!(select.limit, Some(Expression::Literal(LiteralValue::Integer(10))));
+}
+
+#[test]
+fn test_limit_and_offset_take_parameters() {
+    let select = parse_select("SELECT id FROM t ORDER BY id LIMIT $1 OFFSET $2");
+    assert_eq!(select.limit, Some(Expression::Parameter("$1".to_string())));
+    assert_eq!(select.offset, Some(Expression::Parameter("$2".to_string())));
+
+    let select = parse_select("SELECT id FROM t LIMIT ?");
+    assert_eq!(select.limit, Some(Expression::Parameter("?".to_string())));
+    assert_eq!(select.offset, None);
+
+    let select = parse_select("SELECT id FROM t LIMIT ALL OFFSET 20");
+    assert_eq!(select.limit, None);
+    assert_eq!(select.offset, Some(Expression::Literal(LiteralValue::Integer(20))));
+}
+
+#[test]
+fn test_intersect_binds_tighter_than_union() {
+    let from = |select: &SelectStatement| select.from.clone();
+    // a UNION (b INTERSECT c)
+    let select = parse_select("SELECT id FROM a UNION SELECT id FROM b INTERSECT SELECT id FROM c");
+    assert_eq!(select.unions.len(), 1);
+    assert_eq!(select.unions[0].op, SetOperator::Union);
+    assert_eq!(from(&select.unions[0].select), vec![TableReference::table("b")]);
+    let nested = &select.unions[0].select.unions;
+    assert_eq!(nested.len(), 1);
+    assert_eq!(nested[0].op, SetOperator::Intersect);
+    assert_eq!(from(&nested[0].select), vec![TableReference::table("c")]);
+
+    // (a INTERSECT b) UNION (c INTERSECT d) EXCEPT e
+    let select = parse_select(
+        "SELECT id FROM a INTERSECT SELECT id FROM b UNION ALL SELECT id FROM c INTERSECT SELECT id FROM d
+         EXCEPT SELECT id FROM e",
+    );
+    let ops: Vec<(SetOperator, bool)> = select.unions.iter().map(|u| (u.op.clone(), u.all)).collect();
+    assert_eq!(ops, vec![(SetOperator::Intersect, false), (SetOperator::Union, true), (SetOperator::Except, false)]);
+    assert_eq!(select.unions[1].select.unions.len(), 1);
+    assert_eq!(from(&select.unions[1].select.unions[0].select), vec![TableReference::table("d")]);
+    assert!(select.unions[2].select.unions.is_empty());
+
+    // Parentheses override precedence
+    let select = parse_select("(SELECT id FROM a UNION SELECT id FROM b) INTERSECT SELECT id FROM c");
+    let ops: Vec<SetOperator> = select.unions.iter().map(|u| u.op.clone()).collect();
+    assert_eq!(ops, vec![SetOperator::Union, SetOperator::Intersect]);
+}
+
+#[test]
+fn test_parenthesized_set_operands() {
+    let select = parse_select("(SELECT id FROM a) UNION (SELECT id FROM b ORDER BY id LIMIT 5) ORDER BY id DESC");
+    assert_eq!(select.from, vec![TableReference::table("a")]);
+    assert_eq!(select.unions[0].select.limit, Some(Expression::Literal(LiteralValue::Integer(5))));
+    assert_eq!(select.order_by.len(), 1);
+    assert!(!select.order_by[0].ascending);
+
+    // A first operand's own LIMIT stays with it
+    let select = parse_select("(SELECT id FROM a ORDER BY id LIMIT 1) UNION ALL SELECT id FROM b LIMIT 10");
+    assert_eq!(select.limit, Some(Expression::Literal(LiteralValue::Integer(10))));
+    match &select.from[..] {
+        [TableReference::Subquery { query, .. }] => {
+            assert_eq!(query.from, vec![TableReference::table("a")]);
+            assert_eq!(query.limit, Some(Expression::Literal(LiteralValue::Integer(1))));
+        }
+        other => panic!("Expected the first operand as a derived table, got {:?}", other),
+    }
+    assert_eq!(select.unions[0].select.from, vec![TableReference::table("b")]);
+}
+
+#[test]
+fn test_named_windows() {
+    let select = parse_select(
+        "SELECT sum(amount) OVER w AS running, rank() OVER (w ORDER BY amount DESC) AS place
+         FROM payments WINDOW w AS (PARTITION BY account_id), latest AS (ORDER BY paid_at)",
+    );
+    let windows: Vec<&WindowSpecification> = select
+        .select_list
+        .iter()
+        .filter_map(|item| match item {
+            SelectItem::Expression { expr: Expression::WindowFunction { window, .. }, .. } => Some(window),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(*windows[0], WindowSpecification::named("w"));
+    assert_eq!(windows[1].name.as_deref(), Some("w"));
+    assert_eq!(windows[1].order_by.len(), 1);
+
+    let names: Vec<&str> = select.windows.iter().map(|named| named.name.as_str()).collect();
+    assert_eq!(names, vec!["w", "latest"]);
+    assert_eq!(select.windows[0].window.partition_by, vec![Expression::column("account_id")]);
+    assert_eq!(select.windows[1].window.order_by[0].expr, Expression::column("paid_at"));
+}
+
+#[test]
+fn test_window_functions() {
+    let select = parse_select(
+        "SELECT row_number() OVER (PARTITION BY dept ORDER BY salary DESC NULLS LAST
+            ROWS BETWEEN UNBOUNDED PRECEDING AND 1 FOLLOWING) AS rn,
+            sum(x) OVER () FROM staff",
+    );
+
+    match &select.select_list[0] {
+        SelectItem::Expression { expr: Expression::WindowFunction { function, window }, alias } => {
+            assert_eq!(**function, Expression::function("row_number", vec![]));
+            assert_eq!(alias.as_deref(), Some("rn"));
+            assert_eq!(window.partition_by, vec![Expression::column("dept")]);
+            assert_eq!(
+                window.order_by,
+                vec![OrderByClause { expr: Expression::column("salary"), ascending: false, nulls_first: Some(false) }]
+            );
+            assert_eq!(
+                window.frame,
+                Some(WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start: FrameBound::UnboundedPreceding,
+                    end: Some(FrameBound::Following(1)),
+                })
+            );
+        }
+        other => panic!("Expected window function, got {:?}", other),
+    }
+    assert!(matches!(
+        &select.select_list[1],
+        SelectItem::Expression { expr: Expression::WindowFunction { window, .. }, .. }
+            if window.partition_by.is_empty() && window.frame.is_none()
+    ));
+}
+
+#[test]
+fn test_subqueries() {
+    let select = parse_select(
+        "SELECT s.id FROM (SELECT id FROM nodes) AS s
+         WHERE EXISTS (SELECT 1 FROM edges) AND s.id IN (SELECT node_id FROM tags)",
+    );
+
+    assert!(matches!(&select.from[0], TableReference::Subquery { alias, .. } if alias == "s"));
+    match select.where_clause {
+        Some(Expression::BinaryOp { left, right, .. }) => {
+            assert!(matches!(*left, Expression::Exists(_)));
+            assert!(matches!(*right, Expression::In { ref values, .. } if matches!(values[0], Expression::Subquery(_))));
+        }
+        other => panic!("Expected AND of subquery predicates, got {:?}", other),
+    }
+}
+
+fn parse_dml(sql: &str) -> DmlStatement {
+    let tokens = Lexer::new(sql).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    match statements.into_iter().next() {
+        Some(Statement::Dml(dml)) => *dml,
+        other => panic!("Expected DML statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_postgres_upsert_with_returning() {
+    let insert = match parse_dml(
+        "INSERT INTO users (id, email) VALUES ($1, $2)
+         ON CONFLICT (id) WHERE active DO UPDATE SET email = excluded.email, seen = now()
+         RETURNING id, email AS address",
+    ) {
+        DmlStatement::Insert(insert) => insert,
+        other => panic!("Expected Insert statement, got {:?}", other),
+    };
+
+    let on_conflict = insert.on_conflict.expect("upsert should have ON CONFLICT");
+    assert_eq!(
+        on_conflict.target,
+        Some(ConflictTarget::Columns {
+            columns: vec!["id".to_string()],
+            where_clause: Some(Expression::column("active")),
+        })
+    );
+    match on_conflict.action {
+        ConflictAction::DoUpdate { assignments, where_clause } => {
+            let columns: Vec<&str> = assignments.iter().map(|a| a.column.as_str()).collect();
+            assert_eq!(columns, vec!["email", "seen"]);
+            assert_eq!(assignments[0].value, Expression::qualified_column("excluded", "email"));
+            assert!(where_clause.is_none());
+        }
+        other => panic!("Expected DO UPDATE, got {:?}", other),
+    }
+    assert_eq!(insert.returning.len(), 2);
+    assert!(matches!(&insert.returning[1], SelectItem::Expression { alias: Some(alias), .. } if alias == "address"));
+}
+
+#[test]
+fn test_upsert_variants() {
+    match parse_dml("INSERT INTO tags (name) VALUES ('a') ON CONFLICT ON CONSTRAINT tags_name_key DO NOTHING") {
+        DmlStatement::Insert(insert) => assert_eq!(
+            insert.on_conflict,
+            Some(OnConflictClause {
+                target: Some(ConflictTarget::OnConstraint("tags_name_key".to_string())),
+                action: ConflictAction::DoNothing,
+            })
+        ),
+        other => panic!("Expected Insert statement, got {:?}", other),
+    }
+
+    match parse_dml("INSERT INTO hits (path, n) VALUES ('/', 1) ON DUPLICATE KEY UPDATE n = n + VALUES(n)") {
+        DmlStatement::Insert(insert) => {
+            let on_conflict = insert.on_conflict.expect("upsert should have ON DUPLICATE KEY UPDATE");
+            assert!(on_conflict.target.is_none());
+            assert!(matches!(
+                on_conflict.action,
+                ConflictAction::DoUpdate { ref assignments, .. } if assignments[0].column == "n"
+            ));
+        }
+        other => panic!("Expected Insert statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_update_keeps_assignment_order() {
+    let update = match parse_dml(
+        "UPDATE accounts AS a SET z = 1, a.balance = a.balance - t.amount, m = 2
+         FROM transfers t WHERE t.account_id = a.id RETURNING a.id",
+    ) {
+        DmlStatement::Update(update) => update,
+        other => panic!("Expected Update statement, got {:?}", other),
+    };
+
+    let columns: Vec<&str> = update.assignments.iter().map(|a| a.column.as_str()).collect();
+    assert_eq!(columns, vec!["z", "a.balance", "m"]);
+    assert_eq!(update.assignment("m"), Some(&Expression::Literal(LiteralValue::Integer(2))));
+    assert_eq!(update.alias.as_deref(), Some("a"));
+    assert_eq!(update.from, vec![TableReference::table_with_alias("transfers", "t")]);
+    assert_eq!(update.returning.len(), 1);
+}
+
+#[test]
+fn test_mysql_multi_table_update() {
+    match parse_dml("UPDATE orders o JOIN customers c ON c.id = o.customer_id SET o.tier = c.tier") {
+        DmlStatement::Update(update) => {
+            assert_eq!(update.table_name, "orders");
+            assert!(matches!(&update.from[0], TableReference::Join { joins, .. } if joins.len() == 1));
+        }
+        other => panic!("Expected Update statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_delete_using() {
+    match parse_dml("DELETE FROM sessions s USING users u WHERE s.user_id = u.id RETURNING s.id") {
+        DmlStatement::Delete(delete) => {
+            assert_eq!(delete.alias.as_deref(), Some("s"));
+            assert_eq!(delete.using, vec![TableReference::table_with_alias("users", "u")]);
+            assert!(delete.has_where_clause());
+            assert_eq!(delete.returning.len(), 1);
+        }
+        other => panic!("Expected Delete statement, got {:?}", other),
+    }
+
+    match parse_dml("DELETE s FROM sessions s JOIN users u ON u.id = s.user_id WHERE u.disabled") {
+        DmlStatement::Delete(delete) => {
+            assert_eq!(delete.table_name, "s");
+            assert!(delete.uses_other_tables());
+        }
+        other => panic!("Expected Delete statement, got {:?}", other),
+    }
+}
@@ -0,0 +1,104 @@
+use sql2doc::core::analyze::SemanticAnalyzer;
+use sql2doc::core::ast::{AstValidationError, ValidationSeverity};
+use sql2doc::core::parse::SqlDialect;
+
+fn check(sql: &str) -> Vec<AstValidationError> {
+    SemanticAnalyzer::new(SqlDialect::PostgreSQL).check(sql)
+}
+
+fn rules(diagnostics: &[AstValidationError]) -> Vec<&str> {
+    diagnostics.iter().filter_map(|diagnostic| diagnostic.rule.as_deref()).collect()
+}
+
+#[test]
+fn test_check_clean_schema() {
+    let diagnostics = check(
+        "CREATE TABLE customers (id BIGSERIAL PRIMARY KEY, email TEXT UNIQUE);
+         CREATE TABLE orders (
+             id SERIAL PRIMARY KEY,
+             customer_id BIGINT NOT NULL REFERENCES customers,
+             email TEXT REFERENCES customers (email)
+         );
+         CREATE INDEX orders_customer ON orders (customer_id);
+         CREATE VIEW recent AS SELECT * FROM orders WHERE id IN (SELECT id FROM orders);
+         INSERT INTO orders (customer_id) VALUES (1);
+         SELECT * FROM recent, information_schema.tables;",
+    );
+    assert!(diagnostics.is_empty(), "unexpected diagnostics: {:?}", diagnostics);
+}
+
+#[test]
+fn test_check_unknown_and_duplicate_names() {
+    let diagnostics = check(
+        "CREATE TABLE users (id INT PRIMARY KEY, name TEXT, name TEXT);
+         CREATE TABLE users (id INT);
+         CREATE TABLE IF NOT EXISTS users (id INT);
+         CREATE INDEX users_email ON users (email);
+         CREATE VIEW report AS SELECT * FROM users JOIN accounts ON accounts.user_id = users.id;
+         UPDATE users SET nickname = 'x';
+         DROP TABLE IF EXISTS missing;
+         DELETE FROM missing;",
+    );
+    assert_eq!(
+        rules(&diagnostics),
+        vec!["duplicate-column", "duplicate-table", "unknown-column", "unknown-table", "unknown-column", "unknown-table"]
+    );
+    assert!(diagnostics[2].message.contains("users.email does not exist"), "{}", diagnostics[2].message);
+
+    let location = diagnostics[1].location.as_ref().expect("diagnostics should be located");
+    assert_eq!(location.start.line, 2);
+}
+
+#[test]
+fn test_check_replays_migrations_in_order() {
+    let analyzer = SemanticAnalyzer::new(SqlDialect::PostgreSQL);
+    let diagnostics = analyzer.check_all(&[
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT);",
+        "ALTER TABLE users RENAME COLUMN email TO email_address;",
+        "CREATE INDEX users_email ON users (email);
+         ALTER TABLE users DROP COLUMN email_address;
+         CREATE INDEX users_email_address ON users (email_address);",
+    ]);
+
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].0, 2);
+    assert!(diagnostics[0].1.message.contains("was renamed to email_address"), "{}", diagnostics[0].1.message);
+    assert!(diagnostics[1].1.message.contains("email_address does not exist"), "{}", diagnostics[1].1.message);
+}
+
+#[test]
+fn test_check_foreign_keys() {
+    let diagnostics = check(
+        "CREATE TABLE orders (
+             id INT PRIMARY KEY,
+             customer_id INT REFERENCES customers (id),
+             customer_code INT REFERENCES customers (code),
+             region_id INT REFERENCES regions
+         );
+         CREATE TABLE customers (id BIGINT PRIMARY KEY, code TEXT);
+         CREATE TABLE lines (order_id INT, line INT, FOREIGN KEY (order_id, line) REFERENCES orders (id));",
+    );
+
+    let found: Vec<(ValidationSeverity, &str)> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.severity.clone(), diagnostic.rule.as_deref().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (ValidationSeverity::Error, "fk-column-count"),
+            (ValidationSeverity::Warning, "fk-type-mismatch"),
+            (ValidationSeverity::Warning, "fk-target-not-unique"),
+            (ValidationSeverity::Error, "fk-type-mismatch"),
+            (ValidationSeverity::Error, "unknown-table"),
+        ]
+    );
+    assert!(diagnostics[1].message.contains("orders.customer_id is INTEGER"), "{}", diagnostics[1].message);
+}
+
+#[test]
+fn test_check_reports_syntax_errors() {
+    let diagnostics = check("CREATE TABLE t (id INT);\nCREATE TABLE (oops);\nCREATE INDEX i ON t (nope);");
+    assert_eq!(rules(&diagnostics), vec!["syntax", "unknown-column"]);
+    assert_eq!(diagnostics[0].severity, ValidationSeverity::Error);
+}
@@ -0,0 +1,562 @@
+use sql2doc::core::ast::*;
+use sql2doc::core::parse::{Lexer, Parser, SqlDialect};
+use sql2doc::core::transpile::Transpiler;
+
+fn parse(sql: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(sql).tokenize().expect("input should tokenize");
+    Parser::new(tokens).parse().expect("input should parse")
+}
+
+#[test]
+fn test_parse_mysql_create_table() {
+    let statements = parse(
+        "CREATE TABLE `users` (
+            `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+            `status` ENUM('active', 'disabled') DEFAULT 'active',
+            `price` DECIMAL(10,2) DEFAULT 0.00,
+            `created_at` DATETIME DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (`id`),
+            CONSTRAINT fk_owner FOREIGN KEY (owner_id) REFERENCES owners (id) ON DELETE CASCADE
+        ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;",
+    );
+
+    let table = match &statements[0] {
+        Statement::Ddl(DdlStatement::CreateTable(table)) => table,
+        other => panic!("Expected CreateTable statement, got {:?}", other),
+    };
+
+    assert_eq!(table.columns[0].data_type, DataType::Integer { size: Some(32), unsigned: true });
+    assert_eq!(
+        table.columns[1].data_type,
+        DataType::Enum { values: vec!["active".to_string(), "disabled".to_string()] }
+    );
+    assert_eq!(table.columns[2].data_type, DataType::Decimal { precision: Some(10), scale: Some(2) });
+    assert!(table.columns[2].constraints.contains(&ColumnConstraint::Default("0.00".to_string())));
+    assert!(table.columns[3].constraints.contains(&ColumnConstraint::Default("CURRENT_TIMESTAMP".to_string())));
+    assert!(matches!(
+        &table.constraints[1],
+        TableConstraint::ForeignKey { name: Some(name), on_delete: Some(ReferentialAction::Cascade), .. }
+            if name == "fk_owner"
+    ));
+    assert_eq!(table.options.get("ENGINE").map(String::as_str), Some("InnoDB"));
+    assert_eq!(table.options.get("CHARSET").map(String::as_str), Some("utf8mb4"));
+}
+
+#[test]
+fn test_transpile_mysql_to_postgres() {
+    let output = Transpiler::new(SqlDialect::MySQL, SqlDialect::PostgreSQL)
+        .transpile(
+            "CREATE TABLE `orders` (
+                `id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+                `Total` DOUBLE,
+                `placed_at` DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                `notes` LONGTEXT
+            ) ENGINE=InnoDB;",
+        )
+        .expect("DDL should transpile");
+
+    assert_eq!(
+        output.sql,
+        "CREATE TABLE orders (\n    \
+            id BIGINT GENERATED ALWAYS AS IDENTITY NOT NULL PRIMARY KEY,\n    \
+            \"Total\" DOUBLE PRECISION,\n    \
+            placed_at TIMESTAMP DEFAULT now() NOT NULL,\n    \
+            notes TEXT\n\
+        );\n"
+    );
+    assert!(output.warnings.iter().any(|w| w.object == "orders" && w.message.contains("ENGINE")));
+}
+
+#[test]
+fn test_transpile_enum_to_check_constraint() {
+    let output = Transpiler::new(SqlDialect::MySQL, SqlDialect::PostgreSQL)
+        .transpile("CREATE TABLE tickets (state ENUM('open', 'closed') NOT NULL);")
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("state VARCHAR(6) NOT NULL CHECK (state IN ('open', 'closed'))"));
+    assert!(output.warnings.iter().any(|w| w.object == "tickets.state"));
+}
+
+#[test]
+fn test_transpile_postgres_to_mssql() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MSSQL)
+        .transpile(
+            "CREATE TABLE sessions (
+                id SERIAL PRIMARY KEY,
+                token UUID DEFAULT gen_random_uuid(),
+                active BOOLEAN DEFAULT TRUE,
+                \"user\" TEXT
+            );
+            CREATE INDEX idx_sessions_token ON sessions USING hash (token);",
+        )
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("id INTEGER IDENTITY(1,1) PRIMARY KEY"));
+    assert!(output.sql.contains("token UNIQUEIDENTIFIER DEFAULT NEWID()"));
+    assert!(output.sql.contains("active BIT DEFAULT 1"));
+    assert!(output.sql.contains("[user] NVARCHAR(MAX)"));
+    assert!(output.sql.contains("CREATE INDEX idx_sessions_token ON sessions (token);"));
+    assert!(output.warnings.iter().any(|w| w.object == "idx_sessions_token"));
+}
+
+#[test]
+fn test_transpile_warns_on_unconvertible_types() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MySQL)
+        .transpile("CREATE TABLE hosts (address INET NOT NULL);")
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("address INET NOT NULL"));
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].object, "hosts.address");
+}
+
+#[test]
+fn test_transpile_reports_parse_errors() {
+    let result = Transpiler::new(SqlDialect::MySQL, SqlDialect::PostgreSQL).transpile("CREATE TABLE (");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_postgres_default_casts() {
+    let statements = parse("CREATE TABLE docs (body JSONB NOT NULL DEFAULT '{}'::jsonb CHECK (body->>'kind' IS NOT NULL));");
+
+    let table = match &statements[0] {
+        Statement::Ddl(DdlStatement::CreateTable(table)) => table,
+        other => panic!("Expected CreateTable statement, got {:?}", other),
+    };
+
+    assert!(table.columns[0].constraints.contains(&ColumnConstraint::Default("'{}'::jsonb".to_string())));
+    assert!(table.columns[0].constraints.contains(&ColumnConstraint::Check("body ->> 'kind' IS NOT NULL".to_string())));
+}
+
+const POSTGRES_BOOKINGS: &str = "
+    CREATE TYPE mood AS ENUM ('happy', 'sad');
+    CREATE DOMAIN email AS TEXT NOT NULL CHECK (VALUE ~ '^[^@]+@[^@]+$');
+    CREATE TABLE bookings (
+        id BIGINT GENERATED ALWAYS AS IDENTITY (START WITH 100 INCREMENT BY 5) PRIMARY KEY,
+        tags text[] NOT NULL DEFAULT '{}',
+        name TEXT COLLATE \"C\",
+        current_mood mood NOT NULL,
+        contact email,
+        total NUMERIC GENERATED ALWAYS AS (price * 2) STORED,
+        room_id INT,
+        during TSRANGE,
+        EXCLUDE USING gist (room_id WITH =, during WITH &&) WHERE (room_id > 0)
+    );";
+
+#[test]
+fn test_parse_postgres_column_forms() {
+    let statements = parse(POSTGRES_BOOKINGS);
+
+    assert!(matches!(
+        &statements[0],
+        Statement::Ddl(DdlStatement::CreateType(CreateTypeStatement {
+            definition: TypeDefinition::Enum { values },
+            ..
+        })) if values.len() == 2
+    ));
+    let domain = match &statements[1] {
+        Statement::Ddl(DdlStatement::CreateDomain(domain)) => domain,
+        other => panic!("Expected CreateDomain statement, got {:?}", other),
+    };
+    assert_eq!(domain.data_type, DataType::Text);
+    assert!(domain.constraints.contains(&ColumnConstraint::NotNull));
+
+    let table = match &statements[2] {
+        Statement::Ddl(DdlStatement::CreateTable(table)) => table,
+        other => panic!("Expected CreateTable statement, got {:?}", other),
+    };
+    assert!(table.columns[0].constraints.contains(&ColumnConstraint::Identity(IdentityOptions {
+        always: true,
+        start: Some(100),
+        increment: Some(5),
+        ..IdentityOptions::default()
+    })));
+    assert_eq!(
+        table.columns[1].data_type,
+        DataType::Array { element_type: Box::new(DataType::Text), dimensions: Some(1) }
+    );
+    assert!(table.columns[2].constraints.contains(&ColumnConstraint::Collate("C".to_string())));
+    assert_eq!(table.columns[3].data_type, DataType::Custom("MOOD".to_string()));
+    assert!(table.columns[5].constraints.contains(&ColumnConstraint::Generated {
+        expression: "price * 2".to_string(),
+        stored: true,
+    }));
+    match &table.constraints[0] {
+        TableConstraint::Exclude { index_type, elements, where_clause, .. } => {
+            assert_eq!(index_type, &Some(IndexType::Gist));
+            assert_eq!(elements[1].operator, "&&");
+            assert_eq!(where_clause.as_deref(), Some("room_id > 0"));
+        }
+        other => panic!("Expected EXCLUDE constraint, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_transpile_postgres_types_round_trip() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::PostgreSQL)
+        .transpile(POSTGRES_BOOKINGS)
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("CREATE TYPE mood AS ENUM ('happy', 'sad');"));
+    assert!(output.sql.contains("CREATE DOMAIN email AS TEXT NOT NULL CHECK (VALUE ~ '^[^@]+@[^@]+$');"));
+    assert!(output.sql.contains("GENERATED ALWAYS AS IDENTITY (START WITH 100 INCREMENT BY 5) PRIMARY KEY"));
+    assert!(output.sql.contains("tags TEXT[] DEFAULT '{}' NOT NULL"));
+    assert!(output.sql.contains("COLLATE \"C\""));
+    assert!(output.sql.contains("current_mood mood NOT NULL"));
+    assert!(output.sql.contains("EXCLUDE USING gist (room_id WITH =, during WITH &&) WHERE (room_id > 0)"));
+    assert!(output.warnings.is_empty(), "unexpected warnings: {:?}", output.warnings);
+
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::PostgreSQL)
+        .transpile("CREATE INDEX CONCURRENTLY IF NOT EXISTS rooms_floor ON rooms (floor);")
+        .expect("DDL should transpile");
+    assert!(output.sql.contains("CREATE INDEX CONCURRENTLY IF NOT EXISTS rooms_floor ON rooms (floor);"), "{}", output.sql);
+}
+
+#[test]
+fn test_transpile_postgres_types_to_mysql() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MySQL)
+        .transpile(POSTGRES_BOOKINGS)
+        .expect("DDL should transpile");
+
+    assert!(!output.sql.contains("CREATE TYPE"));
+    assert!(output.sql.contains("id BIGINT AUTO_INCREMENT PRIMARY KEY"));
+    assert!(output.sql.contains("tags JSON"));
+    assert!(output.sql.contains("current_mood ENUM('happy', 'sad') NOT NULL"));
+    assert!(output.sql.contains("contact TEXT NOT NULL CHECK (contact ~ '^[^@]+@[^@]+$')"));
+    assert!(output.sql.contains("total DECIMAL GENERATED ALWAYS AS (price * 2) STORED"));
+    assert!(!output.sql.contains("EXCLUDE"));
+    assert!(output.warnings.iter().any(|w| w.object == "bookings" && w.message.contains("EXCLUDE")));
+    assert!(output.warnings.iter().any(|w| w.object == "bookings.name" && w.message.contains("collation")));
+}
+
+#[test]
+fn test_transpile_keeps_one_mysql_auto_increment_key() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MySQL)
+        .transpile(
+            "CREATE TABLE tickets (
+                seq BIGINT GENERATED BY DEFAULT AS IDENTITY,
+                id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+                legacy_id SERIAL
+            );",
+        )
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("seq BIGINT,"), "{}", output.sql);
+    assert!(output.sql.contains("id BIGINT AUTO_INCREMENT PRIMARY KEY"), "{}", output.sql);
+    assert_eq!(output.sql.matches("AUTO_INCREMENT").count(), 1, "{}", output.sql);
+    for column in ["tickets.seq", "tickets.legacy_id"] {
+        assert!(
+            output.warnings.iter().any(|w| w.object == column && w.message.contains("AUTO_INCREMENT")),
+            "{:?}",
+            output.warnings
+        );
+    }
+}
+
+#[test]
+fn test_transpile_types_to_sqlite_affinities() {
+    let output = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::SQLite)
+        .transpile("CREATE TABLE events (at TIMESTAMPTZ, payload JSONB, amount NUMERIC(10, 2), ratio DOUBLE PRECISION);")
+        .expect("DDL should transpile");
+
+    assert!(output.sql.contains("at TEXT,"), "{}", output.sql);
+    assert!(output.sql.contains("payload TEXT,"), "{}", output.sql);
+    assert!(output.sql.contains("amount NUMERIC,"), "{}", output.sql);
+    assert!(output.sql.contains("ratio REAL\n"), "{}", output.sql);
+    assert!(!output.sql.contains("DATETIMEOFFSET"));
+}
+
+#[test]
+fn test_transpile_mysql_inline_indexes() {
+    let sql = "CREATE TABLE `posts` (
+        `id` INT NOT NULL,
+        `slug` VARCHAR(100) NOT NULL COMMENT 'URL path',
+        `body` TEXT,
+        `edited_at` TIMESTAMP NULL ON UPDATE CURRENT_TIMESTAMP,
+        PRIMARY KEY (`id`),
+        KEY `idx_slug` (`slug`(20)),
+        FULLTEXT KEY `ft_body` (`body`)
+    ) ENGINE=InnoDB;";
+
+    let postgres = Transpiler::new(SqlDialect::MySQL, SqlDialect::PostgreSQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert_eq!(
+        postgres.sql,
+        "CREATE TABLE posts (\n    \
+            id INTEGER NOT NULL,\n    \
+            slug VARCHAR(100) NOT NULL,\n    \
+            body TEXT,\n    \
+            edited_at TIMESTAMP NULL,\n    \
+            PRIMARY KEY (id)\n\
+        );\n\n\
+        CREATE INDEX idx_slug ON posts (slug);\n\n\
+        COMMENT ON COLUMN posts.slug IS 'URL path';\n"
+    );
+    assert!(postgres.warnings.iter().any(|w| w.object == "ft_body" && w.message.contains("FULLTEXT")));
+    assert!(postgres.warnings.iter().any(|w| w.object == "posts.edited_at" && w.message.contains("trigger")));
+    assert!(postgres.warnings.iter().any(|w| w.object == "idx_slug" && w.message.contains("prefix lengths")));
+
+    let mysql = Transpiler::new(SqlDialect::MySQL, SqlDialect::MySQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(mysql.sql.contains("slug VARCHAR(100) NOT NULL COMMENT 'URL path'"));
+    assert!(mysql.sql.contains("KEY idx_slug (slug(20)),\n    FULLTEXT KEY ft_body (body)\n)"));
+    assert!(mysql.sql.contains("edited_at DATETIME ON UPDATE CURRENT_TIMESTAMP NULL"));
+}
+
+#[test]
+fn test_transpile_sqlite_affinity_and_table_options() {
+    let sql = "CREATE TABLE devices (id INTEGER PRIMARY KEY, counter UNSIGNED BIG INT, payload) STRICT, WITHOUT ROWID;";
+
+    let sqlite = Transpiler::new(SqlDialect::SQLite, SqlDialect::SQLite)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert_eq!(
+        sqlite.sql,
+        "CREATE TABLE devices (\n    \
+            id INTEGER PRIMARY KEY,\n    \
+            counter UNSIGNED BIG INT,\n    \
+            payload\n\
+        ) STRICT, WITHOUT ROWID;\n"
+    );
+
+    let postgres = Transpiler::new(SqlDialect::SQLite, SqlDialect::PostgreSQL)
+        .transpile("CREATE TABLE devices (id INTEGER PRIMARY KEY, counter UNSIGNED BIG INT, payload) STRICT;")
+        .expect("DDL should transpile");
+    assert_eq!(
+        postgres.sql,
+        "CREATE TABLE devices (\n    \
+            id INTEGER GENERATED ALWAYS AS IDENTITY PRIMARY KEY,\n    \
+            counter BIGINT,\n    \
+            payload BYTEA\n\
+        );\n"
+    );
+    assert!(postgres.warnings.iter().any(|w| w.object == "devices" && w.message.contains("STRICT")));
+
+    // A DESC column key isn't a rowid alias, so it stays DESC and gains no identity
+    let sql = "CREATE TABLE alerts (id INTEGER PRIMARY KEY DESC);";
+    let sqlite = Transpiler::new(SqlDialect::SQLite, SqlDialect::SQLite).transpile(sql).expect("DDL should transpile");
+    assert!(sqlite.sql.contains("id INTEGER PRIMARY KEY DESC\n"), "{}", sqlite.sql);
+    let postgres = Transpiler::new(SqlDialect::SQLite, SqlDialect::PostgreSQL).transpile(sql).expect("DDL should transpile");
+    assert!(postgres.sql.contains("id INTEGER PRIMARY KEY\n"), "{}", postgres.sql);
+}
+
+#[test]
+fn test_transpile_sql_server_identity_and_sequences() {
+    let sql = "CREATE SEQUENCE [dbo].[InvoiceNumbers] AS [bigint] START WITH 1000 INCREMENT BY 1
+GO
+CREATE TABLE [dbo].[Invoices](
+	[ID] [int] IDENTITY(1,1) NOT NULL,
+	[Number] [bigint] NOT NULL DEFAULT (NEXT VALUE FOR [dbo].[InvoiceNumbers]),
+	[Memo] [nvarchar](max) NULL,
+ CONSTRAINT [PK_Invoices] PRIMARY KEY CLUSTERED ([ID] ASC) ON [PRIMARY]
+) ON [PRIMARY]
+GO
+";
+
+    let postgres = Transpiler::new(SqlDialect::MSSQL, SqlDialect::PostgreSQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert_eq!(
+        postgres.sql,
+        "CREATE SEQUENCE \"InvoiceNumbers\" AS BIGINT START WITH 1000 INCREMENT BY 1;\n\n\
+        CREATE TABLE \"Invoices\" (\n    \
+            \"ID\" INTEGER GENERATED ALWAYS AS IDENTITY (START WITH 1 INCREMENT BY 1) NOT NULL,\n    \
+            \"Number\" BIGINT DEFAULT nextval('\"InvoiceNumbers\"') NOT NULL,\n    \
+            \"Memo\" VARCHAR NULL,\n    \
+            CONSTRAINT \"PK_Invoices\" PRIMARY KEY (\"ID\")\n\
+        );\n"
+    );
+
+    let oracle = Transpiler::new(SqlDialect::MSSQL, SqlDialect::Oracle)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(oracle.sql.contains("DEFAULT InvoiceNumbers.NEXTVAL"), "{}", oracle.sql);
+
+    let mysql = Transpiler::new(SqlDialect::MSSQL, SqlDialect::MySQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(mysql.warnings.iter().any(|w| w.object == "InvoiceNumbers" && w.message.contains("sequences")));
+}
+
+#[test]
+fn test_transpile_keeps_include_columns_sort_order_and_constraint_names() {
+    let sql = "CREATE TABLE [dbo].[Orders](
+	[OrderID] [int] NOT NULL,
+	[Status] [nvarchar](20) NOT NULL CONSTRAINT [DF_Orders_Status] DEFAULT ('new'),
+	[Placed] [datetime2] NOT NULL,
+	[Total] [money] NULL CONSTRAINT [CK_Orders_Total] CHECK ([Total] >= 0),
+ CONSTRAINT [PK_Orders] PRIMARY KEY CLUSTERED ([OrderID] ASC)
+)
+GO
+CREATE NONCLUSTERED INDEX [IX_Orders_Placed] ON [dbo].[Orders] ([Placed] DESC, [Status])
+INCLUDE ([Total]) WHERE [Status] = 'open' WITH (ONLINE = ON) ON [PRIMARY]
+GO
+";
+    let tokens = Lexer::for_dialect(sql, &SqlDialect::MSSQL).tokenize().expect("input should tokenize");
+    let statements = Parser::new(tokens).parse().expect("input should parse");
+    let Statement::Ddl(DdlStatement::CreateTable(table)) = &statements[0] else {
+        panic!("Expected CreateTable statement, got {:?}", statements[0]);
+    };
+    assert!(table.columns[1].constraints.contains(&ColumnConstraint::Name("DF_Orders_Status".to_string())));
+    assert!(matches!(
+        &table.constraints[0],
+        TableConstraint::PrimaryKey { name: Some(name), .. } if name == "PK_Orders"
+    ));
+    let Statement::Ddl(DdlStatement::CreateIndex(index)) = &statements[1] else {
+        panic!("Expected CreateIndex statement, got {:?}", statements[1]);
+    };
+    assert!(index.columns[0].descending && !index.columns[1].descending);
+    assert_eq!(index.include, vec!["Total".to_string()]);
+
+    let mssql = Transpiler::new(SqlDialect::MSSQL, SqlDialect::MSSQL).transpile(sql).expect("DDL should transpile");
+    assert!(mssql.sql.contains("Status NVARCHAR(20) CONSTRAINT DF_Orders_Status DEFAULT ('new') NOT NULL"), "{}", mssql.sql);
+    assert!(mssql.sql.contains("CONSTRAINT PK_Orders PRIMARY KEY (OrderID)"), "{}", mssql.sql);
+    assert!(mssql.sql.contains("ON Orders (Placed DESC, Status) INCLUDE (Total)"), "{}", mssql.sql);
+    assert!(!mssql.warnings.iter().any(|w| w.message.contains("dropped")), "{:?}", mssql.warnings);
+
+    let postgres = Transpiler::new(SqlDialect::MSSQL, SqlDialect::PostgreSQL).transpile(sql).expect("DDL should transpile");
+    assert!(postgres.sql.contains("CONSTRAINT \"DF_Orders_Status\" DEFAULT"), "{}", postgres.sql);
+    assert!(postgres.sql.contains("(\"Placed\" DESC, \"Status\") INCLUDE (\"Total\")"), "{}", postgres.sql);
+
+    // Oracle names no defaults, and MySQL only names checks on a column; neither has INCLUDE
+    let oracle = Transpiler::new(SqlDialect::MSSQL, SqlDialect::Oracle).transpile(sql).expect("DDL should transpile");
+    assert!(!oracle.sql.contains("DF_Orders_Status"));
+    assert!(oracle.sql.contains("CONSTRAINT CK_Orders_Total CHECK"), "{}", oracle.sql);
+    assert!(oracle.warnings.iter().any(|w| w.object == "Orders.Status" && w.message.contains("DF_Orders_Status")));
+    assert!(oracle.warnings.iter().any(|w| w.object == "IX_Orders_Placed" && w.message.contains("INCLUDE")));
+    assert!(oracle.sql.contains("(Placed DESC, Status)"), "{}", oracle.sql);
+
+    let mysql = Transpiler::new(SqlDialect::MSSQL, SqlDialect::MySQL).transpile(sql).expect("DDL should transpile");
+    assert!(mysql.sql.contains("CONSTRAINT CK_Orders_Total CHECK"), "{}", mysql.sql);
+    assert!(mysql.warnings.iter().any(|w| w.object == "Orders.Status" && w.message.contains("DF_Orders_Status")));
+    assert!(mysql.warnings.iter().any(|w| w.object == "IX_Orders_Placed" && w.message.contains("INCLUDE")));
+
+    // PostgreSQL's INCLUDE on a key only carries over to PostgreSQL
+    let sql = "CREATE TABLE t (id INT, code TEXT, CONSTRAINT t_pkey PRIMARY KEY (id) INCLUDE (code));";
+    let postgres = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::PostgreSQL).transpile(sql).expect("DDL should transpile");
+    assert!(postgres.sql.contains("CONSTRAINT t_pkey PRIMARY KEY (id) INCLUDE (code)"), "{}", postgres.sql);
+    let sqlite = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::SQLite).transpile(sql).expect("DDL should transpile");
+    assert!(sqlite.warnings.iter().any(|w| w.object == "t" && w.message.contains("INCLUDE")));
+}
+
+#[test]
+fn test_transpile_oracle_types() {
+    let sql = "CREATE TABLE t (id NUMBER(10,0), name VARCHAR2(100 CHAR), created DATE DEFAULT SYSDATE) TABLESPACE users;";
+
+    let postgres = Transpiler::new(SqlDialect::Oracle, SqlDialect::PostgreSQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert_eq!(
+        postgres.sql,
+        "CREATE TABLE t (\n    \
+            id DECIMAL(10, 0),\n    \
+            \"name\" VARCHAR(100),\n    \
+            created TIMESTAMP DEFAULT now()\n\
+        );\n"
+    );
+}
+
+#[test]
+fn test_transpile_schema_qualified_names() {
+    let sql = "CREATE SCHEMA IF NOT EXISTS sales AUTHORIZATION admin;
+        CREATE TABLE public.customers (id INT PRIMARY KEY);
+        CREATE TABLE sales.orders (id INT PRIMARY KEY, customer_id INT REFERENCES public.customers (id));
+        CREATE INDEX orders_customer ON sales.orders (customer_id);";
+
+    let mssql = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MSSQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(mssql.sql.starts_with("CREATE SCHEMA sales AUTHORIZATION admin;"), "{}", mssql.sql);
+    assert!(mssql.sql.contains("CREATE TABLE customers ("), "{}", mssql.sql);
+    assert!(mssql.sql.contains("CREATE TABLE sales.orders ("), "{}", mssql.sql);
+    assert!(mssql.sql.contains("REFERENCES customers (id)"), "{}", mssql.sql);
+    assert!(mssql.sql.contains("ON sales.orders (customer_id)"), "{}", mssql.sql);
+    assert!(mssql.warnings.iter().any(|w| w.object == "sales" && w.message.contains("IF NOT EXISTS")));
+
+    let mysql = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::MySQL)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(mysql.sql.starts_with("CREATE DATABASE IF NOT EXISTS sales;"), "{}", mysql.sql);
+    assert!(mysql.sql.contains("CREATE TABLE sales.orders ("), "{}", mysql.sql);
+
+    let sqlite = Transpiler::new(SqlDialect::PostgreSQL, SqlDialect::SQLite)
+        .transpile(sql)
+        .expect("DDL should transpile");
+    assert!(sqlite.sql.starts_with("CREATE TABLE customers ("), "{}", sqlite.sql);
+    assert!(sqlite.sql.contains("CREATE TABLE orders ("), "{}", sqlite.sql);
+    assert!(sqlite.warnings.iter().any(|w| w.object == "sales.orders" && w.message.contains("schema qualifiers")));
+}
+
+#[test]
+fn test_parse_qualified_names() {
+    let statements = parse("CREATE TABLE db.sales.orders (id INT); USE shop; SET search_path TO sales, public;");
+    match &statements[0] {
+        Statement::Ddl(DdlStatement::CreateTable(table)) => {
+            assert_eq!(table.table_name.catalog.as_deref(), Some("db"));
+            assert_eq!(table.table_name.schema.as_deref(), Some("sales"));
+            assert_eq!(table.table_name, "db.sales.orders");
+        }
+        other => panic!("expected CREATE TABLE, got {:?}", other),
+    }
+    assert!(matches!(&statements[1], Statement::Session(SessionStatement::Use { database }) if database == "shop"));
+
+    let tokens = Lexer::new("CREATE TABLE a.b.c.d (id INT);").tokenize().expect("input should tokenize");
+    let errors = Parser::new(tokens).parse().expect_err("four-part names should be rejected");
+    assert!(errors[0].to_string().contains("Too many qualifiers"), "{:?}", errors);
+}
+
+#[test]
+fn test_parse_alter_table_and_views() {
+    let statements = parse(
+        "ALTER TABLE ONLY users
+             ADD COLUMN age INT NOT NULL,
+             ALTER COLUMN email SET DATA TYPE VARCHAR(320),
+             ALTER COLUMN name DROP NOT NULL,
+             RENAME COLUMN login TO username;
+         ALTER TABLE orders CHANGE amount total DECIMAL(10,2) AFTER id, OWNER TO admin;
+         CREATE OR REPLACE VIEW active_users (id, email) AS
+             SELECT u.id, u.email FROM users u JOIN orders o ON o.user_id = u.id WHERE u.age > 18;",
+    );
+
+    let alter = match &statements[0] {
+        Statement::Ddl(DdlStatement::AlterTable(alter)) => alter,
+        other => panic!("expected ALTER TABLE, got {:?}", other),
+    };
+    assert_eq!(alter.table_name, "users");
+    assert!(matches!(&alter.actions[0], AlterTableAction::AddColumn { column } if column.name == "age"));
+    assert!(matches!(
+        &alter.actions[1],
+        AlterTableAction::AlterColumn { column_name, new_data_type: Some(DataType::Varchar { .. }), .. }
+            if column_name == "email"
+    ));
+    assert!(matches!(
+        &alter.actions[2],
+        AlterTableAction::AlterColumn { drop_constraints, .. } if drop_constraints == &["NOT NULL".to_string()]
+    ));
+    assert!(matches!(
+        &alter.actions[3],
+        AlterTableAction::RenameColumn { old_name, new_name } if old_name == "login" && new_name == "username"
+    ));
+
+    match &statements[1] {
+        Statement::Ddl(DdlStatement::AlterTable(alter)) => {
+            assert!(matches!(&alter.actions[0], AlterTableAction::RenameColumn { new_name, .. } if new_name == "total"));
+            assert!(matches!(&alter.actions[1], AlterTableAction::ModifyColumn { column } if column.name == "total"));
+            assert!(matches!(&alter.actions[2], AlterTableAction::Other(text) if text == "OWNER TO admin"));
+        }
+        other => panic!("expected ALTER TABLE, got {:?}", other),
+    }
+
+    let view = match &statements[2] {
+        Statement::Ddl(DdlStatement::CreateView(view)) => view,
+        other => panic!("expected CREATE VIEW, got {:?}", other),
+    };
+    assert!(view.or_replace);
+    assert_eq!(view.columns, vec!["id", "email"]);
+    assert!(view.definition.starts_with("SELECT u.id"), "{}", view.definition);
+    let tables: Vec<String> = view.query.referenced_tables().iter().map(|table| table.to_string()).collect();
+    assert_eq!(tables, vec!["users", "orders"]);
+}